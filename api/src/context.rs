@@ -13,8 +13,8 @@ use crate::{
 };
 use anyhow::{anyhow, bail, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_api_types::{
-    AptosErrorCode, AsConverter, BcsBlock, GasEstimation, LedgerInfo, ResourceGroup,
-    TransactionOnChainData,
+    AptosErrorCode, AsConverter, BcsBlock, GasEstimatePercentiles, GasEstimation, LedgerInfo,
+    ResourceGroup, TransactionOnChainData,
 };
 use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::HashValue;
@@ -76,6 +76,7 @@ pub struct Context {
     simulate_txn_stats: Arc<FunctionStats>,
     pub table_info_reader: Option<Arc<dyn TableInfoReader>>,
     pub wait_for_hash_active_connections: Arc<AtomicUsize>,
+    pub stream_active_connections: Arc<AtomicUsize>,
 }
 
 impl std::fmt::Debug for Context {
@@ -118,7 +119,7 @@ impl Context {
                 last_updated_epoch: None,
                 last_updated_time: None,
                 estimation: None,
-                min_inclusion_prices: BTreeMap::new(),
+                block_gas_stats: BTreeMap::new(),
             })),
             gas_limit_cache: Arc::new(RwLock::new(GasLimitCache {
                 last_updated_epoch: None,
@@ -129,6 +130,7 @@ impl Context {
             simulate_txn_stats,
             table_info_reader,
             wait_for_hash_active_connections: Arc::new(AtomicUsize::new(0)),
+            stream_active_connections: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -850,10 +852,16 @@ impl Context {
     }
 
     fn default_gas_estimation(&self, min_gas_unit_price: u64) -> GasEstimation {
+        let prioritized_gas_estimate = self.next_bucket(min_gas_unit_price);
         GasEstimation {
             deprioritized_gas_estimate: Some(min_gas_unit_price),
             gas_estimate: min_gas_unit_price,
-            prioritized_gas_estimate: Some(self.next_bucket(min_gas_unit_price)),
+            prioritized_gas_estimate: Some(prioritized_gas_estimate),
+            gas_estimate_percentiles: Some(GasEstimatePercentiles {
+                p25: min_gas_unit_price,
+                p50: min_gas_unit_price,
+                p90: prioritized_gas_estimate,
+            }),
         }
     }
 
@@ -932,6 +940,11 @@ impl Context {
                 deprioritized_gas_estimate: Some(static_override.low),
                 gas_estimate: static_override.market,
                 prioritized_gas_estimate: Some(static_override.aggressive),
+                gas_estimate_percentiles: Some(GasEstimatePercentiles {
+                    p25: static_override.low,
+                    p50: static_override.market,
+                    p90: static_override.aggressive,
+                }),
             });
         }
 
@@ -953,7 +966,7 @@ impl Context {
         // Clear the cache if the epoch has changed
         if let Some(cached_epoch) = cache.last_updated_epoch {
             if cached_epoch != epoch {
-                cache.min_inclusion_prices.clear();
+                cache.block_gas_stats.clear();
             }
         }
 
@@ -969,10 +982,7 @@ impl Context {
         }
         let mut cached_blocks_hit = false;
         for _i in 0..max_block_history {
-            if cache
-                .min_inclusion_prices
-                .contains_key(&(epoch, lookup_version))
-            {
+            if cache.block_gas_stats.contains_key(&(epoch, lookup_version)) {
                 cached_blocks_hit = true;
                 break;
             }
@@ -1001,33 +1011,38 @@ impl Context {
         let remaining = max_block_history - blocks_len;
 
         // 2. Get gas prices per block
-        let mut min_inclusion_prices = vec![];
+        let mut block_gas_stats = vec![];
         // TODO: if multiple calls to db is a perf issue, combine into a single call and then split
         for (first, last) in blocks {
-            let min_inclusion_price = match self.get_gas_prices_and_used(
+            let stats = match self.get_gas_prices_and_used(
                 first,
                 last - first,
                 ledger_info.ledger_version.0,
             ) {
                 Ok(prices_and_used) => {
-                    let is_full_block = if prices_and_used.len() >= config.full_block_txns {
-                        true
-                    } else if let Some(full_block_gas_used) =
-                        block_config.block_gas_limit_type.block_gas_limit()
+                    let txns_fullness_pct =
+                        fullness_pct(prices_and_used.len() as u64, config.full_block_txns as u64);
+                    let gas_fullness_pct = match block_config.block_gas_limit_type.block_gas_limit()
                     {
-                        // be pessimistic for conflicts, as such information is not onchain
-                        let gas_used = prices_and_used.iter().map(|(_, used)| *used).sum::<u64>();
-                        let max_conflict_multiplier = block_config
-                            .block_gas_limit_type
-                            .conflict_penalty_window()
-                            .unwrap_or(1)
-                            as u64;
-                        gas_used * max_conflict_multiplier >= full_block_gas_used
-                    } else {
-                        false
+                        Some(full_block_gas_used) => {
+                            // be pessimistic for conflicts, as such information is not onchain
+                            let gas_used =
+                                prices_and_used.iter().map(|(_, used)| *used).sum::<u64>();
+                            let max_conflict_multiplier = block_config
+                                .block_gas_limit_type
+                                .conflict_penalty_window()
+                                .unwrap_or(1)
+                                as u64;
+                            fullness_pct(
+                                gas_used.saturating_mul(max_conflict_multiplier),
+                                full_block_gas_used,
+                            )
+                        },
+                        None => 0,
                     };
+                    let block_fullness_pct = txns_fullness_pct.max(gas_fullness_pct);
 
-                    if is_full_block {
+                    let min_inclusion_price = if block_fullness_pct >= 100 {
                         self.next_bucket(
                             prices_and_used
                                 .iter()
@@ -1037,25 +1052,34 @@ impl Context {
                         )
                     } else {
                         min_gas_unit_price
+                    };
+                    BlockGasStats {
+                        min_inclusion_price,
+                        fullness_pct: block_fullness_pct,
                     }
                 },
-                Err(_) => min_gas_unit_price,
+                Err(_) => BlockGasStats {
+                    min_inclusion_price: min_gas_unit_price,
+                    fullness_pct: 0,
+                },
             };
-            min_inclusion_prices.push(min_inclusion_price);
-            cache
-                .min_inclusion_prices
-                .insert((epoch, last), min_inclusion_price);
+            block_gas_stats.push(stats);
+            cache.block_gas_stats.insert((epoch, last), stats);
         }
         if cached_blocks_hit {
             for (_, v) in cache
-                .min_inclusion_prices
+                .block_gas_stats
                 .range((Included(&(epoch, 0)), Included(&(epoch, lookup_version))))
                 .rev()
                 .take(remaining)
             {
-                min_inclusion_prices.push(*v);
+                block_gas_stats.push(*v);
             }
         }
+        let mut min_inclusion_prices: Vec<_> = block_gas_stats
+            .iter()
+            .map(|stats| stats.min_inclusion_price)
+            .collect();
 
         // 3. Get values
         // (1) low
@@ -1107,16 +1131,29 @@ impl Context {
         // round up to next bucket
         let aggressive_price = self.next_bucket(p90_price);
 
+        // (4) percentiles
+        let percentile_block_gas_stats = &block_gas_stats
+            [..std::cmp::min(config.percentile_block_history, block_gas_stats.len())];
+        let gas_estimate_percentiles = GasEstimatePercentiles {
+            p25: weighted_percentile_price(percentile_block_gas_stats, 25)
+                .unwrap_or(min_gas_unit_price),
+            p50: weighted_percentile_price(percentile_block_gas_stats, 50)
+                .unwrap_or(min_gas_unit_price),
+            p90: weighted_percentile_price(percentile_block_gas_stats, 90)
+                .unwrap_or(min_gas_unit_price),
+        };
+
         let estimation = GasEstimation {
             deprioritized_gas_estimate: Some(low_price),
             gas_estimate: market_price,
             prioritized_gas_estimate: Some(aggressive_price),
+            gas_estimate_percentiles: Some(gas_estimate_percentiles),
         };
         // 4. Update cache
         // GC old entries
-        if cache.min_inclusion_prices.len() > max_block_history {
-            for _i in max_block_history..cache.min_inclusion_prices.len() {
-                cache.min_inclusion_prices.pop_first();
+        if cache.block_gas_stats.len() > max_block_history {
+            for _i in max_block_history..cache.block_gas_stats.len() {
+                cache.block_gas_stats.pop_first();
             }
         }
         self.update_cached_gas_estimation(&mut cache, epoch, estimation);
@@ -1273,7 +1310,7 @@ impl Context {
         self.gas_estimation_cache
             .read()
             .unwrap()
-            .min_inclusion_prices
+            .block_gas_stats
             .len()
     }
 
@@ -1295,8 +1332,46 @@ pub struct GasEstimationCache {
     last_updated_epoch: Option<u64>,
     last_updated_time: Option<Instant>,
     estimation: Option<GasEstimation>,
-    /// (epoch, lookup_version) -> min_inclusion_price
-    min_inclusion_prices: BTreeMap<(u64, u64), u64>,
+    /// (epoch, lookup_version) -> gas stats of the block
+    block_gas_stats: BTreeMap<(u64, u64), BlockGasStats>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct BlockGasStats {
+    /// The gas unit price required to get into the block
+    min_inclusion_price: u64,
+    /// How full the block is, by number of transactions or gas used, capped at 100
+    fullness_pct: u64,
+}
+
+/// Returns how full a block with `used` out of `full` (transactions or gas) is, capped at 100.
+fn fullness_pct(used: u64, full: u64) -> u64 {
+    if full == 0 {
+        return 100;
+    }
+    std::cmp::min(used.saturating_mul(100) / full, 100)
+}
+
+/// Returns the min inclusion price at the given percentile of the blocks, where each block is
+/// weighted by how full it is: a full block counts twice as much as an empty one. This way, the
+/// percentiles follow congestion quickly, while calm periods with a few full blocks don't
+/// inflate them.
+fn weighted_percentile_price(block_gas_stats: &[BlockGasStats], percentile: u64) -> Option<u64> {
+    let mut weighted_prices: Vec<_> = block_gas_stats
+        .iter()
+        .map(|stats| (stats.min_inclusion_price, 100 + stats.fullness_pct))
+        .collect();
+    weighted_prices.sort();
+    let total_weight: u64 = weighted_prices.iter().map(|(_, weight)| weight).sum();
+    let target_weight = total_weight * percentile / 100;
+    let mut cumulative_weight = 0;
+    for (price, weight) in &weighted_prices {
+        cumulative_weight += weight;
+        if cumulative_weight > target_weight {
+            return Some(*price);
+        }
+    }
+    weighted_prices.last().map(|(price, _)| *price)
 }
 
 pub struct GasLimitCache {
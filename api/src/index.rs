@@ -5,10 +5,14 @@
 use crate::{
     accept_type::AcceptType,
     context::{api_spawn_blocking, Context},
-    response::{BasicResponse, BasicResponseStatus, BasicResult},
+    response::{BasicError, BasicResponse, BasicResponseStatus, BasicResult, InternalError},
     ApiTags,
 };
-use aptos_api_types::{IndexResponse, IndexResponseBcs};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    AptosErrorCode, AvailabilityResponse, IndexAvailability, IndexResponse, IndexResponseBcs,
+    IndexType,
+};
 use poem_openapi::OpenApi;
 use std::sync::Arc;
 
@@ -56,4 +60,80 @@ impl IndexApi {
         })
         .await
     }
+
+    /// Get ledger availability
+    ///
+    /// Get the history the node can serve, i.e., the oldest available ledger
+    /// and state snapshot versions, and which indices the node maintains along
+    /// with how far behind the ledger they are. Requests for older data fail,
+    /// as it has been pruned.
+    #[oai(
+        path = "/availability",
+        method = "get",
+        operation_id = "get_ledger_availability",
+        tag = "ApiTags::General"
+    )]
+    async fn get_ledger_availability(
+        &self,
+        accept_type: AcceptType,
+    ) -> BasicResult<AvailabilityResponse> {
+        self.context
+            .check_api_output_enabled("Get ledger availability", &accept_type)?;
+        let ledger_info = self.context.get_latest_ledger_info()?;
+
+        let context = self.context.clone();
+        api_spawn_blocking(move || {
+            let ledger_version = ledger_info.version();
+            let oldest_state_snapshot_version = context
+                .db
+                .get_first_state_snapshot_version()
+                .context("Failed to retrieve the oldest state snapshot version")
+                .map_err(|err| {
+                    BasicError::internal_with_code(err, AptosErrorCode::InternalError, &ledger_info)
+                })?
+                .unwrap_or(0);
+
+            // Transactions by account and events by key are indexed when they are committed
+            let next_version = ledger_version + 1;
+            let table_info = if let Some(table_info_reader) = &context.table_info_reader {
+                IndexAvailability::enabled(
+                    IndexType::TableInfo,
+                    table_info_reader.next_version(),
+                    ledger_version,
+                )
+            } else if context.db.indexer_enabled() {
+                IndexAvailability::enabled(IndexType::TableInfo, next_version, ledger_version)
+            } else {
+                IndexAvailability::disabled(IndexType::TableInfo)
+            };
+            let availability_response = AvailabilityResponse {
+                ledger_version: ledger_version.into(),
+                oldest_ledger_version: ledger_info.oldest_ledger_version,
+                oldest_block_height: ledger_info.oldest_block_height,
+                oldest_state_snapshot_version: oldest_state_snapshot_version.into(),
+                indices: vec![
+                    IndexAvailability::enabled(
+                        IndexType::AccountTransactions,
+                        next_version,
+                        ledger_version,
+                    ),
+                    IndexAvailability::enabled(
+                        IndexType::EventsByKey,
+                        next_version,
+                        ledger_version,
+                    ),
+                    IndexAvailability::disabled(IndexType::EventsByType),
+                    table_info,
+                ],
+            };
+
+            BasicResponse::try_from_rust_value((
+                availability_response,
+                &ledger_info,
+                BasicResponseStatus::Ok,
+                &accept_type,
+            ))
+        })
+        .await
+    }
 }
@@ -22,6 +22,7 @@ mod response;
 mod runtime;
 mod set_failpoints;
 mod state;
+mod streams;
 #[cfg(test)]
 pub mod tests;
 mod transactions;
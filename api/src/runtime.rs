@@ -5,8 +5,8 @@
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
     context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
-    view_function::ViewFunctionApi,
+    log::middleware_log, set_failpoints, state::StateApi, streams::StreamsApi,
+    transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_config::config::{ApiConfig, NodeConfig};
@@ -106,6 +106,7 @@ pub fn get_api_service(
         EventsApi,
         IndexApi,
         StateApi,
+        StreamsApi,
         TransactionsApi,
         ViewFunctionApi,
     ),
@@ -131,6 +132,9 @@ pub fn get_api_service(
         StateApi {
             context: context.clone(),
         },
+        StreamsApi {
+            context: context.clone(),
+        },
         TransactionsApi {
             context: context.clone(),
         },
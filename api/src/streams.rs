@@ -0,0 +1,342 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Server-sent event streams of new transactions and events, which follow the ledger as it
+//! grows instead of having to be polled page by page.
+
+use crate::{
+    accept_type::AcceptType,
+    context::{api_spawn_blocking, Context},
+    failpoint::fail_point_poem,
+    response::{
+        api_disabled, version_pruned, BadRequestError, BasicErrorWith404, InternalError,
+        ServiceUnavailableError,
+    },
+    ApiTags,
+};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    Address, AptosErrorCode, AsConverter, MoveStructTag, Transaction, VerifyInputWithRecursion,
+    VersionedEvent, U64,
+};
+use aptos_logger::warn;
+use aptos_types::{
+    account_config::AccountResource, contract_event::EventWithVersion, language_storage::TypeTag,
+};
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use move_core_types::{account_address::AccountAddress, language_storage::StructTag};
+use poem_openapi::{
+    param::{Path, Query},
+    payload::EventStream,
+    OpenApi,
+};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+#[derive(Clone)]
+pub struct StreamsApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl StreamsApi {
+    /// Stream account transactions
+    ///
+    /// Streams the committed transactions sent by the account as server-sent
+    /// events, one transaction per event, starting from the given sequence
+    /// number. The stream follows the ledger and stays open until the client
+    /// disconnects.
+    #[oai(
+        path = "/accounts/:address/transactions/stream",
+        method = "get",
+        operation_id = "stream_account_transactions",
+        tag = "ApiTags::Experimental"
+    )]
+    async fn stream_account_transactions(
+        &self,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+        /// Account sequence number to start the stream from
+        ///
+        /// If unspecified, only transactions committed after the stream is
+        /// opened are sent
+        start: Query<Option<U64>>,
+    ) -> Result<EventStream<BoxStream<'static, Transaction>>, BasicErrorWith404> {
+        fail_point_poem("endpoint_stream_account_transactions")?;
+        self.context
+            .check_api_output_enabled("Stream account transactions", &AcceptType::Json)?;
+        let slot = StreamSlot::acquire(&self.context)?;
+
+        let context = self.context.clone();
+        let cursor = api_spawn_blocking(move || -> Result<_, BasicErrorWith404> {
+            let ledger_info = context.get_latest_ledger_info::<BasicErrorWith404>()?;
+            let next_seq_number = match start.0 {
+                Some(start) => start.0,
+                None => context
+                    .get_resource_poem::<AccountResource, BasicErrorWith404>(
+                        address.0.into(),
+                        ledger_info.version(),
+                        &ledger_info,
+                    )?
+                    .map(|account| account.sequence_number())
+                    .unwrap_or(0),
+            };
+            Ok(AccountTransactionsCursor {
+                context,
+                address: address.0.into(),
+                next_seq_number,
+            })
+        })
+        .await?;
+
+        Ok(EventStream::new(into_stream(
+            cursor,
+            slot,
+            self.context.node_config.api.stream_poll_interval_ms,
+        )))
+    }
+
+    /// Stream events by type
+    ///
+    /// Streams the events of the given type as server-sent events, one per
+    /// server-sent event, starting from the given ledger version. The stream follows
+    /// the ledger and stays open until the client disconnects.
+    #[oai(
+        path = "/events/stream",
+        method = "get",
+        operation_id = "stream_events_by_type",
+        tag = "ApiTags::Experimental"
+    )]
+    async fn stream_events_by_type(
+        &self,
+        /// Type of the events to stream e.g. `0x1::coin::CoinDeposit`
+        event_type: Query<MoveStructTag>,
+        /// Ledger version to start the stream from
+        ///
+        /// If unspecified, only events committed after the stream is opened
+        /// are sent
+        start_version: Query<Option<U64>>,
+    ) -> Result<EventStream<BoxStream<'static, VersionedEvent>>, BasicErrorWith404> {
+        event_type
+            .0
+            .verify(0)
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let event_type: StructTag = event_type
+            .0
+            .try_into()
+            .context("Failed to parse given event type")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_stream_events_by_type")?;
+        self.context
+            .check_api_output_enabled("Stream events by type", &AcceptType::Json)?;
+        let slot = StreamSlot::acquire(&self.context)?;
+
+        let context = self.context.clone();
+        let cursor = api_spawn_blocking(move || -> Result<_, BasicErrorWith404> {
+            let ledger_info = context.get_latest_ledger_info::<BasicErrorWith404>()?;
+            let next_version = match start_version.0 {
+                Some(start_version) => {
+                    let start_version = start_version.0;
+                    if start_version < ledger_info.oldest_version() {
+                        return Err(version_pruned(start_version, &ledger_info));
+                    }
+                    start_version
+                },
+                None => ledger_info.version() + 1,
+            };
+            Ok(EventsCursor {
+                context,
+                event_type: TypeTag::Struct(Box::new(event_type)),
+                next_version,
+            })
+        })
+        .await?;
+
+        Ok(EventStream::new(into_stream(
+            cursor,
+            slot,
+            self.context.node_config.api.stream_poll_interval_ms,
+        )))
+    }
+}
+
+/// Holds one of the limited stream connections for as long as the stream is open
+struct StreamSlot {
+    active_connections: Arc<AtomicUsize>,
+}
+
+impl StreamSlot {
+    fn acquire(context: &Context) -> Result<Self, BasicErrorWith404> {
+        let max_active_connections = context.node_config.api.stream_max_active_connections;
+        if max_active_connections == 0 {
+            return Err(api_disabled("Streaming"));
+        }
+
+        let active_connections = context.stream_active_connections.clone();
+        if active_connections.fetch_add(1, Ordering::Relaxed) >= max_active_connections {
+            active_connections.fetch_sub(1, Ordering::Relaxed);
+            return Err(BasicErrorWith404::service_unavailable_with_code_no_info(
+                "Too many streams are open, try again later",
+                AptosErrorCode::InternalError,
+            ));
+        }
+        Ok(Self { active_connections })
+    }
+}
+
+impl Drop for StreamSlot {
+    fn drop(&mut self) {
+        self.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// The position of a stream in the ledger
+trait StreamCursor: Send + 'static {
+    type Item: Send + 'static;
+
+    /// Reads the items committed since the last batch, and advances past them. An empty batch
+    /// means the stream has caught up with the ledger. This blocks on storage.
+    fn next_batch(&mut self) -> Result<Vec<Self::Item>, BasicErrorWith404>;
+}
+
+struct AccountTransactionsCursor {
+    context: Arc<Context>,
+    address: AccountAddress,
+    next_seq_number: u64,
+}
+
+impl StreamCursor for AccountTransactionsCursor {
+    type Item = Transaction;
+
+    fn next_batch(&mut self) -> Result<Vec<Transaction>, BasicErrorWith404> {
+        let ledger_info = self.context.get_latest_ledger_info::<BasicErrorWith404>()?;
+        let data = self.context.get_account_transactions::<BasicErrorWith404>(
+            self.address,
+            Some(self.next_seq_number),
+            self.context.max_transactions_page_size(),
+            ledger_info.version(),
+            &ledger_info,
+        )?;
+        self.next_seq_number += data.len() as u64;
+        self.context
+            .render_transactions_non_sequential::<BasicErrorWith404>(&ledger_info, data)
+    }
+}
+
+struct EventsCursor {
+    context: Arc<Context>,
+    event_type: TypeTag,
+    next_version: u64,
+}
+
+impl StreamCursor for EventsCursor {
+    type Item = VersionedEvent;
+
+    fn next_batch(&mut self) -> Result<Vec<VersionedEvent>, BasicErrorWith404> {
+        let ledger_info = self.context.get_latest_ledger_info::<BasicErrorWith404>()?;
+        let ledger_version = ledger_info.version();
+
+        // Scan pages of transactions until some have matching events, or the ledger is reached
+        let event_type = &self.event_type;
+        let mut events = vec![];
+        while events.is_empty() && self.next_version <= ledger_version {
+            let limit = self
+                .context
+                .max_transactions_page_size()
+                .min((ledger_version - self.next_version + 1).min(u16::MAX as u64) as u16);
+            let txns = self
+                .context
+                .get_transactions(self.next_version, limit, ledger_version)
+                .context("Failed to read raw transactions from storage")
+                .map_err(|err| {
+                    BasicErrorWith404::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &ledger_info,
+                    )
+                })?;
+            if txns.is_empty() {
+                break;
+            }
+            self.next_version += txns.len() as u64;
+
+            events.extend(txns.into_iter().flat_map(|txn| {
+                let version = txn.version;
+                txn.events
+                    .into_iter()
+                    .filter(|event| event.type_tag() == event_type)
+                    .map(move |event| EventWithVersion::new(version, event))
+            }));
+        }
+        if events.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.context
+            .latest_state_view_poem::<BasicErrorWith404>(&ledger_info)?
+            .as_converter(
+                self.context.db.clone(),
+                self.context.table_info_reader.clone(),
+            )
+            .try_into_versioned_events(&events)
+            .context("Failed to convert events from storage into response")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })
+    }
+}
+
+/// Turns the cursor into a stream, which polls the storage for new items once it has caught
+/// up. The stream ends on the first error, which is only logged as the response has already
+/// started.
+fn into_stream<C: StreamCursor>(
+    cursor: C,
+    slot: StreamSlot,
+    poll_interval_ms: u64,
+) -> BoxStream<'static, C::Item> {
+    let poll_interval = Duration::from_millis(poll_interval_ms);
+    stream::unfold((cursor, slot), move |(mut cursor, slot)| async move {
+        loop {
+            let (result, returned_cursor) = match api_spawn_blocking(move || {
+                let result = cursor.next_batch();
+                Ok::<_, BasicErrorWith404>((result, cursor))
+            })
+            .await
+            {
+                Ok(batch) => batch,
+                Err(err) => {
+                    warn!("Stream failed to read from storage: {:?}", err);
+                    return None;
+                },
+            };
+            cursor = returned_cursor;
+            match result {
+                Ok(items) if items.is_empty() => tokio::time::sleep(poll_interval).await,
+                Ok(items) => return Some((stream::iter(items), (cursor, slot))),
+                Err(err) => {
+                    warn!("Stream failed to read from storage: {:?}", err);
+                    return None;
+                },
+            }
+        }
+    })
+    .flatten()
+    .boxed()
+}
@@ -34,6 +34,27 @@ async fn test_return_bad_request_if_method_not_allowed() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_ledger_availability() {
+    let mut context = new_test_context(current_function_name!());
+    let resp = context.get("/availability").await;
+
+    assert_eq!(resp["oldest_ledger_version"], json!("0"));
+    assert_eq!(resp["oldest_state_snapshot_version"], json!("0"));
+    let ledger_version: u64 = resp["ledger_version"].as_str().unwrap().parse().unwrap();
+    let account_transactions = &resp["indices"][0];
+    assert_eq!(account_transactions["index"], json!("account_transactions"));
+    assert_eq!(account_transactions["enabled"], json!(true));
+    assert_eq!(
+        account_transactions["next_version"],
+        json!((ledger_version + 1).to_string())
+    );
+    assert_eq!(account_transactions["lag"], json!("0"));
+    let events_by_type = &resp["indices"][2];
+    assert_eq!(events_by_type["index"], json!("events_by_type"));
+    assert_eq!(events_by_type["enabled"], json!(false));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_health_check() {
     let context = new_test_context(current_function_name!());
@@ -15,6 +15,7 @@ mod resource_groups;
 mod secp256k1_ecdsa;
 mod simulation_test;
 mod state_test;
+mod streams_test;
 mod string_resource_test;
 mod transaction_vector_test;
 mod transactions_test;
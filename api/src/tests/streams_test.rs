@@ -0,0 +1,96 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{new_test_context, new_test_context_with_config};
+use aptos_api_test_context::{current_function_name, ApiSpecificConfig, TestContext};
+use aptos_config::config::NodeConfig;
+use serde_json::Value;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_account_transactions() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let root_address = context.root_account().await.address();
+    let mut resp = open_stream(
+        &context,
+        &format!("/accounts/{}/transactions/stream?start=0", root_address),
+    )
+    .await;
+    let txn = next_event(&mut resp).await;
+    let expected_txns = context.get("/transactions?start=2&limit=1").await;
+    assert_eq!(txn["type"], "user_transaction");
+    assert_eq!(txn["sequence_number"], "0");
+    assert_eq!(txn["hash"], expected_txns[0]["hash"]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_events_by_type() {
+    let context = new_test_context(current_function_name!());
+
+    // The genesis reconfiguration event is the first one of its type
+    let mut resp = open_stream(
+        &context,
+        "/events/stream?event_type=0x1::reconfiguration::NewEpochEvent&start_version=0",
+    )
+    .await;
+    let event = next_event(&mut resp).await;
+    assert_eq!(event["version"], "0");
+    assert_eq!(event["type"], "0x1::reconfiguration::NewEpochEvent");
+    assert_eq!(event["data"]["epoch"], "1");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_streams_disabled() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.stream_max_active_connections = 0;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    let resp = context
+        .expect_status_code(403)
+        .get("/accounts/0x1/transactions/stream")
+        .await;
+    assert_eq!(resp["error_code"], "api_disabled");
+
+    let resp = context
+        .expect_status_code(403)
+        .get("/events/stream?event_type=0x1::coin::CoinDeposit")
+        .await;
+    assert_eq!(resp["error_code"], "api_disabled");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_events_with_invalid_event_type() {
+    let mut context = new_test_context(current_function_name!());
+    context
+        .expect_status_code(400)
+        .get("/events/stream?event_type=not_a_type")
+        .await;
+}
+
+/// Opens the stream with a real connection, as the test proxy waits for the whole
+/// response, which never ends
+async fn open_stream(context: &TestContext, path: &str) -> reqwest::Response {
+    let ApiSpecificConfig::V1(address) = context.api_specific_config;
+    let resp = reqwest::get(format!("http://{}/v1{}", address, path))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    resp
+}
+
+/// Reads the data of the next server-sent event of the stream
+async fn next_event(resp: &mut reqwest::Response) -> Value {
+    let mut received = String::new();
+    while !received.contains("\n\n") {
+        let chunk = resp.chunk().await.unwrap().expect("stream ended");
+        received.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+    let data = received
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .expect("event has data");
+    serde_json::from_str(data).unwrap()
+}
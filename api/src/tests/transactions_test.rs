@@ -187,6 +187,36 @@ async fn test_post_transaction_rejected_by_mempool() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_validate_transaction() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let body = bcs::to_bytes(&txn).unwrap();
+
+    let resp = context.post_bcs_txn("/transactions/validate", &body).await;
+    assert_eq!(resp["accepted"], true);
+    assert_eq!(resp["reason"], serde_json::Value::Null);
+
+    // Once committed, the sequence number is used up
+    context.commit_block(&[txn]).await;
+    let resp = context.post_bcs_txn("/transactions/validate", &body).await;
+    assert_eq!(resp["accepted"], false);
+    assert_eq!(resp["reason"], "sequence_number_too_old");
+    assert_eq!(resp["vm_status"], "SEQUENCE_NUMBER_TOO_OLD");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_validate_invalid_signature_transaction() {
+    let mut context = new_test_context(current_function_name!());
+    let txn = context.create_invalid_signature_transaction().await;
+    let resp = context
+        .post_bcs_txn("/transactions/validate", &bcs::to_bytes(&txn).unwrap())
+        .await;
+    assert_eq!(resp["accepted"], false);
+    assert_eq!(resp["reason"], "invalid_signature");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_multi_agent_signed_transaction() {
     let mut context = new_test_context(current_function_name!());
@@ -1366,6 +1396,7 @@ async fn test_gas_estimation_cache() {
     node_config.api.gas_estimation.low_block_history = max_block_history;
     node_config.api.gas_estimation.market_block_history = max_block_history;
     node_config.api.gas_estimation.aggressive_block_history = max_block_history;
+    node_config.api.gas_estimation.percentile_block_history = max_block_history;
     let sleep_duration =
         Duration::from_millis(node_config.api.gas_estimation.cache_expiration_ms * 2);
     let mut context = new_test_context_with_config(current_function_name!(), node_config);
@@ -24,9 +24,9 @@ use aptos_api_types::{
     verify_function_identifier, verify_module_identifier, Address, AptosError, AptosErrorCode,
     AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, HashValue,
     HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest,
-    Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
-    TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion,
-    MAX_RECURSIVE_TYPES_ALLOWED, U64,
+    Transaction, TransactionData, TransactionOnChainData, TransactionValidationResult,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult, UserTransaction,
+    VerifyInput, VerifyInputWithRecursion, MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
 use aptos_crypto::{hash::CryptoHash, signing_message};
 use aptos_types::{
@@ -39,7 +39,7 @@ use aptos_types::{
     vm_status::StatusCode,
     APTOS_COIN_TYPE,
 };
-use aptos_vm::{AptosSimulationVM, AptosVM};
+use aptos_vm::{data_cache::AsMoveResolver, AptosSimulationVM, AptosVM};
 use move_core_types::{ident_str, language_storage::ModuleId, vm_status::VMStatus};
 use poem_openapi::{
     param::{Path, Query},
@@ -622,6 +622,75 @@ impl TransactionsApi {
         .await
     }
 
+    /// Validate transaction
+    ///
+    /// Validates a signed transaction against the latest state without executing it, the
+    /// same way mempool does on submission: the signature, the sequence number, the balance
+    /// for the max gas, the expiration, and whether the payload and authenticator types are
+    /// enabled. The response tells why the transaction would be discarded, if it would. A
+    /// transaction that passes may still abort when it's executed.
+    ///
+    /// The transaction is given the same way as to `/transactions`.
+    #[oai(
+        path = "/transactions/validate",
+        method = "post",
+        operation_id = "validate_transaction",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn validate_transaction(
+        &self,
+        accept_type: AcceptType,
+        data: SubmitTransactionPost,
+    ) -> SimulateTransactionResult<TransactionValidationResult> {
+        // Unlike on submission, an expired transaction isn't rejected up front, so that the
+        // expiration is reported like any other reason
+        match &data {
+            SubmitTransactionPost::Json(request) => request
+                .0
+                .user_transaction_request
+                .payload
+                .verify()
+                .and_then(|_| request.0.signature.verify()),
+            SubmitTransactionPost::Bcs(_) => Ok(()),
+        }
+        .context("Validated transaction invalid")
+        .map_err(|err| {
+            SubmitTransactionError::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+        })?;
+        fail_point_poem("endpoint_validate_transaction")?;
+        if !self.context.node_config.api.transaction_submission_enabled {
+            return Err(api_disabled("Validate transaction"));
+        }
+        self.context
+            .check_api_output_enabled("Validate transaction", &accept_type)?;
+
+        let api = self.clone();
+        let context = self.context.clone();
+        api_spawn_blocking(move || {
+            let ledger_info = context.get_latest_ledger_info()?;
+            let signed_transaction = api.get_signed_transaction(&ledger_info, data)?;
+            let state_view = context.latest_state_view_poem(&ledger_info)?;
+            let vm = AptosVM::new(
+                &state_view.as_move_resolver(),
+                /*override_is_delayed_field_optimization_capable=*/ None,
+            );
+            let result = TransactionValidationResult::new(
+                vm.check_transaction_validity(signed_transaction, &state_view)
+                    .err(),
+            );
+
+            match accept_type {
+                AcceptType::Json => {
+                    BasicResponse::try_from_json((result, &ledger_info, BasicResponseStatus::Ok))
+                },
+                AcceptType::Bcs => {
+                    BasicResponse::try_from_bcs((result, &ledger_info, BasicResponseStatus::Ok))
+                },
+            }
+        })
+        .await
+    }
+
     /// Encode submission
     ///
     /// This endpoint accepts an EncodeSubmissionRequest, which internally is a
@@ -701,6 +770,9 @@ impl TransactionsApi {
     /// (aggressive). Using a more aggressive value increases the likelihood that the transaction
     /// will make it into the next block; more aggressive values are computed with a larger history
     /// and higher percentile statistics. More details are in AIP-34.
+    ///
+    /// The 25th, 50th and 90th percentiles of the gas unit price required to get into recent
+    /// blocks are also given, with fuller blocks weighted more, for finer-grained choices.
     #[oai(
         path = "/estimate_gas_price",
         method = "get",
@@ -977,31 +977,9 @@ impl<'a, S: StateView> MoveConverter<'a, S> {
         txn_aux_data: Option<TransactionAuxiliaryData>,
     ) -> String {
         match status {
-            ExecutionStatus::MoveAbort {
-                location,
-                code,
-                info,
-            } => match &location {
-                AbortLocation::Module(_) => info
-                    .as_ref()
-                    .map(|i| {
-                        format!(
-                            "Move abort in {}: {}({:#x}): {}",
-                            abort_location_to_str(location),
-                            i.reason_name,
-                            code,
-                            i.description
-                        )
-                    })
-                    .unwrap_or_else(|| {
-                        format!(
-                            "Move abort in {}: {:#x}",
-                            abort_location_to_str(location),
-                            code
-                        )
-                    }),
-                AbortLocation::Script => format!("Move abort: code {:#x}", code),
-            },
+            ExecutionStatus::MoveAbort { .. } => status
+                .abort_message()
+                .expect("Move aborts should always have an abort message"),
             ExecutionStatus::Success => "Executed successfully".to_owned(),
             ExecutionStatus::OutOfGas => "Out of gas".to_owned(),
             ExecutionStatus::ExecutionFailure {
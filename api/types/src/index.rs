@@ -3,7 +3,7 @@
 
 use crate::{LedgerInfo, U64};
 use aptos_config::config::RoleType;
-use poem_openapi::Object as PoemObject;
+use poem_openapi::{Enum as PoemEnum, Object as PoemObject};
 use serde::{Deserialize, Serialize};
 
 // The data in IndexResponse is flattened into a single JSON map to offer
@@ -78,3 +78,68 @@ impl IndexResponseBcs {
         }
     }
 }
+
+/// The history a node can serve, returned by the availability endpoint
+/// (i.e., GET "/availability"). Data older than these versions has been pruned
+/// and requests for it fail.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PoemObject, Serialize)]
+pub struct AvailabilityResponse {
+    pub ledger_version: U64,
+    /// The oldest version of which transactions, events and write sets are available
+    pub oldest_ledger_version: U64,
+    pub oldest_block_height: U64,
+    /// The oldest version of which the state (with proofs) is available. Only the state at the
+    /// end of epochs may be available before it.
+    pub oldest_state_snapshot_version: U64,
+    /// The indices the node maintains, and how far behind the ledger they are
+    pub indices: Vec<IndexAvailability>,
+}
+
+/// The indices a node may maintain to serve lookups
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Eq, PoemEnum, Serialize)]
+#[oai(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum IndexType {
+    /// Transactions by sender and sequence number
+    AccountTransactions,
+    /// Events by event handle (i.e., creation number)
+    EventsByKey,
+    /// Events by event type
+    EventsByType,
+    /// Table handles to their key and value types
+    TableInfo,
+}
+
+/// Whether an index is enabled, and how far behind the ledger it is
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PoemObject, Serialize)]
+pub struct IndexAvailability {
+    pub index: IndexType,
+    pub enabled: bool,
+    /// The next version to be indexed, i.e., all versions before it are indexed. Unset if the
+    /// index is disabled.
+    pub next_version: Option<U64>,
+    /// The number of committed versions which are not indexed yet. Unset if the index is
+    /// disabled.
+    pub lag: Option<U64>,
+}
+
+impl IndexAvailability {
+    pub fn disabled(index: IndexType) -> Self {
+        Self {
+            index,
+            enabled: false,
+            next_version: None,
+            lag: None,
+        }
+    }
+
+    /// An enabled index, which has indexed all versions before `next_version`
+    pub fn enabled(index: IndexType, next_version: u64, ledger_version: u64) -> Self {
+        Self {
+            index,
+            enabled: true,
+            next_version: Some(next_version.into()),
+            lag: Some((ledger_version + 1).saturating_sub(next_version).into()),
+        }
+    }
+}
@@ -31,7 +31,9 @@ pub use convert::{new_vm_utf8_string, AsConverter, MoveConverter};
 pub use error::{AptosError, AptosErrorCode};
 pub use hash::HashValue;
 pub use headers::*;
-pub use index::{IndexResponse, IndexResponseBcs};
+pub use index::{
+    AvailabilityResponse, IndexAvailability, IndexResponse, IndexResponseBcs, IndexType,
+};
 pub use ledger_info::LedgerInfo;
 pub use move_types::{
     verify_field_identifier, verify_function_identifier, verify_module_identifier, EntryFunctionId,
@@ -47,12 +49,13 @@ pub use table::{RawTableItemRequest, TableItemRequest};
 pub use transaction::{
     AccountSignature, BlockMetadataTransaction, DeleteModule, DeleteResource, DeleteTableItem,
     DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest, EntryFunctionPayload, Event,
-    FeePayerSignature, GasEstimation, GasEstimationBcs, GenesisPayload, GenesisTransaction,
-    MultiAgentSignature, MultiEd25519Signature, MultiKeySignature, MultisigPayload,
-    MultisigTransactionPayload, PendingTransaction, PublicKey, ScriptPayload, ScriptWriteSet,
-    Signature, SingleKeySignature, SubmitTransactionRequest, Transaction, TransactionData,
-    TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
-    TransactionSignature, TransactionSigningMessage, TransactionsBatchSingleSubmissionFailure,
+    FeePayerSignature, GasEstimatePercentiles, GasEstimation, GasEstimationBcs, GenesisPayload,
+    GenesisTransaction, MultiAgentSignature, MultiEd25519Signature, MultiKeySignature,
+    MultisigPayload, MultisigTransactionPayload, PendingTransaction, PublicKey, ScriptPayload,
+    ScriptWriteSet, Signature, SingleKeySignature, SubmitTransactionRequest, Transaction,
+    TransactionData, TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
+    TransactionSignature, TransactionSigningMessage, TransactionValidationFailure,
+    TransactionValidationResult, TransactionsBatchSingleSubmissionFailure,
     TransactionsBatchSubmissionResult, UserCreateSigningMessageRequest, UserTransaction,
     UserTransactionRequest, VersionedEvent, WriteModule, WriteResource, WriteSet, WriteSetChange,
     WriteSetPayload, WriteTableItem,
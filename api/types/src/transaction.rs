@@ -30,8 +30,9 @@ use aptos_types::{
         Script, SignedTransaction, TransactionOutput, TransactionWithProof,
     },
 };
+use move_core_types::vm_status::StatusCode;
 use once_cell::sync::Lazy;
-use poem_openapi::{Object, Union};
+use poem_openapi::{Enum, Object, Union};
 use serde::{Deserialize, Serialize};
 use std::{
     boxed::Box,
@@ -459,6 +460,85 @@ pub struct TransactionsBatchSingleSubmissionFailure {
     pub transaction_index: usize,
 }
 
+/// Result of validating a signed transaction without executing it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TransactionValidationResult {
+    /// Whether mempool would accept the transaction. An accepted transaction
+    /// may still abort when it's executed
+    pub accepted: bool,
+    /// Why the transaction fails validation, if it does. A sequence number
+    /// that is too new doesn't get the transaction discarded, as mempool holds
+    /// it until the preceding transactions are committed
+    pub reason: Option<TransactionValidationFailure>,
+    /// The VM status of the failure, e.g. `SEQUENCE_NUMBER_TOO_OLD`
+    pub vm_status: Option<String>,
+}
+
+impl TransactionValidationResult {
+    pub fn new(failure: Option<StatusCode>) -> Self {
+        match failure {
+            None => Self {
+                accepted: true,
+                reason: None,
+                vm_status: None,
+            },
+            Some(status) => Self {
+                accepted: status == StatusCode::SEQUENCE_NUMBER_TOO_NEW,
+                reason: Some(status.into()),
+                vm_status: Some(format!("{:?}", status)),
+            },
+        }
+    }
+}
+
+/// Why a transaction fails validation
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[oai(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionValidationFailure {
+    /// The signature is invalid, or doesn't match the sender's authentication key
+    InvalidSignature,
+    /// The sequence number was already used
+    SequenceNumberTooOld,
+    /// The sequence number is ahead of the sender's
+    SequenceNumberTooNew,
+    /// The sender can't pay for the max gas amount at the gas unit price
+    InsufficientBalanceForGas,
+    /// The expiration timestamp has passed
+    Expired,
+    /// The sender account doesn't exist
+    SenderDoesNotExist,
+    /// The max gas amount or the gas unit price is out of bounds
+    InvalidGasParameters,
+    /// The transaction is for another chain
+    WrongChainId,
+    /// The transaction uses a feature that isn't enabled yet, e.g. a payload
+    /// or authenticator type
+    FeatureNotEnabled,
+    /// Any other reason, see `vm_status` for details
+    Other,
+}
+
+impl From<StatusCode> for TransactionValidationFailure {
+    fn from(status: StatusCode) -> Self {
+        match status {
+            StatusCode::INVALID_SIGNATURE | StatusCode::INVALID_AUTH_KEY => Self::InvalidSignature,
+            StatusCode::SEQUENCE_NUMBER_TOO_OLD => Self::SequenceNumberTooOld,
+            StatusCode::SEQUENCE_NUMBER_TOO_NEW => Self::SequenceNumberTooNew,
+            StatusCode::INSUFFICIENT_BALANCE_FOR_TRANSACTION_FEE => Self::InsufficientBalanceForGas,
+            StatusCode::TRANSACTION_EXPIRED => Self::Expired,
+            StatusCode::SENDING_ACCOUNT_DOES_NOT_EXIST => Self::SenderDoesNotExist,
+            StatusCode::MAX_GAS_UNITS_EXCEEDS_MAX_GAS_UNITS_BOUND
+            | StatusCode::MAX_GAS_UNITS_BELOW_MIN_TRANSACTION_GAS_UNITS
+            | StatusCode::GAS_UNIT_PRICE_BELOW_MIN_BOUND
+            | StatusCode::GAS_UNIT_PRICE_ABOVE_MAX_BOUND => Self::InvalidGasParameters,
+            StatusCode::BAD_CHAIN_ID => Self::WrongChainId,
+            StatusCode::FEATURE_UNDER_GATING => Self::FeatureNotEnabled,
+            _ => Self::Other,
+        }
+    }
+}
+
 // TODO: Rename this to remove the Inner when we cut over.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct UserTransactionRequestInner {
@@ -1938,4 +2018,18 @@ pub struct GasEstimation {
     pub gas_estimate: u64,
     /// The prioritized estimate for the gas unit price
     pub prioritized_gas_estimate: Option<u64>,
+    /// Percentiles of the gas unit price required to get into recent blocks
+    pub gas_estimate_percentiles: Option<GasEstimatePercentiles>,
+}
+
+/// Percentiles of the gas unit price required to get into recent blocks, with fuller blocks
+/// weighted more
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct GasEstimatePercentiles {
+    /// The 25th percentile of the gas unit price
+    pub p25: u64,
+    /// The 50th percentile of the gas unit price
+    pub p50: u64,
+    /// The 90th percentile of the gas unit price
+    pub p90: u64,
 }
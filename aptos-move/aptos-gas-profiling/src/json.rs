@@ -0,0 +1,112 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    erased::{Node, StoragePair},
+    log::TransactionGasLog,
+};
+use anyhow::Result;
+use aptos_gas_algebra::InternalGas;
+use serde_json::{json, Value};
+use std::{fs, path::Path};
+
+impl Node<InternalGas> {
+    fn to_json(&self) -> Option<Value> {
+        if self.val.is_zero() {
+            return None;
+        }
+
+        let children = self
+            .children
+            .iter()
+            .filter_map(|child| child.to_json())
+            .collect::<Vec<_>>();
+        Some(json!({
+            "name": self.text,
+            "value": u64::from(self.val),
+            "children": children,
+        }))
+    }
+}
+
+impl Node<StoragePair> {
+    fn to_json(&self) -> Option<Value> {
+        if self.val.cost.is_zero() && self.val.refund.is_zero() {
+            return None;
+        }
+
+        let children = self
+            .children
+            .iter()
+            .filter_map(|child| child.to_json())
+            .collect::<Vec<_>>();
+        Some(json!({
+            "name": self.text,
+            "value": u64::from(self.val.cost),
+            "refund": u64::from(self.val.refund),
+            "children": children,
+        }))
+    }
+}
+
+fn ops_to_json(ops: Vec<(String, usize, InternalGas)>) -> Value {
+    Value::Array(
+        ops.into_iter()
+            .map(|(name, hits, cost)| {
+                json!({
+                    "name": name,
+                    "hits": hits,
+                    "cost": u64::from(cost),
+                })
+            })
+            .collect(),
+    )
+}
+
+impl TransactionGasLog {
+    /// Converts the gas log into a machine-readable form, so that it can be processed by tools,
+    /// e.g. to compare the gas usage of different versions of a contract.
+    ///
+    /// The execution & IO costs and the storage fees are trees in the format of d3-flame-graph,
+    /// where the value of a node includes the values of its children. The costs are not scaled,
+    /// i.e. execution & IO costs are in internal gas units (the gas units multiplied by the
+    /// gas scaling factor), and storage fees are in Octas.
+    pub fn to_json(&self) -> Value {
+        let mut erased = self.to_erased();
+        erased.exec_io.tree.include_child_costs();
+        erased.storage.tree.include_child_costs();
+
+        let aggregated = self.exec_io.aggregate_gas_events();
+
+        json!({
+            "entry_point": format!("{}", self.entry_point()),
+            "gas_scaling_factor": u64::from(self.exec_io.gas_scaling_factor),
+            "execution_and_io": {
+                "total": u64::from(self.exec_io.total),
+                "intrinsic": u64::from(self.exec_io.intrinsic_cost),
+                "keyless": u64::from(self.exec_io.keyless_cost),
+                "tree": erased.exec_io.tree.to_json(),
+                "ops": ops_to_json(aggregated.ops),
+                "storage_reads": ops_to_json(aggregated.storage_reads),
+                "storage_writes": ops_to_json(aggregated.storage_writes),
+                "event_writes": ops_to_json(aggregated.event_writes),
+                "transaction_write": u64::from(aggregated.transaction_write),
+            },
+            "storage": {
+                "total": u64::from(self.storage.total),
+                "total_refund": u64::from(self.storage.total_refund),
+                "tree": erased.storage.tree.to_json(),
+            },
+        })
+    }
+
+    /// Writes the gas log in the machine-readable form (see `to_json`) to the given file.
+    pub fn generate_json_report(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.to_json())?)?;
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@
 mod aggregate;
 mod erased;
 mod flamegraph;
+mod json;
 mod log;
 mod misc;
 mod profiler;
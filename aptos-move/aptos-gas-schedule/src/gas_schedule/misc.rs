@@ -1,11 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-//! This module defines the miscellaneous gas parameters, currently only including the
-//! ones related to definition of abstract value size.
+//! This module defines the miscellaneous gas parameters, including the ones related to
+//! definition of abstract value size and the limits on types in the VM.
 
 use crate::{
-    gas_schedule::VMGasParameters,
+    gas_schedule::{TypeGasParameters, VMGasParameters},
     traits::{FromOnChainGasSchedule, InitialGasSchedule, ToOnChainGasSchedule},
 };
 use aptos_gas_algebra::{AbstractValueSize, AbstractValueSizePerArg};
@@ -601,6 +601,7 @@ impl AbstractValueSizeGasParameters {
 #[derive(Debug, Clone)]
 pub struct MiscGasParameters {
     pub abs_val: AbstractValueSizeGasParameters,
+    pub ty: TypeGasParameters,
 }
 
 impl FromOnChainGasSchedule for MiscGasParameters {
@@ -613,13 +614,16 @@ impl FromOnChainGasSchedule for MiscGasParameters {
                 gas_schedule,
                 feature_version,
             )?,
+            ty: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule, feature_version)?,
         })
     }
 }
 
 impl ToOnChainGasSchedule for MiscGasParameters {
     fn to_on_chain_gas_schedule(&self, feature_version: u64) -> Vec<(String, u64)> {
-        self.abs_val.to_on_chain_gas_schedule(feature_version)
+        let mut entries = self.abs_val.to_on_chain_gas_schedule(feature_version);
+        entries.extend(self.ty.to_on_chain_gas_schedule(feature_version));
+        entries
     }
 }
 
@@ -627,6 +631,7 @@ impl MiscGasParameters {
     pub fn zeros() -> Self {
         Self {
             abs_val: AbstractValueSizeGasParameters::zeros(),
+            ty: TypeGasParameters::zeros(),
         }
     }
 }
@@ -635,6 +640,7 @@ impl InitialGasSchedule for MiscGasParameters {
     fn initial() -> Self {
         Self {
             abs_val: InitialGasSchedule::initial(),
+            ty: InitialGasSchedule::initial(),
         }
     }
 }
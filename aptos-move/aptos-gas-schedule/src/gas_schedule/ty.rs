@@ -0,0 +1,21 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines the limits on the types built by the VM, which are part of the
+//! miscellaneous gas parameters so that they can be adjusted by governance.
+
+use crate::{gas_schedule::VMGasParameters, ver::gas_feature_versions::RELEASE_V1_15};
+use move_core_types::gas_algebra::NumTypeNodes;
+
+crate::gas_schedule::macros::define_gas_parameters!(
+    TypeGasParameters,
+    "misc.ty",
+    VMGasParameters => .misc.ty,
+    [
+        // The limits are only enforced once the LIMIT_VM_TYPE_SIZE feature is enabled.
+        // Maximum number of nodes of a type instantiation.
+        [max_ty_size: NumTypeNodes, { RELEASE_V1_15.. => "max_size" }, 128],
+        // Maximum depth of an instantiated type.
+        [max_ty_depth: NumTypeNodes, { RELEASE_V1_15.. => "max_depth" }, 256],
+    ]
+);
@@ -8,6 +8,8 @@
 ///   - Changing how gas is calculated in any way
 ///
 /// Change log:
+/// - V20
+///   - Limits on the size and depth of types in the VM
 /// - V19
 ///   - gas for aggregator_v2::is_at_least native function
 /// - V18
@@ -62,7 +64,7 @@
 ///       global operations.
 /// - V1
 ///   - TBA
-pub const LATEST_GAS_FEATURE_VERSION: u64 = 19;
+pub const LATEST_GAS_FEATURE_VERSION: u64 = 20;
 
 #[allow(dead_code)]
 pub mod gas_feature_versions {
@@ -74,4 +76,5 @@ pub mod gas_feature_versions {
     pub const RELEASE_V1_12: u64 = 17;
     pub const RELEASE_V1_13: u64 = 18;
     pub const RELEASE_V1_14: u64 = 19;
+    pub const RELEASE_V1_15: u64 = 20;
 }
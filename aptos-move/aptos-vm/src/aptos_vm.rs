@@ -24,7 +24,7 @@ use crate::{
     VMExecutor, VMValidator,
 };
 use anyhow::anyhow;
-use aptos_block_executor::txn_commit_hook::NoOpTransactionCommitHook;
+use aptos_block_executor::txn_commit_hook::{NoOpTransactionCommitHook, TxnAccessSummarySink};
 use aptos_crypto::HashValue;
 use aptos_framework::{
     natives::{code::PublishRequest, randomness::RandomnessContext},
@@ -55,7 +55,7 @@ use aptos_types::{
         TimedFeaturesBuilder,
     },
     randomness::Randomness,
-    state_store::{StateView, TStateView},
+    state_store::{state_key::StateKey, StateView, TStateView},
     transaction::{
         authenticator::AnySignature, signature_verified_transaction::SignatureVerifiedTransaction,
         BlockOutput, EntryFunction, ExecutionError, ExecutionStatus, ModuleBundle, Multisig,
@@ -116,6 +116,7 @@ static PARANOID_TYPE_CHECKS: OnceCell<bool> = OnceCell::new();
 static DISCARD_FAILED_BLOCKS: OnceCell<bool> = OnceCell::new();
 static PROCESSED_TRANSACTIONS_DETAILED_COUNTERS: OnceCell<bool> = OnceCell::new();
 static TIMED_FEATURE_OVERRIDE: OnceCell<TimedFeatureOverride> = OnceCell::new();
+static ACCESS_SUMMARY_SINK: OnceCell<Arc<dyn TxnAccessSummarySink<StateKey>>> = OnceCell::new();
 
 // TODO: Don't expose this in AptosVM, and use only in BlockAptosVM!
 pub static RAYON_EXEC_POOL: Lazy<Arc<rayon::ThreadPool>> = Lazy::new(|| {
@@ -411,6 +412,18 @@ impl AptosVM {
         }
     }
 
+    /// Sets the sink for the access summaries of the transactions committed by parallel
+    /// execution, when invoked the first time.
+    pub fn set_access_summary_sink_once(sink: Arc<dyn TxnAccessSummarySink<StateKey>>) {
+        // Only the first call succeeds, due to OnceCell semantics.
+        ACCESS_SUMMARY_SINK.set(sink).ok();
+    }
+
+    /// Get the access summary sink if already set, otherwise return None
+    pub fn get_access_summary_sink() -> Option<Arc<dyn TxnAccessSummarySink<StateKey>>> {
+        ACCESS_SUMMARY_SINK.get().cloned()
+    }
+
     /// Returns the internal gas schedule if it has been loaded, or an error if it hasn't.
     #[cfg(any(test, feature = "testing"))]
     pub fn gas_params(&self) -> Result<&AptosGasParameters, VMStatus> {
@@ -2601,25 +2614,15 @@ impl VMExecutor for AptosVM {
     }
 }
 
-// VMValidator external API
-impl VMValidator for AptosVM {
-    /// Determine if a transaction is valid. Will return `None` if the transaction is accepted,
-    /// `Some(Err)` if the VM rejects it, with `Err` as an error code. Verification performs the
-    /// following steps:
-    /// 1. The signature on the `SignedTransaction` matches the public key included in the
-    ///    transaction
-    /// 2. The script to be executed is under given specific configuration.
-    /// 3. Invokes `Account.prologue`, which checks properties such as the transaction has the
-    /// right sequence number and the sender has enough balance to pay for the gas.
-    /// TBD:
-    /// 1. Transaction arguments matches the main function's type signature.
-    ///    We don't check this item for now and would execute the check at execution time.
-    fn validate_transaction(
+impl AptosVM {
+    /// Validates the transaction like [`VMValidator::validate_transaction`], but returns the
+    /// reason of any failure. This includes a sequence number that is too new, which mempool
+    /// doesn't discard but holds until the preceding transactions are committed.
+    pub fn check_transaction_validity(
         &self,
         transaction: SignedTransaction,
         state_view: &impl StateView,
-    ) -> VMValidatorResult {
-        let _timer = TXN_VALIDATION_SECONDS.start_timer();
+    ) -> Result<(), StatusCode> {
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
 
         if !self
@@ -2627,7 +2630,7 @@ impl VMValidator for AptosVM {
             .is_enabled(FeatureFlag::SINGLE_SENDER_AUTHENTICATOR)
         {
             if let aptos_types::transaction::authenticator::TransactionAuthenticator::SingleSender{ .. } = transaction.authenticator_ref() {
-                return VMValidatorResult::error(StatusCode::FEATURE_UNDER_GATING);
+                return Err(StatusCode::FEATURE_UNDER_GATING);
             }
         }
 
@@ -2638,18 +2641,18 @@ impl VMValidator for AptosVM {
             {
                 for authenticator in sk_authenticators {
                     if let AnySignature::WebAuthn { .. } = authenticator.signature() {
-                        return VMValidatorResult::error(StatusCode::FEATURE_UNDER_GATING);
+                        return Err(StatusCode::FEATURE_UNDER_GATING);
                     }
                 }
             } else {
-                return VMValidatorResult::error(StatusCode::INVALID_SIGNATURE);
+                return Err(StatusCode::INVALID_SIGNATURE);
             }
         }
 
         let txn = match transaction.check_signature() {
             Ok(t) => t,
             _ => {
-                return VMValidatorResult::error(StatusCode::INVALID_SIGNATURE);
+                return Err(StatusCode::INVALID_SIGNATURE);
             },
         };
         let mut txn_data = TransactionMetadata::new(&txn);
@@ -2673,19 +2676,18 @@ impl VMValidator for AptosVM {
             match maybe_required_deposit {
                 Ok(required_deposit) => required_deposit,
                 Err(vm_status) => {
-                    return VMValidatorResult::error(vm_status.status_code());
+                    return Err(vm_status.status_code());
                 },
             }
         } else {
-            return VMValidatorResult::error(StatusCode::GAS_PARAMS_MISSING);
+            return Err(StatusCode::GAS_PARAMS_MISSING);
         };
 
         txn_data.set_required_deposit(required_deposit);
 
         let storage = TraversalStorage::new();
 
-        // Increment the counter for transactions verified.
-        let (counter_label, result) = match self.validate_signed_transaction(
+        self.validate_signed_transaction(
             &mut session,
             &resolver,
             &txn,
@@ -2693,15 +2695,39 @@ impl VMValidator for AptosVM {
             &log_context,
             is_approved_gov_script,
             &mut TraversalContext::new(&storage),
-        ) {
-            Err(err) if err.status_code() != StatusCode::SEQUENCE_NUMBER_TOO_NEW => (
-                "failure",
-                VMValidatorResult::new(Some(err.status_code()), 0),
-            ),
-            _ => (
-                "success",
-                VMValidatorResult::new(None, txn.gas_unit_price()),
-            ),
+        )
+        .map_err(|err| err.status_code())
+    }
+}
+
+// VMValidator external API
+impl VMValidator for AptosVM {
+    /// Determine if a transaction is valid. Will return `None` if the transaction is accepted,
+    /// `Some(Err)` if the VM rejects it, with `Err` as an error code. Verification performs the
+    /// following steps:
+    /// 1. The signature on the `SignedTransaction` matches the public key included in the
+    ///    transaction
+    /// 2. The script to be executed is under given specific configuration.
+    /// 3. Invokes `Account.prologue`, which checks properties such as the transaction has the
+    /// right sequence number and the sender has enough balance to pay for the gas.
+    /// TBD:
+    /// 1. Transaction arguments matches the main function's type signature.
+    ///    We don't check this item for now and would execute the check at execution time.
+    fn validate_transaction(
+        &self,
+        transaction: SignedTransaction,
+        state_view: &impl StateView,
+    ) -> VMValidatorResult {
+        let _timer = TXN_VALIDATION_SECONDS.start_timer();
+        let gas_unit_price = transaction.gas_unit_price();
+
+        // Increment the counter for transactions verified.
+        let (counter_label, result) = match self.check_transaction_validity(transaction, state_view)
+        {
+            Err(status) if status != StatusCode::SEQUENCE_NUMBER_TOO_NEW => {
+                ("failure", VMValidatorResult::error(status))
+            },
+            _ => ("success", VMValidatorResult::new(None, gas_unit_price)),
         };
 
         TRANSACTIONS_VALIDATED
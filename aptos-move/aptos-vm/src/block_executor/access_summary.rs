@@ -0,0 +1,74 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_block_executor::{txn_commit_hook::TxnAccessSummarySink, types::TxnAccessSummary};
+use aptos_infallible::Mutex;
+use aptos_logger::warn;
+use aptos_mvhashmap::types::TxnIndex;
+use aptos_types::state_store::state_key::StateKey;
+use serde::Serialize;
+use std::{
+    fs::{File, OpenOptions},
+    io::{LineWriter, Write},
+    path::Path,
+};
+
+/// A line of the access summary file.
+#[derive(Serialize)]
+struct AccessSummaryRecord<'a> {
+    /// The number of blocks executed in parallel before this one, since the sink was created.
+    block: u64,
+    txn_idx: TxnIndex,
+    read_keys: &'a [StateKey],
+    written_keys: &'a [StateKey],
+    delta_keys: &'a [StateKey],
+}
+
+struct AccessSummaryWriter {
+    block: u64,
+    writer: LineWriter<File>,
+}
+
+/// Appends the access summaries of the committed transactions to a file, one JSON object per
+/// line, so that the conflict graphs of the executed blocks can be built offline.
+pub struct FileAccessSummarySink {
+    inner: Mutex<AccessSummaryWriter>,
+}
+
+impl FileAccessSummarySink {
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            inner: Mutex::new(AccessSummaryWriter {
+                block: 0,
+                writer: LineWriter::new(file),
+            }),
+        })
+    }
+}
+
+impl TxnAccessSummarySink<StateKey> for FileAccessSummarySink {
+    fn on_access_summary(&self, txn_idx: TxnIndex, summary: TxnAccessSummary<StateKey>) {
+        let mut inner = self.inner.lock();
+        // Transactions of a block are reported in order, so the first one starts a new block.
+        if txn_idx == 0 {
+            inner.block += 1;
+        }
+        let record = AccessSummaryRecord {
+            block: inner.block - 1,
+            txn_idx,
+            read_keys: &summary.read_keys,
+            written_keys: &summary.written_keys,
+            delta_keys: &summary.delta_keys,
+        };
+        let result = serde_json::to_writer(&mut inner.writer, &record)
+            .map_err(std::io::Error::from)
+            .and_then(|()| inner.writer.write_all(b"\n"));
+        if let Err(err) = result {
+            warn!(
+                "Failed to write the access summary of txn {}: {}",
+                txn_idx, err
+            );
+        }
+    }
+}
@@ -2,11 +2,16 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod access_summary;
 pub(crate) mod vm_wrapper;
 
 use crate::{
+    aptos_vm::AptosVM,
     block_executor::vm_wrapper::AptosExecutorTask,
-    counters::{BLOCK_EXECUTOR_CONCURRENCY, BLOCK_EXECUTOR_EXECUTE_BLOCK_SECONDS},
+    counters::{
+        BLOCK_EXECUTOR_CONCURRENCY, BLOCK_EXECUTOR_EXECUTE_BLOCK_SECONDS,
+        DESERIALIZED_MODULE_CACHE, DESERIALIZED_MODULE_CACHE_MEMORY_BYTES, STRUCT_TYPE_CACHE,
+    },
 };
 use aptos_aggregator::{
     delayed_change::DelayedChange, delta_change_set::DeltaOp, resolver::TAggregatorV1View,
@@ -37,6 +42,7 @@ use move_core_types::{
     value::MoveTypeLayout,
     vm_status::{StatusCode, VMStatus},
 };
+use move_vm_runtime::{data_cache::deserialized_module_cache_stats, struct_type_cache_stats};
 use move_vm_types::delayed_values::delayed_field_id::DelayedFieldID;
 use once_cell::sync::OnceCell;
 use rayon::ThreadPool;
@@ -413,15 +419,19 @@ impl BlockAptosVM {
         }
 
         BLOCK_EXECUTOR_CONCURRENCY.set(config.local.concurrency_level as i64);
-        let executor = BlockExecutor::<
+        let mut executor = BlockExecutor::<
             SignatureVerifiedTransaction,
             AptosExecutorTask<S>,
             S,
             L,
             ExecutableTestType,
         >::new(config, executor_thread_pool, transaction_commit_listener);
+        if let Some(access_summary_sink) = AptosVM::get_access_summary_sink() {
+            executor = executor.with_access_summary_sink(access_summary_sink);
+        }
 
         let ret = executor.execute_block(state_view, signature_verified_block, state_view);
+        update_deserialized_module_cache_counters();
         match ret {
             Ok(block_output) => {
                 let transaction_outputs = block_output.into_inner();
@@ -452,3 +462,22 @@ impl BlockAptosVM {
         }
     }
 }
+
+fn update_deserialized_module_cache_counters() {
+    let stats = deserialized_module_cache_stats();
+    for (stat, value) in [
+        ("hits", stats.hits as i64),
+        ("misses", stats.misses as i64),
+        ("num_modules", stats.num_modules as i64),
+    ] {
+        DESERIALIZED_MODULE_CACHE
+            .with_label_values(&[stat])
+            .set(value);
+    }
+    DESERIALIZED_MODULE_CACHE_MEMORY_BYTES.set(stats.size_in_bytes as i64);
+
+    let stats = struct_type_cache_stats();
+    for (stat, value) in [("hits", stats.hits as i64), ("misses", stats.misses as i64)] {
+        STRUCT_TYPE_CACHE.with_label_values(&[stat]).set(value);
+    }
+}
@@ -4,8 +4,8 @@
 
 use aptos_metrics_core::{
     exponential_buckets, register_histogram, register_histogram_vec, register_int_counter,
-    register_int_counter_vec, register_int_gauge, Histogram, HistogramVec, IntCounter,
-    IntCounterVec, IntGauge,
+    register_int_counter_vec, register_int_gauge, register_int_gauge_vec, Histogram, HistogramVec,
+    IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 
@@ -32,6 +32,35 @@ pub static BLOCK_EXECUTOR_CONCURRENCY: Lazy<IntGauge> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Statistics of the cache for deserialized modules, which is shared across blocks
+pub static DESERIALIZED_MODULE_CACHE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_vm_deserialized_module_cache",
+        "Hits, misses and number of modules of the deserialized module cache",
+        &["stat"]
+    )
+    .unwrap()
+});
+
+/// Memory used by the cache for deserialized modules, approximated by the size of the module bytes
+pub static DESERIALIZED_MODULE_CACHE_MEMORY_BYTES: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_vm_deserialized_module_cache_memory_bytes",
+        "Memory used by the deserialized module cache, approximated by the size of the module bytes"
+    )
+    .unwrap()
+});
+
+/// Statistics of the struct type caches of the loaders, which are kept across blocks
+pub static STRUCT_TYPE_CACHE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_vm_struct_type_cache",
+        "Hits and misses of the struct type caches of the loaders",
+        &["stat"]
+    )
+    .unwrap()
+});
+
 pub static BLOCK_EXECUTOR_SIGNATURE_VERIFICATION_SECONDS: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!(
         // metric name
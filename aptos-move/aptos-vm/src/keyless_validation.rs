@@ -6,10 +6,10 @@ use crate::move_vm_ext::AptosMoveResolver;
 use aptos_crypto::ed25519::Ed25519PublicKey;
 use aptos_types::{
     invalid_signature,
-    jwks::{jwk::JWK, PatchedJWKs},
+    jwks::{rsa::RSA_JWK, PatchedJWKs},
     keyless::{
-        get_public_inputs_hash, Configuration, EphemeralCertificate, Groth16ProofAndStatement,
-        Groth16VerificationKey, KeylessPublicKey, KeylessSignature, ZKP,
+        get_jwk_for_jwt_header, get_public_inputs_hash, Configuration, EphemeralCertificate,
+        Groth16ProofAndStatement, Groth16VerificationKey, KeylessPublicKey, KeylessSignature, ZKP,
     },
     on_chain_config::{CurrentTimeMicroseconds, Features, OnChainConfig},
     transaction::authenticator::{EphemeralPublicKey, EphemeralSignature},
@@ -84,39 +84,12 @@ fn get_jwk_for_authenticator(
     jwks: &PatchedJWKs,
     pk: &KeylessPublicKey,
     sig: &KeylessSignature,
-) -> Result<JWK, VMStatus> {
+) -> Result<RSA_JWK, VMStatus> {
     let jwt_header = sig
         .parse_jwt_header()
         .map_err(|_| invalid_signature!("Failed to parse JWT header"))?;
-    let jwk_move_struct = jwks.get_jwk(&pk.iss_val, &jwt_header.kid).map_err(|_| {
-        invalid_signature!(format!(
-            "JWK for {} with KID {} was not found",
-            pk.iss_val, jwt_header.kid
-        ))
-    })?;
-
-    let jwk = JWK::try_from(jwk_move_struct)
-        .map_err(|_| invalid_signature!("Could not unpack Any in JWK Move struct"))?;
-
-    match &jwk {
-        JWK::RSA(rsa_jwk) => {
-            if rsa_jwk.alg != jwt_header.alg {
-                return Err(invalid_signature!(format!(
-                    "JWK alg ({}) does not match JWT header's alg ({})",
-                    rsa_jwk.alg, jwt_header.alg
-                )));
-            }
-        },
-        JWK::Unsupported(jwk) => {
-            return Err(invalid_signature!(format!(
-                "JWK with KID {} and hex-encoded payload {} is not supported",
-                jwt_header.kid,
-                hex::encode(&jwk.payload)
-            )))
-        },
-    }
-
-    Ok(jwk)
+    get_jwk_for_jwt_header(jwks, &pk.iss_val, &jwt_header)
+        .map_err(|e| invalid_signature!(e.to_string()))
 }
 
 /// Ensures that **all** keyless authenticators in the transaction are valid.
@@ -178,112 +151,100 @@ pub(crate) fn validate_authenticators(
     };
 
     for (pk, sig) in authenticators {
-        let jwk = get_jwk_for_authenticator(&patched_jwks, pk, sig)?;
+        let rsa_jwk = get_jwk_for_authenticator(&patched_jwks, pk, sig)?;
 
         match &sig.cert {
-            EphemeralCertificate::ZeroKnowledgeSig(zksig) => match jwk {
-                JWK::RSA(rsa_jwk) => {
-                    if zksig.exp_horizon_secs > config.max_exp_horizon_secs {
-                        // println!("[aptos-vm][groth16] Expiration horizon is too long");
-                        return Err(invalid_signature!("The expiration horizon is too long"));
-                    }
+            EphemeralCertificate::ZeroKnowledgeSig(zksig) => {
+                if zksig.exp_horizon_secs > config.max_exp_horizon_secs {
+                    // println!("[aptos-vm][groth16] Expiration horizon is too long");
+                    return Err(invalid_signature!("The expiration horizon is too long"));
+                }
 
-                    // If an `aud` override was set for account recovery purposes, check that it is
-                    // in the allow-list on-chain.
-                    if zksig.override_aud_val.is_some() {
-                        config.is_allowed_override_aud(zksig.override_aud_val.as_ref().unwrap())?;
-                    }
+                // If an `aud` override was set for account recovery purposes, check that it is
+                // in the allow-list on-chain.
+                if zksig.override_aud_val.is_some() {
+                    config.is_allowed_override_aud(zksig.override_aud_val.as_ref().unwrap())?;
+                }
 
-                    match &zksig.proof {
-                        ZKP::Groth16(groth16proof) => {
-                            // let start = std::time::Instant::now();
-                            let public_inputs_hash = get_public_inputs_hash(
-                                sig, pk, &rsa_jwk, config,
-                            )
+                match &zksig.proof {
+                    ZKP::Groth16(groth16proof) => {
+                        // let start = std::time::Instant::now();
+                        let public_inputs_hash = get_public_inputs_hash(sig, pk, &rsa_jwk, config)
                             .map_err(|_| {
                                 // println!("[aptos-vm][groth16] PIH computation failed");
                                 invalid_signature!("Could not compute public inputs hash")
                             })?;
-                            // println!("Public inputs hash time: {:?}", start.elapsed());
-
-                            let groth16_and_stmt =
-                                Groth16ProofAndStatement::new(*groth16proof, public_inputs_hash);
-
-                            // The training wheels signature is only checked if a training wheels PK is set on chain
-                            if training_wheels_pk.is_some() {
-                                match &zksig.training_wheels_signature {
-                                    Some(training_wheels_sig) => {
-                                        training_wheels_sig
-                                            .verify(
-                                                &groth16_and_stmt,
-                                                training_wheels_pk.as_ref().unwrap(),
+                        // println!("Public inputs hash time: {:?}", start.elapsed());
+
+                        let groth16_and_stmt =
+                            Groth16ProofAndStatement::new(*groth16proof, public_inputs_hash);
+
+                        // The training wheels signature is only checked if a training wheels PK is set on chain
+                        if training_wheels_pk.is_some() {
+                            match &zksig.training_wheels_signature {
+                                Some(training_wheels_sig) => {
+                                    training_wheels_sig
+                                        .verify(
+                                            &groth16_and_stmt,
+                                            training_wheels_pk.as_ref().unwrap(),
+                                        )
+                                        .map_err(|_| {
+                                            // println!("[aptos-vm][groth16] TW sig verification failed");
+                                            invalid_signature!(
+                                                "Could not verify training wheels signature"
                                             )
-                                            .map_err(|_| {
-                                                // println!("[aptos-vm][groth16] TW sig verification failed");
-                                                invalid_signature!(
-                                                    "Could not verify training wheels signature"
-                                                )
-                                            })?;
-                                    },
-                                    None => {
-                                        // println!("[aptos-vm][groth16] Expected TW sig to be set");
-                                        return Err(invalid_signature!(
-                                            "Training wheels signature expected but it is missing"
-                                        ));
-                                    },
-                                }
+                                        })?;
+                                },
+                                None => {
+                                    // println!("[aptos-vm][groth16] Expected TW sig to be set");
+                                    return Err(invalid_signature!(
+                                        "Training wheels signature expected but it is missing"
+                                    ));
+                                },
                             }
-
-                            let result = zksig.verify_groth16_proof(public_inputs_hash, pvk);
-
-                            result.map_err(|_| {
-                                // println!("[aptos-vm][groth16] ZKP verification failed");
-                                // println!("[aptos-vm][groth16] PIH: {}", public_inputs_hash);
-                                // match zksig.proof {
-                                //     ZKP::Groth16(proof) => {
-                                //         println!("[aptos-vm][groth16] ZKP: {}", proof.hash());
-                                //     },
-                                // }
-                                // println!(
-                                //     "[aptos-vm][groth16] PVK: {}",
-                                //     Groth16VerificationKey::from(pvk).hash()
-                                // );
-                                invalid_signature!("Proof verification failed")
-                            })?;
-                        },
-                    }
-                },
-                JWK::Unsupported(_) => return Err(invalid_signature!("JWK is not supported")),
-            },
-            EphemeralCertificate::OpenIdSig(openid_sig) => {
-                match jwk {
-                    JWK::RSA(rsa_jwk) => {
-                        openid_sig
-                            .verify_jwt_claims(sig.exp_date_secs, &sig.ephemeral_pubkey, pk, config)
-                            .map_err(|_| invalid_signature!("OpenID claim verification failed"))?;
-
-                        // TODO(OpenIdSig): Implement batch verification for all RSA signatures in
-                        //  one TXN.
-                        // Note: Individual OpenID RSA signature verification will be fast when the
-                        // RSA public exponent is small (e.g., 65537). For the same TXN, batch
-                        // verification of all RSA signatures will be even faster even when the
-                        // exponent is the same. Across different TXNs, batch verification will be
-                        // (1) more difficult to implement and (2) not very beneficial since, when
-                        // it fails, bad signature identification will require re-verifying all
-                        // signatures assuming an adversarial batch.
-                        //
-                        // We are now ready to verify the RSA signature
-                        openid_sig
-                            .verify_jwt_signature(&rsa_jwk, &sig.jwt_header_json)
-                            .map_err(|_| {
-                                invalid_signature!(
-                                    "RSA signature verification failed for OpenIdSig"
-                                )
-                            })?;
+                        }
+
+                        let result = zksig.verify_groth16_proof(public_inputs_hash, pvk);
+
+                        result.map_err(|_| {
+                            // println!("[aptos-vm][groth16] ZKP verification failed");
+                            // println!("[aptos-vm][groth16] PIH: {}", public_inputs_hash);
+                            // match zksig.proof {
+                            //     ZKP::Groth16(proof) => {
+                            //         println!("[aptos-vm][groth16] ZKP: {}", proof.hash());
+                            //     },
+                            // }
+                            // println!(
+                            //     "[aptos-vm][groth16] PVK: {}",
+                            //     Groth16VerificationKey::from(pvk).hash()
+                            // );
+                            invalid_signature!("Proof verification failed")
+                        })?;
                     },
-                    JWK::Unsupported(_) => return Err(invalid_signature!("JWK is not supported")),
                 }
             },
+            EphemeralCertificate::OpenIdSig(openid_sig) => {
+                openid_sig
+                    .verify_jwt_claims(sig.exp_date_secs, &sig.ephemeral_pubkey, pk, config)
+                    .map_err(|_| invalid_signature!("OpenID claim verification failed"))?;
+
+                // TODO(OpenIdSig): Implement batch verification for all RSA signatures in
+                //  one TXN.
+                // Note: Individual OpenID RSA signature verification will be fast when the
+                // RSA public exponent is small (e.g., 65537). For the same TXN, batch
+                // verification of all RSA signatures will be even faster even when the
+                // exponent is the same. Across different TXNs, batch verification will be
+                // (1) more difficult to implement and (2) not very beneficial since, when
+                // it fails, bad signature identification will require re-verifying all
+                // signatures assuming an adversarial batch.
+                //
+                // We are now ready to verify the RSA signature
+                openid_sig
+                    .verify_jwt_signature(&rsa_jwk, &sig.jwt_header_json)
+                    .map_err(|_| {
+                        invalid_signature!("RSA signature verification failed for OpenIdSig")
+                    })?;
+            },
         }
     }
 
@@ -13,7 +13,9 @@ use aptos_framework::natives::{
     transaction_context::NativeTransactionContext,
 };
 use aptos_gas_algebra::DynamicExpression;
-use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
+use aptos_gas_schedule::{
+    gas_feature_versions::RELEASE_V1_15, MiscGasParameters, NativeGasParameters,
+};
 use aptos_native_interface::SafeNativeBuilder;
 use aptos_table_natives::NativeTableContext;
 use aptos_types::{
@@ -23,7 +25,11 @@ use aptos_types::{
     vm::configs::aptos_prod_vm_config,
 };
 use move_binary_format::errors::VMResult;
-use move_vm_runtime::{move_vm::MoveVM, native_extensions::NativeContextExtensions};
+use move_vm_runtime::{
+    config::{DEFAULT_MAX_TY_DEPTH, DEFAULT_MAX_TY_SIZE},
+    move_vm::MoveVM,
+    native_extensions::NativeContextExtensions,
+};
 use std::ops::Deref;
 
 pub struct MoveVmExt {
@@ -58,10 +64,22 @@ impl MoveVmExt {
             builder.set_gas_hook(hook);
         }
 
+        // The limits on types are only in the gas schedule since 1.15.
+        let (max_ty_size, max_ty_depth) = if gas_feature_version >= RELEASE_V1_15 {
+            (
+                misc_gas_params.ty.max_ty_size.into(),
+                misc_gas_params.ty.max_ty_depth.into(),
+            )
+        } else {
+            (DEFAULT_MAX_TY_SIZE, DEFAULT_MAX_TY_DEPTH)
+        };
+
         let paranoid_type_checks = crate::AptosVM::get_paranoid_checks();
         let vm_config = aptos_prod_vm_config(
             &features,
             &timed_features,
+            max_ty_size,
+            max_ty_depth,
             aggregator_v2_type_tagging,
             paranoid_type_checks,
         );
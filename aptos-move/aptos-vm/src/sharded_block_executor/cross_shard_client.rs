@@ -5,50 +5,132 @@ use crate::{
     block_executor::AptosTransactionOutput,
     sharded_block_executor::{
         cross_shard_state_view::CrossShardStateView,
-        messages::{CrossShardMsg, CrossShardMsg::RemoteTxnWriteMsg, RemoteTxnWrite},
+        messages::{CrossShardMsg, CrossShardMsg::RemoteTxnWriteMsg, RemoteTxnWrite, StopMsg},
     },
 };
 use aptos_block_executor::txn_commit_hook::TransactionCommitHook;
 use aptos_logger::trace;
 use aptos_mvhashmap::types::TxnIndex;
 use aptos_types::{
-    block_executor::partitioner::{RoundId, ShardId, SubBlock, GLOBAL_ROUND_ID},
+    block_executor::partitioner::{
+        RoundId, ShardId, SubBlock, TransactionWithDependencies, GLOBAL_ROUND_ID, GLOBAL_SHARD_ID,
+    },
     state_store::{state_key::StateKey, StateView},
     transaction::analyzed_transaction::AnalyzedTransaction,
-    write_set::TransactionWrite,
+    write_set::{TransactionWrite, WriteOp},
 };
 use std::{
-    collections::{HashMap, HashSet},
-    sync::Arc,
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::{Arc, Mutex},
 };
 
+/// Buffers the cross-shard writes received from each sender (i.e. the sub-block of a shard
+/// and round), so that they are applied in the order they were sent, regardless of the order
+/// the transport delivers them in. Duplicated writes are dropped.
+#[derive(Default)]
+pub struct CrossShardMsgReorderBuffer {
+    // The sequence number of the next write to apply, and the writes received ahead of it,
+    // per sender.
+    senders: HashMap<(ShardId, RoundId), (u64, BTreeMap<u64, RemoteTxnWrite>)>,
+}
+
+impl CrossShardMsgReorderBuffer {
+    /// Adds a received write, and returns the writes that can be applied now, in order.
+    pub fn insert(&mut self, write: RemoteTxnWrite) -> Vec<RemoteTxnWrite> {
+        let (next_seq_num, pending) = self.senders.entry(write.source()).or_default();
+        if write.seq_num() < *next_seq_num {
+            // Already applied
+            return vec![];
+        }
+        pending.entry(write.seq_num()).or_insert(write);
+
+        let mut ready = vec![];
+        while let Some(write) = pending.remove(next_seq_num) {
+            ready.push(write);
+            *next_seq_num += 1;
+        }
+        ready
+    }
+
+    /// Returns the number of writes that can't be applied yet, because a write sent before
+    /// them by the same sender wasn't received.
+    pub fn num_pending(&self) -> usize {
+        self.senders
+            .values()
+            .map(|(_, pending)| pending.len())
+            .sum()
+    }
+
+    /// Returns the number of writes from the sender that were applied.
+    pub fn num_applied(&self, source: &(ShardId, RoundId)) -> u64 {
+        self.senders
+            .get(source)
+            .map_or(0, |(next_seq_num, _)| *next_seq_num)
+    }
+}
+
 pub struct CrossShardCommitReceiver {}
 
 impl CrossShardCommitReceiver {
+    /// Returns the shards and rounds that the transactions depend on, i.e. that the receiver
+    /// receives writes from.
+    pub fn senders(
+        transactions: &[TransactionWithDependencies<AnalyzedTransaction>],
+    ) -> HashSet<(ShardId, RoundId)> {
+        transactions
+            .iter()
+            .flat_map(|txn| txn.cross_shard_dependencies.required_edges_iter())
+            .map(|(txn_idx, _)| (txn_idx.shard_id, txn_idx.round_id))
+            .collect()
+    }
+
+    /// Applies the writes received from the senders, until the sub-block is executed and all
+    /// the writes sent by each of the senders are applied, so that none of them is left in the
+    /// channel.
     pub fn start<S: StateView + Sync + Send>(
         cross_shard_state_view: Arc<CrossShardStateView<S>>,
         cross_shard_client: Arc<dyn CrossShardClient>,
         round: RoundId,
+        senders: HashSet<(ShardId, RoundId)>,
     ) {
+        let mut reorder_buffer = CrossShardMsgReorderBuffer::default();
+        let mut execution_done = false;
+        let mut num_writes_per_sender = HashMap::new();
         loop {
             let msg = cross_shard_client.receive_cross_shard_msg(round);
             match msg {
                 RemoteTxnWriteMsg(txn_commit_msg) => {
-                    let (state_key, write_op) = txn_commit_msg.take();
-                    cross_shard_state_view
-                        .set_value(&state_key, write_op.and_then(|w| w.as_state_value()));
+                    for txn_commit_msg in reorder_buffer.insert(txn_commit_msg) {
+                        let (state_key, write_op) = txn_commit_msg.take();
+                        cross_shard_state_view
+                            .set_value(&state_key, write_op.and_then(|w| w.as_state_value()));
+                    }
+                },
+                CrossShardMsg::StopMsg(StopMsg::ExecutionDone) => {
+                    execution_done = true;
                 },
-                CrossShardMsg::StopMsg => {
-                    trace!("Cross shard commit receiver stopped for round {}", round);
-                    break;
+                CrossShardMsg::StopMsg(StopMsg::SenderDone { source, num_writes }) => {
+                    num_writes_per_sender.insert(source, num_writes);
                 },
             }
+            let all_writes_applied = senders.iter().all(|sender| {
+                num_writes_per_sender
+                    .get(sender)
+                    .map_or(false, |num_writes| {
+                        reorder_buffer.num_applied(sender) == *num_writes
+                    })
+            });
+            if execution_done && all_writes_applied {
+                trace!("Cross shard commit receiver stopped for round {}", round);
+                break;
+            }
         }
     }
 }
 
 pub struct CrossShardCommitSender {
     shard_id: ShardId,
+    round: RoundId,
     cross_shard_client: Arc<dyn CrossShardClient>,
     // The hashmap of source txn index to hashmap of conflicting storage location to the
     // list shard id and round id. Please note that the transaction indices stored here is
@@ -58,15 +140,23 @@ pub struct CrossShardCommitSender {
     // The offset of the first transaction in the sub-block. This is used to convert the local index
     // in parallel execution to the global index.
     index_offset: TxnIndex,
+    // The sequence number of the next write to send, per receiving shard and round. The
+    // receivers apply the writes by sequence number, so that they don't depend on the
+    // transport delivering them in order.
+    next_seq_nums: Mutex<HashMap<(ShardId, RoundId), u64>>,
+    // The shards and rounds that the writes are sent to.
+    receivers: HashSet<(ShardId, RoundId)>,
 }
 
 impl CrossShardCommitSender {
     pub fn new(
         shard_id: ShardId,
+        round: RoundId,
         cross_shard_client: Arc<dyn CrossShardClient>,
         sub_block: &SubBlock<AnalyzedTransaction>,
     ) -> Self {
         let mut dependent_edges = HashMap::new();
+        let mut receivers = HashSet::new();
         let mut num_dependent_edges = 0;
         for (txn_idx, txn_with_deps) in sub_block.txn_with_index_iter() {
             let mut storage_locations_to_target = HashMap::new();
@@ -80,6 +170,10 @@ impl CrossShardCommitSender {
                         .entry(storage_location.clone().into_state_key())
                         .or_insert_with(HashSet::new)
                         .insert((txn_id_with_shard.shard_id, txn_id_with_shard.round_id));
+                    receivers.insert(Self::receiver(
+                        txn_id_with_shard.shard_id,
+                        txn_id_with_shard.round_id,
+                    ));
                     num_dependent_edges += 1;
                 }
             }
@@ -96,9 +190,65 @@ impl CrossShardCommitSender {
 
         Self {
             shard_id,
+            round,
             cross_shard_client,
             dependent_edges,
             index_offset: sub_block.start_index as TxnIndex,
+            next_seq_nums: Mutex::new(HashMap::new()),
+            receivers,
+        }
+    }
+
+    fn receiver(dependent_shard_id: ShardId, round_id: RoundId) -> (ShardId, RoundId) {
+        // The global shard receives all the writes on a single channel.
+        if round_id == GLOBAL_ROUND_ID {
+            (GLOBAL_SHARD_ID, GLOBAL_ROUND_ID)
+        } else {
+            (dependent_shard_id, round_id)
+        }
+    }
+
+    fn send_msg(&self, (shard_id, round_id): (ShardId, RoundId), message: CrossShardMsg) {
+        if round_id == GLOBAL_ROUND_ID {
+            self.cross_shard_client.send_global_msg(message);
+        } else {
+            self.cross_shard_client
+                .send_cross_shard_msg(shard_id, round_id, message);
+        }
+    }
+
+    fn send_remote_write(
+        &self,
+        dependent_shard_id: ShardId,
+        round_id: RoundId,
+        state_key: StateKey,
+        write_op: Option<WriteOp>,
+    ) {
+        let receiver = Self::receiver(dependent_shard_id, round_id);
+        // Hold the lock while sending, so that the writes are also sent in order.
+        let mut next_seq_nums = self.next_seq_nums.lock().unwrap();
+        let seq_num = next_seq_nums.entry(receiver).or_insert(0);
+        let message = RemoteTxnWriteMsg(RemoteTxnWrite::new(
+            state_key,
+            write_op,
+            (self.shard_id, self.round),
+            *seq_num,
+        ));
+        *seq_num += 1;
+        self.send_msg(receiver, message);
+    }
+
+    /// Lets each of the receivers know the number of writes sent to it, so that it waits for all
+    /// of them before it stops. Must be called once the sub-block is executed, i.e. after the last
+    /// write is sent.
+    pub fn send_stop_msgs(&self) {
+        let next_seq_nums = self.next_seq_nums.lock().unwrap();
+        for receiver in &self.receivers {
+            let message = CrossShardMsg::StopMsg(StopMsg::SenderDone {
+                source: (self.shard_id, self.round),
+                num_writes: next_seq_nums.get(receiver).copied().unwrap_or(0),
+            });
+            self.send_msg(*receiver, message);
         }
     }
 
@@ -115,19 +265,12 @@ impl CrossShardCommitSender {
             if let Some(dependent_shard_ids) = edges.get(state_key) {
                 for (dependent_shard_id, round_id) in dependent_shard_ids.iter() {
                     trace!("Sending remote update for success for shard id {:?} and txn_idx: {:?}, state_key: {:?}, dependent shard id: {:?}", self.shard_id, txn_idx, state_key, dependent_shard_id);
-                    let message = RemoteTxnWriteMsg(RemoteTxnWrite::new(
+                    self.send_remote_write(
+                        *dependent_shard_id,
+                        *round_id,
                         state_key.clone(),
                         Some(write_op.clone()),
-                    ));
-                    if *round_id == GLOBAL_ROUND_ID {
-                        self.cross_shard_client.send_global_msg(message);
-                    } else {
-                        self.cross_shard_client.send_cross_shard_msg(
-                            *dependent_shard_id,
-                            *round_id,
-                            message,
-                        );
-                    }
+                    );
                 }
             }
         }
@@ -149,6 +292,20 @@ impl TransactionCommitHook for CrossShardCommitSender {
     }
 }
 
+// The sender is borrowed by the block executor, so that the stop messages can be sent after the
+// execution.
+impl TransactionCommitHook for &CrossShardCommitSender {
+    type Output = AptosTransactionOutput;
+
+    fn on_transaction_committed(&self, txn_idx: TxnIndex, txn_output: &Self::Output) {
+        (**self).on_transaction_committed(txn_idx, txn_output)
+    }
+
+    fn on_execution_aborted(&self, txn_idx: TxnIndex) {
+        (**self).on_execution_aborted(txn_idx)
+    }
+}
+
 // CrossShardClient is a trait that defines the interface for sending and receiving messages across
 // shards.
 pub trait CrossShardClient: Send + Sync {
@@ -158,3 +315,167 @@ pub trait CrossShardClient: Send + Sync {
 
     fn receive_cross_shard_msg(&self, current_round: RoundId) -> CrossShardMsg;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::state_store::{
+        in_memory_state_view::InMemoryStateView, state_value::StateValue, TStateView,
+    };
+    use crossbeam_channel::{unbounded, Receiver, Sender};
+    use proptest::{collection::vec, prelude::*, sample::Index};
+
+    struct TestCrossShardClient {
+        message_tx: Sender<CrossShardMsg>,
+        message_rx: Receiver<CrossShardMsg>,
+    }
+
+    impl CrossShardClient for TestCrossShardClient {
+        fn send_global_msg(&self, msg: CrossShardMsg) {
+            self.message_tx.send(msg).unwrap()
+        }
+
+        fn send_cross_shard_msg(&self, _shard_id: ShardId, _round: RoundId, msg: CrossShardMsg) {
+            self.message_tx.send(msg).unwrap()
+        }
+
+        fn receive_cross_shard_msg(&self, _current_round: RoundId) -> CrossShardMsg {
+            self.message_rx.recv().unwrap()
+        }
+    }
+
+    /// Returns the writes sent by each of the senders, in the order they were sent
+    fn sent_writes(num_writes_per_sender: &[usize]) -> Vec<RemoteTxnWrite> {
+        num_writes_per_sender
+            .iter()
+            .enumerate()
+            .flat_map(|(shard_id, num_writes)| {
+                (0..*num_writes).map(move |seq_num| {
+                    RemoteTxnWrite::new(
+                        StateKey::raw(format!("key-{}-{}", shard_id, seq_num).as_bytes()),
+                        None,
+                        (shard_id, 0),
+                        seq_num as u64,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Generates the number of writes sent by each sender, and the writes as received, i.e.
+    /// with some of them duplicated, and all of them in a random order
+    fn received_writes() -> impl Strategy<Value = (Vec<usize>, Vec<RemoteTxnWrite>)> {
+        (vec(0usize..20, 1..4), vec(any::<Index>(), 0..10)).prop_flat_map(
+            |(num_writes_per_sender, duplicates)| {
+                let sent = sent_writes(&num_writes_per_sender);
+                let mut received = sent.clone();
+                if !sent.is_empty() {
+                    received.extend(duplicates.iter().map(|index| index.get(&sent).clone()));
+                }
+                (Just(num_writes_per_sender), Just(received).prop_shuffle())
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_reorder_buffer_applies_writes_in_order(
+            (num_writes_per_sender, received) in received_writes(),
+        ) {
+            let mut reorder_buffer = CrossShardMsgReorderBuffer::default();
+            let applied: Vec<_> = received
+                .into_iter()
+                .flat_map(|write| reorder_buffer.insert(write))
+                .collect();
+
+            // Every write is applied exactly once, and in the order it was sent by its sender
+            prop_assert_eq!(reorder_buffer.num_pending(), 0);
+            prop_assert_eq!(applied.len(), num_writes_per_sender.iter().sum::<usize>());
+            for (shard_id, num_writes) in num_writes_per_sender.iter().enumerate() {
+                let seq_nums: Vec<_> = applied
+                    .iter()
+                    .filter(|write| write.source() == (shard_id, 0))
+                    .map(|write| write.seq_num())
+                    .collect();
+                prop_assert_eq!(seq_nums, (0..*num_writes as u64).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_reorder_buffer_holds_writes_after_a_gap() {
+        let sent = sent_writes(&[3]);
+        let mut reorder_buffer = CrossShardMsgReorderBuffer::default();
+
+        // The writes after the missing first one are held back, until it is received
+        assert!(reorder_buffer.insert(sent[2].clone()).is_empty());
+        assert!(reorder_buffer.insert(sent[1].clone()).is_empty());
+        assert_eq!(reorder_buffer.num_pending(), 2);
+        let applied = reorder_buffer.insert(sent[0].clone());
+        assert_eq!(
+            applied
+                .iter()
+                .map(|write| write.seq_num())
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        // Late duplicates are dropped
+        assert!(reorder_buffer.insert(sent[1].clone()).is_empty());
+        assert_eq!(reorder_buffer.num_pending(), 0);
+    }
+
+    #[test]
+    fn test_receiver_applies_all_writes_before_stopping() {
+        let (message_tx, message_rx) = unbounded();
+        let cross_shard_client = Arc::new(TestCrossShardClient {
+            message_tx,
+            message_rx: message_rx.clone(),
+        });
+        let state_key = |idx: usize| StateKey::raw(format!("key-{}", idx).as_bytes());
+        let value = |idx: usize| vec![idx as u8];
+        let write = |idx: usize| {
+            RemoteTxnWriteMsg(RemoteTxnWrite::new(
+                state_key(idx),
+                Some(WriteOp::legacy_modification(value(idx).into())),
+                (0, 0),
+                idx as u64,
+            ))
+        };
+        let base_view = InMemoryStateView::new(HashMap::new());
+        let cross_shard_state_view = Arc::new(CrossShardStateView::new(
+            (0..2).map(state_key).collect(),
+            &base_view,
+        ));
+
+        // The sub-block is executed before the writes of the sender arrive
+        for msg in [
+            CrossShardMsg::StopMsg(StopMsg::ExecutionDone),
+            write(1),
+            CrossShardMsg::StopMsg(StopMsg::SenderDone {
+                source: (0, 0),
+                num_writes: 2,
+            }),
+            write(0),
+        ] {
+            cross_shard_client.send_global_msg(msg);
+        }
+        CrossShardCommitReceiver::start(
+            cross_shard_state_view.clone(),
+            cross_shard_client,
+            1,
+            HashSet::from([(0, 0)]),
+        );
+
+        // All the writes are applied, and none of them is left in the channel
+        assert!(message_rx.is_empty());
+        for idx in 0..2 {
+            assert_eq!(
+                cross_shard_state_view
+                    .get_state_value(&state_key(idx))
+                    .unwrap(),
+                Some(StateValue::new_legacy(value(idx).into()))
+            );
+        }
+    }
+}
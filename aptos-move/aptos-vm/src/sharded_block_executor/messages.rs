@@ -1,13 +1,29 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_types::{state_store::state_key::StateKey, write_set::WriteOp};
+use aptos_types::{
+    block_executor::partitioner::{RoundId, ShardId},
+    state_store::state_key::StateKey,
+    write_set::WriteOp,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum CrossShardMsg {
     RemoteTxnWriteMsg(RemoteTxnWrite),
-    StopMsg,
+    StopMsg(StopMsg),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum StopMsg {
+    // Sent by a shard to its own receiver once it has executed its sub-block.
+    ExecutionDone,
+    // Sent by a sender to each of its receivers after the last write to it, with the number of
+    // writes it sent, so that the receiver doesn't stop before all of them are applied.
+    SenderDone {
+        source: (ShardId, RoundId),
+        num_writes: u64,
+    },
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -15,16 +31,36 @@ pub struct RemoteTxnWrite {
     state_key: StateKey,
     // The write op is None if the transaction is aborted.
     write_op: Option<WriteOp>,
+    // The shard and round of the sub-block that sent the write.
+    source: (ShardId, RoundId),
+    // The sequence number of the write among the writes sent by the source to the same
+    // shard and round, so that they are applied in the order they were sent.
+    seq_num: u64,
 }
 
 impl RemoteTxnWrite {
-    pub fn new(state_key: StateKey, write_op: Option<WriteOp>) -> Self {
+    pub fn new(
+        state_key: StateKey,
+        write_op: Option<WriteOp>,
+        source: (ShardId, RoundId),
+        seq_num: u64,
+    ) -> Self {
         Self {
             state_key,
             write_op,
+            source,
+            seq_num,
         }
     }
 
+    pub fn source(&self) -> (ShardId, RoundId) {
+        self.source
+    }
+
+    pub fn seq_num(&self) -> u64 {
+        self.seq_num
+    }
+
     pub fn take(self) -> (StateKey, Option<WriteOp>) {
         (self.state_key, self.write_op)
     }
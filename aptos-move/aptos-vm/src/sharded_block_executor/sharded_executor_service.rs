@@ -12,7 +12,7 @@ use crate::{
         },
         cross_shard_client::{CrossShardClient, CrossShardCommitReceiver, CrossShardCommitSender},
         cross_shard_state_view::CrossShardStateView,
-        messages::CrossShardMsg,
+        messages::{CrossShardMsg, StopMsg},
         ExecutorShardCommand,
     },
 };
@@ -81,8 +81,12 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
             self.shard_id,
             round
         );
-        let cross_shard_commit_sender =
-            CrossShardCommitSender::new(self.shard_id, self.cross_shard_client.clone(), &sub_block);
+        let cross_shard_commit_sender = CrossShardCommitSender::new(
+            self.shard_id,
+            round,
+            self.cross_shard_client.clone(),
+            &sub_block,
+        );
         Self::execute_transactions_with_dependencies(
             Some(self.shard_id),
             self.executor_thread_pool.clone(),
@@ -112,6 +116,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
             &transactions,
         ));
 
+        let senders = CrossShardCommitReceiver::senders(&transactions);
         let cross_shard_state_view_clone = cross_shard_state_view.clone();
         let cross_shard_client_clone = cross_shard_client.clone();
 
@@ -132,6 +137,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                     cross_shard_state_view_clone,
                     cross_shard_client,
                     round,
+                    senders,
                 );
             });
             s.spawn(move |_| {
@@ -140,9 +146,12 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                     &signature_verified_transactions,
                     aggr_overridden_state_view.as_ref(),
                     config,
-                    cross_shard_commit_sender,
+                    cross_shard_commit_sender.as_ref(),
                 )
                 .map(BlockOutput::into_transaction_outputs_forced);
+                if let Some(cross_shard_commit_sender) = &cross_shard_commit_sender {
+                    cross_shard_commit_sender.send_stop_msgs();
+                }
                 if let Some(shard_id) = shard_id {
                     trace!(
                         "executed sub block for shard {} and round {}",
@@ -153,12 +162,13 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                     cross_shard_client_clone.send_cross_shard_msg(
                         shard_id,
                         round,
-                        CrossShardMsg::StopMsg,
+                        CrossShardMsg::StopMsg(StopMsg::ExecutionDone),
                     );
                 } else {
                     trace!("executed block for global shard and round {}", round);
                     // Send a self message to stop the cross-shard commit receiver.
-                    cross_shard_client_clone.send_global_msg(CrossShardMsg::StopMsg);
+                    cross_shard_client_clone
+                        .send_global_msg(CrossShardMsg::StopMsg(StopMsg::ExecutionDone));
                 }
                 callback.send(ret).unwrap();
                 executor_thread_pool_clone.spawn(move || {
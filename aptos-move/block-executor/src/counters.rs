@@ -87,6 +87,16 @@ pub static EXCEED_PER_BLOCK_OUTPUT_LIMIT_COUNT: Lazy<IntCounterVec> = Lazy::new(
     .unwrap()
 });
 
+/// Count of aggregator v1 base values preloaded from storage during execution, so that the
+/// deltas are materialized at commit time without reading the storage.
+pub static AGGREGATOR_V1_BASE_VALUE_PRELOAD_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_execution_aggregator_v1_base_value_preload_count",
+        "Count of aggregator v1 base values preloaded from storage during execution"
+    )
+    .unwrap()
+});
+
 pub static PARALLEL_EXECUTION_SECONDS: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!(
         // metric name
@@ -14,7 +14,7 @@ use crate::{
     limit_processor::BlockGasLimitProcessor,
     scheduler::{DependencyStatus, ExecutionTaskType, Scheduler, SchedulerTask, Wave},
     task::{ExecutionStatus, ExecutorTask, TransactionOutput},
-    txn_commit_hook::TransactionCommitHook,
+    txn_commit_hook::{TransactionCommitHook, TxnAccessSummarySink},
     txn_last_input_output::{KeyKind, TxnLastInputOutput},
     types::ReadWriteSummary,
     view::{LatestView, ParallelState, SequentialState, ViewState},
@@ -60,12 +60,14 @@ use std::{
     },
 };
 
-pub struct BlockExecutor<T, E, S, L, X> {
+pub struct BlockExecutor<T: Transaction, E, S, L, X> {
     // Number of active concurrent tasks, corresponding to the maximum number of rayon
     // threads that may be concurrently participating in parallel execution.
     config: BlockExecutorConfig,
     executor_thread_pool: Arc<ThreadPool>,
     transaction_commit_hook: Option<L>,
+    // Receives the access summaries of the committed transactions, for conflict analytics.
+    access_summary_sink: Option<Arc<dyn TxnAccessSummarySink<T::Key>>>,
     phantom: PhantomData<(T, E, S, L, X)>,
 }
 
@@ -93,10 +95,21 @@ where
             config,
             executor_thread_pool,
             transaction_commit_hook,
+            access_summary_sink: None,
             phantom: PhantomData,
         }
     }
 
+    /// Sets a sink for the access summaries of the committed transactions. The summaries are
+    /// only collected by parallel execution, which records the reads of the transactions.
+    pub fn with_access_summary_sink(
+        mut self,
+        access_summary_sink: Arc<dyn TxnAccessSummarySink<T::Key>>,
+    ) -> Self {
+        self.access_summary_sink = Some(access_summary_sink);
+        self
+    }
+
     fn execute(
         idx_to_execute: TxnIndex,
         incarnation: Incarnation,
@@ -188,6 +201,12 @@ where
                 if prev_modified_keys.remove(&k).is_none() {
                     needs_suffix_validation = true;
                 }
+                Self::preload_aggregator_v1_base_value(
+                    &k,
+                    last_input_output,
+                    versioned_cache,
+                    base_view,
+                );
                 versioned_cache.data().add_delta(k, idx_to_execute, d);
             }
 
@@ -544,6 +563,12 @@ where
                 }
             }
 
+            // Transactions are committed sequentially, so the sink receives them in order.
+            if let Some(access_summary_sink) = &self.access_summary_sink {
+                access_summary_sink
+                    .on_access_summary(txn_idx, last_input_output.get_txn_access_summary(txn_idx));
+            }
+
             let finalized_groups = groups_to_finalize!(last_input_output, txn_idx)
                 .map(|((group_key, metadata_op), is_read_needing_exchange)| {
                     // finalize_group copies Arc of values and the Tags (TODO: optimize as needed).
@@ -605,6 +630,35 @@ where
         Ok(())
     }
 
+    /// Sets the base value of an aggregator v1 that a transaction produced a delta for, so that
+    /// the first committed delta of the aggregator is materialized without reading the storage.
+    /// The storage is read at most once per aggregator in a block (by the first execution that
+    /// produces a delta for it), and not at all if the base value was already set by a read. As
+    /// the delta is speculative, the aggregator may not exist in storage, in which case nothing
+    /// is set. Note that this does not materialize the deltas themselves.
+    fn preload_aggregator_v1_base_value(
+        key: &T::Key,
+        last_input_output: &TxnLastInputOutput<T, E::Output, E::Error>,
+        versioned_cache: &MVHashMap<T::Key, T::Tag, T::Value, X, T::Identifier>,
+        base_view: &S,
+    ) {
+        if versioned_cache.data().has_base_value(key)
+            || !last_input_output.mark_aggregator_v1_base_value_preloaded(key)
+        {
+            return;
+        }
+
+        if let Ok(storage_value @ Some(_)) = base_view.get_state_value(key) {
+            let w: T::Value = TransactionWrite::from_state_value(storage_value);
+            if let Ok(Some(_)) = w.as_u128() {
+                counters::AGGREGATOR_V1_BASE_VALUE_PRELOAD_COUNT.inc();
+                versioned_cache
+                    .data()
+                    .set_base_value(key.clone(), ValueWithLayout::RawFromStorage(Arc::new(w)));
+            }
+        }
+    }
+
     fn materialize_aggregator_v1_delta_writes(
         txn_idx: TxnIndex,
         last_input_output: &TxnLastInputOutput<T, E::Output, E::Error>,
@@ -1206,8 +1206,10 @@ where
     }
 
     fn output_approx_size(&self) -> u64 {
-        // TODO add block output limit testing
-        0
+        self.writes
+            .iter()
+            .map(|(_, value)| value.bytes().map_or(0, |bytes| bytes.len() as u64))
+            .sum()
     }
 
     fn get_write_summary(
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::task::TransactionOutput;
+use crate::{task::TransactionOutput, types::TxnAccessSummary};
 use aptos_mvhashmap::types::TxnIndex;
 
 /// An interface for listening to transaction commit events. The listener is called only once
@@ -14,6 +14,13 @@ pub trait TransactionCommitHook: Send + Sync {
     fn on_execution_aborted(&self, txn_idx: TxnIndex);
 }
 
+/// An interface for collecting the access summaries of committed transactions, e.g. to
+/// analyze the conflicts in a block. The sink is called once for each committed transaction,
+/// in the order of the transactions in the block.
+pub trait TxnAccessSummarySink<K>: Send + Sync {
+    fn on_access_summary(&self, txn_idx: TxnIndex, summary: TxnAccessSummary<K>);
+}
+
 pub struct NoOpTransactionCommitHook<T, E> {
     phantom: std::marker::PhantomData<(T, E)>,
 }
@@ -6,7 +6,7 @@ use crate::{
     errors::ParallelBlockExecutionError,
     explicit_sync_wrapper::ExplicitSyncWrapper,
     task::{ExecutionStatus, TransactionOutput},
-    types::{InputOutputKey, ReadWriteSummary},
+    types::{InputOutputKey, ReadWriteSummary, TxnAccessSummary},
 };
 use aptos_aggregator::types::code_invariant_error;
 use aptos_logger::error;
@@ -21,7 +21,7 @@ use crossbeam::utils::CachePadded;
 use dashmap::DashSet;
 use move_core_types::value::MoveTypeLayout;
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
     fmt::Debug,
     iter::{empty, Iterator},
     sync::Arc,
@@ -73,6 +73,10 @@ pub struct TxnLastInputOutput<T: Transaction, O: TransactionOutput<Txn = T>, E:
     // Move-VM loader cache - see 'record' function comment for more information.
     module_writes: DashSet<T::Key>,
     module_reads: DashSet<T::Key>,
+
+    // Aggregator v1 keys whose base value was preloaded from storage by a (speculative)
+    // execution, so that the storage is read at most once per key in the block.
+    aggregator_v1_preloaded_keys: DashSet<T::Key>,
 }
 
 impl<T: Transaction, O: TransactionOutput<Txn = T>, E: Debug + Send + Clone>
@@ -94,9 +98,16 @@ impl<T: Transaction, O: TransactionOutput<Txn = T>, E: Debug + Send + Clone>
                 .collect(),
             module_writes: DashSet::new(),
             module_reads: DashSet::new(),
+            aggregator_v1_preloaded_keys: DashSet::new(),
         }
     }
 
+    /// Returns true if the base value of the aggregator v1 at the given key has not yet been
+    /// preloaded in the block, and marks it as preloaded.
+    pub(crate) fn mark_aggregator_v1_base_value_preloaded(&self, key: &T::Key) -> bool {
+        self.aggregator_v1_preloaded_keys.insert(key.clone())
+    }
+
     fn append_and_check<'a>(
         paths: impl Iterator<Item = &'a T::Key>,
         set_to_append: &DashSet<T::Key>,
@@ -428,6 +439,36 @@ impl<T: Transaction, O: TransactionOutput<Txn = T>, E: Debug + Send + Clone>
         ReadWriteSummary::new(reads, writes)
     }
 
+    /// Returns the keys the transaction read, wrote and applied deltas to, for conflict
+    /// analytics. Must be called on commit, while the read set and the output are recorded.
+    pub(crate) fn get_txn_access_summary(&self, txn_idx: TxnIndex) -> TxnAccessSummary<T::Key> {
+        let read_set = self.read_set(txn_idx).expect("Read set must be recorded");
+
+        let read_keys: BTreeSet<_> = read_set
+            .get_read_summary()
+            .into_iter()
+            .filter_map(|key| match key {
+                InputOutputKey::Resource(key) | InputOutputKey::Group(key, _) => Some(key),
+                InputOutputKey::DelayedField(_) => None,
+            })
+            .collect();
+        let delta_keys: BTreeSet<_> = self.aggregator_v1_delta_keys(txn_idx).into_iter().collect();
+        // Modified keys include the delta keys, which are reported separately.
+        let written_keys: BTreeSet<_> = self
+            .modified_keys(txn_idx)
+            .into_iter()
+            .flatten()
+            .map(|(key, _)| key)
+            .filter(|key| !delta_keys.contains(key))
+            .collect();
+
+        TxnAccessSummary {
+            read_keys: read_keys.into_iter().collect(),
+            written_keys: written_keys.into_iter().collect(),
+            delta_keys: delta_keys.into_iter().collect(),
+        }
+    }
+
     pub(crate) fn get_write_summary(
         &self,
         txn_idx: TxnIndex,
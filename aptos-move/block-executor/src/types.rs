@@ -11,6 +11,16 @@ pub enum InputOutputKey<K, T, I> {
     DelayedField(I),
 }
 
+/// A compact summary of the keys a committed transaction read (from storage or from an earlier
+/// transaction in the block), wrote, and applied aggregator v1 deltas to. The keys are sorted,
+/// so that conflict graphs of a block can be built after execution, without re-executing it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TxnAccessSummary<K> {
+    pub read_keys: Vec<K>,
+    pub written_keys: Vec<K>,
+    pub delta_keys: Vec<K>,
+}
+
 pub struct ReadWriteSummary<T: Transaction> {
     reads: HashSet<InputOutputKey<T::Key, T::Tag, T::Identifier>>,
     writes: HashSet<InputOutputKey<T::Key, T::Tag, T::Identifier>>,
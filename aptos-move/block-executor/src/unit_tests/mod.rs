@@ -15,7 +15,8 @@ use crate::{
     scheduler::{
         DependencyResult, ExecutionTaskType, Scheduler, SchedulerTask, TWaitForDependency,
     },
-    txn_commit_hook::NoOpTransactionCommitHook,
+    txn_commit_hook::{NoOpTransactionCommitHook, TxnAccessSummarySink},
+    types::TxnAccessSummary,
 };
 use aptos_aggregator::{
     bounded_math::SignedU128,
@@ -24,12 +25,15 @@ use aptos_aggregator::{
 };
 use aptos_mvhashmap::types::TxnIndex;
 use aptos_types::{
-    block_executor::config::BlockExecutorConfig,
+    block_executor::config::{
+        BlockExecutorConfig, BlockExecutorConfigFromOnchain, BlockExecutorLocalConfig,
+    },
     contract_event::TransactionEvent,
     executable::{ExecutableTestType, ModulePath},
+    on_chain_config::BlockGasLimitType,
     state_store::state_value::StateValueMetadata,
 };
-use claims::assert_matches;
+use claims::{assert_matches, assert_ok};
 use fail::FailScenario;
 use rand::{prelude::*, random};
 use std::{
@@ -38,7 +42,7 @@ use std::{
     fmt::Debug,
     hash::Hash,
     marker::PhantomData,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 #[test]
@@ -232,6 +236,158 @@ fn skip_rest_gas_limit() {
     let _ = block_executor.execute_transactions_parallel((), &transactions, &data_view);
 }
 
+#[test]
+fn block_output_limit_cut() {
+    // Every txn writes a 32 byte value, so the output limit is reached by the third txn.
+    let transactions: Vec<_> = (0..10)
+        .map(|i| {
+            MockTransaction::from_behavior(MockIncarnation::<KeyType<u32>, MockEvent>::new(
+                vec![],
+                vec![(KeyType::<u32>(i, false), random_value(false))],
+                vec![],
+                vec![],
+                1,
+            ))
+        })
+        .collect();
+
+    let data_view = DeltaDataView::<KeyType<u32>> {
+        phantom: PhantomData,
+    };
+    let executor_thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_cpus::get())
+            .build()
+            .unwrap(),
+    );
+    let config = BlockExecutorConfig {
+        local: BlockExecutorLocalConfig {
+            concurrency_level: num_cpus::get(),
+            allow_fallback: true,
+            discard_failed_blocks: false,
+        },
+        onchain: BlockExecutorConfigFromOnchain {
+            block_gas_limit_type: BlockGasLimitType::ComplexLimitV1 {
+                effective_block_gas_limit: 1_000_000,
+                execution_gas_effective_multiplier: 1,
+                io_gas_effective_multiplier: 1,
+                conflict_penalty_window: 1,
+                use_granular_resource_group_conflicts: false,
+                use_module_publishing_block_conflict: false,
+                block_output_limit: Some(80),
+                include_user_txn_size_in_block_output: false,
+                add_block_limit_outcome_onchain: false,
+            },
+        },
+    };
+    let block_executor = BlockExecutor::<
+        MockTransaction<KeyType<u32>, MockEvent>,
+        MockTask<KeyType<u32>, MockEvent>,
+        DeltaDataView<KeyType<u32>>,
+        NoOpTransactionCommitHook<MockOutput<KeyType<u32>, MockEvent>, usize>,
+        ExecutableTestType,
+    >::new(config, executor_thread_pool, None);
+
+    // Both execution modes must cut the block at the same txn, and skip the remaining txns,
+    // so that they are retried in the next block.
+    let par_output = block_executor.execute_transactions_parallel((), &transactions, &data_view);
+    let seq_output =
+        block_executor.execute_transactions_sequential((), &transactions, &data_view, false);
+    for output in [par_output.ok(), seq_output.ok()] {
+        let txn_outputs = output
+            .expect("Block execution must succeed")
+            .into_transaction_outputs_forced();
+        assert_eq!(txn_outputs.len(), 10);
+        for (idx, txn_output) in txn_outputs.iter().enumerate() {
+            assert_eq!(txn_output.skipped, idx > 2);
+            assert_eq!(txn_output.writes.is_empty(), idx > 2);
+        }
+    }
+}
+
+/// Collects the access summaries of the committed transactions.
+#[derive(Default)]
+struct CollectingAccessSummarySink<K> {
+    summaries: Mutex<Vec<(TxnIndex, TxnAccessSummary<K>)>>,
+}
+
+impl<K: Send> TxnAccessSummarySink<K> for CollectingAccessSummarySink<K> {
+    fn on_access_summary(&self, txn_idx: TxnIndex, summary: TxnAccessSummary<K>) {
+        self.summaries.lock().unwrap().push((txn_idx, summary));
+    }
+}
+
+#[test]
+fn access_summaries() {
+    let [key_a, key_b, key_c] = [1, 2, 3].map(|k| KeyType::<u32>(k, false));
+    let transactions = vec![
+        MockTransaction::from_behavior(MockIncarnation::<KeyType<u32>, MockEvent>::new(
+            vec![],
+            vec![(key_c, random_value(false)), (key_a, random_value(false))], // writes
+            vec![],
+            vec![],
+            1, // gas
+        )),
+        MockTransaction::from_behavior(MockIncarnation::<KeyType<u32>, MockEvent>::new(
+            vec![key_a],                            // reads
+            vec![(key_b, random_value(false))],     // writes
+            vec![(key_c, delta_add(5, u128::MAX))], // deltas
+            vec![],
+            1, // gas
+        )),
+        MockTransaction::from_behavior(MockIncarnation::<KeyType<u32>, MockEvent>::new(
+            vec![key_b], // reads
+            vec![],
+            vec![],
+            vec![],
+            1, // gas
+        )),
+    ];
+
+    let data_view = DeltaDataView::<KeyType<u32>> {
+        phantom: PhantomData,
+    };
+    let executor_thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_cpus::get())
+            .build()
+            .unwrap(),
+    );
+    let sink = Arc::new(CollectingAccessSummarySink::default());
+    let block_executor = BlockExecutor::<
+        MockTransaction<KeyType<u32>, MockEvent>,
+        MockTask<KeyType<u32>, MockEvent>,
+        DeltaDataView<KeyType<u32>>,
+        NoOpTransactionCommitHook<MockOutput<KeyType<u32>, MockEvent>, usize>,
+        ExecutableTestType,
+    >::new(
+        BlockExecutorConfig::new_no_block_limit(num_cpus::get()),
+        executor_thread_pool,
+        None,
+    )
+    .with_access_summary_sink(sink.clone());
+    assert_ok!(block_executor.execute_transactions_parallel((), &transactions, &data_view));
+
+    // Summaries are received in order, with sorted keys
+    assert_eq!(sink.summaries.lock().unwrap().clone(), vec![
+        (0, TxnAccessSummary {
+            read_keys: vec![],
+            written_keys: vec![key_a, key_c],
+            delta_keys: vec![],
+        }),
+        (1, TxnAccessSummary {
+            read_keys: vec![key_a],
+            written_keys: vec![key_b],
+            delta_keys: vec![key_c],
+        }),
+        (2, TxnAccessSummary {
+            read_keys: vec![key_b],
+            written_keys: vec![],
+            delta_keys: vec![],
+        }),
+    ]);
+}
+
 // TODO: add unit test for block gas limit!
 fn run_and_assert<K, E>(transactions: Vec<MockTransaction<K, E>>)
 where
@@ -0,0 +1,189 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates random blocks of transfer, publish and object transactions, to cross-check
+//! sequential and parallel (Block-STM) execution.
+
+use crate::{build_package, harness::MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_framework::{BuildOptions, BuiltPackage};
+use aptos_language_e2e_tests::{
+    account::Account,
+    executor::{ExecutorMode, FakeExecutor},
+};
+use aptos_package_builder::PackageBuilder;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{SignedTransaction, TransactionOutput},
+};
+use proptest::prelude::*;
+
+/// The number of distinct collections an account may create, kept small so that transactions
+/// of a block often conflict on them.
+const NUM_COLLECTIONS: usize = 3;
+
+/// A transaction of a generated block. Accounts are referred to by their index.
+#[derive(Clone, Debug)]
+pub enum GeneratedTransaction {
+    /// Transfers APT, which aborts if the sender's balance is too low
+    Transfer {
+        sender: usize,
+        receiver: usize,
+        amount: u64,
+    },
+    /// Publishes the sender's package, or upgrades it if it's already published
+    Publish { sender: usize },
+    /// Creates a collection object, which aborts if the sender already created it
+    CreateCollection { sender: usize, collection: usize },
+    /// Mints a token object into a collection, which aborts if it hasn't been created yet
+    MintToken { sender: usize, collection: usize },
+}
+
+impl GeneratedTransaction {
+    pub fn arbitrary(num_accounts: usize) -> BoxedStrategy<GeneratedTransaction> {
+        let account = move || 0..num_accounts;
+        prop_oneof![
+            3 => (account(), account(), 0u64..2_000_000_000).prop_map(
+                |(sender, receiver, amount)| GeneratedTransaction::Transfer {
+                    sender,
+                    receiver,
+                    amount,
+                }
+            ),
+            1 => account().prop_map(|sender| GeneratedTransaction::Publish { sender }),
+            2 => (account(), 0..NUM_COLLECTIONS).prop_map(|(sender, collection)| {
+                GeneratedTransaction::CreateCollection { sender, collection }
+            }),
+            2 => (account(), 0..NUM_COLLECTIONS).prop_map(|(sender, collection)| {
+                GeneratedTransaction::MintToken { sender, collection }
+            }),
+        ]
+        .boxed()
+    }
+
+    /// Generates a block of between 1 and `max_len` transactions.
+    pub fn arbitrary_block(
+        num_accounts: usize,
+        max_len: usize,
+    ) -> BoxedStrategy<Vec<GeneratedTransaction>> {
+        proptest::collection::vec(Self::arbitrary(num_accounts), 1..=max_len).boxed()
+    }
+}
+
+/// A harness which runs generated blocks both sequentially and in parallel, and panics if the
+/// write sets, events or gas of the two differ.
+pub struct BlockComparisonHarness {
+    pub harness: MoveHarness,
+    accounts: Vec<Account>,
+    /// The package of each account, built to be published at its address.
+    packages: Vec<BuiltPackage>,
+}
+
+impl BlockComparisonHarness {
+    pub fn new(num_accounts: usize) -> Self {
+        let executor =
+            FakeExecutor::from_head_genesis().set_executor_mode(ExecutorMode::BothComparison);
+        let mut harness = MoveHarness::new_with_executor(executor);
+
+        let mut accounts = vec![];
+        let mut packages = vec![];
+        for idx in 0..num_accounts {
+            let address = AccountAddress::from_hex_literal(&format!("0xa{:x}", idx)).unwrap();
+            accounts.push(harness.new_account_at(address));
+
+            let mut builder = PackageBuilder::new(&format!("Package{}", idx));
+            builder.add_source(
+                "m",
+                &format!(
+                    "module {}::m {{ public entry fun f() {{}} }}",
+                    address.to_hex_literal()
+                ),
+            );
+            let dir = builder
+                .write_to_temp()
+                .expect("writing package must succeed");
+            packages.push(
+                build_package(dir.path().to_owned(), BuildOptions::default())
+                    .expect("building package must succeed"),
+            );
+        }
+
+        Self {
+            harness,
+            accounts,
+            packages,
+        }
+    }
+
+    pub fn create_transaction(&mut self, txn: &GeneratedTransaction) -> SignedTransaction {
+        match *txn {
+            GeneratedTransaction::Transfer {
+                sender,
+                receiver,
+                amount,
+            } => self.harness.create_transaction_payload(
+                &self.accounts[sender],
+                aptos_stdlib::aptos_account_transfer(*self.accounts[receiver].address(), amount),
+            ),
+            GeneratedTransaction::Publish { sender } => self.harness.create_publish_built_package(
+                &self.accounts[sender],
+                &self.packages[sender],
+                |_| {},
+            ),
+            GeneratedTransaction::CreateCollection { sender, collection } => {
+                self.harness.create_entry_function(
+                    &self.accounts[sender],
+                    str::parse("0x4::aptos_token::create_collection").unwrap(),
+                    vec![],
+                    vec![
+                        bcs::to_bytes("description").unwrap(),
+                        bcs::to_bytes(&1000u64).unwrap(),
+                        bcs::to_bytes(&collection_name(collection)).unwrap(),
+                        bcs::to_bytes("uri").unwrap(),
+                        bcs::to_bytes(&true).unwrap(),
+                        bcs::to_bytes(&true).unwrap(),
+                        bcs::to_bytes(&true).unwrap(),
+                        bcs::to_bytes(&true).unwrap(),
+                        bcs::to_bytes(&true).unwrap(),
+                        bcs::to_bytes(&true).unwrap(),
+                        bcs::to_bytes(&true).unwrap(),
+                        bcs::to_bytes(&true).unwrap(),
+                        bcs::to_bytes(&true).unwrap(),
+                        bcs::to_bytes(&0u64).unwrap(),
+                        bcs::to_bytes(&1u64).unwrap(),
+                    ],
+                )
+            },
+            GeneratedTransaction::MintToken { sender, collection } => {
+                self.harness.create_entry_function(
+                    &self.accounts[sender],
+                    str::parse("0x4::aptos_token::mint").unwrap(),
+                    vec![],
+                    vec![
+                        bcs::to_bytes(&collection_name(collection)).unwrap(),
+                        bcs::to_bytes("description").unwrap(),
+                        bcs::to_bytes("token").unwrap(),
+                        bcs::to_bytes("uri").unwrap(),
+                        bcs::to_bytes(&Vec::<String>::new()).unwrap(),
+                        bcs::to_bytes(&Vec::<String>::new()).unwrap(),
+                        bcs::to_bytes(&Vec::<Vec<u8>>::new()).unwrap(),
+                    ],
+                )
+            },
+        }
+    }
+
+    /// Runs the block and applies the outputs. Panics if sequential and parallel execution
+    /// diverge.
+    pub fn run_block(&mut self, txns: &[GeneratedTransaction]) -> Vec<TransactionOutput> {
+        let txns = txns
+            .iter()
+            .map(|txn| self.create_transaction(txn))
+            .collect();
+        self.harness.run_block_get_output(txns)
+    }
+}
+
+fn collection_name(collection: usize) -> String {
+    format!("collection {}", collection)
+}
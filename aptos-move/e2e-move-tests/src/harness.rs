@@ -3,7 +3,11 @@
 
 use crate::{assert_success, build_package, AptosPackageHooks};
 use aptos_cached_packages::aptos_stdlib;
-use aptos_framework::{natives::code::PackageMetadata, BuildOptions, BuiltPackage};
+use aptos_framework::{
+    chunked_publish::{chunk_package_and_create_payloads, PublishType},
+    natives::code::PackageMetadata,
+    BuildOptions, BuiltPackage,
+};
 use aptos_gas_profiling::TransactionGasLog;
 use aptos_gas_schedule::{
     AptosGasParameters, FromOnChainGasSchedule, InitialGasSchedule, ToOnChainGasSchedule,
@@ -13,6 +17,7 @@ use aptos_language_e2e_tests::{
     executor::FakeExecutor,
 };
 use aptos_types::{
+    access_path::Path as AccessPath,
     account_address::AccountAddress,
     account_config::{
         fungible_store::FungibleStoreResource, object::ObjectGroupResource, AccountResource,
@@ -20,16 +25,19 @@ use aptos_types::{
     },
     chain_id::ChainId,
     contract_event::ContractEvent,
+    fee_statement::FeeStatement,
     move_utils::MemberId,
-    on_chain_config::{FeatureFlag, GasScheduleV2, OnChainConfig},
+    on_chain_config::{ConfigurationResource, FeatureFlag, Features, GasScheduleV2, OnChainConfig},
+    randomness::PerBlockRandomness,
     state_store::{
-        state_key::StateKey,
+        state_key::{inner::StateKeyInner, StateKey},
         state_value::{StateValue, StateValueMetadata},
+        table::TableHandle,
     },
     transaction::{
-        EntryFunction, Multisig, MultisigTransactionPayload, Script, SignedTransaction,
-        TransactionArgument, TransactionOutput, TransactionPayload, TransactionStatus,
-        ViewFunctionOutput,
+        EntryFunction, ExecutionStatus, Multisig, MultisigTransactionPayload, Script,
+        SignedTransaction, TransactionArgument, TransactionOutput, TransactionPayload,
+        TransactionStatus, ViewFunctionOutput,
     },
 };
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
@@ -78,6 +86,8 @@ pub struct MoveHarness {
     pub executor: FakeExecutor,
     /// The last counted transaction sequence number, by account address.
     txn_seq_no: BTreeMap<AccountAddress, u64>,
+    /// The fee statement of the last transaction that was run, if it was kept.
+    last_fee_statement: Option<FeeStatement>,
 
     pub default_gas_unit_price: u64,
     pub max_gas_per_txn: u64,
@@ -99,6 +109,7 @@ impl MoveHarness {
         Self {
             executor: FakeExecutor::from_head_genesis(),
             txn_seq_no: BTreeMap::default(),
+            last_fee_statement: None,
             default_gas_unit_price: DEFAULT_GAS_UNIT_PRICE,
             max_gas_per_txn: Self::DEFAULT_MAX_GAS_PER_TXN,
         }
@@ -109,6 +120,7 @@ impl MoveHarness {
         Self {
             executor,
             txn_seq_no: BTreeMap::default(),
+            last_fee_statement: None,
             default_gas_unit_price: DEFAULT_GAS_UNIT_PRICE,
             max_gas_per_txn: Self::DEFAULT_MAX_GAS_PER_TXN,
         }
@@ -119,6 +131,7 @@ impl MoveHarness {
         Self {
             executor: FakeExecutor::from_head_genesis_with_count(count),
             txn_seq_no: BTreeMap::default(),
+            last_fee_statement: None,
             default_gas_unit_price: DEFAULT_GAS_UNIT_PRICE,
             max_gas_per_txn: Self::DEFAULT_MAX_GAS_PER_TXN,
         }
@@ -129,6 +142,7 @@ impl MoveHarness {
         Self {
             executor: FakeExecutor::from_testnet_genesis(),
             txn_seq_no: BTreeMap::default(),
+            last_fee_statement: None,
             default_gas_unit_price: DEFAULT_GAS_UNIT_PRICE,
             max_gas_per_txn: Self::DEFAULT_MAX_GAS_PER_TXN,
         }
@@ -148,6 +162,7 @@ impl MoveHarness {
         Self {
             executor: FakeExecutor::from_mainnet_genesis(),
             txn_seq_no: BTreeMap::default(),
+            last_fee_statement: None,
             default_gas_unit_price: DEFAULT_GAS_UNIT_PRICE,
             max_gas_per_txn: Self::DEFAULT_MAX_GAS_PER_TXN,
         }
@@ -195,6 +210,7 @@ impl MoveHarness {
     /// Runs a signed transaction. On success, applies the write set.
     pub fn run_raw(&mut self, txn: SignedTransaction) -> TransactionOutput {
         let mut output = self.executor.execute_transaction(txn);
+        self.record_fee_statement(&output);
         if matches!(output.status(), TransactionStatus::Keep(_)) {
             self.executor.apply_write_set(output.write_set());
             self.executor.append_events(output.events().to_vec());
@@ -214,6 +230,7 @@ impl MoveHarness {
         txn: SignedTransaction,
     ) -> (TransactionStatus, Vec<ContractEvent>) {
         let output = self.executor.execute_transaction(txn);
+        self.record_fee_statement(&output);
         if matches!(output.status(), TransactionStatus::Keep(_)) {
             self.executor.apply_write_set(output.write_set());
         }
@@ -224,6 +241,7 @@ impl MoveHarness {
     pub fn run_block(&mut self, txn_block: Vec<SignedTransaction>) -> Vec<TransactionStatus> {
         let mut result = vec![];
         for output in self.executor.execute_block(txn_block).unwrap() {
+            self.record_fee_statement(&output);
             if matches!(output.status(), TransactionStatus::Keep(_)) {
                 self.executor.apply_write_set(output.write_set());
             }
@@ -239,6 +257,7 @@ impl MoveHarness {
     ) -> Vec<TransactionOutput> {
         let mut result = assert_ok!(self.executor.execute_block(txn_block));
         for output in &mut result {
+            self.record_fee_statement(output);
             if matches!(output.status(), TransactionStatus::Keep(_)) {
                 self.executor.apply_write_set(output.write_set());
             }
@@ -247,6 +266,18 @@ impl MoveHarness {
         result
     }
 
+    /// Returns the fee breakdown (execution gas, io gas, storage fee and refund) of the last
+    /// transaction that was run. Returns `None` if it was discarded.
+    pub fn last_fee_statement(&self) -> Option<FeeStatement> {
+        self.last_fee_statement
+    }
+
+    fn record_fee_statement(&mut self, output: &TransactionOutput) {
+        self.last_fee_statement = output
+            .try_extract_fee_statement()
+            .expect("fee statement must deserialize");
+    }
+
     /// Creates a transaction without signing it
     pub fn create_transaction_without_sign(
         &mut self,
@@ -276,6 +307,33 @@ impl MoveHarness {
             .sign()
     }
 
+    /// Creates a multi-agent transaction, signed by the sender and the secondary signers.
+    pub fn create_multi_agent_transaction_payload(
+        &mut self,
+        account: &Account,
+        secondary_signers: &[Account],
+        payload: TransactionPayload,
+    ) -> SignedTransaction {
+        self.create_transaction_without_sign(account, payload)
+            .secondary_signers(secondary_signers.to_vec())
+            .sign_multi_agent()
+    }
+
+    /// Creates a transaction whose gas is paid by `fee_payer`, signed by the sender, the
+    /// secondary signers and the fee payer.
+    pub fn create_fee_payer_transaction_payload(
+        &mut self,
+        account: &Account,
+        secondary_signers: &[Account],
+        fee_payer: &Account,
+        payload: TransactionPayload,
+    ) -> SignedTransaction {
+        self.create_transaction_without_sign(account, payload)
+            .secondary_signers(secondary_signers.to_vec())
+            .fee_payer(fee_payer.clone())
+            .sign_fee_payer()
+    }
+
     /// Creates a transaction to be sent to mainnet
     pub fn create_transaction_payload_mainnet(
         &mut self,
@@ -358,6 +416,59 @@ impl MoveHarness {
         )
     }
 
+    /// Creates a multi-agent transaction which runs the specified entry point `fun`. Arguments
+    /// need to be provided in bcs-serialized form.
+    pub fn create_multi_agent_entry_function(
+        &mut self,
+        account: &Account,
+        secondary_signers: &[Account],
+        fun: MemberId,
+        ty_args: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+    ) -> SignedTransaction {
+        let MemberId {
+            module_id,
+            member_id: function_id,
+        } = fun;
+        self.create_multi_agent_transaction_payload(
+            account,
+            secondary_signers,
+            TransactionPayload::EntryFunction(EntryFunction::new(
+                module_id,
+                function_id,
+                ty_args,
+                args,
+            )),
+        )
+    }
+
+    /// Creates a transaction which runs the specified entry point `fun`, with the gas paid by
+    /// `fee_payer`. Arguments need to be provided in bcs-serialized form.
+    pub fn create_fee_payer_entry_function(
+        &mut self,
+        account: &Account,
+        fee_payer: &Account,
+        fun: MemberId,
+        ty_args: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+    ) -> SignedTransaction {
+        let MemberId {
+            module_id,
+            member_id: function_id,
+        } = fun;
+        self.create_fee_payer_transaction_payload(
+            account,
+            &[],
+            fee_payer,
+            TransactionPayload::EntryFunction(EntryFunction::new(
+                module_id,
+                function_id,
+                ty_args,
+                args,
+            )),
+        )
+    }
+
     /// Create a multisig transaction.
     pub fn create_multisig(
         &mut self,
@@ -399,6 +510,35 @@ impl MoveHarness {
         self.run(txn)
     }
 
+    /// Run the specified entry point `fun` as a multi-agent transaction, signed by the sender
+    /// and the secondary signers. Arguments need to be provided in bcs-serialized form.
+    pub fn run_multi_agent_entry_function(
+        &mut self,
+        account: &Account,
+        secondary_signers: &[Account],
+        fun: MemberId,
+        ty_args: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+    ) -> TransactionStatus {
+        let txn =
+            self.create_multi_agent_entry_function(account, secondary_signers, fun, ty_args, args);
+        self.run(txn)
+    }
+
+    /// Run the specified entry point `fun` with the gas paid by `fee_payer`. Arguments need to
+    /// be provided in bcs-serialized form.
+    pub fn run_fee_payer_entry_function(
+        &mut self,
+        account: &Account,
+        fee_payer: &Account,
+        fun: MemberId,
+        ty_args: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+    ) -> TransactionStatus {
+        let txn = self.create_fee_payer_entry_function(account, fee_payer, fun, ty_args, args);
+        self.run(txn)
+    }
+
     /// Run the multisig transaction.
     pub fn run_multisig(
         &mut self,
@@ -463,10 +603,22 @@ impl MoveHarness {
             .extract_metadata()
             .expect("extracting package metadata must succeed");
         patch_metadata(&mut metadata);
+        self.create_object_code_deployment_from_metadata(account, &metadata, code)
+    }
+
+    /// Creates a transaction which publishes the given package metadata and module bytes to an
+    /// object, on behalf of the given account. Unlike the other variants, this doesn't build a
+    /// package, so the metadata and code can be arbitrary, e.g. inconsistent with each other.
+    pub fn create_object_code_deployment_from_metadata(
+        &mut self,
+        account: &Account,
+        metadata: &PackageMetadata,
+        code: Vec<Vec<u8>>,
+    ) -> SignedTransaction {
         self.create_transaction_payload(
             account,
             aptos_stdlib::object_code_deployment_publish(
-                bcs::to_bytes(&metadata).expect("PackageMetadata has BCS"),
+                bcs::to_bytes(metadata).expect("PackageMetadata has BCS"),
                 code,
             ),
         )
@@ -591,6 +743,54 @@ impl MoveHarness {
         self.run(txn)
     }
 
+    /// Runs the transactions which publish the Move Package to an object in chunks, through the
+    /// `large_packages` module published at the given address. Stops at the first transaction
+    /// which fails, and returns its status, or the status of the last one.
+    pub fn object_code_deployment_package_in_chunks(
+        &mut self,
+        account: &Account,
+        path: &Path,
+        options: BuildOptions,
+        large_packages_module_address: AccountAddress,
+    ) -> TransactionStatus {
+        let package =
+            build_package(path.to_owned(), options).expect("building package must succeed");
+        let metadata = package
+            .extract_metadata()
+            .expect("extracting package metadata must succeed");
+        let payloads = chunk_package_and_create_payloads(
+            bcs::to_bytes(&metadata).expect("PackageMetadata has BCS"),
+            package.extract_code(),
+            PublishType::ObjectDeploy,
+            large_packages_module_address,
+        );
+
+        let mut status = None;
+        for payload in payloads {
+            let txn_status = self.run_transaction_payload(account, payload);
+            let success = matches!(
+                txn_status,
+                TransactionStatus::Keep(ExecutionStatus::Success)
+            );
+            status = Some(txn_status);
+            if !success {
+                break;
+            }
+        }
+        status.expect("there is at least one chunk")
+    }
+
+    /// Runs transaction which publishes the given package metadata and module bytes to an object.
+    pub fn object_code_deployment_from_metadata(
+        &mut self,
+        account: &Account,
+        metadata: &PackageMetadata,
+        code: Vec<Vec<u8>>,
+    ) -> TransactionStatus {
+        let txn = self.create_object_code_deployment_from_metadata(account, metadata, code);
+        self.run(txn)
+    }
+
     /// Creates a transaction which publishes the passed already-built Move Package to an object,
     /// on behalf of the given account.
     ///
@@ -671,11 +871,79 @@ impl MoveHarness {
             .set_block_time(current_time + seconds * 1_000_000)
     }
 
+    /// Advances the block time to `timestamp_secs`, which must not be in the past.
+    pub fn fast_forward_to(&mut self, timestamp_secs: u64) {
+        let current_time_secs = self.current_time_secs();
+        assert!(
+            timestamp_secs >= current_time_secs,
+            "cannot go back in time from {} to {}",
+            current_time_secs,
+            timestamp_secs
+        );
+        self.fast_forward(timestamp_secs - current_time_secs)
+    }
+
+    /// Returns the block time in seconds.
+    pub fn current_time_secs(&mut self) -> u64 {
+        self.executor.get_block_time_seconds()
+    }
+
+    /// Starts a new block after the epoch interval has passed, so that the block prologue
+    /// reconfigures as it would on chain.
     pub fn new_epoch(&mut self) {
         self.fast_forward(7200);
         self.executor.new_block()
     }
 
+    /// Ends the epoch immediately, applying the on-chain configs buffered for the next
+    /// epoch (e.g., by `change_features_for_next_epoch`).
+    pub fn force_end_epoch(&mut self) {
+        self.executor
+            .exec("aptos_governance", "force_end_epoch", vec![], vec![
+                MoveValue::Signer(CORE_CODE_ADDRESS)
+                    .simple_serialize()
+                    .unwrap(),
+            ]);
+    }
+
+    /// Returns the current epoch.
+    pub fn current_epoch(&self) -> u64 {
+        self.read_resource::<ConfigurationResource>(
+            &CORE_CODE_ADDRESS,
+            <ConfigurationResource as MoveStructType>::struct_tag(),
+        )
+        .expect("configuration must exist")
+        .epoch()
+    }
+
+    /// Returns the time of the last reconfiguration in microseconds.
+    pub fn last_reconfiguration_time(&self) -> u64 {
+        self.read_resource::<ConfigurationResource>(
+            &CORE_CODE_ADDRESS,
+            <ConfigurationResource as MoveStructType>::struct_tag(),
+        )
+        .expect("configuration must exist")
+        .last_reconfiguration_time()
+    }
+
+    /// Sets the randomness seed of the current block and of the ones started from now on, so
+    /// that the randomness APIs return reproducible results.
+    pub fn set_randomness_seed(&mut self, seed: Vec<u8>) {
+        self.executor.set_randomness_seed(Some(seed.clone()));
+        let mut randomness = self
+            .read_resource::<PerBlockRandomness>(
+                &CORE_CODE_ADDRESS,
+                PerBlockRandomness::struct_tag(),
+            )
+            .expect("randomness must be initialized");
+        randomness.seed = Some(seed);
+        self.set_resource(
+            CORE_CODE_ADDRESS,
+            PerBlockRandomness::struct_tag(),
+            &randomness,
+        );
+    }
+
     pub fn new_block_with_metadata(
         &mut self,
         proposer: AccountAddress,
@@ -762,6 +1030,77 @@ impl MoveHarness {
             .map(|data| bcs::from_bytes(&data).unwrap())
     }
 
+    /// Reads all resources of the resource group, keyed by their struct tags. Returns an empty
+    /// map if the group doesn't exist.
+    pub fn read_all_resources_in_group(
+        &self,
+        addr: &AccountAddress,
+        resource_group: StructTag,
+    ) -> BTreeMap<StructTag, Vec<u8>> {
+        self.read_resource_group(addr, resource_group)
+            .unwrap_or_default()
+    }
+
+    /// Iterates over the raw data of all resources stored at the address, including the members
+    /// of its resource groups. The order is unspecified.
+    pub fn iter_resources(
+        &self,
+        addr: AccountAddress,
+    ) -> impl Iterator<Item = (StructTag, Vec<u8>)> + '_ {
+        self.executor
+            .data_store()
+            .iter()
+            .filter_map(move |(state_key, state_value)| match state_key.inner() {
+                StateKeyInner::AccessPath(access_path) if access_path.address == addr => {
+                    Some((access_path.get_path(), state_value.bytes().to_vec()))
+                },
+                _ => None,
+            })
+            .flat_map(|(path, data)| match path {
+                AccessPath::Resource(struct_tag) => vec![(struct_tag, data)],
+                AccessPath::ResourceGroup(_) => {
+                    bcs::from_bytes::<BTreeMap<StructTag, Vec<u8>>>(&data)
+                        .expect("resource group must deserialize")
+                        .into_iter()
+                        .collect()
+                },
+                AccessPath::Code(_) => vec![],
+            })
+    }
+
+    /// Reads the resources at the address whose struct tags match `filter` as `T`, e.g. all
+    /// instances of a generic resource. The order is unspecified.
+    pub fn read_resources<T: DeserializeOwned>(
+        &self,
+        addr: &AccountAddress,
+        filter: impl Fn(&StructTag) -> bool,
+    ) -> Vec<(StructTag, T)> {
+        self.iter_resources(*addr)
+            .filter(|(struct_tag, _)| filter(struct_tag))
+            .map(|(struct_tag, data)| {
+                let resource = bcs::from_bytes::<T>(&data).expect(
+                    "serialization expected to succeed (Rust type incompatible with Move type?)",
+                );
+                (struct_tag, resource)
+            })
+            .collect()
+    }
+
+    /// Reads the value of a table item. The key is serialized with BCS.
+    pub fn read_table_item<K: Serialize, V: DeserializeOwned>(
+        &self,
+        handle: &TableHandle,
+        key: &K,
+    ) -> Option<V> {
+        let key = bcs::to_bytes(key).expect("table key must serialize");
+        let data = self.read_state_value_bytes(&StateKey::table_item(handle, &key))?;
+        Some(
+            bcs::from_bytes::<V>(&data).expect(
+                "serialization expected to succeed (Rust type incompatible with Move type?)",
+            ),
+        )
+    }
+
     pub fn read_resource_from_resource_group<T: DeserializeOwned>(
         &self,
         addr: &AccountAddress,
@@ -823,6 +1162,41 @@ impl MoveHarness {
             ]);
     }
 
+    /// Buffers feature changes for the next epoch, the way governance proposals change
+    /// features. They take effect after `force_end_epoch`.
+    pub fn change_features_for_next_epoch(
+        &mut self,
+        enabled: Vec<FeatureFlag>,
+        disabled: Vec<FeatureFlag>,
+    ) {
+        let enabled = enabled.into_iter().map(|f| f as u64).collect::<Vec<_>>();
+        let disabled = disabled.into_iter().map(|f| f as u64).collect::<Vec<_>>();
+        self.executor.exec(
+            "features",
+            "change_feature_flags_for_next_epoch",
+            vec![],
+            vec![
+                MoveValue::Signer(CORE_CODE_ADDRESS)
+                    .simple_serialize()
+                    .unwrap(),
+                bcs::to_bytes(&enabled).unwrap(),
+                bcs::to_bytes(&disabled).unwrap(),
+            ],
+        );
+    }
+
+    /// Changes features and reconfigures, so that they take effect in a new epoch.
+    pub fn toggle_features(&mut self, enabled: Vec<FeatureFlag>, disabled: Vec<FeatureFlag>) {
+        self.change_features_for_next_epoch(enabled, disabled);
+        self.force_end_epoch();
+    }
+
+    /// Checks whether a feature is currently enabled.
+    pub fn is_feature_enabled(&self, feature: FeatureFlag) -> bool {
+        self.read_resource::<Features>(&CORE_CODE_ADDRESS, Features::struct_tag())
+            .map_or(false, |features| features.is_enabled(feature))
+    }
+
     fn override_one_gas_param(&mut self, param: &str, param_value: u64) {
         // TODO: The AptosGasParameters::zeros() schedule doesn't do what we want, so
         // explicitly manipulating gas entries. Wasn't obvious from the gas code how to
@@ -844,20 +1218,16 @@ impl MoveHarness {
             entries,
         };
         let schedule_bytes = bcs::to_bytes(&gas_schedule).expect("bcs");
-        let core_signer_arg = MoveValue::Signer(AccountAddress::ONE)
-            .simple_serialize()
-            .unwrap();
         self.executor
             .exec("gas_schedule", "set_for_next_epoch", vec![], vec![
-                core_signer_arg.clone(),
+                MoveValue::Signer(AccountAddress::ONE)
+                    .simple_serialize()
+                    .unwrap(),
                 MoveValue::vector_u8(schedule_bytes)
                     .simple_serialize()
                     .unwrap(),
             ]);
-        self.executor
-            .exec("aptos_governance", "force_end_epoch", vec![], vec![
-                core_signer_arg,
-            ]);
+        self.force_end_epoch();
     }
 
     pub fn modify_gas_scaling(&mut self, gas_scaling_factor: u64) {
@@ -1110,6 +1480,25 @@ macro_rules! assert_success {
     }};
 }
 
+/// Asserts that an amount of gas (or fee) is within `tolerance_percent` percent of the expected
+/// amount, e.g. `assert_gas_within!(fee_statement.gas_used(), 1_000, 5)`.
+#[macro_export]
+macro_rules! assert_gas_within {
+    ($actual:expr, $expected:expr, $tolerance_percent:expr $(,)?) => {{
+        let actual: u64 = $actual;
+        let expected: u64 = $expected;
+        let tolerance = (expected as u128 * $tolerance_percent as u128 / 100) as u64;
+        assert!(
+            actual >= expected.saturating_sub(tolerance)
+                && actual <= expected.saturating_add(tolerance),
+            "gas {} is not within {}% of {}",
+            actual,
+            $tolerance_percent,
+            expected,
+        );
+    }};
+}
+
 /// Helper to assert transaction resulted in OUT_OF_GAS error
 #[macro_export]
 macro_rules! assert_out_of_gas {
@@ -4,6 +4,7 @@
 pub mod aggregator;
 pub mod aggregator_v2;
 pub mod aptos_governance;
+pub mod block_generator;
 pub mod harness;
 pub mod resource_groups;
 pub mod stake;
@@ -0,0 +1,36 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::block_generator::{BlockComparisonHarness, GeneratedTransaction};
+use aptos_types::transaction::TransactionStatus;
+use proptest::prelude::*;
+
+const NUM_ACCOUNTS: usize = 3;
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        // Cases are expensive, few cases is enough.
+        cases: 5,
+        .. ProptestConfig::default()
+    })]
+
+    /// Sequential and parallel execution are compared by the executor of the harness, for
+    /// every block.
+    #[test]
+    fn test_sequential_and_parallel_execution_match(
+        blocks in proptest::collection::vec(
+            GeneratedTransaction::arbitrary_block(NUM_ACCOUNTS, 20),
+            1..4,
+        ),
+    ) {
+        let mut h = BlockComparisonHarness::new(NUM_ACCOUNTS);
+        for block in blocks {
+            let outputs = h.run_block(&block);
+            prop_assert_eq!(outputs.len(), block.len());
+            // Generated transactions may abort, but are always well-formed
+            for output in outputs {
+                prop_assert!(matches!(output.status(), TransactionStatus::Keep(_)));
+            }
+        }
+    }
+}
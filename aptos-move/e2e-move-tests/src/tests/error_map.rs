@@ -5,10 +5,7 @@ extern crate core;
 
 use crate::{assert_success, tests::common, MoveHarness};
 use aptos_framework::BuildOptions;
-use aptos_types::{
-    account_address::AccountAddress,
-    transaction::{ExecutionStatus, TransactionStatus},
-};
+use aptos_types::{account_address::AccountAddress, transaction::TransactionStatus};
 use move_core_types::value::MoveValue;
 use serde::{Deserialize, Serialize};
 
@@ -60,15 +57,14 @@ fn error_map() {
 }
 
 fn check_error(status: TransactionStatus, reason_name: &str, description: &str) {
-    match status {
-        TransactionStatus::Keep(ExecutionStatus::MoveAbort { info, .. }) => {
-            if let Some(i) = info {
-                assert_eq!(i.reason_name, reason_name);
-                assert_eq!(i.description, description);
-            } else {
-                panic!("expected AbortInfo populated")
-            }
+    match status.abort_info() {
+        Some(i) => {
+            assert_eq!(i.reason_name, reason_name);
+            assert_eq!(i.description, description);
         },
-        _ => panic!("expected MoveAbort"),
+        None => panic!(
+            "expected MoveAbort with AbortInfo populated, got {:?}",
+            status
+        ),
     }
 }
@@ -1,8 +1,13 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{assert_abort, assert_success, assert_vm_status, tests::common, MoveHarness};
+use crate::{
+    assert_abort, assert_gas_within, assert_success, assert_vm_status, build_package,
+    tests::common, MoveHarness,
+};
+use aptos_cached_packages::aptos_stdlib;
 use aptos_framework::{
+    chunked_publish::{chunk_package_and_create_payloads, PublishType},
     natives::{
         code::{PackageRegistry, UpgradePolicy},
         object_code_deployment::ManagingRefs,
@@ -11,10 +16,8 @@ use aptos_framework::{
 };
 use aptos_language_e2e_tests::account::Account;
 use aptos_types::{
-    account_address::AccountAddress,
-    object_address::create_object_code_deployment_address,
-    on_chain_config::FeatureFlag,
-    transaction::{ExecutionStatus, TransactionStatus},
+    account_address::AccountAddress, object_address::create_object_code_deployment_address,
+    on_chain_config::FeatureFlag, transaction::TransactionStatus,
 };
 use move_core_types::{parser::parse_struct_tag, vm_status::StatusCode};
 use rstest::rstest;
@@ -94,17 +97,9 @@ impl TestContext {
     }
 
     fn assert_feature_flag_error(&self, status: TransactionStatus, err: &str) {
-        if let TransactionStatus::Keep(ExecutionStatus::MoveAbort { info, .. }) = status {
-            if let Some(abort_info) = info {
-                assert_eq!(abort_info.reason_name, err);
-            } else {
-                panic!("Expected AbortInfo, but got None");
-            }
-        } else {
-            panic!(
-                "Expected TransactionStatus::Keep with ExecutionStatus::MoveAbort, but got {:?}",
-                status
-            );
+        match status.abort_info() {
+            Some(abort_info) => assert_eq!(abort_info.reason_name, err),
+            None => panic!("Expected a Move abort with AbortInfo, but got {:?}", status),
         }
     }
 
@@ -161,6 +156,23 @@ fn object_code_deployment_publish_package(enabled: Vec<FeatureFlag>, disabled: V
         // Verify the object created owns the `ManagingRefs`
         assert_eq!(code_object, ManagingRefs::new(context.object_address));
 
+        // The code object holds the object core and the refs, and the registry outside the group
+        let object_group = context.harness.read_all_resources_in_group(
+            &context.object_address,
+            parse_struct_tag("0x1::object::ObjectGroup").unwrap(),
+        );
+        assert_eq!(object_group.into_keys().collect::<Vec<_>>(), vec![
+            parse_struct_tag("0x1::object::ObjectCore").unwrap(),
+            parse_struct_tag("0x1::object_code_deployment::ManagingRefs").unwrap(),
+        ]);
+        let registries = context
+            .harness
+            .read_resources::<PackageRegistry>(&context.object_address, |struct_tag| {
+                struct_tag.module.as_str() == "code"
+            });
+        assert_eq!(registries.len(), 1);
+        assert_eq!(registries[0].1.packages[0].name, "test_package");
+
         let module_address = context.object_address.to_string();
         assert_success!(context.harness.run_entry_function(
             &context.account,
@@ -181,6 +193,148 @@ fn object_code_deployment_publish_package(enabled: Vec<FeatureFlag>, disabled: V
     }
 }
 
+/// Tests that enabling the feature through a reconfiguration only takes effect in the next epoch.
+#[test]
+fn object_code_deployment_enabled_in_next_epoch() {
+    let mut context = TestContext::new(None, Some(vec![FeatureFlag::OBJECT_CODE_DEPLOYMENT]));
+    let acc = context.account.clone();
+    let epoch = context.harness.current_epoch();
+
+    context
+        .harness
+        .change_features_for_next_epoch(vec![FeatureFlag::OBJECT_CODE_DEPLOYMENT], vec![]);
+    assert!(!context
+        .harness
+        .is_feature_enabled(FeatureFlag::OBJECT_CODE_DEPLOYMENT));
+    let status = context.execute_object_code_action(
+        &acc,
+        "object_code_deployment.data/pack_initial",
+        ObjectCodeAction::Deploy,
+    );
+    context.assert_feature_flag_error(status, EOBJECT_CODE_DEPLOYMENT_NOT_SUPPORTED);
+
+    context.harness.force_end_epoch();
+    assert_eq!(context.harness.current_epoch(), epoch + 1);
+    assert!(context
+        .harness
+        .is_feature_enabled(FeatureFlag::OBJECT_CODE_DEPLOYMENT));
+
+    // The failed deployment used up a sequence number, so the object address changes
+    let sequence_number = context.harness.sequence_number(acc.address());
+    context.object_address =
+        create_object_code_deployment_address(*acc.address(), sequence_number + 1);
+    assert_success!(context.execute_object_code_action(
+        &acc,
+        "object_code_deployment.data/pack_initial",
+        ObjectCodeAction::Deploy,
+    ));
+}
+
+/// Tests deploying a package with the gas paid by another account.
+#[test]
+fn object_code_deployment_publish_package_with_fee_payer() {
+    let mut context = TestContext::new(None, None);
+    let acc = context.account.clone();
+    let sponsor = context
+        .harness
+        .new_account_at(AccountAddress::from_hex_literal("0xbeef").unwrap());
+    let acc_balance = context.harness.read_aptos_balance(acc.address());
+    let sponsor_balance = context.harness.read_aptos_balance(sponsor.address());
+
+    let mut options = BuildOptions::default();
+    options
+        .named_addresses
+        .insert(MODULE_ADDRESS_NAME.to_string(), context.object_address);
+    let package = build_package(
+        common::test_dir_path("object_code_deployment.data/pack_initial"),
+        options,
+    )
+    .expect("building package must succeed");
+    let metadata = package
+        .extract_metadata()
+        .expect("extracting package metadata must succeed");
+    let txn = context.harness.create_fee_payer_transaction_payload(
+        &acc,
+        &[],
+        &sponsor,
+        aptos_stdlib::object_code_deployment_publish(
+            bcs::to_bytes(&metadata).expect("PackageMetadata has BCS"),
+            package.extract_code(),
+        ),
+    );
+    assert_success!(context.harness.run(txn));
+
+    let registry = context
+        .read_resource::<PackageRegistry>(&context.object_address, PACKAGE_REGISTRY_ACCESS_PATH)
+        .unwrap();
+    assert_eq!(registry.packages.len(), 1);
+    assert_eq!(
+        context.harness.read_aptos_balance(acc.address()),
+        acc_balance
+    );
+    assert!(context.harness.read_aptos_balance(sponsor.address()) < sponsor_balance);
+}
+
+/// Guards the cost of deployments: deploying the same package again should cost about the same.
+#[test]
+fn object_code_deployment_publish_package_fees() {
+    let mut context = TestContext::new(None, None);
+    let acc = context.account.clone();
+
+    assert_success!(context.execute_object_code_action(
+        &acc,
+        "object_code_deployment.data/pack_initial",
+        ObjectCodeAction::Deploy,
+    ));
+    let first = context.harness.last_fee_statement().unwrap();
+    assert!(first.storage_fee_used() > 0);
+    assert_eq!(first.storage_fee_refund(), 0);
+    assert!(first.execution_gas_used() + first.io_gas_used() < first.gas_used());
+
+    let sequence_number = context.harness.sequence_number(acc.address());
+    context.object_address =
+        create_object_code_deployment_address(*acc.address(), sequence_number + 1);
+    assert_success!(context.execute_object_code_action(
+        &acc,
+        "object_code_deployment.data/pack_initial",
+        ObjectCodeAction::Deploy,
+    ));
+    let second = context.harness.last_fee_statement().unwrap();
+    assert_gas_within!(second.gas_used(), first.gas_used(), 5);
+    assert_gas_within!(second.storage_fee_used(), first.storage_fee_used(), 5);
+}
+
+/// Tests that deploying modules which don't match the package metadata fails.
+#[test]
+fn object_code_deployment_publish_fail_metadata_mismatch() {
+    let mut context = TestContext::new(None, None);
+    let acc = context.account.clone();
+
+    let mut options = BuildOptions::default();
+    options
+        .named_addresses
+        .insert(MODULE_ADDRESS_NAME.to_string(), context.object_address);
+    let package = build_package(
+        common::test_dir_path("object_code_deployment.data/pack_initial"),
+        options,
+    )
+    .expect("building package must succeed");
+    let mut metadata = package
+        .extract_metadata()
+        .expect("extracting package metadata must succeed");
+    metadata.modules[0].name = "not_test".to_string();
+
+    let status = context.harness.object_code_deployment_from_metadata(
+        &acc,
+        &metadata,
+        package.extract_code(),
+    );
+    assert_vm_status!(status, StatusCode::CONSTRAINT_NOT_SATISFIED);
+    assert!(context
+        .read_resource::<PackageRegistry>(&context.object_address, PACKAGE_REGISTRY_ACCESS_PATH)
+        .is_none());
+}
+
 /// Tests the `upgrade` object code deployment function after `publish`ing a package prior calling.
 #[test]
 fn object_code_deployment_upgrade_success_compat() {
@@ -372,3 +526,72 @@ fn freeze_code_object_fail_when_package_registry_does_not_exist() {
     let status = context.execute_object_code_action(&acc, "", ObjectCodeAction::Freeze);
     assert_abort!(status, _);
 }
+
+/// Tests deploying a package which is too large for a single transaction to an object, by
+/// staging it in chunks through the `large_packages` module.
+#[test]
+fn object_code_deployment_publish_large_package_in_chunks() {
+    let mut h = MoveHarness::new();
+    let large_packages_acc =
+        h.new_account_at(AccountAddress::from_hex_literal("0x0a11ce").unwrap());
+    let mut options = BuildOptions::default();
+    options
+        .named_addresses
+        .insert("large_packages".to_string(), *large_packages_acc.address());
+    assert_success!(h.publish_package_with_options(
+        &large_packages_acc,
+        &common::test_dir_path("../../../move-examples/large_packages"),
+        options,
+    ));
+
+    // The object address is derived from the sequence number of the last transaction, so the
+    // number of chunks is needed upfront. It doesn't depend on the address the package is built
+    // with, as addresses are of a fixed size.
+    let path = common::test_dir_path("../../../move-examples/large_packages/large_package_example");
+    let build_options = |address: AccountAddress| {
+        let mut options = BuildOptions::default();
+        options
+            .named_addresses
+            .insert("large_package_example".to_string(), address);
+        options
+    };
+    let package = build_package(path.clone(), build_options(AccountAddress::ZERO)).unwrap();
+    let num_chunks = chunk_package_and_create_payloads(
+        bcs::to_bytes(&package.extract_metadata().unwrap()).unwrap(),
+        package.extract_code(),
+        PublishType::ObjectDeploy,
+        *large_packages_acc.address(),
+    )
+    .len() as u64;
+    assert!(num_chunks > 1);
+
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    let object_address = create_object_code_deployment_address(
+        *acc.address(),
+        h.sequence_number(acc.address()) + num_chunks,
+    );
+    assert_success!(h.object_code_deployment_package_in_chunks(
+        &acc,
+        &path,
+        build_options(object_address),
+        *large_packages_acc.address(),
+    ));
+
+    let registry = h
+        .read_resource::<PackageRegistry>(
+            &object_address,
+            parse_struct_tag(PACKAGE_REGISTRY_ACCESS_PATH).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(registry.packages.len(), 1);
+    assert_eq!(registry.packages[0].name, "LargePackageExample");
+    // The staging area is cleaned up after publishing.
+    assert!(!h.exists_resource(
+        acc.address(),
+        parse_struct_tag(&format!(
+            "{}::large_packages::StagingArea",
+            large_packages_acc.address()
+        ))
+        .unwrap(),
+    ));
+}
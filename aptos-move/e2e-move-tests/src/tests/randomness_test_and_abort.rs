@@ -6,8 +6,6 @@ use aptos_framework::BuiltPackage;
 use aptos_language_e2e_tests::account::{Account, TransactionBuilder};
 use aptos_types::{
     account_address::AccountAddress,
-    on_chain_config::OnChainConfig,
-    randomness::PerBlockRandomness,
     transaction::{ExecutionStatus, Script, TransactionStatus},
 };
 use claims::assert_ok;
@@ -35,7 +33,7 @@ fn test_and_abort_defense_is_sound_and_correct() {
     }
 
     // The randomness module is initialized, but the randomness seed is not set.
-    set_randomness_seed(&mut h);
+    h.set_randomness_seed(vec![0; 32]);
 
     h.set_default_gas_unit_price(100);
     h.set_max_gas_per_txn(10000); // Should match the default required gas amount.
@@ -83,7 +81,7 @@ fn test_unbiasable_annotation() {
 
     deploy_code(AccountAddress::ONE, "randomness.data/pack", &mut h)
         .expect("building package must succeed");
-    set_randomness_seed(&mut h);
+    h.set_randomness_seed(vec![0; 32]);
 
     h.set_default_gas_unit_price(100);
     h.set_max_gas_per_txn(10000); // Should match the default required gas amount.
@@ -123,16 +121,43 @@ fn test_unbiasable_annotation() {
     }
 }
 
-fn set_randomness_seed(h: &mut MoveHarness) {
-    let fx = h.aptos_framework_account();
-    let mut pbr = h
-        .read_resource::<PerBlockRandomness>(fx.address(), PerBlockRandomness::struct_tag())
-        .unwrap();
-    assert!(pbr.seed.is_none());
+#[test]
+fn test_randomness_is_reproducible_with_seed() {
+    let lucky_money_split = |seed: Vec<u8>| {
+        let mut h = MoveHarness::new();
+        deploy_code(AccountAddress::ONE, "randomness.data/pack", &mut h)
+            .expect("building package must succeed");
+        h.set_randomness_seed(seed);
+        // The seed is carried over to new blocks.
+        h.executor.new_block();
+
+        h.set_default_gas_unit_price(100);
+        h.set_max_gas_per_txn(10000);
+
+        let sender = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+        let recipient_0 = *h
+            .new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap())
+            .address();
+        let recipient_1 = *h
+            .new_account_at(AccountAddress::from_hex_literal("0xca7").unwrap())
+            .address();
+        let balance_before = h.read_aptos_balance(&recipient_0);
+        let status = h.run_entry_function(
+            &sender,
+            str::parse("0x1::test::transfer_lucky_money").unwrap(),
+            vec![],
+            vec![
+                bcs::to_bytes(&1000u64).unwrap(),
+                bcs::to_bytes(&recipient_0).unwrap(),
+                bcs::to_bytes(&recipient_1).unwrap(),
+            ],
+        );
+        assert_success!(status);
+        h.read_aptos_balance(&recipient_0) - balance_before
+    };
 
-    pbr.seed = Some((0..32).map(|_| 0u8).collect::<Vec<u8>>());
-    assert_eq!(pbr.seed.as_ref().unwrap().len(), 32);
-    h.set_resource(*fx.address(), PerBlockRandomness::struct_tag(), &pbr);
+    let seed = (0..32).collect::<Vec<u8>>();
+    assert_eq!(lucky_money_split(seed.clone()), lucky_money_split(seed));
 }
 
 fn deploy_code(
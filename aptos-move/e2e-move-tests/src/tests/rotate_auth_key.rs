@@ -16,7 +16,7 @@ use aptos_language_e2e_tests::account::Account;
 use aptos_types::{
     account_address::AccountAddress,
     account_config::{AccountResource, RotationProofChallenge, CORE_CODE_ADDRESS},
-    state_store::{state_key::StateKey, table::TableHandle},
+    state_store::table::TableHandle,
     transaction::{authenticator::AuthenticationKey, TransactionStatus},
 };
 use move_core_types::parser::parse_struct_tag;
@@ -230,10 +230,14 @@ pub fn verify_originating_address(
             parse_struct_tag("0x1::account::OriginatingAddress").unwrap(),
         )
         .unwrap();
-    let state_key = &StateKey::table_item(&originating_address_handle, &auth_key);
     // Verify that the value in the address redirection table is expected
-    let result = harness.read_state_value_bytes(state_key).unwrap();
-    assert_eq!(result, expected_address.to_vec());
+    let result = harness
+        .read_table_item::<AccountAddress, AccountAddress>(
+            &originating_address_handle,
+            &AccountAddress::from_bytes(&auth_key).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(result, expected_address);
 
     let account_resource = parse_struct_tag("0x1::account::Account").unwrap();
     let key_rotation_events = harness
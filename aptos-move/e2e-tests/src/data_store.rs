@@ -89,6 +89,11 @@ impl FakeDataStore {
         self.state_data.contains_key(state_key)
     }
 
+    /// Returns an iterator over the (key, value) pairs of this data store, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&StateKey, &StateValue)> {
+        self.state_data.iter()
+    }
+
     /// Deletes a key from this data store.
     ///
     /// Returns the previous data if the key was occupied.
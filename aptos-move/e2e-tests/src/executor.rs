@@ -34,6 +34,7 @@ use aptos_types::{
         BlockExecutorConfig, BlockExecutorConfigFromOnchain, BlockExecutorLocalConfig,
     },
     block_metadata::BlockMetadata,
+    block_metadata_ext::BlockMetadataExt,
     chain_id::ChainId,
     contract_event::ContractEvent,
     move_utils::MemberId,
@@ -41,6 +42,7 @@ use aptos_types::{
         AptosVersion, FeatureFlag, Features, OnChainConfig, TimedFeatureOverride,
         TimedFeaturesBuilder, ValidatorSet,
     },
+    randomness::{RandMetadata, Randomness},
     state_store::{state_key::StateKey, state_value::StateValue, StateView, TStateView},
     transaction::{
         signature_verified_transaction::{
@@ -134,6 +136,9 @@ pub struct FakeExecutor {
     features: Features,
     chain_id: u8,
     allow_block_executor_fallback: bool,
+    /// If set, the blocks carry this randomness seed, so that the randomness APIs return
+    /// reproducible results. Otherwise, the randomness APIs abort.
+    randomness_seed: Option<Vec<u8>>,
 }
 
 pub enum GasMeterType {
@@ -168,6 +173,7 @@ impl FakeExecutor {
             features: Features::default(),
             chain_id: chain_id.id(),
             allow_block_executor_fallback: true,
+            randomness_seed: None,
         };
         executor.apply_write_set(write_set);
         executor
@@ -191,6 +197,7 @@ impl FakeExecutor {
             features: Features::default(),
             chain_id: chain_id.id(),
             allow_block_executor_fallback: true,
+            randomness_seed: None,
         };
         executor.apply_write_set(write_set);
         executor
@@ -275,6 +282,7 @@ impl FakeExecutor {
             features: Features::default(),
             chain_id: ChainId::test().id(),
             allow_block_executor_fallback: true,
+            randomness_seed: None,
         }
     }
 
@@ -790,16 +798,33 @@ impl FakeExecutor {
             txns.into_iter().map(Transaction::UserTransaction).collect();
         let validator_set = ValidatorSet::fetch_config(&self.data_store.as_move_resolver())
             .expect("Unable to retrieve the validator set from storage");
-        let new_block_metadata = BlockMetadata::new(
-            HashValue::zero(),
-            0,
-            0,
-            proposer,
-            BitVec::with_num_bits(validator_set.num_validators() as u16).into(),
-            failed_proposer_indices,
-            self.block_time,
-        );
-        txn_block.insert(0, Transaction::BlockMetadata(new_block_metadata));
+        let previous_block_votes_bitvec =
+            BitVec::with_num_bits(validator_set.num_validators() as u16).into();
+        let new_block_metadata = match &self.randomness_seed {
+            Some(seed) => Transaction::BlockMetadataExt(BlockMetadataExt::new_v1(
+                HashValue::zero(),
+                0,
+                0,
+                proposer,
+                previous_block_votes_bitvec,
+                failed_proposer_indices,
+                self.block_time,
+                Some(Randomness::new(
+                    RandMetadata { epoch: 0, round: 0 },
+                    seed.clone(),
+                )),
+            )),
+            None => Transaction::BlockMetadata(BlockMetadata::new(
+                HashValue::zero(),
+                0,
+                0,
+                proposer,
+                previous_block_votes_bitvec,
+                failed_proposer_indices,
+                self.block_time,
+            )),
+        };
+        txn_block.insert(0, new_block_metadata);
 
         let outputs = self
             .execute_transaction_block(txn_block)
@@ -839,6 +864,12 @@ impl FakeExecutor {
         Identifier::new(name).unwrap()
     }
 
+    /// Sets the randomness seed of the blocks started from now on. With a seed, the randomness
+    /// APIs return the same results in every run, and without one (the default), they abort.
+    pub fn set_randomness_seed(&mut self, seed: Option<Vec<u8>>) {
+        self.randomness_seed = seed;
+    }
+
     pub fn set_block_time(&mut self, new_block_time: u64) {
         self.block_time = new_block_time;
     }
@@ -570,6 +570,16 @@ module std::features {
         is_enabled(DEFAULT_TO_CONCURRENT_FUNGIBLE_BALANCE)
     }
 
+    /// Whether the limits on the size and depth of types in the VM are taken from the gas schedule.
+    /// Lifetime: transient
+    const LIMIT_VM_TYPE_SIZE: u64 = 69;
+
+    public fun get_limit_vm_type_size_feature(): u64 { LIMIT_VM_TYPE_SIZE }
+
+    public fun limit_vm_type_size_enabled(): bool acquires Features {
+        is_enabled(LIMIT_VM_TYPE_SIZE)
+    }
+
     // ============================================================================================
     // Feature Flag Implementation
 
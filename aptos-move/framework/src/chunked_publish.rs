@@ -0,0 +1,115 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Publishing of packages that are too large for a single transaction, through the
+//! `large_packages` module (see `move-examples/large_packages`). The package metadata and code
+//! are staged across several transactions, and the last one publishes the staged package.
+
+use aptos_types::transaction::{EntryFunction, TransactionPayload};
+use move_core_types::{account_address::AccountAddress, ident_str, language_storage::ModuleId};
+
+/// Maximum number of bytes of metadata and code staged by a single transaction, leaving some
+/// room below the transaction size limit for the rest of the transaction.
+pub const CHUNK_SIZE_IN_BYTES: usize = 55_000;
+
+/// Where the staged package is published to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PublishType {
+    /// Under the account sending the transactions, as `code::publish_package_txn` does.
+    AccountDeploy,
+    /// To a new object, as `object_code_deployment::publish` does. The address of the object is
+    /// derived from the sequence number of the last transaction.
+    ObjectDeploy,
+}
+
+#[derive(Default)]
+struct Chunk {
+    metadata: Vec<u8>,
+    code_indices: Vec<u16>,
+    code_chunks: Vec<Vec<u8>>,
+    size: usize,
+}
+
+impl Chunk {
+    fn remaining(&self) -> usize {
+        CHUNK_SIZE_IN_BYTES - self.size
+    }
+}
+
+/// Splits the package into chunks of at most `CHUNK_SIZE_IN_BYTES`, and returns the payloads of
+/// the transactions which stage them, to be sent in order. The last one publishes the package.
+pub fn chunk_package_and_create_payloads(
+    metadata: Vec<u8>,
+    package_code: Vec<Vec<u8>>,
+    publish_type: PublishType,
+    large_packages_module_address: AccountAddress,
+) -> Vec<TransactionPayload> {
+    let mut chunks = vec![];
+    let mut chunk = Chunk::default();
+
+    let mut metadata = metadata.as_slice();
+    while !metadata.is_empty() {
+        if chunk.remaining() == 0 {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+        let (head, tail) = metadata.split_at(chunk.remaining().min(metadata.len()));
+        chunk.metadata.extend_from_slice(head);
+        chunk.size += head.len();
+        metadata = tail;
+    }
+
+    for (idx, module_code) in package_code.iter().enumerate() {
+        // A module can be split across transactions, but each transaction stages at most one
+        // piece of it, as the pieces it stages are not appended in order.
+        let mut module_code = module_code.as_slice();
+        while !module_code.is_empty() {
+            if chunk.remaining() == 0 {
+                chunks.push(std::mem::take(&mut chunk));
+            }
+            let (head, tail) = module_code.split_at(chunk.remaining().min(module_code.len()));
+            chunk.code_indices.push(idx as u16);
+            chunk.code_chunks.push(head.to_vec());
+            chunk.size += head.len();
+            module_code = tail;
+        }
+    }
+    chunks.push(chunk);
+
+    let num_chunks = chunks.len();
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            let is_last = i + 1 == num_chunks;
+            create_payload(chunk, is_last, publish_type, large_packages_module_address)
+        })
+        .collect()
+}
+
+fn create_payload(
+    chunk: Chunk,
+    is_last: bool,
+    publish_type: PublishType,
+    large_packages_module_address: AccountAddress,
+) -> TransactionPayload {
+    let mut args = vec![
+        bcs::to_bytes(&chunk.metadata).unwrap(),
+        bcs::to_bytes(&chunk.code_indices).unwrap(),
+        bcs::to_bytes(&chunk.code_chunks).unwrap(),
+    ];
+    let function = if is_last && publish_type == PublishType::ObjectDeploy {
+        ident_str!("stage_code_and_publish_to_object")
+    } else {
+        args.push(bcs::to_bytes(&is_last).unwrap());
+        ident_str!("stage_code")
+    };
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            large_packages_module_address,
+            ident_str!("large_packages").to_owned(),
+        ),
+        function.to_owned(),
+        vec![],
+        args,
+    ))
+}
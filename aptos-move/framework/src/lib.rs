@@ -12,6 +12,8 @@ use std::io::{Read, Write};
 mod built_package;
 pub use built_package::*;
 
+pub mod chunked_publish;
+
 mod module_metadata;
 pub use module_metadata::*;
 
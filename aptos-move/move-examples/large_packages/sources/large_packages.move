@@ -1,14 +1,17 @@
 /// This provides a framework for uploading large packages. In each pass, the caller pushes more
 /// code by calling `stage_code`. In the last call, the caller can set the optoinal `publish` and
 /// the package will be published inline, saving an extra transaction and additional storage costs.
-/// Currently this module does not support modules that are larger than 63KB as that is the maximum
-/// that can fit within a transaction and this framework does not split up individual modules.
+/// To publish the package to a new object instead, the last call should be to
+/// `stage_code_and_publish_to_object`.
+/// A module larger than what fits within a transaction can be split across several calls, as the
+/// chunks staged for the same index are appended to each other.
 module large_packages::large_packages {
     use std::error;
     use std::signer;
     use std::vector;
 
     use aptos_framework::code;
+    use aptos_framework::object_code_deployment;
 
     /// code_indices and code_chunks should be the same length.
     const ECODE_MISMATCH: u64 = 1;
@@ -24,6 +27,35 @@ module large_packages::large_packages {
         code_indices: vector<u16>,
         code_chunks: vector<vector<u8>>,
         publish: bool,
+    ) acquires StagingArea {
+        stage_code_internal(owner, metadata_serialized, code_indices, code_chunks);
+
+        if (publish) {
+            let owner_address = signer::address_of(owner);
+            publish_staged_code(owner, owner_address);
+            move_from<StagingArea>(owner_address);
+        }
+    }
+
+    /// Stages the last chunk of the package, and publishes the staged package to a new object.
+    public entry fun stage_code_and_publish_to_object(
+        owner: &signer,
+        metadata_serialized: vector<u8>,
+        code_indices: vector<u16>,
+        code_chunks: vector<vector<u8>>,
+    ) acquires StagingArea {
+        stage_code_internal(owner, metadata_serialized, code_indices, code_chunks);
+
+        let StagingArea { metadata_serialized: staged_metadata, code } =
+            move_from<StagingArea>(signer::address_of(owner));
+        object_code_deployment::publish(owner, staged_metadata, code);
+    }
+
+    fun stage_code_internal(
+        owner: &signer,
+        metadata_serialized: vector<u8>,
+        code_indices: vector<u16>,
+        code_chunks: vector<vector<u8>>,
     ) acquires StagingArea {
         assert!(
             vector::length(&code_indices) == vector::length(&code_chunks),
@@ -51,13 +83,6 @@ module large_packages::large_packages {
             let source_code = vector::borrow_mut(&mut staging_area.code, idx);
             vector::append(source_code, inner_code)
         };
-
-        let _ = staging_area;
-
-        if (publish) {
-            publish_staged_code(owner, owner_address);
-            move_from<StagingArea>(owner_address);
-        }
     }
 
     public entry fun cleanup(owner: &signer) acquires StagingArea {
@@ -307,6 +307,7 @@ fn materialize_delta_shortcut() {
     vd.add_delta(ap.clone(), 11, delta_add(30, limit));
 
     match_unresolved(vd.fetch_data(&ap, 10), SignedU128::Positive(30));
+    assert!(!vd.has_base_value(&ap));
     assert_err_eq!(
         vd.materialize_delta(&ap, 8),
         DeltaOp::new(SignedU128::Positive(30), limit, DeltaHistory {
@@ -325,6 +326,7 @@ fn materialize_delta_shortcut() {
         ap.clone(),
         ValueWithLayout::RawFromStorage(Arc::new(TestValue::from_u128(5))),
     );
+    assert!(vd.has_base_value(&ap));
 
     // With base set, commit delta should now succeed.
     assert_ok_eq!(vd.materialize_delta(&ap, 8), 35);
@@ -229,6 +229,13 @@ impl<K: Hash + Clone + Debug + Eq, V: TransactionWrite> VersionedData<K, V> {
         self.total_base_value_size.load(Ordering::Relaxed)
     }
 
+    /// Returns true if the base value (from storage) has been set at the given key.
+    pub fn has_base_value(&self, key: &K) -> bool {
+        self.values.get(key).map_or(false, |v| {
+            v.versioned_map.contains_key(&ShiftedTxnIndex::zero_idx())
+        })
+    }
+
     pub fn add_delta(&self, key: K, txn_idx: TxnIndex, delta: DeltaOp) {
         let mut v = self.values.entry(key).or_default();
         v.versioned_map.insert(
@@ -0,0 +1,168 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::config::NodeConfig;
+use aptos_data_streaming_service::streaming_service::StreamingServiceConfigHandle;
+use aptos_logger::{error, info, warn, LoggerFilterUpdater};
+use aptos_mempool::{MempoolCapacities, MempoolClientRequest, MempoolClientSender};
+use futures::{channel::oneshot, SinkExt};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use tokio::runtime::Runtime;
+
+/// The interval at which the node config file is checked for changes
+const CONFIG_FILE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The handles through which the reloaded sections of the node config are applied
+pub struct ConfigReloadHandles {
+    pub logger_filter_updater: Option<LoggerFilterUpdater>,
+    pub mempool_client_sender: MempoolClientSender,
+    pub streaming_service_config: StreamingServiceConfigHandle,
+}
+
+/// Starts the config reloader, which reloads the node config from the given
+/// path when the node receives a SIGHUP, or when the config file changes.
+pub fn start_config_reloader(
+    config_path: PathBuf,
+    node_config: NodeConfig,
+    handles: ConfigReloadHandles,
+) -> Runtime {
+    let runtime = aptos_runtimes::spawn_named_runtime("config-reload".into(), Some(1));
+    let config_reloader = ConfigReloader::new(config_path, node_config, handles);
+    runtime.spawn(config_reloader.run());
+    runtime
+}
+
+/// Reloads the node config and applies the sections that can be reloaded without
+/// restarting the node (i.e., log levels, mempool capacities and the state sync
+/// request concurrency). Changes to all other fields are rejected and reported.
+struct ConfigReloader {
+    config_path: PathBuf,
+    node_config: NodeConfig,
+    handles: ConfigReloadHandles,
+    last_modified: Option<SystemTime>,
+}
+
+impl ConfigReloader {
+    fn new(config_path: PathBuf, node_config: NodeConfig, handles: ConfigReloadHandles) -> Self {
+        let last_modified = get_last_modified(&config_path);
+        Self {
+            config_path,
+            node_config,
+            handles,
+            last_modified,
+        }
+    }
+
+    async fn run(mut self) {
+        info!(
+            "Started the node config reloader for the config file: {:?}",
+            self.config_path
+        );
+
+        #[cfg(unix)]
+        let mut hangup_signals =
+            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+                .expect("Failed to listen for SIGHUP signals!");
+        let mut check_interval = tokio::time::interval(CONFIG_FILE_CHECK_INTERVAL);
+        loop {
+            #[cfg(unix)]
+            let hangup_signal = hangup_signals.recv();
+            #[cfg(not(unix))]
+            let hangup_signal = futures::future::pending::<Option<()>>();
+
+            tokio::select! {
+                _ = check_interval.tick() => {
+                    if !self.config_file_changed() {
+                        continue;
+                    }
+                    info!("The node config file changed, reloading the node config.");
+                },
+                _ = hangup_signal => {
+                    info!("Received SIGHUP, reloading the node config.");
+                },
+            }
+            self.reload_config().await;
+        }
+    }
+
+    /// Returns true iff the config file was modified since it was last checked
+    fn config_file_changed(&mut self) -> bool {
+        let last_modified = get_last_modified(&self.config_path);
+        if last_modified == self.last_modified {
+            return false;
+        }
+        self.last_modified = last_modified;
+        true
+    }
+
+    /// Reloads the node config from the config file, and applies the reloaded fields
+    async fn reload_config(&mut self) {
+        let new_config = match NodeConfig::load_from_path(&self.config_path) {
+            Ok(new_config) => new_config,
+            Err(error) => {
+                error!(
+                    "Failed to load the node config file {:?}, the running config is kept: {:?}",
+                    self.config_path, error
+                );
+                return;
+            },
+        };
+        let (reloaded_config, report) = match self.node_config.reload_from(&new_config) {
+            Ok(result) => result,
+            Err(error) => {
+                error!("Failed to reload the node config: {:?}", error);
+                return;
+            },
+        };
+
+        if report.rejected_fields.is_empty() {
+            info!("{}", report);
+        } else {
+            warn!("{}", report);
+        }
+        if report.reloaded_fields.is_empty() {
+            return;
+        }
+
+        self.apply_config(&reloaded_config).await;
+        self.node_config = reloaded_config;
+    }
+
+    /// Applies the reloadable sections of the given config to the running node
+    async fn apply_config(&mut self, node_config: &NodeConfig) {
+        // Update the log levels
+        if let Some(logger_filter_updater) = &self.handles.logger_filter_updater {
+            logger_filter_updater
+                .set_levels(node_config.logger.level, node_config.logger.telemetry_level);
+        }
+
+        // Update the mempool capacities
+        let (callback, callback_receiver) = oneshot::channel();
+        let request = MempoolClientRequest::UpdateCapacities(
+            MempoolCapacities::from(&node_config.mempool),
+            callback,
+        );
+        if let Err(error) = self.handles.mempool_client_sender.send(request).await {
+            error!("Failed to send the mempool capacities update: {:?}", error);
+        } else if let Err(error) = callback_receiver.await {
+            error!("Failed to update the mempool capacities: {:?}", error);
+        }
+
+        // Update the streaming service config (used by new data streams)
+        self.handles
+            .streaming_service_config
+            .store(Arc::new(node_config.state_sync.data_streaming_service));
+    }
+}
+
+/// Returns the last modification time of the file (if it can be read)
+fn get_last_modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -4,6 +4,7 @@
 
 #![forbid(unsafe_code)]
 
+mod config_reloader;
 mod indexer;
 mod logger;
 mod network;
@@ -25,6 +26,7 @@ use aptos_config::config::{
 };
 use aptos_consensus::consensus_provider::start_consensus_observer;
 use aptos_dkg_runtime::start_dkg_runtime;
+use aptos_executor_types::block_output_stream::BlockOutputPublisher;
 use aptos_framework::ReleaseBundle;
 use aptos_jwk_consensus::start_jwk_consensus_runtime;
 use aptos_logger::{prelude::*, telemetry_log_writer::TelemetryLog, Level, LoggerFilterUpdater};
@@ -185,7 +187,7 @@ impl AptosNodeArgs {
             });
 
             // Start the node
-            start(config, None, true).expect("Node should start correctly");
+            start(config, Some(config_path), None, true).expect("Node should start correctly");
         };
     }
 }
@@ -200,6 +202,7 @@ pub struct AptosHandle {
     _admin_service: AdminService,
     _api_runtime: Option<Runtime>,
     _backup_runtime: Option<Runtime>,
+    _config_reloader_runtime: Option<Runtime>,
     _consensus_observer_runtime: Option<Runtime>,
     _consensus_runtime: Option<Runtime>,
     _dkg_runtime: Option<Runtime>,
@@ -209,14 +212,17 @@ pub struct AptosHandle {
     _jwk_consensus_runtime: Option<Runtime>,
     _mempool_runtime: Runtime,
     _network_runtimes: Vec<Runtime>,
+    _otlp_exporter_runtime: Option<Runtime>,
     _peer_monitoring_service_runtime: Runtime,
     _state_sync_runtimes: StateSyncRuntimes,
     _telemetry_runtime: Option<Runtime>,
 }
 
-/// Start an Aptos node
+/// Start an Aptos node. If the path of the config file is given, the node
+/// config is reloaded on SIGHUP, or when the file changes.
 pub fn start(
     config: NodeConfig,
+    config_path: Option<PathBuf>,
     log_file: Option<PathBuf>,
     create_global_rayon_pool: bool,
 ) -> anyhow::Result<()> {
@@ -257,8 +263,12 @@ pub fn start(
     }
 
     // Set up the node environment and start it
-    let _node_handle =
-        setup_environment_and_start_node(config, remote_log_receiver, Some(logger_filter_update))?;
+    let _node_handle = setup_environment_and_start_node(
+        config,
+        config_path,
+        remote_log_receiver,
+        Some(logger_filter_update),
+    )?;
     let term = Arc::new(AtomicBool::new(false));
     while !term.load(Ordering::Acquire) {
         thread::park();
@@ -354,7 +364,17 @@ pub fn start_test_environment_node(
     }
     println!("\nAptos is running, press ctrl-c to exit\n");
 
-    start(config, Some(log_file), false)
+    start(config, None, Some(log_file), false)
+}
+
+/// Starts a node in the current process, e.g., for tools and tests that run a localnet
+/// in-process. Unlike `start`, this doesn't set up the global logger or block: the node
+/// runs until the returned handle is dropped.
+///
+/// Note: the node identity is global, so only one node can be started per process.
+pub fn start_in_process(config: NodeConfig) -> anyhow::Result<AptosHandle> {
+    aptos_node_identity::init(config.get_peer_id())?;
+    setup_environment_and_start_node(config, None, None, None)
 }
 
 /// Creates a simple test environment and starts the node.
@@ -602,12 +622,18 @@ where
 /// Initializes the node environment and starts the node
 pub fn setup_environment_and_start_node(
     mut node_config: NodeConfig,
+    config_path: Option<PathBuf>,
     remote_log_rx: Option<mpsc::Receiver<TelemetryLog>>,
     logger_filter_update_job: Option<LoggerFilterUpdater>,
 ) -> anyhow::Result<AptosHandle> {
     // Log the node config at node startup
     node_config.log_all_configs();
 
+    // Keep the loaded node config (before it is modified below) to compare it with
+    // the reloaded config, so that only the changes to the config file are reported.
+    let loaded_node_config = node_config.clone();
+    let logger_filter_updater = logger_filter_update_job.clone();
+
     // Starts the admin service
     let admin_service = services::start_admin_service(&node_config);
 
@@ -634,6 +660,9 @@ pub fn setup_environment_and_start_node(
         chain_id,
     );
 
+    // Start tracing the sampled transactions (before any of them are processed)
+    let otlp_exporter_runtime = services::start_otlp_exporter(&node_config);
+
     // Create an event subscription service (and reconfig subscriptions for consensus and mempool)
     let (
         mut event_subscription_service,
@@ -644,6 +673,10 @@ pub fn setup_environment_and_start_node(
         jwk_consensus_subscriptions,
     ) = state_sync::create_event_subscription_service(&node_config, &db_rw);
 
+    // Create the block output stream (to publish committed blocks to in-process subscribers)
+    let block_output_publisher =
+        BlockOutputPublisher::new(node_config.execution.block_output_stream_capacity);
+
     // Set up the networks and gather the application network handles
     let peers_and_metadata = network::create_peers_and_metadata(&node_config);
     let (
@@ -655,6 +688,7 @@ pub fn setup_environment_and_start_node(
         mempool_network_interfaces,
         peer_monitoring_service_network_interfaces,
         storage_service_network_interfaces,
+        commit_stream_network_interfaces,
     ) = network::setup_networks_and_get_interfaces(
         &node_config,
         chain_id,
@@ -670,14 +704,21 @@ pub fn setup_environment_and_start_node(
     );
 
     // Start state sync and get the notification endpoints for mempool and consensus
-    let (aptos_data_client, state_sync_runtimes, mempool_listener, consensus_notifier) =
-        state_sync::start_state_sync_and_get_notification_handles(
-            &node_config,
-            storage_service_network_interfaces,
-            genesis_waypoint,
-            event_subscription_service,
-            db_rw.clone(),
-        )?;
+    let (
+        aptos_data_client,
+        state_sync_runtimes,
+        mempool_listener,
+        consensus_notifier,
+        streaming_service_config,
+    ) = state_sync::start_state_sync_and_get_notification_handles(
+        &node_config,
+        storage_service_network_interfaces,
+        commit_stream_network_interfaces,
+        genesis_waypoint,
+        event_subscription_service,
+        db_rw.clone(),
+        block_output_publisher.clone(),
+    )?;
 
     // Start the node inspection service
     services::start_node_inspection_service(
@@ -688,12 +729,32 @@ pub fn setup_environment_and_start_node(
 
     // Bootstrap the API and indexer
     let (
+        mempool_client_sender,
         mempool_client_receiver,
         api_runtime,
         indexer_table_info_runtime,
         indexer_runtime,
         indexer_grpc_runtime,
-    ) = services::bootstrap_api_and_indexer(&node_config, db_rw.clone(), chain_id)?;
+    ) = services::bootstrap_api_and_indexer(
+        &node_config,
+        db_rw.clone(),
+        chain_id,
+        &block_output_publisher,
+    )?;
+    admin_service.set_mempool_client_sender(mempool_client_sender.clone());
+
+    // Start reloading the node config on SIGHUP, or when the config file changes
+    let config_reloader_runtime = config_path.map(|config_path| {
+        config_reloader::start_config_reloader(
+            config_path,
+            loaded_node_config,
+            config_reloader::ConfigReloadHandles {
+                logger_filter_updater,
+                mempool_client_sender,
+                streaming_service_config,
+            },
+        )
+    });
 
     // Create mempool and get the consensus to mempool sender
     let (mempool_runtime, consensus_to_mempool_sender) =
@@ -800,6 +861,7 @@ pub fn setup_environment_and_start_node(
                     consensus_to_mempool_sender,
                     vtxn_pool,
                     consensus_observer_network_client,
+                    block_output_publisher,
                 );
             admin_service.set_consensus_dbs(consensus_db, quorum_store_db);
 
@@ -818,6 +880,7 @@ pub fn setup_environment_and_start_node(
                     consensus_to_mempool_sender,
                     db_rw,
                     consensus_observer_reconfig_subscription,
+                    block_output_publisher,
                 );
 
                 (None, Some(consensus_observer_runtime))
@@ -831,6 +894,7 @@ pub fn setup_environment_and_start_node(
         _admin_service: admin_service,
         _api_runtime: api_runtime,
         _backup_runtime: backup_service,
+        _config_reloader_runtime: config_reloader_runtime,
         _consensus_observer_runtime: consensus_observer_runtime,
         _consensus_runtime: consensus_runtime,
         _dkg_runtime: dkg_runtime,
@@ -840,6 +904,7 @@ pub fn setup_environment_and_start_node(
         _jwk_consensus_runtime: jwk_consensus_runtime,
         _mempool_runtime: mempool_runtime,
         _network_runtimes: network_runtimes,
+        _otlp_exporter_runtime: otlp_exporter_runtime,
         _peer_monitoring_service_runtime: peer_monitoring_service_runtime,
         _state_sync_runtimes: state_sync_runtimes,
         _telemetry_runtime: telemetry_runtime,
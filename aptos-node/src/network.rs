@@ -30,6 +30,7 @@ use aptos_network::{
 use aptos_network_benchmark::NetbenchMessage;
 use aptos_network_builder::builder::NetworkBuilder;
 use aptos_peer_monitoring_service_types::PeerMonitoringServiceMessage;
+use aptos_state_sync_driver::{commit_stream::CommitStreamMessage, metrics as state_sync_metrics};
 use aptos_storage_service_types::StorageServiceMessage;
 use aptos_time_service::TimeService;
 use aptos_types::chain_id::ChainId;
@@ -186,6 +187,27 @@ pub fn consensus_observer_network_configuration(
     NetworkApplicationConfig::new(network_client_config, network_service_config)
 }
 
+/// Returns the network application config for the commit stream client and service
+pub fn commit_stream_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
+    let direct_send_protocols = vec![ProtocolId::CommitStreamDirectSend];
+    let rpc_protocols = vec![];
+    let max_network_channel_size = node_config
+        .state_sync
+        .state_sync_driver
+        .max_commit_stream_network_channel_size as usize;
+
+    let network_client_config =
+        NetworkClientConfig::new(direct_send_protocols.clone(), rpc_protocols.clone());
+    let network_service_config = NetworkServiceConfig::new(
+        direct_send_protocols,
+        rpc_protocols,
+        aptos_channel::Config::new(max_network_channel_size)
+            .queue_style(QueueStyle::KLAST) // Only the latest commits matter
+            .counters(&state_sync_metrics::PENDING_COMMIT_STREAM_NETWORK_EVENTS),
+    );
+    NetworkApplicationConfig::new(network_client_config, network_service_config)
+}
+
 /// Returns the network application config for the netbench client and server
 pub fn netbench_network_configuration(
     node_config: &NodeConfig,
@@ -257,6 +279,7 @@ pub fn setup_networks_and_get_interfaces(
     ApplicationNetworkInterfaces<MempoolSyncMsg>,
     ApplicationNetworkInterfaces<PeerMonitoringServiceMessage>,
     ApplicationNetworkInterfaces<StorageServiceMessage>,
+    Option<ApplicationNetworkInterfaces<CommitStreamMessage>>,
 ) {
     // Gather all network configs
     let network_configs = extract_network_configs(node_config);
@@ -272,6 +295,7 @@ pub fn setup_networks_and_get_interfaces(
     let mut mempool_network_handles = vec![];
     let mut peer_monitoring_service_network_handles = vec![];
     let mut storage_service_network_handles = vec![];
+    let mut commit_stream_network_handles = vec![];
     let mut netbench_handles = Vec::<ApplicationNetworkHandle<NetbenchMessage>>::new();
     for network_config in network_configs.into_iter() {
         // Create a network runtime for the config
@@ -385,6 +409,24 @@ pub fn setup_networks_and_get_interfaces(
         );
         storage_service_network_handles.push(storage_service_network_handle);
 
+        // Register the commit stream (both client and server) with the network. The
+        // commit stream is only used on fullnode networks (e.g., by validators and VFNs).
+        if node_config
+            .state_sync
+            .state_sync_driver
+            .enable_commit_stream
+            && !network_id.is_validator_network()
+        {
+            let commit_stream_network_handle = register_client_and_service_with_network(
+                &mut network_builder,
+                network_id,
+                &network_config,
+                commit_stream_network_configuration(node_config),
+                true,
+            );
+            commit_stream_network_handles.push(commit_stream_network_handle);
+        }
+
         // Register the network benchmark test service
         if let Some(app_config) = netbench_network_configuration(node_config) {
             let netbench_handle = register_client_and_service_with_network(
@@ -428,6 +470,17 @@ pub fn setup_networks_and_get_interfaces(
         peers_and_metadata.clone(),
     );
 
+    // Transform the commit stream handles into interfaces (if the commit stream is enabled)
+    let commit_stream_interfaces = if commit_stream_network_handles.is_empty() {
+        None
+    } else {
+        Some(create_network_interfaces(
+            commit_stream_network_handles,
+            commit_stream_network_configuration(node_config),
+            peers_and_metadata.clone(),
+        ))
+    };
+
     if !netbench_handles.is_empty() {
         let netbench_interfaces = create_network_interfaces(
             netbench_handles,
@@ -450,6 +503,7 @@ pub fn setup_networks_and_get_interfaces(
         mempool_interfaces,
         peer_monitoring_service_interfaces,
         storage_service_interfaces,
+        commit_stream_interfaces,
     )
 }
 
@@ -13,10 +13,13 @@ use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::client::AptosDataClient;
 use aptos_db_indexer::table_info_reader::TableInfoReader;
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
+use aptos_executor_types::block_output_stream::BlockOutputPublisher;
 use aptos_indexer_grpc_fullnode::runtime::bootstrap as bootstrap_indexer_grpc;
 use aptos_indexer_grpc_table_info::runtime::bootstrap as bootstrap_indexer_table_info;
 use aptos_logger::{debug, telemetry_log_writer::TelemetryLog, LoggerFilterUpdater};
-use aptos_mempool::{network::MempoolSyncMsg, MempoolClientRequest, QuorumStoreRequest};
+use aptos_mempool::{
+    network::MempoolSyncMsg, MempoolClientRequest, MempoolClientSender, QuorumStoreRequest,
+};
 use aptos_mempool_notifications::MempoolNotificationListener;
 use aptos_network::application::{
     interface::{NetworkClient, NetworkClientInterface},
@@ -40,12 +43,14 @@ const AC_SMP_CHANNEL_BUFFER_SIZE: usize = 1_024;
 const INTRA_NODE_CHANNEL_BUFFER_SIZE: usize = 1;
 
 /// Bootstraps the API and the indexer. Returns the Mempool client
-/// receiver, and both the api and indexer runtimes.
+/// sender and receiver, and both the api and indexer runtimes.
 pub fn bootstrap_api_and_indexer(
     node_config: &NodeConfig,
     db_rw: DbReaderWriter,
     chain_id: ChainId,
+    block_output_publisher: &BlockOutputPublisher,
 ) -> anyhow::Result<(
+    MempoolClientSender,
     Receiver<MempoolClientRequest>,
     Option<Runtime>,
     Option<Runtime>,
@@ -61,6 +66,7 @@ pub fn bootstrap_api_and_indexer(
         chain_id,
         db_rw.clone(),
         mempool_client_sender.clone(),
+        block_output_publisher,
     ) {
         Some((runtime, indexer_v2)) => (Some(runtime), Some(indexer_v2)),
         None => (None, None),
@@ -97,10 +103,11 @@ pub fn bootstrap_api_and_indexer(
         node_config,
         chain_id,
         db_rw.reader.clone(),
-        mempool_client_sender,
+        mempool_client_sender.clone(),
     )?;
 
     Ok((
+        mempool_client_sender,
         mempool_client_receiver,
         api_runtime,
         indexer_table_info_runtime,
@@ -119,6 +126,7 @@ pub fn start_consensus_runtime(
     consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
     vtxn_pool: VTxnPoolState,
     observer_network_client: Option<NetworkClient<ObserverMessage>>,
+    block_output_publisher: BlockOutputPublisher,
 ) -> (Runtime, Arc<StorageWriteProxy>, Arc<QuorumStoreDB>) {
     let instant = Instant::now();
 
@@ -135,6 +143,7 @@ pub fn start_consensus_runtime(
         reconfig_subscription,
         vtxn_pool,
         observer_network_client,
+        block_output_publisher,
     );
     debug!("Consensus started in {} ms", instant.elapsed().as_millis());
 
@@ -250,6 +259,11 @@ pub fn start_netbench_service(
     ));
 }
 
+/// Starts the export of the traces of sampled transactions to an OTLP collector (if enabled)
+pub fn start_otlp_exporter(node_config: &NodeConfig) -> Option<Runtime> {
+    aptos_telemetry::otlp_exporter::start_otlp_exporter(node_config)
+}
+
 /// Starts the telemetry service and grabs the build information
 pub fn start_telemetry_service(
     node_config: &NodeConfig,
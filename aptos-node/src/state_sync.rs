@@ -7,13 +7,14 @@ use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::{client::AptosDataClient, poller};
 use aptos_data_streaming_service::{
     streaming_client::{new_streaming_service_client_listener_pair, StreamingServiceClient},
-    streaming_service::DataStreamingService,
+    streaming_service::{DataStreamingService, StreamingServiceConfigHandle},
 };
 use aptos_event_notifications::{
     DbBackedOnChainConfig, EventNotificationListener, EventSubscriptionService,
     ReconfigNotificationListener,
 };
 use aptos_executor::chunk_executor::ChunkExecutor;
+use aptos_executor_types::block_output_stream::BlockOutputPublisher;
 use aptos_infallible::RwLock;
 use aptos_mempool_notifications::MempoolNotificationListener;
 use aptos_network::application::{
@@ -21,6 +22,7 @@ use aptos_network::application::{
     storage::PeersAndMetadata,
 };
 use aptos_state_sync_driver::{
+    commit_stream::{CommitStreamHandler, CommitStreamMessage},
     driver_factory::{DriverFactory, StateSyncRuntimes},
     metadata_storage::PersistentMetadataStorage,
 };
@@ -128,14 +130,17 @@ pub fn create_event_subscription_service(
 pub fn start_state_sync_and_get_notification_handles(
     node_config: &NodeConfig,
     storage_network_interfaces: ApplicationNetworkInterfaces<StorageServiceMessage>,
+    commit_stream_network_interfaces: Option<ApplicationNetworkInterfaces<CommitStreamMessage>>,
     waypoint: Waypoint,
     event_subscription_service: EventSubscriptionService,
     db_rw: DbReaderWriter,
+    block_output_publisher: BlockOutputPublisher,
 ) -> anyhow::Result<(
     AptosDataClient,
     StateSyncRuntimes,
     MempoolNotificationListener,
     ConsensusNotifier,
+    StreamingServiceConfigHandle,
 )> {
     // Get the network client and events
     let network_client = storage_network_interfaces.network_client;
@@ -148,11 +153,14 @@ pub fn start_state_sync_and_get_notification_handles(
 
     // Start the data streaming service
     let state_sync_config = node_config.state_sync;
-    let (streaming_service_client, streaming_service_runtime) =
+    let (streaming_service_client, streaming_service_config, streaming_service_runtime) =
         setup_data_streaming_service(state_sync_config, aptos_data_client.clone())?;
 
     // Create the chunk executor and persistent storage
-    let chunk_executor = Arc::new(ChunkExecutor::<AptosVM>::new(db_rw.clone()));
+    let chunk_executor = Arc::new(ChunkExecutor::<AptosVM>::new_with_block_output_publisher(
+        db_rw.clone(),
+        block_output_publisher,
+    ));
     let metadata_storage = PersistentMetadataStorage::new(&node_config.storage.dir());
 
     // Create notification senders and listeners for mempool, consensus and the storage service
@@ -180,6 +188,15 @@ pub fn start_state_sync_and_get_notification_handles(
         storage_service_listener,
     )?;
 
+    // Create the commit stream handler (if the commit stream is enabled)
+    let commit_stream_handler = match commit_stream_network_interfaces {
+        Some(commit_stream_network_interfaces) => CommitStreamHandler::new(
+            commit_stream_network_interfaces.network_client,
+            commit_stream_network_interfaces.network_service_events,
+        ),
+        None => CommitStreamHandler::disabled(),
+    };
+
     // Create the state sync driver factory
     let state_sync = DriverFactory::create_and_spawn_driver(
         true,
@@ -194,6 +211,7 @@ pub fn start_state_sync_and_get_notification_handles(
         event_subscription_service,
         aptos_data_client.clone(),
         streaming_service_client,
+        commit_stream_handler,
         TimeService::real(),
     );
 
@@ -210,14 +228,20 @@ pub fn start_state_sync_and_get_notification_handles(
         state_sync_runtimes,
         mempool_listener,
         consensus_notifier,
+        streaming_service_config,
     ))
 }
 
-/// Sets up the data streaming service runtime
+/// Sets up the data streaming service runtime, and returns the handle
+/// through which the streaming service config can be updated at runtime.
 fn setup_data_streaming_service(
     state_sync_config: StateSyncConfig,
     aptos_data_client: AptosDataClient,
-) -> anyhow::Result<(StreamingServiceClient, Runtime)> {
+) -> anyhow::Result<(
+    StreamingServiceClient,
+    StreamingServiceConfigHandle,
+    Runtime,
+)> {
     // Create the data streaming service
     let (streaming_service_client, streaming_service_listener) =
         new_streaming_service_client_listener_pair();
@@ -229,11 +253,17 @@ fn setup_data_streaming_service(
         TimeService::real(),
     );
 
+    let streaming_service_config = data_streaming_service.get_streaming_service_config();
+
     // Start the data streaming service
     let streaming_service_runtime = aptos_runtimes::spawn_named_runtime("stream-serv".into(), None);
     streaming_service_runtime.spawn(data_streaming_service.start_service());
 
-    Ok((streaming_service_client, streaming_service_runtime))
+    Ok((
+        streaming_service_client,
+        streaming_service_config,
+        streaming_service_runtime,
+    ))
 }
 
 /// Sets up the aptos data client runtime
@@ -3,12 +3,13 @@
 
 use anyhow::anyhow;
 use aptos_config::config::{NodeConfig, DEFAULT_EXECUTION_CONCURRENCY_LEVEL};
+use aptos_logger::error;
 use aptos_storage_interface::{state_view::LatestDbStateCheckpointView, DbReaderWriter};
 use aptos_types::{
     account_config::ChainIdResource, chain_id::ChainId, on_chain_config::OnChainConfig,
 };
-use aptos_vm::AptosVM;
-use std::cmp::min;
+use aptos_vm::{block_executor::access_summary::FileAccessSummarySink, AptosVM};
+use std::{cmp::min, sync::Arc};
 
 /// Error message to display when non-production features are enabled
 pub const ERROR_MSG_BAD_FEATURE_FLAGS: &str = r#"
@@ -66,4 +67,14 @@ pub fn set_aptos_vm_configurations(node_config: &NodeConfig) {
     {
         AptosVM::set_processed_transactions_detailed_counters();
     }
+
+    if let Some(path) = &node_config.execution.access_summary_file {
+        match FileAccessSummarySink::create(path) {
+            Ok(sink) => AptosVM::set_access_summary_sink_once(Arc::new(sink)),
+            Err(err) => error!(
+                "Failed to open the access summary file {:?}, summaries won't be recorded: {}",
+                path, err
+            ),
+        }
+    }
 }
@@ -89,6 +89,10 @@ pub struct ApiConfig {
     pub wait_by_hash_poll_interval_ms: u64,
     /// The number of active wait_by_hash requests that can be active at any given time.
     pub wait_by_hash_max_active_connections: usize,
+    /// The interval at which the streaming APIs poll the storage for new transactions and events.
+    pub stream_poll_interval_ms: u64,
+    /// The number of streams that can be open at any given time. Set to 0 to disable streaming.
+    pub stream_max_active_connections: usize,
 }
 
 const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -141,6 +145,8 @@ impl Default for ApiConfig {
             wait_by_hash_timeout_ms: 1_000,
             wait_by_hash_poll_interval_ms: 20,
             wait_by_hash_max_active_connections: 100,
+            stream_poll_interval_ms: 500,
+            stream_max_active_connections: 100,
         }
     }
 }
@@ -6,7 +6,7 @@ use crate::config::{
     utils::{are_failpoints_enabled, get_config_name},
     AdminServiceConfig, ApiConfig, BaseConfig, ConsensusConfig, DagConsensusConfig, Error,
     ExecutionConfig, IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig,
-    NetbenchConfig, NodeConfig, StateSyncConfig, StorageConfig,
+    NetbenchConfig, NetworkConfig, NodeConfig, OtlpExporterConfig, StateSyncConfig, StorageConfig,
 };
 use aptos_types::chain_id::ChainId;
 use std::collections::HashSet;
@@ -60,6 +60,7 @@ impl ConfigSanitizer for NodeConfig {
         LoggerConfig::sanitize(node_config, node_type, chain_id)?;
         MempoolConfig::sanitize(node_config, node_type, chain_id)?;
         NetbenchConfig::sanitize(node_config, node_type, chain_id)?;
+        OtlpExporterConfig::sanitize(node_config, node_type, chain_id)?;
         StateSyncConfig::sanitize(node_config, node_type, chain_id)?;
         StorageConfig::sanitize(node_config, node_type, chain_id)?;
         sanitize_validator_network_config(node_config, node_type, chain_id)?;
@@ -146,6 +147,9 @@ fn sanitize_fullnode_network_configs(
                 ),
             ));
         }
+
+        // Verify the inbound rpc rate limit config
+        sanitize_inbound_rpc_rate_limit_config(&sanitizer_name, fullnode_network_config)?;
     }
 
     Ok(())
@@ -193,6 +197,32 @@ fn sanitize_validator_network_config(
                 "Mutual authentication must be enabled for the validator network!".into(),
             ));
         }
+
+        // Verify the inbound rpc rate limit config
+        sanitize_inbound_rpc_rate_limit_config(&sanitizer_name, validator_network_config)?;
+    }
+
+    Ok(())
+}
+
+/// Sanitize the inbound rpc rate limit config of the network (if rate limiting is enabled)
+fn sanitize_inbound_rpc_rate_limit_config(
+    sanitizer_name: &str,
+    network_config: &NetworkConfig,
+) -> Result<(), Error> {
+    if let Some(rate_limit_config) = &network_config.inbound_rpc_rate_limit_config {
+        if rate_limit_config.enabled
+            && (rate_limit_config.request_bucket_rate == 0
+                || rate_limit_config.request_bucket_size == 0)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name.to_string(),
+                format!(
+                    "The inbound rpc rate limit of network {} must allow at least one request!",
+                    network_config.network_id
+                ),
+            ));
+        }
     }
 
     Ok(())
@@ -202,7 +232,7 @@ fn sanitize_validator_network_config(
 mod tests {
     use super::*;
     use crate::{
-        config::{node_startup_config::NodeStartupConfig, NetworkConfig},
+        config::{node_startup_config::NodeStartupConfig, RpcRateLimitConfig},
         network_id::NetworkId,
     };
 
@@ -320,6 +350,44 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_invalid_inbound_rpc_rate_limit_config() {
+        // Create a node config with a zero inbound rpc rate
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                inbound_rpc_rate_limit_config: Some(RpcRateLimitConfig {
+                    request_bucket_rate: 0,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Disable the rate limiting and verify that the config is accepted
+        node_config.full_node_networks[0]
+            .inbound_rpc_rate_limit_config
+            .as_mut()
+            .unwrap()
+            .enabled = false;
+        sanitize_fullnode_network_configs(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+    }
+
     #[test]
     fn test_sanitize_missing_validator_network_config() {
         // Create a node config with an empty validator network config
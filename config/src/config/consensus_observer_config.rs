@@ -38,13 +38,117 @@ impl ConsensusObserverConfig {
 
 impl ConfigOptimizer for ConsensusObserverConfig {
     fn optimize(
-        _node_config: &mut NodeConfig,
-        _local_config_yaml: &Value,
-        _node_type: NodeType,
+        node_config: &mut NodeConfig,
+        local_config_yaml: &Value,
+        node_type: NodeType,
         _chain_id: Option<ChainId>,
     ) -> Result<bool, Error> {
+        let dag_observer_mode = node_config.dag_consensus.observer_mode;
+        let consensus_observer_config = &mut node_config.consensus_observer;
+        let local_observer_config_yaml = &local_config_yaml["consensus_observer"];
+
         // TODO: use me to enable consensus observer for validators and VFNs
         // in controlled environments, e.g., devnet.
-        Ok(false)
+
+        // Fullnodes don't run consensus, so in dag observer mode they follow the
+        // blocks ordered by the validators through the consensus observer.
+        let mut modified_config = false;
+        if dag_observer_mode
+            && !node_type.is_validator()
+            && local_observer_config_yaml["observer_enabled"].is_null()
+        {
+            consensus_observer_config.observer_enabled = true;
+            modified_config = true;
+        }
+
+        Ok(modified_config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DagConsensusConfig;
+
+    #[test]
+    fn test_optimize_dag_observer_mode_vfn() {
+        // Create a node config with the dag observer mode enabled
+        let mut node_config = NodeConfig {
+            dag_consensus: DagConsensusConfig {
+                observer_mode: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Optimize the config and verify that it succeeds
+        let modified_config = ConsensusObserverConfig::optimize(
+            &mut node_config,
+            &Value::Null,
+            NodeType::ValidatorFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+
+        // Verify that the consensus observer is enabled
+        assert!(modified_config);
+        assert!(node_config.consensus_observer.observer_enabled);
+    }
+
+    #[test]
+    fn test_optimize_dag_observer_mode_validator() {
+        // Create a node config with the dag observer mode enabled
+        let mut node_config = NodeConfig {
+            dag_consensus: DagConsensusConfig {
+                observer_mode: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Optimize the config and verify that it succeeds
+        let modified_config = ConsensusObserverConfig::optimize(
+            &mut node_config,
+            &Value::Null,
+            NodeType::Validator,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+
+        // Verify that validators observe the dag themselves
+        assert!(!modified_config);
+        assert!(!node_config.consensus_observer.observer_enabled);
+    }
+
+    #[test]
+    fn test_optimize_dag_observer_mode_local_override() {
+        // Create a node config with the dag observer mode enabled
+        let mut node_config = NodeConfig {
+            dag_consensus: DagConsensusConfig {
+                observer_mode: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Create a local config YAML that disables the consensus observer
+        let local_config_yaml = serde_yaml::from_str(
+            r#"
+            consensus_observer:
+                observer_enabled: false
+            "#,
+        )
+        .unwrap();
+
+        // Optimize the config and verify that the local config is respected
+        let modified_config = ConsensusObserverConfig::optimize(
+            &mut node_config,
+            &local_config_yaml,
+            NodeType::ValidatorFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+        assert!(!modified_config);
+        assert!(!node_config.consensus_observer.observer_enabled);
     }
 }
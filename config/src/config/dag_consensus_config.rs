@@ -85,6 +85,11 @@ pub struct DagFetcherConfig {
     pub min_concurrent_responders: u32,
     pub max_concurrent_responders: u32,
     pub max_concurrent_fetches: usize,
+    /// The number of rounds a node can fall behind before it fetches all the missing
+    /// nodes in the range of rounds, instead of the parents of a single node
+    pub range_fetch_threshold_rounds: u64,
+    /// The maximum size of a single part of the response to a range fetch request
+    pub max_range_fetch_response_bytes: u64,
 }
 
 impl Default for DagFetcherConfig {
@@ -95,6 +100,8 @@ impl Default for DagFetcherConfig {
             min_concurrent_responders: 1,
             max_concurrent_responders: 4,
             max_concurrent_fetches: 4,
+            range_fetch_threshold_rounds: 3,
+            max_range_fetch_response_bytes: 4 * 1024 * 1024,
         }
     }
 }
@@ -142,6 +149,7 @@ pub struct DagHealthConfig {
     pub chain_backoff_config: Vec<ChainHealthBackoffValues>,
     pub voter_pipeline_latency_limit_ms: u64,
     pub pipeline_backpressure_config: Vec<PipelineBackpressureValues>,
+    pub proposal_backpressure_config: DagProposalBackpressureConfig,
 }
 
 impl Default for DagHealthConfig {
@@ -150,6 +158,51 @@ impl Default for DagHealthConfig {
             chain_backoff_config: Vec::new(),
             voter_pipeline_latency_limit_ms: 30_000,
             pipeline_backpressure_config: Vec::new(),
+            proposal_backpressure_config: DagProposalBackpressureConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DagProposalBackpressureConfig {
+    /// The number of queued fetch requests above which the driver is overloaded
+    pub max_pending_fetch_requests: usize,
+    /// The number of rounds between the highest strong links round and the
+    /// highest committed anchor round above which the driver is overloaded
+    pub max_commit_lag_rounds: u64,
+    /// The number of ordered, but not yet committed, blocks above which the
+    /// driver is overloaded
+    pub max_pending_ordered_blocks: usize,
+    /// The minimum delay before proposing a node while overloaded
+    pub proposal_delay_ms: u64,
+}
+
+impl Default for DagProposalBackpressureConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_fetch_requests: 8,
+            max_commit_lag_rounds: 30,
+            max_pending_ordered_blocks: 20,
+            proposal_delay_ms: 300,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DagHandlerConfig {
+    /// The maximum number of messages from a single author that are processed concurrently
+    pub max_concurrent_messages_per_author: usize,
+    /// The maximum number of fetch requests from a single author that are processed concurrently
+    pub max_concurrent_fetch_requests_per_author: usize,
+}
+
+impl Default for DagHandlerConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_messages_per_author: 10,
+            max_concurrent_fetch_requests_per_author: 2,
         }
     }
 }
@@ -162,6 +215,12 @@ pub struct DagConsensusConfig {
     pub fetcher_config: DagFetcherConfig,
     pub round_state_config: DagRoundStateConfig,
     pub health_config: DagHealthConfig,
+    pub handler_config: DagHandlerConfig,
+    /// Follows the dag and orders blocks without voting or proposing. Fullnodes
+    /// (e.g., VFNs) follow the blocks ordered by the validators through the
+    /// consensus observer instead, which requires the validators to enable the
+    /// consensus observer publisher.
+    pub observer_mode: bool,
     #[serde(default = "QuorumStoreConfig::default_for_dag")]
     pub quorum_store: QuorumStoreConfig,
 }
@@ -174,6 +233,17 @@ impl ConfigSanitizer for DagConsensusConfig {
     ) -> Result<(), Error> {
         DagPayloadConfig::sanitize(node_config, node_type, chain_id)?;
 
+        // Fullnodes can only observe the dag through the consensus observer
+        if node_config.dag_consensus.observer_mode
+            && !node_type.is_validator()
+            && !node_config.consensus_observer.observer_enabled
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                Self::get_sanitizer_name(),
+                "The consensus observer must be enabled for fullnodes in dag observer mode!".into(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -223,4 +293,24 @@ mod tests {
             DagPayloadConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_observer_mode_without_consensus_observer() {
+        // Create a node config with the dag observer mode, but no consensus observer
+        let node_config = NodeConfig {
+            dag_consensus: DagConsensusConfig {
+                observer_mode: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails for a VFN
+        let error = DagConsensusConfig::sanitize(&node_config, NodeType::ValidatorFullnode, None)
+            .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that validators don't need the consensus observer
+        DagConsensusConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+    }
 }
@@ -18,6 +18,8 @@ pub enum Error {
     Yaml(String, #[source] serde_yaml::Error),
     #[error("Config is missing expected value: {0}")]
     Missing(&'static str),
+    #[error("Config contains unknown fields: {}", .0.join(", "))]
+    UnknownFields(Vec<String>),
     #[error("Unexpected error: {0}")]
     Unexpected(String),
 }
@@ -54,6 +54,12 @@ pub struct ExecutionConfig {
     pub transaction_filter: Filter,
     /// Used during DB bootstrapping
     pub genesis_waypoint: Option<WaypointConfig>,
+    /// The number of committed blocks buffered for each in-process subscriber of the
+    /// block output stream. Subscribers that fall further behind miss the oldest blocks.
+    pub block_output_stream_capacity: usize,
+    /// If set, the access summaries of the transactions committed by parallel execution are
+    /// appended to this file, one JSON object per line, for offline conflict analytics
+    pub access_summary_file: Option<PathBuf>,
 }
 
 impl std::fmt::Debug for ExecutionConfig {
@@ -86,6 +92,8 @@ impl Default for ExecutionConfig {
             processed_transactions_detailed_counters: false,
             transaction_filter: Filter::empty(),
             genesis_waypoint: None,
+            block_output_stream_capacity: 1_000,
+            access_summary_file: None,
         }
     }
 }
@@ -157,6 +165,14 @@ impl ConfigSanitizer for ExecutionConfig {
         let sanitizer_name = Self::get_sanitizer_name();
         let execution_config = &node_config.execution;
 
+        // Verify that the block output stream can buffer blocks
+        if execution_config.block_output_stream_capacity == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "block_output_stream_capacity must be greater than 0!".into(),
+            ));
+        }
+
         // If this is a mainnet node, ensure that additional verifiers are enabled
         if let Some(chain_id) = chain_id {
             if chain_id.is_mainnet() {
@@ -429,6 +445,24 @@ mod test {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_block_output_stream_capacity() {
+        // Create a node config with an empty block output stream
+        let node_config = NodeConfig {
+            execution: ExecutionConfig {
+                block_output_stream_capacity: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            ExecutionConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::test()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     #[test]
     fn test_no_genesis() {
         let (mut config, path) = generate_config();
@@ -30,6 +30,8 @@ pub struct GasEstimationConfig {
     pub market_block_history: usize,
     /// Maximum number of blocks read for aggressive gas estimation
     pub aggressive_block_history: usize,
+    /// Maximum number of blocks read for the gas estimate percentiles
+    pub percentile_block_history: usize,
     /// Time after write when previous value is returned without recomputing
     pub cache_expiration_ms: u64,
 }
@@ -43,6 +45,7 @@ impl Default for GasEstimationConfig {
             low_block_history: 10,
             market_block_history: 30,
             aggressive_block_history: 120,
+            percentile_block_history: 30,
             cache_expiration_ms: 500,
         }
     }
@@ -61,14 +64,17 @@ impl ConfigSanitizer for GasEstimationConfig {
         if gas_estimation_config.low_block_history > gas_estimation_config.aggressive_block_history
             || gas_estimation_config.market_block_history
                 > gas_estimation_config.aggressive_block_history
+            || gas_estimation_config.percentile_block_history
+                > gas_estimation_config.aggressive_block_history
         {
             return Err(Error::ConfigSanitizerFailed(
                 sanitizer_name,
                 format!(
-                    "aggressive block history {} must be > low {}, market {}",
+                    "aggressive block history {} must be > low {}, market {}, percentile {}",
                     gas_estimation_config.aggressive_block_history,
                     gas_estimation_config.low_block_history,
-                    gas_estimation_config.market_block_history
+                    gas_estimation_config.market_block_history,
+                    gas_estimation_config.percentile_block_history
                 ),
             ));
         }
@@ -76,14 +82,16 @@ impl ConfigSanitizer for GasEstimationConfig {
         if gas_estimation_config.low_block_history == 0
             || gas_estimation_config.market_block_history == 0
             || gas_estimation_config.aggressive_block_history == 0
+            || gas_estimation_config.percentile_block_history == 0
         {
             return Err(Error::ConfigSanitizerFailed(
                 sanitizer_name,
                 format!(
-                    "low {}, market {}, aggressive {} block history must be > 0",
+                    "low {}, market {}, aggressive {}, percentile {} block history must be > 0",
                     gas_estimation_config.low_block_history,
                     gas_estimation_config.market_block_history,
-                    gas_estimation_config.aggressive_block_history
+                    gas_estimation_config.aggressive_block_history,
+                    gas_estimation_config.percentile_block_history
                 ),
             ));
         }
@@ -147,6 +155,31 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_invalid_aggressive_percentile_block_history() {
+        // Create a node config with an aggressive block history that is too low
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                gas_estimation: GasEstimationConfig {
+                    percentile_block_history: 121,
+                    aggressive_block_history: 120,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = GasEstimationConfig::sanitize(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     #[test]
     fn test_sanitize_invalid_zero_low_block_history() {
         // Create a node config with a zero low block history
@@ -20,6 +20,10 @@ pub struct IndexerTableInfoConfig {
     pub parser_batch_size: u16,
 
     pub enable_expensive_logging: bool,
+
+    /// If set and the node has no local table info db yet, the latest db snapshot is restored
+    /// from this GCS bucket and parsing resumes from the snapshot's version instead of genesis
+    pub db_snapshot_gcs_bucket: Option<String>,
 }
 
 // Reminder, #[serde(default)] on IndexerTableInfoConfig means that the default values for
@@ -32,6 +36,7 @@ impl Default for IndexerTableInfoConfig {
             parser_task_count: DEFAULT_PARSER_TASK_COUNT,
             parser_batch_size: DEFAULT_PARSER_BATCH_SIZE,
             enable_expensive_logging: false,
+            db_snapshot_gcs_bucket: None,
         }
     }
 }
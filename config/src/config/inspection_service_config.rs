@@ -4,7 +4,7 @@
 use crate::{
     config::{
         config_optimizer::ConfigOptimizer, config_sanitizer::ConfigSanitizer,
-        node_config_loader::NodeType, Error, NodeConfig,
+        node_config_loader::NodeType, AuthenticationConfig, Error, NodeConfig,
     },
     utils,
 };
@@ -20,6 +20,9 @@ pub struct InspectionServiceConfig {
     pub expose_configuration: bool,
     pub expose_peer_information: bool,
     pub expose_system_information: bool,
+    // The authentication configs of the log filters endpoint, which can change
+    // the log filters at runtime. If empty, the endpoint is disabled.
+    pub log_filters_authentication_configs: Vec<AuthenticationConfig>,
 }
 
 impl Default for InspectionServiceConfig {
@@ -30,6 +33,7 @@ impl Default for InspectionServiceConfig {
             expose_configuration: false,
             expose_peer_information: true,
             expose_system_information: true,
+            log_filters_authentication_configs: vec![],
         }
     }
 }
@@ -54,12 +54,65 @@ pub struct MempoolConfig {
     pub system_transaction_timeout_secs: u64,
     /// Interval to garbage collect and remove transactions that have expired from the Mempool.
     pub system_transaction_gc_interval_ms: u64,
+    /// Overrides of `system_transaction_timeout_secs` for classes of transactions.
+    pub payload_ttl_policy: PayloadTtlPolicyConfig,
     /// Gas unit price buckets for broadcasting to upstream nodes.
     ///
     /// Overriding this won't make much of a difference if the upstream nodes don't match.
     pub broadcast_buckets: Vec<u64>,
     pub eager_expire_threshold_ms: Option<u64>,
     pub eager_expire_time_ms: u64,
+    /// Minimum gas unit price increase, in percent, for a transaction to replace a pending one
+    /// with the same sender and sequence number but e.g. a different payload.
+    ///
+    /// Replacing with a different transaction is disabled if unset. Resubmitting the same
+    /// transaction with a higher gas unit price is always allowed.
+    pub replace_by_fee_min_gas_price_bump_pct: Option<u64>,
+    /// Gas unit price bands with a quota on how much of a batch pulled by consensus their
+    /// transactions may take, so that a flood of cheap transactions can't fill the batches.
+    ///
+    /// Bands must be sorted by their minimum gas unit price. Transactions priced below the
+    /// first band are not limited.
+    pub priority_bands: Vec<PriorityBandConfig>,
+}
+
+/// A band of gas unit prices, from its minimum up to the minimum of the next band
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PriorityBandConfig {
+    pub min_gas_unit_price: u64,
+    /// Maximum percentage of the transactions of a batch pulled by consensus that are in the
+    /// band
+    pub max_batch_pct: u64,
+}
+
+/// Overrides of the system transaction timeout for classes of transactions, which may be
+/// worth keeping in Mempool for a shorter (or longer) time than the others. If several
+/// classes apply to a transaction, the shortest of their timeouts is used. Transactions
+/// of classes without a timeout use `system_transaction_timeout_secs`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PayloadTtlPolicyConfig {
+    /// Timeout for keyless transactions. Regardless of it, keyless transactions are removed
+    /// once their ephemeral key expires, as they can't be executed anymore.
+    pub keyless_transaction_timeout_secs: Option<u64>,
+    /// Timeout for transactions with a script payload
+    pub script_transaction_timeout_secs: Option<u64>,
+    /// Timeout for large transactions (e.g., publishing packages)
+    pub large_transaction_timeout_secs: Option<u64>,
+    /// Minimum size (in bytes) of a large transaction
+    pub large_transaction_min_bytes: u64,
+}
+
+impl Default for PayloadTtlPolicyConfig {
+    fn default() -> Self {
+        Self {
+            keyless_transaction_timeout_secs: None,
+            script_transaction_timeout_secs: None,
+            large_transaction_timeout_secs: None,
+            large_transaction_min_bytes: 64 * 1024, // 64 KiB
+        }
+    }
 }
 
 impl Default for MempoolConfig {
@@ -84,20 +137,46 @@ impl Default for MempoolConfig {
             shared_mempool_failover_delay_ms: 500,
             system_transaction_timeout_secs: 600,
             system_transaction_gc_interval_ms: 60_000,
+            payload_ttl_policy: PayloadTtlPolicyConfig::default(),
             broadcast_buckets: DEFAULT_BUCKETS.to_vec(),
             eager_expire_threshold_ms: Some(10_000),
             eager_expire_time_ms: 3_000,
+            replace_by_fee_min_gas_price_bump_pct: None,
+            priority_bands: vec![],
         }
     }
 }
 
 impl ConfigSanitizer for MempoolConfig {
     fn sanitize(
-        _node_config: &NodeConfig,
+        node_config: &NodeConfig,
         _node_type: NodeType,
         _chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
-        Ok(()) // TODO: add reasonable verifications
+        let sanitizer_name = Self::get_sanitizer_name();
+        let priority_bands = &node_config.mempool.priority_bands;
+
+        // Verify that the priority bands are sorted and have a valid quota
+        if priority_bands
+            .windows(2)
+            .any(|bands| bands[0].min_gas_unit_price >= bands[1].min_gas_unit_price)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Priority bands must be sorted by min_gas_unit_price!".into(),
+            ));
+        }
+        if priority_bands
+            .iter()
+            .any(|band| band.max_batch_pct == 0 || band.max_batch_pct > 100)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The max_batch_pct of priority bands must be between 1 and 100!".into(),
+            ));
+        }
+
+        Ok(())
     }
 }
 
@@ -252,4 +331,42 @@ mod tests {
             local_max_broadcasts_per_peer
         );
     }
+
+    #[test]
+    fn test_sanitize_priority_bands() {
+        // Create a node config with valid priority bands
+        let mut node_config = NodeConfig {
+            mempool: MempoolConfig {
+                priority_bands: vec![
+                    PriorityBandConfig {
+                        min_gas_unit_price: 100,
+                        max_batch_pct: 50,
+                    },
+                    PriorityBandConfig {
+                        min_gas_unit_price: 150,
+                        max_batch_pct: 100,
+                    },
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        MempoolConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+            .unwrap();
+
+        // Verify that unsorted bands are rejected
+        node_config.mempool.priority_bands.reverse();
+        let error =
+            MempoolConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that an empty quota is rejected
+        node_config.mempool.priority_bands.reverse();
+        node_config.mempool.priority_bands[0].max_batch_pct = 0;
+        let error =
+            MempoolConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
@@ -27,7 +27,10 @@ mod netbench_config;
 mod network_config;
 mod node_config;
 mod node_config_loader;
+mod node_config_reload;
+mod node_config_validation;
 mod node_startup_config;
+mod otlp_exporter_config;
 mod override_node_config;
 mod peer_monitoring_config;
 mod persistable_config;
@@ -60,6 +63,9 @@ pub use netbench_config::*;
 pub use network_config::*;
 pub use node_config::*;
 pub use node_config_loader::sanitize_node_config;
+pub use node_config_reload::*;
+pub use node_config_validation::validate_node_config;
+pub use otlp_exporter_config::*;
 pub use override_node_config::*;
 pub use peer_monitoring_config::*;
 pub use persistable_config::*;
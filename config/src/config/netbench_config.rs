@@ -22,6 +22,41 @@ pub struct NetbenchConfig {
     pub rpc_data_size: usize,
     pub rpc_per_second: u64,
     pub rpc_in_flight: usize,
+    pub rpc_echo: bool, // Whether RPC responses echo the request payload back (i.e., round-trip the data)
+    pub rpc_payload_distribution: NetbenchPayloadDistribution, // The distribution of RPC payload sizes
+}
+
+/// The distribution of the payload sizes of netbench requests
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NetbenchPayloadDistribution {
+    /// All payloads have the configured data size
+    Fixed,
+    /// Payload sizes are sampled uniformly from `[min_bytes, max_bytes]`
+    Uniform { min_bytes: usize, max_bytes: usize },
+    /// Payloads are small, except for the given percentage of large payloads
+    /// (e.g., to mimic votes interleaved with blocks).
+    Bimodal {
+        small_bytes: usize,
+        large_bytes: usize,
+        large_percentage: u8,
+    },
+}
+
+impl NetbenchPayloadDistribution {
+    /// Returns the largest payload size of the distribution, where
+    /// `data_size` is the configured (fixed) data size.
+    pub fn max_payload_size(&self, data_size: usize) -> usize {
+        match self {
+            NetbenchPayloadDistribution::Fixed => data_size,
+            NetbenchPayloadDistribution::Uniform { max_bytes, .. } => *max_bytes,
+            NetbenchPayloadDistribution::Bimodal {
+                small_bytes,
+                large_bytes,
+                ..
+            } => (*small_bytes).max(*large_bytes),
+        }
+    }
 }
 
 impl Default for NetbenchConfig {
@@ -39,6 +74,8 @@ impl Default for NetbenchConfig {
             rpc_data_size: 100 * 1024, // 100 KB
             rpc_per_second: 1_000,
             rpc_in_flight: 8,
+            rpc_echo: false,
+            rpc_payload_distribution: NetbenchPayloadDistribution::Fixed,
         }
     }
 }
@@ -73,6 +110,34 @@ impl ConfigSanitizer for NetbenchConfig {
             }
         }
 
+        // Verify that the RPC payload distribution is valid
+        match netbench_config.rpc_payload_distribution {
+            NetbenchPayloadDistribution::Uniform {
+                min_bytes,
+                max_bytes,
+            } if min_bytes > max_bytes => {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The uniform RPC payload distribution has min_bytes ({}) > max_bytes ({})!",
+                        min_bytes, max_bytes
+                    ),
+                ));
+            },
+            NetbenchPayloadDistribution::Bimodal {
+                large_percentage, ..
+            } if large_percentage > 100 => {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The bimodal RPC payload distribution has large_percentage ({}) > 100!",
+                        large_percentage
+                    ),
+                ));
+            },
+            _ => {},
+        }
+
         Ok(())
     }
 }
@@ -107,4 +172,42 @@ mod test {
         // Verify that the config passes sanitization (for an unknown network)
         NetbenchConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
     }
+
+    #[test]
+    fn test_sanitize_payload_distribution() {
+        // Create a netbench config with an invalid uniform distribution
+        let node_config = NodeConfig {
+            netbench: Some(NetbenchConfig {
+                enabled: true,
+                rpc_payload_distribution: NetbenchPayloadDistribution::Uniform {
+                    min_bytes: 1024,
+                    max_bytes: 10,
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error = NetbenchConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a netbench config with an invalid bimodal distribution
+        let node_config = NodeConfig {
+            netbench: Some(NetbenchConfig {
+                enabled: true,
+                rpc_payload_distribution: NetbenchPayloadDistribution::Bimodal {
+                    small_bytes: 10,
+                    large_bytes: 1024,
+                    large_percentage: 101,
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error = NetbenchConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
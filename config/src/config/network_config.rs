@@ -53,6 +53,8 @@ pub const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024; /* 64 MiB */
 pub const CONNECTION_BACKOFF_BASE: u64 = 2;
 pub const IP_BYTE_BUCKET_RATE: usize = 102400 /* 100 KiB */;
 pub const IP_BYTE_BUCKET_SIZE: usize = IP_BYTE_BUCKET_RATE;
+pub const RPC_REQUEST_BUCKET_RATE: usize = 100; /* 100 requests per second */
+pub const RPC_REQUEST_BUCKET_SIZE: usize = 2 * RPC_REQUEST_BUCKET_RATE;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -121,6 +123,9 @@ pub struct NetworkConfig {
     pub inbound_rate_limit_config: Option<RateLimitConfig>,
     /// Outbound rate limiting configuration, if not specified, no rate limiting
     pub outbound_rate_limit_config: Option<RateLimitConfig>,
+    /// Inbound rpc rate limiting configuration (per peer and protocol), if not
+    /// specified, no rate limiting
+    pub inbound_rpc_rate_limit_config: Option<RpcRateLimitConfig>,
     /// The maximum size of an inbound or outbound message (it may be divided into multiple frame)
     pub max_message_size: usize,
     /// The maximum number of parallel message deserialization tasks that can run (per application)
@@ -162,6 +167,7 @@ impl NetworkConfig {
             max_inbound_connections: MAX_INBOUND_CONNECTIONS,
             inbound_rate_limit_config: None,
             outbound_rate_limit_config: None,
+            inbound_rpc_rate_limit_config: None,
             max_message_size: MAX_MESSAGE_SIZE,
             inbound_rx_buffer_size_bytes: None,
             inbound_tx_buffer_size_bytes: None,
@@ -392,6 +398,27 @@ impl Default for RateLimitConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcRateLimitConfig {
+    /// Maximum number of rpc requests/s for a peer and protocol
+    pub request_bucket_rate: usize,
+    /// Maximum burst of rpc requests for a peer and protocol
+    pub request_bucket_size: usize,
+    /// Allow for disabling the throttles
+    pub enabled: bool,
+}
+
+impl Default for RpcRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            request_bucket_rate: RPC_REQUEST_BUCKET_RATE,
+            request_bucket_size: RPC_REQUEST_BUCKET_SIZE,
+            enabled: true,
+        }
+    }
+}
+
 pub type PeerSet = HashMap<PeerId, Peer>;
 
 // TODO: Combine with RoleType?
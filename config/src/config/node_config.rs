@@ -9,7 +9,7 @@ use crate::{
         node_config_loader::NodeConfigLoader, node_startup_config::NodeStartupConfig,
         persistable_config::PersistableConfig, utils::RootPath, AdminServiceConfig, ApiConfig,
         BaseConfig, ConsensusConfig, Error, ExecutionConfig, IndexerConfig, IndexerGrpcConfig,
-        InspectionServiceConfig, LoggerConfig, MempoolConfig, NetworkConfig,
+        InspectionServiceConfig, LoggerConfig, MempoolConfig, NetworkConfig, OtlpExporterConfig,
         PeerMonitoringServiceConfig, SafetyRulesTestConfig, StateSyncConfig, StorageConfig,
     },
     network_id::NetworkId,
@@ -72,6 +72,8 @@ pub struct NodeConfig {
     #[serde(default)]
     pub node_startup: NodeStartupConfig,
     #[serde(default)]
+    pub otlp_exporter: OtlpExporterConfig,
+    #[serde(default)]
     pub peer_monitoring_service: PeerMonitoringServiceConfig,
     /// In a randomness stall, set this to be on-chain `RandomnessConfigSeqNum` + 1.
     /// Once enough nodes restarted with the new value, the chain should unblock with randomness disabled.
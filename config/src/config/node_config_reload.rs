@@ -0,0 +1,246 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{Error, NodeConfig};
+use serde_yaml::Value;
+use std::fmt::{Display, Formatter};
+
+/// A field of the node config that can be reloaded without restarting the node
+struct ReloadableField {
+    /// The path of the field in the node config yaml, e.g., `logger.level`
+    path: &'static str,
+    /// Copies the field from the new config to the running config
+    reload: fn(&mut NodeConfig, &NodeConfig),
+}
+
+/// All fields of the node config that can be reloaded without restarting the node.
+/// Note: new fields should only be added here if the node applies them on reload.
+const RELOADABLE_FIELDS: &[ReloadableField] = &[
+    ReloadableField {
+        path: "logger.level",
+        reload: |config, new_config| config.logger.level = new_config.logger.level,
+    },
+    ReloadableField {
+        path: "logger.telemetry_level",
+        reload: |config, new_config| {
+            config.logger.telemetry_level = new_config.logger.telemetry_level
+        },
+    },
+    ReloadableField {
+        path: "mempool.capacity",
+        reload: |config, new_config| config.mempool.capacity = new_config.mempool.capacity,
+    },
+    ReloadableField {
+        path: "mempool.capacity_bytes",
+        reload: |config, new_config| {
+            config.mempool.capacity_bytes = new_config.mempool.capacity_bytes
+        },
+    },
+    ReloadableField {
+        path: "mempool.capacity_per_user",
+        reload: |config, new_config| {
+            config.mempool.capacity_per_user = new_config.mempool.capacity_per_user
+        },
+    },
+    ReloadableField {
+        path: "state_sync.data_streaming_service.max_concurrent_requests",
+        reload: |config, new_config| {
+            config
+                .state_sync
+                .data_streaming_service
+                .max_concurrent_requests = new_config
+                .state_sync
+                .data_streaming_service
+                .max_concurrent_requests
+        },
+    },
+    ReloadableField {
+        path: "state_sync.data_streaming_service.max_concurrent_state_requests",
+        reload: |config, new_config| {
+            config
+                .state_sync
+                .data_streaming_service
+                .max_concurrent_state_requests = new_config
+                .state_sync
+                .data_streaming_service
+                .max_concurrent_state_requests
+        },
+    },
+];
+
+/// A report of the changed fields found when reloading the node config
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NodeConfigReloadReport {
+    /// The changed fields that were reloaded
+    pub reloaded_fields: Vec<String>,
+    /// The changed fields that can't be reloaded, and require a restart to be applied
+    pub rejected_fields: Vec<String>,
+}
+
+impl NodeConfigReloadReport {
+    /// Returns true iff no fields changed
+    pub fn is_empty(&self) -> bool {
+        self.reloaded_fields.is_empty() && self.rejected_fields.is_empty()
+    }
+}
+
+impl Display for NodeConfigReloadReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "The node config is unchanged");
+        }
+
+        write!(f, "Reloaded fields: {:?}", self.reloaded_fields)?;
+        if !self.rejected_fields.is_empty() {
+            write!(
+                f,
+                ". Rejected fields (the node must be restarted to apply them): {:?}",
+                self.rejected_fields
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl NodeConfig {
+    /// Reloads the node config from the given new config. Only the changed fields that
+    /// can be reloaded without restarting the node are copied over, the other changed
+    /// fields are rejected (i.e., they keep their current values). Returns the reloaded
+    /// config, and a report of the reloaded and rejected fields.
+    pub fn reload_from(
+        &self,
+        new_config: &NodeConfig,
+    ) -> Result<(NodeConfig, NodeConfigReloadReport), Error> {
+        // Find the fields that changed
+        let mut changed_fields = vec![];
+        diff_config_yaml(
+            "",
+            &config_to_yaml(self)?,
+            &config_to_yaml(new_config)?,
+            &mut changed_fields,
+        );
+
+        // Reload the changed fields that are reloadable, and reject the others
+        let mut reloaded_config = self.clone();
+        let mut report = NodeConfigReloadReport::default();
+        for changed_field in changed_fields {
+            match RELOADABLE_FIELDS
+                .iter()
+                .find(|field| field.path == changed_field)
+            {
+                Some(field) => {
+                    (field.reload)(&mut reloaded_config, new_config);
+                    report.reloaded_fields.push(changed_field);
+                },
+                None => report.rejected_fields.push(changed_field),
+            }
+        }
+
+        Ok((reloaded_config, report))
+    }
+}
+
+/// Serializes the node config into a yaml value
+fn config_to_yaml(node_config: &NodeConfig) -> Result<Value, Error> {
+    serde_yaml::to_value(node_config)
+        .map_err(|error| Error::Yaml("Failed to serialize the node config".into(), error))
+}
+
+/// Collects the paths of all leaf values that differ between the two config yamls
+fn diff_config_yaml(
+    path: &str,
+    value: &Value,
+    new_value: &Value,
+    changed_fields: &mut Vec<String>,
+) {
+    match (value, new_value) {
+        (Value::Mapping(mapping), Value::Mapping(new_mapping)) => {
+            let keys = mapping.iter().map(|(key, _)| key).chain(
+                new_mapping
+                    .iter()
+                    .map(|(key, _)| key)
+                    .filter(|key| !mapping.contains_key(key)),
+            );
+            for key in keys {
+                let key_path = match key {
+                    Value::String(key) if path.is_empty() => key.clone(),
+                    Value::String(key) => format!("{}.{}", path, key),
+                    key => format!("{}.{:?}", path, key),
+                };
+                diff_config_yaml(
+                    &key_path,
+                    mapping.get(key).unwrap_or(&Value::Null),
+                    new_mapping.get(key).unwrap_or(&Value::Null),
+                    changed_fields,
+                );
+            }
+        },
+        (value, new_value) => {
+            if value != new_value {
+                changed_fields.push(path.to_string());
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_logger::Level;
+
+    #[test]
+    fn test_reload_unchanged_config() {
+        let node_config = NodeConfig::default();
+        let (reloaded_config, report) = node_config.reload_from(&node_config).unwrap();
+
+        assert!(report.is_empty());
+        assert_eq!(reloaded_config, node_config);
+    }
+
+    #[test]
+    fn test_reload_reloadable_fields() {
+        // Change some of the reloadable fields
+        let node_config = NodeConfig::default();
+        let mut new_config = node_config.clone();
+        new_config.logger.level = Level::Debug;
+        new_config.mempool.capacity_per_user = 1_000;
+        new_config
+            .state_sync
+            .data_streaming_service
+            .max_concurrent_requests = 1;
+
+        // Verify the fields are reloaded
+        let (reloaded_config, report) = node_config.reload_from(&new_config).unwrap();
+        assert_eq!(report.reloaded_fields, vec![
+            "logger.level".to_string(),
+            "mempool.capacity_per_user".to_string(),
+            "state_sync.data_streaming_service.max_concurrent_requests".to_string(),
+        ]);
+        assert!(report.rejected_fields.is_empty());
+        assert_eq!(reloaded_config, new_config);
+    }
+
+    #[test]
+    fn test_reload_rejects_other_fields() {
+        // Change a reloadable and a non-reloadable field
+        let node_config = NodeConfig::default();
+        let mut new_config = node_config.clone();
+        new_config.mempool.capacity = 10;
+        new_config.mempool.max_broadcasts_per_peer = 10;
+        new_config.api.enabled = !node_config.api.enabled;
+
+        // Verify only the reloadable field is reloaded
+        let (reloaded_config, report) = node_config.reload_from(&new_config).unwrap();
+        assert_eq!(report.reloaded_fields, vec!["mempool.capacity".to_string()]);
+        assert_eq!(report.rejected_fields, vec![
+            "api.enabled".to_string(),
+            "mempool.max_broadcasts_per_peer".to_string(),
+        ]);
+        assert_eq!(reloaded_config.mempool.capacity, 10);
+        assert_eq!(
+            reloaded_config.mempool.max_broadcasts_per_peer,
+            node_config.mempool.max_broadcasts_per_peer
+        );
+        assert_eq!(reloaded_config.api.enabled, node_config.api.enabled);
+    }
+}
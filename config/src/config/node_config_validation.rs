@@ -0,0 +1,370 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{Error, NodeConfig};
+use serde_yaml::{Mapping, Value};
+use std::borrow::Cow;
+
+/// The maximum number of unknown fields reported for a single node config
+const MAX_NUM_UNKNOWN_FIELDS: usize = 100;
+
+/// The maximum edit distance between an unknown field and
+/// an expected field, for the expected field to be suggested.
+const MAX_SUGGESTION_EDIT_DISTANCE: usize = 3;
+
+/// A deprecated field of the node config, and the field that replaces it
+struct DeprecatedField {
+    /// The path of the deprecated field, e.g., `mempool.old_field`
+    path: &'static str,
+    /// The path of the field that replaces it, e.g., `mempool.new_field`
+    replacement: &'static str,
+}
+
+/// The deprecated fields of the node config. When a deprecated field is found in
+/// the config file, its value is moved to the replacement field and a warning is
+/// returned, so that operators can update their configs before the field is removed.
+const DEPRECATED_FIELDS: &[DeprecatedField] = &[];
+
+/// Parses and validates the given serialized node config. Deprecated fields are
+/// mapped to their replacements (and a warning is returned for each of them). If
+/// the config contains unknown fields (e.g., typos), all of them are reported in
+/// the error, together with the expected fields they most likely refer to.
+pub fn validate_node_config(serialized_config: &str) -> Result<(NodeConfig, Vec<String>), Error> {
+    // Parse the config into a yaml value and map the deprecated fields
+    let mut config_yaml: Value = serde_yaml::from_str(serialized_config)
+        .map_err(|error| Error::Yaml("config".to_string(), error))?;
+    let warnings = map_deprecated_fields(&mut config_yaml, DEPRECATED_FIELDS)?;
+
+    // Only re-serialize the config if fields were mapped, to parse
+    // the original config exactly as it would be parsed otherwise.
+    let serialized_config = if warnings.is_empty() {
+        Cow::Borrowed(serialized_config)
+    } else {
+        Cow::Owned(
+            serde_yaml::to_string(&config_yaml)
+                .map_err(|error| Error::Yaml("config".to_string(), error))?,
+        )
+    };
+
+    // Parse the node config, and report all unknown fields on failure
+    match serde_yaml::from_str(&serialized_config) {
+        Ok(node_config) => Ok((node_config, warnings)),
+        Err(error) => {
+            let unknown_fields = find_unknown_fields(config_yaml, &error);
+            if unknown_fields.is_empty() {
+                Err(Error::Yaml("config".to_string(), error))
+            } else {
+                Err(Error::UnknownFields(unknown_fields))
+            }
+        },
+    }
+}
+
+/// Moves the values of the deprecated fields to their replacements,
+/// and returns a warning for each deprecated field that was found.
+fn map_deprecated_fields(
+    config_yaml: &mut Value,
+    deprecated_fields: &[DeprecatedField],
+) -> Result<Vec<String>, Error> {
+    let mut warnings = vec![];
+    for deprecated_field in deprecated_fields {
+        let value = match remove_value(config_yaml, deprecated_field.path) {
+            Some(value) => value,
+            None => continue,
+        };
+        if get_value(config_yaml, deprecated_field.replacement).is_some() {
+            return Err(Error::InvariantViolation(format!(
+                "The deprecated field `{}` and its replacement `{}` are both set! Remove the deprecated field.",
+                deprecated_field.path, deprecated_field.replacement
+            )));
+        }
+        insert_value(config_yaml, deprecated_field.replacement, value)?;
+        warnings.push(format!(
+            "The field `{}` is deprecated, and was mapped to `{}`. Update the config, as the deprecated field will be removed.",
+            deprecated_field.path, deprecated_field.replacement
+        ));
+    }
+    Ok(warnings)
+}
+
+/// Returns the value at the given (dot separated) path, if it exists
+fn get_value<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(value, |value, key| {
+        value.as_mapping()?.get(&Value::String(key.to_string()))
+    })
+}
+
+/// Removes and returns the value at the given (dot separated) path, if it exists
+fn remove_value(value: &mut Value, path: &str) -> Option<Value> {
+    let (parent_path, key) = match path.rsplit_once('.') {
+        Some((parent_path, key)) => (Some(parent_path), key),
+        None => (None, path),
+    };
+    let parent = match parent_path {
+        Some(parent_path) => get_value_mut(value, parent_path)?,
+        None => value,
+    };
+    parent
+        .as_mapping_mut()?
+        .remove(&Value::String(key.to_string()))
+}
+
+/// Returns a mutable reference to the value at the given (dot separated) path
+fn get_value_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split('.').try_fold(value, |value, key| {
+        value
+            .as_mapping_mut()?
+            .get_mut(&Value::String(key.to_string()))
+    })
+}
+
+/// Inserts the value at the given (dot separated) path, creating the missing parents
+fn insert_value(value: &mut Value, path: &str, new_value: Value) -> Result<(), Error> {
+    let mut keys = path.split('.').peekable();
+    let mut value = value;
+    while let Some(key) = keys.next() {
+        let mapping = value.as_mapping_mut().ok_or_else(|| {
+            Error::InvariantViolation(format!(
+                "Unable to insert the field `{}`, its parent is not a mapping!",
+                path
+            ))
+        })?;
+        let key = Value::String(key.to_string());
+        if keys.peek().is_none() {
+            mapping.insert(key, new_value);
+            return Ok(());
+        }
+        if !mapping.contains_key(&key) {
+            mapping.insert(key.clone(), Value::Mapping(Mapping::new()));
+        }
+        value = mapping.get_mut(&key).expect("The key was just inserted!");
+    }
+    Ok(())
+}
+
+/// Finds all unknown fields in the config, by repeatedly removing the unknown
+/// field reported by the deserialization error, until no unknown field is left.
+fn find_unknown_fields(mut config_yaml: Value, error: &serde_yaml::Error) -> Vec<String> {
+    let mut unknown_fields = vec![];
+    let mut error_message = error.to_string();
+    while unknown_fields.len() < MAX_NUM_UNKNOWN_FIELDS {
+        // Identify the unknown field and remove it from the config
+        let (field, expected_fields) = match parse_unknown_field_error(&error_message) {
+            Some(unknown_field) => unknown_field,
+            None => break,
+        };
+        let path = match remove_unknown_field(&mut config_yaml, "", &field, &expected_fields, true)
+            .or_else(|| remove_unknown_field(&mut config_yaml, "", &field, &expected_fields, false))
+        {
+            Some(path) => path,
+            None => break,
+        };
+
+        // Describe the unknown field
+        let description = match suggest_field(&field, &expected_fields) {
+            Some(suggestion) => format!("`{}` (did you mean `{}`?)", path, suggestion),
+            None => format!("`{}`", path),
+        };
+        unknown_fields.push(description);
+
+        // Parse the config again, to find the next unknown field
+        let result = serde_yaml::to_string(&config_yaml)
+            .and_then(|serialized_config| serde_yaml::from_str::<NodeConfig>(&serialized_config));
+        match result {
+            Ok(_) => break,
+            Err(error) => error_message = error.to_string(),
+        }
+    }
+    unknown_fields
+}
+
+/// Parses an unknown field error of serde (e.g., "unknown field `capacityy`,
+/// expected one of `capacity`, `capacity_bytes`"), and returns the unknown
+/// field and the expected fields.
+fn parse_unknown_field_error(error_message: &str) -> Option<(String, Vec<String>)> {
+    const UNKNOWN_FIELD_PREFIX: &str = "unknown field `";
+    let start = error_message.find(UNKNOWN_FIELD_PREFIX)? + UNKNOWN_FIELD_PREFIX.len();
+    let (field, remaining_message) = error_message[start..].split_once('`')?;
+
+    // The expected fields are quoted with backticks after "expected"
+    let expected_fields = match remaining_message.split_once("expected") {
+        Some((_, expected_fields)) => expected_fields
+            .split('`')
+            .skip(1)
+            .step_by(2)
+            .map(|expected_field| expected_field.to_string())
+            .collect(),
+        None => vec![],
+    };
+
+    Some((field.to_string(), expected_fields))
+}
+
+/// Removes the unknown field from the first mapping that contains it, and returns the path
+/// of the field. If `match_expected_fields` is set, only mappings with no other unexpected
+/// fields are considered, to find the mapping the error refers to.
+fn remove_unknown_field(
+    value: &mut Value,
+    path: &str,
+    field: &str,
+    expected_fields: &[String],
+    match_expected_fields: bool,
+) -> Option<String> {
+    match value {
+        Value::Mapping(mapping) => {
+            let field_key = Value::String(field.to_string());
+            if mapping.contains_key(&field_key)
+                && (!match_expected_fields
+                    || mapping.iter().all(|(key, _)| {
+                        key == &field_key
+                            || key.as_str().map_or(false, |key| {
+                                expected_fields.iter().any(|expected| expected == key)
+                            })
+                    }))
+            {
+                mapping.remove(&field_key);
+                return Some(join_path(path, field));
+            }
+
+            for (key, value) in mapping.iter_mut() {
+                let key = match key {
+                    Value::String(key) => key.clone(),
+                    key => format!("{:?}", key),
+                };
+                let path = join_path(path, &key);
+                if let Some(path) = remove_unknown_field(
+                    value,
+                    &path,
+                    field,
+                    expected_fields,
+                    match_expected_fields,
+                ) {
+                    return Some(path);
+                }
+            }
+            None
+        },
+        Value::Sequence(values) => values.iter_mut().enumerate().find_map(|(index, value)| {
+            let path = format!("{}[{}]", path, index);
+            remove_unknown_field(value, &path, field, expected_fields, match_expected_fields)
+        }),
+        _ => None,
+    }
+}
+
+/// Joins the path of a mapping with the key of one of its fields
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Returns the expected field closest to the unknown field, if it is close enough
+fn suggest_field<'a>(field: &str, expected_fields: &'a [String]) -> Option<&'a str> {
+    expected_fields
+        .iter()
+        .map(|expected_field| (edit_distance(field, expected_field), expected_field))
+        .filter(|(distance, _)| {
+            *distance <= MAX_SUGGESTION_EDIT_DISTANCE && *distance < field.len()
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, expected_field)| expected_field.as_str())
+}
+
+/// Returns the Levenshtein distance between the two strings
+fn edit_distance(first: &str, second: &str) -> usize {
+    let second: Vec<char> = second.chars().collect();
+    let mut distances: Vec<usize> = (0..=second.len()).collect();
+    for (i, first_char) in first.chars().enumerate() {
+        let mut previous_diagonal = distances[0];
+        distances[0] = i + 1;
+        for (j, second_char) in second.iter().enumerate() {
+            let substitution_cost = if first_char == *second_char { 0 } else { 1 };
+            let distance = (previous_diagonal + substitution_cost)
+                .min(distances[j] + 1)
+                .min(distances[j + 1] + 1);
+            previous_diagonal = distances[j + 1];
+            distances[j + 1] = distance;
+        }
+    }
+    distances[second.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_valid_config() {
+        let serialized_config = include_str!("test_data/validator.yaml");
+        let (node_config, warnings) = validate_node_config(serialized_config).unwrap();
+
+        assert!(warnings.is_empty());
+        assert!(node_config.base.role.is_validator());
+    }
+
+    #[test]
+    fn test_validate_unknown_fields() {
+        let serialized_config = r#"
+            api:
+                enabld: true
+            mempool:
+                capacity: 100
+                capacityy: 200
+            not_a_config: 1
+        "#;
+        let error = validate_node_config(serialized_config).unwrap_err();
+
+        // Verify all unknown fields are reported, with suggestions for the typos
+        match error {
+            Error::UnknownFields(unknown_fields) => {
+                assert_eq!(unknown_fields.len(), 3);
+                assert!(unknown_fields.contains(&"`api.enabld` (did you mean `enabled`?)".into()));
+                assert!(unknown_fields
+                    .contains(&"`mempool.capacityy` (did you mean `capacity`?)".into()));
+                assert!(unknown_fields.contains(&"`not_a_config`".into()));
+            },
+            error => panic!("Expected unknown fields, but got: {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_map_deprecated_fields() {
+        let deprecated_fields = &[DeprecatedField {
+            path: "mempool.old_capacity",
+            replacement: "mempool.capacity",
+        }];
+
+        // Verify the deprecated field is mapped to its replacement
+        let mut config_yaml: Value = serde_yaml::from_str("mempool:\n  old_capacity: 10").unwrap();
+        let warnings = map_deprecated_fields(&mut config_yaml, deprecated_fields).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            config_yaml,
+            serde_yaml::from_str::<Value>("mempool:\n  capacity: 10").unwrap()
+        );
+
+        // Verify setting both the deprecated field and its replacement is rejected
+        let mut config_yaml: Value =
+            serde_yaml::from_str("mempool:\n  old_capacity: 10\n  capacity: 20").unwrap();
+        map_deprecated_fields(&mut config_yaml, deprecated_fields).unwrap_err();
+    }
+
+    #[test]
+    fn test_suggest_field() {
+        let expected_fields = vec![
+            "capacity".to_string(),
+            "capacity_bytes".to_string(),
+            "capacity_per_user".to_string(),
+        ];
+
+        assert_eq!(suggest_field("capacty", &expected_fields), Some("capacity"));
+        assert_eq!(
+            suggest_field("capacity_byte", &expected_fields),
+            Some("capacity_bytes")
+        );
+        assert_eq!(suggest_field("max_broadcasts", &expected_fields), None);
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Exports the traces of sampled transactions, from mempool admission through consensus ordering
+/// and execution to commit, to an OpenTelemetry (OTLP) collector.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OtlpExporterConfig {
+    /// Whether to trace transactions and export the traces
+    pub enabled: bool,
+    /// The OTLP/HTTP traces endpoint of the collector
+    pub endpoint: String,
+    /// One in this many transactions is traced, chosen by transaction hash
+    pub txn_sample_rate: u64,
+    /// Maximum number of spans sent to the collector in one request
+    pub max_batch_size: usize,
+    /// Interval between exports of the spans recorded since the last one
+    pub export_interval_ms: u64,
+    /// Maximum number of spans waiting to be exported, further ones are dropped
+    pub max_queued_spans: usize,
+}
+
+impl Default for OtlpExporterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://localhost:4318/v1/traces".to_string(),
+            txn_sample_rate: 1000,
+            max_batch_size: 512,
+            export_interval_ms: 5_000,
+            max_queued_spans: 10_000,
+        }
+    }
+}
+
+impl ConfigSanitizer for OtlpExporterConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let otlp_exporter_config = &node_config.otlp_exporter;
+
+        // Only verify the config if the exporter is enabled
+        if !otlp_exporter_config.enabled {
+            return Ok(());
+        }
+
+        // Verify that the endpoint is an HTTP(S) URL
+        match Url::parse(&otlp_exporter_config.endpoint) {
+            Ok(url) if url.scheme() == "http" || url.scheme() == "https" => {},
+            _ => {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The OTLP endpoint must be an HTTP(S) URL, got: {}",
+                        otlp_exporter_config.endpoint
+                    ),
+                ));
+            },
+        }
+
+        // Verify that transactions are sampled and spans are exported
+        if otlp_exporter_config.txn_sample_rate == 0
+            || otlp_exporter_config.max_batch_size == 0
+            || otlp_exporter_config.max_queued_spans == 0
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "txn_sample_rate, max_batch_size and max_queued_spans must all be non-zero!".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_otlp_exporter() {
+        // Create a node config with the exporter enabled
+        let mut node_config = NodeConfig {
+            otlp_exporter: OtlpExporterConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        OtlpExporterConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+            .unwrap();
+
+        // Verify that a non-HTTP endpoint is rejected
+        node_config.otlp_exporter.endpoint = "grpc://localhost:4317".into();
+        let error = OtlpExporterConfig::sanitize(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that a zero sample rate is rejected
+        node_config.otlp_exporter.endpoint = OtlpExporterConfig::default().endpoint;
+        node_config.otlp_exporter.txn_sample_rate = 0;
+        let error = OtlpExporterConfig::sanitize(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that nothing is checked when the exporter is disabled
+        node_config.otlp_exporter.enabled = false;
+        OtlpExporterConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+            .unwrap();
+    }
+}
@@ -1,7 +1,8 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::config::{Error, NodeConfig, SafetyRulesConfig};
+use crate::config::{validate_node_config, Error, NodeConfig, SafetyRulesConfig};
+use aptos_logger::warn;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     fs::{read_to_string, File},
@@ -56,5 +57,15 @@ pub trait PersistableConfig: Serialize + DeserializeOwned {
 }
 
 // We only implement PersistableConfig for the configs that should be read/written to disk
-impl PersistableConfig for NodeConfig {}
+impl PersistableConfig for NodeConfig {
+    /// Parses the node config strictly, to report all unknown fields (e.g., typos)
+    /// at once, and maps the deprecated fields to their replacements.
+    fn parse_serialized_config(serialized_config: &str) -> Result<Self, Error> {
+        let (node_config, warnings) = validate_node_config(serialized_config)?;
+        for warning in warnings {
+            warn!("{}", warning);
+        }
+        Ok(node_config)
+    }
+}
 impl PersistableConfig for SafetyRulesConfig {}
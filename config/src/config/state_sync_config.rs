@@ -102,10 +102,15 @@ pub struct StateSyncDriverConfig {
     pub continuous_syncing_mode: ContinuousSyncingMode,
     /// Enable auto-bootstrapping if no peers are found after `max_connection_deadline_secs`
     pub enable_auto_bootstrapping: bool,
+    /// Enable the commit stream, where validators push commit certificates to their
+    /// downstream fullnodes, and fullnodes sync as soon as they receive them
+    pub enable_commit_stream: bool,
     /// The interval (ms) to refresh the storage summary
     pub fallback_to_output_syncing_secs: u64,
     /// The interval (ms) at which to check state sync progress
     pub progress_check_interval_ms: u64,
+    /// The maximum number of pending commit stream messages
+    pub max_commit_stream_network_channel_size: u64,
     /// The maximum time (secs) to wait for connections from peers before auto-bootstrapping
     pub max_connection_deadline_secs: u64,
     /// The maximum number of notifications to process per driver loop
@@ -131,8 +136,10 @@ impl Default for StateSyncDriverConfig {
             commit_notification_timeout_ms: 5000,
             continuous_syncing_mode: ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs,
             enable_auto_bootstrapping: false,
+            enable_commit_stream: false,
             fallback_to_output_syncing_secs: 180, // 3 minutes
             progress_check_interval_ms: 100,
+            max_commit_stream_network_channel_size: 100,
             max_connection_deadline_secs: 10,
             max_consecutive_stream_notifications: 10,
             max_num_stream_timeouts: 12,
@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    config::{config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig},
+    config::{
+        config_optimizer::ConfigOptimizer, config_sanitizer::ConfigSanitizer,
+        node_config_loader::NodeType, Error, NodeConfig,
+    },
     utils,
 };
 use anyhow::{ensure, Result};
@@ -12,6 +15,7 @@ use aptos_types::chain_id::ChainId;
 use arr_macro::arr;
 use number_range::NumberRangeOptions;
 use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
 use std::{
     collections::HashMap,
     net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -94,6 +98,72 @@ pub struct RocksdbConfig {
     pub block_size: u64,
     /// Whether cache index and filter blocks into block cache.
     pub cache_index_and_filter_blocks: bool,
+    /// Tuning of the column families read on the critical path, e.g. the latest state values,
+    /// the Jellyfish Merkle nodes and the transaction indices.
+    pub hot_cf_config: ColumnFamilyConfig,
+    /// Tuning of the column families holding the ledger history, which are mostly written and
+    /// rarely read back.
+    pub cold_cf_config: ColumnFamilyConfig,
+}
+
+impl RocksdbConfig {
+    /// Preset for archival nodes, which keep the whole ledger history. The history is compressed
+    /// harder, and most of the block cache is kept for the hot column families.
+    pub fn archival() -> Self {
+        Self {
+            hot_cf_config: ColumnFamilyConfig {
+                compression: RocksdbCompression::Lz4,
+                block_cache_share_pct: Some(80),
+                bloom_filter_bits_per_key: 10,
+            },
+            cold_cf_config: ColumnFamilyConfig {
+                compression: RocksdbCompression::Zstd,
+                block_cache_share_pct: Some(20),
+                bloom_filter_bits_per_key: 0,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Preset for the indexer DBs, which mostly serve point lookups of their indices.
+    pub fn indexer() -> Self {
+        Self {
+            max_open_files: 1000,
+            hot_cf_config: ColumnFamilyConfig {
+                bloom_filter_bits_per_key: 10,
+                ..Default::default()
+            },
+            ..Self::default()
+        }
+    }
+
+    fn sanitize(&self, db_name: &str) -> Result<(), String> {
+        let mut dedicated_block_cache_pct = 0;
+        for cf_config in [&self.hot_cf_config, &self.cold_cf_config] {
+            if let Some(pct) = cf_config.block_cache_share_pct {
+                if pct == 0 || pct > 100 {
+                    return Err(format!(
+                        "block_cache_share_pct of {} must be between 1 and 100, got {}.",
+                        db_name, pct
+                    ));
+                }
+                dedicated_block_cache_pct += pct;
+            }
+            if cf_config.bloom_filter_bits_per_key > MAX_BLOOM_FILTER_BITS_PER_KEY {
+                return Err(format!(
+                    "bloom_filter_bits_per_key of {} must be at most {}, got {}.",
+                    db_name, MAX_BLOOM_FILTER_BITS_PER_KEY, cf_config.bloom_filter_bits_per_key
+                ));
+            }
+        }
+        if dedicated_block_cache_pct > 100 {
+            return Err(format!(
+                "block_cache_share_pct of the column families of {} add up to more than 100.",
+                db_name
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl Default for RocksdbConfig {
@@ -113,6 +183,43 @@ impl Default for RocksdbConfig {
             block_size: 4 * (1u64 << 10),
             // Whether cache index and filter blocks into block cache.
             cache_index_and_filter_blocks: false,
+            hot_cf_config: ColumnFamilyConfig::default(),
+            cold_cf_config: ColumnFamilyConfig::default(),
+        }
+    }
+}
+
+/// Bloom filters with more bits per key hardly lower the false positive rate further.
+const MAX_BLOOM_FILTER_BITS_PER_KEY: u64 = 32;
+
+/// Compression algorithm of the SST files of a column family
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RocksdbCompression {
+    None,
+    Lz4,
+    Zstd,
+}
+
+/// Tuning of a group of column families of a RocksDB instance.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColumnFamilyConfig {
+    /// Compression of the SST files
+    pub compression: RocksdbCompression,
+    /// Percentage of the block cache dedicated to the column families. If not set, they share
+    /// the part of the block cache that isn't dedicated to any column families.
+    pub block_cache_share_pct: Option<u64>,
+    /// Bits per key of the bloom filters of the SST files, 0 for no bloom filters
+    pub bloom_filter_bits_per_key: u64,
+}
+
+impl Default for ColumnFamilyConfig {
+    fn default() -> Self {
+        Self {
+            compression: RocksdbCompression::Lz4,
+            block_cache_share_pct: None,
+            bloom_filter_bits_per_key: 0,
         }
     }
 }
@@ -127,6 +234,20 @@ pub struct RocksdbConfigs {
     pub index_db_config: RocksdbConfig,
     // Note: Not ready for production use yet.
     pub enable_storage_sharding: bool,
+    /// Preset applied to the RocksDB configs which aren't set explicitly in the node config
+    pub preset: RocksdbPreset,
+}
+
+/// Tuning presets of the RocksDB instances of AptosDB, for the kind of node they serve
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RocksdbPreset {
+    /// Keep the defaults of every DB
+    #[default]
+    Default,
+    /// Use `RocksdbConfig::archival()` for the ledger and state DBs, and
+    /// `RocksdbConfig::indexer()` for the index DB
+    Archival,
 }
 
 impl Default for RocksdbConfigs {
@@ -140,6 +261,7 @@ impl Default for RocksdbConfigs {
                 ..Default::default()
             },
             enable_storage_sharding: false,
+            preset: RocksdbPreset::Default,
         }
     }
 }
@@ -469,6 +591,54 @@ impl ShardedDbPaths {
     }
 }
 
+impl ConfigOptimizer for StorageConfig {
+    fn optimize(
+        node_config: &mut NodeConfig,
+        local_config_yaml: &Value,
+        _node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<bool, Error> {
+        let rocksdb_configs = &mut node_config.storage.rocksdb_configs;
+        let local_rocksdb_configs_yaml = &local_config_yaml["storage"]["rocksdb_configs"];
+
+        if rocksdb_configs.preset == RocksdbPreset::Default {
+            return Ok(false);
+        }
+
+        // Apply the preset to the DB configs that aren't set by the user
+        let mut modified_config = false;
+        for (db_name, rocksdb_config, preset_config) in [
+            (
+                "ledger_db_config",
+                &mut rocksdb_configs.ledger_db_config,
+                RocksdbConfig::archival(),
+            ),
+            (
+                "state_merkle_db_config",
+                &mut rocksdb_configs.state_merkle_db_config,
+                RocksdbConfig::archival(),
+            ),
+            (
+                "state_kv_db_config",
+                &mut rocksdb_configs.state_kv_db_config,
+                RocksdbConfig::archival(),
+            ),
+            (
+                "index_db_config",
+                &mut rocksdb_configs.index_db_config,
+                RocksdbConfig::indexer(),
+            ),
+        ] {
+            if local_rocksdb_configs_yaml[db_name].is_null() {
+                *rocksdb_config = preset_config;
+                modified_config = true;
+            }
+        }
+
+        Ok(modified_config)
+    }
+}
+
 impl ConfigSanitizer for StorageConfig {
     fn sanitize(
         node_config: &NodeConfig,
@@ -517,6 +687,21 @@ impl ConfigSanitizer for StorageConfig {
             ));
         }
 
+        let rocksdb_configs = &config.rocksdb_configs;
+        for (db_name, rocksdb_config) in [
+            ("ledger_db_config", &rocksdb_configs.ledger_db_config),
+            (
+                "state_merkle_db_config",
+                &rocksdb_configs.state_merkle_db_config,
+            ),
+            ("state_kv_db_config", &rocksdb_configs.state_kv_db_config),
+            ("index_db_config", &rocksdb_configs.index_db_config),
+        ] {
+            if let Err(error) = rocksdb_config.sanitize(db_name) {
+                return Err(Error::ConfigSanitizerFailed(sanitizer_name, error));
+            }
+        }
+
         if let Some(db_path_overrides) = config.db_path_overrides.as_ref() {
             if !config.rocksdb_configs.enable_storage_sharding {
                 return Err(Error::ConfigSanitizerFailed(
@@ -574,7 +759,13 @@ impl ConfigSanitizer for StorageConfig {
 
 #[cfg(test)]
 mod test {
-    use crate::config::{PrunerConfig, ShardPathConfig, ShardedDbPathConfig};
+    use crate::config::{
+        config_optimizer::ConfigOptimizer, config_sanitizer::ConfigSanitizer,
+        node_config_loader::NodeType, ColumnFamilyConfig, Error, NodeConfig, PrunerConfig,
+        RocksdbConfig, RocksdbConfigs, RocksdbPreset, ShardPathConfig, ShardedDbPathConfig,
+        StorageConfig,
+    };
+    use aptos_types::chain_id::ChainId;
 
     #[test]
     pub fn test_default_prune_window() {
@@ -650,4 +841,110 @@ mod test {
 
         assert!(path_overrides.get_shard_paths().is_err());
     }
+
+    #[test]
+    fn test_sanitize_rocksdb_configs() {
+        // Create a node config with the presets
+        let mut node_config = NodeConfig {
+            storage: StorageConfig {
+                rocksdb_configs: RocksdbConfigs {
+                    ledger_db_config: RocksdbConfig::archival(),
+                    state_merkle_db_config: RocksdbConfig::archival(),
+                    state_kv_db_config: RocksdbConfig::default(),
+                    index_db_config: RocksdbConfig::indexer(),
+                    enable_storage_sharding: false,
+                    preset: RocksdbPreset::Default,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        StorageConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+            .unwrap();
+
+        // Verify that dedicated block cache shares adding up to more than 100% are rejected
+        node_config
+            .storage
+            .rocksdb_configs
+            .ledger_db_config
+            .cold_cf_config
+            .block_cache_share_pct = Some(30);
+        let error =
+            StorageConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that oversized bloom filters are rejected
+        node_config.storage.rocksdb_configs.ledger_db_config = RocksdbConfig::archival();
+        node_config
+            .storage
+            .rocksdb_configs
+            .index_db_config
+            .hot_cf_config = ColumnFamilyConfig {
+            bloom_filter_bits_per_key: 64,
+            ..Default::default()
+        };
+        let error =
+            StorageConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_optimize_rocksdb_preset() {
+        // Create a node config without a preset and verify that no modifications are made
+        let mut node_config = NodeConfig::default();
+        let modified_config = StorageConfig::optimize(
+            &mut node_config,
+            &serde_yaml::from_str("{}").unwrap(), // An empty local config,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap();
+        assert!(!modified_config);
+        assert_eq!(
+            node_config.storage.rocksdb_configs,
+            RocksdbConfigs::default()
+        );
+
+        // Create a local config selecting the archival preset, and setting the state kv db config
+        let local_config_yaml = serde_yaml::from_str(
+            r#"
+            storage:
+                rocksdb_configs:
+                    preset: archival
+                    state_kv_db_config:
+                        max_open_files: 100
+            "#,
+        )
+        .unwrap();
+        let mut node_config = NodeConfig::default();
+        node_config.storage.rocksdb_configs.preset = RocksdbPreset::Archival;
+        node_config
+            .storage
+            .rocksdb_configs
+            .state_kv_db_config
+            .max_open_files = 100;
+
+        // Optimize the config and verify that the preset is applied to the other db configs
+        let modified_config = StorageConfig::optimize(
+            &mut node_config,
+            &local_config_yaml,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap();
+        assert!(modified_config);
+        let rocksdb_configs = &node_config.storage.rocksdb_configs;
+        assert_eq!(rocksdb_configs.ledger_db_config, RocksdbConfig::archival());
+        assert_eq!(
+            rocksdb_configs.state_merkle_db_config,
+            RocksdbConfig::archival()
+        );
+        assert_eq!(rocksdb_configs.state_kv_db_config, RocksdbConfig {
+            max_open_files: 100,
+            ..Default::default()
+        });
+        assert_eq!(rocksdb_configs.index_db_config, RocksdbConfig::indexer());
+    }
 }
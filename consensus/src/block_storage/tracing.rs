@@ -4,7 +4,9 @@
 
 use crate::counters;
 use aptos_infallible::duration_since_epoch;
-use std::time::Duration;
+use aptos_logger::txn_trace::{self, TxnTraceStage};
+use aptos_types::transaction::SignedTransaction;
+use std::time::{Duration, SystemTime};
 
 pub struct BlockStage;
 
@@ -39,3 +41,14 @@ pub fn observe_block(timestamp: u64, stage: &'static str) {
             .observe(t.as_secs_f64());
     }
 }
+
+/// Record the stage of the sampled transactions of a block, from the given time until now.
+pub fn trace_txns(txns: &[SignedTransaction], stage: TxnTraceStage, start_time: SystemTime) {
+    if !txn_trace::is_txn_tracing_enabled() {
+        return;
+    }
+    let end_time = SystemTime::now();
+    for txn in txns {
+        txn_trace::record_txn_span(*txn.committed_hash(), stage, start_time, end_time);
+    }
+}
@@ -22,6 +22,7 @@ use aptos_config::config::NodeConfig;
 use aptos_consensus_notifications::ConsensusNotificationSender;
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use aptos_executor::block_executor::BlockExecutor;
+use aptos_executor_types::block_output_stream::BlockOutputPublisher;
 use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_network::application::interface::{
@@ -46,6 +47,7 @@ pub fn start_consensus(
     reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
     vtxn_pool: VTxnPoolState,
     observer_network: Option<NetworkClient<ObserverMessage>>,
+    block_output_publisher: BlockOutputPublisher,
 ) -> (Runtime, Arc<StorageWriteProxy>, Arc<QuorumStoreDB>) {
     let runtime = aptos_runtimes::spawn_named_runtime("consensus".into(), None);
     let storage = Arc::new(StorageWriteProxy::new(node_config, aptos_db.reader.clone()));
@@ -57,7 +59,10 @@ pub fn start_consensus(
     ));
 
     let execution_proxy = ExecutionProxy::new(
-        Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
+        Arc::new(BlockExecutor::<AptosVM>::new_with_block_output_publisher(
+            aptos_db,
+            block_output_publisher,
+        )),
         txn_notifier,
         state_sync_notifier,
         runtime.handle(),
@@ -120,6 +125,7 @@ pub fn start_consensus_observer(
     consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
     aptos_db: DbReaderWriter,
     reconfig_events: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    block_output_publisher: BlockOutputPublisher,
 ) -> Runtime {
     let publisher_enabled = node_config.consensus_observer.publisher_enabled;
     let runtime = aptos_runtimes::spawn_named_runtime("observer".into(), None);
@@ -131,7 +137,10 @@ pub fn start_consensus_observer(
     ));
 
     let execution_proxy = ExecutionProxy::new(
-        Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
+        Arc::new(BlockExecutor::<AptosVM>::new_with_block_output_publisher(
+            aptos_db,
+            block_output_publisher,
+        )),
         txn_notifier,
         state_sync_notifier,
         runtime.handle(),
@@ -105,6 +105,11 @@ fn test_dag() {
         Extensions::empty(),
     );
     test_dag_type::<NodeSchema, <NodeSchema as Schema>::Key>((), node.clone(), &db);
+    test_dag_type::<DagVotedNodeSchema, <DagVotedNodeSchema as Schema>::Key>(
+        node.id(),
+        node.clone(),
+        &db,
+    );
 
     let certified_node = CertifiedNode::new(node.clone(), AggregateSignature::empty());
     test_dag_type::<CertifiedNodeSchema, <CertifiedNodeSchema as Schema>::Key>(
@@ -66,6 +66,30 @@ impl ValueCodec<DagVoteSchema> for Vote {
     }
 }
 
+pub const DAG_VOTED_NODE_CF_NAME: ColumnFamilyName = "dag_voted_node";
+
+define_schema!(DagVotedNodeSchema, NodeId, Node, DAG_VOTED_NODE_CF_NAME);
+
+impl KeyCodec<DagVotedNodeSchema> for NodeId {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+impl ValueCodec<DagVotedNodeSchema> for Node {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
 pub const CERTIFIED_NODE_CF_NAME: ColumnFamilyName = "certified_node";
 
 define_schema!(
@@ -7,7 +7,9 @@ use super::{
 };
 use crate::{
     block_storage::tracing::{observe_block, BlockStage},
-    consensusdb::{CertifiedNodeSchema, ConsensusDB, DagVoteSchema, NodeSchema},
+    consensusdb::{
+        CertifiedNodeSchema, ConsensusDB, DagVoteSchema, DagVotedNodeSchema, NodeSchema,
+    },
     counters,
     counters::update_counters_for_committed_blocks,
     dag::{
@@ -133,6 +135,10 @@ impl OrderedNotifierAdapter {
             None => Duration::ZERO,
         }
     }
+
+    pub(super) fn pipeline_pending_blocks(&self) -> usize {
+        self.block_ordered_ts.read().len()
+    }
 }
 
 impl OrderedNotifier for OrderedNotifierAdapter {
@@ -362,6 +368,20 @@ impl DAGStorage for StorageAdapter {
         Ok(self.consensus_db.delete::<DagVoteSchema>(node_ids)?)
     }
 
+    fn save_voted_node(&self, node: &Node) -> anyhow::Result<()> {
+        Ok(self
+            .consensus_db
+            .put::<DagVotedNodeSchema>(&node.id(), node)?)
+    }
+
+    fn get_voted_nodes(&self) -> anyhow::Result<Vec<(NodeId, Node)>> {
+        Ok(self.consensus_db.get_all::<DagVotedNodeSchema>()?)
+    }
+
+    fn delete_voted_nodes(&self, node_ids: Vec<NodeId>) -> anyhow::Result<()> {
+        Ok(self.consensus_db.delete::<DagVotedNodeSchema>(node_ids)?)
+    }
+
     fn save_certified_node(&self, node: &CertifiedNode) -> anyhow::Result<()> {
         Ok(self
             .consensus_db
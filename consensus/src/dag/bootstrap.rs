@@ -640,8 +640,13 @@ impl DagBootstrapper {
             self.onchain_config.dag_ordering_causal_history_window as Round,
             self.config.node_payload_config.clone(),
             health_backoff.clone(),
+            self.config
+                .health_config
+                .proposal_backpressure_config
+                .clone(),
             self.quorum_store_enabled,
             self.allow_batches_without_pos_in_proposal,
+            self.config.observer_mode,
         );
         let rb_handler = NodeBroadcastHandler::new(
             dag_store.clone(),
@@ -656,7 +661,11 @@ impl DagBootstrapper {
             self.jwk_consensus_config.clone(),
             health_backoff,
         );
-        let fetch_handler = FetchRequestHandler::new(dag_store.clone(), self.epoch_state.clone());
+        let fetch_handler = FetchRequestHandler::new(
+            dag_store.clone(),
+            self.epoch_state.clone(),
+            self.config.fetcher_config.max_range_fetch_response_bytes,
+        );
 
         let dag_handler = NetworkHandler::new(
             self.epoch_state.clone(),
@@ -667,6 +676,9 @@ impl DagBootstrapper {
             certified_node_fetch_waiter,
             state_sync_trigger,
             new_round_rx,
+            self.config.handler_config.clone(),
+            self.config.observer_mode,
+            self.dag_network_sender.clone(),
         );
 
         (dag_handler, dag_fetcher)
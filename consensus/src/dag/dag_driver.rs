@@ -25,7 +25,7 @@ use crate::{
 };
 use anyhow::{bail, ensure};
 use aptos_collections::BoundedVecDeque;
-use aptos_config::config::DagPayloadConfig;
+use aptos_config::config::{DagPayloadConfig, DagProposalBackpressureConfig};
 use aptos_consensus_types::common::{Author, Payload, PayloadFilter};
 use aptos_crypto::hash::CryptoHash;
 use aptos_infallible::Mutex;
@@ -43,6 +43,24 @@ use futures_channel::oneshot;
 use std::{collections::HashSet, sync::Arc, time::Duration};
 use tokio_retry::strategy::ExponentialBackoff;
 
+/// Whether the driver is able to keep up with the rounds it proposes in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BackpressureSignal {
+    Healthy,
+    /// The driver is overloaded, and should wait at least the given duration
+    /// before proposing in a new round
+    Overloaded(Duration),
+}
+
+impl BackpressureSignal {
+    pub fn proposal_delay(&self) -> Duration {
+        match self {
+            BackpressureSignal::Healthy => Duration::ZERO,
+            BackpressureSignal::Overloaded(delay) => *delay,
+        }
+    }
+}
+
 pub(crate) struct DagDriver {
     author: Author,
     epoch_state: Arc<EpochState>,
@@ -59,8 +77,10 @@ pub(crate) struct DagDriver {
     window_size_config: Round,
     payload_config: DagPayloadConfig,
     health_backoff: HealthBackoff,
+    backpressure_config: DagProposalBackpressureConfig,
     quorum_store_enabled: bool,
     allow_batches_without_pos_in_proposal: bool,
+    is_observer: bool,
 }
 
 impl DagDriver {
@@ -80,8 +100,10 @@ impl DagDriver {
         window_size_config: Round,
         payload_config: DagPayloadConfig,
         health_backoff: HealthBackoff,
+        backpressure_config: DagProposalBackpressureConfig,
         quorum_store_enabled: bool,
         allow_batches_without_pos_in_proposal: bool,
+        is_observer: bool,
     ) -> Self {
         let pending_node = storage
             .get_pending_node()
@@ -105,8 +127,10 @@ impl DagDriver {
             window_size_config,
             payload_config,
             health_backoff,
+            backpressure_config,
             quorum_store_enabled,
             allow_batches_without_pos_in_proposal,
+            is_observer,
         };
 
         // If we were broadcasting the node for the round already, resume it
@@ -163,7 +187,11 @@ impl DagDriver {
 
         let minimum_delay = self
             .health_backoff
-            .backoff_duration(highest_strong_link_round + 1);
+            .backoff_duration(highest_strong_link_round + 1)
+            .max(
+                self.proposal_backpressure(highest_strong_link_round)
+                    .proposal_delay(),
+            );
         self.round_state.check_for_new_round(
             highest_strong_link_round,
             strong_links,
@@ -171,6 +199,38 @@ impl DagDriver {
         );
     }
 
+    /// Derives whether the driver is overloaded from the pending fetch requests,
+    /// the lag between the dag and the committed anchors, and the number of ordered
+    /// blocks waiting to be committed.
+    pub(crate) fn proposal_backpressure(
+        &self,
+        highest_strong_links_round: Round,
+    ) -> BackpressureSignal {
+        let pending_fetch_requests = self.fetch_requester.num_pending_requests();
+        let commit_lag = highest_strong_links_round.saturating_sub(
+            self.ledger_info_provider
+                .get_highest_committed_anchor_round(),
+        );
+        let pending_ordered_blocks = self.health_backoff.pipeline_pending_blocks();
+
+        if pending_fetch_requests > self.backpressure_config.max_pending_fetch_requests
+            || commit_lag > self.backpressure_config.max_commit_lag_rounds
+            || pending_ordered_blocks > self.backpressure_config.max_pending_ordered_blocks
+        {
+            debug!(
+                pending_fetch_requests = pending_fetch_requests,
+                commit_lag = commit_lag,
+                pending_ordered_blocks = pending_ordered_blocks,
+                "dag driver is overloaded, delaying proposal"
+            );
+            BackpressureSignal::Overloaded(Duration::from_millis(
+                self.backpressure_config.proposal_delay_ms,
+            ))
+        } else {
+            BackpressureSignal::Healthy
+        }
+    }
+
     fn get_highest_strong_links_round(&self) -> (Round, Vec<NodeCertificate>) {
         let dag_reader = self.dag.read();
         let highest_strong_links_round =
@@ -207,6 +267,11 @@ impl DagDriver {
             debug!(LogSchema::new(LogEvent::NewRound).round(new_round));
             counters::CURRENT_ROUND.set(new_round as i64);
 
+            // Observers follow the rounds of the dag, but never propose
+            if self.is_observer {
+                return;
+            }
+
             let strong_links = dag_reader
                 .get_strong_links_for_round(new_round - 1, &self.epoch_state.verifier)
                 .unwrap_or_else(|| {
@@ -6,7 +6,10 @@ use crate::dag::{
     dag_network::{RpcResultWithResponder, TDAGNetworkSender},
     errors::FetchRequestHandleError,
     observability::logging::{LogEvent, LogSchema},
-    types::{CertifiedNode, FetchResponse, Node, NodeMetadata, RemoteFetchRequest},
+    types::{
+        CertifiedNode, FetchRangeResponse, FetchResponse, Node, NodeMetadata,
+        RemoteFetchRangeRequest, RemoteFetchRequest,
+    },
     RpcHandler, RpcWithFallback,
 };
 use anyhow::{bail, ensure};
@@ -62,6 +65,9 @@ impl<T> Stream for FetchWaiter<T> {
 pub trait TFetchRequester: Send + Sync {
     fn request_for_node(&self, node: Node) -> anyhow::Result<()>;
     fn request_for_certified_node(&self, node: CertifiedNode) -> anyhow::Result<()>;
+
+    /// The number of fetch requests that are queued, but not yet picked up by the fetcher
+    fn num_pending_requests(&self) -> usize;
 }
 
 pub struct FetchRequester {
@@ -93,6 +99,10 @@ impl TFetchRequester for FetchRequester {
         self.certified_node_waiter_tx.try_send(res_rx)?;
         Ok(())
     }
+
+    fn num_pending_requests(&self) -> usize {
+        self.request_tx.max_capacity() - self.request_tx.capacity()
+    }
 }
 
 #[derive(Debug)]
@@ -142,6 +152,7 @@ pub struct DagFetcherService {
     futures:
         FuturesUnordered<Pin<Box<dyn Future<Output = anyhow::Result<LocalFetchRequest>> + Send>>>,
     max_concurrent_fetches: usize,
+    range_fetch_threshold_rounds: Round,
 }
 
 impl DagFetcherService {
@@ -164,6 +175,7 @@ impl DagFetcherService {
         (
             Self {
                 max_concurrent_fetches: config.max_concurrent_fetches,
+                range_fetch_threshold_rounds: config.range_fetch_threshold_rounds,
                 inner: Arc::new(DagFetcher::new(epoch_state, network, time_service, config)),
                 dag,
                 request_rx,
@@ -215,13 +227,14 @@ impl DagFetcherService {
         node: &Node,
         responders: Vec<Author>,
     ) -> anyhow::Result<Shared<impl Future<Output = Result<(), DagFetchError>>>> {
-        let remote_request = {
+        let (remote_request, is_range_request) = {
             let dag_reader = self.dag.read();
+            let lowest_incomplete_round = dag_reader.lowest_incomplete_round();
             ensure!(
-                node.round() >= dag_reader.lowest_incomplete_round(),
+                node.round() >= lowest_incomplete_round,
                 "Already synced beyond requested round {}, lowest incomplete round {}",
                 node.round(),
-                dag_reader.lowest_incomplete_round()
+                lowest_incomplete_round
             );
 
             let missing_parents: Vec<NodeMetadata> = dag_reader
@@ -233,10 +246,18 @@ impl DagFetcherService {
                 return Ok(async { Ok(()) }.boxed().shared());
             }
 
-            RemoteFetchRequest::new(
-                node.metadata().epoch(),
-                missing_parents,
-                dag_reader.bitmask(node.round().saturating_sub(1)),
+            // If we fell a few rounds behind, fetch everything that is missing in one go
+            // rather than issuing a fetch for the parents of every node.
+            let is_range_request =
+                node.round() - lowest_incomplete_round > self.range_fetch_threshold_rounds;
+
+            (
+                RemoteFetchRequest::new(
+                    node.metadata().epoch(),
+                    missing_parents,
+                    dag_reader.bitmask(node.round().saturating_sub(1)),
+                ),
+                is_range_request,
             )
         };
 
@@ -255,9 +276,19 @@ impl DagFetcherService {
             .or_insert_with(|| {
                 let fetcher = self.inner.clone();
                 let dag_clone = self.dag.clone();
-                async move { fetcher.fetch(remote_request, responders, dag_clone).await }
+                if is_range_request {
+                    async move {
+                        fetcher
+                            .fetch_range(remote_request, responders, dag_clone)
+                            .await
+                    }
                     .boxed()
                     .shared()
+                } else {
+                    async move { fetcher.fetch(remote_request, responders, dag_clone).await }
+                        .boxed()
+                        .shared()
+                }
             })
             .clone();
 
@@ -296,6 +327,94 @@ impl DagFetcher {
             config,
         }
     }
+
+    /// Fetches all the nodes missing up to the target round of `remote_request` with range
+    /// requests, part by part, until the targets exist.
+    pub(crate) async fn fetch_range(
+        &self,
+        remote_request: RemoteFetchRequest,
+        responders: Vec<Author>,
+        dag: Arc<DagStore>,
+    ) -> Result<(), DagFetchError> {
+        let target_round = remote_request.target_round();
+        loop {
+            let exists_bitmask = dag.read().bitmask(target_round);
+            let num_missing = exists_bitmask.num_missing();
+            let range_request =
+                RemoteFetchRangeRequest::new(remote_request.epoch(), exists_bitmask);
+
+            let has_more = self
+                .fetch_range_part(&range_request, responders.clone(), &dag)
+                .await?;
+
+            let dag_reader = dag.read();
+            if dag_reader.all_exists(remote_request.targets()) {
+                return Ok(());
+            }
+            // Give up if the responders have nothing more, or if the part didn't make any
+            // progress, so that a misbehaving responder can't keep us in this loop.
+            if !has_more || dag_reader.bitmask(target_round).num_missing() >= num_missing {
+                return Err(DagFetchError::Failed);
+            }
+        }
+    }
+
+    /// Fetches a single part of the response to `range_request`, and returns whether the
+    /// responder has more nodes in the range.
+    async fn fetch_range_part(
+        &self,
+        range_request: &RemoteFetchRangeRequest,
+        responders: Vec<Author>,
+        dag: &DagStore,
+    ) -> Result<bool, DagFetchError> {
+        debug!(
+            LogSchema::new(LogEvent::FetchNodes),
+            start_round = range_request.start_round(),
+            end_round = range_request.end_round(),
+            missing_nodes = range_request.exists_bitmask().num_missing(),
+        );
+        let mut rpc = RpcWithFallback::new(
+            responders,
+            range_request.clone().into(),
+            Duration::from_millis(self.config.retry_interval_ms),
+            Duration::from_millis(self.config.rpc_timeout_ms),
+            self.network.clone(),
+            self.time_service.clone(),
+            self.config.min_concurrent_responders,
+            self.config.max_concurrent_responders,
+        );
+
+        while let Some(RpcResultWithResponder { responder, result }) = rpc.next().await {
+            match result {
+                Ok(DAGRpcResult(Ok(response))) => {
+                    match FetchRangeResponse::try_from(response).and_then(|response| {
+                        response.verify(range_request, &self.epoch_state.verifier)
+                    }) {
+                        Ok(fetch_response) => {
+                            let has_more = fetch_response.has_more();
+                            // The nodes are ordered by round, so parents are added first
+                            for node in fetch_response.certified_nodes() {
+                                if let Err(e) = dag.add_node(node) {
+                                    error!(error = ?e, "failed to add node");
+                                }
+                            }
+                            return Ok(has_more);
+                        },
+                        Err(err) => {
+                            info!(error = ?err, "failure parsing/verifying fetch range response from {}", responder);
+                        },
+                    };
+                },
+                Ok(DAGRpcResult(Err(dag_rpc_error))) => {
+                    info!(error = ?dag_rpc_error, responder = responder, "fetch range failure: target {} returned error", responder);
+                },
+                Err(err) => {
+                    info!(error = ?err, responder = responder, "rpc failed to {}", responder);
+                },
+            }
+        }
+        Err(DagFetchError::Failed)
+    }
 }
 
 #[async_trait]
@@ -365,15 +484,81 @@ impl TDagFetcher for DagFetcher {
 pub struct FetchRequestHandler {
     dag: Arc<DagStore>,
     author_to_index: HashMap<Author, usize>,
+    max_range_response_bytes: u64,
 }
 
 impl FetchRequestHandler {
-    pub fn new(dag: Arc<DagStore>, epoch_state: Arc<EpochState>) -> Self {
+    pub fn new(
+        dag: Arc<DagStore>,
+        epoch_state: Arc<EpochState>,
+        max_range_response_bytes: u64,
+    ) -> Self {
         Self {
             dag,
             author_to_index: epoch_state.verifier.address_to_validator_index().clone(),
+            max_range_response_bytes,
         }
     }
+
+    /// Responds with the nodes in the requested range that are missing from the request
+    /// bitmask, ordered by round. The response is truncated once it exceeds the maximum
+    /// response size, in which case the requester is expected to ask for the rest.
+    pub fn process_range(
+        &self,
+        message: RemoteFetchRangeRequest,
+    ) -> anyhow::Result<FetchRangeResponse> {
+        let dag_reader = self.dag.read();
+
+        debug!(
+            LogSchema::new(LogEvent::ReceiveFetchNodes).round(dag_reader.highest_round()),
+            start_round = message.start_round(),
+            end_round = message.end_round(),
+        );
+        ensure!(
+            dag_reader.lowest_round() <= message.start_round(),
+            FetchRequestHandleError::GarbageCollected(
+                message.start_round(),
+                dag_reader.lowest_round()
+            ),
+        );
+
+        let mut certified_nodes = vec![];
+        let mut response_bytes = 0;
+        for round in message.start_round()..=message.end_round() {
+            let Some(round_nodes) = dag_reader.get_round_iter(round) else {
+                continue;
+            };
+            for node_status in round_nodes {
+                let node = node_status.as_node();
+                let Some(author_idx) = self.author_to_index.get(node.author()) else {
+                    continue;
+                };
+                if message.exists_bitmask().has(round, *author_idx) {
+                    continue;
+                }
+
+                let node_bytes = bcs::serialized_size(node.as_ref())? as u64;
+                // Always respond with at least one node, so that the requester makes progress
+                if !certified_nodes.is_empty()
+                    && response_bytes + node_bytes > self.max_range_response_bytes
+                {
+                    return Ok(FetchRangeResponse::new(
+                        message.epoch(),
+                        certified_nodes,
+                        true,
+                    ));
+                }
+                response_bytes += node_bytes;
+                certified_nodes.push(node.as_ref().clone());
+            }
+        }
+
+        Ok(FetchRangeResponse::new(
+            message.epoch(),
+            certified_nodes,
+            false,
+        ))
+    }
 }
 
 #[async_trait]
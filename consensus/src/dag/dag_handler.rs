@@ -5,23 +5,27 @@ use crate::{
     dag::{
         dag_driver::DagDriver,
         dag_fetcher::{FetchRequestHandler, FetchWaiter},
-        dag_network::RpcHandler,
+        dag_network::{RpcHandler, TDAGNetworkSender},
         dag_state_sync::{StateSyncTrigger, SyncOutcome},
         errors::{
-            DAGError, DAGRpcError, DagDriverError, FetchRequestHandleError,
+            DAGError, DAGRpcError, DAGRpcErrorCode, DagDriverError, FetchRequestHandleError,
             NodeBroadcastHandleError,
         },
+        inbound_guard::InboundMessageGuard,
         rb_handler::NodeBroadcastHandler,
         types::{DAGMessage, DAGRpcResult},
+        verification_pool::verified_request_stream,
         CertifiedNode, Node,
     },
     monitor,
     network::{IncomingDAGRequest, RpcResponder},
 };
-use aptos_bounded_executor::{concurrent_map, BoundedExecutor};
+use aptos_bounded_executor::BoundedExecutor;
 use aptos_channels::aptos_channel;
+use aptos_config::config::DagHandlerConfig;
 use aptos_consensus_types::common::{Author, Round};
 use aptos_logger::{debug, error, warn};
+use aptos_network::application::reputation::PeerMisbehavior;
 use aptos_types::epoch_state::EpochState;
 use futures::{stream::FuturesUnordered, StreamExt};
 use std::sync::Arc;
@@ -35,6 +39,8 @@ pub(crate) struct NetworkHandler {
     certified_node_fetch_waiter: FetchWaiter<CertifiedNode>,
     new_round_event: tokio::sync::mpsc::UnboundedReceiver<Round>,
     verified_msg_processor: Arc<VerifiedMessageProcessor>,
+    inbound_guard: InboundMessageGuard,
+    is_observer: bool,
 }
 
 impl NetworkHandler {
@@ -47,6 +53,9 @@ impl NetworkHandler {
         certified_node_fetch_waiter: FetchWaiter<CertifiedNode>,
         state_sync_trigger: StateSyncTrigger,
         new_round_event: tokio::sync::mpsc::UnboundedReceiver<Round>,
+        handler_config: DagHandlerConfig,
+        is_observer: bool,
+        dag_network_sender: Arc<dyn TDAGNetworkSender>,
     ) -> Self {
         let node_receiver = Arc::new(node_receiver);
         let dag_driver = Arc::new(dag_driver);
@@ -63,7 +72,10 @@ impl NetworkHandler {
                 fetch_receiver,
                 state_sync_trigger,
                 epoch_state,
+                dag_network_sender,
             }),
+            inbound_guard: InboundMessageGuard::new(handler_config),
+            is_observer,
         }
     }
 
@@ -82,31 +94,15 @@ impl NetworkHandler {
             mut certified_node_fetch_waiter,
             mut new_round_event,
             verified_msg_processor,
+            mut inbound_guard,
+            is_observer,
             ..
         } = self;
 
-        // TODO: feed in the executor based on verification Runtime
-        let mut verified_msg_stream = concurrent_map(
-            dag_rpc_rx,
-            executor.clone(),
-            move |rpc_request: IncomingDAGRequest| {
-                let epoch_state = epoch_state.clone();
-                async move {
-                    let epoch = rpc_request.req.epoch();
-                    let result = rpc_request
-                        .req
-                        .try_into()
-                        .and_then(|dag_message: DAGMessage| {
-                            monitor!(
-                                "dag_message_verify",
-                                dag_message.verify(rpc_request.sender, &epoch_state.verifier)
-                            )?;
-                            Ok(dag_message)
-                        });
-                    (result, epoch, rpc_request.sender, rpc_request.responder)
-                }
-            },
-        );
+        // Signature verification is the bottleneck at high validator counts, so
+        // messages are verified in parallel while preserving per-author order.
+        let mut verified_msg_stream =
+            verified_request_stream(dag_rpc_rx, executor.clone(), epoch_state);
 
         let dag_driver_clone = dag_driver.clone();
         let node_receiver_clone = node_receiver.clone();
@@ -128,29 +124,54 @@ impl NetworkHandler {
         loop {
             select! {
                 Some((msg, epoch, author, responder)) = verified_msg_stream.next() => {
+                    // Don't bother processing requests the sender no longer waits for.
+                    if responder.is_expired() {
+                        debug!(author = author, "dropping expired dag rpc request");
+                        continue;
+                    }
+                    // Observers follow the certified nodes, but never vote for nodes
+                    if is_observer && matches!(msg, Ok(DAGMessage::NodeMsg(_))) {
+                        let error = DAGRpcErrorCode::Observer.into_rpc_error("observers don't vote");
+                        let _ = responder.respond_with_error(error);
+                        continue;
+                    }
+                    // Enforce the per-author quotas before doing any work. Messages
+                    // that failed verification are rejected by the processor.
+                    let ticket = match &msg {
+                        Ok(dag_message) => match inbound_guard.try_admit(author, dag_message) {
+                            Ok(ticket) => Some(ticket),
+                            Err(error) => {
+                                debug!(author = author, error = %error, "rejecting dag rpc request");
+                                let _ = responder.respond_with_error(error);
+                                continue;
+                            },
+                        },
+                        Err(_) => None,
+                    };
                     let verified_msg_processor = verified_msg_processor.clone();
                     let f = executor.spawn(async move {
-                        monitor!("dag_on_verified_msg", {
+                        let status = monitor!("dag_on_verified_msg", {
                             match verified_msg_processor.process_verified_message(msg, epoch, author, responder).await {
-                                Ok(sync_status) => {
-                                    if matches!(
-                                        sync_status,
-                                        SyncOutcome::NeedsSync(_) | SyncOutcome::EpochEnds
-                                    ) {
-                                        return Some(sync_status);
-                                    }
+                                Ok(sync_status @ (SyncOutcome::NeedsSync(_) | SyncOutcome::EpochEnds)) => {
+                                    Some(sync_status)
                                 },
+                                Ok(_) => None,
                                 Err(e) => {
                                     warn!(error = ?e, "error processing rpc");
+                                    None
                                 },
-                            };
-                            None
-                        })
+                            }
+                        });
+                        (ticket, status)
                     }).await;
                     futures.push(f);
                 },
-                Some(status) = futures.next() => {
-                    if let Some(status) = status.expect("future must not panic") {
+                Some(result) = futures.next() => {
+                    let (ticket, status) = result.expect("future must not panic");
+                    if let Some(ticket) = ticket {
+                        inbound_guard.release(ticket);
+                    }
+                    if let Some(status) = status {
                         return status;
                     }
                 },
@@ -200,6 +221,7 @@ struct VerifiedMessageProcessor {
     fetch_receiver: FetchRequestHandler,
     state_sync_trigger: StateSyncTrigger,
     epoch_state: Arc<EpochState>,
+    dag_network_sender: Arc<dyn TDAGNetworkSender>,
 }
 
 impl VerifiedMessageProcessor {
@@ -235,8 +257,8 @@ impl VerifiedMessageProcessor {
                         ),
                         DAGMessage::CertifiedNodeMsg(certified_node_msg) => {
                             monitor!("dag_on_cert_node_msg", {
-                                match self.state_sync_trigger.check(certified_node_msg).await? {
-                                    SyncOutcome::Synced(Some(certified_node_msg)) => self
+                                match self.state_sync_trigger.check(certified_node_msg).await {
+                                    Ok(SyncOutcome::Synced(Some(certified_node_msg))) => self
                                         .dag_driver
                                         .process(certified_node_msg.certified_node())
                                         .await
@@ -247,9 +269,25 @@ impl VerifiedMessageProcessor {
                                                     DAGError::DagDriverError(err)
                                                 })
                                         }),
-                                    status @ (SyncOutcome::NeedsSync(_)
-                                    | SyncOutcome::EpochEnds) => return Ok(status),
-                                    _ => unreachable!(),
+                                    Ok(
+                                        status @ (SyncOutcome::NeedsSync(_)
+                                        | SyncOutcome::EpochEnds),
+                                    ) => {
+                                        // The message won't be handled until we've synced,
+                                        // so let the sender know that it can retry later.
+                                        // The sender may have already given up on the rpc.
+                                        let error = DAGRpcErrorCode::Syncing
+                                            .into_rpc_error("node is syncing");
+                                        let _ = responder.respond_with_error(error);
+                                        return Ok(status);
+                                    },
+                                    Ok(_) => unreachable!(),
+                                    Err(err) => {
+                                        let error = DAGRpcErrorCode::InvalidMessage
+                                            .into_rpc_error(err.to_string());
+                                        let _ = responder.respond_with_error(error);
+                                        return Err(err);
+                                    },
                                 }
                             })
                         },
@@ -266,11 +304,25 @@ impl VerifiedMessageProcessor {
                                     )
                                 })
                         ),
+                        DAGMessage::FetchRangeRequest(request) => monitor!(
+                            "dag_on_fetch_range_request",
+                            self.fetch_receiver
+                                .process_range(request)
+                                .map(|r| r.into())
+                                .map_err(|err| {
+                                    err.downcast::<FetchRequestHandleError>().map_or(
+                                        DAGError::Unknown,
+                                        DAGError::FetchRequestHandleError,
+                                    )
+                                })
+                        ),
                         _ => unreachable!("verification must catch this error"),
                     }
                 },
                 Err(err) => {
                     error!(error = ?err, "DAG message verification failed");
+                    self.dag_network_sender
+                        .report_peer_misbehavior(author, PeerMisbehavior::InvalidConsensusMessage);
                     Err(DAGError::MessageVerificationError)
                 },
             }
@@ -3,6 +3,7 @@
 
 use super::{types::DAGMessage, DAGRpcResult};
 use aptos_consensus_types::common::Author;
+use aptos_network::application::reputation::PeerMisbehavior;
 use aptos_reliable_broadcast::RBNetworkSender;
 use aptos_time_service::{Interval, TimeService, TimeServiceTrait};
 use async_trait::async_trait;
@@ -46,6 +47,9 @@ pub trait TDAGNetworkSender: Send + Sync + RBNetworkSender<DAGMessage, DAGRpcRes
         min_concurrent_responders: u32,
         max_concurrent_responders: u32,
     ) -> RpcWithFallback;
+
+    /// Reports the given misbehavior for the peer (e.g., a message that failed verification)
+    fn report_peer_misbehavior(&self, peer: Author, misbehavior: PeerMisbehavior);
 }
 
 struct Responders {
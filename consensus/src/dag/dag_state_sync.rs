@@ -5,8 +5,9 @@ use super::{
     adapter::TLedgerInfoProvider,
     dag_fetcher::TDagFetcher,
     dag_store::DagStore,
+    errors::DAGRpcErrorCode,
     storage::DAGStorage,
-    types::{CertifiedNodeMessage, RemoteFetchRequest},
+    types::{CertifiedNodeMessage, DAGNetworkMessage, RemoteFetchRequest},
     ProofNotifier,
 };
 use crate::{
@@ -307,15 +308,36 @@ impl SyncModeMessageHandler {
         rpc_request: IncomingDAGRequest,
         buffer: &mut Vec<DAGMessage>,
     ) -> anyhow::Result<Option<CertifiedNodeMessage>> {
-        let dag_message: DAGMessage = rpc_request.req.try_into()?;
+        let result = self.process_dag_message(rpc_request.req, rpc_request.sender, buffer);
+
+        // Messages aren't answered while syncing, so let the sender know
+        // whether it is worth retrying (instead of letting the rpc time out)
+        let error = match &result {
+            Ok(_) => DAGRpcErrorCode::Syncing.into_rpc_error("node is syncing"),
+            Err(err) => DAGRpcErrorCode::InvalidMessage.into_rpc_error(err.to_string()),
+        };
+        if let Err(err) = rpc_request.responder.respond_with_error(error) {
+            debug!("unable to respond to rpc: {}", err);
+        }
+
+        result
+    }
+
+    fn process_dag_message(
+        &mut self,
+        request: DAGNetworkMessage,
+        sender: Author,
+        buffer: &mut Vec<DAGMessage>,
+    ) -> anyhow::Result<Option<CertifiedNodeMessage>> {
+        let dag_message: DAGMessage = request.try_into()?;
 
         debug!(
             "processing rpc message {} from {}",
             dag_message.name(),
-            rpc_request.sender
+            sender
         );
 
-        match dag_message.verify(rpc_request.sender, &self.epoch_state.verifier) {
+        match dag_message.verify(sender, &self.epoch_state.verifier) {
             Ok(_) => match dag_message {
                 DAGMessage::NodeMsg(_) => {
                     debug!("ignoring node msg");
@@ -330,7 +352,7 @@ impl SyncModeMessageHandler {
                         buffer.push(dag_message);
                     }
                 },
-                DAGMessage::FetchRequest(_) => {
+                DAGMessage::FetchRequest(_) | DAGMessage::FetchRangeRequest(_) => {
                     debug!("ignoring fetch msg");
                 },
                 _ => unreachable!("verification must catch this error"),
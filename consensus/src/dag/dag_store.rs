@@ -236,7 +236,7 @@ impl InMemDag {
         Some(&mut round_ref[*index])
     }
 
-    fn get_round_iter(&self, round: Round) -> Option<impl Iterator<Item = &NodeStatus>> {
+    pub(super) fn get_round_iter(&self, round: Round) -> Option<impl Iterator<Item = &NodeStatus>> {
         self.nodes_by_round
             .get(&round)
             .map(|round_ref| round_ref.iter().flatten())
@@ -3,6 +3,7 @@
 
 use aptos_bitvec::BitVec;
 use aptos_consensus_types::common::Round;
+use aptos_network::protocols::rpc::error::RpcApplicationError;
 use serde::{Deserialize, Serialize};
 use std::ops::Deref;
 use thiserror::Error as ThisError;
@@ -72,6 +73,41 @@ impl DAGRpcError {
     }
 }
 
+/// Error codes for DAG rpcs that fail before a response can be produced.
+/// These are sent back to the requester as [`RpcApplicationError`]s.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DAGRpcErrorCode {
+    /// The message could not be decoded or verified
+    InvalidMessage = 1,
+    /// The node is syncing and is not processing messages
+    Syncing = 2,
+    /// The same message from the sender is already being processed
+    Duplicate = 3,
+    /// The sender has too many messages being processed
+    QuotaExceeded = 4,
+    /// The node is an observer and doesn't vote
+    Observer = 5,
+}
+
+impl DAGRpcErrorCode {
+    /// Invalid and duplicate messages, and votes requested from an observer, will never
+    /// succeed (the original message of a duplicate is responded to separately), but
+    /// others might after a backoff
+    pub fn is_retriable(&self) -> bool {
+        !matches!(
+            self,
+            DAGRpcErrorCode::InvalidMessage
+                | DAGRpcErrorCode::Duplicate
+                | DAGRpcErrorCode::Observer
+        )
+    }
+
+    pub fn into_rpc_error(self, message: impl Into<String>) -> RpcApplicationError {
+        RpcApplicationError::new(self as u32, self.is_retriable(), message)
+    }
+}
+
 impl Deref for DAGRpcError {
     type Target = DAGError;
 
@@ -83,4 +83,8 @@ impl HealthBackoff {
     pub fn stop_voting(&self) -> bool {
         self.pipeline_health.stop_voting()
     }
+
+    pub fn pipeline_pending_blocks(&self) -> usize {
+        self.pipeline_health.num_pending_blocks()
+    }
 }
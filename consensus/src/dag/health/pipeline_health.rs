@@ -12,6 +12,8 @@ pub trait TPipelineHealth: Send + Sync {
     fn get_payload_limits(&self) -> Option<(u64, u64)>;
 
     fn stop_voting(&self) -> bool;
+
+    fn num_pending_blocks(&self) -> usize;
 }
 
 pub struct NoPipelineBackpressure {}
@@ -34,6 +36,10 @@ impl TPipelineHealth for NoPipelineBackpressure {
     fn stop_voting(&self) -> bool {
         false
     }
+
+    fn num_pending_blocks(&self) -> usize {
+        0
+    }
 }
 
 pub struct PipelineLatencyBasedBackpressure {
@@ -78,4 +84,8 @@ impl TPipelineHealth for PipelineLatencyBasedBackpressure {
         let latency = self.adapter.pipeline_pending_latency();
         latency > self.voter_pipeline_latency_limit
     }
+
+    fn num_pending_blocks(&self) -> usize {
+        self.adapter.pipeline_pending_blocks()
+    }
 }
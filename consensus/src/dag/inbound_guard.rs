@@ -0,0 +1,108 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{errors::DAGRpcErrorCode, types::DAGMessage};
+use aptos_config::config::DagHandlerConfig;
+use aptos_consensus_types::common::{Author, Round};
+use aptos_crypto::HashValue;
+use aptos_network::protocols::rpc::error::RpcApplicationError;
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+/// The messages of a single author that are currently being processed
+#[derive(Default)]
+struct InFlightMessages {
+    num_messages: usize,
+    num_fetch_requests: usize,
+    nodes: HashSet<(Round, HashValue)>,
+}
+
+/// A message admitted by the [`InboundMessageGuard`]. It must be handed back
+/// to the guard once the message has been processed.
+pub(crate) struct InFlightTicket {
+    author: Author,
+    is_fetch_request: bool,
+    node: Option<(Round, HashValue)>,
+}
+
+/// Enforces per-author quotas on the messages being processed, so that a single
+/// (possibly byzantine) author can't monopolize the handler, and rejects nodes
+/// that are already being processed.
+///
+/// Duplicates are only rejected while the original is in flight. A duplicate
+/// that arrives afterwards is let through, as it may be a legitimate retry
+/// (e.g., by reliable broadcast when the original response was lost), which the
+/// handlers respond to idempotently.
+pub(crate) struct InboundMessageGuard {
+    config: DagHandlerConfig,
+    in_flight: HashMap<Author, InFlightMessages>,
+}
+
+impl InboundMessageGuard {
+    pub(crate) fn new(config: DagHandlerConfig) -> Self {
+        Self {
+            config,
+            in_flight: HashMap::new(),
+        }
+    }
+
+    /// Admits the message for processing, or returns the error to respond with
+    pub(crate) fn try_admit(
+        &mut self,
+        author: Author,
+        message: &DAGMessage,
+    ) -> Result<InFlightTicket, RpcApplicationError> {
+        let in_flight = self.in_flight.entry(author).or_default();
+        if in_flight.num_messages >= self.config.max_concurrent_messages_per_author {
+            return Err(DAGRpcErrorCode::QuotaExceeded
+                .into_rpc_error("too many concurrent messages from author"));
+        }
+
+        let mut ticket = InFlightTicket {
+            author,
+            is_fetch_request: false,
+            node: None,
+        };
+        match message {
+            DAGMessage::NodeMsg(node) => {
+                let node_id = (node.round(), node.digest());
+                if !in_flight.nodes.insert(node_id) {
+                    return Err(DAGRpcErrorCode::Duplicate
+                        .into_rpc_error("node is already being processed"));
+                }
+                ticket.node = Some(node_id);
+            },
+            DAGMessage::FetchRequest(_) | DAGMessage::FetchRangeRequest(_) => {
+                if in_flight.num_fetch_requests
+                    >= self.config.max_concurrent_fetch_requests_per_author
+                {
+                    return Err(DAGRpcErrorCode::QuotaExceeded
+                        .into_rpc_error("too many concurrent fetch requests from author"));
+                }
+                in_flight.num_fetch_requests += 1;
+                ticket.is_fetch_request = true;
+            },
+            _ => (),
+        }
+        in_flight.num_messages += 1;
+        Ok(ticket)
+    }
+
+    /// Releases the quota held by a processed message
+    pub(crate) fn release(&mut self, ticket: InFlightTicket) {
+        let mut entry = match self.in_flight.entry(ticket.author) {
+            Entry::Occupied(entry) => entry,
+            Entry::Vacant(_) => return,
+        };
+        let in_flight = entry.get_mut();
+        in_flight.num_messages = in_flight.num_messages.saturating_sub(1);
+        if ticket.is_fetch_request {
+            in_flight.num_fetch_requests = in_flight.num_fetch_requests.saturating_sub(1);
+        }
+        if let Some(node_id) = ticket.node {
+            in_flight.nodes.remove(&node_id);
+        }
+        if in_flight.num_messages == 0 {
+            entry.remove();
+        }
+    }
+}
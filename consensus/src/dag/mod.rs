@@ -14,6 +14,7 @@ mod dag_state_sync;
 mod dag_store;
 mod errors;
 mod health;
+mod inbound_guard;
 mod observability;
 mod order_rule;
 mod rb_handler;
@@ -22,6 +23,7 @@ mod storage;
 #[cfg(test)]
 mod tests;
 mod types;
+mod verification_pool;
 
 pub use adapter::{ProofNotifier, StorageAdapter};
 pub use bootstrap::DagBootstrapper;
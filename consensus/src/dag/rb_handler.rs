@@ -67,6 +67,7 @@ impl NodeBroadcastHandler {
     ) -> Self {
         let epoch = epoch_state.epoch;
         let votes_by_round_peer = read_votes_from_storage(&storage, epoch);
+        restore_voted_nodes(&dag, order_rule.as_ref(), &storage, &votes_by_round_peer);
 
         Self {
             dag,
@@ -105,8 +106,9 @@ impl NodeBroadcastHandler {
                     .iter()
                     .map(|(author, _)| NodeId::new(self.epoch_state.epoch, *r, *author))
             })
-            .collect();
-        self.storage.delete_votes(to_delete)
+            .collect::<Vec<_>>();
+        self.storage.delete_votes(to_delete.clone())?;
+        self.storage.delete_voted_nodes(to_delete)
     }
 
     fn validate(&self, node: Node) -> anyhow::Result<Node> {
@@ -207,6 +209,43 @@ fn read_votes_from_storage(
     votes_by_round_peer
 }
 
+/// Replays the nodes we voted for before a restart into the dag, so that our votes
+/// count towards the voting power of their parents again. Nodes without a vote
+/// (i.e., we crashed between saving the node and the vote, or the vote has been
+/// garbage collected) are deleted.
+fn restore_voted_nodes(
+    dag: &DagStore,
+    order_rule: &dyn TOrderRule,
+    storage: &Arc<dyn DAGStorage>,
+    votes_by_round_peer: &BTreeMap<Round, BTreeMap<Author, Vote>>,
+) {
+    let mut voted_nodes = storage.get_voted_nodes().unwrap_or_default();
+    voted_nodes.sort_unstable_by_key(|(node_id, _)| node_id.round());
+
+    let mut to_delete = vec![];
+    for (node_id, node) in voted_nodes {
+        let has_vote = votes_by_round_peer
+            .get(&node_id.round())
+            .and_then(|votes| votes.get(node_id.author()))
+            .map_or(false, |vote| vote.metadata() == node.metadata());
+        if !has_vote {
+            to_delete.push(node_id);
+            continue;
+        }
+
+        // The parents may have been pruned or not be restored yet
+        let mut dag_writer = dag.write();
+        if dag_writer.all_exists(node.parents_metadata()) {
+            dag_writer.update_votes(&node, false);
+            drop(dag_writer);
+            order_rule.process_new_node(node.metadata());
+        }
+    }
+    if let Err(err) = storage.delete_voted_nodes(to_delete) {
+        error!("unable to clear old voted nodes: {}", err);
+    }
+}
+
 #[async_trait]
 impl RpcHandler for NodeBroadcastHandler {
     type Request = Node;
@@ -245,6 +284,9 @@ impl RpcHandler for NodeBroadcastHandler {
 
         let signature = node.sign_vote(&self.signer)?;
         let vote = Vote::new(node.metadata().clone(), signature);
+        // The node is saved before the vote, so that every persisted vote can be
+        // replayed into the dag after a restart (see `restore_voted_nodes`).
+        self.storage.save_voted_node(&node)?;
         self.storage.save_vote(&node.id(), &vote)?;
         self.votes_by_round_peer
             .lock()
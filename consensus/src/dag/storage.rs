@@ -58,6 +58,12 @@ pub trait DAGStorage: Send + Sync {
 
     fn delete_votes(&self, node_ids: Vec<NodeId>) -> anyhow::Result<()>;
 
+    fn save_voted_node(&self, node: &Node) -> anyhow::Result<()>;
+
+    fn get_voted_nodes(&self) -> anyhow::Result<Vec<(NodeId, Node)>>;
+
+    fn delete_voted_nodes(&self, node_ids: Vec<NodeId>) -> anyhow::Result<()>;
+
     fn save_certified_node(&self, node: &CertifiedNode) -> anyhow::Result<()>;
 
     fn get_certified_nodes(&self) -> anyhow::Result<Vec<(HashValue, CertifiedNode)>>;
@@ -5,7 +5,7 @@ use crate::{
     dag::{
         adapter::TLedgerInfoProvider,
         anchor_election::RoundRobinAnchorElection,
-        dag_driver::DagDriver,
+        dag_driver::{BackpressureSignal, DagDriver},
         dag_fetcher::TFetchRequester,
         dag_network::{RpcWithFallback, TDAGNetworkSender},
         dag_store::DagStore,
@@ -13,6 +13,7 @@ use crate::{
         health::{HealthBackoff, NoChainHealth, NoPipelineBackpressure},
         order_rule::OrderRule,
         round_state::{OptimisticResponsive, RoundState},
+        storage::DAGStorage,
         tests::{
             dag_test::MockStorage,
             helpers::{new_certified_node, MockPayloadManager, TEST_DAG_WINDOW},
@@ -24,9 +25,10 @@ use crate::{
     test_utils::MockPayloadManager as MockPayloadClient,
 };
 use aptos_bounded_executor::BoundedExecutor;
-use aptos_config::config::DagPayloadConfig;
+use aptos_config::config::{DagPayloadConfig, DagProposalBackpressureConfig};
 use aptos_consensus_types::common::{Author, Round};
 use aptos_infallible::Mutex;
+use aptos_network::application::reputation::PeerMisbehavior;
 use aptos_reliable_broadcast::{RBNetworkSender, ReliableBroadcast};
 use aptos_time_service::TimeService;
 use aptos_types::{
@@ -36,7 +38,7 @@ use aptos_types::{
     validator_verifier::{random_validator_verifier, ValidatorVerifier},
 };
 use async_trait::async_trait;
-use claims::{assert_ok, assert_ok_eq};
+use claims::{assert_none, assert_ok, assert_ok_eq};
 use futures_channel::mpsc::unbounded;
 use std::{sync::Arc, time::Duration};
 use tokio::{runtime::Handle, sync::oneshot};
@@ -82,6 +84,8 @@ impl TDAGNetworkSender for MockNetworkSender {
     ) -> RpcWithFallback {
         unimplemented!()
     }
+
+    fn report_peer_misbehavior(&self, _peer: Author, _misbehavior: PeerMisbehavior) {}
 }
 
 struct MockLedgerInfoProvider {
@@ -108,13 +112,19 @@ impl TFetchRequester for MockFetchRequester {
     fn request_for_certified_node(&self, _node: crate::dag::CertifiedNode) -> anyhow::Result<()> {
         Ok(())
     }
+
+    fn num_pending_requests(&self) -> usize {
+        0
+    }
 }
 
 fn setup(
     signers: &[ValidatorSigner],
     validator_verifier: ValidatorVerifier,
     network_sender: Arc<MockNetworkSender>,
-) -> DagDriver {
+    backpressure_config: DagProposalBackpressureConfig,
+    is_observer: bool,
+) -> (DagDriver, Arc<MockStorage>) {
     let epoch_state = Arc::new(EpochState {
         epoch: 1,
         verifier: validator_verifier,
@@ -166,14 +176,14 @@ fn setup(
         Box::new(OptimisticResponsive::new(round_tx)),
     );
 
-    DagDriver::new(
+    let driver = DagDriver::new(
         signers[0].author(),
         epoch_state.clone(),
         dag,
         Arc::new(MockPayloadClient::new(None)),
         rb,
         time_service,
-        storage,
+        storage.clone(),
         order_rule,
         fetch_requester,
         ledger_info_provider,
@@ -185,9 +195,12 @@ fn setup(
             NoChainHealth::new(),
             NoPipelineBackpressure::new(),
         ),
+        backpressure_config,
         false,
         true,
-    )
+        is_observer,
+    );
+    (driver, storage)
 }
 
 #[tokio::test]
@@ -196,7 +209,13 @@ async fn test_certified_node_handler() {
     let network_sender = Arc::new(MockNetworkSender {
         _drop_notifier: None,
     });
-    let driver = setup(&signers, validator_verifier, network_sender);
+    let (driver, _) = setup(
+        &signers,
+        validator_verifier,
+        network_sender,
+        DagProposalBackpressureConfig::default(),
+        false,
+    );
 
     let first_round_node = new_certified_node(1, signers[0].author(), vec![]);
     // expect an ack for a valid message
@@ -221,7 +240,13 @@ async fn test_dag_driver_drop() {
     let network_sender = Arc::new(MockNetworkSender {
         _drop_notifier: Some(tx),
     });
-    let driver = setup(&signers, validator_verifier, network_sender);
+    let (driver, _) = setup(
+        &signers,
+        validator_verifier,
+        network_sender,
+        DagProposalBackpressureConfig::default(),
+        false,
+    );
 
     driver.enter_new_round(1).await;
 
@@ -232,3 +257,53 @@ async fn test_dag_driver_drop() {
 
     let _ = rx.await;
 }
+
+#[tokio::test]
+async fn test_proposal_backpressure_on_commit_lag() {
+    let (signers, validator_verifier) = random_validator_verifier(4, None, false);
+    let network_sender = Arc::new(MockNetworkSender {
+        _drop_notifier: None,
+    });
+    let (driver, _) = setup(
+        &signers,
+        validator_verifier,
+        network_sender,
+        DagProposalBackpressureConfig {
+            max_commit_lag_rounds: 1,
+            proposal_delay_ms: 100,
+            ..Default::default()
+        },
+        false,
+    );
+
+    // Nothing is committed yet, but the dag is within the allowed lag
+    assert_eq!(driver.proposal_backpressure(1), BackpressureSignal::Healthy);
+
+    // The dag moved too far ahead of the committed anchors
+    assert_eq!(
+        driver.proposal_backpressure(2),
+        BackpressureSignal::Overloaded(Duration::from_millis(100))
+    );
+}
+
+#[tokio::test]
+async fn test_observer_does_not_propose() {
+    let (signers, validator_verifier) = random_validator_verifier(4, None, false);
+    let network_sender = Arc::new(MockNetworkSender {
+        _drop_notifier: None,
+    });
+    let (driver, storage) = setup(
+        &signers,
+        validator_verifier,
+        network_sender,
+        DagProposalBackpressureConfig::default(),
+        true,
+    );
+
+    driver.enter_new_round(1).await;
+    assert_none!(storage.get_pending_node().unwrap());
+
+    // Observers still follow the certified nodes
+    let node = new_certified_node(1, signers[1].author(), vec![]);
+    assert_ok_eq!(driver.process(node).await, CertifiedAck::new(1));
+}
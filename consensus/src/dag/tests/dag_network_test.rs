@@ -9,6 +9,7 @@ use crate::dag::{
 use anyhow::{anyhow, bail};
 use aptos_consensus_types::common::Author;
 use aptos_infallible::Mutex;
+use aptos_network::application::reputation::PeerMisbehavior;
 use aptos_reliable_broadcast::RBNetworkSender;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::validator_verifier::random_validator_verifier;
@@ -91,6 +92,8 @@ impl TDAGNetworkSender for MockDAGNetworkSender {
             max_concurrent_responders,
         )
     }
+
+    fn report_peer_misbehavior(&self, _peer: Author, _misbehavior: PeerMisbehavior) {}
 }
 
 #[tokio::test]
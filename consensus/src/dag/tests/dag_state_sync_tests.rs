@@ -19,8 +19,10 @@ use crate::{
     },
     pipeline::execution_client::DummyExecutionClient,
 };
+use aptos_config::config::DagFetcherConfig;
 use aptos_consensus_types::common::{Author, Round};
 use aptos_crypto::HashValue;
+use aptos_network::application::reputation::PeerMisbehavior;
 use aptos_reliable_broadcast::RBNetworkSender;
 use aptos_time_service::TimeService;
 use aptos_types::{
@@ -72,6 +74,8 @@ impl TDAGNetworkSender for MockDAGNetworkSender {
     ) -> RpcWithFallback {
         unimplemented!()
     }
+
+    fn report_peer_misbehavior(&self, _peer: Author, _misbehavior: PeerMisbehavior) {}
 }
 
 struct MockDagFetcher {
@@ -87,10 +91,14 @@ impl TDagFetcher for MockDagFetcher {
         _responders: Vec<Author>,
         new_dag: Arc<DagStore>,
     ) -> Result<(), DagFetchError> {
-        let response = FetchRequestHandler::new(self.target_dag.clone(), self.epoch_state.clone())
-            .process(remote_request)
-            .await
-            .unwrap();
+        let response = FetchRequestHandler::new(
+            self.target_dag.clone(),
+            self.epoch_state.clone(),
+            DagFetcherConfig::default().max_range_fetch_response_bytes,
+        )
+        .process(remote_request)
+        .await
+        .unwrap();
 
         for node in response.certified_nodes().into_iter().rev() {
             new_dag.write().add_node_for_test(node).unwrap()
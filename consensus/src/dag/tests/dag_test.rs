@@ -21,6 +21,7 @@ use std::{collections::HashMap, sync::Arc};
 pub struct MockStorage {
     node_data: Mutex<Option<Node>>,
     vote_data: Mutex<HashMap<NodeId, Vote>>,
+    voted_node_data: Mutex<HashMap<NodeId, Node>>,
     certified_node_data: Mutex<HashMap<HashValue, CertifiedNode>>,
     latest_ledger_info: Option<LedgerInfoWithSignatures>,
     epoch_state: Option<Arc<EpochState>>,
@@ -31,6 +32,7 @@ impl MockStorage {
         Self {
             node_data: Mutex::new(None),
             vote_data: Mutex::new(HashMap::new()),
+            voted_node_data: Mutex::new(HashMap::new()),
             certified_node_data: Mutex::new(HashMap::new()),
             latest_ledger_info: None,
             epoch_state: None,
@@ -44,6 +46,7 @@ impl MockStorage {
         Self {
             node_data: Mutex::new(None),
             vote_data: Mutex::new(HashMap::new()),
+            voted_node_data: Mutex::new(HashMap::new()),
             certified_node_data: Mutex::new(HashMap::new()),
             latest_ledger_info: Some(ledger_info),
             epoch_state: Some(epoch_state),
@@ -82,6 +85,22 @@ impl DAGStorage for MockStorage {
         Ok(())
     }
 
+    fn save_voted_node(&self, node: &Node) -> anyhow::Result<()> {
+        self.voted_node_data.lock().insert(node.id(), node.clone());
+        Ok(())
+    }
+
+    fn get_voted_nodes(&self) -> anyhow::Result<Vec<(NodeId, Node)>> {
+        Ok(self.voted_node_data.lock().clone().into_iter().collect())
+    }
+
+    fn delete_voted_nodes(&self, node_ids: Vec<NodeId>) -> anyhow::Result<()> {
+        for node_id in node_ids {
+            self.voted_node_data.lock().remove(&node_id);
+        }
+        Ok(())
+    }
+
     fn save_certified_node(&self, node: &CertifiedNode) -> anyhow::Result<()> {
         self.certified_node_data
             .lock()
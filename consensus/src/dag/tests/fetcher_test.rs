@@ -6,9 +6,13 @@ use crate::dag::{
     dag_fetcher::FetchRequestHandler,
     dag_store::DagStore,
     tests::helpers::{new_certified_node, MockPayloadManager, TEST_DAG_WINDOW},
-    types::{DagSnapshotBitmask, FetchResponse, RemoteFetchRequest},
+    types::{
+        DagSnapshotBitmask, FetchRangeResponse, FetchResponse, RemoteFetchRangeRequest,
+        RemoteFetchRequest,
+    },
     RpcHandler,
 };
+use aptos_config::config::DagFetcherConfig;
 use aptos_types::{epoch_state::EpochState, validator_verifier::random_validator_verifier};
 use claims::assert_ok_eq;
 use std::sync::Arc;
@@ -29,7 +33,11 @@ async fn test_dag_fetcher_receiver() {
         TEST_DAG_WINDOW,
     ));
 
-    let fetcher = FetchRequestHandler::new(dag.clone(), epoch_state);
+    let fetcher = FetchRequestHandler::new(
+        dag.clone(),
+        epoch_state,
+        DagFetcherConfig::default().max_range_fetch_response_bytes,
+    );
 
     let mut first_round_nodes = vec![];
 
@@ -61,4 +69,70 @@ async fn test_dag_fetcher_receiver() {
     );
 }
 
+#[tokio::test]
+async fn test_dag_fetcher_range_receiver() {
+    let (signers, validator_verifier) = random_validator_verifier(4, None, false);
+    let epoch_state = Arc::new(EpochState {
+        epoch: 1,
+        verifier: validator_verifier,
+    });
+    let storage = Arc::new(MockStorage::new());
+    let dag = Arc::new(DagStore::new(
+        epoch_state.clone(),
+        storage,
+        Arc::new(MockPayloadManager {}),
+        0,
+        TEST_DAG_WINDOW,
+    ));
+
+    // Round 1 - nodes 0, 1, 2 links to vec![]
+    let mut first_round_nodes = vec![];
+    for signer in &signers[0..3] {
+        let node = new_certified_node(1, signer.author(), vec![]);
+        assert!(dag.add_node(node.clone()).is_ok());
+        first_round_nodes.push(node);
+    }
+
+    // Round 2 - node 0
+    let second_round_node = new_certified_node(2, signers[0].author(), vec![
+        first_round_nodes[0].certificate(),
+        first_round_nodes[1].certificate(),
+        first_round_nodes[2].certificate(),
+    ]);
+    assert!(dag.add_node(second_round_node.clone()).is_ok());
+
+    let request = RemoteFetchRangeRequest::new(
+        1,
+        DagSnapshotBitmask::new(1, vec![vec![true, false, false, false], vec![
+            false, false, false, false,
+        ]]),
+    );
+
+    // The whole range fits in a single response
+    let fetcher = FetchRequestHandler::new(
+        dag.clone(),
+        epoch_state.clone(),
+        DagFetcherConfig::default().max_range_fetch_response_bytes,
+    );
+    assert_ok_eq!(
+        fetcher.process_range(request.clone()),
+        FetchRangeResponse::new(
+            1,
+            vec![
+                first_round_nodes[1].clone(),
+                first_round_nodes[2].clone(),
+                second_round_node,
+            ],
+            false
+        )
+    );
+
+    // The response is split when it exceeds the maximum size, but always makes progress
+    let fetcher = FetchRequestHandler::new(dag, epoch_state, 1);
+    assert_ok_eq!(
+        fetcher.process_range(request),
+        FetchRangeResponse::new(1, vec![first_round_nodes[1].clone()], true)
+    );
+}
+
 // TODO: add more tests after commit rule tests
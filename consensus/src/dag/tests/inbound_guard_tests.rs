@@ -0,0 +1,88 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::{
+    errors::DAGRpcErrorCode,
+    inbound_guard::InboundMessageGuard,
+    tests::helpers::new_node,
+    types::{DAGMessage, DagSnapshotBitmask, RemoteFetchRequest},
+};
+use aptos_config::config::DagHandlerConfig;
+use aptos_consensus_types::common::Author;
+
+fn fetch_request() -> DAGMessage {
+    DAGMessage::FetchRequest(RemoteFetchRequest::new(
+        1,
+        vec![],
+        DagSnapshotBitmask::new(1, vec![vec![false; 5]]),
+    ))
+}
+
+#[test]
+fn test_duplicate_node_rejected_while_in_flight() {
+    let author = Author::random();
+    let mut guard = InboundMessageGuard::new(DagHandlerConfig::default());
+    let node = DAGMessage::NodeMsg(new_node(1, 10, author, vec![]));
+
+    let ticket = guard.try_admit(author, &node).unwrap();
+    let error = guard.try_admit(author, &node).unwrap_err();
+    assert_eq!(error.code, DAGRpcErrorCode::Duplicate as u32);
+    assert!(!error.retriable);
+
+    // Once processed, a retry of the same node is admitted again
+    guard.release(ticket);
+    guard.try_admit(author, &node).unwrap();
+}
+
+#[test]
+fn test_fetch_request_quota() {
+    let (author, other_author) = (Author::random(), Author::random());
+    let mut guard = InboundMessageGuard::new(DagHandlerConfig {
+        max_concurrent_messages_per_author: 10,
+        max_concurrent_fetch_requests_per_author: 2,
+    });
+
+    let tickets: Vec<_> = (0..2)
+        .map(|_| guard.try_admit(author, &fetch_request()).unwrap())
+        .collect();
+    let error = guard.try_admit(author, &fetch_request()).unwrap_err();
+    assert_eq!(error.code, DAGRpcErrorCode::QuotaExceeded as u32);
+    assert!(error.retriable);
+
+    // Other authors have their own quota
+    guard.try_admit(other_author, &fetch_request()).unwrap();
+
+    for ticket in tickets {
+        guard.release(ticket);
+    }
+    guard.try_admit(author, &fetch_request()).unwrap();
+}
+
+#[test]
+fn test_message_quota() {
+    let author = Author::random();
+    let mut guard = InboundMessageGuard::new(DagHandlerConfig {
+        max_concurrent_messages_per_author: 2,
+        max_concurrent_fetch_requests_per_author: 2,
+    });
+
+    guard
+        .try_admit(
+            author,
+            &DAGMessage::NodeMsg(new_node(1, 10, author, vec![])),
+        )
+        .unwrap();
+    guard
+        .try_admit(
+            author,
+            &DAGMessage::NodeMsg(new_node(2, 20, author, vec![])),
+        )
+        .unwrap();
+    let error = guard
+        .try_admit(
+            author,
+            &DAGMessage::NodeMsg(new_node(3, 30, author, vec![])),
+        )
+        .unwrap_err();
+    assert_eq!(error.code, DAGRpcErrorCode::QuotaExceeded as u32);
+}
@@ -7,7 +7,9 @@ mod dag_state_sync_tests;
 mod dag_test;
 mod fetcher_test;
 mod helpers;
+mod inbound_guard_tests;
 mod integration_tests;
 mod order_rule_tests;
 mod rb_handler_tests;
 mod types_test;
+mod verification_pool_tests;
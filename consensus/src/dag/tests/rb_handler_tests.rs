@@ -36,6 +36,10 @@ impl TFetchRequester for MockFetchRequester {
     fn request_for_certified_node(&self, _node: crate::dag::CertifiedNode) -> anyhow::Result<()> {
         Ok(())
     }
+
+    fn num_pending_requests(&self) -> usize {
+        0
+    }
 }
 
 #[tokio::test]
@@ -229,12 +233,23 @@ async fn test_node_broadcast_receiver_storage() {
             NoPipelineBackpressure::new(),
         ),
     );
-    let sig = rb_receiver.process(node).await.expect("must succeed");
+    let sig = rb_receiver
+        .process(node.clone())
+        .await
+        .expect("must succeed");
 
     assert_ok_eq!(storage.get_votes(), vec![(
         NodeId::new(1, 1, signers[0].author()),
         sig
     )],);
+    assert_ok_eq!(storage.get_voted_nodes(), vec![(
+        NodeId::new(1, 1, signers[0].author()),
+        node
+    )]);
+
+    // A voted node without a vote is deleted on restart
+    let orphan_node = new_node(1, 10, signers[1].author(), vec![]);
+    assert_ok!(storage.save_voted_node(&orphan_node));
 
     let rb_receiver = NodeBroadcastHandler::new(
         dag,
@@ -253,6 +268,9 @@ async fn test_node_broadcast_receiver_storage() {
             NoPipelineBackpressure::new(),
         ),
     );
+    assert_eq!(storage.get_voted_nodes().unwrap().len(), 1);
+
     assert_ok!(rb_receiver.gc_before_round(2));
     assert_eq!(storage.get_votes().unwrap().len(), 0);
+    assert_eq!(storage.get_voted_nodes().unwrap().len(), 0);
 }
@@ -0,0 +1,29 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::dag::verification_pool::PerAuthorReorderBuffer;
+use aptos_consensus_types::common::Author;
+
+#[test]
+fn test_reorder_buffer_releases_in_order() {
+    let author = Author::random();
+    let mut buffer = PerAuthorReorderBuffer::default();
+
+    // Later items are held back until the earlier ones complete
+    assert!(buffer.push(author, 2, "c").is_empty());
+    assert!(buffer.push(author, 1, "b").is_empty());
+    assert_eq!(buffer.push(author, 0, "a"), vec!["a", "b", "c"]);
+    assert_eq!(buffer.push(author, 3, "d"), vec!["d"]);
+}
+
+#[test]
+fn test_reorder_buffer_authors_are_independent() {
+    let (author_1, author_2) = (Author::random(), Author::random());
+    let mut buffer = PerAuthorReorderBuffer::default();
+
+    // A slow item of one author doesn't hold back the other authors
+    assert!(buffer.push(author_1, 1, 11).is_empty());
+    assert_eq!(buffer.push(author_2, 0, 20), vec![20]);
+    assert_eq!(buffer.push(author_2, 1, 21), vec![21]);
+    assert_eq!(buffer.push(author_1, 0, 10), vec![10, 11]);
+}
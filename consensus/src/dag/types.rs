@@ -440,6 +440,26 @@ impl CertifiedNode {
 
         Ok(verifier.verify_multi_signatures(self.metadata(), self.signatures())?)
     }
+
+    /// Verifies the given certified nodes, batch-verifying the multi-signatures of their
+    /// certificates (which is much faster than verifying them one by one for large fetches).
+    pub fn verify_batch(
+        certified_nodes: &[CertifiedNode],
+        verifier: &ValidatorVerifier,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            certified_nodes
+                .iter()
+                .all(|node| node.digest() == node.calculate_digest()),
+            "invalid digest"
+        );
+
+        let messages_and_signatures: Vec<_> = certified_nodes
+            .iter()
+            .map(|node| (node.metadata(), node.signatures()))
+            .collect();
+        Ok(verifier.batch_verify_multi_signatures(&messages_and_signatures)?)
+    }
 }
 
 impl Deref for CertifiedNode {
@@ -511,6 +531,10 @@ impl Vote {
         }
     }
 
+    pub fn metadata(&self) -> &NodeMetadata {
+        &self.metadata
+    }
+
     pub fn signature(&self) -> &Signature {
         &self.signature
     }
@@ -760,9 +784,116 @@ impl FetchResponse {
             "nodes don't match requested bitmask"
         );
         ensure!(
-            self.certified_nodes
+            CertifiedNode::verify_batch(&self.certified_nodes, validator_verifier).is_ok(),
+            "unable to verify certified nodes"
+        );
+
+        Ok(self)
+    }
+}
+
+/// Represents a request to fetch all the nodes missing from `exists_bitmask`, i.e., the nodes
+/// in the contiguous range of rounds [first_round, first_round + len - 1] by the authors that
+/// are not set in the bitmask. The response may be split into multiple parts to bound its size.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RemoteFetchRangeRequest {
+    epoch: u64,
+    exists_bitmask: DagSnapshotBitmask,
+}
+
+impl RemoteFetchRangeRequest {
+    pub fn new(epoch: u64, exists_bitmask: DagSnapshotBitmask) -> Self {
+        Self {
+            epoch,
+            exists_bitmask,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn exists_bitmask(&self) -> &DagSnapshotBitmask {
+        &self.exists_bitmask
+    }
+
+    pub fn start_round(&self) -> Round {
+        self.exists_bitmask.first_round()
+    }
+
+    pub fn end_round(&self) -> Round {
+        self.exists_bitmask.first_round() + self.exists_bitmask.len() as u64 - 1
+    }
+
+    pub fn verify(&self, verifier: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(!self.exists_bitmask.bitmask.is_empty(), "Range is empty");
+        ensure!(
+            self.exists_bitmask
+                .bitmask
                 .iter()
-                .all(|node| node.verify(validator_verifier).is_ok()),
+                .all(|round| round.len() == verifier.len()),
+            "invalid bitmask: each round length is not equal to validator count"
+        );
+
+        Ok(())
+    }
+}
+
+/// Represents one part of the response to a [RemoteFetchRangeRequest], `certified_nodes` are
+/// ordered by round. If `has_more` is set, the response was truncated to bound its size, and the
+/// remaining nodes should be requested with an updated bitmask.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FetchRangeResponse {
+    epoch: u64,
+    certified_nodes: Vec<CertifiedNode>,
+    has_more: bool,
+}
+
+impl FetchRangeResponse {
+    pub fn new(epoch: u64, certified_nodes: Vec<CertifiedNode>, has_more: bool) -> Self {
+        Self {
+            epoch,
+            certified_nodes,
+            has_more,
+        }
+    }
+
+    pub fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    pub fn certified_nodes(self) -> Vec<CertifiedNode> {
+        self.certified_nodes
+    }
+
+    pub fn verify(
+        self,
+        request: &RemoteFetchRangeRequest,
+        validator_verifier: &ValidatorVerifier,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            !self.has_more || !self.certified_nodes.is_empty(),
+            "partial response is empty"
+        );
+        ensure!(
+            self.certified_nodes.iter().all(|node| {
+                let round = node.round();
+                let author = node.author();
+                if round < request.start_round() || round > request.end_round() {
+                    return false;
+                }
+                if let Some(author_idx) =
+                    validator_verifier.address_to_validator_index().get(author)
+                {
+                    !request.exists_bitmask.has(round, *author_idx)
+                } else {
+                    false
+                }
+            }),
+            "nodes don't match requested bitmask"
+        );
+        ensure!(
+            CertifiedNode::verify_batch(&self.certified_nodes, validator_verifier).is_ok(),
             "unable to verify certified nodes"
         );
 
@@ -808,6 +939,8 @@ pub enum DAGMessage {
     CertifiedAckMsg(CertifiedAck),
     FetchRequest(RemoteFetchRequest),
     FetchResponse(FetchResponse),
+    FetchRangeRequest(RemoteFetchRangeRequest),
+    FetchRangeResponse(FetchRangeResponse),
 
     #[cfg(test)]
     TestMessage(TestMessage),
@@ -824,6 +957,8 @@ impl DAGMessage {
             DAGMessage::CertifiedAckMsg(_) => "CertifiedAckMsg",
             DAGMessage::FetchRequest(_) => "FetchRequest",
             DAGMessage::FetchResponse(_) => "FetchResponse",
+            DAGMessage::FetchRangeRequest(_) => "FetchRangeRequest",
+            DAGMessage::FetchRangeResponse(_) => "FetchRangeResponse",
             #[cfg(test)]
             DAGMessage::TestMessage(_) => "TestMessage",
             #[cfg(test)]
@@ -844,9 +979,11 @@ impl DAGMessage {
             DAGMessage::NodeMsg(node) => node.verify(sender, verifier),
             DAGMessage::CertifiedNodeMsg(certified_node) => certified_node.verify(sender, verifier),
             DAGMessage::FetchRequest(fetch_request) => fetch_request.verify(verifier),
+            DAGMessage::FetchRangeRequest(fetch_request) => fetch_request.verify(verifier),
             DAGMessage::VoteMsg(_)
             | DAGMessage::CertifiedAckMsg(_)
-            | DAGMessage::FetchResponse(_) => {
+            | DAGMessage::FetchResponse(_)
+            | DAGMessage::FetchRangeResponse(_) => {
                 bail!("Unexpected to verify {} in rpc handler", self.name())
             },
             #[cfg(test)]
@@ -868,6 +1005,8 @@ impl TConsensusMsg for DAGMessage {
             DAGMessage::CertifiedAckMsg(ack) => ack.epoch,
             DAGMessage::FetchRequest(req) => req.epoch,
             DAGMessage::FetchResponse(res) => res.epoch,
+            DAGMessage::FetchRangeRequest(req) => req.epoch,
+            DAGMessage::FetchRangeResponse(res) => res.epoch,
             #[cfg(test)]
             DAGMessage::TestMessage(_) => 1,
             #[cfg(test)]
@@ -0,0 +1,124 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    dag::types::DAGMessage,
+    monitor,
+    network::{IncomingDAGRequest, RpcResponder},
+};
+use aptos_bounded_executor::BoundedExecutor;
+use aptos_consensus_types::common::Author;
+use aptos_types::epoch_state::EpochState;
+use futures::{
+    future,
+    stream::{self, FusedStream},
+    FutureExt, Stream, StreamExt,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// The maximum number of messages that may be verified (or awaiting delivery)
+/// at once. Bounds the memory held by the pool when the handler falls behind.
+const MAX_PENDING_VERIFICATIONS: usize = 128;
+
+/// The outcome of verifying an incoming request: the verified message (or the
+/// verification error), the epoch, the sender and the responder.
+pub(crate) type VerifiedRequest = (anyhow::Result<DAGMessage>, u64, Author, RpcResponder);
+
+/// Verifies incoming DAG requests on a bounded pool of blocking threads, so that
+/// signature verification doesn't hog the async runtime. Requests from different
+/// authors are verified in parallel, but the verified requests of each author
+/// are yielded in the order they were received.
+pub(crate) fn verified_request_stream<S>(
+    requests: S,
+    executor: BoundedExecutor,
+    epoch_state: Arc<EpochState>,
+) -> impl FusedStream<Item = VerifiedRequest>
+where
+    S: Stream<Item = IncomingDAGRequest> + Send,
+{
+    let mut next_sequence_numbers: HashMap<Author, u64> = HashMap::new();
+    requests
+        .then(move |request| {
+            let sequence_number = next_sequence_numbers.entry(request.sender).or_default();
+            let request_sequence_number = *sequence_number;
+            *sequence_number += 1;
+
+            let executor = executor.clone();
+            let epoch_state = epoch_state.clone();
+            async move {
+                // This waits for a slot in the pool, which applies backpressure
+                // to the incoming requests.
+                executor
+                    .spawn_blocking(move || verify_request(request, &epoch_state))
+                    .await
+                    .map(move |result| {
+                        (
+                            request_sequence_number,
+                            result.expect("verification must not panic"),
+                        )
+                    })
+            }
+        })
+        .buffer_unordered(MAX_PENDING_VERIFICATIONS)
+        .scan(
+            PerAuthorReorderBuffer::default(),
+            |reorder_buffer, (sequence_number, verified_request)| {
+                let author = verified_request.2;
+                let ready = reorder_buffer.push(author, sequence_number, verified_request);
+                future::ready(Some(stream::iter(ready)))
+            },
+        )
+        .flatten()
+        .fuse()
+}
+
+fn verify_request(request: IncomingDAGRequest, epoch_state: &EpochState) -> VerifiedRequest {
+    let epoch = request.req.epoch();
+    let result = request.req.try_into().and_then(|dag_message: DAGMessage| {
+        monitor!(
+            "dag_message_verify",
+            dag_message.verify(request.sender, &epoch_state.verifier)
+        )?;
+        Ok(dag_message)
+    });
+    (result, epoch, request.sender, request.responder)
+}
+
+/// Restores the per-author order of items that complete out of order. Each
+/// author's items are numbered sequentially (starting at 0) in the order they
+/// were received, and are only released once all earlier items have been.
+pub(crate) struct PerAuthorReorderBuffer<T> {
+    /// The sequence number of the next item to release for each author
+    next_sequence_numbers: HashMap<Author, u64>,
+    /// The items that completed before an earlier item of the same author
+    pending: HashMap<Author, BTreeMap<u64, T>>,
+}
+
+impl<T> Default for PerAuthorReorderBuffer<T> {
+    fn default() -> Self {
+        Self {
+            next_sequence_numbers: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+}
+
+impl<T> PerAuthorReorderBuffer<T> {
+    /// Adds a completed item, and returns the items of the author that are now
+    /// ready to be released, in order.
+    pub(crate) fn push(&mut self, author: Author, sequence_number: u64, item: T) -> Vec<T> {
+        let pending = self.pending.entry(author).or_default();
+        pending.insert(sequence_number, item);
+
+        let next_sequence_number = self.next_sequence_numbers.entry(author).or_default();
+        let mut ready = vec![];
+        while let Some(item) = pending.remove(next_sequence_number) {
+            ready.push(item);
+            *next_sequence_number += 1;
+        }
+        ready
+    }
+}
@@ -5,6 +5,7 @@
 
 use crate::{
     block_preparer::BlockPreparer,
+    block_storage::tracing::trace_txns,
     monitor,
     state_computer::{PipelineExecutionResult, StateComputeResultFut},
 };
@@ -15,7 +16,7 @@ use aptos_executor_types::{
     ExecutorResult,
 };
 use aptos_experimental_runtimes::thread_manager::optimal_min_len;
-use aptos_logger::{debug, error};
+use aptos_logger::{debug, error, txn_trace::TxnTraceStage};
 use aptos_types::{
     block_executor::{config::BlockExecutorConfigFromOnchain, partitioner::ExecutableBlock},
     block_metadata_ext::BlockMetadataExt,
@@ -26,7 +27,10 @@ use aptos_types::{
 use fail::fail_point;
 use once_cell::sync::Lazy;
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
-use std::sync::Arc;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tokio::sync::{mpsc, oneshot};
 
 pub static SIG_VERIFY_POOL: Lazy<Arc<rayon::ThreadPool>> = Lazy::new(|| {
@@ -122,6 +126,12 @@ impl ExecutionPipeline {
         }
         let validator_txns = block.validator_txns().cloned().unwrap_or_default();
         let input_txns = input_txns.unwrap();
+        // Blocks only reach the execution pipeline once they're ordered
+        trace_txns(
+            &input_txns,
+            TxnTraceStage::ConsensusOrdering,
+            UNIX_EPOCH + Duration::from_micros(block.timestamp_usecs()),
+        );
         tokio::task::spawn_blocking(move || {
             let txns_to_execute =
                 Block::combine_to_input_transactions(validator_txns, input_txns.clone(), metadata);
@@ -177,6 +187,7 @@ impl ExecutionPipeline {
             let block_id = block.block_id;
             debug!("execute_stage received block {}.", block_id);
             let executor = executor.clone();
+            let execution_start_time = SystemTime::now();
             let state_checkpoint_output = monitor!(
                 "execute_block",
                 tokio::task::spawn_blocking(move || {
@@ -194,6 +205,7 @@ impl ExecutionPipeline {
                 .await
             )
             .expect("Failed to spawn_blocking.");
+            trace_txns(&input_txns, TxnTraceStage::Execution, execution_start_time);
 
             ledger_apply_tx
                 .send(LedgerApplyCommand {
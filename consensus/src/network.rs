@@ -34,8 +34,14 @@ use aptos_consensus_types::{
 };
 use aptos_logger::prelude::*;
 use aptos_network::{
-    application::interface::{NetworkClient, NetworkServiceEvents},
-    protocols::{network::Event, rpc::error::RpcError},
+    application::{
+        interface::{NetworkClient, NetworkServiceEvents},
+        reputation::PeerMisbehavior,
+    },
+    protocols::{
+        network::Event,
+        rpc::error::{RpcApplicationError, RpcError},
+    },
     ProtocolId,
 };
 use aptos_reliable_broadcast::{RBMessage, RBNetworkSender};
@@ -88,6 +94,19 @@ impl RpcResponder {
             .send(rpc_response)
             .map_err(|_| anyhow::anyhow!("unable to respond to rpc"))
     }
+
+    /// Returns true if the requester's deadline has passed, in which case
+    /// nobody is waiting for the response and the request can be dropped.
+    pub fn is_expired(&self) -> bool {
+        self.response_sender.is_canceled()
+    }
+
+    /// Responds with a structured error, which is sent back to the requester
+    pub fn respond_with_error(self, error: RpcApplicationError) -> anyhow::Result<()> {
+        self.response_sender
+            .send(Err(error.into()))
+            .map_err(|_| anyhow::anyhow!("unable to respond to rpc"))
+    }
 }
 
 /// The block retrieval request is used internally for implementing RPC: the callback is executed
@@ -584,6 +603,11 @@ impl TDAGNetworkSender for NetworkSender {
             max_concurrent_responders,
         )
     }
+
+    fn report_peer_misbehavior(&self, peer: Author, misbehavior: PeerMisbehavior) {
+        self.consensus_network_client
+            .report_peer_misbehavior(peer, misbehavior);
+    }
 }
 
 #[async_trait]
@@ -22,7 +22,7 @@ use aptos_consensus_types::{
     vote_msg::VoteMsg,
 };
 use aptos_network::{
-    application::{error::Error, interface::NetworkClientInterface},
+    application::{error::Error, interface::NetworkClientInterface, reputation::PeerMisbehavior},
     ProtocolId,
 };
 use aptos_types::{epoch_change::EpochChangeProof, PeerId};
@@ -125,6 +125,7 @@ pub struct ConsensusNetworkClient<NetworkClient> {
 
 /// Supported protocols in preferred order (from highest priority to lowest).
 pub const RPC: &[ProtocolId] = &[
+    ProtocolId::ConsensusRpcZstd,
     ProtocolId::ConsensusRpcCompressed,
     ProtocolId::ConsensusRpcBcs,
     ProtocolId::ConsensusRpcJson,
@@ -132,6 +133,7 @@ pub const RPC: &[ProtocolId] = &[
 
 /// Supported protocols in preferred order (from highest priority to lowest).
 pub const DIRECT_SEND: &[ProtocolId] = &[
+    ProtocolId::ConsensusDirectSendZstd,
     ProtocolId::ConsensusDirectSendCompressed,
     ProtocolId::ConsensusDirectSendBcs,
     ProtocolId::ConsensusDirectSendJson,
@@ -175,6 +177,14 @@ impl<NetworkClient: NetworkClientInterface<ConsensusMsg>> ConsensusNetworkClient
             .await
     }
 
+    /// Reports the given misbehavior for the peer (e.g., an invalid message)
+    pub fn report_peer_misbehavior(&self, peer: PeerId, misbehavior: PeerMisbehavior) {
+        let peer_network_id = self.get_peer_network_id_for_peer(peer);
+        self.network_client
+            .get_peers_and_metadata()
+            .report_peer_misbehavior(peer_network_id, misbehavior);
+    }
+
     // TODO: we shouldn't need to expose this. Migrate the code to handle
     // peer and network ids.
     fn get_peer_network_id_for_peer(&self, peer: PeerId) -> PeerNetworkId {
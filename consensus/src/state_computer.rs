@@ -4,7 +4,7 @@
 
 use crate::{
     block_preparer::BlockPreparer,
-    block_storage::tracing::{observe_block, BlockStage},
+    block_storage::tracing::{observe_block, trace_txns, BlockStage},
     counters,
     error::StateSyncError,
     execution_pipeline::ExecutionPipeline,
@@ -22,7 +22,7 @@ use aptos_consensus_types::{block::Block, common::Round, pipelined_block::Pipeli
 use aptos_crypto::HashValue;
 use aptos_executor_types::{BlockExecutorTrait, ExecutorResult, StateComputeResult};
 use aptos_infallible::RwLock;
-use aptos_logger::prelude::*;
+use aptos_logger::{prelude::*, txn_trace::TxnTraceStage};
 use aptos_types::{
     account_address::AccountAddress,
     block_executor::config::BlockExecutorConfigFromOnchain,
@@ -34,7 +34,7 @@ use aptos_types::{
 };
 use fail::fail_point;
 use futures::{future::BoxFuture, SinkExt, StreamExt};
-use std::{boxed::Box, sync::Arc};
+use std::{boxed::Box, sync::Arc, time::SystemTime};
 use tokio::sync::Mutex as AsyncMutex;
 
 pub type StateComputeResultFut = BoxFuture<'static, ExecutorResult<PipelineExecutionResult>>;
@@ -300,6 +300,7 @@ impl StateComputer for ExecutionProxy {
 
         let executor = self.executor.clone();
         let proof = finality_proof.clone();
+        let commit_start_time = SystemTime::now();
         monitor!(
             "commit_block",
             tokio::task::spawn_blocking(move || {
@@ -310,6 +311,13 @@ impl StateComputer for ExecutionProxy {
             .await
         )
         .expect("spawn_blocking failed");
+        for block in blocks {
+            trace_txns(
+                block.input_transactions(),
+                TxnTraceStage::Commit,
+                commit_start_time,
+            );
+        }
 
         let blocks = blocks.to_vec();
         let wrapped_callback = move || {
@@ -0,0 +1,131 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Error;
+use aptos_logger::info;
+use aptos_mempool::{MempoolClientRequest, MempoolClientSender};
+use aptos_system_utils::utils::{reply_with, reply_with_status};
+use aptos_types::account_address::AccountAddress;
+use futures::{channel::oneshot, SinkExt};
+use http::header::{HeaderValue, CONTENT_TYPE};
+use hyper::{Body, Request, Response, StatusCode};
+use std::collections::HashMap;
+
+/// Lists the transactions of the account in mempool, including the parked ones, with their
+/// broadcast state.
+pub async fn handle_account_transactions_request(
+    req: Request<Body>,
+    mut mempool_client_sender: MempoolClientSender,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let address: AccountAddress = match query_pairs.get("address") {
+        Some(val) => match AccountAddress::from_str_strict(val) {
+            Ok(val) => val,
+            Err(err) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, err.to_string())),
+        },
+        None => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                "Missing address.",
+            ))
+        },
+    };
+
+    info!("Dumping mempool transactions of account {address}.");
+
+    let (callback, receiver) = oneshot::channel();
+    let result = async {
+        mempool_client_sender
+            .send(MempoolClientRequest::GetAccountTransactions(
+                address, callback,
+            ))
+            .await?;
+        let txns = receiver.await?;
+        Ok::<_, Error>(serde_json::to_vec(&txns)?)
+    }
+    .await;
+
+    match result {
+        Ok(body) => Ok(reply_with(
+            vec![(CONTENT_TYPE, HeaderValue::from_static("application/json"))],
+            body,
+        )),
+        Err(e) => {
+            info!("Failed to dump mempool transactions: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
+/// Evicts the transaction of the account with the sequence number from mempool, e.g. one that
+/// blocks the account, without having to restart the node.
+pub async fn handle_evict_transaction_request(
+    req: Request<Body>,
+    mut mempool_client_sender: MempoolClientSender,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let address: AccountAddress = match query_pairs.get("address") {
+        Some(val) => match AccountAddress::from_str_strict(val) {
+            Ok(val) => val,
+            Err(err) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, err.to_string())),
+        },
+        None => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                "Missing address.",
+            ))
+        },
+    };
+    let sequence_number: u64 = match query_pairs.get("sequence_number") {
+        Some(val) => match val.parse() {
+            Ok(val) => val,
+            Err(err) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, err.to_string())),
+        },
+        None => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                "Missing sequence_number.",
+            ))
+        },
+    };
+
+    info!("Evicting mempool transaction {address}:{sequence_number}.");
+
+    let (callback, receiver) = oneshot::channel();
+    let result = async {
+        mempool_client_sender
+            .send(MempoolClientRequest::EvictTransaction(
+                address,
+                sequence_number,
+                callback,
+            ))
+            .await?;
+        Ok::<_, Error>(receiver.await?)
+    }
+    .await;
+
+    match result {
+        Ok(true) => Ok(reply_with_status(
+            StatusCode::OK,
+            format!("Evicted transaction {address}:{sequence_number}."),
+        )),
+        Ok(false) => Ok(reply_with_status(
+            StatusCode::NOT_FOUND,
+            format!("Transaction {address}:{sequence_number} is not in mempool."),
+        )),
+        Err(e) => {
+            info!("Failed to evict mempool transaction: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
@@ -7,6 +7,7 @@ use aptos_consensus::{
 };
 use aptos_infallible::RwLock;
 use aptos_logger::info;
+use aptos_mempool::MempoolClientSender;
 use aptos_storage_interface::DbReaderWriter;
 use aptos_system_utils::utils::reply_with_status;
 #[cfg(target_os = "linux")]
@@ -26,6 +27,7 @@ use std::{
 use tokio::runtime::Runtime;
 
 mod consensus;
+mod mempool;
 
 #[derive(Default)]
 pub struct Context {
@@ -34,6 +36,7 @@ pub struct Context {
     aptos_db: RwLock<Option<Arc<DbReaderWriter>>>,
     consensus_db: RwLock<Option<Arc<StorageWriteProxy>>>,
     quorum_store_db: RwLock<Option<Arc<QuorumStoreDB>>>,
+    mempool_client_sender: RwLock<Option<MempoolClientSender>>,
 }
 
 impl Context {
@@ -49,6 +52,10 @@ impl Context {
         *self.consensus_db.write() = Some(consensus_db);
         *self.quorum_store_db.write() = Some(quorum_store_db);
     }
+
+    fn set_mempool_client_sender(&self, mempool_client_sender: MempoolClientSender) {
+        *self.mempool_client_sender.write() = Some(mempool_client_sender);
+    }
 }
 
 pub struct AdminService {
@@ -107,6 +114,11 @@ impl AdminService {
             .set_consensus_dbs(consensus_db, quorum_store_db)
     }
 
+    pub fn set_mempool_client_sender(&self, mempool_client_sender: MempoolClientSender) {
+        self.context
+            .set_mempool_client_sender(mempool_client_sender)
+    }
+
     fn start(&self, address: SocketAddr, enabled: bool) {
         let context = self.context.clone();
         self.runtime.spawn(async move {
@@ -210,6 +222,28 @@ impl AdminService {
                     ))
                 }
             },
+            (hyper::Method::GET, "/debug/mempool/account") => {
+                let mempool_client_sender = context.mempool_client_sender.read().clone();
+                if let Some(mempool_client_sender) = mempool_client_sender {
+                    mempool::handle_account_transactions_request(req, mempool_client_sender).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Mempool is not available.",
+                    ))
+                }
+            },
+            (hyper::Method::POST, "/debug/mempool/evict") => {
+                let mempool_client_sender = context.mempool_client_sender.read().clone();
+                if let Some(mempool_client_sender) = mempool_client_sender {
+                    mempool::handle_evict_transaction_request(req, mempool_client_sender).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Mempool is not available.",
+                    ))
+                }
+            },
             _ => Ok(reply_with_status(StatusCode::NOT_FOUND, "Not found.")),
         }
     }
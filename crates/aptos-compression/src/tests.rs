@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::CompressionClient;
+use crate::{CompressionAlgorithm, CompressionClient};
 use aptos_crypto::{ed25519::Ed25519PrivateKey, hash::HashValue, PrivateKey, SigningKey, Uniform};
 use aptos_types::{
     account_address::AccountAddress,
@@ -21,6 +21,8 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 
 // Useful test constants
+const ALGORITHMS: [CompressionAlgorithm; 2] =
+    [CompressionAlgorithm::Lz4, CompressionAlgorithm::Zstd];
 const MAX_COMPRESSION_SIZE: usize = 64 * 1024 * 1024; // 64 MiBi
 const MIB: usize = 1024 * 1024;
 
@@ -49,50 +51,79 @@ fn test_compression_limits() {
     let too_small_bytes = 1;
     let transactions_with_proof = create_transaction_list_with_proof(1000, 1999, 1999, true);
 
-    // Test compression limit
-    let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
-    let maybe_compressed_bytes = crate::compress(
-        bcs_encoded_bytes,
-        CompressionClient::StateSync,
-        too_small_bytes,
-    );
-    assert!(maybe_compressed_bytes.is_err());
+    for algorithm in ALGORITHMS {
+        // Test compression limit
+        let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
+        let maybe_compressed_bytes = crate::compress_with_algorithm(
+            bcs_encoded_bytes,
+            CompressionClient::StateSync,
+            too_small_bytes,
+            algorithm,
+        );
+        assert!(maybe_compressed_bytes.is_err());
+
+        // Test decompression limit
+        let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
+        let compressed_bytes = crate::compress_with_algorithm(
+            bcs_encoded_bytes,
+            CompressionClient::StateSync,
+            MAX_COMPRESSION_SIZE,
+            algorithm,
+        )
+        .unwrap();
+        let maybe_decompressed_bytes = crate::decompress_with_algorithm(
+            &compressed_bytes,
+            CompressionClient::StateSync,
+            too_small_bytes,
+            algorithm,
+        );
+        assert!(maybe_decompressed_bytes.is_err());
+    }
+}
 
-    // Test decompression limit
-    let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
-    let compressed_bytes = crate::compress(
+#[test]
+fn test_algorithm_mismatch() {
+    // Data compressed with one algorithm can't be decompressed with the other
+    let bcs_encoded_bytes = bcs::to_bytes(&create_epoch_ending_ledger_infos(0, 99)).unwrap();
+    let compressed_bytes = crate::compress_with_algorithm(
         bcs_encoded_bytes,
-        CompressionClient::StateSync,
+        CompressionClient::Consensus,
         MAX_COMPRESSION_SIZE,
+        CompressionAlgorithm::Zstd,
     )
     .unwrap();
-    let maybe_decompressed_bytes = crate::decompress(
+    let maybe_decompressed_bytes = crate::decompress_with_algorithm(
         &compressed_bytes,
-        CompressionClient::StateSync,
-        too_small_bytes,
+        CompressionClient::Consensus,
+        MAX_COMPRESSION_SIZE,
+        CompressionAlgorithm::Lz4,
     );
     assert!(maybe_decompressed_bytes.is_err());
 }
 
 /// Ensures that the given object can be compressed and decompressed successfully
-/// when BCS encoded.
+/// when BCS encoded (using all supported algorithms).
 fn test_compress_and_decompress<T: Debug + DeserializeOwned + PartialEq + Serialize>(object: T) {
-    let bcs_encoded_bytes = bcs::to_bytes(&object).unwrap();
-    let compressed_bytes = crate::compress(
-        bcs_encoded_bytes,
-        CompressionClient::StateSync,
-        MAX_COMPRESSION_SIZE,
-    )
-    .unwrap();
-    let decompressed_bytes = crate::decompress(
-        &compressed_bytes,
-        CompressionClient::StateSync,
-        MAX_COMPRESSION_SIZE,
-    )
-    .unwrap();
-    let decoded_object = bcs::from_bytes::<T>(&decompressed_bytes).unwrap();
-
-    assert_eq!(object, decoded_object);
+    for algorithm in ALGORITHMS {
+        let bcs_encoded_bytes = bcs::to_bytes(&object).unwrap();
+        let compressed_bytes = crate::compress_with_algorithm(
+            bcs_encoded_bytes,
+            CompressionClient::StateSync,
+            MAX_COMPRESSION_SIZE,
+            algorithm,
+        )
+        .unwrap();
+        let decompressed_bytes = crate::decompress_with_algorithm(
+            &compressed_bytes,
+            CompressionClient::StateSync,
+            MAX_COMPRESSION_SIZE,
+            algorithm,
+        )
+        .unwrap();
+        let decoded_object = bcs::from_bytes::<T>(&decompressed_bytes).unwrap();
+
+        assert_eq!(object, decoded_object);
+    }
 }
 
 /// Creates a test epoch change proof
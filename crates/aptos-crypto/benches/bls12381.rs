@@ -65,6 +65,7 @@ fn bench_group(c: &mut Criterion) {
 
         verify_multisig(&mut group, size);
         verify_aggsig(&mut group, size);
+        verify_batch(&mut group, size);
         size *= 2;
     }
 
@@ -415,6 +416,46 @@ fn verify_aggsig<M: Measurement>(g: &mut BenchmarkGroup<M>, n: usize) {
     });
 }
 
+/// Benchmarks the time to batch-verify `n` signatures on different messages from the perspective of
+/// a verifier who receives the individual signatures from `n` signers. (Compare with `verify_aggsig`,
+/// which cannot tell which of the signatures are invalid.)
+fn verify_batch<M: Measurement>(g: &mut BenchmarkGroup<M>, n: usize) {
+    let mut rng = thread_rng();
+
+    // pick `n` random keypairs
+    let key_pairs: Vec<KeyPair<bls12381::PrivateKey, bls12381::PublicKey>> =
+        random_keypairs(&mut rng, n);
+
+    g.throughput(Throughput::Elements(n as u64));
+    g.bench_with_input(BenchmarkId::new("verify_batch", n), &n, |b, &_n| {
+        b.iter_batched(
+            || {
+                // each of the signers computes a signature share on a random message
+                let mut sigshares = vec![];
+                let mut pks = vec![];
+                let mut msgs = vec![];
+
+                for kp in key_pairs.iter() {
+                    msgs.push(random_message(&mut rng));
+                    sigshares.push(kp.private_key.sign(msgs.last().unwrap()).unwrap());
+                    pks.push(&kp.public_key)
+                }
+
+                (msgs, pks, sigshares)
+            },
+            |(msgs, pks, sigshares)| {
+                let msgs_refs = msgs.iter().collect::<Vec<&TestAptosCrypto>>();
+                let sigs_refs = sigshares.iter().collect::<Vec<&bls12381::Signature>>();
+
+                let result = bls12381::Signature::verify_batch(&msgs_refs, &pks, &sigs_refs);
+
+                assert!(result.is_ok());
+            },
+            BatchSize::SmallInput,
+        );
+    });
+}
+
 criterion_group!(
     name = bls12381_benches;
     config = Criterion::default(); //.measurement_time(Duration::from_secs(100));
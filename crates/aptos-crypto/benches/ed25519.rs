@@ -11,7 +11,9 @@ use aptos_crypto::{
     PrivateKey,
 };
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
-use criterion::{measurement::Measurement, BenchmarkGroup, Criterion, Throughput};
+use criterion::{
+    measurement::Measurement, BatchSize, BenchmarkGroup, BenchmarkId, Criterion, Throughput,
+};
 use curve25519_dalek::{constants::ED25519_BASEPOINT_POINT, scalar::Scalar};
 use rand::{distributions, prelude::ThreadRng, thread_rng, Rng};
 use serde::{Deserialize, Serialize};
@@ -39,6 +41,13 @@ fn benchmark_groups(c: &mut Criterion) {
     sig_deserialize(&mut group);
     small_subgroup_check(&mut group);
 
+    let mut size = 16;
+    for _ in 1..=4 {
+        // Compare the per-signature throughput with `sig_verify_struct`
+        sig_verify_batch(&mut group, size);
+        size *= 4;
+    }
+
     group.finish();
 }
 
@@ -61,6 +70,45 @@ fn sig_verify_struct<M: Measurement>(g: &mut BenchmarkGroup<M>) {
     });
 }
 
+/// Benchmarks the time to batch-verify `size` signatures on different messages under different keys.
+fn sig_verify_batch<M: Measurement>(g: &mut BenchmarkGroup<M>, size: usize) {
+    let mut csprng: ThreadRng = thread_rng();
+
+    let priv_keys: Vec<Ed25519PrivateKey> = (0..size)
+        .map(|_| Ed25519PrivateKey::generate(&mut csprng))
+        .collect();
+    let pub_keys: Vec<Ed25519PublicKey> = priv_keys.iter().map(|k| k.public_key()).collect();
+
+    g.throughput(Throughput::Elements(size as u64));
+    g.bench_with_input(
+        BenchmarkId::new("sig_verify_batch", size),
+        &size,
+        move |b, &size| {
+            b.iter_batched(
+                || {
+                    let msgs: Vec<_> = (0..size).map(|_| random_message(&mut csprng)).collect();
+                    let sigs: Vec<_> = priv_keys
+                        .iter()
+                        .zip(msgs.iter())
+                        .map(|(priv_key, msg)| priv_key.sign(msg).unwrap())
+                        .collect();
+                    (msgs, sigs)
+                },
+                |(msgs, sigs)| {
+                    let msgs_refs: Vec<_> = msgs.iter().collect();
+                    let pub_keys_refs: Vec<_> = pub_keys.iter().collect();
+                    let sigs_refs: Vec<_> = sigs.iter().collect();
+
+                    let result =
+                        Ed25519Signature::verify_batch(&msgs_refs, &pub_keys_refs, &sigs_refs);
+                    assert!(result.is_ok());
+                },
+                BatchSize::SmallInput,
+            );
+        },
+    );
+}
+
 /// Benchmarks the time to verify a signature on an empty message. (Used for gas estimation.)
 fn sig_verify_zero_bytes<M: Measurement>(g: &mut BenchmarkGroup<M>) {
     let mut csprng: ThreadRng = thread_rng();
@@ -27,10 +27,14 @@ use crate::{
 };
 use anyhow::{anyhow, Result};
 use aptos_crypto_derive::{DeserializeKey, SerializeKey};
-use blst::BLST_ERROR;
+use blst::{blst_scalar, BLST_ERROR};
+use rand::RngCore;
 use serde::Serialize;
 use std::{convert::TryFrom, fmt};
 
+/// The number of random bits in the scalars used to weigh the signatures in a batch verification
+const BATCH_VERIFICATION_RAND_BITS: usize = 64;
+
 #[derive(Clone, Eq, SerializeKey, DeserializeKey)]
 /// Either (1) a BLS signature share from an individual signer, (2) a BLS multisignature or (3) a
 /// BLS aggregate signature
@@ -118,6 +122,84 @@ impl Signature {
         self.verify_aggregate_arbitrary_msg(&msgs_refs, pks)
     }
 
+    /// Batch-verifies the signatures in `sigs`, i.e., verifies that each `sigs[i]` is a valid
+    /// signature (or multisignature) on `msgs[i]` under `pks[i]`. This is faster than verifying
+    /// each signature individually, since all the pairings share a single final exponentiation.
+    ///
+    /// Unlike verifying the aggregate of `sigs` via `Signature::verify_aggregate_arbitrary_msg`,
+    /// each signature is weighted by a random 64-bit scalar, so an invalid signature cannot be
+    /// offset by another signature in the batch. If the batch fails to verify, the caller has to
+    /// verify the signatures individually to find the invalid ones.
+    ///
+    /// WARNING: This function assumes that the public keys have been subgroup-checked by the caller
+    /// implicitly when verifying their proof-of-possession (PoP) in `ProofOfPossession::verify`.
+    pub fn verify_batch_arbitrary_msgs(
+        msgs: &[&[u8]],
+        pks: &[&PublicKey],
+        sigs: &[&Signature],
+    ) -> Result<()> {
+        if msgs.len() != pks.len() || msgs.len() != sigs.len() {
+            return Err(anyhow!(
+                "Mismatched batch lengths: {} messages, {} public keys and {} signatures",
+                msgs.len(),
+                pks.len(),
+                sigs.len()
+            ));
+        }
+        if sigs.is_empty() {
+            return Ok(());
+        }
+
+        let pks = pks
+            .iter()
+            .map(|&pk| &pk.pubkey)
+            .collect::<Vec<&blst::min_pk::PublicKey>>();
+        let sigs = sigs
+            .iter()
+            .map(|&sig| &sig.sig)
+            .collect::<Vec<&blst::min_pk::Signature>>();
+        let mut rng = rand::thread_rng();
+        let rands = (0..sigs.len())
+            .map(|_| random_batch_scalar(&mut rng))
+            .collect::<Vec<_>>();
+
+        let result = blst::min_pk::Signature::verify_multiple_aggregate_signatures(
+            msgs,
+            DST_BLS_SIG_IN_G2_WITH_POP,
+            &pks,
+            false,
+            &sigs,
+            true,
+            &rands,
+            BATCH_VERIFICATION_RAND_BITS,
+        );
+
+        if result == BLST_ERROR::BLST_SUCCESS {
+            Ok(())
+        } else {
+            Err(anyhow!("{:?}", result))
+        }
+    }
+
+    /// Serializes the messages of type `T` to bytes and calls `Signature::verify_batch_arbitrary_msgs`.
+    pub fn verify_batch<T: CryptoHash + Serialize>(
+        msgs: &[&T],
+        pks: &[&PublicKey],
+        sigs: &[&Signature],
+    ) -> Result<()> {
+        let mut messages: Vec<Vec<u8>> = vec![];
+        for message in msgs {
+            messages.push(signing_message(*message)?);
+        }
+
+        let msgs_refs = messages
+            .iter()
+            .map(|m| m.as_slice())
+            .collect::<Vec<&[u8]>>();
+
+        Self::verify_batch_arbitrary_msgs(&msgs_refs, pks, sigs)
+    }
+
     /// Return a dummy signature for testing.
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn dummy_signature() -> Self {
@@ -130,6 +212,19 @@ impl Signature {
     }
 }
 
+/// Returns a random non-zero scalar of `BATCH_VERIFICATION_RAND_BITS` bits, used to weigh a
+/// signature in a batch verification.
+fn random_batch_scalar<R: RngCore>(rng: &mut R) -> blst_scalar {
+    let mut value = 0;
+    while value == 0 {
+        value = rng.next_u64();
+    }
+
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(&value.to_le_bytes());
+    blst_scalar { b: bytes }
+}
+
 ///////////////////////////
 // SignatureShare Traits //
 ///////////////////////////
@@ -11,6 +11,7 @@ use crate::{
 use anyhow::{anyhow, Result};
 use aptos_crypto_derive::{DeserializeKey, SerializeKey};
 use core::convert::TryFrom;
+use curve25519_dalek::edwards::CompressedEdwardsY;
 use serde::Serialize;
 use std::{cmp::Ordering, fmt};
 
@@ -83,6 +84,77 @@ impl Ed25519Signature {
         // As this stage S == L which implies a non canonical S.
         false
     }
+
+    /// Batch-verifies the signatures in `signatures`, i.e., verifies that each `signatures[i]` is
+    /// a valid signature on `messages[i]` under `public_keys[i]`. This is considerably faster than
+    /// calling [Ed25519Signature::verify_arbitrary_msg][Ed25519Signature::verify_arbitrary_msg] on
+    /// each signature, and performs the same scalar malleability and small subgroup checks. If the
+    /// batch fails to verify, the caller has to verify the signatures individually to find the
+    /// invalid ones.
+    ///
+    /// WARNING: Batch verification checks a random linear combination of the (cofactorless)
+    /// verification equations. A signer can craft a signature whose R-component has a small-order
+    /// component, which `verify_arbitrary_msg` always rejects, but which the batch accepts with
+    /// small probability. Hence, the outcome is not deterministic for such signatures, and this
+    /// function must NOT be used where all nodes must agree on the result (e.g., to decide the
+    /// validity of transactions during execution).
+    pub fn verify_batch_arbitrary_msgs(
+        messages: &[&[u8]],
+        public_keys: &[&Ed25519PublicKey],
+        signatures: &[&Ed25519Signature],
+    ) -> Result<()> {
+        if messages.len() != public_keys.len() || messages.len() != signatures.len() {
+            return Err(anyhow!(
+                "Mismatched batch lengths: {} messages, {} public keys and {} signatures",
+                messages.len(),
+                public_keys.len(),
+                signatures.len()
+            ));
+        }
+
+        // Perform the same checks as ed25519::PublicKey::verify_strict, since the batch
+        // verification in ed25519-dalek does not reject small-order points.
+        for (public_key, signature) in public_keys.iter().zip(signatures) {
+            let signature_bytes = signature.to_bytes();
+            Ed25519Signature::check_s_malleability(&signature_bytes)?;
+            if !is_valid_and_not_small_order(&signature_bytes[..32]) {
+                return Err(anyhow!(
+                    "Signature R-component is invalid or has small order"
+                ));
+            }
+            if !is_valid_and_not_small_order(&public_key.to_bytes()) {
+                return Err(anyhow!("Public key is invalid or has small order"));
+            }
+        }
+
+        let public_keys: Vec<_> = public_keys.iter().map(|public_key| public_key.0).collect();
+        let signatures: Vec<_> = signatures.iter().map(|signature| signature.0).collect();
+        ed25519_dalek::verify_batch(messages, &signatures, &public_keys)
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    /// Serializes the messages of type `T` to bytes and calls
+    /// [Ed25519Signature::verify_batch_arbitrary_msgs][Ed25519Signature::verify_batch_arbitrary_msgs].
+    pub fn verify_batch<T: CryptoHash + Serialize>(
+        messages: &[&T],
+        public_keys: &[&Ed25519PublicKey],
+        signatures: &[&Ed25519Signature],
+    ) -> Result<()> {
+        let messages = messages
+            .iter()
+            .map(|message| signing_message(*message))
+            .collect::<Result<Vec<_>, _>>()?;
+        let messages_refs: Vec<&[u8]> = messages.iter().map(|m| m.as_slice()).collect();
+
+        Ed25519Signature::verify_batch_arbitrary_msgs(&messages_refs, public_keys, signatures)
+    }
+}
+
+/// Returns true iff the bytes are a valid compressed Edwards point that is not of small order
+fn is_valid_and_not_small_order(bytes: &[u8]) -> bool {
+    CompressedEdwardsY::from_slice(bytes)
+        .decompress()
+        .map_or(false, |point| !point.is_small_order())
 }
 
 //////////////////////
@@ -221,6 +221,40 @@ fn bls12381_aggsig_zero_messages_or_pks_does_not_verify() {
     assert!(aggsig.verify_aggregate(&msgs_refs, &pubkeys).is_err());
 }
 
+/// Tests that a batch of signatures on `n` different messages verifies correctly, and that the batch
+/// does NOT verify when two of its signatures are swapped (even though their aggregate is unchanged).
+#[test]
+fn bls12381_batch_should_verify() {
+    let mut rng = OsRng;
+    let num_signers = 100;
+
+    let messages = random_messages_for_signing(&mut rng, num_signers);
+    let key_pairs = bls12381_keygen(num_signers, &mut rng);
+
+    let signatures = zip(&messages, &key_pairs)
+        .map(|(msg, key)| key.private_key.sign(msg).unwrap())
+        .collect::<Vec<bls12381::Signature>>();
+    let pubkeys = key_pairs
+        .iter()
+        .map(|key| &key.public_key)
+        .collect::<Vec<&PublicKey>>();
+    let msgs_refs = messages.iter().collect::<Vec<&TestAptosCrypto>>();
+
+    // the batch should verify on the correct messages under the correct PKs
+    let mut sigs_refs = signatures.iter().collect::<Vec<&bls12381::Signature>>();
+    assert!(bls12381::Signature::verify_batch(&msgs_refs, &pubkeys, &sigs_refs).is_ok());
+
+    // an empty batch should verify
+    assert!(bls12381::Signature::verify_batch::<TestAptosCrypto>(&[], &[], &[]).is_ok());
+
+    // the batch should NOT verify with mismatched lengths
+    assert!(bls12381::Signature::verify_batch(&msgs_refs, &pubkeys[1..], &sigs_refs).is_err());
+
+    // the batch should NOT verify with swapped signatures
+    sigs_refs.swap(0, 1);
+    assert!(bls12381::Signature::verify_batch(&msgs_refs, &pubkeys, &sigs_refs).is_err());
+}
+
 /// Tests that a multisignature incorrectly aggregated from signature shares on different messages does
 /// NOT verify.
 #[test]
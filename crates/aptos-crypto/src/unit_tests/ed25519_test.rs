@@ -325,6 +325,25 @@ proptest! {
         prop_assert!(Ed25519Signature::batch_verify(&message, signatures).is_err());
     }
 
+    #[test]
+    fn test_verify_batch(
+        messages in proptest::array::uniform10(random_serializable_struct()),
+        keypairs in proptest::array::uniform10(uniform_keypair_strategy::<Ed25519PrivateKey, Ed25519PublicKey>())
+    ) {
+        let signatures: Vec<Ed25519Signature> = messages.iter().zip(keypairs.iter()).map(|(message, keypair)| {
+            keypair.private_key.sign(message).unwrap()
+        }).collect();
+        let messages_refs: Vec<_> = messages.iter().collect();
+        let public_keys: Vec<_> = keypairs.iter().map(|keypair| &keypair.public_key).collect();
+        let mut signatures_refs: Vec<_> = signatures.iter().collect();
+        prop_assert!(Ed25519Signature::verify_batch(&messages_refs, &public_keys, &signatures_refs).is_ok());
+        // Mismatched lengths are rejected
+        prop_assert!(Ed25519Signature::verify_batch(&messages_refs, &public_keys[1..], &signatures_refs).is_err());
+        // We swap the first two signatures, resulting in two incorrect signatures
+        signatures_refs.swap(0, 1);
+        prop_assert!(Ed25519Signature::verify_batch(&messages_refs, &public_keys, &signatures_refs).is_err());
+    }
+
     #[test]
     fn test_keys_custom_serialisation(
         keypair in uniform_keypair_strategy::<Ed25519PrivateKey, Ed25519PublicKey>()
@@ -496,6 +515,9 @@ proptest! {
         // calls ed25519-dalek's verify_strict
         let sig = Ed25519Signature::from_bytes_unchecked(sig_bytes.as_ref()).unwrap();
         prop_assert!(pk.verify_struct_signature(&m, &sig).is_err());
+
+        // We expect batch verification to fail as well, since it performs the same checks
+        prop_assert!(Ed25519Signature::verify_batch(&[&m], &[&pk], &[&sig]).is_err());
     }
 }
 
@@ -3,7 +3,7 @@
 
 use crate::{
     server::utils::CONTENT_TYPE_TEXT, CONFIGURATION_PATH, FORGE_METRICS_PATH, JSON_METRICS_PATH,
-    METRICS_PATH, PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    LOG_FILTERS_PATH, METRICS_PATH, PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
 };
 use hyper::{Body, StatusCode};
 
@@ -27,6 +27,7 @@ fn get_index_response() -> String {
     index_response.push(format!("\t- {}", CONFIGURATION_PATH));
     index_response.push(format!("\t- {}", FORGE_METRICS_PATH));
     index_response.push(format!("\t- {}", JSON_METRICS_PATH));
+    index_response.push(format!("\t- {}", LOG_FILTERS_PATH));
     index_response.push(format!("\t- {}", METRICS_PATH));
     index_response.push(format!("\t- {}", PEER_INFORMATION_PATH));
     index_response.push(format!("\t- {}", SYSTEM_INFORMATION_PATH));
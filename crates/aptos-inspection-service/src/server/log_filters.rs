@@ -0,0 +1,133 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_config::config::{AuthenticationConfig, NodeConfig};
+use aptos_logger::{global_aptos_data, info, Filter};
+use hyper::{Body, Method, StatusCode};
+use std::{borrow::Cow, collections::HashMap};
+
+// The messages to display when the log filters endpoint can't be used
+pub const LOG_FILTERS_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.log_filters_authentication_configs";
+pub const LOG_FILTERS_UNAUTHENTICATED_MESSAGE: &str =
+    "This endpoint requires authentication! Provide the passcode as a query parameter.";
+pub const LOGGER_NOT_INITIALIZED_MESSAGE: &str = "The logger has not been initialized!";
+
+// The query parameters of the log filters endpoint
+const DIRECTIVES_PARAM: &str = "directives";
+const PASSCODE_PARAM: &str = "passcode";
+const RESET_PARAM: &str = "reset";
+
+/// Handles a new log filters request. A GET request returns the current log
+/// filters, and a POST request changes the local log filter, e.g.,
+/// `/log_filters?directives=info,aptos_consensus=debug`, or removes the
+/// change, e.g., `/log_filters?reset=true`.
+pub fn handle_log_filters_request(
+    node_config: &NodeConfig,
+    method: &Method,
+    query: Option<&str>,
+) -> (StatusCode, Body, String) {
+    // Only handle the request if the endpoint is enabled
+    let authentication_configs = &node_config
+        .inspection_service
+        .log_filters_authentication_configs;
+    if authentication_configs.is_empty() {
+        return (
+            StatusCode::FORBIDDEN,
+            Body::from(LOG_FILTERS_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        );
+    }
+
+    // Verify the request is authenticated
+    let query_pairs: HashMap<_, _> =
+        url::form_urlencoded::parse(query.unwrap_or("").as_bytes()).collect();
+    if !is_authenticated(authentication_configs, &query_pairs) {
+        return (
+            StatusCode::NETWORK_AUTHENTICATION_REQUIRED,
+            Body::from(LOG_FILTERS_UNAUTHENTICATED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        );
+    }
+
+    // Fetch the logger
+    let logger = match global_aptos_data() {
+        Some(logger) => logger,
+        None => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Body::from(LOGGER_NOT_INITIALIZED_MESSAGE),
+                CONTENT_TYPE_TEXT.into(),
+            );
+        },
+    };
+
+    // Update the local log filter (if requested)
+    if *method == Method::POST {
+        if query_pairs
+            .get(RESET_PARAM)
+            .map_or(false, |reset| *reset == "true")
+        {
+            logger.set_local_filter_override(None);
+            info!("The local log filter override has been removed");
+        } else if let Some(directives) = query_pairs.get(DIRECTIVES_PARAM) {
+            let filter = match Filter::builder().try_parse(directives) {
+                Ok(builder) => builder.build(),
+                Err(_) => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        Body::from(format!("Invalid log filter directives: {}", directives)),
+                        CONTENT_TYPE_TEXT.into(),
+                    );
+                },
+            };
+            logger.set_local_filter_override(Some(filter));
+            info!(
+                "The local log filter has been overridden with: {}",
+                directives
+            );
+        } else {
+            return (
+                StatusCode::BAD_REQUEST,
+                Body::from(format!(
+                    "Either the {} or the {} query parameter is required!",
+                    DIRECTIVES_PARAM, RESET_PARAM
+                )),
+                CONTENT_TYPE_TEXT.into(),
+            );
+        }
+    }
+
+    // Return the current log filters
+    match serde_json::to_string_pretty(&logger.filter_directives()) {
+        Ok(encoded_filters) => (
+            StatusCode::OK,
+            Body::from(encoded_filters),
+            CONTENT_TYPE_JSON.into(),
+        ),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Body::from(format!("Failed to encode the log filters: {}", error)),
+            CONTENT_TYPE_TEXT.into(),
+        ),
+    }
+}
+
+/// Returns true iff the query contains a passcode matching one of the authentication configs
+fn is_authenticated(
+    authentication_configs: &[AuthenticationConfig],
+    query_pairs: &HashMap<Cow<str>, Cow<str>>,
+) -> bool {
+    let passcode = match query_pairs.get(PASSCODE_PARAM) {
+        Some(passcode) => passcode,
+        None => return false,
+    };
+    authentication_configs
+        .iter()
+        .any(|authentication_config| match authentication_config {
+            AuthenticationConfig::PasscodeSha256(passcode_sha256) => {
+                sha256::digest(passcode.to_string()) == *passcode_sha256
+            },
+        })
+}
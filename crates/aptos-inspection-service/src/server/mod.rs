@@ -20,6 +20,7 @@ use std::{
 mod configuration;
 mod index;
 mod json_encoder;
+mod log_filters;
 mod metrics;
 mod peer_information;
 mod system_information;
@@ -33,6 +34,7 @@ pub const CONFIGURATION_PATH: &str = "/configuration";
 pub const FORGE_METRICS_PATH: &str = "/forge_metrics";
 pub const INDEX_PATH: &str = "/";
 pub const JSON_METRICS_PATH: &str = "/json_metrics";
+pub const LOG_FILTERS_PATH: &str = "/log_filters";
 pub const METRICS_PATH: &str = "/metrics";
 pub const PEER_INFORMATION_PATH: &str = "/peer_information";
 pub const SYSTEM_INFORMATION_PATH: &str = "/system_information";
@@ -126,6 +128,11 @@ async fn serve_requests(
             // Exposes JSON encoded metrics
             metrics::handle_json_metrics_request()
         },
+        LOG_FILTERS_PATH => {
+            // /log_filters
+            // Exposes and updates the log filters (requires authentication)
+            log_filters::handle_log_filters_request(&node_config, req.method(), req.uri().query())
+        },
         METRICS_PATH => {
             // /metrics
             // Exposes text encoded metrics
@@ -164,6 +171,9 @@ async fn serve_requests(
     let response = match *req.method() {
         Method::HEAD => response_builder.body(Body::empty()), // Return only the headers
         Method::GET => response_builder.body(body),           // Include the response body
+        Method::POST if req.uri().path() == LOG_FILTERS_PATH => {
+            response_builder.body(body) // Only the log filters can be updated
+        },
         _ => {
             // Invalid method found
             Response::builder()
@@ -4,13 +4,16 @@
 use crate::{
     server::{
         configuration::CONFIGURATION_DISABLED_MESSAGE,
-        peer_information::PEER_INFO_DISABLED_MESSAGE, serve_requests,
-        system_information::SYS_INFO_DISABLED_MESSAGE, utils::get_all_metrics,
+        log_filters::{LOG_FILTERS_DISABLED_MESSAGE, LOG_FILTERS_UNAUTHENTICATED_MESSAGE},
+        peer_information::PEER_INFO_DISABLED_MESSAGE,
+        serve_requests,
+        system_information::SYS_INFO_DISABLED_MESSAGE,
+        utils::get_all_metrics,
     },
-    CONFIGURATION_PATH, FORGE_METRICS_PATH, INDEX_PATH, JSON_METRICS_PATH, METRICS_PATH,
-    PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    CONFIGURATION_PATH, FORGE_METRICS_PATH, INDEX_PATH, JSON_METRICS_PATH, LOG_FILTERS_PATH,
+    METRICS_PATH, PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
 };
-use aptos_config::config::{AptosDataClientConfig, BaseConfig, NodeConfig};
+use aptos_config::config::{AptosDataClientConfig, AuthenticationConfig, BaseConfig, NodeConfig};
 use aptos_data_client::client::AptosDataClient;
 use aptos_network::application::{interface::NetworkClient, storage::PeersAndMetadata};
 use aptos_storage_interface::DbReader;
@@ -109,6 +112,36 @@ async fn test_inspect_json_metrics() {
     assert!(response_body_string.contains(INT_COUNTER_NAME));
 }
 
+#[tokio::test]
+async fn test_inspect_log_filters() {
+    // Create a validator config
+    let mut node_config = NodeConfig::get_default_validator_config();
+
+    // Ping the log filters endpoint (which is disabled by default)
+    let mut response = send_get_request_to_path(&node_config, LOG_FILTERS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, LOG_FILTERS_DISABLED_MESSAGE);
+
+    // Enable the log filters endpoint and ping it with an invalid passcode
+    node_config
+        .inspection_service
+        .log_filters_authentication_configs =
+        vec![AuthenticationConfig::PasscodeSha256(sha256::digest("abc"))];
+    let log_filters_path = format!("{}?passcode=xyz", LOG_FILTERS_PATH);
+    let mut response = send_get_request_to_path(&node_config, &log_filters_path).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the request is not authenticated
+    assert_eq!(
+        response.status(),
+        StatusCode::NETWORK_AUTHENTICATION_REQUIRED
+    );
+    assert_eq!(response_body, LOG_FILTERS_UNAUTHENTICATED_MESSAGE);
+}
+
 #[tokio::test]
 async fn test_inspect_metrics() {
     // Create a validator config
@@ -15,11 +15,11 @@ use crate::{
     telemetry_log_writer::{TelemetryLog, TelemetryLogWriter},
     Event, Filter, Key, Level, LevelFilter, Metadata,
 };
-use aptos_infallible::RwLock;
+use aptos_infallible::{Mutex, RwLock};
 use backtrace::Backtrace;
 use chrono::{SecondsFormat, Utc};
 use futures::channel;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use serde::{ser::SerializeStruct, Serialize, Serializer};
 use std::{
     collections::BTreeMap,
@@ -407,6 +407,7 @@ impl AptosDataBuilder {
         FilterTuple {
             local_filter,
             telemetry_filter,
+            local_filter_override: None,
         }
     }
 
@@ -467,24 +468,52 @@ impl AptosDataBuilder {
         };
 
         crate::logger::set_global_logger(logger.clone(), tokio_console_port);
+        let _ = GLOBAL_APTOS_DATA.set(logger.clone());
         logger
     }
 }
 
+/// The logger set as the global logger by `AptosDataBuilder::build`, kept to change its filters
+/// at runtime
+static GLOBAL_APTOS_DATA: OnceCell<Arc<AptosData>> = OnceCell::new();
+
+/// Returns the global logger, if it was built by `AptosDataBuilder::build`
+pub fn global_aptos_data() -> Option<Arc<AptosData>> {
+    GLOBAL_APTOS_DATA.get().cloned()
+}
+
 /// A combination of `Filter`s to control where logs are written
 pub struct FilterTuple {
     /// The local printer `Filter` to control what is logged in text output
     local_filter: Filter,
     /// The logging `Filter` to control what is sent to telemetry service
     telemetry_filter: Filter,
+    /// A `Filter` replacing the local printer one, set at runtime
+    local_filter_override: Option<Filter>,
 }
 
 impl FilterTuple {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        self.local_filter.enabled(metadata) || self.telemetry_filter.enabled(metadata)
+        self.local_filter().enabled(metadata) || self.telemetry_filter.enabled(metadata)
+    }
+
+    fn local_filter(&self) -> &Filter {
+        self.local_filter_override
+            .as_ref()
+            .unwrap_or(&self.local_filter)
     }
 }
 
+/// The filters of a logger as directives strings, in the `RUST_LOG` format
+#[derive(Clone, Debug, Serialize)]
+pub struct FilterDirectives {
+    /// The effective local printer filter
+    pub local_filter: String,
+    /// Whether the local printer filter is overridden at runtime
+    pub local_filter_overridden: bool,
+    pub telemetry_filter: String,
+}
+
 pub struct AptosData {
     enable_backtrace: bool,
     sender: Option<sync::mpsc::SyncSender<LoggerServiceEvent>>,
@@ -520,8 +549,27 @@ impl AptosData {
         builder.build();
     }
 
-    pub fn set_filter(&self, filter_tuple: FilterTuple) {
-        *self.filter.write() = filter_tuple;
+    /// Replaces the filters, but keeps the local filter override (if any)
+    pub fn set_filter(&self, mut filter_tuple: FilterTuple) {
+        let mut filter = self.filter.write();
+        filter_tuple.local_filter_override = filter.local_filter_override.take();
+        *filter = filter_tuple;
+    }
+
+    /// Overrides the local printer filter at runtime, until the override is removed by passing
+    /// `None`. Unlike the filters set by `set_filter`, the override is not replaced when the
+    /// `LoggerFilterUpdater` rebuilds the filters.
+    pub fn set_local_filter_override(&self, filter: Option<Filter>) {
+        self.filter.write().local_filter_override = filter;
+    }
+
+    pub fn filter_directives(&self) -> FilterDirectives {
+        let filter = self.filter.read();
+        FilterDirectives {
+            local_filter: filter.local_filter().to_string(),
+            local_filter_overridden: filter.local_filter_override.is_some(),
+            telemetry_filter: filter.telemetry_filter.to_string(),
+        }
     }
 
     pub fn set_local_filter(&self, filter: Filter) {
@@ -610,7 +658,7 @@ impl LoggerService {
                             .facade
                             .filter
                             .read()
-                            .local_filter
+                            .local_filter()
                             .enabled(&entry.metadata)
                         {
                             let s = (self.facade.formatter)(&entry).expect("Unable to format");
@@ -771,19 +819,32 @@ fn json_format(entry: &LogEntry) -> Result<String, fmt::Error> {
 /// Periodically rebuilds the filter and replaces the current logger filter.
 /// This is useful for dynamically changing log levels at runtime via existing
 /// environment variables such as `RUST_LOG_TELEMETRY`.
+#[derive(Clone)]
 pub struct LoggerFilterUpdater {
     logger: Arc<AptosData>,
-    logger_builder: AptosDataBuilder,
+    logger_builder: Arc<Mutex<AptosDataBuilder>>,
 }
 
 impl LoggerFilterUpdater {
     pub fn new(logger: Arc<AptosData>, logger_builder: AptosDataBuilder) -> Self {
         Self {
             logger,
-            logger_builder,
+            logger_builder: Arc::new(Mutex::new(logger_builder)),
         }
     }
 
+    /// Updates the local and telemetry log levels, e.g. when the node config is
+    /// reloaded, and rebuilds the filter. The environment variables still take
+    /// precedence over the levels, and the levels are kept when the filter is
+    /// periodically rebuilt.
+    pub fn set_levels(&self, level: Level, telemetry_level: Level) {
+        self.logger_builder
+            .lock()
+            .level(level)
+            .telemetry_level(telemetry_level);
+        self.update_filter();
+    }
+
     pub async fn run(self) {
         let mut interval = time::interval(FILTER_REFRESH_INTERVAL);
         loop {
@@ -795,7 +856,7 @@ impl LoggerFilterUpdater {
 
     fn update_filter(&self) {
         // TODO: check for change to env var before rebuilding filter.
-        let filter = self.logger_builder.build_filter();
+        let filter = self.logger_builder.lock().build_filter();
         self.logger.set_filter(filter);
     }
 }
@@ -808,8 +869,8 @@ mod tests {
         debug, error, info,
         logger::Logger,
         telemetry_log_writer::TelemetryLog,
-        trace, warn, AptosDataBuilder, Event, Key, KeyValue, Level, LoggerFilterUpdater, Metadata,
-        Schema, Value, Visitor, Writer,
+        trace, warn, AptosDataBuilder, Event, Filter, Key, KeyValue, Level, LoggerFilterUpdater,
+        Metadata, Schema, Value, Visitor, Writer,
     };
     use chrono::{DateTime, Utc};
     use futures::StreamExt;
@@ -1167,6 +1228,41 @@ mod tests {
             )));
     }
 
+    #[test]
+    fn test_local_filter_override() {
+        let (logger_builder, logger) = new_async_logger();
+        let debug_metadata = &Metadata::new(Level::Debug, "target", "module_path", "source_path");
+        assert!(!logger.filter.read().local_filter().enabled(debug_metadata));
+
+        logger.set_local_filter_override(Some(Filter::builder().parse("debug").build()));
+        assert!(logger.filter.read().local_filter().enabled(debug_metadata));
+        assert!(logger.filter_directives().local_filter_overridden);
+
+        // The override survives the filters being rebuilt
+        let updater = LoggerFilterUpdater::new(logger.clone(), logger_builder);
+        updater.update_filter();
+        assert!(logger.filter.read().local_filter().enabled(debug_metadata));
+
+        logger.set_local_filter_override(None);
+        assert!(!logger.filter.read().local_filter().enabled(debug_metadata));
+        assert!(!logger.filter_directives().local_filter_overridden);
+    }
+
+    #[test]
+    fn test_set_levels() {
+        let (logger_builder, logger) = new_async_logger();
+        let debug_metadata = &Metadata::new(Level::Debug, "target", "module_path", "source_path");
+        assert!(!logger.filter.read().local_filter().enabled(debug_metadata));
+
+        let updater = LoggerFilterUpdater::new(logger.clone(), logger_builder);
+        updater.set_levels(Level::Debug, Level::Warn);
+        assert!(logger.filter.read().local_filter().enabled(debug_metadata));
+
+        // The levels are kept when the filters are rebuilt
+        updater.update_filter();
+        assert!(logger.filter.read().local_filter().enabled(debug_metadata));
+    }
+
     #[test]
     fn test_log_event_truncation() {
         let log_entry = LogEntry::new(
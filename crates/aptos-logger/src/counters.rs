@@ -41,6 +41,15 @@ pub static STRUCT_LOG_PARSE_ERROR_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the spans of sampled transactions dropped because the exporter fell behind
+pub static TXN_TRACE_SPANS_DROPPED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_txn_trace_spans_dropped",
+        "Count of the spans of sampled transactions dropped due to channel full"
+    )
+    .unwrap()
+});
+
 /// Counter for failed log ingest writes (see also: aptos-telemetry for sender metrics)
 pub static APTOS_LOG_INGEST_WRITER_FULL: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -5,8 +5,9 @@
 //! Filtering definitions for controlling what modules and levels are logged
 
 use crate::{Level, Metadata};
-use std::{env, str::FromStr};
+use std::{env, fmt, str::FromStr};
 
+#[derive(Debug)]
 pub struct FilterParseError;
 
 /// A definition of the most verbose `Level` allowed, or completely off.
@@ -41,6 +42,20 @@ impl FromStr for LevelFilter {
     }
 }
 
+impl fmt::Display for LevelFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let level = match self {
+            LevelFilter::Off => "off",
+            LevelFilter::Error => "error",
+            LevelFilter::Warn => "warn",
+            LevelFilter::Info => "info",
+            LevelFilter::Debug => "debug",
+            LevelFilter::Trace => "trace",
+        };
+        f.write_str(level)
+    }
+}
+
 impl From<Level> for LevelFilter {
     fn from(level: Level) -> Self {
         match level {
@@ -103,6 +118,16 @@ impl Builder {
         self
     }
 
+    /// Parses a directives string, failing on the first invalid directive instead of skipping it.
+    pub fn try_parse(&mut self, filters: &str) -> Result<&mut Self, FilterParseError> {
+        for directive in filters.split(',').map(str::trim) {
+            if !directive.is_empty() {
+                self.directives.push(directive.parse()?);
+            }
+        }
+        Ok(self)
+    }
+
     pub fn build(&mut self) -> Filter {
         if self.directives.is_empty() {
             // Add the default filter if none exist
@@ -146,6 +171,22 @@ impl Filter {
     }
 }
 
+/// Formats the filter as a directives string, which parses back into the same filter
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, directive) in self.directives.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(",")?;
+            }
+            match &directive.name {
+                Some(name) => write!(f, "{}={}", name, directive.level)?,
+                None => write!(f, "{}", directive.level)?,
+            }
+        }
+        Ok(())
+    }
+}
+
 /// A `Filter` directive for which logs to keep based on a module `name` based filter
 #[derive(Debug)]
 struct Directive {
@@ -217,6 +258,24 @@ mod tests {
         assert!(logger.enabled(&make_metadata(Level::Info, "crate2::mod2")));
     }
 
+    #[test]
+    fn try_parse_and_display() {
+        let filter = Builder::new()
+            .try_parse("info, crate1::mod1=warn,crate2=debug")
+            .unwrap()
+            .build();
+        assert_eq!(filter.to_string(), "info,crate2=debug,crate1::mod1=warn");
+        assert!(filter.enabled(&make_metadata(Level::Debug, "crate2::mod2")));
+
+        let reparsed = Builder::new()
+            .try_parse(&filter.to_string())
+            .unwrap()
+            .build();
+        assert_eq!(reparsed.to_string(), filter.to_string());
+
+        assert!(Builder::new().try_parse("info,crate1=loud").is_err());
+    }
+
     #[test]
     fn match_full_path() {
         let logger = Builder::new()
@@ -154,15 +154,17 @@ mod metadata;
 pub mod sample;
 pub mod telemetry_log_writer;
 pub mod tracing_adapter;
+pub mod txn_trace;
 
 mod security;
 
 pub use crate::aptos_logger::{
-    AptosData as Logger, AptosDataBuilder, LoggerFilterUpdater, Writer, CHANNEL_SIZE,
+    global_aptos_data, AptosData as Logger, AptosDataBuilder, FilterDirectives,
+    LoggerFilterUpdater, Writer, CHANNEL_SIZE,
 };
 pub use aptos_log_derive::Schema;
 pub use event::Event;
-pub use filter::{Filter, LevelFilter};
+pub use filter::{Filter, FilterParseError, LevelFilter};
 pub use kv::{Key, KeyValue, Schema, Value, Visitor};
 pub use logger::flush;
 pub use metadata::{Level, Metadata};
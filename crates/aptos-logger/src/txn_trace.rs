@@ -0,0 +1,128 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Traces of sampled transactions through the stages of the node, from mempool admission to
+//! commit. The spans are only recorded once a tracer is set up, and are handed to the exporter
+//! which drains the receiver returned by `init_txn_tracer`, e.g. the OTLP exporter of
+//! aptos-telemetry.
+
+use crate::counters::TXN_TRACE_SPANS_DROPPED;
+use once_cell::sync::OnceCell;
+use std::time::SystemTime;
+use tokio::sync::mpsc;
+
+static TXN_TRACER: OnceCell<TxnTracer> = OnceCell::new();
+
+/// A stage of the processing of a transaction by the node
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TxnTraceStage {
+    /// Validation and insertion into mempool
+    MempoolAdmission,
+    /// From the proposal of the block with the transaction until it's ordered
+    ConsensusOrdering,
+    /// Execution of the block with the transaction
+    Execution,
+    /// Commit of the block with the transaction to storage
+    Commit,
+}
+
+impl TxnTraceStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TxnTraceStage::MempoolAdmission => "mempool_admission",
+            TxnTraceStage::ConsensusOrdering => "consensus_ordering",
+            TxnTraceStage::Execution => "execution",
+            TxnTraceStage::Commit => "commit",
+        }
+    }
+}
+
+/// A stage of a sampled transaction
+#[derive(Clone, Debug)]
+pub struct TxnSpan {
+    pub txn_hash: [u8; 32],
+    pub stage: TxnTraceStage,
+    pub start_time: SystemTime,
+    pub end_time: SystemTime,
+}
+
+struct TxnTracer {
+    sample_rate: u64,
+    span_tx: mpsc::Sender<TxnSpan>,
+}
+
+/// Sets up the tracing of one in `sample_rate` transactions, and returns the receiver of their
+/// spans. Up to `max_queued_spans` spans are buffered, further ones are dropped. Returns `None`
+/// if a tracer is already set up.
+pub fn init_txn_tracer(
+    sample_rate: u64,
+    max_queued_spans: usize,
+) -> Option<mpsc::Receiver<TxnSpan>> {
+    let (span_tx, span_rx) = mpsc::channel(max_queued_spans);
+    TXN_TRACER
+        .set(TxnTracer {
+            sample_rate: sample_rate.max(1),
+            span_tx,
+        })
+        .ok()
+        .map(|_| span_rx)
+}
+
+/// Whether transactions are traced at all, so that callers can skip computing the hashes of
+/// their transactions otherwise.
+pub fn is_txn_tracing_enabled() -> bool {
+    TXN_TRACER.get().is_some()
+}
+
+/// Records the stage of the transaction if it's sampled. Sampling is deterministic on the hash,
+/// so that every stage of a sampled transaction is traced.
+pub fn record_txn_span(
+    txn_hash: [u8; 32],
+    stage: TxnTraceStage,
+    start_time: SystemTime,
+    end_time: SystemTime,
+) {
+    let Some(tracer) = TXN_TRACER.get() else {
+        return;
+    };
+    if !is_sampled(&txn_hash, tracer.sample_rate) {
+        return;
+    }
+    let span = TxnSpan {
+        txn_hash,
+        stage,
+        start_time,
+        end_time,
+    };
+    if tracer.span_tx.try_send(span).is_err() {
+        TXN_TRACE_SPANS_DROPPED.inc();
+    }
+}
+
+fn is_sampled(txn_hash: &[u8; 32], sample_rate: u64) -> bool {
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&txn_hash[..8]);
+    u64::from_le_bytes(prefix) % sample_rate == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_sampled;
+
+    #[test]
+    fn test_sampling_is_deterministic() {
+        let txn_hash = [7u8; 32];
+        assert!(is_sampled(&txn_hash, 1));
+        assert_eq!(is_sampled(&txn_hash, 10), is_sampled(&txn_hash, 10));
+
+        // About one in sample_rate hashes is sampled
+        let num_sampled = (0..1000u64)
+            .filter(|i| {
+                let mut txn_hash = [0u8; 32];
+                txn_hash[..8].copy_from_slice(&(i * 7919).to_le_bytes());
+                is_sampled(&txn_hash, 10)
+            })
+            .count();
+        assert_eq!(num_sampled, 100);
+    }
+}
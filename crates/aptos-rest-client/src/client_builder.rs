@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    endpoint_pool::{EndpointPool, HealthPolicy, RetryPolicy},
     get_version_path_with_base, Client, DEFAULT_VERSION_PATH_BASE, X_APTOS_SDK_HEADER_VALUE,
 };
 use anyhow::Result;
@@ -10,7 +11,7 @@ use reqwest::{
     header::{self, HeaderMap, HeaderName, HeaderValue},
     Client as ReqwestClient, ClientBuilder as ReqwestClientBuilder,
 };
-use std::{env, str::FromStr, time::Duration};
+use std::{env, str::FromStr, sync::Arc, time::Duration};
 use url::Url;
 
 pub enum AptosBaseUrl {
@@ -41,6 +42,9 @@ pub struct ClientBuilder {
     base_url: Url,
     timeout: Duration,
     headers: HeaderMap,
+    failover_urls: Vec<Url>,
+    retry_policy: Option<RetryPolicy>,
+    health_policy: HealthPolicy,
 }
 
 impl ClientBuilder {
@@ -57,6 +61,9 @@ impl ClientBuilder {
             version_path_base: DEFAULT_VERSION_PATH_BASE.to_string(),
             timeout: Duration::from_secs(10), // Default to 10 seconds
             headers,
+            failover_urls: vec![],
+            retry_policy: None,
+            health_policy: HealthPolicy::default(),
         };
 
         if let Ok(key) = env::var("X_API_KEY") {
@@ -70,6 +77,24 @@ impl ClientBuilder {
         self
     }
 
+    /// Other fullnodes to spread reads over, and to retry failed reads on
+    pub fn failover_urls(mut self, failover_urls: Vec<Url>) -> Self {
+        self.failover_urls = failover_urls;
+        self
+    }
+
+    /// Defaults to [`RetryPolicy::default`] with failover URLs, and to
+    /// [`RetryPolicy::no_retries`] without
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    pub fn health_policy(mut self, health_policy: HealthPolicy) -> Self {
+        self.health_policy = health_policy;
+        self
+    }
+
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
         self
@@ -98,6 +123,19 @@ impl ClientBuilder {
 
     pub fn build(self) -> Client {
         let version_path_base = get_version_path_with_base(self.base_url.clone());
+        let retry_policy = self.retry_policy.unwrap_or_else(|| {
+            if self.failover_urls.is_empty() {
+                RetryPolicy::no_retries()
+            } else {
+                RetryPolicy::default()
+            }
+        });
+        let endpoints = EndpointPool::new(
+            self.base_url.clone(),
+            self.failover_urls,
+            retry_policy,
+            self.health_policy,
+        );
 
         Client {
             inner: self
@@ -109,6 +147,7 @@ impl ClientBuilder {
                 .unwrap(),
             base_url: self.base_url,
             version_path_base,
+            endpoints: Arc::new(endpoints),
         }
     }
 }
@@ -0,0 +1,361 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Load balancing and failover of a [`Client`](crate::Client) across several fullnodes
+
+use crate::get_version_path_with_base;
+use aptos_infallible::Mutex;
+use aptos_logger::info;
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+use url::Url;
+
+/// Weight of the latest request in the moving averages of an endpoint's error rate and latency
+const EWMA_WEIGHT: f64 = 0.2;
+
+/// How failed idempotent GET requests are retried
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt
+    pub max_retries: u32,
+    /// Upper bound of the backoff before the first retry, which doubles with every retry
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retries, which is the default for a client with a single endpoint
+    pub fn no_retries() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// The backoff before the given retry, jittered so that clients don't retry in lockstep
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let max_backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff);
+        max_backoff.mul_f64(rand::random::<f64>())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+/// When an endpoint is considered unhealthy and taken out of rotation
+#[derive(Clone, Debug)]
+pub struct HealthPolicy {
+    /// Error rate above which the endpoint is quarantined
+    pub max_error_rate: f64,
+    /// Latency above which the endpoint is quarantined
+    pub max_latency: Duration,
+    /// Number of requests the endpoint has to serve before it can be quarantined, so that a
+    /// single failure doesn't take it out of rotation
+    pub min_requests: u32,
+    /// How long the endpoint is out of rotation, after which it's given another chance
+    pub quarantine_duration: Duration,
+}
+
+impl Default for HealthPolicy {
+    fn default() -> Self {
+        Self {
+            max_error_rate: 0.5,
+            max_latency: Duration::from_secs(5),
+            min_requests: 5,
+            quarantine_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct EndpointHealth {
+    error_rate: f64,
+    latency_secs: f64,
+    num_requests: u32,
+    quarantined_until: Option<Instant>,
+}
+
+impl EndpointHealth {
+    /// Lower is better. Endpoints that haven't served any requests yet score best, so that
+    /// they're tried.
+    fn score(&self) -> f64 {
+        self.latency_secs * (1.0 + 10.0 * self.error_rate)
+    }
+
+    fn is_quarantined(&mut self, now: Instant) -> bool {
+        match self.quarantined_until {
+            Some(until) if until > now => true,
+            Some(_) => {
+                *self = Self::default();
+                false
+            },
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    base_url: Url,
+    /// The base URL joined with the version path, which request paths are relative to
+    prefix: Url,
+    health: Mutex<EndpointHealth>,
+}
+
+/// The endpoints of a client, with the health of each. The first endpoint is the base URL of
+/// the client, which requests are built against and then rebased onto the chosen endpoint.
+#[derive(Debug)]
+pub(crate) struct EndpointPool {
+    endpoints: Vec<Endpoint>,
+    next: AtomicUsize,
+    retry_policy: RetryPolicy,
+    health_policy: HealthPolicy,
+}
+
+impl EndpointPool {
+    pub(crate) fn new(
+        base_url: Url,
+        failover_urls: Vec<Url>,
+        retry_policy: RetryPolicy,
+        health_policy: HealthPolicy,
+    ) -> Self {
+        let endpoints = std::iter::once(base_url)
+            .chain(failover_urls)
+            .map(|base_url| Endpoint {
+                prefix: base_url
+                    .join(&get_version_path_with_base(base_url.clone()))
+                    .unwrap_or_else(|_| base_url.clone()),
+                base_url,
+                health: Mutex::new(EndpointHealth::default()),
+            })
+            .collect();
+        Self {
+            endpoints,
+            next: AtomicUsize::new(0),
+            retry_policy,
+            health_policy,
+        }
+    }
+
+    pub(crate) fn single(base_url: Url) -> Self {
+        Self::new(
+            base_url,
+            vec![],
+            RetryPolicy::no_retries(),
+            HealthPolicy::default(),
+        )
+    }
+
+    pub(crate) fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Picks the endpoint for the next attempt, other than the one that just failed if there is
+    /// another. Requests are spread over the healthy endpoints, by picking the better scoring of
+    /// two in round robin order.
+    pub(crate) fn select(&self, failed: Option<usize>) -> usize {
+        let now = Instant::now();
+        let mut candidates: Vec<usize> = (0..self.endpoints.len())
+            .filter(|idx| Some(*idx) != failed)
+            .filter(|idx| !self.endpoints[*idx].health.lock().is_quarantined(now))
+            .collect();
+        if candidates.is_empty() {
+            // All the others are quarantined, so fall back to any of them rather than giving up
+            candidates = (0..self.endpoints.len())
+                .filter(|idx| Some(*idx) != failed)
+                .collect();
+        }
+        if candidates.is_empty() {
+            return failed.unwrap_or(0);
+        }
+
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let first = candidates[start % candidates.len()];
+        let second = candidates[(start + 1) % candidates.len()];
+        let first_score = self.endpoints[first].health.lock().score();
+        let second_score = self.endpoints[second].health.lock().score();
+        if second_score < first_score {
+            second
+        } else {
+            first
+        }
+    }
+
+    /// Rebases a URL built against the client's primary prefix onto the endpoint
+    pub(crate) fn rebase(&self, endpoint: usize, primary_prefix: &Url, url: &Url) -> Url {
+        if endpoint == 0 {
+            return url.clone();
+        }
+        url.as_str()
+            .strip_prefix(primary_prefix.as_str())
+            .and_then(|path| self.endpoints[endpoint].prefix.join(path).ok())
+            .unwrap_or_else(|| url.clone())
+    }
+
+    /// Records the outcome of a request, and quarantines the endpoint if it's unhealthy
+    pub(crate) fn record(&self, endpoint: usize, success: bool, latency: Duration) {
+        let mut health = self.endpoints[endpoint].health.lock();
+        let error = if success { 0.0 } else { 1.0 };
+        if health.num_requests == 0 {
+            health.error_rate = error;
+            health.latency_secs = latency.as_secs_f64();
+        } else {
+            health.error_rate += EWMA_WEIGHT * (error - health.error_rate);
+            health.latency_secs += EWMA_WEIGHT * (latency.as_secs_f64() - health.latency_secs);
+        }
+        health.num_requests = health.num_requests.saturating_add(1);
+
+        let is_unhealthy = health.error_rate > self.health_policy.max_error_rate
+            || health.latency_secs > self.health_policy.max_latency.as_secs_f64();
+        if self.endpoints.len() > 1
+            && health.num_requests >= self.health_policy.min_requests
+            && is_unhealthy
+            && health.quarantined_until.is_none()
+        {
+            info!(
+                "Quarantining endpoint {} for {:?} (error rate {:.2}, latency {:.3}s)",
+                self.endpoints[endpoint].base_url,
+                self.health_policy.quarantine_duration,
+                health.error_rate,
+                health.latency_secs
+            );
+            health.quarantined_until =
+                Some(Instant::now() + self.health_policy.quarantine_duration);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(num_endpoints: usize, health_policy: HealthPolicy) -> EndpointPool {
+        let urls: Vec<Url> = (0..num_endpoints)
+            .map(|i| Url::parse(&format!("http://node{}.test/", i)).unwrap())
+            .collect();
+        EndpointPool::new(
+            urls[0].clone(),
+            urls[1..].to_vec(),
+            RetryPolicy::default(),
+            health_policy,
+        )
+    }
+
+    #[test]
+    fn test_select_skips_failed_endpoint() {
+        let pool = pool(3, HealthPolicy::default());
+        for _ in 0..10 {
+            assert_ne!(pool.select(Some(1)), 1);
+        }
+
+        // With a single endpoint, the failed one is retried.
+        let pool = pool(1, HealthPolicy::default());
+        assert_eq!(pool.select(Some(0)), 0);
+        assert_eq!(pool.select(None), 0);
+    }
+
+    #[test]
+    fn test_select_prefers_better_score() {
+        let pool = pool(2, HealthPolicy::default());
+        pool.record(0, true, Duration::from_millis(500));
+        pool.record(1, true, Duration::from_millis(10));
+        for _ in 0..10 {
+            assert_eq!(pool.select(None), 1);
+        }
+    }
+
+    #[test]
+    fn test_record_quarantines_unhealthy_endpoint() {
+        let health_policy = HealthPolicy {
+            min_requests: 3,
+            quarantine_duration: Duration::from_secs(3600),
+            ..HealthPolicy::default()
+        };
+        let pool = pool(2, health_policy);
+        pool.record(1, false, Duration::from_millis(10));
+        pool.record(1, false, Duration::from_millis(10));
+        // Not quarantined before serving `min_requests` requests.
+        assert!(pool.endpoints[1].health.lock().quarantined_until.is_none());
+        pool.record(1, false, Duration::from_millis(10));
+        assert!(pool.endpoints[1].health.lock().quarantined_until.is_some());
+        for _ in 0..10 {
+            assert_eq!(pool.select(None), 0);
+        }
+        // If all the others are quarantined, the quarantined endpoint is still used.
+        assert_eq!(pool.select(Some(0)), 1);
+    }
+
+    #[test]
+    fn test_record_never_quarantines_single_endpoint() {
+        let pool = pool(1, HealthPolicy {
+            min_requests: 1,
+            ..HealthPolicy::default()
+        });
+        for _ in 0..10 {
+            pool.record(0, false, Duration::from_secs(10));
+        }
+        assert!(pool.endpoints[0].health.lock().quarantined_until.is_none());
+    }
+
+    #[test]
+    fn test_quarantine_expires() {
+        let pool = pool(2, HealthPolicy {
+            min_requests: 1,
+            quarantine_duration: Duration::ZERO,
+            ..HealthPolicy::default()
+        });
+        pool.record(1, false, Duration::from_millis(10));
+        assert!(pool.endpoints[1].health.lock().quarantined_until.is_some());
+        // The expired quarantine resets the health of the endpoint.
+        assert!(!pool.endpoints[1]
+            .health
+            .lock()
+            .is_quarantined(Instant::now()));
+        assert_eq!(pool.endpoints[1].health.lock().num_requests, 0);
+    }
+
+    #[test]
+    fn test_rebase() {
+        let pool = pool(2, HealthPolicy::default());
+        let primary_prefix = pool.endpoints[0].prefix.clone();
+        let url = Url::parse("http://node0.test/v1/accounts/0x1?limit=10").unwrap();
+        assert_eq!(pool.rebase(0, &primary_prefix, &url), url);
+        assert_eq!(
+            pool.rebase(1, &primary_prefix, &url).as_str(),
+            "http://node1.test/v1/accounts/0x1?limit=10"
+        );
+
+        // URLs not built against the primary prefix are left as they are.
+        let url = Url::parse("http://other.test/v1/accounts/0x1").unwrap();
+        assert_eq!(pool.rebase(1, &primary_prefix, &url), url);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let retry_policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(1000),
+        };
+        for retry in 0..10 {
+            let max_backoff =
+                (Duration::from_millis(100) * 2u32.pow(retry)).min(Duration::from_millis(1000));
+            for _ in 0..10 {
+                assert!(retry_policy.backoff(retry) <= max_backoff);
+            }
+        }
+        assert_eq!(RetryPolicy::no_retries().max_retries, 0);
+    }
+}
@@ -8,27 +8,35 @@ pub mod aptos;
 pub mod error;
 pub mod faucet;
 pub use faucet::FaucetClient;
+pub mod pagination;
+pub use pagination::{Page, PageCursor};
 pub mod response;
 pub use response::Response;
 pub mod client_builder;
+pub mod endpoint_pool;
 pub mod state;
 pub mod types;
 
-pub use crate::client_builder::{AptosBaseUrl, ClientBuilder};
 use crate::{
     aptos::{AptosVersion, Balance},
+    endpoint_pool::EndpointPool,
     error::RestError,
 };
+pub use crate::{
+    client_builder::{AptosBaseUrl, ClientBuilder},
+    endpoint_pool::{HealthPolicy, RetryPolicy},
+};
 use anyhow::{anyhow, Result};
 pub use aptos_api_types::{
-    self, IndexResponseBcs, MoveModuleBytecode, PendingTransaction, Transaction,
+    self, GasEstimatePercentiles, GasEstimation, IndexResponseBcs, MoveModuleBytecode,
+    PendingTransaction, Transaction,
 };
 use aptos_api_types::{
     deserialize_from_string,
     mime_types::{BCS, BCS_SIGNED_TRANSACTION, BCS_VIEW_FUNCTION, JSON},
-    AptosError, BcsBlock, Block, GasEstimation, HexEncodedBytes, IndexResponse, MoveModuleId,
-    TransactionData, TransactionOnChainData, TransactionsBatchSubmissionResult, UserTransaction,
-    VersionedEvent, ViewFunction, ViewRequest,
+    AptosError, BcsBlock, Block, HexEncodedBytes, IndexResponse, MoveModuleId, TransactionData,
+    TransactionOnChainData, TransactionsBatchSubmissionResult, UserTransaction, VersionedEvent,
+    ViewFunction, ViewRequest,
 };
 use aptos_crypto::HashValue;
 use aptos_logger::{debug, info, sample, sample::SampleRate};
@@ -39,15 +47,16 @@ use aptos_types::{
     state_store::state_key::StateKey,
     transaction::SignedTransaction,
 };
+use futures::{stream, Stream, StreamExt};
 use move_core_types::language_storage::StructTag;
 use reqwest::{
     header::{ACCEPT, CONTENT_TYPE},
-    Client as ReqwestClient, StatusCode,
+    Client as ReqwestClient, RequestBuilder, StatusCode,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{json, Value};
 pub use state::State;
-use std::{collections::BTreeMap, future::Future, time::Duration};
+use std::{collections::BTreeMap, future::Future, sync::Arc, time::Duration};
 use tokio::time::Instant;
 pub use types::{deserialize_from_prefixed_hex_string, Account, Resource};
 use url::Url;
@@ -60,6 +69,7 @@ static DEFAULT_INTERVAL_DURATION: Duration = Duration::from_millis(DEFAULT_INTER
 const DEFAULT_MAX_SERVER_LAG_WAIT_DURATION: Duration = Duration::from_secs(60);
 const RESOURCES_PER_CALL_PAGINATION: u64 = 9999;
 const MODULES_PER_CALL_PAGINATION: u64 = 1000;
+const DEFAULT_PAGE_SIZE: u16 = 100;
 const X_APTOS_SDK_HEADER_VALUE: &str = concat!("aptos-rust-sdk/", env!("CARGO_PKG_VERSION"));
 
 type AptosResult<T> = Result<T, RestError>;
@@ -69,6 +79,7 @@ pub struct Client {
     inner: ReqwestClient,
     base_url: Url,
     version_path_base: String,
+    endpoints: Arc<EndpointPool>,
 }
 
 impl Client {
@@ -80,6 +91,18 @@ impl Client {
         Self::builder(AptosBaseUrl::Custom(base_url)).build()
     }
 
+    /// Creates a client which spreads reads over several fullnodes, and retries failed reads on
+    /// the others with the default [`RetryPolicy`]. Transactions are only submitted to
+    /// `base_url`.
+    ///
+    /// The fullnodes may lag behind each other, so a read may not yet reflect a transaction that
+    /// was just committed.
+    pub fn new_with_failover(base_url: Url, failover_urls: Vec<Url>) -> Self {
+        Self::builder(AptosBaseUrl::Custom(base_url))
+            .failover_urls(failover_urls)
+            .build()
+    }
+
     pub fn path_prefix_string(&self) -> String {
         self.base_url
             .join(&self.version_path_base)
@@ -862,7 +885,7 @@ impl Client {
             request = request.query(&[("limit", limit)])
         }
 
-        let response = request.send().await?;
+        let response = self.send_get(request).await?;
 
         self.json(response).await
     }
@@ -899,7 +922,9 @@ impl Client {
         hash: HashValue,
     ) -> AptosResult<reqwest::Response> {
         let url = self.build_path(&format!("transactions/by_hash/{}", hash.to_hex_literal()))?;
-        let response = self.inner.get(url).header(ACCEPT, BCS).send().await?;
+        let response = self
+            .send_get(self.inner.get(url).header(ACCEPT, BCS))
+            .await?;
         Ok(response)
     }
 
@@ -908,7 +933,7 @@ impl Client {
         hash: HashValue,
     ) -> AptosResult<reqwest::Response> {
         let url = self.build_path(&format!("transactions/by_hash/{}", hash.to_hex_literal()))?;
-        Ok(self.inner.get(url).send().await?)
+        self.send_get(self.inner.get(url)).await
     }
 
     pub async fn get_transaction_by_version(
@@ -933,7 +958,7 @@ impl Client {
         version: u64,
     ) -> AptosResult<reqwest::Response> {
         let url = self.build_path(&format!("transactions/by_version/{}", version))?;
-        Ok(self.inner.get(url).send().await?)
+        self.send_get(self.inner.get(url)).await
     }
 
     pub async fn get_account_transactions(
@@ -953,11 +978,27 @@ impl Client {
             request = request.query(&[("limit", limit)])
         }
 
-        let response = request.send().await?;
+        let response = self.send_get(request).await?;
 
         self.json(response).await
     }
 
+    /// Streams the transactions sent by the account from sequence number `start` on, which
+    /// defaults to the first transaction. Pages of `page_size` transactions are fetched as the
+    /// stream is consumed.
+    pub fn get_account_transactions_stream(
+        &self,
+        address: AccountAddress,
+        start: Option<u64>,
+        page_size: Option<u16>,
+    ) -> impl Stream<Item = AptosResult<Transaction>> + '_ {
+        self.paginate(
+            format!("accounts/{}/transactions", address.to_hex()),
+            Some(PageCursor::SequenceNumber(start.unwrap_or(0))),
+            page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+        )
+    }
+
     pub async fn get_account_transactions_bcs(
         &self,
         address: AccountAddress,
@@ -1051,12 +1092,7 @@ impl Client {
             resource_type
         ))?;
 
-        let response = self
-            .inner
-            .get(url)
-            .send()
-            .await
-            .map_err(anyhow::Error::from)?;
+        let response = self.send_get(self.inner.get(url)).await?;
         self.json(response).await
     }
 
@@ -1136,7 +1172,7 @@ impl Client {
             version
         ))?;
 
-        let response = self.inner.get(url).send().await?;
+        let response = self.send_get(self.inner.get(url)).await?;
         self.json(response).await
     }
 
@@ -1228,10 +1264,32 @@ impl Client {
             request = request.query(&[("limit", limit)])
         }
 
-        let response = request.send().await?;
+        let response = self.send_get(request).await?;
         self.json(response).await
     }
 
+    /// Streams the events of the event handle from sequence number `start` on, which defaults
+    /// to the first event. Pages of `page_size` events are fetched as the stream is consumed.
+    pub fn get_events_stream<'a>(
+        &'a self,
+        address: AccountAddress,
+        struct_tag: &str,
+        field_name: &str,
+        start: Option<u64>,
+        page_size: Option<u16>,
+    ) -> impl Stream<Item = AptosResult<VersionedEvent>> + 'a {
+        self.paginate(
+            format!(
+                "accounts/{}/events/{}/{}",
+                address.to_hex_literal(),
+                struct_tag,
+                field_name
+            ),
+            Some(PageCursor::SequenceNumber(start.unwrap_or(0))),
+            page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+        )
+    }
+
     pub async fn get_account_events_bcs(
         &self,
         address: AccountAddress,
@@ -1419,7 +1477,7 @@ impl Client {
 
     pub async fn get_account(&self, address: AccountAddress) -> AptosResult<Response<Account>> {
         let url = self.build_path(&format!("accounts/{}", address.to_hex()))?;
-        let response = self.inner.get(url).send().await?;
+        let response = self.send_get(self.inner.get(url)).await?;
         self.json(response).await
     }
 
@@ -1434,7 +1492,7 @@ impl Client {
 
     pub async fn estimate_gas_price(&self) -> AptosResult<Response<GasEstimation>> {
         let url = self.build_path("estimate_gas_price")?;
-        let response = self.inner.get(url).send().await?;
+        let response = self.send_get(self.inner.get(url)).await?;
         self.json(response).await
     }
 
@@ -1495,12 +1553,55 @@ impl Client {
         }
     }
 
+    /// Sends an idempotent GET request to the healthiest endpoint, and retries it on the others
+    /// with a jittered backoff if it fails with a connection error or a retriable status.
+    async fn send_get(&self, request: RequestBuilder) -> AptosResult<reqwest::Response> {
+        let request = request.build()?;
+        let primary_prefix = self.base_url.join(&self.version_path_base)?;
+        let retry_policy = self.endpoints.retry_policy();
+
+        let mut failed = None;
+        let mut retry = 0;
+        loop {
+            let endpoint = self.endpoints.select(failed);
+            let mut attempt = request
+                .try_clone()
+                .ok_or_else(|| anyhow!("Failed to clone GET request"))?;
+            *attempt.url_mut() = self
+                .endpoints
+                .rebase(endpoint, &primary_prefix, request.url());
+
+            let start = std::time::Instant::now();
+            let result = self.inner.execute(attempt).await;
+            let is_retriable = match &result {
+                Ok(response) => retriable(response.status(), None),
+                Err(err) => err.is_connect() || err.is_timeout(),
+            };
+            self.endpoints
+                .record(endpoint, !is_retriable, start.elapsed());
+
+            if !is_retriable || retry >= retry_policy.max_retries {
+                return Ok(result?);
+            }
+            debug!(
+                "Retrying GET {} after attempt {} failed",
+                request.url(),
+                retry + 1
+            );
+            tokio::time::sleep(retry_policy.backoff(retry)).await;
+            retry += 1;
+            failed = Some(endpoint);
+        }
+    }
+
     async fn get<T: DeserializeOwned>(&self, url: Url) -> AptosResult<Response<T>> {
-        self.json(self.inner.get(url).send().await?).await
+        self.json(self.send_get(self.inner.get(url)).await?).await
     }
 
     async fn get_bcs(&self, url: Url) -> AptosResult<Response<bytes::Bytes>> {
-        let response = self.inner.get(url).header(ACCEPT, BCS).send().await?;
+        let response = self
+            .send_get(self.inner.get(url).header(ACCEPT, BCS))
+            .await?;
         self.check_and_parse_bcs_response(response).await
     }
 
@@ -1534,7 +1635,7 @@ impl Client {
             request = request.query(&[("limit", limit)])
         }
 
-        let response = request.send().await?;
+        let response = self.send_get(request).await?;
         self.check_and_parse_bcs_response(response).await
     }
 
@@ -1617,6 +1718,58 @@ impl Client {
         self.build_path(&path)
     }
 
+    /// Fetches a page of a paginated endpoint starting at `cursor`, or at the endpoint's
+    /// default start if there's none, along with the cursor of the next page.
+    pub async fn get_page<T: DeserializeOwned>(
+        &self,
+        base_path: &str,
+        cursor: Option<PageCursor>,
+        limit: u16,
+    ) -> AptosResult<Page<T>> {
+        let mut request = self
+            .inner
+            .get(self.build_path(base_path)?)
+            .query(&[("limit", limit)]);
+        if let Some(cursor) = &cursor {
+            request = request.query(&[("start", cursor.to_string())]);
+        }
+
+        let response: Response<Vec<T>> = self.json(self.send_get(request).await?).await?;
+        let (items, state) = response.into_parts();
+        let next = PageCursor::after(cursor.as_ref(), items.len(), &state);
+        Ok(Page { items, state, next })
+    }
+
+    /// Streams the items of a paginated endpoint starting at `cursor`, fetching the pages as
+    /// the stream is consumed. The stream ends after the last page, or after the first error.
+    pub fn paginate<'a, T: DeserializeOwned + 'a>(
+        &'a self,
+        base_path: String,
+        cursor: Option<PageCursor>,
+        page_size: u16,
+    ) -> impl Stream<Item = AptosResult<T>> + 'a {
+        // The state is `None` once the last page has been fetched
+        stream::unfold(Some(cursor), move |cursor| {
+            let base_path = base_path.clone();
+            async move {
+                let cursor = cursor?;
+                match self.get_page(&base_path, cursor, page_size).await {
+                    Ok(page) => {
+                        let next = page.next.map(Some);
+                        Some((
+                            stream::iter(page.items)
+                                .map(Ok::<T, RestError>)
+                                .left_stream(),
+                            next,
+                        ))
+                    },
+                    Err(err) => Some((stream::once(async { Err(err) }).right_stream(), None)),
+                }
+            }
+        })
+        .flatten()
+    }
+
     /// This function calls an endpoint that has pagination support and paginates
     /// using the cursor the API returns. It keeps paginating until the API doesn't
     /// return a cursor anymore. Since the functions calling this function are
@@ -1639,7 +1792,7 @@ impl Client {
                 ledger_version,
                 cursor,
             )?;
-            let raw_response = self.inner.get(url).send().await?;
+            let raw_response = self.send_get(self.inner.get(url)).await?;
             let response: Response<Vec<T>> = self.json(raw_response).await?;
             cursor = response.state().cursor.clone();
             if cursor.is_none() {
@@ -1725,6 +1878,7 @@ impl From<(ReqwestClient, Url)> for Client {
     fn from((inner, base_url): (ReqwestClient, Url)) -> Self {
         Client {
             inner,
+            endpoints: Arc::new(EndpointPool::single(base_url.clone())),
             base_url,
             version_path_base: DEFAULT_VERSION_PATH_BASE.to_string(),
         }
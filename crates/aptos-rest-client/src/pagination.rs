@@ -0,0 +1,99 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::state::State;
+use std::fmt;
+
+/// Where a page of a paginated endpoint starts
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PageCursor {
+    /// For endpoints paginated by sequence number, e.g. account transactions and events
+    SequenceNumber(u64),
+    /// The opaque cursor returned in the `X-Aptos-Cursor` header, e.g. by account resources
+    Opaque(String),
+}
+
+impl PageCursor {
+    /// The cursor of the page after the one fetched from `cursor`, or `None` if that was the
+    /// last one.  The API may cap the limit of an endpoint paginated by sequence number, so
+    /// only an empty page is known to be the last one.
+    pub(crate) fn after(
+        cursor: Option<&PageCursor>,
+        num_items: usize,
+        state: &State,
+    ) -> Option<PageCursor> {
+        if let Some(cursor) = &state.cursor {
+            return Some(PageCursor::Opaque(cursor.clone()));
+        }
+        match cursor {
+            Some(PageCursor::SequenceNumber(start)) if num_items > 0 => {
+                Some(PageCursor::SequenceNumber(start + num_items as u64))
+            },
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PageCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PageCursor::SequenceNumber(start) => write!(f, "{}", start),
+            PageCursor::Opaque(cursor) => write!(f, "{}", cursor),
+        }
+    }
+}
+
+/// A page of a paginated endpoint
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub state: State,
+    /// Where the next page starts, or `None` if this is the last page
+    pub next: Option<PageCursor>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(cursor: Option<&str>) -> State {
+        State {
+            chain_id: 4,
+            epoch: 1,
+            version: 100,
+            timestamp_usecs: 0,
+            oldest_ledger_version: 0,
+            oldest_block_height: 0,
+            block_height: 10,
+            cursor: cursor.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_after_sequence_number() {
+        let cursor = PageCursor::SequenceNumber(5);
+        assert_eq!(
+            PageCursor::after(Some(&cursor), 3, &state(None)),
+            Some(PageCursor::SequenceNumber(8))
+        );
+        // Only an empty page is the last one.
+        assert_eq!(PageCursor::after(Some(&cursor), 0, &state(None)), None);
+        assert_eq!(PageCursor::after(None, 3, &state(None)), None);
+    }
+
+    #[test]
+    fn test_after_opaque() {
+        // The cursor returned by the API takes precedence.
+        let cursor = PageCursor::SequenceNumber(5);
+        assert_eq!(
+            PageCursor::after(Some(&cursor), 3, &state(Some("0x1234"))),
+            Some(PageCursor::Opaque("0x1234".to_string()))
+        );
+        let cursor = PageCursor::Opaque("0x1234".to_string());
+        assert_eq!(
+            PageCursor::after(Some(&cursor), 3, &state(Some("0x5678"))),
+            Some(PageCursor::Opaque("0x5678".to_string()))
+        );
+        assert_eq!(PageCursor::after(Some(&cursor), 3, &state(None)), None);
+    }
+}
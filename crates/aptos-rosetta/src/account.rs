@@ -17,9 +17,10 @@ use crate::{
 };
 use aptos_logger::{debug, trace, warn};
 use aptos_types::{
-    account_address::AccountAddress,
-    account_config::{AccountResource, CoinStoreResource},
+    account_address::{create_derived_object_address, AccountAddress},
+    account_config::{fungible_store::FungibleStoreResource, AccountResource, CoinStoreResource},
 };
+use move_core_types::move_resource::MoveStructType;
 use std::{collections::HashSet, str::FromStr};
 use warp::Filter;
 
@@ -71,6 +72,7 @@ async fn account_balance(
         request.account_identifier,
         balance_version,
         request.currencies,
+        &server_context.fa_currencies(),
     )
     .await?;
 
@@ -92,6 +94,7 @@ async fn get_balances(
     account: AccountIdentifier,
     version: u64,
     maybe_filter_currencies: Option<Vec<Currency>>,
+    fa_currencies: &[(AccountAddress, Currency)],
 ) -> ApiResult<(u64, Option<Vec<AccountAddress>>, Vec<Amount>, u64)> {
     let owner_address = account.account_address()?;
     let pool_address = account.pool_address()?;
@@ -229,6 +232,36 @@ async fn get_balances(
             }
         }
 
+        // Add the balances of the primary fungible stores, the native coin's is added to its
+        // coin balance
+        if account.is_base_account() {
+            for (metadata_address, currency) in fa_currencies {
+                let store_address = create_derived_object_address(owner_address, *metadata_address);
+                if let Ok(response) = rest_client
+                    .get_account_resource_at_version_bcs::<FungibleStoreResource>(
+                        store_address,
+                        &FungibleStoreResource::struct_tag().to_string(),
+                        version,
+                    )
+                    .await
+                {
+                    let balance = response.into_inner().balance();
+                    if let Some(amount) = balances
+                        .iter_mut()
+                        .find(|amount| &amount.currency == currency)
+                    {
+                        amount.value = (u64::from_str(&amount.value).unwrap_or_default() + balance)
+                            .to_string();
+                    } else {
+                        balances.push(Amount {
+                            value: balance.to_string(),
+                            currency: currency.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
         let sequence_number = if let Some(sequence_number) = maybe_sequence_number {
             sequence_number
         } else {
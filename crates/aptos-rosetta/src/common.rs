@@ -5,7 +5,8 @@ use crate::{
     error::{ApiError, ApiResult},
     types::{
         Currency, CurrencyMetadata, MetadataRequest, NetworkIdentifier, PartialBlockIdentifier,
-        APTOS_COIN_MODULE, APTOS_COIN_RESOURCE,
+        APTOS_COIN_MODULE, APTOS_COIN_RESOURCE, FUNGIBLE_ASSET_METADATA_RESOURCE,
+        FUNGIBLE_ASSET_MODULE,
     },
     RosettaContext,
 };
@@ -144,7 +145,8 @@ pub fn native_coin() -> Currency {
         symbol: DEFAULT_COIN.to_string(),
         decimals: DEFAULT_DECIMALS,
         metadata: Some(CurrencyMetadata {
-            move_type: native_coin_tag().to_string(),
+            move_type: Some(native_coin_tag().to_string()),
+            fa_address: None,
         }),
     }
 }
@@ -158,6 +160,30 @@ pub fn native_coin_tag() -> TypeTag {
     }))
 }
 
+/// The address of the fungible asset metadata of the native coin
+pub fn native_coin_fa_address() -> AccountAddress {
+    AccountAddress::TEN
+}
+
+/// The type of fungible asset metadata objects, e.g., for `primary_fungible_store::transfer`
+pub fn fungible_asset_metadata_tag() -> TypeTag {
+    TypeTag::Struct(Box::new(StructTag {
+        address: AccountAddress::ONE,
+        module: ident_str!(FUNGIBLE_ASSET_MODULE).into(),
+        name: ident_str!(FUNGIBLE_ASSET_METADATA_RESOURCE).into(),
+        type_args: vec![],
+    }))
+}
+
+/// Retrieves the fungible asset metadata address of a currency, if it's a fungible asset
+pub fn fa_metadata_address(currency: &Currency) -> Option<AccountAddress> {
+    currency
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.fa_address.as_ref())
+        .and_then(|fa_address| AccountAddress::from_str(fa_address).ok())
+}
+
 pub fn is_native_coin(currency: &Currency) -> ApiResult<()> {
     if currency == &native_coin() {
         Ok(())
@@ -294,8 +320,14 @@ pub fn parse_currency(address: AccountAddress, module: &str, name: &str) -> ApiR
 
 #[cfg(test)]
 mod test {
-    use crate::common::BlockHash;
-    use aptos_types::chain_id::{ChainId, NamedChain};
+    use crate::{
+        common::{fa_metadata_address, native_coin, BlockHash},
+        types::{Currency, CurrencyMetadata},
+    };
+    use aptos_types::{
+        account_address::AccountAddress,
+        chain_id::{ChainId, NamedChain},
+    };
     use std::str::FromStr;
 
     #[test]
@@ -339,4 +371,30 @@ mod test {
             BlockHash::from_str(str).expect_err("Invalid block hash");
         }
     }
+
+    #[test]
+    pub fn fa_currency_check() {
+        // The native coin is a coin, and its metadata doesn't change
+        assert_eq!(fa_metadata_address(&native_coin()), None);
+        assert_eq!(
+            serde_json::to_string(&native_coin()).unwrap(),
+            r#"{"symbol":"APT","decimals":8,"metadata":{"move_type":"0x1::aptos_coin::AptosCoin"}}"#
+        );
+
+        let currency: Currency = serde_json::from_str(
+            r#"{"symbol":"USDC","decimals":6,"metadata":{"fa_address":"0xbae"}}"#,
+        )
+        .expect("Fungible asset currency should parse");
+        assert_eq!(
+            currency.metadata,
+            Some(CurrencyMetadata {
+                move_type: None,
+                fa_address: Some("0xbae".to_string()),
+            })
+        );
+        assert_eq!(
+            fa_metadata_address(&currency),
+            Some(AccountAddress::from_hex_literal("0xbae").unwrap())
+        );
+    }
 }
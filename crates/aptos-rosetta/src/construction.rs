@@ -534,6 +534,9 @@ async fn construction_parse(
                 (AccountAddress::ONE, APTOS_ACCOUNT_MODULE, TRANSFER_FUNCTION) => {
                     parse_account_transfer_operation(sender, &type_args, &args)?
                 },
+                (AccountAddress::ONE, PRIMARY_FUNGIBLE_STORE_MODULE, TRANSFER_FUNCTION) => {
+                    parse_primary_fa_transfer_operation(&server_context, sender, &type_args, &args)?
+                },
                 (AccountAddress::ONE, APTOS_ACCOUNT_MODULE, CREATE_ACCOUNT_FUNCTION) => {
                     parse_create_account_operation(sender, &type_args, &args)?
                 },
@@ -741,6 +744,70 @@ fn parse_account_transfer_operation(
     Ok(operations)
 }
 
+fn parse_primary_fa_transfer_operation(
+    server_context: &RosettaContext,
+    sender: AccountAddress,
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    // There is only the metadata object type for primary fungible store transfers
+    if type_args.len() != 1 {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Primary fungible store transfer should have one type argument: {:?}",
+            type_args
+        ))));
+    }
+    let mut operations = Vec::new();
+
+    // Retrieve the args for the operations
+
+    let metadata_address: AccountAddress = if let Some(metadata_address) = args.first() {
+        bcs::from_bytes(metadata_address)?
+    } else {
+        return Err(ApiError::TransactionParseError(Some(
+            "No metadata in primary fungible store transfer".to_string(),
+        )));
+    };
+    let currency = if let Some(currency) = server_context.find_fa_currency(metadata_address) {
+        currency
+    } else {
+        return Err(ApiError::TransactionParseError(Some(format!(
+            "Invalid fungible asset for transfer {}",
+            metadata_address
+        ))));
+    };
+    let receiver: AccountAddress = if let Some(receiver) = args.get(1) {
+        bcs::from_bytes(receiver)?
+    } else {
+        return Err(ApiError::TransactionParseError(Some(
+            "No receiver in primary fungible store transfer".to_string(),
+        )));
+    };
+    let amount: u64 = if let Some(amount) = args.get(2) {
+        bcs::from_bytes(amount)?
+    } else {
+        return Err(ApiError::TransactionParseError(Some(
+            "No amount in primary fungible store transfer".to_string(),
+        )));
+    };
+
+    operations.push(Operation::withdraw(
+        0,
+        None,
+        AccountIdentifier::base_account(sender),
+        currency.clone(),
+        amount,
+    ));
+    operations.push(Operation::deposit(
+        1,
+        None,
+        AccountIdentifier::base_account(receiver),
+        currency,
+        amount,
+    ));
+    Ok(operations)
+}
+
 pub fn parse_function_arg<T: DeserializeOwned>(
     name: &str,
     args: &[Vec<u8>],
@@ -1010,7 +1077,7 @@ async fn construction_payloads(
     check_network(request.network_identifier, &server_context)?;
 
     // Retrieve the real operation we're doing
-    let mut operation = InternalOperation::extract(&request.operations)?;
+    let mut operation = InternalOperation::extract(&server_context, &request.operations)?;
     let metadata = if let Some(ref metadata) = request.metadata {
         metadata
     } else {
@@ -1256,7 +1323,7 @@ async fn construction_preprocess(
     debug!("/construction/preprocess {:?}", request);
     check_network(request.network_identifier, &server_context)?;
 
-    let internal_operation = InternalOperation::extract(&request.operations)?;
+    let internal_operation = InternalOperation::extract(&server_context, &request.operations)?;
     let required_public_keys = vec![AccountIdentifier::base_account(internal_operation.sender())];
 
     if let Some(max_gas) = request
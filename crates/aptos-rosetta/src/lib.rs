@@ -7,15 +7,21 @@
 
 use crate::{
     block::BlockRetriever,
-    common::{handle_request, with_context},
+    common::{
+        fa_metadata_address, handle_request, native_coin, native_coin_fa_address, with_context,
+    },
     error::{ApiError, ApiResult},
-    types::Store,
+    types::{Currency, Store},
 };
 use aptos_config::config::ApiConfig;
 use aptos_logger::{debug, warn};
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use aptos_warp_webserver::{logger, Error, WebServer};
-use std::{collections::BTreeMap, convert::Infallible, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::Infallible,
+    sync::Arc,
+};
 use tokio::task::JoinHandle;
 use warp::{
     http::{HeaderValue, Method, StatusCode},
@@ -46,6 +52,8 @@ pub struct RosettaContext {
     pub block_cache: Option<Arc<BlockRetriever>>,
     pub owner_addresses: Vec<AccountAddress>,
     pub pool_address_to_owner: BTreeMap<AccountAddress, AccountAddress>,
+    /// Supported currencies, the native coin is always supported
+    pub currencies: HashSet<Currency>,
 }
 
 impl RosettaContext {
@@ -54,7 +62,10 @@ impl RosettaContext {
         chain_id: ChainId,
         block_cache: Option<Arc<BlockRetriever>>,
         owner_addresses: Vec<AccountAddress>,
+        mut currencies: HashSet<Currency>,
     ) -> Self {
+        currencies.insert(native_coin());
+
         let mut pool_address_to_owner = BTreeMap::new();
         if let Some(ref rest_client) = rest_client {
             // We have to now fill in all of the mappings of owner to pool address
@@ -87,6 +98,7 @@ impl RosettaContext {
             block_cache,
             owner_addresses,
             pool_address_to_owner,
+            currencies,
         }
     }
 
@@ -105,6 +117,40 @@ impl RosettaContext {
             Err(ApiError::NodeIsOffline)
         }
     }
+
+    /// Checks that the currency is supported
+    fn check_currency(&self, currency: &Currency) -> ApiResult<()> {
+        if self.currencies.contains(currency) {
+            Ok(())
+        } else {
+            Err(ApiError::UnsupportedCurrency(Some(currency.symbol.clone())))
+        }
+    }
+
+    /// Finds the supported currency of a fungible asset by its metadata address
+    ///
+    /// The native coin's fungible asset always maps to the native coin
+    fn find_fa_currency(&self, metadata_address: AccountAddress) -> Option<Currency> {
+        if metadata_address == native_coin_fa_address() {
+            return Some(native_coin());
+        }
+
+        self.currencies
+            .iter()
+            .find(|currency| fa_metadata_address(currency) == Some(metadata_address))
+            .cloned()
+    }
+
+    /// All supported fungible assets (including the native coin's) by metadata address
+    fn fa_currencies(&self) -> Vec<(AccountAddress, Currency)> {
+        let mut fa_currencies = vec![(native_coin_fa_address(), native_coin())];
+        fa_currencies.extend(self.currencies.iter().filter_map(|currency| {
+            fa_metadata_address(currency)
+                .filter(|metadata_address| *metadata_address != native_coin_fa_address())
+                .map(|metadata_address| (metadata_address, currency.clone()))
+        }));
+        fa_currencies
+    }
 }
 
 /// Creates HTTP server (warp-based) for Rosetta
@@ -113,6 +159,7 @@ pub fn bootstrap(
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
     owner_addresses: Vec<AccountAddress>,
+    currencies: HashSet<Currency>,
 ) -> anyhow::Result<tokio::runtime::Runtime> {
     let runtime = aptos_runtimes::spawn_named_runtime("rosetta".into(), None);
 
@@ -123,6 +170,7 @@ pub fn bootstrap(
         api_config,
         rest_client,
         owner_addresses,
+        currencies,
     ));
     Ok(runtime)
 }
@@ -133,6 +181,7 @@ pub async fn bootstrap_async(
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
     owner_addresses: Vec<AccountAddress>,
+    currencies: HashSet<Currency>,
 ) -> anyhow::Result<JoinHandle<()>> {
     debug!("Starting up Rosetta server with {:?}", api_config);
 
@@ -160,8 +209,14 @@ pub async fn bootstrap_async(
             ))
         });
 
-        let context =
-            RosettaContext::new(rest_client.clone(), chain_id, block_cache, owner_addresses).await;
+        let context = RosettaContext::new(
+            rest_client.clone(),
+            chain_id,
+            block_cache,
+            owner_addresses,
+            currencies,
+        )
+        .await;
         api.serve(routes(context)).await;
     });
     Ok(handle)
@@ -6,11 +6,12 @@
 use aptos_config::config::{ApiConfig, DEFAULT_MAX_PAGE_SIZE};
 use aptos_logger::prelude::*;
 use aptos_node::AptosNodeArgs;
-use aptos_rosetta::bootstrap;
+use aptos_rosetta::{bootstrap, types::Currency};
 use aptos_sdk::move_types::account_address::AccountAddress;
 use aptos_types::chain_id::ChainId;
 use clap::Parser;
 use std::{
+    collections::HashSet,
     fs::read_to_string,
     net::SocketAddr,
     path::PathBuf,
@@ -90,6 +91,7 @@ async fn main() {
         args.api_config(),
         args.rest_client(),
         args.owner_addresses(),
+        args.currencies(),
     )
     .expect("aptos-rosetta: Should bootstrap rosetta server");
 
@@ -114,6 +116,9 @@ trait ServerArgs {
 
     /// Retrieve owner addresses
     fn owner_addresses(&self) -> Vec<AccountAddress>;
+
+    /// Retrieve the supported currencies, in addition to the native coin
+    fn currencies(&self) -> HashSet<Currency>;
 }
 
 /// Aptos Rosetta API Server
@@ -162,6 +167,14 @@ impl ServerArgs for CommandArgs {
             CommandArgs::Online(args) => args.owner_addresses(),
         }
     }
+
+    fn currencies(&self) -> HashSet<Currency> {
+        match self {
+            CommandArgs::OnlineRemote(args) => args.currencies(),
+            CommandArgs::Offline(args) => args.currencies(),
+            CommandArgs::Online(args) => args.currencies(),
+        }
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -186,6 +199,11 @@ pub struct OfflineArgs {
     /// This can be configured to change performance characteristics
     #[clap(long, default_value_t = DEFAULT_MAX_PAGE_SIZE)]
     transactions_page_size: u16,
+    /// Supported currencies file as a YAML file with a list of currencies
+    ///
+    /// Fungible assets are identified by the `fa_address` in the currency metadata
+    #[clap(long, value_parser)]
+    currency_config_file: Option<PathBuf>,
 }
 
 impl ServerArgs for OfflineArgs {
@@ -212,6 +230,17 @@ impl ServerArgs for OfflineArgs {
     fn owner_addresses(&self) -> Vec<AccountAddress> {
         vec![]
     }
+
+    fn currencies(&self) -> HashSet<Currency> {
+        if let Some(ref path) = self.currency_config_file {
+            serde_yaml::from_str(
+                &read_to_string(path.as_path()).expect("Failed to read currency config file"),
+            )
+            .expect("Currency config file is in an invalid format")
+        } else {
+            HashSet::new()
+        }
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -249,6 +278,10 @@ impl ServerArgs for OnlineRemoteArgs {
             vec![]
         }
     }
+
+    fn currencies(&self) -> HashSet<Currency> {
+        self.offline_args.currencies()
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -277,6 +310,10 @@ impl ServerArgs for OnlineLocalArgs {
     fn owner_addresses(&self) -> Vec<AccountAddress> {
         self.online_args.owner_addresses()
     }
+
+    fn currencies(&self) -> HashSet<Currency> {
+        self.online_args.currencies()
+    }
 }
 
 #[test]
@@ -6,7 +6,7 @@
 //! [Spec](https://www.rosetta-api.org/docs/api_objects.html)
 
 use crate::{
-    common::{is_native_coin, native_coin, native_coin_tag},
+    common::{fa_metadata_address, fungible_asset_metadata_tag, native_coin, native_coin_tag},
     construction::{
         parse_create_stake_pool_operation, parse_delegation_pool_add_stake_operation,
         parse_delegation_pool_unlock_operation, parse_delegation_pool_withdraw_operation,
@@ -28,7 +28,9 @@ use aptos_logger::warn;
 use aptos_rest_client::aptos_api_types::{TransactionOnChainData, U64};
 use aptos_types::{
     account_address::AccountAddress,
-    account_config::{AccountResource, CoinStoreResource, WithdrawEvent},
+    account_config::{
+        fungible_store::FungibleStoreResource, AccountResource, CoinStoreResource, WithdrawEvent,
+    },
     contract_event::{ContractEvent, FEE_STATEMENT_EVENT_TYPE},
     event::EventKey,
     fee_statement::FeeStatement,
@@ -38,7 +40,12 @@ use aptos_types::{
     write_set::{WriteOp, WriteSet},
 };
 use itertools::Itertools;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    language_storage::{ModuleId, StructTag, TypeTag},
+    move_resource::MoveStructType,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
@@ -146,9 +153,15 @@ pub struct Currency {
     pub metadata: Option<CurrencyMetadata>,
 }
 
+/// Identifies the on-chain asset of a [`Currency`], either a coin or a fungible asset
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct CurrencyMetadata {
-    pub move_type: String,
+    /// Move type of the coin e.g. `0x1::aptos_coin::AptosCoin`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub move_type: Option<String>,
+    /// Address of the fungible asset metadata object e.g. `0xa`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fa_address: Option<String>,
 }
 
 /// Various signing curves supported by Rosetta.  We only use [`CurveType::Edwards25519`]
@@ -936,6 +949,7 @@ impl Transaction {
             // Parse all failed operations from the payload
             if let Some(user_txn) = maybe_user_txn {
                 let mut ops = parse_failed_operations_from_txn_payload(
+                    server_context,
                     operation_index,
                     user_txn.sender(),
                     user_txn.payload(),
@@ -980,6 +994,7 @@ impl Transaction {
 /// This case only occurs if the transaction failed, and that's because it's less accurate
 /// than just following the state changes
 fn parse_failed_operations_from_txn_payload(
+    server_context: &RosettaContext,
     operation_index: u64,
     sender: AccountAddress,
     payload: &TransactionPayload,
@@ -1012,6 +1027,18 @@ fn parse_failed_operations_from_txn_payload(
                 operations =
                     parse_transfer_from_txn_payload(inner, native_coin(), sender, operation_index)
             },
+            (AccountAddress::ONE, PRIMARY_FUNGIBLE_STORE_MODULE, TRANSFER_FUNCTION) => {
+                // Only put the transfer in if we can understand the fungible asset
+                if let Some(currency) = inner
+                    .args()
+                    .first()
+                    .and_then(|encoded| bcs::from_bytes::<AccountAddress>(encoded).ok())
+                    .and_then(|metadata_address| server_context.find_fa_currency(metadata_address))
+                {
+                    operations =
+                        parse_transfer_from_txn_payload(inner, currency, sender, operation_index)
+                }
+            },
             (AccountAddress::ONE, ACCOUNT_MODULE, CREATE_ACCOUNT_FUNCTION) => {
                 if let Some(Ok(address)) = inner
                     .args()
@@ -1160,7 +1187,12 @@ fn parse_transfer_from_txn_payload(
 ) -> Vec<Operation> {
     let mut operations = vec![];
 
-    let args = payload.args();
+    // Fungible asset transfers have the metadata object before the receiver and the amount
+    let args = if payload.module().name().as_str() == PRIMARY_FUNGIBLE_STORE_MODULE {
+        payload.args().get(1..).unwrap_or_default()
+    } else {
+        payload.args()
+    };
     let maybe_receiver = args
         .first()
         .map(|encoded| bcs::from_bytes::<AccountAddress>(encoded));
@@ -1260,6 +1292,16 @@ async fn parse_operations_from_write_set(
             parse_delegation_pool_resource_changes(address, data, events, operation_index, changes)
                 .await
         },
+        (AccountAddress::ONE, OBJECT_MODULE, OBJECT_GROUP_RESOURCE, 0) => {
+            parse_fungible_store_changes(
+                server_context,
+                version,
+                address,
+                data,
+                events,
+                operation_index,
+            )
+        },
         (AccountAddress::ONE, COIN_MODULE, COIN_STORE_RESOURCE, 1) => {
             if let Some(type_tag) = struct_tag.type_args.first() {
                 // TODO: This will need to be updated to support more coins
@@ -1821,6 +1863,118 @@ async fn parse_coinstore_changes(
     Ok(operations)
 }
 
+/// Parses the balance changes of a fungible store, which is in the resource group of its object
+///
+/// The changes are attributed to the owner of the object, e.g., the owner of a primary store
+fn parse_fungible_store_changes(
+    server_context: &RosettaContext,
+    version: u64,
+    address: AccountAddress,
+    data: &[u8],
+    events: &[ContractEvent],
+    mut operation_index: u64,
+) -> ApiResult<Vec<Operation>> {
+    let resources: BTreeMap<StructTag, Vec<u8>> = if let Ok(resources) = bcs::from_bytes(data) {
+        resources
+    } else {
+        warn!(
+            "Object group failed to parse for address {} at version {}",
+            address, version
+        );
+        return Ok(vec![]);
+    };
+
+    // Skip objects that aren't fungible stores
+    let fungible_store = if let Some(bytes) = resources.get(&FungibleStoreResource::struct_tag()) {
+        bcs::from_bytes::<FungibleStoreResource>(bytes)?
+    } else {
+        return Ok(vec![]);
+    };
+    let object_core_tag = StructTag {
+        address: AccountAddress::ONE,
+        module: ident_str!(OBJECT_MODULE).into(),
+        name: ident_str!(OBJECT_CORE_RESOURCE).into(),
+        type_args: vec![],
+    };
+    let owner = if let Some(bytes) = resources.get(&object_core_tag) {
+        bcs::from_bytes::<ObjectCore>(bytes)?.owner
+    } else {
+        warn!(
+            "Fungible store {} has no object core at version {}",
+            address, version
+        );
+        return Ok(vec![]);
+    };
+
+    // Skip if the fungible asset isn't a supported currency
+    let currency =
+        if let Some(currency) = server_context.find_fa_currency(fungible_store.metadata()) {
+            currency
+        } else {
+            return Ok(vec![]);
+        };
+
+    let mut operations = vec![];
+    let withdraw_amounts =
+        get_amount_from_fa_event(events, ident_str!(FUNGIBLE_ASSET_WITHDRAW_EVENT), address);
+    for amount in withdraw_amounts {
+        operations.push(Operation::withdraw(
+            operation_index,
+            Some(OperationStatusType::Success),
+            AccountIdentifier::base_account(owner),
+            currency.clone(),
+            amount,
+        ));
+        operation_index += 1;
+    }
+
+    let deposit_amounts =
+        get_amount_from_fa_event(events, ident_str!(FUNGIBLE_ASSET_DEPOSIT_EVENT), address);
+    for amount in deposit_amounts {
+        operations.push(Operation::deposit(
+            operation_index,
+            Some(OperationStatusType::Success),
+            AccountIdentifier::base_account(owner),
+            currency.clone(),
+            amount,
+        ));
+        operation_index += 1;
+    }
+
+    Ok(operations)
+}
+
+/// Pulls the balance changes of a fungible store from its v2 withdraw or deposit events
+fn get_amount_from_fa_event(
+    events: &[ContractEvent],
+    event_name: &IdentStr,
+    store_address: AccountAddress,
+) -> Vec<u64> {
+    let event_type = TypeTag::Struct(Box::new(StructTag {
+        address: AccountAddress::ONE,
+        module: ident_str!(FUNGIBLE_ASSET_MODULE).into(),
+        name: event_name.to_owned(),
+        type_args: vec![],
+    }));
+    events
+        .iter()
+        .filter_map(
+            |event| match event.try_v2_typed::<FungibleAssetEvent>(&event_type) {
+                Ok(Some(event)) if event.store == store_address => Some(event.amount),
+                Ok(_) => None,
+                Err(_) => {
+                    // If we can't parse the event, then there's nothing
+                    warn!(
+                        "Failed to parse fungible asset {} event!  Skipping for {}",
+                        event_name, store_address
+                    );
+                    None
+                },
+            },
+        )
+        .collect()
+}
+
 /// Pulls the balance change from a withdraw or deposit event
 fn get_amount_from_event(events: &[ContractEvent], event_key: &EventKey) -> Vec<u64> {
     filter_events(events, event_key, |event_key, event| {
@@ -1901,7 +2055,10 @@ pub enum InternalOperation {
 
 impl InternalOperation {
     /// Pulls the [`InternalOperation`] from the set of [`Operation`]
-    pub fn extract(operations: &Vec<Operation>) -> ApiResult<InternalOperation> {
+    pub fn extract(
+        server_context: &RosettaContext,
+        operations: &Vec<Operation>,
+    ) -> ApiResult<InternalOperation> {
         match operations.len() {
             1 => {
                 if let Some(operation) = operations.first() {
@@ -2147,7 +2304,10 @@ impl InternalOperation {
                     operations
                 ))))
             },
-            2 => Ok(Self::Transfer(Transfer::extract_transfer(operations)?)),
+            2 => Ok(Self::Transfer(Transfer::extract_transfer(
+                server_context,
+                operations,
+            )?)),
             _ => Err(ApiError::InvalidOperations(Some(format!(
                 "Unrecognized operation combination {:?}",
                 operations
@@ -2182,11 +2342,25 @@ impl InternalOperation {
                 create_account.sender,
             ),
             InternalOperation::Transfer(transfer) => {
-                is_native_coin(&transfer.currency)?;
-                (
-                    aptos_stdlib::aptos_account_transfer(transfer.receiver, transfer.amount.0),
-                    transfer.sender,
-                )
+                if transfer.currency == native_coin() {
+                    (
+                        aptos_stdlib::aptos_account_transfer(transfer.receiver, transfer.amount.0),
+                        transfer.sender,
+                    )
+                } else if let Some(metadata_address) = fa_metadata_address(&transfer.currency) {
+                    (
+                        primary_fungible_store_transfer(
+                            metadata_address,
+                            transfer.receiver,
+                            transfer.amount.0,
+                        ),
+                        transfer.sender,
+                    )
+                } else {
+                    return Err(ApiError::UnsupportedCurrency(Some(
+                        transfer.currency.symbol.clone(),
+                    )));
+                }
             },
             InternalOperation::SetOperator(set_operator) => {
                 if set_operator.old_operator.is_none() {
@@ -2276,6 +2450,29 @@ impl InternalOperation {
     }
 }
 
+/// Transfers a fungible asset between primary stores, creating them if needed
+///
+/// `primary_fungible_store::transfer` is not in the generated SDK builders
+fn primary_fungible_store_transfer(
+    metadata_address: AccountAddress,
+    receiver: AccountAddress,
+    amount: u64,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::ONE,
+            ident_str!(PRIMARY_FUNGIBLE_STORE_MODULE).to_owned(),
+        ),
+        ident_str!(TRANSFER_FUNCTION).to_owned(),
+        vec![fungible_asset_metadata_tag()],
+        vec![
+            bcs::to_bytes(&metadata_address).unwrap(),
+            bcs::to_bytes(&receiver).unwrap(),
+            bcs::to_bytes(&amount).unwrap(),
+        ],
+    ))
+}
+
 /// Operation to create an account
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct CreateAccount {
@@ -2293,7 +2490,10 @@ pub struct Transfer {
 }
 
 impl Transfer {
-    pub fn extract_transfer(operations: &Vec<Operation>) -> ApiResult<Transfer> {
+    pub fn extract_transfer(
+        server_context: &RosettaContext,
+        operations: &Vec<Operation>,
+    ) -> ApiResult<Transfer> {
         // Only support 1:1 P2P transfer
         // This is composed of a Deposit and a Withdraw operation
         if operations.len() != 2 {
@@ -2365,8 +2565,7 @@ impl Transfer {
         }
 
         // Check that the currency is supported
-        // TODO: in future use currency, since there's more than just 1
-        is_native_coin(&withdraw_amount.currency)?;
+        server_context.check_currency(&withdraw_amount.currency)?;
 
         let withdraw_value = i128::from_str(&withdraw_amount.value)
             .map_err(|_| ApiError::InvalidTransferOperations(Some("Withdraw amount is invalid")))?;
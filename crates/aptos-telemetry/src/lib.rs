@@ -11,6 +11,7 @@ mod sender;
 mod telemetry_log_sender;
 
 pub mod cli_metrics;
+pub mod otlp_exporter;
 pub mod service;
 pub mod system_information;
 pub mod utils;
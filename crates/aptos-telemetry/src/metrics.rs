@@ -76,6 +76,34 @@ pub(crate) fn increment_telemetry_service_failures(event_name: &str) {
         .inc();
 }
 
+/// Counter for transaction spans exported to the OTLP collector
+pub(crate) static APTOS_OTLP_EXPORT_SUCCESS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_otlp_export_success",
+        "Number of transaction spans successfully exported to the OTLP collector"
+    )
+    .unwrap()
+});
+
+/// Counter for transaction spans that failed to be exported to the OTLP collector
+pub(crate) static APTOS_OTLP_EXPORT_FAILURE: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_otlp_export_failure",
+        "Number of transaction spans that failed to be exported to the OTLP collector"
+    )
+    .unwrap()
+});
+
+/// Increments the number of transaction spans exported to the OTLP collector
+pub(crate) fn increment_otlp_export_successes_by(v: u64) {
+    APTOS_OTLP_EXPORT_SUCCESS.inc_by(v);
+}
+
+/// Increments the number of transaction spans that failed to be exported
+pub(crate) fn increment_otlp_export_failures_by(v: u64) {
+    APTOS_OTLP_EXPORT_FAILURE.inc_by(v);
+}
+
 /// Counter for successful log ingest events sent to Telemetry Service
 pub(crate) static APTOS_LOG_INGEST_SUCCESS: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -0,0 +1,199 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports the traces of sampled transactions (see `aptos_logger::txn_trace`) to an
+//! OpenTelemetry collector, over OTLP/HTTP with the JSON encoding.
+//!
+//! Every stage of a transaction is a span of the same trace, whose id is derived from the
+//! transaction hash, so the collector puts mempool admission, consensus ordering, execution and
+//! commit of the transaction together.
+
+use crate::metrics::{increment_otlp_export_failures_by, increment_otlp_export_successes_by};
+use aptos_config::config::{NodeConfig, OtlpExporterConfig};
+use aptos_crypto::HashValue;
+use aptos_logger::{
+    prelude::*,
+    txn_trace::{init_txn_tracer, TxnSpan},
+};
+use serde_json::{json, Value};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::{runtime::Runtime, sync::mpsc};
+
+const OTLP_SCOPE_NAME: &str = "aptos-txn-trace";
+const OTLP_SERVICE_NAME: &str = "aptos-node";
+/// `SPAN_KIND_INTERNAL` of the OTLP protocol
+const OTLP_SPAN_KIND_INTERNAL: u64 = 1;
+
+/// Starts tracing the sampled transactions and exporting their spans, if enabled in the config
+pub fn start_otlp_exporter(node_config: &NodeConfig) -> Option<Runtime> {
+    let config = node_config.otlp_exporter.clone();
+    if !config.enabled {
+        return None;
+    }
+
+    let span_rx = match init_txn_tracer(config.txn_sample_rate, config.max_queued_spans) {
+        Some(span_rx) => span_rx,
+        None => {
+            warn!("The transaction tracer is already set up, not starting the OTLP exporter!");
+            return None;
+        },
+    };
+    info!(
+        "Exporting the traces of one in {} transactions to {}",
+        config.txn_sample_rate, config.endpoint
+    );
+
+    let resource_attributes = vec![
+        string_attribute("service.name", OTLP_SERVICE_NAME),
+        string_attribute(
+            "aptos.peer_id",
+            &node_config
+                .get_peer_id()
+                .map_or_else(|| "unknown".to_string(), |peer_id| peer_id.to_string()),
+        ),
+    ];
+    let runtime = aptos_runtimes::spawn_named_runtime("otlp-export".into(), Some(1));
+    runtime
+        .handle()
+        .spawn(run_otlp_exporter(config, resource_attributes, span_rx));
+    Some(runtime)
+}
+
+async fn run_otlp_exporter(
+    config: OtlpExporterConfig,
+    resource_attributes: Vec<Value>,
+    mut span_rx: mpsc::Receiver<TxnSpan>,
+) {
+    let client = reqwest::Client::new();
+    let mut interval = tokio::time::interval(Duration::from_millis(config.export_interval_ms));
+    let mut spans = Vec::with_capacity(config.max_batch_size);
+    loop {
+        tokio::select! {
+            maybe_span = span_rx.recv() => match maybe_span {
+                Some(span) => {
+                    spans.push(span);
+                    if spans.len() >= config.max_batch_size {
+                        export_spans(&client, &config.endpoint, &resource_attributes, &mut spans)
+                            .await;
+                    }
+                },
+                None => break,
+            },
+            _ = interval.tick() => {
+                if !spans.is_empty() {
+                    export_spans(&client, &config.endpoint, &resource_attributes, &mut spans)
+                        .await;
+                }
+            },
+        }
+    }
+}
+
+/// Sends the spans to the collector. Spans that fail to be sent are dropped, so that a collector
+/// that is down doesn't make them pile up.
+async fn export_spans(
+    client: &reqwest::Client,
+    endpoint: &str,
+    resource_attributes: &[Value],
+    spans: &mut Vec<TxnSpan>,
+) {
+    let num_spans = spans.len() as u64;
+    let request = export_request(resource_attributes, spans.drain(..));
+    let result = client
+        .post(endpoint)
+        .json(&request)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    match result {
+        Ok(_) => increment_otlp_export_successes_by(num_spans),
+        Err(error) => {
+            increment_otlp_export_failures_by(num_spans);
+            sample!(
+                SampleRate::Duration(Duration::from_secs(60)),
+                warn!(
+                    "Failed to export transaction spans to {}: {}",
+                    endpoint, error
+                )
+            );
+        },
+    }
+}
+
+/// Builds an `ExportTraceServiceRequest` of the OTLP protocol, in its JSON encoding
+fn export_request(resource_attributes: &[Value], spans: impl Iterator<Item = TxnSpan>) -> Value {
+    let spans: Vec<Value> = spans.map(|span| otlp_span(&span)).collect();
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": resource_attributes },
+            "scopeSpans": [{
+                "scope": { "name": OTLP_SCOPE_NAME },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+fn otlp_span(span: &TxnSpan) -> Value {
+    let txn_hash = HashValue::new(span.txn_hash);
+    // The span id only has to be unique within the trace of the transaction
+    let mut span_id = [0u8; 8];
+    span_id.copy_from_slice(&span.txn_hash[16..24]);
+    span_id[7] ^= span.stage as u8 + 1;
+
+    json!({
+        "traceId": &txn_hash.to_hex()[..32],
+        "spanId": format!("{:016x}", u64::from_be_bytes(span_id)),
+        "name": span.stage.as_str(),
+        "kind": OTLP_SPAN_KIND_INTERNAL,
+        "startTimeUnixNano": unix_nanos(span.start_time).to_string(),
+        "endTimeUnixNano": unix_nanos(span.end_time).to_string(),
+        "attributes": [string_attribute("aptos.txn_hash", &txn_hash.to_hex_literal())],
+    })
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_logger::txn_trace::TxnTraceStage;
+
+    #[test]
+    fn test_export_request() {
+        let txn_hash = [1u8; 32];
+        let start_time = UNIX_EPOCH + Duration::from_secs(1);
+        let spans = [TxnTraceStage::MempoolAdmission, TxnTraceStage::Commit].map(|stage| TxnSpan {
+            txn_hash,
+            stage,
+            start_time,
+            end_time: start_time + Duration::from_millis(5),
+        });
+
+        let request = export_request(
+            &[string_attribute("service.name", OTLP_SERVICE_NAME)],
+            spans.into_iter(),
+        );
+        let otlp_spans = request["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+        assert_eq!(otlp_spans.len(), 2);
+
+        // Both stages are in the trace of the transaction, as different spans
+        assert_eq!(otlp_spans[0]["traceId"], "01".repeat(16));
+        assert_eq!(otlp_spans[0]["traceId"], otlp_spans[1]["traceId"]);
+        assert_ne!(otlp_spans[0]["spanId"], otlp_spans[1]["spanId"]);
+        assert_eq!(otlp_spans[0]["spanId"].as_str().unwrap().len(), 16);
+        assert_eq!(otlp_spans[0]["name"], "mempool_admission");
+        assert_eq!(otlp_spans[1]["name"], "commit");
+        assert_eq!(otlp_spans[0]["startTimeUnixNano"], "1000000000");
+        assert_eq!(otlp_spans[0]["endTimeUnixNano"], "1005000000");
+    }
+}
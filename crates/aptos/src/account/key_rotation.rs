@@ -6,7 +6,8 @@ use crate::common::{
         account_address_from_auth_key, account_address_from_public_key,
         AuthenticationKeyInputOptions, CliCommand, CliConfig, CliError, CliTypedResult,
         ConfigSearchMode, EncodingOptions, ExtractPublicKey, ParsePrivateKey, ProfileConfig,
-        ProfileOptions, PublicKeyInputOptions, RestOptions, TransactionOptions, TransactionSummary,
+        ProfileOptions, PublicKeyInputOptions, RestOptions, RngArgs, TransactionOptions,
+        TransactionSummary, DEFAULT_PROFILE,
     },
     utils::{prompt_yes, prompt_yes_with_override, read_line},
 };
@@ -14,7 +15,7 @@ use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
     encoding_type::EncodingType,
-    PrivateKey, SigningKey,
+    PrivateKey, SigningKey, ValidCryptoMaterialStringExt,
 };
 use aptos_rest_client::{
     aptos_api_types::{AptosError, AptosErrorCode},
@@ -34,10 +35,13 @@ use std::{collections::BTreeMap, path::PathBuf};
 /// Rotate an account's authentication key
 ///
 /// Rotating the account's authentication key allows you to use a new
-/// private key.  You must provide a new private key.  Once it is
-/// rotated you will need to use the original account address, with the
-/// new private key.  There is an interactive prompt to help you add it
-/// to a new profile.
+/// private key.  A new private key is generated, unless one is provided.
+/// Once it is rotated you will need to use the original account address,
+/// with the new private key.  After the rotation is verified on-chain, the
+/// profile of the account is updated with the new key, unless
+/// `--save-to-profile` or `--skip-saving-profile` is provided.  A generated
+/// key is only kept in a profile, so it can't be used with
+/// `--skip-saving-profile`.
 #[derive(Debug, Parser)]
 pub struct RotateKey {
     #[clap(flatten)]
@@ -53,16 +57,21 @@ pub struct RotateKey {
 
     /// Name of the profile to save the new private key
     ///
-    /// If not provided, it will interactively have you save a profile,
-    /// unless `--skip_saving_profile` is provided
+    /// If not provided, the profile of the account is updated.  If the current
+    /// private key was given on the command line instead, it will interactively
+    /// have you save a profile, unless `--skip-saving-profile` is provided
     #[clap(long)]
     pub(crate) save_to_profile: Option<String>,
 
     /// Skip saving profile
     ///
-    /// This skips the interactive profile saving after rotating the authentication key
+    /// This skips the interactive profile saving after rotating the authentication key.
+    /// The new private key must then be provided
     #[clap(long)]
     pub(crate) skip_saving_profile: bool,
+
+    #[clap(flatten)]
+    pub(crate) rng_args: RngArgs,
 }
 
 impl ParsePrivateKey for RotateKey {}
@@ -94,14 +103,18 @@ impl CliCommand<RotateSummary> for RotateKey {
     }
 
     async fn execute(self) -> CliTypedResult<RotateSummary> {
-        let new_private_key = self
-            .extract_private_key(self.txn_options.encoding_options.encoding)?
-            .ok_or_else(|| {
-                CliError::CommandArgumentError(
-                    "One of ['--new-private-key', '--new-private-key-file'] must be used"
-                        .to_string(),
-                )
-            })?;
+        let (new_private_key, is_generated_key) = if let Some(key) =
+            self.extract_private_key(self.txn_options.encoding_options.encoding)?
+        {
+            (key, false)
+        } else {
+            eprintln!("No new private key given, generating key...");
+            let key = self
+                .rng_args
+                .key_generator()?
+                .generate_ed25519_private_key();
+            (key, true)
+        };
 
         let (current_private_key, sender_address) = self.txn_options.get_key_and_address()?;
 
@@ -111,6 +124,48 @@ impl CliCommand<RotateSummary> for RotateKey {
             ));
         }
 
+        // Decide where to save the new key up front, as the current key is no longer valid once
+        // the rotation went through
+        let profile_name = self.profile_to_save()?;
+        if is_generated_key && profile_name.is_none() {
+            return Err(CliError::CommandArgumentError(
+                "A generated private key must be saved to a profile, provide the new private key \
+                with --new-private-key or --new-private-key-file instead"
+                    .to_string(),
+            ));
+        }
+
+        let result = self
+            .rotate(
+                &current_private_key,
+                &new_private_key,
+                sender_address,
+                profile_name,
+            )
+            .await;
+        // A generated key only exists in memory, so it must not be lost if anything fails once
+        // the rotation may have gone through
+        if result.is_err() && is_generated_key {
+            eprintln!(
+                "Failed to rotate to the new private key, please store it safely: {}",
+                new_private_key
+                    .to_encoded_string()
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+            );
+        }
+        result
+    }
+}
+
+impl RotateKey {
+    /// Rotates the authentication key to the new key, and saves the new key to the profile
+    async fn rotate(
+        &self,
+        current_private_key: &Ed25519PrivateKey,
+        new_private_key: &Ed25519PrivateKey,
+        sender_address: AccountAddress,
+        profile_name: Option<String>,
+    ) -> CliTypedResult<RotateSummary> {
         // Get sequence number for account
         let sequence_number = self.txn_options.sequence_number(sender_address).await?;
         let auth_key = self.txn_options.auth_key(sender_address).await?;
@@ -137,7 +192,7 @@ impl CliCommand<RotateSummary> for RotateKey {
 
         let txn_summary = self
             .txn_options
-            .submit_transaction(aptos_stdlib::account_rotate_authentication_key(
+            .submit_transaction_as_sender(aptos_stdlib::account_rotate_authentication_key(
                 0,
                 // Existing public key
                 current_private_key.public_key().to_bytes().to_vec(),
@@ -169,88 +224,134 @@ impl CliCommand<RotateSummary> for RotateKey {
             ));
         }
 
-        let mut profile_name: String;
+        // Only replace the key in the profile once the new key is in effect on-chain
+        let new_auth_key = self.txn_options.auth_key(sender_address).await?;
+        if new_auth_key != AuthenticationKey::ed25519(&new_private_key.public_key()) {
+            return Err(CliError::UnexpectedError(format!(
+                "Authentication key of account {} is {} after the rotation, which doesn't match the new private key",
+                sender_address, new_auth_key
+            )));
+        }
 
-        if self.save_to_profile.is_none() {
-            if self.skip_saving_profile
-                || !prompt_yes("Do you want to create a profile for the new key?")
-            {
+        let profile_name = match profile_name {
+            Some(profile_name) => profile_name,
+            None => {
                 return Ok(RotateSummary {
                     transaction: txn_summary,
                     message: None,
-                });
-            }
+                })
+            },
+        };
 
-            eprintln!("Enter the name for the profile");
-            profile_name = read_line("Profile name")?.trim().to_string();
-        } else {
-            // We can safely unwrap here
-            profile_name = self.save_to_profile.unwrap();
-        }
+        self.save_profile(&profile_name, new_private_key, sender_address)?;
+
+        eprintln!("Profile {} is saved.", profile_name);
+
+        Ok(RotateSummary {
+            transaction: txn_summary,
+            message: Some(format!("Profile {} is saved.", profile_name)),
+        })
+    }
 
-        // Check if profile name exists
-        let mut config = CliConfig::load(ConfigSearchMode::CurrentDirAndParents)?;
+    /// The name of the profile to save the new key to, if any
+    ///
+    /// Defaults to the profile of the account, as its key is no longer valid after the rotation.
+    fn profile_to_save(&self) -> CliTypedResult<Option<String>> {
+        if self.skip_saving_profile {
+            return Ok(None);
+        }
 
-        if let Some(ref profiles) = config.profiles {
-            if profiles.contains_key(&profile_name) {
-                if let Err(cli_err) = prompt_yes_with_override(
-                    format!(
-                        "Profile {} exits. Do you want to provide a new profile name?",
+        let current_profile_name = self
+            .txn_options
+            .profile_options
+            .profile_name()
+            .unwrap_or(DEFAULT_PROFILE);
+        if let Some(ref profile_name) = self.save_to_profile {
+            if profile_name != current_profile_name
+                && CliConfig::load_profile(
+                    Some(profile_name),
+                    ConfigSearchMode::CurrentDirAndParents,
+                )?
+                .is_some()
+            {
+                prompt_yes_with_override(
+                    &format!(
+                        "Profile {} already exists, do you want to overwrite it?",
                         profile_name
-                    )
-                    .as_str(),
+                    ),
                     self.txn_options.prompt_options,
-                ) {
-                    match cli_err {
-                        CliError::AbortedError => {
-                            return Ok(RotateSummary {
-                                transaction: txn_summary,
-                                message: None,
-                            });
-                        },
-                        _ => {
-                            return Err(cli_err);
-                        },
-                    }
-                }
-
-                eprintln!("Enter the name for the profile");
-                profile_name = read_line("Profile name")?.trim().to_string();
+                )?;
             }
+            return Ok(Some(profile_name.clone()));
         }
 
+        // If the current key came from the profile, the profile has to be updated
+        let key_from_profile = self
+            .txn_options
+            .private_key_options
+            .extract_private_key_cli(self.txn_options.encoding_options.encoding)?
+            .is_none();
+        if key_from_profile
+            && CliConfig::load_profile(
+                Some(current_profile_name),
+                ConfigSearchMode::CurrentDirAndParents,
+            )?
+            .is_some()
+        {
+            eprintln!(
+                "Profile {} will be updated with the new key",
+                current_profile_name
+            );
+            return Ok(Some(current_profile_name.to_string()));
+        }
+
+        if !prompt_yes("Do you want to create a profile for the new key?") {
+            return Ok(None);
+        }
+        eprintln!("Enter the name for the profile");
+        let profile_name = read_line("Profile name")?.trim().to_string();
         if profile_name.is_empty() {
             return Err(CliError::AbortedError);
         }
+        Ok(Some(profile_name))
+    }
+
+    /// Saves the new key to the profile, keeping the other settings of the current profile
+    fn save_profile(
+        &self,
+        profile_name: &str,
+        new_private_key: &Ed25519PrivateKey,
+        sender_address: AccountAddress,
+    ) -> CliTypedResult<()> {
+        let mut config = if CliConfig::config_exists(ConfigSearchMode::CurrentDirAndParents) {
+            CliConfig::load(ConfigSearchMode::CurrentDirAndParents)?
+        } else {
+            CliConfig::default()
+        };
 
         let mut profile_config = ProfileConfig {
             private_key: Some(new_private_key.clone()),
             public_key: Some(new_private_key.public_key()),
             account: Some(sender_address),
-            ..self.txn_options.profile_options.profile()?
+            // The key is no longer on a hardware wallet, or ephemeral
+            derivation_path: None,
+            keyless: None,
+            ..self
+                .txn_options
+                .profile_options
+                .profile()
+                .unwrap_or_default()
         };
 
-        if let Some(url) = self.txn_options.rest_options.url {
-            profile_config.rest_url = Some(url.into());
-        }
-
-        if config.profiles.is_none() {
-            config.profiles = Some(BTreeMap::new());
+        if let Some(ref url) = self.txn_options.rest_options.url {
+            profile_config.rest_url = Some(url.to_string());
         }
 
         config
             .profiles
-            .as_mut()
-            .unwrap()
-            .insert(profile_name.clone(), profile_config);
-        config.save()?;
-
-        eprintln!("Profile {} is saved.", profile_name);
-
-        Ok(RotateSummary {
-            transaction: txn_summary,
-            message: Some(format!("Profile {} is saved.", profile_name)),
-        })
+            .get_or_insert_with(BTreeMap::new)
+            .insert(profile_name.to_string(), profile_config);
+        config.save()
     }
 }
 
@@ -27,6 +27,7 @@ pub enum AccountTool {
     LookupAddress(key_rotation::LookupAddress),
     RotateKey(key_rotation::RotateKey),
     Transfer(transfer::TransferCoins),
+    TransferBatch(transfer::TransferBatch),
 }
 
 impl AccountTool {
@@ -40,6 +41,7 @@ impl AccountTool {
             AccountTool::LookupAddress(tool) => tool.execute_serialized().await,
             AccountTool::RotateKey(tool) => tool.execute_serialized().await,
             AccountTool::Transfer(tool) => tool.execute_serialized().await,
+            AccountTool::TransferBatch(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -54,6 +56,7 @@ pub enum MultisigAccountTool {
     ExecuteReject(multisig_account::ExecuteReject),
     ExecuteWithPayload(multisig_account::ExecuteWithPayload),
     Reject(multisig_account::Reject),
+    Status(multisig_account::Status),
     VerifyProposal(multisig_account::VerifyProposal),
 }
 
@@ -67,6 +70,7 @@ impl MultisigAccountTool {
             MultisigAccountTool::ExecuteReject(tool) => tool.execute_serialized().await,
             MultisigAccountTool::ExecuteWithPayload(tool) => tool.execute_serialized().await,
             MultisigAccountTool::Reject(tool) => tool.execute_serialized().await,
+            MultisigAccountTool::Status(tool) => tool.execute_serialized().await,
             MultisigAccountTool::VerifyProposal(tool) => tool.execute_serialized().await,
         }
     }
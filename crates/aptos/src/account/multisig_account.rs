@@ -22,7 +22,7 @@ use aptos_types::{
 use async_trait::async_trait;
 use bcs::to_bytes;
 use clap::Parser;
-use move_core_types::{ident_str, language_storage::ModuleId};
+use move_core_types::{ident_str, identifier::IdentStr, language_storage::ModuleId};
 use serde::Serialize;
 use serde_json::json;
 
@@ -67,7 +67,7 @@ impl From<Transaction> for CreateSummary {
                         && *address.inner().to_hex() != *txn.request.sender.inner().to_hex()
                     {
                         Some(MultisigAccount {
-                            multisig_address: *address.inner(),
+                            multisig_address: Some(*address.inner()),
                         })
                     } else {
                         None
@@ -89,7 +89,7 @@ impl CliCommand<CreateSummary> for Create {
 
     async fn execute(self) -> CliTypedResult<CreateSummary> {
         self.txn_options
-            .submit_transaction(aptos_stdlib::multisig_account_create_with_owners(
+            .submit_transaction_as_sender(aptos_stdlib::multisig_account_create_with_owners(
                 self.additional_owners,
                 self.num_signatures_required,
                 // TODO: Support passing in custom metadata.
@@ -106,7 +106,11 @@ impl CliCommand<CreateSummary> for Create {
 /// As one of the owners of the multisig, propose a new transaction. This also implicitly approves
 /// the created transaction so it has one approval initially. In order for the transaction to be
 /// executed, it needs as many approvals as the number of signatures required.
+///
+/// With a profile for an owner of a multisig account (see `aptos init --multisig-address`), any
+/// other command that submits a transaction proposes it to the multisig account as well.
 #[derive(Debug, Parser)]
+#[clap(alias = "propose")]
 pub struct CreateTransaction {
     #[clap(flatten)]
     pub(crate) multisig_account: MultisigAccount,
@@ -126,21 +130,22 @@ impl CliCommand<TransactionSummary> for CreateTransaction {
     }
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let multisig_address = self.multisig_account.address(&self.txn_options)?;
         let multisig_transaction_payload_bytes =
             to_bytes::<MultisigTransactionPayload>(&self.entry_function_args.try_into()?)?;
         let transaction_payload = if self.store_hash_only {
             aptos_stdlib::multisig_account_create_transaction_with_hash(
-                self.multisig_account.multisig_address,
+                multisig_address,
                 HashValue::sha3_256_of(&multisig_transaction_payload_bytes).to_vec(),
             )
         } else {
             aptos_stdlib::multisig_account_create_transaction(
-                self.multisig_account.multisig_address,
+                multisig_address,
                 multisig_transaction_payload_bytes,
             )
         };
         self.txn_options
-            .submit_transaction(transaction_payload)
+            .submit_transaction_as_sender(transaction_payload)
             .await
             .map(|inner| inner.into())
     }
@@ -164,6 +169,10 @@ impl CliCommand<serde_json::Value> for VerifyProposal {
     }
 
     async fn execute(self) -> CliTypedResult<serde_json::Value> {
+        let multisig_address = self
+            .multisig_account_with_sequence_number
+            .multisig_account
+            .address(&self.txn_options)?;
         // Get multisig transaction via view function.
         let multisig_transaction = &self
             .txn_options
@@ -175,13 +184,7 @@ impl CliCommand<serde_json::Value> for VerifyProposal {
                 function: ident_str!("get_transaction").to_owned(),
                 ty_args: vec![],
                 args: vec![
-                    bcs::to_bytes(
-                        &self
-                            .multisig_account_with_sequence_number
-                            .multisig_account
-                            .multisig_address,
-                    )
-                    .unwrap(),
+                    bcs::to_bytes(&multisig_address).unwrap(),
                     bcs::to_bytes(&self.multisig_account_with_sequence_number.sequence_number)
                         .unwrap(),
                 ],
@@ -223,6 +226,93 @@ impl CliCommand<serde_json::Value> for VerifyProposal {
     }
 }
 
+/// Show the status of a multisig account.
+///
+/// This shows the owners and the number of signatures required, along with the pending
+/// transactions and their votes, so owners can decide what to approve or execute next.
+#[derive(Debug, Parser)]
+pub struct Status {
+    #[clap(flatten)]
+    pub(crate) multisig_account: MultisigAccount,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<serde_json::Value> for Status {
+    fn command_name(&self) -> &'static str {
+        "StatusMultisig"
+    }
+
+    async fn execute(self) -> CliTypedResult<serde_json::Value> {
+        let multisig_address = self.multisig_account.address(&self.txn_options)?;
+        let view = |function: &'static IdentStr| {
+            view_multisig_account(&self.txn_options, function, multisig_address)
+        };
+        let owners = view(ident_str!("owners")).await?;
+        let num_signatures_required = view(ident_str!("num_signatures_required")).await?;
+        let last_resolved_sequence_number =
+            view(ident_str!("last_resolved_sequence_number")).await?;
+        let pending_transactions = view(ident_str!("get_pending_transactions")).await?;
+
+        // The pending transactions are the ones following the last resolved one
+        let last_resolved_sequence_number: u64 = last_resolved_sequence_number
+            .as_str()
+            .and_then(|sequence_number| sequence_number.parse().ok())
+            .ok_or_else(|| {
+                CliError::UnexpectedError(format!(
+                    "Invalid last resolved sequence number {}",
+                    last_resolved_sequence_number
+                ))
+            })?;
+        let pending_transactions: Vec<_> = pending_transactions
+            .as_array()
+            .into_iter()
+            .flatten()
+            .zip(last_resolved_sequence_number + 1..)
+            .map(|(transaction, sequence_number)| {
+                json!({
+                    "sequence_number": sequence_number.to_string(),
+                    "transaction": transaction,
+                })
+            })
+            .collect();
+
+        Ok(json!({
+            "multisig_address": multisig_address,
+            "owners": owners,
+            "num_signatures_required": num_signatures_required,
+            "last_resolved_sequence_number": last_resolved_sequence_number.to_string(),
+            "pending_transactions": pending_transactions,
+        }))
+    }
+}
+
+/// Calls a view function of the multisig account module that takes the multisig address as its
+/// only argument, and returns its single return value
+async fn view_multisig_account(
+    txn_options: &TransactionOptions,
+    function: &IdentStr,
+    multisig_address: AccountAddress,
+) -> CliTypedResult<serde_json::Value> {
+    txn_options
+        .view(ViewFunction {
+            module: ModuleId::new(
+                AccountAddress::ONE,
+                ident_str!("multisig_account").to_owned(),
+            ),
+            function: function.to_owned(),
+            ty_args: vec![],
+            args: vec![bcs::to_bytes(&multisig_address)?],
+        })
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            CliError::UnexpectedError(format!("View function {} returned no value", function))
+        })
+}
+
 /// Approve a multisig transaction.
 ///
 /// As one of the owners of the multisig, approve a transaction proposed for the multisig.
@@ -244,10 +334,10 @@ impl CliCommand<TransactionSummary> for Approve {
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
         self.txn_options
-            .submit_transaction(aptos_stdlib::multisig_account_approve_transaction(
+            .submit_transaction_as_sender(aptos_stdlib::multisig_account_approve_transaction(
                 self.multisig_account_with_sequence_number
                     .multisig_account
-                    .multisig_address,
+                    .address(&self.txn_options)?,
                 self.multisig_account_with_sequence_number.sequence_number,
             ))
             .await
@@ -276,10 +366,10 @@ impl CliCommand<TransactionSummary> for Reject {
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
         self.txn_options
-            .submit_transaction(aptos_stdlib::multisig_account_reject_transaction(
+            .submit_transaction_as_sender(aptos_stdlib::multisig_account_reject_transaction(
                 self.multisig_account_with_sequence_number
                     .multisig_account
-                    .multisig_address,
+                    .address(&self.txn_options)?,
                 self.multisig_account_with_sequence_number.sequence_number,
             ))
             .await
@@ -304,8 +394,8 @@ impl CliCommand<TransactionSummary> for Execute {
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
         self.txn_options
-            .submit_transaction(TransactionPayload::Multisig(Multisig {
-                multisig_address: self.multisig_account.multisig_address,
+            .submit_transaction_as_sender(TransactionPayload::Multisig(Multisig {
+                multisig_address: self.multisig_account.address(&self.txn_options)?,
                 transaction_payload: None,
             }))
             .await
@@ -331,8 +421,11 @@ impl CliCommand<TransactionSummary> for ExecuteWithPayload {
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
         self.execute
             .txn_options
-            .submit_transaction(TransactionPayload::Multisig(Multisig {
-                multisig_address: self.execute.multisig_account.multisig_address,
+            .submit_transaction_as_sender(TransactionPayload::Multisig(Multisig {
+                multisig_address: self
+                    .execute
+                    .multisig_account
+                    .address(&self.execute.txn_options)?,
                 transaction_payload: Some(self.entry_function_args.try_into()?),
             }))
             .await
@@ -360,9 +453,11 @@ impl CliCommand<TransactionSummary> for ExecuteReject {
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
         self.txn_options
-            .submit_transaction(aptos_stdlib::multisig_account_execute_rejected_transaction(
-                self.multisig_account.multisig_address,
-            ))
+            .submit_transaction_as_sender(
+                aptos_stdlib::multisig_account_execute_rejected_transaction(
+                    self.multisig_account.address(&self.txn_options)?,
+                ),
+            )
             .await
             .map(|inner| inner.into())
     }
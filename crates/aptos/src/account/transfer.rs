@@ -1,17 +1,26 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::common::types::{CliCommand, CliTypedResult, TransactionOptions};
+use crate::common::{
+    types::{
+        CliCommand, CliError, CliTypedResult, PromptOptions, TransactionOptions, TransactionSummary,
+    },
+    utils::prompt_yes_with_override,
+};
 use aptos_cached_packages::aptos_stdlib;
 use aptos_rest_client::{
     aptos_api_types::{HashValue, WriteResource, WriteSetChange},
     Transaction,
 };
-use aptos_types::account_address::AccountAddress;
+use aptos_types::{account_address::AccountAddress, transaction::TransactionPayload};
 use async_trait::async_trait;
 use clap::Parser;
 use serde::Serialize;
-use std::collections::BTreeMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    str::FromStr,
+};
 
 // TODO: Add ability to transfer non-APT coins
 // TODO: Add ability to not create account by default
@@ -48,6 +57,277 @@ impl CliCommand<TransferSummary> for TransferCoins {
     }
 }
 
+/// Transfer APT to the recipients of a CSV file
+///
+/// Each row of the file has the address of a recipient and the amount of Octas (10^-8 APT) to
+/// transfer to it, e.g. `0x1234,100000000`.  A header row starting with `address` is skipped.
+///
+/// All rows are validated, and every transaction is built, before anything is submitted.
+/// Unless `--max-gas` is given (or set in the profile), building a transaction simulates it
+/// to estimate its gas.  Each transaction is simulated against the current state, not the
+/// state after the transactions before it, so a transaction may still fail on submission
+/// (e.g. once the balance runs out).  The cost of all transactions is confirmed once.
+///
+/// Up to `--batch-size` recipients are paid in each transaction, which either succeeds or
+/// fails as a whole.  Transactions are submitted in order, and submission stops at the first
+/// failed one.
+#[derive(Debug, Parser)]
+pub struct TransferBatch {
+    /// CSV file with the recipients and amounts
+    #[clap(long, value_parser)]
+    pub(crate) csv: PathBuf,
+
+    /// Maximum number of recipients per transaction
+    ///
+    /// With a batch size of 1, each recipient is paid in a separate transaction.
+    #[clap(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+    pub(crate) batch_size: u64,
+
+    /// Only validate the file and estimate the gas of the transactions, without submitting
+    /// them
+    #[clap(long)]
+    pub(crate) dry_run: bool,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+/// The transactions of a batch transfer
+#[derive(Debug, Serialize)]
+pub struct TransferBatchSummary {
+    pub num_recipients: usize,
+    pub total_amount: u64,
+    /// The transactions in order, without a result if they weren't submitted, i.e. on a dry
+    /// run or after a failed transaction
+    pub transactions: Vec<TransferBatchTransaction>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransferBatchTransaction {
+    /// Rows of the CSV file paid in the transaction, starting from 1
+    pub rows: Vec<usize>,
+    pub gas_unit_price: u64,
+    pub max_gas: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<TransactionSummary>,
+    /// Why the transaction couldn't be submitted, if it couldn't
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A row of the CSV file of a batch transfer
+struct TransferRow {
+    row: usize,
+    recipient: AccountAddress,
+    amount: u64,
+}
+
+#[async_trait]
+impl CliCommand<TransferBatchSummary> for TransferBatch {
+    fn command_name(&self) -> &'static str {
+        "TransferBatch"
+    }
+
+    async fn execute(mut self) -> CliTypedResult<TransferBatchSummary> {
+        let rows = self.read_rows()?;
+        let total_amount = rows
+            .iter()
+            .try_fold(0u64, |total, row| total.checked_add(row.amount))
+            .ok_or_else(|| {
+                CliError::CommandArgumentError("Total amount overflows u64".to_string())
+            })?;
+
+        // Build all transactions first, so that no sequence numbers are burned on a file
+        // that can't be transferred completely.  The cost is confirmed once for all of them.
+        let prompt_options = self.txn_options.prompt_options;
+        self.txn_options.prompt_options = PromptOptions::yes();
+        let mut batches = vec![];
+        for chunk in rows.chunks(self.batch_size as usize) {
+            let row_numbers: Vec<_> = chunk.iter().map(|row| row.row).collect();
+            let payload = transfer_payload(chunk);
+            let raw_transaction = self
+                .txn_options
+                .build_transaction(payload.clone())
+                .await
+                .map_err(|err| {
+                    CliError::UnexpectedError(format!(
+                        "Transfer of rows {} failed to build: {}",
+                        row_range(&row_numbers),
+                        err
+                    ))
+                })?;
+            let transaction = TransferBatchTransaction {
+                rows: row_numbers,
+                gas_unit_price: raw_transaction.gas_unit_price(),
+                max_gas: raw_transaction.max_gas_amount(),
+                result: None,
+                error: None,
+            };
+            batches.push((transaction, payload));
+        }
+
+        if !self.dry_run {
+            let max_gas_cost = batches
+                .iter()
+                .map(|(transaction, _)| transaction.max_gas * transaction.gas_unit_price)
+                .sum::<u64>();
+            let message = format!(
+                "Do you want to transfer {} Octas to {} recipients in {} transactions, for a maximum of {} Octas of gas?",
+                total_amount,
+                rows.len(),
+                batches.len(),
+                max_gas_cost
+            );
+            prompt_yes_with_override(&message, prompt_options)?;
+        }
+
+        // A failure to submit stops the transfer, but still reports the transactions that
+        // were submitted before it
+        let mut transactions = vec![];
+        let mut failed = self.dry_run;
+        for (mut transaction, payload) in batches {
+            if !failed {
+                // Submit with the estimated gas, instead of simulating again
+                self.txn_options.gas_options.gas_unit_price = Some(transaction.gas_unit_price);
+                self.txn_options.gas_options.max_gas = Some(transaction.max_gas);
+                let result = match self.txn_options.submit_transaction(payload).await {
+                    Ok(result) => TransactionSummary::from(result),
+                    Err(err) => {
+                        eprintln!(
+                            "Transfer of rows {} failed to submit, not submitting the remaining rows",
+                            row_range(&transaction.rows)
+                        );
+                        transaction.error = Some(err.to_string());
+                        transactions.push(transaction);
+                        failed = true;
+                        continue;
+                    },
+                };
+                if result.success == Some(true) {
+                    eprintln!(
+                        "Transferred rows {} in transaction {}",
+                        row_range(&transaction.rows),
+                        result.transaction_hash
+                    );
+                } else {
+                    eprintln!(
+                        "Transfer of rows {} failed, not submitting the remaining rows",
+                        row_range(&transaction.rows)
+                    );
+                    failed = true;
+                }
+                transaction.result = Some(result);
+            }
+            transactions.push(transaction);
+        }
+
+        Ok(TransferBatchSummary {
+            num_recipients: rows.len(),
+            total_amount,
+            transactions,
+        })
+    }
+}
+
+impl TransferBatch {
+    /// Reads and validates all rows of the CSV file, reporting every invalid row at once
+    fn read_rows(&self) -> CliTypedResult<Vec<TransferRow>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_path(&self.csv)
+            .map_err(|err| {
+                CliError::UnableToReadFile(self.csv.display().to_string(), err.to_string())
+            })?;
+
+        let mut rows = vec![];
+        let mut errors = vec![];
+        let mut first_rows: HashMap<AccountAddress, usize> = HashMap::new();
+        for (index, record) in reader.records().enumerate() {
+            let row = index + 1;
+            let record = record.map_err(|err| {
+                CliError::UnableToParse("CSV file", format!("row {}: {}", row, err))
+            })?;
+            if row == 1 && record.get(0) == Some("address") {
+                continue;
+            }
+            if record.len() != 2 {
+                errors.push(format!("row {}: expected address and amount", row));
+                continue;
+            }
+
+            let recipient = match AccountAddress::from_str(&record[0]) {
+                Ok(recipient) => recipient,
+                Err(err) => {
+                    errors.push(format!("row {}: invalid address: {}", row, err));
+                    continue;
+                },
+            };
+            let amount = match record[1].parse::<u64>() {
+                Ok(0) => {
+                    errors.push(format!("row {}: amount must be positive", row));
+                    continue;
+                },
+                Ok(amount) => amount,
+                Err(err) => {
+                    errors.push(format!("row {}: invalid amount: {}", row, err));
+                    continue;
+                },
+            };
+            if let Some(first_row) = first_rows.insert(recipient, row) {
+                errors.push(format!(
+                    "row {}: recipient {} is already paid in row {}",
+                    row, recipient, first_row
+                ));
+                continue;
+            }
+
+            rows.push(TransferRow {
+                row,
+                recipient,
+                amount,
+            });
+        }
+
+        if !errors.is_empty() {
+            return Err(CliError::CommandArgumentError(format!(
+                "Invalid rows in {}:\n{}",
+                self.csv.display(),
+                errors.join("\n")
+            )));
+        }
+        if rows.is_empty() {
+            return Err(CliError::CommandArgumentError(format!(
+                "No recipients in {}",
+                self.csv.display()
+            )));
+        }
+        Ok(rows)
+    }
+}
+
+/// A single transfer, or a batch transfer for multiple rows
+fn transfer_payload(rows: &[TransferRow]) -> TransactionPayload {
+    if let [row] = rows {
+        aptos_stdlib::aptos_account_transfer(row.recipient, row.amount)
+    } else {
+        aptos_stdlib::aptos_account_batch_transfer(
+            rows.iter().map(|row| row.recipient).collect(),
+            rows.iter().map(|row| row.amount).collect(),
+        )
+    }
+}
+
+/// The range of consecutive rows, for reporting progress
+fn row_range(rows: &[usize]) -> String {
+    match (rows.first(), rows.last()) {
+        (Some(first), Some(last)) if first != last => format!("{}-{}", first, last),
+        (Some(first), _) => first.to_string(),
+        _ => String::new(),
+    }
+}
+
 const SUPPORTED_COINS: [&str; 1] = ["0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"];
 
 /// A shortened transaction output
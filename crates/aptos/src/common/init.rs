@@ -4,25 +4,33 @@
 use crate::{
     account::key_rotation::lookup_address,
     common::{
+        keyless::KeylessOptions,
         types::{
-            account_address_from_public_key, CliCommand, CliConfig, CliError, CliTypedResult,
-            ConfigSearchMode, EncodingOptions, HardwareWalletOptions, PrivateKeyInputOptions,
-            ProfileConfig, ProfileOptions, PromptOptions, RngArgs, DEFAULT_PROFILE,
+            account_address_from_public_key, derivation_path_from_index,
+            derive_private_key_from_mnemonic, CliCommand, CliConfig, CliError, CliTypedResult,
+            ConfigSearchMode, EncodingOptions, HardwareWalletOptions, NetworkConfig,
+            PrivateKeyInputOptions, ProfileConfig, ProfileOptions, ProfileSummary, PromptOptions,
+            RestOptions, RngArgs, TransactionOptions, TransactionSummary, DEFAULT_PROFILE,
         },
-        utils::{fund_account, prompt_yes_with_override, read_line},
+        utils::{fund_account, prompt_yes_with_override, read_line, read_mnemonic},
     },
 };
+use aptos_api_types::ViewFunction;
+use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, ValidCryptoMaterialStringExt};
 use aptos_ledger;
 use aptos_rest_client::{
     aptos_api_types::{AptosError, AptosErrorCode},
     error::{AptosErrorResponse, RestError},
+    AptosBaseUrl, Client, RetryPolicy,
 };
+use aptos_types::{account_address::AccountAddress, chain_id::NamedChain};
 use async_trait::async_trait;
 use clap::Parser;
+use move_core_types::{ident_str, language_storage::ModuleId};
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, str::FromStr};
+use std::{collections::BTreeMap, fmt::Formatter, str::FromStr};
 
 /// 1 APT (might not actually get that much, depending on the faucet)
 const NUM_DEFAULT_OCTAS: u64 = 100000000;
@@ -30,20 +38,26 @@ const NUM_DEFAULT_OCTAS: u64 = 100000000;
 /// Tool to initialize current directory for the aptos tool
 ///
 /// Configuration will be pushed into .aptos/config.yaml
+///
+/// To initialize without prompts (e.g. in CI), pass `--assume-yes`.  Any value that isn't
+/// provided is then defaulted: devnet for the network, and a newly generated private key
+/// (or the existing one of the profile).
 #[derive(Debug, Parser)]
 pub struct InitTool {
     /// Network to use for default settings
     ///
-    /// If custom `rest_url` and `faucet_url` are wanted, use `custom`
-    #[clap(long)]
-    pub network: Option<Network>,
+    /// One of [devnet, testnet, mainnet, local, custom], or the name of a network added
+    /// with `aptos config network add`.  If custom `rest_url` and `faucet_url` are wanted,
+    /// use `custom`
+    #[clap(long, env = "APTOS_NETWORK")]
+    pub network: Option<String>,
 
     /// URL to a fullnode on the network
-    #[clap(long)]
+    #[clap(long, env = "APTOS_REST_URL")]
     pub rest_url: Option<Url>,
 
     /// URL for the Faucet endpoint
-    #[clap(long)]
+    #[clap(long, env = "APTOS_FAUCET_URL")]
     pub faucet_url: Option<Url>,
 
     /// Auth token, if we're using the faucet. This is only used this time, we don't
@@ -55,6 +69,13 @@ pub struct InitTool {
     #[clap(long)]
     pub skip_faucet: bool,
 
+    /// Profile to fund the new account from, if there is no faucet
+    ///
+    /// The account is created by transferring coins from the account of the profile, e.g. on a
+    /// private network without a faucet
+    #[clap(long)]
+    pub funder_profile: Option<String>,
+
     /// Whether you want to create a profile from your ledger account
     ///
     /// Make sure that you have your Ledger device connected and unlocked, with the Aptos app installed and opened.
@@ -62,8 +83,33 @@ pub struct InitTool {
     #[clap(long)]
     pub ledger: bool,
 
+    /// Whether you want to create a profile from a BIP-39 mnemonic phrase
+    ///
+    /// The phrase is read from stdin, and the private key is derived along `--derivation-path`
+    /// or `--derivation-index` (defaults to the first account `m/44'/637'/0'/0'/0'`).  This is
+    /// the same account as in a wallet using the phrase.
+    #[clap(long, conflicts_with_all = &["ledger", "private_key_input"])]
+    pub from_mnemonic: bool,
+
+    /// Whether you want to create a profile for a keyless account
+    ///
+    /// Instead of a private key, the account is derived from a login with an OpenID provider
+    /// (e.g. Google) in the browser.  An ephemeral key, which the login authorizes, is stored
+    /// in the profile until it expires.
+    #[clap(long, conflicts_with_all = &["ledger", "from_mnemonic", "private_key_input"])]
+    pub keyless: bool,
+
+    /// Address of a multisig account that the account is an owner of
+    ///
+    /// Transactions submitted with the profile are then proposed to the multisig account,
+    /// and can be approved and executed with `aptos multisig`
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub multisig_address: Option<AccountAddress>,
+
     #[clap(flatten)]
     pub(crate) hardware_wallet_options: HardwareWalletOptions,
+    #[clap(flatten)]
+    pub(crate) keyless_options: KeylessOptions,
 
     #[clap(flatten)]
     pub rng_args: RngArgs,
@@ -77,13 +123,26 @@ pub struct InitTool {
     pub(crate) encoding_options: EncodingOptions,
 }
 
+/// The profile set up by `aptos init`
+#[derive(Debug, Serialize)]
+pub struct InitResult {
+    pub profile: String,
+    pub network: String,
+    #[serde(flatten)]
+    pub summary: ProfileSummary,
+    /// Whether a new private key was generated for the profile
+    pub key_generated: bool,
+    /// Whether the account was created and funded by `aptos init`
+    pub funded: bool,
+}
+
 #[async_trait]
-impl CliCommand<()> for InitTool {
+impl CliCommand<InitResult> for InitTool {
     fn command_name(&self) -> &'static str {
         "AptosInit"
     }
 
-    async fn execute(self) -> CliTypedResult<()> {
+    async fn execute(self) -> CliTypedResult<InitResult> {
         let mut config = if CliConfig::config_exists(ConfigSearchMode::CurrentDir) {
             CliConfig::load(ConfigSearchMode::CurrentDir)?
         } else {
@@ -105,53 +164,56 @@ impl CliCommand<()> for InitTool {
         eprintln!("Configuring for profile {}", profile_name);
 
         // Choose a network
-        let network = if let Some(network) = self.network {
-            eprintln!("Configuring for network {:?}", network);
-            network
+        let network_name = if let Some(ref network) = self.network {
+            eprintln!("Configuring for network {}", network);
+            network.clone()
+        } else if self.is_non_interactive() {
+            eprintln!("No network given, using devnet...");
+            Network::Devnet.to_string()
         } else {
             eprintln!(
-                "Choose network from [devnet, testnet, mainnet, local, custom | defaults to devnet]"
+                "Choose network from [devnet, testnet, mainnet, local, custom{} | defaults to devnet]",
+                config
+                    .networks
+                    .iter()
+                    .flat_map(|networks| networks.keys())
+                    .map(|name| format!(", {}", name))
+                    .collect::<String>()
             );
             let input = read_line("network")?;
             let input = input.trim();
             if input.is_empty() {
                 eprintln!("No network given, using devnet...");
-                Network::Devnet
+                Network::Devnet.to_string()
             } else {
-                Network::from_str(input)?
+                input.to_string()
             }
         };
 
         // Ensure that there is at least a REST URL set for the network
-        match network {
-            Network::Mainnet => {
-                profile_config.rest_url =
-                    Some("https://fullnode.mainnet.aptoslabs.com".to_string());
-                profile_config.faucet_url = None;
-            },
-            Network::Testnet => {
-                profile_config.rest_url =
-                    Some("https://fullnode.testnet.aptoslabs.com".to_string());
-                profile_config.faucet_url =
-                    Some("https://faucet.testnet.aptoslabs.com".to_string());
-            },
-            Network::Devnet => {
-                profile_config.rest_url = Some("https://fullnode.devnet.aptoslabs.com".to_string());
-                profile_config.faucet_url = Some("https://faucet.devnet.aptoslabs.com".to_string());
-            },
-            Network::Local => {
-                profile_config.rest_url = Some("http://localhost:8080".to_string());
-                profile_config.faucet_url = Some("http://localhost:8081".to_string());
-            },
-            Network::Custom => self.custom_network(&mut profile_config)?,
-        }
+        let (network, network_config) =
+            if Network::from_str(&network_name).ok() == Some(Network::Custom) {
+                self.custom_network(&mut profile_config)?;
+                (Network::Custom, None)
+            } else {
+                let (network, network_config) = config.network_config(&network_name)?;
+                profile_config.rest_url = Some(network_config.rest_url.clone());
+                profile_config.faucet_url = network_config.faucet_url.clone();
+                (network, Some(network_config))
+            };
 
         // Check if any ledger flag is set
-        let derivation_path = if let Some(deri_path) =
-            self.hardware_wallet_options.extract_derivation_path()?
-        {
+        let derivation_path = if !self.is_hardware_wallet() {
+            None
+        } else if let Some(deri_path) = self.hardware_wallet_options.extract_derivation_path()? {
             Some(deri_path)
         } else if self.ledger {
+            if self.is_non_interactive() {
+                return Err(CliError::CommandArgumentError(
+                    "--derivation-index or --derivation-path must be provided with --ledger when running without prompts".to_string(),
+                ));
+            }
+
             // Fetch the top 5 (index 0-4) accounts from Ledger
             let account_map = aptos_ledger::fetch_batch_accounts(Some(0..5))?;
             eprintln!(
@@ -185,17 +247,71 @@ impl CliCommand<()> for InitTool {
         // Set the derivation_path to the one user chose
         profile_config.derivation_path = derivation_path.clone();
 
+        let rest_url = Url::parse(
+            profile_config
+                .rest_url
+                .as_ref()
+                .expect("Must have rest client as created above"),
+        )
+        .map_err(|err| CliError::UnableToParse("rest_url", err.to_string()))?;
+        // Reads are retried, so that a flaky connection doesn't fail the checks below
+        let client = Client::builder(AptosBaseUrl::Custom(rest_url))
+            .retry_policy(RetryPolicy::default())
+            .build();
+
+        // Catch mixed up endpoints early, e.g. a testnet url registered as devnet
+        if let Some(chain_id) = network_config.and_then(|network_config| network_config.chain_id) {
+            let actual_chain_id = client.get_ledger_information().await?.into_inner().chain_id;
+            if actual_chain_id != chain_id {
+                return Err(CliError::UnexpectedError(format!(
+                    "Network {} is expected to have chain id {}, but the rest endpoint has chain id {}",
+                    network_name, chain_id, actual_chain_id
+                )));
+            }
+        }
+
+        // A keyless account has no private key of its own, only the ephemeral one of the login
+        let keyless = if self.keyless {
+            Some(self.keyless_options.login(network, &client).await?)
+        } else {
+            None
+        };
+
         // Private key
+        let mut key_generated = false;
         let private_key = if self.is_hardware_wallet() {
             // Private key stays in ledger
             None
+        } else if keyless.is_some() {
+            None
         } else {
-            let ed25519_private_key = if let Some(key) = self
+            let ed25519_private_key = if self.from_mnemonic {
+                let derivation_path = self
+                    .hardware_wallet_options
+                    .extract_derivation_path()?
+                    .unwrap_or_else(|| derivation_path_from_index(0));
+                eprintln!(
+                    "Deriving private key from mnemonic phrase with derivation path {}",
+                    derivation_path
+                );
+                derive_private_key_from_mnemonic(&read_mnemonic()?, &derivation_path)?
+            } else if let Some(key) = self
                 .private_key_options
                 .extract_private_key_cli(self.encoding_options.encoding)?
             {
                 eprintln!("Using command line argument for private key");
                 key
+            } else if self.is_non_interactive() {
+                if let Some(key) = profile_config.private_key {
+                    eprintln!("No key given, keeping existing key...");
+                    key
+                } else {
+                    eprintln!("No key given, generating key...");
+                    key_generated = true;
+                    self.rng_args
+                        .key_generator()?
+                        .generate_ed25519_private_key()
+                }
             } else {
                 eprintln!("Enter your private key as a hex literal (0x...) [Current: {} | No input: Generate new key (or keep one if present)]", profile_config.private_key.as_ref().map(|_| "Redacted").unwrap_or("None"));
                 let input = read_line("Private key")?;
@@ -206,6 +322,7 @@ impl CliCommand<()> for InitTool {
                         key
                     } else {
                         eprintln!("No key given, generating key...");
+                        key_generated = true;
                         self.rng_args
                             .key_generator()?
                             .generate_ed25519_private_key()
@@ -238,28 +355,28 @@ impl CliCommand<()> for InitTool {
                     )))
                 },
             };
-            pub_key
+            Some(pub_key)
         } else {
-            private_key.clone().unwrap().public_key()
+            private_key.as_ref().map(|key| key.public_key())
         };
 
-        let rest_url = Url::parse(
-            profile_config
-                .rest_url
-                .as_ref()
-                .expect("Must have rest client as created above"),
-        )
-        .map_err(|err| CliError::UnableToParse("rest_url", err.to_string()))?;
-        let client = aptos_rest_client::Client::new(rest_url);
-
-        // lookup the address from onchain instead of deriving it
-        // if this is the rotated key, deriving it will outputs an incorrect address
-        let derived_address = account_address_from_public_key(&public_key);
-        let address = lookup_address(&client, derived_address, false).await?;
+        let address = if let Some(ref keyless) = keyless {
+            keyless.account_address()?
+        } else {
+            // lookup the address from onchain instead of deriving it
+            // if this is the rotated key, deriving it will outputs an incorrect address
+            let derived_address = account_address_from_public_key(
+                public_key
+                    .as_ref()
+                    .expect("Must have a public key, as the account isn't keyless"),
+            );
+            lookup_address(&client, derived_address, false).await?
+        };
 
         profile_config.private_key = private_key;
-        profile_config.public_key = Some(public_key);
+        profile_config.public_key = public_key;
         profile_config.account = Some(address);
+        profile_config.keyless = keyless;
 
         // Create account if it doesn't exist (and there's a faucet)
         // Check if account exists
@@ -300,6 +417,7 @@ impl CliCommand<()> for InitTool {
             profile_config.faucet_url.as_ref()
         };
 
+        let mut funded = false;
         if let Some(faucet_url) = maybe_faucet_url {
             if account_exists {
                 eprintln!("Account {} has been already found onchain", address);
@@ -317,16 +435,57 @@ impl CliCommand<()> for InitTool {
                     NUM_DEFAULT_OCTAS,
                 )
                 .await?;
+                funded = true;
                 eprintln!("Account {} funded successfully", address);
             }
         } else if account_exists {
             eprintln!("Account {} has been already found onchain", address);
+        } else if let Some(ref funder_profile) = self.funder_profile {
+            eprintln!(
+                "Account {} doesn't exist, creating it and funding it with {} Octas from profile {}",
+                address, NUM_DEFAULT_OCTAS, funder_profile
+            );
+            self.fund_from_profile(funder_profile, &profile_config, address)
+                .await?;
+            funded = true;
+            eprintln!("Account {} funded successfully", address);
         } else if network == Network::Mainnet {
             eprintln!("Account {} does not exist, you will need to create and fund the account by transferring funds from another account", address);
         } else {
             eprintln!("Account {} has been initialized locally, but you must transfer coins to it to create the account onchain", address);
         }
 
+        // Only owners can propose transactions to the multisig account
+        if let Some(multisig_address) = self.multisig_address {
+            let is_owner = client
+                .view_bcs_with_json_response(
+                    &ViewFunction {
+                        module: ModuleId::new(
+                            AccountAddress::ONE,
+                            ident_str!("multisig_account").to_owned(),
+                        ),
+                        function: ident_str!("is_owner").to_owned(),
+                        ty_args: vec![],
+                        args: vec![bcs::to_bytes(&address)?, bcs::to_bytes(&multisig_address)?],
+                    },
+                    None,
+                )
+                .await?
+                .into_inner();
+            if is_owner.first().and_then(|is_owner| is_owner.as_bool()) != Some(true) {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Account {} is not an owner of multisig account {}",
+                    address, multisig_address
+                )));
+            }
+            eprintln!(
+                "Transactions will be proposed to multisig account {}",
+                multisig_address
+            );
+        }
+        profile_config.multisig_address = self.multisig_address;
+        let summary = ProfileSummary::from(&profile_config);
+
         // Ensure the loaded config has profiles setup for a possible empty file
         if config.profiles.is_none() {
             config.profiles = Some(BTreeMap::new());
@@ -337,18 +496,72 @@ impl CliCommand<()> for InitTool {
             .expect("Must have profiles, as created above")
             .insert(profile_name.to_string(), profile_config);
         config.save()?;
-        eprintln!("\n---\nAptos CLI is now set up for account {} as profile {}!  Run `aptos --help` for more information about commands", address, self.profile_options.profile_name().unwrap_or(DEFAULT_PROFILE));
-        Ok(())
+        eprintln!("\n---\nAptos CLI is now set up for account {} as profile {}!  Run `aptos --help` for more information about commands", address, profile_name);
+        Ok(InitResult {
+            profile: profile_name.to_string(),
+            network: network_name,
+            summary,
+            key_generated,
+            funded,
+        })
     }
 }
 
 impl InitTool {
+    /// Creates the account by transferring coins from the account of another profile
+    async fn fund_from_profile(
+        &self,
+        funder_profile: &str,
+        profile_config: &ProfileConfig,
+        address: AccountAddress,
+    ) -> CliTypedResult<()> {
+        // The transfer must happen on the network of the new profile, whatever the funder uses
+        let rest_url = profile_config
+            .rest_url
+            .as_deref()
+            .map(Url::parse)
+            .transpose()
+            .map_err(|err| CliError::UnableToParse("rest_url", err.to_string()))?;
+        let txn_options = TransactionOptions {
+            profile_options: ProfileOptions {
+                profile: Some(funder_profile.to_string()),
+            },
+            rest_options: RestOptions::new(rest_url, None),
+            prompt_options: self.prompt_options,
+            ..Default::default()
+        };
+        let transaction = txn_options
+            .submit_transaction_as_sender(aptos_stdlib::aptos_account_transfer(
+                address,
+                NUM_DEFAULT_OCTAS,
+            ))
+            .await?;
+        if TransactionSummary::from(&transaction).success == Some(true) {
+            Ok(())
+        } else {
+            Err(CliError::ApiError(format!(
+                "Failed to fund account {} from profile {}",
+                address, funder_profile
+            )))
+        }
+    }
+
     /// Custom network created, which requires a REST URL
     fn custom_network(&self, profile_config: &mut ProfileConfig) -> CliTypedResult<()> {
         // Rest Endpoint
         let rest_url = if let Some(ref rest_url) = self.rest_url {
             eprintln!("Using command line argument for rest URL {}", rest_url);
             Some(rest_url.to_string())
+        } else if self.is_non_interactive() {
+            if let Some(current) = profile_config.rest_url.as_deref() {
+                eprintln!("No rest url given, keeping the existing url...");
+                Some(current.to_string())
+            } else {
+                return Err(CliError::CommandArgumentError(
+                    "--rest-url must be provided for a custom network when running without prompts"
+                        .to_string(),
+                ));
+            }
         } else {
             let current = profile_config.rest_url.as_deref();
             eprintln!(
@@ -382,6 +595,13 @@ impl InitTool {
         } else if let Some(ref faucet_url) = self.faucet_url {
             eprintln!("Using command line argument for faucet URL {}", faucet_url);
             Some(faucet_url.to_string())
+        } else if self.is_non_interactive() {
+            if profile_config.faucet_url.is_some() {
+                eprintln!("No faucet url given, keeping the existing url...");
+            } else {
+                eprintln!("No faucet url given, skipping faucet...");
+            }
+            profile_config.faucet_url.clone()
         } else {
             let current = profile_config.faucet_url.as_deref();
             eprintln!(
@@ -415,7 +635,13 @@ impl InitTool {
     }
 
     fn is_hardware_wallet(&self) -> bool {
-        self.hardware_wallet_options.is_hardware_wallet() || self.ledger
+        // With a mnemonic, the derivation path is only used to derive the private key
+        self.ledger || (!self.from_mnemonic && self.hardware_wallet_options.is_hardware_wallet())
+    }
+
+    /// Whether to use defaults instead of prompting, so that init can run without a TTY
+    fn is_non_interactive(&self) -> bool {
+        self.prompt_options.assume_yes
     }
 }
 
@@ -431,6 +657,50 @@ pub enum Network {
     Custom,
 }
 
+impl Network {
+    /// The built-in endpoints of the network, `None` for a custom network
+    pub fn preset(&self) -> Option<NetworkConfig> {
+        let (rest_url, faucet_url, chain_id) = match self {
+            Network::Mainnet => (
+                "https://fullnode.mainnet.aptoslabs.com",
+                None,
+                Some(NamedChain::MAINNET),
+            ),
+            Network::Testnet => (
+                "https://fullnode.testnet.aptoslabs.com",
+                Some("https://faucet.testnet.aptoslabs.com"),
+                Some(NamedChain::TESTNET),
+            ),
+            // Devnet is reset regularly with a new chain id, and local networks may use any
+            Network::Devnet => (
+                "https://fullnode.devnet.aptoslabs.com",
+                Some("https://faucet.devnet.aptoslabs.com"),
+                None,
+            ),
+            Network::Local => ("http://localhost:8080", Some("http://localhost:8081"), None),
+            Network::Custom => return None,
+        };
+
+        Some(NetworkConfig {
+            rest_url: rest_url.to_string(),
+            faucet_url: faucet_url.map(|url| url.to_string()),
+            chain_id: chain_id.map(|chain| chain as u8),
+        })
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+            Network::Devnet => "devnet",
+            Network::Local => "local",
+            Network::Custom => "custom",
+        })
+    }
+}
+
 impl FromStr for Network {
     type Err = CliError;
 
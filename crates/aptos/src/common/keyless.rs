@@ -0,0 +1,347 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Setting up keyless accounts, which are derived from an OpenID Connect login instead of a
+//! private key
+
+use crate::common::{
+    init::Network,
+    types::{CliError, CliTypedResult},
+};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey};
+use aptos_keygen::KeyGen;
+use aptos_keyless_pepper_client::{PepperClient, RestPepperClient};
+use aptos_keyless_pepper_common::{jwt, PepperRequest, PepperResponse};
+use aptos_rest_client::Client;
+use aptos_types::{
+    keyless::{Configuration, IdCommitment, KeylessPublicKey, OpenIdSig, Pepper},
+    transaction::authenticator::{AnyPublicKey, AuthenticationKey, EphemeralPublicKey},
+};
+use clap::Parser;
+use move_core_types::{
+    account_address::AccountAddress, language_storage::CORE_CODE_ADDRESS,
+    move_resource::MoveStructType,
+};
+use rand::{rngs::OsRng, RngCore};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+/// Google, which most keyless accounts are created with
+const DEFAULT_OIDC_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const DEFAULT_CALLBACK_PORT: u16 = 8765;
+const DEFAULT_EPHEMERAL_KEY_EXPIRY_SECS: u64 = 24 * 60 * 60;
+/// How long to wait for the login in the browser
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// The JWT field identifying the user, which the account address is derived from
+const UID_KEY: &str = "sub";
+
+/// Options for setting up a keyless account with `aptos init --keyless`
+#[derive(Debug, Default, Parser)]
+pub struct KeylessOptions {
+    /// Authorization endpoint of the OpenID provider to log in with
+    ///
+    /// Defaults to Google
+    #[clap(long)]
+    pub oidc_auth_url: Option<Url>,
+
+    /// OAuth client ID registered with the OpenID provider
+    ///
+    /// The client must allow `http://127.0.0.1:<callback-port>/callback` as a redirect URI.
+    /// The account address is derived from the client ID, so the same one must be used to
+    /// access the account again.
+    #[clap(long, env = "APTOS_KEYLESS_CLIENT_ID")]
+    pub oidc_client_id: Option<String>,
+
+    /// Port of the localhost callback that the OpenID provider redirects the login to
+    ///
+    /// Defaults to 8765
+    #[clap(long)]
+    pub callback_port: Option<u16>,
+
+    /// URL of the pepper service, which the account address is derived with
+    ///
+    /// Defaults to the Aptos Labs pepper service of devnet, testnet and mainnet
+    #[clap(long, env = "APTOS_PEPPER_SERVICE_URL")]
+    pub pepper_service_url: Option<Url>,
+
+    /// Number of seconds the ephemeral key is valid for, after which `aptos init --keyless`
+    /// has to be run again
+    ///
+    /// Defaults to a day
+    #[clap(long)]
+    pub ephemeral_key_expiry_secs: Option<u64>,
+}
+
+/// The keyless account of a profile
+///
+/// The ephemeral private key signs for the account until it expires, together with the JWT
+/// of the login that committed to it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeylessConfig {
+    /// JWT of the OpenID login, whose nonce commits to the ephemeral key
+    pub jwt: String,
+    pub ephemeral_private_key: Ed25519PrivateKey,
+    /// Unix timestamp after which the ephemeral key can no longer sign
+    pub expiry_date_secs: u64,
+    /// Randomness that hides the ephemeral key in the nonce
+    #[serde(with = "hex")]
+    pub blinder: Vec<u8>,
+    /// Pepper from the pepper service, which hides the user's identity in the address
+    #[serde(with = "hex")]
+    pub pepper: Vec<u8>,
+}
+
+impl KeylessConfig {
+    /// Derives the address of the keyless account, from the identity in the JWT and the pepper
+    pub fn account_address(&self) -> CliTypedResult<AccountAddress> {
+        let claims = jwt::parse(&self.jwt)
+            .map_err(|err| CliError::UnableToParse("JWT", err.to_string()))?
+            .claims;
+        let pepper = Pepper::new(self.pepper.as_slice().try_into().map_err(|_| {
+            CliError::UnableToParse("pepper", format!("Expected {} bytes", Pepper::NUM_BYTES))
+        })?);
+        let idc = IdCommitment::new_from_preimage(&pepper, &claims.aud, UID_KEY, &claims.sub)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        let public_key = KeylessPublicKey {
+            iss_val: claims.iss,
+            idc,
+        };
+        Ok(AuthenticationKey::any_key(AnyPublicKey::keyless(public_key)).account_address())
+    }
+}
+
+impl KeylessOptions {
+    /// Logs in with the OpenID provider in the browser, and fetches the pepper of the account
+    pub async fn login(&self, network: Network, client: &Client) -> CliTypedResult<KeylessConfig> {
+        let client_id = self.oidc_client_id.as_deref().ok_or_else(|| {
+            CliError::CommandArgumentError(
+                "--oidc-client-id must be provided with --keyless".to_string(),
+            )
+        })?;
+        let pepper_service_url = self.pepper_service_url(network)?;
+        let configuration = fetch_configuration(client).await?;
+
+        // The nonce of the login commits to the ephemeral key, which authorizes it to sign
+        let ephemeral_private_key = KeyGen::from_os_rng().generate_ed25519_private_key();
+        let mut blinder = vec![0u8; OpenIdSig::EPK_BLINDER_NUM_BYTES];
+        OsRng.fill_bytes(&mut blinder);
+        let expiry_date_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+            .as_secs()
+            + self
+                .ephemeral_key_expiry_secs
+                .unwrap_or(DEFAULT_EPHEMERAL_KEY_EXPIRY_SECS);
+        let epk = EphemeralPublicKey::ed25519(ephemeral_private_key.public_key());
+        let nonce =
+            OpenIdSig::reconstruct_oauth_nonce(&blinder, expiry_date_secs, &epk, &configuration)
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+
+        let port = self.callback_port.unwrap_or(DEFAULT_CALLBACK_PORT);
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .await
+            .map_err(|err| CliError::IO(format!("Callback port {}", port), err))?;
+        let auth_url = self.auth_url(client_id, port, &nonce)?;
+        eprintln!(
+            "Log in with the browser to continue.  If it doesn't open, go to:\n{}",
+            auth_url
+        );
+        open_browser(&auth_url);
+        let jwt = tokio::time::timeout(LOGIN_TIMEOUT, receive_jwt(&listener))
+            .await
+            .map_err(|_| {
+                CliError::UnexpectedError("Timed out waiting for the login".to_string())
+            })??;
+
+        // Make sure the login is the one for this ephemeral key, not a stale one
+        let claims = jwt::parse(&jwt)
+            .map_err(|err| CliError::UnableToParse("JWT", err.to_string()))?
+            .claims;
+        if claims.nonce != nonce {
+            return Err(CliError::UnexpectedError(
+                "The nonce of the login doesn't match, please try again".to_string(),
+            ));
+        }
+
+        let pepper_request = PepperRequest {
+            jwt: jwt.clone(),
+            epk,
+            exp_date_secs: expiry_date_secs,
+            epk_blinder: blinder.clone(),
+            uid_key: None,
+            derivation_path: None,
+        };
+        let PepperResponse { pepper, .. } = RestPepperClient::new(vec![pepper_service_url])
+            .fetch_pepper(&pepper_request)
+            .await
+            .map_err(|err| CliError::ApiError(format!("Pepper service issue: {}", err)))?;
+
+        Ok(KeylessConfig {
+            jwt,
+            ephemeral_private_key,
+            expiry_date_secs,
+            blinder,
+            pepper,
+        })
+    }
+
+    fn pepper_service_url(&self, network: Network) -> CliTypedResult<Url> {
+        let url = match (&self.pepper_service_url, network) {
+            (Some(url), _) => url.to_string(),
+            (None, Network::Mainnet) => {
+                "https://api.mainnet.aptoslabs.com/keyless/pepper/v0".to_string()
+            },
+            (None, Network::Testnet) => {
+                "https://api.testnet.aptoslabs.com/keyless/pepper/v0".to_string()
+            },
+            (None, Network::Devnet) => {
+                "https://api.devnet.aptoslabs.com/keyless/pepper/v0".to_string()
+            },
+            (None, _) => {
+                return Err(CliError::CommandArgumentError(format!(
+                    "--pepper-service-url must be provided with --keyless for network {}",
+                    network
+                )))
+            },
+        };
+        Url::parse(&url)
+            .map_err(|err| CliError::UnableToParse("pepper_service_url", err.to_string()))
+    }
+
+    fn auth_url(&self, client_id: &str, port: u16, nonce: &str) -> CliTypedResult<Url> {
+        let mut url = match self.oidc_auth_url {
+            Some(ref url) => url.clone(),
+            None => Url::parse(DEFAULT_OIDC_AUTH_URL)
+                .map_err(|err| CliError::UnableToParse("oidc_auth_url", err.to_string()))?,
+        };
+        // The ID token is posted to the callback, as a fragment would never reach it
+        url.query_pairs_mut()
+            .append_pair("client_id", client_id)
+            .append_pair(
+                "redirect_uri",
+                &format!("http://127.0.0.1:{}/callback", port),
+            )
+            .append_pair("response_type", "id_token")
+            .append_pair("response_mode", "form_post")
+            .append_pair("scope", "openid")
+            .append_pair("nonce", nonce);
+        Ok(url)
+    }
+}
+
+/// Fetches the keyless configuration of the network the profile is for, which the nonce of the
+/// login has to be computed with
+async fn fetch_configuration(client: &Client) -> CliTypedResult<Configuration> {
+    let configuration = client
+        .get_account_resource_bcs::<Configuration>(
+            CORE_CODE_ADDRESS,
+            &Configuration::struct_tag().to_canonical_string(),
+        )
+        .await
+        .map_err(|err| {
+            CliError::ApiError(format!(
+                "Failed to fetch the keyless configuration of the network: {}",
+                err
+            ))
+        })?
+        .into_inner();
+    Ok(configuration)
+}
+
+/// Opens the URL in the default browser, on a best effort basis
+fn open_browser(url: &Url) {
+    let command = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    if let Err(err) = std::process::Command::new(command)
+        .arg(url.as_str())
+        .spawn()
+    {
+        eprintln!("Failed to open the browser: {}", err);
+    }
+}
+
+/// Waits for the OpenID provider to post the ID token to the callback
+async fn receive_jwt(listener: &TcpListener) -> CliTypedResult<String> {
+    loop {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .map_err(|err| CliError::IO("Callback".to_string(), err))?;
+        // Anything else, e.g. the browser asking for a favicon, is ignored
+        let body = match read_request_body(&mut stream).await {
+            Ok(Some(body)) => body,
+            Ok(None) | Err(_) => continue,
+        };
+
+        let mut id_token = None;
+        let mut error = None;
+        for (key, value) in url::form_urlencoded::parse(body.as_bytes()) {
+            match key.as_ref() {
+                "id_token" => id_token = Some(value.into_owned()),
+                "error" => error = Some(value.into_owned()),
+                _ => (),
+            }
+        }
+
+        let message = if id_token.is_some() {
+            "Logged in, you can close this window and return to the Aptos CLI"
+        } else {
+            "Failed to log in, see the Aptos CLI for details"
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            message.len(),
+            message
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+
+        match (id_token, error) {
+            (Some(id_token), _) => return Ok(id_token),
+            (None, Some(error)) => {
+                return Err(CliError::UnexpectedError(format!(
+                    "Failed to log in: {}",
+                    error
+                )))
+            },
+            (None, None) => continue,
+        }
+    }
+}
+
+/// Reads the body of a POST request, or `None` for any other request
+async fn read_request_body(stream: &mut TcpStream) -> std::io::Result<Option<String>> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let is_post = request_line.starts_with("POST ");
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    if !is_post {
+        return Ok(None);
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(String::from_utf8_lossy(&body).into_owned()))
+}
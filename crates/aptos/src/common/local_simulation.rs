@@ -5,12 +5,28 @@ use crate::common::types::{CliError, CliTypedResult};
 use aptos_crypto::HashValue;
 use aptos_gas_profiling::FrameName;
 use aptos_move_debugger::aptos_debugger::AptosDebugger;
-use aptos_types::transaction::SignedTransaction;
+use aptos_resource_viewer::AptosValueAnnotator;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::CoinStoreResource,
+    fee_statement::FeeStatement,
+    on_chain_config::{FeatureFlag, Features},
+    state_store::{
+        state_key::{inner::StateKeyInner, StateKey},
+        state_storage_usage::StateStorageUsage,
+        state_value::StateValue,
+        StateView, TStateView,
+    },
+    transaction::SignedTransaction,
+    write_set::WriteOp,
+};
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
 use aptos_vm_logging::log_schema::AdapterLogSchema;
 use aptos_vm_types::{output::VMOutput, resolver::StateStorageView};
+use clap::Parser;
 use move_core_types::vm_status::VMStatus;
-use std::{path::Path, time::Instant};
+use serde::Serialize;
+use std::{collections::HashMap, path::Path, str::FromStr, time::Instant};
 
 pub fn run_transaction_using_debugger(
     debugger: &AptosDebugger,
@@ -129,8 +145,210 @@ pub fn profile_transaction_using_debugger(
     // Generate the report
     let path = Path::new("gas-profiling").join(raw_file_name);
     gas_log.generate_html_report(&path, format!("Gas Report - {}", human_readable_name))?;
+    // Also save a machine-readable version of the report, for tools to process.
+    let json_path = path.join("gas-profile.json");
+    gas_log.generate_json_report(&json_path)?;
 
     println!("Gas report saved to {}.", path.display());
+    println!(
+        "Machine-readable gas profile saved to {}.",
+        json_path.display()
+    );
 
     Ok((vm_status, vm_output))
 }
+
+/// Overrides of the state that a transaction is simulated on
+#[derive(Debug, Default, Parser)]
+pub struct StateOverrides {
+    /// APT balance to give an account for the simulation, as `<ADDRESS>=<OCTAS>`
+    ///
+    /// The account must already have an APT coin store.  Can be repeated for multiple
+    /// accounts.
+    #[clap(long = "override-balance", value_parser = parse_balance_override)]
+    pub balances: Vec<(AccountAddress, u64)>,
+
+    /// Feature flag to enable for the simulation, e.g. `PARTIAL_GOVERNANCE_VOTING`
+    #[clap(long = "enable-feature", value_parser = parse_feature_flag)]
+    pub enabled_features: Vec<FeatureFlag>,
+
+    /// Feature flag to disable for the simulation
+    #[clap(long = "disable-feature", value_parser = parse_feature_flag)]
+    pub disabled_features: Vec<FeatureFlag>,
+}
+
+fn parse_balance_override(str: &str) -> CliTypedResult<(AccountAddress, u64)> {
+    let (address, balance) = str.split_once('=').ok_or_else(|| {
+        CliError::CommandArgumentError(format!(
+            "Balance override {} must be of the form <ADDRESS>=<OCTAS>",
+            str
+        ))
+    })?;
+    let address = AccountAddress::from_str(address)
+        .map_err(|err| CliError::UnableToParse("address", err.to_string()))?;
+    let balance = balance.parse().map_err(|err: std::num::ParseIntError| {
+        CliError::UnableToParse("balance", err.to_string())
+    })?;
+    Ok((address, balance))
+}
+
+fn parse_feature_flag(str: &str) -> CliTypedResult<FeatureFlag> {
+    FeatureFlag::from_str(str)
+        .map_err(|_| CliError::UnableToParse("feature flag", format!("Unknown feature {}", str)))
+}
+
+impl StateOverrides {
+    /// Overrides the values of the base state view
+    fn apply<S: StateView>(&self, base: S) -> CliTypedResult<OverriddenStateView<S>> {
+        let mut overrides = HashMap::new();
+        for (address, balance) in &self.balances {
+            let key = StateKey::resource_typed::<CoinStoreResource>(address)?;
+            let value = base.get_state_value(&key)?.ok_or_else(|| {
+                CliError::CommandArgumentError(format!(
+                    "Account {} has no APT coin store to override the balance of",
+                    address
+                ))
+            })?;
+            let coin_store: CoinStoreResource = bcs::from_bytes(value.bytes())?;
+            let coin_store = CoinStoreResource::new(
+                *balance,
+                coin_store.frozen(),
+                coin_store.deposit_events().clone(),
+                coin_store.withdraw_events().clone(),
+            );
+            let value = value.map_bytes(|_| Ok(bcs::to_bytes(&coin_store)?.into()))?;
+            overrides.insert(key, value);
+        }
+
+        if !self.enabled_features.is_empty() || !self.disabled_features.is_empty() {
+            let key = StateKey::on_chain_config::<Features>()?;
+            let value = base.get_state_value(&key)?.ok_or_else(|| {
+                CliError::UnexpectedError("Features are missing from the state".to_string())
+            })?;
+            let mut features: Features = bcs::from_bytes(value.bytes())?;
+            for flag in &self.enabled_features {
+                features.enable(*flag);
+            }
+            for flag in &self.disabled_features {
+                features.disable(*flag);
+            }
+            let value = value.map_bytes(|_| Ok(bcs::to_bytes(&features)?.into()))?;
+            overrides.insert(key, value);
+        }
+
+        Ok(OverriddenStateView { base, overrides })
+    }
+}
+
+/// A state view with some of the values of another state view overridden
+struct OverriddenStateView<S> {
+    base: S,
+    overrides: HashMap<StateKey, StateValue>,
+}
+
+impl<S: StateView> TStateView for OverriddenStateView<S> {
+    type Key = StateKey;
+
+    fn get_state_value(&self, state_key: &StateKey) -> anyhow::Result<Option<StateValue>> {
+        match self.overrides.get(state_key) {
+            Some(value) => Ok(Some(value.clone())),
+            None => self.base.get_state_value(state_key),
+        }
+    }
+
+    fn get_usage(&self) -> anyhow::Result<StateStorageUsage> {
+        self.base.get_usage()
+    }
+}
+
+/// The effects of a simulated transaction
+#[derive(Debug, Serialize)]
+pub struct SimulationDetails {
+    pub fee_statement: FeeStatement,
+    pub write_set: Vec<SimulatedWrite>,
+    pub events: Vec<SimulatedEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SimulatedWrite {
+    pub state_key: String,
+    pub op: &'static str,
+    /// The new value, if it is a resource
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SimulatedEvent {
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub data: serde_json::Value,
+}
+
+/// Runs the transaction on the state with the overrides applied, returning its effects along
+/// with the output
+pub fn run_transaction_with_overrides(
+    debugger: &AptosDebugger,
+    version: u64,
+    transaction: SignedTransaction,
+    overrides: &StateOverrides,
+) -> CliTypedResult<(VMStatus, VMOutput, SimulationDetails)> {
+    let state_view = overrides.apply(debugger.state_view_at_version(version))?;
+    let resolver = state_view.as_move_resolver();
+
+    let vm = AptosVM::new(&resolver, None);
+    let log_context = AdapterLogSchema::new(resolver.id(), 0);
+
+    let (vm_status, vm_output) = vm.execute_user_transaction(&resolver, &transaction, &log_context);
+
+    // Values are decoded with the modules on chain, so they may be missing for modules
+    // published by the transaction itself
+    let annotator = AptosValueAnnotator::new(&state_view);
+    let fee_statement = *vm_output.fee_statement();
+    let transaction_output = vm_output
+        .clone()
+        .try_materialize_into_transaction_output(&resolver)
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+    let write_set = transaction_output
+        .write_set()
+        .iter()
+        .map(|(state_key, write_op)| {
+            let op = match write_op {
+                WriteOp::Creation { .. } => "creation",
+                WriteOp::Modification { .. } => "modification",
+                WriteOp::Deletion { .. } => "deletion",
+            };
+            let struct_tag = match state_key.inner() {
+                StateKeyInner::AccessPath(access_path) => access_path.get_struct_tag(),
+                _ => None,
+            };
+            let value = struct_tag
+                .zip(write_op.bytes())
+                .and_then(|(struct_tag, bytes)| annotator.view_resource(&struct_tag, bytes).ok())
+                .and_then(|value| serde_json::to_value(value).ok());
+            SimulatedWrite {
+                state_key: format!("{:?}", state_key),
+                op,
+                value,
+            }
+        })
+        .collect();
+    let events = transaction_output
+        .events()
+        .iter()
+        .map(|event| SimulatedEvent {
+            typ: event.type_tag().to_canonical_string(),
+            data: annotator
+                .view_value(event.type_tag(), event.event_data())
+                .ok()
+                .and_then(|value| serde_json::to_value(value).ok())
+                .unwrap_or_else(|| hex::encode(event.event_data()).into()),
+        })
+        .collect();
+
+    Ok((vm_status, vm_output, SimulationDetails {
+        fee_statement,
+        write_set,
+        events,
+    }))
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod init;
+pub mod keyless;
 pub mod local_simulation;
 pub mod types;
 pub mod utils;
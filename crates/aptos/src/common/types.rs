@@ -5,7 +5,8 @@ use super::utils::fund_account;
 use crate::{
     common::{
         init::Network,
-        local_simulation,
+        keyless::KeylessConfig,
+        local_simulation::{self, SimulationDetails, StateOverrides},
         utils::{
             check_if_file_exists, create_dir_if_not_exist, dir_default_to_current,
             get_account_with_state, get_auth_key, get_sequence_number, parse_json_file,
@@ -20,6 +21,7 @@ use crate::{
 };
 use anyhow::Context;
 use aptos_api_types::ViewFunction;
+use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
     encoding_type::{EncodingError, EncodingType},
@@ -41,8 +43,9 @@ use aptos_sdk::{
 use aptos_types::{
     chain_id::ChainId,
     transaction::{
-        authenticator::AuthenticationKey, EntryFunction, MultisigTransactionPayload, Script,
-        SignedTransaction, TransactionArgument, TransactionPayload, TransactionStatus,
+        authenticator::AuthenticationKey, EntryFunction, MultisigTransactionPayload,
+        RawTransaction, Script, SignedTransaction, TransactionArgument, TransactionPayload,
+        TransactionStatus,
     },
 };
 use aptos_vm_types::output::VMOutput;
@@ -61,7 +64,7 @@ use std::{
     convert::TryFrom,
     fmt::{Debug, Display, Formatter},
     fs::OpenOptions,
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
@@ -219,11 +222,21 @@ impl From<EncodingError> for CliError {
 /// Config saved to `.aptos/config.yaml`
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CliConfig {
+    /// Version of the config format, 0 for configs written before it was versioned
+    #[serde(default)]
+    pub version: u32,
     /// Map of profile configs
     #[serde(skip_serializing_if = "Option::is_none")]
     pub profiles: Option<BTreeMap<String, ProfileConfig>>,
+    /// Map of custom networks, added with `aptos config network add`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub networks: Option<BTreeMap<String, NetworkConfig>>,
 }
 
+/// The version of the config format written by this CLI.  Configs of a newer version are
+/// rejected, as they may have settings this CLI doesn't understand.
+pub const CLI_CONFIG_VERSION: u32 = 1;
+
 const CONFIG_FILE: &str = "config.yaml";
 const LEGACY_CONFIG_FILE: &str = "config.yml";
 pub const CONFIG_FOLDER: &str = ".aptos";
@@ -251,6 +264,34 @@ pub struct ProfileConfig {
     /// Derivation path index of the account on ledger
     #[serde(skip_serializing_if = "Option::is_none")]
     pub derivation_path: Option<String>,
+    /// Multisig account that the account is an owner of, to which transactions are proposed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multisig_address: Option<AccountAddress>,
+    /// Keyless account, set up with `aptos init --keyless`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyless: Option<KeylessConfig>,
+    /// Default gas unit price for transactions, set with `aptos config set-gas-defaults`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_unit_price: Option<u64>,
+    /// Default max gas for transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_gas: Option<u64>,
+    /// Default number of seconds to expire transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_secs: Option<u64>,
+}
+
+/// The endpoints of a network, used to set up profiles
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct NetworkConfig {
+    /// URL for the Aptos rest endpoint
+    pub rest_url: String,
+    /// URL for the Faucet endpoint (if applicable)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub faucet_url: Option<String>,
+    /// Chain id of the network, if it doesn't change across resets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<u8>,
 }
 
 /// ProfileConfig but without the private parts
@@ -265,6 +306,15 @@ pub struct ProfileSummary {
     pub rest_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub faucet_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multisig_address: Option<AccountAddress>,
+    pub is_keyless: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_unit_price: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_gas: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_secs: Option<u64>,
 }
 
 impl From<&ProfileConfig> for ProfileSummary {
@@ -275,6 +325,11 @@ impl From<&ProfileConfig> for ProfileSummary {
             account: config.account,
             rest_url: config.rest_url.clone(),
             faucet_url: config.faucet_url.clone(),
+            multisig_address: config.multisig_address,
+            is_keyless: config.keyless.is_some(),
+            gas_unit_price: config.gas_unit_price,
+            max_gas: config.max_gas,
+            expiration_secs: config.expiration_secs,
         }
     }
 }
@@ -282,7 +337,9 @@ impl From<&ProfileConfig> for ProfileSummary {
 impl Default for CliConfig {
     fn default() -> Self {
         CliConfig {
+            version: CLI_CONFIG_VERSION,
             profiles: Some(BTreeMap::new()),
+            networks: None,
         }
     }
 }
@@ -312,15 +369,9 @@ impl CliConfig {
         let config_file = folder.join(CONFIG_FILE);
         let old_config_file = folder.join(LEGACY_CONFIG_FILE);
         if config_file.exists() {
-            from_yaml(
-                &String::from_utf8(read_from_file(config_file.as_path())?)
-                    .map_err(CliError::from)?,
-            )
+            Self::from_file(config_file.as_path())
         } else if old_config_file.exists() {
-            from_yaml(
-                &String::from_utf8(read_from_file(old_config_file.as_path())?)
-                    .map_err(CliError::from)?,
-            )
+            Self::from_file(old_config_file.as_path())
         } else {
             Err(CliError::ConfigNotFoundError(format!(
                 "{}",
@@ -329,6 +380,23 @@ impl CliConfig {
         }
     }
 
+    /// Reads a config (or an exported one), upgrading it to the current version
+    pub fn from_file(path: &Path) -> CliTypedResult<Self> {
+        let mut config: CliConfig =
+            from_yaml(&String::from_utf8(read_from_file(path)?).map_err(CliError::from)?)?;
+        if config.version > CLI_CONFIG_VERSION {
+            return Err(CliError::UnexpectedError(format!(
+                "Config {} has version {}, but this CLI only supports up to version {}, please update the CLI",
+                path.display(),
+                config.version,
+                CLI_CONFIG_VERSION
+            )));
+        }
+        // Unversioned configs have the same format as version 1
+        config.version = CLI_CONFIG_VERSION;
+        Ok(config)
+    }
+
     pub fn load_profile(
         profile: Option<&str>,
         mode: ConfigSearchMode,
@@ -350,6 +418,26 @@ impl CliConfig {
         }
     }
 
+    /// Looks up a network by name, either a built-in one or one added to the config
+    pub fn network_config(&self, name: &str) -> CliTypedResult<(Network, NetworkConfig)> {
+        if let Ok(network) = Network::from_str(name) {
+            if let Some(network_config) = network.preset() {
+                return Ok((network, network_config));
+            }
+        }
+
+        self.networks
+            .as_ref()
+            .and_then(|networks| networks.get(name))
+            .map(|network_config| (Network::Custom, network_config.clone()))
+            .ok_or_else(|| {
+                CliError::CommandArgumentError(format!(
+                    "Network {} not found, must be one of [devnet, testnet, mainnet, local] or added with `aptos config network add`",
+                    name
+                ))
+            })
+    }
+
     pub fn remove_profile(&mut self, profile: &str) -> Option<ProfileConfig> {
         if let Some(ref mut profiles) = self.profiles {
             profiles.remove(&profile.to_string())
@@ -365,12 +453,16 @@ impl CliConfig {
         // Create if it doesn't exist
         create_dir_if_not_exist(aptos_folder.as_path())?;
 
-        // Save over previous config file
+        // Save over previous config file.  The config is written to a temporary file first and
+        // then moved into place, so that a failure can't leave a truncated config (and lose keys)
         let config_file = aptos_folder.join(CONFIG_FILE);
+        let temp_config_file = aptos_folder.join(format!("{}.tmp", CONFIG_FILE));
         let config_bytes = serde_yaml::to_string(&self).map_err(|err| {
             CliError::UnexpectedError(format!("Failed to serialize config {}", err))
         })?;
-        write_to_user_only_file(&config_file, CONFIG_FILE, config_bytes.as_bytes())?;
+        write_to_user_only_file(&temp_config_file, CONFIG_FILE, config_bytes.as_bytes())?;
+        std::fs::rename(&temp_config_file, &config_file)
+            .map_err(|err| CliError::IO(CONFIG_FILE.to_string(), err))?;
 
         // As a cleanup, delete the old if it exists
         let legacy_config_file = aptos_folder.join(LEGACY_CONFIG_FILE);
@@ -694,8 +786,7 @@ impl HardwareWalletOptions {
         if let Some(derivation_path) = &self.derivation_path {
             Ok(Some(derivation_path.clone()))
         } else if let Some(derivation_index) = &self.derivation_index {
-            let derivation_path = format!("m/44'/637'/{}'/0'/0'", derivation_index);
-            Ok(Some(derivation_path))
+            Ok(Some(derivation_path_from_index(derivation_index)))
         } else {
             Ok(None)
         }
@@ -706,6 +797,32 @@ impl HardwareWalletOptions {
     }
 }
 
+/// The Aptos BIP-44 derivation path of the account at the given index
+pub fn derivation_path_from_index(index: impl std::fmt::Display) -> String {
+    format!("m/44'/637'/{}'/0'/0'", index)
+}
+
+/// Derives the private key of an account from a BIP-39 mnemonic phrase
+///
+/// The key is derived with SLIP-10 along the derivation path, the same way as wallets do, so
+/// that the CLI controls the same account as a wallet using the phrase.
+pub fn derive_private_key_from_mnemonic(
+    mnemonic_phrase: &str,
+    derivation_path: &str,
+) -> CliTypedResult<Ed25519PrivateKey> {
+    let words: Vec<_> = mnemonic_phrase.split_whitespace().collect();
+    if words.len() != 12 && words.len() != 24 {
+        return Err(CliError::CommandArgumentError(format!(
+            "Mnemonic phrase must have 12 or 24 words, but has {}",
+            words.len()
+        )));
+    }
+
+    let account = LocalAccount::from_derive_path(derivation_path, &words.join(" "), 0)
+        .map_err(|err| CliError::UnableToParse("mnemonic phrase", err.to_string()))?;
+    Ok(account.private_key().clone())
+}
+
 #[derive(Debug, Default, Parser)]
 pub struct PrivateKeyInputOptions {
     /// Signing Ed25519 private key file path
@@ -1467,8 +1584,10 @@ pub struct GasOptions {
     /// Number of seconds to expire the transaction
     ///
     /// This is the number of seconds from the current local computer time.
-    #[clap(long, default_value_t = DEFAULT_EXPIRATION_SECS)]
-    pub expiration_secs: u64,
+    ///
+    /// Defaults to 30 seconds
+    #[clap(long)]
+    pub expiration_secs: Option<u64>,
 }
 
 impl Default for GasOptions {
@@ -1476,7 +1595,7 @@ impl Default for GasOptions {
         GasOptions {
             gas_unit_price: None,
             max_gas: None,
-            expiration_secs: DEFAULT_EXPIRATION_SECS,
+            expiration_secs: None,
         }
     }
 }
@@ -1487,6 +1606,45 @@ pub enum AccountType {
     HardwareWallet,
 }
 
+/// The account that signs transactions built by the CLI, either with a private key held
+/// locally, or with a key that never leaves the hardware wallet
+#[derive(Debug)]
+pub enum CliSigner {
+    Local(LocalAccount),
+    HardwareWallet(HardwareWalletAccount),
+}
+
+impl CliSigner {
+    pub fn address(&self) -> AccountAddress {
+        match self {
+            CliSigner::Local(account) => account.address(),
+            CliSigner::HardwareWallet(account) => account.address(),
+        }
+    }
+
+    pub fn public_key(&self) -> &Ed25519PublicKey {
+        match self {
+            CliSigner::Local(account) => account.public_key(),
+            CliSigner::HardwareWallet(account) => account.public_key(),
+        }
+    }
+
+    /// Signs the transaction, which for a hardware wallet requires the user to approve it
+    /// on the device
+    pub fn sign_transaction(
+        &self,
+        raw_transaction: RawTransaction,
+    ) -> CliTypedResult<SignedTransaction> {
+        match self {
+            CliSigner::Local(account) => Ok(account.sign_transaction(raw_transaction)),
+            CliSigner::HardwareWallet(account) => {
+                eprintln!("Please approve the transaction on your Ledger device");
+                Ok(account.sign_transaction(raw_transaction)?)
+            },
+        }
+    }
+}
+
 /// Common options for interacting with an account for a validator
 #[derive(Debug, Default, Parser)]
 pub struct TransactionOptions {
@@ -1510,6 +1668,16 @@ pub struct TransactionOptions {
     #[clap(flatten)]
     pub(crate) prompt_options: PromptOptions,
 
+    /// Sign the transaction with a Ledger device
+    ///
+    /// The derivation path is taken from `--derivation-path` or `--derivation-index`,
+    /// or otherwise from the profile.  Make sure your Ledger is unlocked and the Aptos app
+    /// is open.
+    #[clap(long)]
+    pub(crate) ledger: bool,
+    #[clap(flatten)]
+    pub(crate) hardware_wallet_options: HardwareWalletOptions,
+
     /// If this option is set, simulate the transaction locally.
     #[clap(long)]
     pub(crate) local: bool,
@@ -1530,8 +1698,31 @@ impl TransactionOptions {
         self.rest_options.client(&self.profile_options)
     }
 
+    /// The gas options, falling back to the defaults of the profile for any that aren't given
+    fn gas_options(&self) -> GasOptions {
+        let profile = self.profile_options.profile().ok();
+        let profile_default =
+            |default: fn(&ProfileConfig) -> Option<u64>| profile.as_ref().and_then(default);
+        GasOptions {
+            gas_unit_price: self
+                .gas_options
+                .gas_unit_price
+                .or_else(|| profile_default(|profile| profile.gas_unit_price)),
+            max_gas: self
+                .gas_options
+                .max_gas
+                .or_else(|| profile_default(|profile| profile.max_gas)),
+            expiration_secs: self
+                .gas_options
+                .expiration_secs
+                .or_else(|| profile_default(|profile| profile.expiration_secs)),
+        }
+    }
+
     pub fn get_transaction_account_type(&self) -> CliTypedResult<AccountType> {
-        if self.private_key_options.private_key.is_some()
+        if self.ledger || self.hardware_wallet_options.is_hardware_wallet() {
+            Ok(AccountType::HardwareWallet)
+        } else if self.private_key_options.private_key.is_some()
             || self.private_key_options.private_key_file.is_some()
         {
             Ok(AccountType::Local)
@@ -1563,6 +1754,16 @@ impl TransactionOptions {
     }
 
     pub fn get_public_key_and_address(&self) -> CliTypedResult<(Ed25519PublicKey, AccountAddress)> {
+        // A derivation path given on the command line may not match the profile, so the
+        // public key has to come from the device
+        if let Some(derivation_path) = self.hardware_wallet_options.extract_derivation_path()? {
+            let public_key = aptos_ledger::get_public_key(&derivation_path, false)?;
+            let address = self
+                .sender_account
+                .unwrap_or_else(|| account_address_from_public_key(&public_key));
+            return Ok((public_key, address));
+        }
+
         self.private_key_options.extract_public_key_and_address(
             self.encoding_options.encoding,
             &self.profile_options,
@@ -1570,8 +1771,55 @@ impl TransactionOptions {
         )
     }
 
+    /// Retrieves the derivation path of the sender on the hardware wallet
+    fn derivation_path(&self) -> CliTypedResult<String> {
+        if let Some(derivation_path) = self.hardware_wallet_options.extract_derivation_path()? {
+            return Ok(derivation_path);
+        }
+
+        self.profile_options.derivation_path()?.ok_or_else(|| {
+            CliError::CommandArgumentError(
+                "One of ['--derivation-path', '--derivation-index'] or a profile created with `aptos init --ledger` must be used".to_string(),
+            )
+        })
+    }
+
+    /// Retrieves the signer of the sender
+    pub fn signer(&self) -> CliTypedResult<CliSigner> {
+        // Transactions are built with their sequence number, so the signer doesn't track it
+        let sequence_number = 0;
+        match self.get_transaction_account_type()? {
+            AccountType::Local => {
+                let (private_key, sender_address) = self.get_key_and_address()?;
+                Ok(CliSigner::Local(LocalAccount::new(
+                    sender_address,
+                    private_key,
+                    sequence_number,
+                )))
+            },
+            AccountType::HardwareWallet => {
+                let (public_key, sender_address) = self.get_public_key_and_address()?;
+                Ok(CliSigner::HardwareWallet(HardwareWalletAccount::new(
+                    sender_address,
+                    public_key,
+                    self.derivation_path()?,
+                    HardwareWalletType::Ledger,
+                    sequence_number,
+                )))
+            },
+        }
+    }
+
     pub fn sender_address(&self) -> CliTypedResult<AccountAddress> {
-        Ok(self.get_key_and_address()?.1)
+        Ok(self.get_public_key_and_address()?.1)
+    }
+
+    /// The multisig account of the profile, if the profile is for an owner of one
+    pub fn profile_multisig_address(&self) -> Option<AccountAddress> {
+        self.profile_options
+            .profile()
+            .ok()
+            .and_then(|profile| profile.multisig_address)
     }
 
     pub fn get_public_key(&self) -> CliTypedResult<Ed25519PublicKey> {
@@ -1603,17 +1851,59 @@ impl TransactionOptions {
     }
 
     /// Submit a transaction
+    ///
+    /// If the profile is for an owner of a multisig account, the transaction is proposed to the
+    /// multisig account instead, and can be executed once enough owners approved it (see
+    /// `aptos multisig`).
     pub async fn submit_transaction(
         &self,
         payload: TransactionPayload,
+    ) -> CliTypedResult<Transaction> {
+        let payload = if let Some(multisig_address) = self.profile_multisig_address() {
+            eprintln!(
+                "Proposing the transaction to multisig account {}, it must be approved by the owners before it can be executed",
+                multisig_address
+            );
+            multisig_proposal(multisig_address, payload)?
+        } else {
+            payload
+        };
+        self.submit_transaction_as_sender(payload).await
+    }
+
+    /// Submit a transaction that is executed by the sender itself, even if the profile is for
+    /// an owner of a multisig account
+    pub async fn submit_transaction_as_sender(
+        &self,
+        payload: TransactionPayload,
     ) -> CliTypedResult<Transaction> {
+        let client = self.rest_client()?;
+        let raw_transaction = self.build_transaction(payload).await?;
+        let transaction = self.signer()?.sign_transaction(raw_transaction)?;
+        let response = client
+            .submit_and_wait(&transaction)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+
+        Ok(response.into_inner())
+    }
+
+    /// Builds the unsigned transaction of the sender
+    ///
+    /// Unless they are given, the gas unit price is estimated, and the max gas is estimated by
+    /// simulating the transaction, which requires the public key of the sender.
+    pub async fn build_transaction(
+        &self,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<RawTransaction> {
         let client = self.rest_client()?;
         let (sender_public_key, sender_address) = self.get_public_key_and_address()?;
+        let gas_options = self.gas_options();
 
         // Ask to confirm price if the gas unit price is estimated above the lowest value when
         // it is automatically estimated
         let ask_to_confirm_price;
-        let gas_unit_price = if let Some(gas_unit_price) = self.gas_options.gas_unit_price {
+        let gas_unit_price = if let Some(gas_unit_price) = gas_options.gas_unit_price {
             ask_to_confirm_price = false;
             gas_unit_price
         } else {
@@ -1639,12 +1929,15 @@ impl TransactionOptions {
         if now_usecs < state.timestamp_usecs - ACCEPTED_CLOCK_SKEW_US {
             eprintln!("Local clock is is skewed from blockchain clock.  Clock is more than {} seconds behind the blockchain {}", ACCEPTED_CLOCK_SKEW_US, state.timestamp_usecs / US_IN_SECS );
         }
-        let expiration_time_secs = now + self.gas_options.expiration_secs;
+        let expiration_time_secs = now
+            + gas_options
+                .expiration_secs
+                .unwrap_or(DEFAULT_EXPIRATION_SECS);
 
         let chain_id = ChainId::new(state.chain_id);
         // TODO: Check auth key against current private key and provide a better message
 
-        let max_gas = if let Some(max_gas) = self.gas_options.max_gas {
+        let max_gas = if let Some(max_gas) = gas_options.max_gas {
             // If the gas unit price was estimated ask, but otherwise you've chosen hwo much you want to spend
             if ask_to_confirm_price {
                 let message = format!("Do you want to submit transaction for a maximum of {} Octas at a gas unit price of {} Octas?",  max_gas * gas_unit_price, gas_unit_price);
@@ -1698,48 +1991,14 @@ impl TransactionOptions {
             adjusted_max_gas
         };
 
-        // Sign and submit transaction
-        let transaction_factory = TransactionFactory::new(chain_id)
+        Ok(TransactionFactory::new(chain_id)
             .with_gas_unit_price(gas_unit_price)
             .with_max_gas_amount(max_gas)
-            .with_transaction_expiration_time(self.gas_options.expiration_secs);
-
-        match self.get_transaction_account_type() {
-            Ok(AccountType::Local) => {
-                let (private_key, _) = self.get_key_and_address()?;
-                let sender_account =
-                    &mut LocalAccount::new(sender_address, private_key, sequence_number);
-                let transaction = sender_account
-                    .sign_with_transaction_builder(transaction_factory.payload(payload));
-                let response = client
-                    .submit_and_wait(&transaction)
-                    .await
-                    .map_err(|err| CliError::ApiError(err.to_string()))?;
-
-                Ok(response.into_inner())
-            },
-            Ok(AccountType::HardwareWallet) => {
-                let sender_account = &mut HardwareWalletAccount::new(
-                    sender_address,
-                    sender_public_key,
-                    self.profile_options
-                        .derivation_path()
-                        .expect("derivative path is missing from profile")
-                        .unwrap(),
-                    HardwareWalletType::Ledger,
-                    sequence_number,
-                );
-                let transaction = sender_account
-                    .sign_with_transaction_builder(transaction_factory.payload(payload))?;
-                let response = client
-                    .submit_and_wait(&transaction)
-                    .await
-                    .map_err(|err| CliError::ApiError(err.to_string()))?;
-
-                Ok(response.into_inner())
-            },
-            Err(err) => Err(err),
-        }
+            .payload(payload)
+            .sender(sender_address)
+            .sequence_number(sequence_number)
+            .expiration_timestamp_secs(expiration_time_secs)
+            .build())
     }
 
     /// Simulates a transaction locally, using the debugger to fetch required data from remote.
@@ -1763,11 +2022,9 @@ impl TransactionOptions {
         const DEFAULT_GAS_UNIT_PRICE: u64 = 100;
         const DEFAULT_MAX_GAS: u64 = 2_000_000;
 
-        let (sender_key, sender_address) = self.get_key_and_address()?;
-        let gas_unit_price = self
-            .gas_options
-            .gas_unit_price
-            .unwrap_or(DEFAULT_GAS_UNIT_PRICE);
+        let sender_address = self.sender_address()?;
+        let gas_options = self.gas_options();
+        let gas_unit_price = gas_options.gas_unit_price.unwrap_or(DEFAULT_GAS_UNIT_PRICE);
         let (account, state) = get_account_with_state(&client, sender_address).await?;
         let version = state.version;
         let chain_id = ChainId::new(state.chain_id);
@@ -1779,7 +2036,7 @@ impl TransactionOptions {
             .map_err(|err| CliError::ApiError(err.to_string()))?
             .into_inner();
 
-        let max_gas = self.gas_options.max_gas.unwrap_or_else(|| {
+        let max_gas = gas_options.max_gas.unwrap_or_else(|| {
             if gas_unit_price == 0 {
                 DEFAULT_MAX_GAS
             } else {
@@ -1790,10 +2047,17 @@ impl TransactionOptions {
         let transaction_factory = TransactionFactory::new(chain_id)
             .with_gas_unit_price(gas_unit_price)
             .with_max_gas_amount(max_gas)
-            .with_transaction_expiration_time(self.gas_options.expiration_secs);
-        let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
-        let transaction =
-            sender_account.sign_with_transaction_builder(transaction_factory.payload(payload));
+            .with_transaction_expiration_time(
+                gas_options
+                    .expiration_secs
+                    .unwrap_or(DEFAULT_EXPIRATION_SECS),
+            );
+        let raw_transaction = transaction_factory
+            .payload(payload)
+            .sender(sender_address)
+            .sequence_number(sequence_number)
+            .build();
+        let transaction = self.signer()?.sign_transaction(raw_transaction)?;
         let hash = transaction.committed_hash();
 
         let debugger = AptosDebugger::rest_client(client).unwrap();
@@ -1832,6 +2096,33 @@ impl TransactionOptions {
             .await
     }
 
+    /// Simulates the transaction payload locally, on the state with the overrides applied, and
+    /// returns the effects of the transaction along with its summary
+    pub async fn simulate_with_overrides(
+        &self,
+        payload: TransactionPayload,
+        overrides: &StateOverrides,
+    ) -> CliTypedResult<(TransactionSummary, SimulationDetails)> {
+        let mut details = None;
+        let summary = self
+            .simulate_using_debugger(payload, |debugger, version, transaction, _hash| {
+                let (vm_status, vm_output, simulation_details) =
+                    local_simulation::run_transaction_with_overrides(
+                        debugger,
+                        version,
+                        transaction,
+                        overrides,
+                    )?;
+                details = Some(simulation_details);
+                Ok((vm_status, vm_output))
+            })
+            .await?;
+        Ok((
+            summary,
+            details.expect("Must have details, as the transaction was simulated"),
+        ))
+    }
+
     /// Benchmarks the transaction payload locally.
     /// The transaction is executed multiple times, and the median value is calculated to improve
     /// the accuracy of the measurement results.
@@ -1899,8 +2190,40 @@ pub struct PoolAddressArgs {
 #[derive(Clone, Debug, Parser, Serialize)]
 pub struct MultisigAccount {
     /// The address of the multisig account to interact with
+    ///
+    /// Defaults to the multisig account of the profile
     #[clap(long, value_parser = crate::common::types::load_account_arg)]
-    pub(crate) multisig_address: AccountAddress,
+    pub(crate) multisig_address: Option<AccountAddress>,
+}
+
+impl MultisigAccount {
+    /// The multisig address from the command line, or otherwise from the profile
+    pub fn address(&self, txn_options: &TransactionOptions) -> CliTypedResult<AccountAddress> {
+        self.multisig_address
+            .or_else(|| txn_options.profile_multisig_address())
+            .ok_or_else(|| {
+                CliError::CommandArgumentError(
+                    "--multisig-address must be provided, or the profile must be for a multisig account".to_string(),
+                )
+            })
+    }
+}
+
+/// Wraps the payload into a proposal of it to the multisig account
+pub fn multisig_proposal(
+    multisig_address: AccountAddress,
+    payload: TransactionPayload,
+) -> CliTypedResult<TransactionPayload> {
+    if let TransactionPayload::EntryFunction(entry_function) = payload {
+        Ok(aptos_stdlib::multisig_account_create_transaction(
+            multisig_address,
+            bcs::to_bytes(&MultisigTransactionPayload::EntryFunction(entry_function))?,
+        ))
+    } else {
+        Err(CliError::CommandArgumentError(
+            "Only entry function transactions can be proposed to a multisig account".to_string(),
+        ))
+    }
 }
 
 #[derive(Clone, Debug, Parser, Serialize)]
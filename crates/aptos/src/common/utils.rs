@@ -428,6 +428,13 @@ pub fn read_line(input_name: &'static str) -> CliTypedResult<String> {
     Ok(input_buf)
 }
 
+/// Reads a mnemonic phrase from stdin, rather than from the arguments, to keep it out of the
+/// shell history
+pub fn read_mnemonic() -> CliTypedResult<String> {
+    eprintln!("Enter your 12 or 24 word mnemonic phrase:");
+    Ok(read_line("Mnemonic phrase")?.trim().to_string())
+}
+
 /// Lists the content of a directory
 pub fn read_dir_files(
     path: &Path,
@@ -444,6 +451,12 @@ pub fn read_dir_files(
     Ok(result)
 }
 
+/// The number of attempts to fund an account with the faucet, which is often rate limited or
+/// briefly unavailable
+const FAUCET_FUND_ATTEMPTS: u32 = 4;
+/// The backoff before the first retry, which doubles with every further retry
+const FAUCET_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
 /// Fund account (and possibly create it) from a faucet. This function waits for the
 /// transaction on behalf of the caller.
 pub async fn fund_account(
@@ -457,10 +470,25 @@ pub async fn fund_account(
     if let Some(token) = faucet_auth_token {
         client = client.with_auth_token(token.to_string());
     }
-    client
-        .fund(address, num_octas)
-        .await
-        .map_err(|err| CliError::ApiError(format!("Faucet issue: {:#}", err)))
+
+    let mut backoff = FAUCET_INITIAL_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match client.fund(address, num_octas).await {
+            Ok(()) => return Ok(()),
+            Err(err) if attempt < FAUCET_FUND_ATTEMPTS => {
+                eprintln!(
+                    "Faucet issue: {:#}, retrying in {} seconds...",
+                    err,
+                    backoff.as_secs()
+                );
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            },
+            Err(err) => return Err(CliError::ApiError(format!("Faucet issue: {:#}", err))),
+        }
+    }
 }
 
 /// Wait for transactions, returning an error if any of them fail.
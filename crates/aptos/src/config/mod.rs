@@ -19,13 +19,22 @@ use clap_complete::Shell;
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fmt::Formatter, path::PathBuf, str::FromStr};
 
+pub mod network;
+pub mod profile;
+
 /// Tool for interacting with configuration of the Aptos CLI tool
 ///
 /// This tool handles the global configuration of the CLI tool for
 /// default configuration, and user specific settings.
 #[derive(Parser)]
 pub enum ConfigTool {
+    ExportProfile(profile::ExportProfile),
     GenerateShellCompletions(GenerateShellCompletions),
+    ImportProfile(profile::ImportProfile),
+    #[clap(subcommand)]
+    Network(network::NetworkTool),
+    RenameProfile(profile::RenameProfile),
+    SetGasDefaults(profile::SetGasDefaults),
     SetGlobalConfig(SetGlobalConfig),
     ShowGlobalConfig(ShowGlobalConfig),
     ShowProfiles(ShowProfiles),
@@ -34,7 +43,12 @@ pub enum ConfigTool {
 impl ConfigTool {
     pub async fn execute(self) -> CliResult {
         match self {
+            ConfigTool::ExportProfile(tool) => tool.execute_serialized().await,
             ConfigTool::GenerateShellCompletions(tool) => tool.execute_serialized_success().await,
+            ConfigTool::ImportProfile(tool) => tool.execute_serialized().await,
+            ConfigTool::Network(tool) => tool.execute().await,
+            ConfigTool::RenameProfile(tool) => tool.execute_serialized_success().await,
+            ConfigTool::SetGasDefaults(tool) => tool.execute_serialized().await,
             ConfigTool::SetGlobalConfig(tool) => tool.execute_serialized().await,
             ConfigTool::ShowGlobalConfig(tool) => tool.execute_serialized().await,
             ConfigTool::ShowProfiles(tool) => tool.execute_serialized().await,
@@ -42,6 +56,15 @@ impl ConfigTool {
     }
 }
 
+/// Loads the config of the current directory, or an empty one if there is none yet
+fn load_config() -> CliTypedResult<CliConfig> {
+    if CliConfig::config_exists(ConfigSearchMode::CurrentDir) {
+        CliConfig::load(ConfigSearchMode::CurrentDir)
+    } else {
+        Ok(CliConfig::default())
+    }
+}
+
 /// Generate shell completion files
 ///
 /// First generate the completion file, then follow the shell specific directions on how
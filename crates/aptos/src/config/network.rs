@@ -0,0 +1,146 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::load_config;
+use crate::common::{
+    init::Network,
+    types::{CliCommand, CliError, CliResult, CliTypedResult, NetworkConfig, PromptOptions},
+    utils::prompt_yes_with_override,
+};
+use async_trait::async_trait;
+use clap::{Parser, Subcommand};
+use reqwest::Url;
+use std::{collections::BTreeMap, str::FromStr};
+
+/// Tool for managing the networks that profiles can be initialized with
+///
+/// Custom networks are saved in the config, and can be used with `aptos init --network <NAME>`
+#[derive(Debug, Subcommand)]
+pub enum NetworkTool {
+    Add(AddNetwork),
+    List(ListNetworks),
+    Remove(RemoveNetwork),
+}
+
+impl NetworkTool {
+    pub async fn execute(self) -> CliResult {
+        match self {
+            NetworkTool::Add(tool) => tool.execute_serialized_success().await,
+            NetworkTool::List(tool) => tool.execute_serialized().await,
+            NetworkTool::Remove(tool) => tool.execute_serialized_success().await,
+        }
+    }
+}
+
+/// Add a custom network
+#[derive(Debug, Parser)]
+pub struct AddNetwork {
+    /// Name of the network
+    #[clap(long)]
+    name: String,
+    /// URL to a fullnode on the network
+    #[clap(long)]
+    rest_url: Url,
+    /// URL for the Faucet endpoint (if applicable)
+    #[clap(long)]
+    faucet_url: Option<Url>,
+    /// Chain id of the network
+    ///
+    /// If provided, `aptos init` checks that the rest endpoint is on this chain
+    #[clap(long)]
+    chain_id: Option<u8>,
+    #[clap(flatten)]
+    prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<()> for AddNetwork {
+    fn command_name(&self) -> &'static str {
+        "AddNetwork"
+    }
+
+    async fn execute(self) -> CliTypedResult<()> {
+        if Network::from_str(&self.name).is_ok() {
+            return Err(CliError::CommandArgumentError(format!(
+                "Network {} is built-in and can't be replaced",
+                self.name
+            )));
+        }
+
+        let mut config = load_config()?;
+        let networks = config.networks.get_or_insert_with(BTreeMap::new);
+        if networks.contains_key(&self.name) {
+            prompt_yes_with_override(
+                &format!(
+                    "Network {} already exists, do you want to overwrite it?",
+                    self.name
+                ),
+                self.prompt_options,
+            )?;
+        }
+        networks.insert(self.name, NetworkConfig {
+            rest_url: self.rest_url.to_string(),
+            faucet_url: self.faucet_url.map(|url| url.to_string()),
+            chain_id: self.chain_id,
+        });
+        config.save()
+    }
+}
+
+/// List the built-in and custom networks
+#[derive(Debug, Parser)]
+pub struct ListNetworks {}
+
+#[async_trait]
+impl CliCommand<BTreeMap<String, NetworkConfig>> for ListNetworks {
+    fn command_name(&self) -> &'static str {
+        "ListNetworks"
+    }
+
+    async fn execute(self) -> CliTypedResult<BTreeMap<String, NetworkConfig>> {
+        let mut networks: BTreeMap<_, _> = [
+            Network::Mainnet,
+            Network::Testnet,
+            Network::Devnet,
+            Network::Local,
+        ]
+        .into_iter()
+        .filter_map(|network| Some((network.to_string(), network.preset()?)))
+        .collect();
+        networks.extend(load_config()?.networks.unwrap_or_default());
+        Ok(networks)
+    }
+}
+
+/// Remove a custom network
+///
+/// Profiles that were initialized with the network are not changed
+#[derive(Debug, Parser)]
+pub struct RemoveNetwork {
+    /// Name of the network
+    #[clap(long)]
+    name: String,
+}
+
+#[async_trait]
+impl CliCommand<()> for RemoveNetwork {
+    fn command_name(&self) -> &'static str {
+        "RemoveNetwork"
+    }
+
+    async fn execute(self) -> CliTypedResult<()> {
+        let mut config = load_config()?;
+        if config
+            .networks
+            .as_mut()
+            .and_then(|networks| networks.remove(&self.name))
+            .is_none()
+        {
+            return Err(CliError::CommandArgumentError(format!(
+                "Network {} not found",
+                self.name
+            )));
+        }
+        config.save()
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::load_config;
+use crate::common::{
+    types::{
+        CliCommand, CliConfig, CliError, CliTypedResult, GasOptions, ProfileOptions,
+        ProfileSummary, PromptOptions, SaveFile, DEFAULT_PROFILE,
+    },
+    utils::prompt_yes_with_override,
+};
+use async_trait::async_trait;
+use clap::Parser;
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// Export profiles to a file, to share them or to import them elsewhere
+///
+/// Private keys are left out, unless `--include-private-keys` is provided.  Custom networks
+/// are exported along with the profiles.
+#[derive(Debug, Parser)]
+pub struct ExportProfile {
+    /// Profiles to export
+    ///
+    /// Defaults to all profiles
+    #[clap(long, num_args = 0..)]
+    profiles: Vec<String>,
+
+    /// Whether to export the private keys of the profiles, including the ephemeral keys of
+    /// keyless accounts
+    ///
+    /// Only use this if the exported file is kept as safe as the config itself
+    #[clap(long)]
+    include_private_keys: bool,
+
+    #[clap(flatten)]
+    output_file_options: SaveFile,
+}
+
+#[async_trait]
+impl CliCommand<Vec<String>> for ExportProfile {
+    fn command_name(&self) -> &'static str {
+        "ExportProfile"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<String>> {
+        self.output_file_options.check_file()?;
+
+        let mut config = load_config()?;
+        let mut profiles = config.profiles.take().unwrap_or_default();
+        if !self.profiles.is_empty() {
+            if let Some(missing) = self
+                .profiles
+                .iter()
+                .find(|name| !profiles.contains_key(*name))
+            {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Profile {} not found",
+                    missing
+                )));
+            }
+            profiles.retain(|name, _| self.profiles.contains(name));
+        }
+        if !self.include_private_keys {
+            for profile in profiles.values_mut() {
+                profile.private_key = None;
+                profile.keyless = None;
+            }
+        }
+
+        let names = profiles.keys().cloned().collect();
+        let export = CliConfig {
+            profiles: Some(profiles),
+            ..config
+        };
+        let yaml = serde_yaml::to_string(&export).map_err(|err| {
+            CliError::UnexpectedError(format!("Failed to serialize config {}", err))
+        })?;
+        if self.include_private_keys {
+            self.output_file_options
+                .save_to_file_confidential("Exported profiles", yaml.as_bytes())?;
+        } else {
+            self.output_file_options
+                .save_to_file("Exported profiles", yaml.as_bytes())?;
+        }
+        Ok(names)
+    }
+}
+
+/// Import profiles exported with `aptos config export-profile`
+///
+/// The profiles and custom networks are merged into the config, and overwriting existing ones
+/// has to be confirmed.
+#[derive(Debug, Parser)]
+pub struct ImportProfile {
+    /// File with the exported profiles
+    #[clap(long, value_parser)]
+    input_file: PathBuf,
+
+    /// Profiles to import
+    ///
+    /// Defaults to all profiles in the file
+    #[clap(long, num_args = 0..)]
+    profiles: Vec<String>,
+
+    #[clap(flatten)]
+    prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<Vec<String>> for ImportProfile {
+    fn command_name(&self) -> &'static str {
+        "ImportProfile"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<String>> {
+        let import = CliConfig::from_file(&self.input_file)?;
+        let mut config = load_config()?;
+
+        let mut imported = vec![];
+        let profiles = config.profiles.get_or_insert_with(BTreeMap::new);
+        for (name, profile) in import.profiles.unwrap_or_default() {
+            if !self.profiles.is_empty() && !self.profiles.contains(&name) {
+                continue;
+            }
+            if profiles.contains_key(&name) {
+                prompt_yes_with_override(
+                    &format!(
+                        "Profile {} already exists, do you want to overwrite it?",
+                        name
+                    ),
+                    self.prompt_options,
+                )?;
+            }
+            if profile.private_key.is_none()
+                && profile.derivation_path.is_none()
+                && profile.keyless.is_none()
+            {
+                eprintln!(
+                    "Profile {} has no private key, so it can only be used for commands that don't sign transactions",
+                    name
+                );
+            }
+            profiles.insert(name.clone(), profile);
+            imported.push(name);
+        }
+
+        let networks = config.networks.get_or_insert_with(BTreeMap::new);
+        for (name, network) in import.networks.unwrap_or_default() {
+            match networks.get(&name) {
+                Some(existing) if *existing == network => continue,
+                Some(_) => prompt_yes_with_override(
+                    &format!(
+                        "Network {} already exists, do you want to overwrite it?",
+                        name
+                    ),
+                    self.prompt_options,
+                )?,
+                None => (),
+            }
+            networks.insert(name, network);
+        }
+
+        config.save()?;
+        Ok(imported)
+    }
+}
+
+/// Rename a profile
+#[derive(Debug, Parser)]
+pub struct RenameProfile {
+    /// Current name of the profile
+    #[clap(long)]
+    profile: String,
+
+    /// New name of the profile
+    #[clap(long)]
+    new_name: String,
+}
+
+#[async_trait]
+impl CliCommand<()> for RenameProfile {
+    fn command_name(&self) -> &'static str {
+        "RenameProfile"
+    }
+
+    async fn execute(self) -> CliTypedResult<()> {
+        let mut config = load_config()?;
+        let profiles = config.profiles.get_or_insert_with(BTreeMap::new);
+        if profiles.contains_key(&self.new_name) {
+            return Err(CliError::CommandArgumentError(format!(
+                "Profile {} already exists",
+                self.new_name
+            )));
+        }
+        let profile = profiles.remove(&self.profile).ok_or_else(|| {
+            CliError::CommandArgumentError(format!("Profile {} not found", self.profile))
+        })?;
+        profiles.insert(self.new_name, profile);
+        config.save()
+    }
+}
+
+/// Set the default gas options of a profile
+///
+/// Transactions of the profile use the defaults, unless the gas options are given on the
+/// command line.  Options that aren't provided are left unchanged.
+#[derive(Debug, Parser)]
+pub struct SetGasDefaults {
+    #[clap(flatten)]
+    gas_options: GasOptions,
+
+    /// Whether to remove all the gas defaults of the profile
+    #[clap(long, conflicts_with_all = &["gas_unit_price", "max_gas", "expiration_secs"])]
+    clear: bool,
+
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<ProfileSummary> for SetGasDefaults {
+    fn command_name(&self) -> &'static str {
+        "SetGasDefaults"
+    }
+
+    async fn execute(self) -> CliTypedResult<ProfileSummary> {
+        let profile_name = self
+            .profile_options
+            .profile_name()
+            .unwrap_or(DEFAULT_PROFILE);
+        let mut config = load_config()?;
+        let profile = config
+            .profiles
+            .as_mut()
+            .and_then(|profiles| profiles.get_mut(profile_name))
+            .ok_or_else(|| CliError::ConfigNotFoundError(profile_name.to_string()))?;
+
+        if self.clear {
+            profile.gas_unit_price = None;
+            profile.max_gas = None;
+            profile.expiration_secs = None;
+        } else {
+            let GasOptions {
+                gas_unit_price,
+                max_gas,
+                expiration_secs,
+            } = self.gas_options;
+            profile.gas_unit_price = gas_unit_price.or(profile.gas_unit_price);
+            profile.max_gas = max_gas.or(profile.max_gas);
+            profile.expiration_secs = expiration_secs.or(profile.expiration_secs);
+        }
+
+        let summary = ProfileSummary::from(&*profile);
+        config.save()?;
+        Ok(summary)
+    }
+}
@@ -15,6 +15,7 @@ pub mod op;
 pub mod stake;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod test;
+pub mod transaction;
 pub mod update;
 
 use crate::common::{
@@ -50,6 +51,8 @@ pub enum Tool {
     #[clap(subcommand)]
     Stake(stake::StakeTool),
     #[clap(subcommand)]
+    Transaction(transaction::TransactionTool),
+    #[clap(subcommand)]
     Update(update::UpdateTool),
 }
 
@@ -63,12 +66,13 @@ impl Tool {
             Governance(tool) => tool.execute().await,
             Info(tool) => tool.execute_serialized().await,
             // TODO: Replace entirely with config init
-            Init(tool) => tool.execute_serialized_success().await,
+            Init(tool) => tool.execute_serialized().await,
             Key(tool) => tool.execute().await,
             Move(tool) => tool.execute().await,
             Multisig(tool) => tool.execute().await,
             Node(tool) => tool.execute().await,
             Stake(tool) => tool.execute().await,
+            Transaction(tool) => tool.execute().await,
             Update(tool) => tool.execute().await,
         }
     }
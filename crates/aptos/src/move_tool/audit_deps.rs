@@ -0,0 +1,340 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::types::{
+        CliCommand, CliError, CliTypedResult, MovePackageDir, ProfileOptions, RestOptions,
+    },
+    move_tool::{stored_package::CachedPackageRegistry, IncludedArtifacts},
+};
+use aptos_framework::{BuildOptions, BuiltPackage};
+use aptos_rest_client::{
+    aptos_api_types::{AptosError, AptosErrorCode},
+    error::{AptosErrorResponse, RestError},
+};
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::Parser;
+use move_package::{
+    resolution::resolution_graph::ResolvedGraph, source_package::parsed_manifest::SubstOrRename,
+    BuildConfig,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Audit the dependencies of a Move package
+///
+/// Resolves the full dependency graph of the package, and looks up each dependency on-chain.
+/// Warns about dependencies with the `arbitrary` upgrade policy, or with a weaker upgrade
+/// policy than the package (e.g. upgradable dependencies of an immutable package), which
+/// publishing would reject.  Also lists the named addresses of dependencies which are bound
+/// by the packages depending on them, rather than by their own manifest.
+#[derive(Parser)]
+pub struct AuditDependencies {
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyAudit {
+    pub package: String,
+    pub upgrade_policy: String,
+    pub dependencies: Vec<AuditedDependency>,
+    pub address_rebindings: Vec<AddressRebinding>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditedDependency {
+    pub package: String,
+    pub account: AccountAddress,
+    /// The upgrade policy on-chain, or `None` if the package isn't published
+    pub upgrade_policy: Option<String>,
+    pub upgrade_number: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddressRebinding {
+    /// The dependency declaring the named address
+    pub dependency: String,
+    pub named_address: String,
+    pub address: AccountAddress,
+    /// How the named address is bound, e.g. which package renames or assigns it
+    pub binding: String,
+}
+
+#[async_trait]
+impl CliCommand<DependencyAudit> for AuditDependencies {
+    fn command_name(&self) -> &'static str {
+        "AuditDependencies"
+    }
+
+    async fn execute(self) -> CliTypedResult<DependencyAudit> {
+        let package_path = self.move_options.get_package_path()?;
+        let build_config = BuildConfig {
+            dev_mode: self.move_options.dev,
+            additional_named_addresses: self.move_options.named_addresses(),
+            install_dir: self.move_options.output_dir.clone(),
+            skip_fetch_latest_git_deps: self.move_options.skip_fetch_latest_git_deps,
+            ..Default::default()
+        };
+        let graph = build_config
+            .resolution_graph_for_package(&package_path, &mut std::io::stderr())
+            .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+
+        // The addresses the dependencies are published at are only known once compiled
+        let build_options = BuildOptions {
+            install_dir: self.move_options.output_dir.clone(),
+            // The dependencies have been fetched while resolving the graph
+            skip_fetch_latest_git_deps: true,
+            ..IncludedArtifacts::None.build_options(
+                self.move_options.dev,
+                self.move_options.skip_fetch_latest_git_deps,
+                self.move_options.named_addresses(),
+                self.move_options.bytecode_version,
+                self.move_options.compiler_version,
+                self.move_options.language_version,
+                self.move_options.skip_attribute_checks,
+                self.move_options.check_test_code,
+            )
+        };
+        let package = BuiltPackage::build(package_path, build_options)
+            .map_err(|err| CliError::MoveCompilationError(format!("{:#}", err)))?;
+        let metadata = package.extract_metadata()?;
+
+        let url = self.rest_options.url(&self.profile_options)?;
+        let mut registries = BTreeMap::new();
+        let mut dependencies = vec![];
+        let mut warnings = vec![];
+        for dep in &metadata.deps {
+            // Only a missing account or registry means the dependency isn't published, any
+            // other failure to look it up fails the audit
+            if !registries.contains_key(&dep.account) {
+                let registry =
+                    match CachedPackageRegistry::create(url.clone(), dep.account, false).await {
+                        Ok(registry) => Some(registry),
+                        Err(err) if is_not_found(&err) => None,
+                        Err(err) => {
+                            return Err(CliError::ApiError(format!(
+                                "Failed to look up the packages at {}: {:#}",
+                                dep.account, err
+                            )))
+                        },
+                    };
+                registries.insert(dep.account, registry);
+            }
+            let on_chain = match &registries[&dep.account] {
+                Some(registry)
+                    if registry
+                        .package_names()
+                        .contains(&dep.package_name.as_str()) =>
+                {
+                    Some(registry.get_package(&dep.package_name).await?)
+                },
+                _ => None,
+            };
+            let Some(on_chain) = on_chain else {
+                warnings.push(format!(
+                    "`{}` is not published at {}, publishing would be rejected",
+                    dep.package_name, dep.account
+                ));
+                dependencies.push(AuditedDependency {
+                    package: dep.package_name.clone(),
+                    account: dep.account,
+                    upgrade_policy: None,
+                    upgrade_number: None,
+                });
+                continue;
+            };
+
+            let upgrade_policy = on_chain.upgrade_policy();
+            if upgrade_policy.policy == 0 {
+                warnings.push(format!(
+                    "`{}` at {} has upgrade policy `arbitrary`, so its code can change \
+                    incompatibly at any time. Only packages published at the same address \
+                    can depend on it",
+                    dep.package_name, dep.account
+                ));
+            }
+            if upgrade_policy.policy < metadata.upgrade_policy.policy {
+                warnings.push(format!(
+                    "`{}` at {} has upgrade policy `{}`, which is weaker than `{}` of `{}`, \
+                    publishing would be rejected",
+                    dep.package_name,
+                    dep.account,
+                    upgrade_policy,
+                    metadata.upgrade_policy,
+                    metadata.name
+                ));
+            }
+            dependencies.push(AuditedDependency {
+                package: dep.package_name.clone(),
+                account: dep.account,
+                upgrade_policy: Some(upgrade_policy.to_string()),
+                upgrade_number: Some(on_chain.upgrade_number()),
+            });
+        }
+
+        let address_rebindings = address_rebindings(&graph);
+        Ok(DependencyAudit {
+            package: metadata.name,
+            upgrade_policy: metadata.upgrade_policy.to_string(),
+            dependencies,
+            address_rebindings,
+            warnings,
+        })
+    }
+}
+
+/// Whether the lookup failed because the account or its package registry doesn't exist
+fn is_not_found(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<RestError>(),
+        Some(RestError::Api(AptosErrorResponse {
+            error: AptosError {
+                error_code: AptosErrorCode::ResourceNotFound | AptosErrorCode::AccountNotFound,
+                ..
+            },
+            ..
+        }))
+    )
+}
+
+/// Returns the named addresses of dependencies which are renamed or assigned by the packages
+/// depending on them, or left unassigned in their own manifest.
+fn address_rebindings(graph: &ResolvedGraph) -> Vec<AddressRebinding> {
+    let mut rebindings = BTreeMap::new();
+    for package in graph.package_table.values() {
+        let package_name = package.source_package.package.name;
+        for (dep_name, dep) in &package.source_package.dependencies {
+            let Some(dep_package) = graph.package_table.get(dep_name) else {
+                continue;
+            };
+            for (named_address, subst) in dep.subst.iter().flatten() {
+                let (dep_named_address, binding) = match subst {
+                    SubstOrRename::RenameFrom(from) => (
+                        *from,
+                        format!("renamed to `{}` by `{}`", named_address, package_name),
+                    ),
+                    SubstOrRename::Assign(_) => {
+                        (*named_address, format!("assigned by `{}`", package_name))
+                    },
+                };
+                if let Some(address) = dep_package.resolution_table.get(&dep_named_address) {
+                    rebindings
+                        .entry((*dep_name, dep_named_address))
+                        .or_insert(AddressRebinding {
+                            dependency: dep_name.to_string(),
+                            named_address: dep_named_address.to_string(),
+                            address: *address,
+                            binding,
+                        });
+                }
+            }
+        }
+    }
+
+    // Named addresses left unassigned get their address from the packages depending on them,
+    // or from `--named-addresses`
+    let root_name = graph.root_package.package.name;
+    for (package_name, package) in &graph.package_table {
+        if *package_name == root_name {
+            continue;
+        }
+        for (named_address, declared) in package.source_package.addresses.iter().flatten() {
+            if declared.is_some() {
+                continue;
+            }
+            if let Some(address) = package.resolution_table.get(named_address) {
+                rebindings
+                    .entry((*package_name, *named_address))
+                    .or_insert(AddressRebinding {
+                        dependency: package_name.to_string(),
+                        named_address: named_address.to_string(),
+                        address: *address,
+                        binding: "unassigned in its manifest".to_string(),
+                    });
+            }
+        }
+    }
+    rebindings.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, path::Path};
+
+    fn write_package(dir: &Path, manifest: &str) {
+        fs::create_dir_all(dir.join("sources")).unwrap();
+        fs::write(dir.join("Move.toml"), manifest).unwrap();
+    }
+
+    #[test]
+    fn test_address_rebindings() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        write_package(
+            &temp_dir.path().join("dep"),
+            r#"
+                [package]
+                name = "Dep"
+                version = "0.0.0"
+
+                [addresses]
+                dep_addr = "_"
+                dep_other = "0x7"
+                dep_own = "0x8"
+            "#,
+        );
+        write_package(
+            &temp_dir.path().join("root"),
+            r#"
+                [package]
+                name = "Root"
+                version = "0.0.0"
+
+                [addresses]
+                root = "0x1234"
+
+                [dependencies]
+                Dep = { local = "../dep", addr_subst = { "dep_addr" = "0x42", "renamed" = "dep_other" } }
+            "#,
+        );
+
+        let graph = BuildConfig::default()
+            .resolution_graph_for_package(&temp_dir.path().join("root"), &mut std::io::sink())
+            .unwrap();
+        let mut rebindings: Vec<_> = address_rebindings(&graph)
+            .into_iter()
+            .map(|rebinding| {
+                (
+                    rebinding.dependency,
+                    rebinding.named_address,
+                    rebinding.address,
+                    rebinding.binding,
+                )
+            })
+            .collect();
+        rebindings.sort();
+
+        // The address bound by the dependency's own manifest isn't reported
+        assert_eq!(rebindings, vec![
+            (
+                "Dep".to_string(),
+                "dep_addr".to_string(),
+                AccountAddress::from_hex_literal("0x42").unwrap(),
+                "assigned by `Root`".to_string(),
+            ),
+            (
+                "Dep".to_string(),
+                "dep_other".to_string(),
+                AccountAddress::from_hex_literal("0x7").unwrap(),
+                "renamed to `renamed` by `Root`".to_string(),
+            ),
+        ]);
+    }
+}
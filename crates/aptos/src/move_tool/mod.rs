@@ -27,7 +27,11 @@ use crate::{
 };
 use aptos_crypto::HashValue;
 use aptos_framework::{
-    docgen::DocgenOptions, extended_checks, natives::code::UpgradePolicy, prover::ProverOptions,
+    chunked_publish::{chunk_package_and_create_payloads, PublishType},
+    docgen::DocgenOptions,
+    extended_checks,
+    natives::code::UpgradePolicy,
+    prover::ProverOptions,
     BuildOptions, BuiltPackage,
 };
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
@@ -66,11 +70,13 @@ use tokio::task;
 use url::Url;
 
 mod aptos_debug_natives;
+mod audit_deps;
 mod bytecode;
 pub mod coverage;
 mod manifest;
 pub mod package_hooks;
 mod show;
+mod simulate;
 pub mod stored_package;
 
 /// Tool for Move related operations
@@ -80,6 +86,7 @@ pub mod stored_package;
 /// about this code.
 #[derive(Subcommand)]
 pub enum MoveTool {
+    AuditDeps(audit_deps::AuditDependencies),
     BuildPublishPayload(BuildPublishPayload),
     Clean(CleanPackage),
     Compile(CompilePackage),
@@ -101,6 +108,8 @@ pub enum MoveTool {
     RunScript(RunScript),
     #[clap(subcommand, hide = true)]
     Show(show::ShowTool),
+    Simulate(simulate::SimulateFunction),
+    SimulateScript(simulate::SimulateScript),
     Test(TestPackage),
     VerifyPackage(VerifyPackage),
     View(ViewFunction),
@@ -110,6 +119,7 @@ pub enum MoveTool {
 impl MoveTool {
     pub async fn execute(self) -> CliResult {
         match self {
+            MoveTool::AuditDeps(tool) => tool.execute_serialized().await,
             MoveTool::BuildPublishPayload(tool) => tool.execute_serialized().await,
             MoveTool::Clean(tool) => tool.execute_serialized().await,
             MoveTool::Compile(tool) => tool.execute_serialized().await,
@@ -133,6 +143,8 @@ impl MoveTool {
             MoveTool::Run(tool) => tool.execute_serialized().await,
             MoveTool::RunScript(tool) => tool.execute_serialized().await,
             MoveTool::Show(tool) => tool.execute_serialized().await,
+            MoveTool::Simulate(tool) => tool.execute_serialized().await,
+            MoveTool::SimulateScript(tool) => tool.execute_serialized().await,
             MoveTool::Test(tool) => tool.execute_serialized().await,
             MoveTool::VerifyPackage(tool) => tool.execute_serialized().await,
             MoveTool::View(tool) => tool.execute_serialized().await,
@@ -892,6 +904,15 @@ pub struct CreateObjectAndPublishPackage {
     /// This will take the derived account address for the object and put it in this location
     #[clap(long)]
     pub(crate) address_name: String,
+    /// Publish the package in chunks over multiple transactions
+    ///
+    /// This is for packages which are too large for a single transaction. The chunks are staged
+    /// with the `large_packages` module, and the last transaction publishes the package.
+    #[clap(long)]
+    pub(crate) chunked_publish: bool,
+    /// Address of the `large_packages` module to stage the chunks with
+    #[clap(long, value_parser = crate::common::types::load_account_arg, requires = "chunked_publish")]
+    pub(crate) large_packages_module_address: Option<AccountAddress>,
     #[clap(flatten)]
     pub(crate) override_size_check_option: OverrideSizeCheckOption,
     #[clap(flatten)]
@@ -902,34 +923,106 @@ pub struct CreateObjectAndPublishPackage {
     pub(crate) txn_options: TransactionOptions,
 }
 
+impl CreateObjectAndPublishPackage {
+    fn build_package(&self, object_address: AccountAddress) -> CliTypedResult<BuiltPackage> {
+        let mut move_options = self.move_options.clone();
+        move_options.add_named_address(self.address_name.clone(), object_address.to_string());
+
+        let options = self
+            .included_artifacts_args
+            .included_artifacts
+            .build_options(
+                move_options.dev,
+                move_options.skip_fetch_latest_git_deps,
+                move_options.named_addresses(),
+                move_options.bytecode_version,
+                move_options.compiler_version,
+                move_options.language_version,
+                move_options.skip_attribute_checks,
+                move_options.check_test_code,
+            );
+        Ok(BuiltPackage::build(
+            move_options.get_package_path()?,
+            options,
+        )?)
+    }
+
+    async fn execute_chunked_publish(self) -> CliTypedResult<TransactionSummary> {
+        let large_packages_module_address =
+            self.large_packages_module_address.ok_or_else(|| {
+                CliError::CommandArgumentError(
+                    "--large-packages-module-address is required for a chunked publish".to_string(),
+                )
+            })?;
+        let sender_address = self.txn_options.get_public_key_and_address()?.1;
+        let sequence_number = self.txn_options.sequence_number(sender_address).await?;
+
+        // The object address is derived from the sequence number of the last transaction, which
+        // depends on the number of chunks. That doesn't depend on the address the package is
+        // built with, as addresses are of a fixed size.
+        let create_payloads = |package: &BuiltPackage| -> CliTypedResult<_> {
+            Ok(chunk_package_and_create_payloads(
+                bcs::to_bytes(&package.extract_metadata()?)
+                    .expect("Failed to serialize PackageMetadata"),
+                package.extract_code(),
+                PublishType::ObjectDeploy,
+                large_packages_module_address,
+            ))
+        };
+        let mut object_address =
+            create_object_code_deployment_address(sender_address, sequence_number + 1);
+        let mut payloads = create_payloads(&self.build_package(object_address)?)?;
+        if payloads.len() > 1 {
+            object_address = create_object_code_deployment_address(
+                sender_address,
+                sequence_number + payloads.len() as u64,
+            );
+            payloads = create_payloads(&self.build_package(object_address)?)?;
+        }
+
+        let message = format!(
+            "Do you want to publish this package at object address {} in {} transactions",
+            object_address,
+            payloads.len()
+        );
+        prompt_yes_with_override(&message, self.txn_options.prompt_options)?;
+
+        let num_payloads = payloads.len();
+        let mut result = None;
+        for (i, payload) in payloads.into_iter().enumerate() {
+            println!("Submitting transaction {} of {}", i + 1, num_payloads);
+            result = Some(
+                self.txn_options
+                    .submit_transaction(payload)
+                    .await
+                    .map(TransactionSummary::from)?,
+            );
+        }
+
+        println!(
+            "Code was successfully deployed to object address {}.",
+            object_address
+        );
+        Ok(result.expect("There is at least one chunk"))
+    }
+}
+
 #[async_trait]
 impl CliCommand<TransactionSummary> for CreateObjectAndPublishPackage {
     fn command_name(&self) -> &'static str {
         "CreateObjectAndPublishPackage"
     }
 
-    async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        if self.chunked_publish {
+            return self.execute_chunked_publish().await;
+        }
+
         let sender_address = self.txn_options.get_public_key_and_address()?.1;
         let sequence_number = self.txn_options.sequence_number(sender_address).await? + 1;
         let object_address = create_object_code_deployment_address(sender_address, sequence_number);
 
-        self.move_options
-            .add_named_address(self.address_name, object_address.to_string());
-
-        let options = self
-            .included_artifacts_args
-            .included_artifacts
-            .build_options(
-                self.move_options.dev,
-                self.move_options.skip_fetch_latest_git_deps,
-                self.move_options.named_addresses(),
-                self.move_options.bytecode_version,
-                self.move_options.compiler_version,
-                self.move_options.language_version,
-                self.move_options.skip_attribute_checks,
-                self.move_options.check_test_code,
-            );
-        let package = BuiltPackage::build(self.move_options.get_package_path()?, options)?;
+        let package = self.build_package(object_address)?;
         let message = format!(
             "Do you want to publish this package at object address {}",
             object_address
@@ -947,7 +1040,8 @@ impl CliCommand<TransactionSummary> for CreateObjectAndPublishPackage {
         if !self.override_size_check_option.override_size_check && size > MAX_PUBLISH_PACKAGE_SIZE {
             return Err(CliError::UnexpectedError(format!(
                 "The package is larger than {} bytes ({} bytes)! To lower the size \
-                you may want to include less artifacts via `--included-artifacts`. \
+                you may want to include less artifacts via `--included-artifacts`, \
+                or publish it over multiple transactions with `--chunked-publish`. \
                 You can also override this check with `--override-size-check",
                 MAX_PUBLISH_PACKAGE_SIZE, size
             )));
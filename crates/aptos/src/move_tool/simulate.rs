@@ -0,0 +1,103 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        local_simulation::{SimulationDetails, StateOverrides},
+        types::{
+            CliCommand, CliTypedResult, EntryFunctionArguments, ScriptFunctionArguments,
+            TransactionOptions, TransactionSummary,
+        },
+    },
+    governance::CompileScriptFunction,
+};
+use aptos_types::transaction::TransactionPayload;
+use async_trait::async_trait;
+use clap::Parser;
+use serde::Serialize;
+
+/// Simulate an entry function locally, on the state of the network
+///
+/// The transaction is executed by the CLI on the latest state of the network, fetched from
+/// the REST endpoint, and is not submitted.  Parts of the state can be overridden for the
+/// simulation, e.g. to give an account a balance it doesn't have, or to enable a feature
+/// that isn't enabled on the network yet.
+#[derive(Parser)]
+pub struct SimulateFunction {
+    #[clap(flatten)]
+    pub(crate) entry_function_args: EntryFunctionArguments,
+    #[clap(flatten)]
+    pub(crate) state_overrides: StateOverrides,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<SimulationResult> for SimulateFunction {
+    fn command_name(&self) -> &'static str {
+        "SimulateFunction"
+    }
+
+    async fn execute(self) -> CliTypedResult<SimulationResult> {
+        simulate(
+            TransactionPayload::EntryFunction(self.entry_function_args.try_into()?),
+            &self.state_overrides,
+            &self.txn_options,
+        )
+        .await
+    }
+}
+
+/// Simulate a script locally, on the state of the network
+///
+/// See `aptos move simulate` for how the state can be overridden.
+#[derive(Parser)]
+pub struct SimulateScript {
+    #[clap(flatten)]
+    pub(crate) compile_proposal_args: CompileScriptFunction,
+    #[clap(flatten)]
+    pub(crate) script_function_args: ScriptFunctionArguments,
+    #[clap(flatten)]
+    pub(crate) state_overrides: StateOverrides,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<SimulationResult> for SimulateScript {
+    fn command_name(&self) -> &'static str {
+        "SimulateScript"
+    }
+
+    async fn execute(self) -> CliTypedResult<SimulationResult> {
+        let (bytecode, _script_hash) = self
+            .compile_proposal_args
+            .compile("SimulateScript", self.txn_options.prompt_options)?;
+        simulate(
+            self.script_function_args.create_script_payload(bytecode)?,
+            &self.state_overrides,
+            &self.txn_options,
+        )
+        .await
+    }
+}
+
+/// The outcome of a simulated transaction, with its write set, events and gas breakdown
+#[derive(Debug, Serialize)]
+pub struct SimulationResult {
+    #[serde(flatten)]
+    pub summary: TransactionSummary,
+    #[serde(flatten)]
+    pub details: SimulationDetails,
+}
+
+async fn simulate(
+    payload: TransactionPayload,
+    state_overrides: &StateOverrides,
+    txn_options: &TransactionOptions,
+) -> CliTypedResult<SimulationResult> {
+    let (summary, details) = txn_options
+        .simulate_with_overrides(payload, state_overrides)
+        .await?;
+    Ok(SimulationResult { summary, details })
+}
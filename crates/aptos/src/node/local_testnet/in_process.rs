@@ -0,0 +1,255 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A library entry point for running a localnet (a single node with the txn stream, and
+//! a faucet) in-process, for tools and tests that would otherwise run `aptos node
+//! run-localnet` and poll its ports.
+
+use super::{health_checker::HealthChecker, utils::socket_addr_to_url};
+use anyhow::{anyhow, Context, Result};
+use aptos_config::utils::get_available_port;
+use aptos_faucet_core::server::{FunderKeyEnum, RunConfig};
+use aptos_node::{load_node_config, start_in_process};
+use aptos_temppath::TempPath;
+use rand::{rngs::StdRng, SeedableRng};
+use reqwest::Url;
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+/// Builds and starts an [`InProcessLocalnet`].
+#[derive(Debug)]
+pub struct InProcessLocalnetBuilder {
+    test_dir: Option<PathBuf>,
+    seed: Option<[u8; 32]>,
+    with_faucet: bool,
+}
+
+impl Default for InProcessLocalnetBuilder {
+    fn default() -> Self {
+        Self {
+            test_dir: None,
+            seed: None,
+            with_faucet: true,
+        }
+    }
+}
+
+impl InProcessLocalnetBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The directory to save all files for the node in. If there is a node config in it
+    /// already, the existing chain is reused. Defaults to a temporary directory, which is
+    /// deleted when the localnet is dropped.
+    pub fn test_dir(mut self, test_dir: PathBuf) -> Self {
+        self.test_dir = Some(test_dir);
+        self
+    }
+
+    /// Random seed for key generation, for deterministic keys in tests.
+    pub fn seed(mut self, seed: [u8; 32]) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Do not run a faucet alongside the node.
+    pub fn no_faucet(mut self) -> Self {
+        self.with_faucet = false;
+        self
+    }
+
+    /// Starts the node (with the txn stream) and the faucet, and waits for all of them
+    /// to be healthy. All of them are bound to 127.0.0.1, on available ports.
+    ///
+    /// Note: only one localnet can be started per process, see `start_in_process`.
+    pub async fn start(self) -> Result<InProcessLocalnet> {
+        let (test_dir, temp_dir) = match self.test_dir {
+            Some(test_dir) => (test_dir, None),
+            None => {
+                let temp_dir = TempPath::new();
+                temp_dir
+                    .create_as_dir()
+                    .context("Failed to create temporary test dir")?;
+                (temp_dir.path().to_path_buf(), Some(temp_dir))
+            },
+        };
+        std::fs::create_dir_all(&test_dir)
+            .with_context(|| format!("Failed to create {}", test_dir.display()))?;
+
+        let rng = self
+            .seed
+            .map(StdRng::from_seed)
+            .unwrap_or_else(StdRng::from_entropy);
+        let mut node_config = load_node_config(
+            &None,
+            &None,
+            &test_dir,
+            true,
+            false,
+            false,
+            aptos_cached_packages::head_release_bundle(),
+            rng,
+        )
+        .context("Failed to load / create config for node")?;
+
+        // Enable the txn stream on the node, see `NodeManager` for more info.
+        let bind_to = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        node_config.indexer_grpc.enabled = true;
+        node_config.indexer_grpc.use_data_service_interface = true;
+        node_config
+            .indexer_grpc
+            .address
+            .set_port(get_available_port());
+        node_config.storage.enable_indexer = true;
+        node_config.api.address.set_ip(bind_to);
+        node_config.indexer_grpc.address.set_ip(bind_to);
+        node_config.admin_service.address = bind_to.to_string();
+        node_config.inspection_service.address = bind_to.to_string();
+
+        let node_api_url = socket_addr_to_url(&node_config.api.address, "http")?;
+        let txn_stream_url = socket_addr_to_url(&node_config.indexer_grpc.address, "http")?;
+
+        // The node runs on its own thread until it's told to shut down (or the sender is
+        // dropped along with the localnet).
+        let (node_shutdown_tx, node_shutdown_rx) = mpsc::channel::<()>();
+        let node_thread = thread::spawn(move || -> Result<()> {
+            let _node_handle = start_in_process(node_config)?;
+            let _ = node_shutdown_rx.recv();
+            Ok(())
+        });
+
+        let mut localnet = InProcessLocalnet {
+            node_api_url: node_api_url.clone(),
+            txn_stream_url: txn_stream_url.clone(),
+            faucet_url: None,
+            test_dir,
+            node_shutdown_tx: Some(node_shutdown_tx),
+            node_thread: Some(node_thread),
+            faucet_task: None,
+            _temp_dir: temp_dir,
+        };
+        let mut health_checkers = vec![
+            HealthChecker::NodeApi(node_api_url.clone()),
+            HealthChecker::DataServiceGrpc(txn_stream_url),
+        ];
+
+        if self.with_faucet {
+            let faucet_port = get_available_port();
+            let faucet_config = RunConfig::build_for_cli(
+                node_api_url,
+                bind_to.to_string(),
+                faucet_port,
+                FunderKeyEnum::KeyFile(localnet.test_dir.join("mint.key")),
+                false,
+                None,
+            );
+            let faucet_checker =
+                HealthChecker::http_checker_from_port(faucet_port, "Faucet".to_string());
+            localnet.faucet_url = Some(Url::parse(faucet_checker.address_str())?);
+            let node_health_checker = HealthChecker::NodeApi(localnet.node_api_url.clone());
+            localnet.faucet_task = Some(tokio::spawn(async move {
+                // The faucet needs the node to be up to start.
+                node_health_checker.wait(Some("Faucet")).await?;
+                faucet_config.run().await
+            }));
+            health_checkers.push(faucet_checker);
+        }
+
+        // Wait for all the services to start up, unless the node fails to start.
+        tokio::select! {
+            result = futures::future::try_join_all(
+                health_checkers.iter().map(|health_checker| health_checker.wait(None))
+            ) => {
+                result?;
+            },
+            error = localnet.wait_for_node_exit() => {
+                return Err(error.context("Node failed to start up"));
+            },
+        }
+
+        Ok(localnet)
+    }
+}
+
+/// A localnet running in-process, see [`InProcessLocalnetBuilder`]. Dropping it stops
+/// all the services, use [`InProcessLocalnet::shutdown`] to wait for them to stop.
+#[derive(Debug)]
+pub struct InProcessLocalnet {
+    node_api_url: Url,
+    txn_stream_url: Url,
+    faucet_url: Option<Url>,
+    test_dir: PathBuf,
+    node_shutdown_tx: Option<mpsc::Sender<()>>,
+    node_thread: Option<thread::JoinHandle<Result<()>>>,
+    faucet_task: Option<JoinHandle<Result<()>>>,
+    _temp_dir: Option<TempPath>,
+}
+
+impl InProcessLocalnet {
+    /// The endpoint of the node REST API.
+    pub fn node_api_url(&self) -> &Url {
+        &self.node_api_url
+    }
+
+    /// The endpoint of the grpc transaction stream.
+    pub fn txn_stream_url(&self) -> &Url {
+        &self.txn_stream_url
+    }
+
+    /// The endpoint of the faucet, if it is running.
+    pub fn faucet_url(&self) -> Option<&Url> {
+        self.faucet_url.as_ref()
+    }
+
+    /// The directory with all files for the node, e.g., the root key at `mint.key`.
+    pub fn test_dir(&self) -> &Path {
+        &self.test_dir
+    }
+
+    /// Stops all the services, and waits for the node to shut down.
+    pub async fn shutdown(mut self) -> Result<()> {
+        if let Some(faucet_task) = self.faucet_task.take() {
+            faucet_task.abort();
+        }
+        self.node_shutdown_tx.take();
+        if let Some(node_thread) = self.node_thread.take() {
+            tokio::task::spawn_blocking(move || node_thread.join())
+                .await?
+                .map_err(|_| anyhow!("Node thread panicked"))??;
+        }
+        Ok(())
+    }
+
+    /// Waits for the node thread to exit (which should never happen while the localnet
+    /// is running), and returns the error it exited with.
+    async fn wait_for_node_exit(&mut self) -> anyhow::Error {
+        loop {
+            let finished = self
+                .node_thread
+                .as_ref()
+                .map_or(false, |node_thread| node_thread.is_finished());
+            if finished {
+                return match self.node_thread.take().unwrap().join() {
+                    Ok(Err(error)) => error,
+                    _ => anyhow!("Node thread finished unexpectedly"),
+                };
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    }
+}
+
+impl Drop for InProcessLocalnet {
+    fn drop(&mut self) {
+        if let Some(faucet_task) = self.faucet_task.take() {
+            faucet_task.abort();
+        }
+    }
+}
@@ -4,6 +4,7 @@
 mod docker;
 mod faucet;
 mod health_checker;
+mod in_process;
 mod indexer_api;
 mod logging;
 mod node;
@@ -13,6 +14,7 @@ mod ready_server;
 mod traits;
 mod utils;
 
+pub use self::in_process::{InProcessLocalnet, InProcessLocalnetBuilder};
 use self::{
     faucet::FaucetArgs,
     health_checker::HealthChecker,
@@ -25,6 +25,7 @@ use aptos_backup_cli::{
     utils::GlobalRestoreOpt,
 };
 use aptos_cached_packages::aptos_stdlib;
+use aptos_config::config::validate_node_config;
 use aptos_crypto::{bls12381, bls12381::PublicKey, x25519, ValidCryptoMaterialStringExt};
 use aptos_genesis::config::{HostAndPort, OperatorConfiguration};
 use aptos_logger::Level;
@@ -66,6 +67,7 @@ const SECS_TO_MICROSECS: u64 = 1_000_000;
 pub enum NodeTool {
     AnalyzeValidatorPerformance(AnalyzeValidatorPerformance),
     BootstrapDb(BootstrapDb),
+    CheckConfig(CheckConfig),
     CheckNetworkConnectivity(CheckNetworkConnectivity),
     GetPerformance(GetPerformance),
     GetStakePool(GetStakePool),
@@ -91,6 +93,7 @@ impl NodeTool {
                 tool.execute_serialized_with_logging_level(Level::Info)
                     .await
             },
+            CheckConfig(tool) => tool.execute_serialized().await,
             CheckNetworkConnectivity(tool) => tool.execute_serialized().await,
             GetPerformance(tool) => tool.execute_serialized().await,
             GetStakePool(tool) => tool.execute_serialized().await,
@@ -1354,6 +1357,34 @@ impl CliCommand<()> for BootstrapDb {
     }
 }
 
+/// Checks a node config file
+///
+/// Validates the node config file without starting the node. All unknown fields
+/// (e.g., typos) are reported at once, with suggestions for the fields they likely
+/// refer to. Returns a warning for each deprecated field found in the config.
+#[derive(Parser)]
+pub struct CheckConfig {
+    /// Path to the node config file
+    #[clap(long, value_parser)]
+    pub(crate) config_path: PathBuf,
+}
+
+#[async_trait]
+impl CliCommand<Vec<String>> for CheckConfig {
+    fn command_name(&self) -> &'static str {
+        "CheckConfig"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<String>> {
+        let config_path = self.config_path.display().to_string();
+        let serialized_config = String::from_utf8(read_from_file(&self.config_path)?)
+            .map_err(|error| CliError::UnableToParse("node config", error.to_string()))?;
+        let (_, warnings) = validate_node_config(&serialized_config)
+            .map_err(|error| CliError::ConfigLoadError(config_path, error.to_string()))?;
+        Ok(warnings)
+    }
+}
+
 /// Checks the network connectivity of a node
 ///
 /// Checks network connectivity by dialing the node and attempting
@@ -4,12 +4,13 @@
 use crate::{
     common::{
         types::{
-            account_address_from_public_key, CliError, CliTypedResult, EncodingOptions, KeyType,
+            account_address_from_public_key, derivation_path_from_index,
+            derive_private_key_from_mnemonic, CliError, CliTypedResult, EncodingOptions, KeyType,
             RngArgs, SaveFile,
         },
         utils::{
             append_file_extension, check_if_file_exists, generate_vanity_account_ed25519,
-            write_to_file,
+            read_mnemonic, write_to_file,
         },
     },
     CliCommand, CliResult,
@@ -24,6 +25,7 @@ use aptos_types::account_address::{
 };
 use async_trait::async_trait;
 use clap::{Parser, Subcommand};
+use serde::Serialize;
 use std::{
     collections::{HashMap, HashSet},
     path::{Path, PathBuf},
@@ -39,6 +41,7 @@ pub const PUBLIC_KEY_EXTENSION: &str = "pub";
 pub enum KeyTool {
     Generate(GenerateKey),
     ExtractPeer(ExtractPeer),
+    Derive(DeriveKeys),
 }
 
 impl KeyTool {
@@ -46,6 +49,7 @@ impl KeyTool {
         match self {
             KeyTool::Generate(tool) => tool.execute_serialized().await,
             KeyTool::ExtractPeer(tool) => tool.execute_serialized().await,
+            KeyTool::Derive(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -113,6 +117,55 @@ impl CliCommand<HashMap<AccountAddress, Peer>> for ExtractPeer {
     }
 }
 
+/// Lists the accounts derived from a BIP-39 mnemonic phrase
+///
+/// The mnemonic phrase is read from stdin.  Accounts are derived along the derivation
+/// path `m/44'/637'/{index}'/0'/0'`, the same way as wallets do, so this can be used to find
+/// the `--derivation-index` to pass to `aptos init --from-mnemonic`.  Addresses are derived
+/// from the public keys, so they don't reflect any key rotation.
+#[derive(Debug, Parser)]
+pub struct DeriveKeys {
+    /// Number of accounts to derive
+    #[clap(long, default_value_t = 5)]
+    pub(crate) count: u32,
+
+    /// Index of the first account to derive
+    #[clap(long, default_value_t = 0)]
+    pub(crate) start_index: u32,
+}
+
+/// An account derived from a mnemonic phrase
+#[derive(Debug, Serialize)]
+pub struct DerivedAccount {
+    pub derivation_path: String,
+    pub account: AccountAddress,
+    pub public_key: ed25519::Ed25519PublicKey,
+}
+
+#[async_trait]
+impl CliCommand<Vec<DerivedAccount>> for DeriveKeys {
+    fn command_name(&self) -> &'static str {
+        "DeriveKeys"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<DerivedAccount>> {
+        let mnemonic_phrase = read_mnemonic()?;
+        (self.start_index..self.start_index.saturating_add(self.count))
+            .map(|index| {
+                let derivation_path = derivation_path_from_index(index);
+                let public_key =
+                    derive_private_key_from_mnemonic(&mnemonic_phrase, &derivation_path)?
+                        .public_key();
+                Ok(DerivedAccount {
+                    derivation_path,
+                    account: account_address_from_public_key(&public_key),
+                    public_key,
+                })
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Default, Parser)]
 pub struct NetworkKeyInputOptions {
     /// x25519 Private key input file name
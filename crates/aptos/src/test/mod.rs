@@ -10,7 +10,7 @@ use crate::{
         transfer::{TransferCoins, TransferSummary},
     },
     common::{
-        init::{InitTool, Network},
+        init::{InitResult, InitTool, Network},
         types::{
             account_address_from_public_key, AccountAddressWrapper, ArgWithTypeVec,
             AuthenticationKeyInputOptions, CliError, CliTypedResult, EncodingOptions,
@@ -268,6 +268,7 @@ impl CliTestFramework {
             save_to_profile: None,
             new_private_key_file: None,
             skip_saving_profile: true,
+            rng_args: RngArgs::from_seed([0; 32]),
         }
         .execute()
         .await?;
@@ -544,9 +545,9 @@ impl CliTestFramework {
         .await
     }
 
-    pub async fn init(&self, private_key: &Ed25519PrivateKey) -> CliTypedResult<()> {
+    pub async fn init(&self, private_key: &Ed25519PrivateKey) -> CliTypedResult<InitResult> {
         InitTool {
-            network: Some(Network::Custom),
+            network: Some(Network::Custom.to_string()),
             rest_url: Some(self.endpoint.clone()),
             faucet_url: Some(self.faucet_endpoint.clone()),
             faucet_auth_token: None,
@@ -557,7 +558,12 @@ impl CliTestFramework {
             encoding_options: EncodingOptions::default(),
             skip_faucet: false,
             ledger: false,
+            from_mnemonic: false,
+            funder_profile: None,
+            keyless: false,
+            multisig_address: None,
             hardware_wallet_options: Default::default(),
+            keyless_options: Default::default(),
         }
         .execute()
         .await
@@ -22,10 +22,18 @@ async fn ensure_every_command_args_work() {
     assert_cmd_not_panic(&["aptos", "account", "lookup-address", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "account", "rotate-key", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "account", "transfer", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "account", "transfer-batch", "--help"]).await;
 
     assert_cmd_not_panic(&["aptos", "config"]).await;
+    assert_cmd_not_panic(&["aptos", "config", "export-profile", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "config", "generate-shell-completions", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "config", "import-profile", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "config", "init", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "config", "network", "add", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "config", "network", "list", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "config", "network", "remove", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "config", "rename-profile", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "config", "set-gas-defaults", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "config", "set-global-config", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "config", "show-global-config"]).await;
     assert_cmd_not_panic(&["aptos", "config", "show-profiles"]).await;
@@ -61,6 +69,7 @@ async fn ensure_every_command_args_work() {
     assert_cmd_not_panic(&["aptos", "key"]).await;
     assert_cmd_not_panic(&["aptos", "key", "generate", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "key", "extract-peer", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "key", "derive", "--help"]).await;
 
     assert_cmd_not_panic(&["aptos", "move"]).await;
     assert_cmd_not_panic(&["aptos", "move", "clean", "--help"]).await;
@@ -75,10 +84,19 @@ async fn ensure_every_command_args_work() {
     assert_cmd_not_panic(&["aptos", "move", "publish", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "run", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "run-script", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "move", "simulate", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "move", "simulate-script", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "test", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "transactional-test", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "view", "--help"]).await;
 
+    assert_cmd_not_panic(&["aptos", "multisig"]).await;
+    assert_cmd_not_panic(&["aptos", "multisig", "approve", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "multisig", "create-transaction", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "multisig", "execute", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "multisig", "propose", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "multisig", "status", "--help"]).await;
+
     assert_cmd_not_panic(&["aptos", "node"]).await;
     assert_cmd_not_panic(&["aptos", "node", "check-network-connectivity", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "node", "get-stake-pool", "--help"]).await;
@@ -108,6 +126,11 @@ async fn ensure_every_command_args_work() {
     assert_cmd_not_panic(&["aptos", "stake", "set-operator", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "stake", "unlock-stake", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "stake", "withdraw-stake", "--help"]).await;
+
+    assert_cmd_not_panic(&["aptos", "transaction"]).await;
+    assert_cmd_not_panic(&["aptos", "transaction", "build", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "transaction", "sign", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "transaction", "submit", "--help"]).await;
 }
 
 /// Ensure we can parse URLs for args
@@ -0,0 +1,191 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{
+        account_address_from_public_key, CliCommand, CliError, CliResult, CliTypedResult,
+        EncodingOptions, EntryFunctionArguments, PrivateKeyInputOptions, ProfileOptions,
+        PromptOptions, RestOptions, TransactionOptions, TransactionSummary,
+    },
+    utils::{check_if_file_exists, prompt_yes_with_override, read_from_file, write_to_file},
+};
+use aptos_crypto::PrivateKey;
+use aptos_types::transaction::{RawTransaction, SignedTransaction, TransactionPayload};
+use async_trait::async_trait;
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+
+/// Tool for signing transactions offline
+///
+/// A transaction is built on a connected machine, signed on a machine holding the key (which
+/// can be air-gapped), and submitted back from the connected machine.
+#[derive(Subcommand)]
+pub enum TransactionTool {
+    Build(BuildTransaction),
+    Sign(SignTransaction),
+    Submit(SubmitTransaction),
+}
+
+impl TransactionTool {
+    pub async fn execute(self) -> CliResult {
+        use TransactionTool::*;
+        match self {
+            Build(tool) => tool.execute_serialized().await,
+            Sign(tool) => tool.execute_serialized().await,
+            Submit(tool) => tool.execute_serialized().await,
+        }
+    }
+}
+
+/// Build an unsigned entry function transaction
+///
+/// The transaction is written BCS encoded to `--output`, to be signed with
+/// `aptos transaction sign`.  Only the public key of the sender is needed, e.g. from the
+/// profile, and the transaction has to be signed and submitted before `--expiration-secs`.
+#[derive(Parser)]
+pub struct BuildTransaction {
+    /// Output file for the unsigned transaction
+    #[clap(long, value_parser)]
+    pub(crate) output: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) entry_function_args: EntryFunctionArguments,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<RawTransaction> for BuildTransaction {
+    fn command_name(&self) -> &'static str {
+        "BuildTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<RawTransaction> {
+        check_if_file_exists(&self.output, self.txn_options.prompt_options)?;
+        let raw_transaction = self
+            .txn_options
+            .build_transaction(TransactionPayload::EntryFunction(
+                self.entry_function_args.try_into()?,
+            ))
+            .await?;
+        write_to_file(
+            &self.output,
+            "Unsigned transaction",
+            &bcs::to_bytes(&raw_transaction)?,
+        )?;
+        Ok(raw_transaction)
+    }
+}
+
+/// Sign a transaction built with `aptos transaction build`
+///
+/// This doesn't connect to the network, so it can be run on an air-gapped machine.  The
+/// signed transaction is written BCS encoded to `--output`, to be submitted with
+/// `aptos transaction submit`.
+#[derive(Parser)]
+pub struct SignTransaction {
+    /// Input file of the unsigned transaction
+    #[clap(long, value_parser)]
+    pub(crate) input: PathBuf,
+
+    /// Output file for the signed transaction
+    #[clap(long, value_parser)]
+    pub(crate) output: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) private_key_options: PrivateKeyInputOptions,
+    #[clap(flatten)]
+    pub(crate) encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<SignedTransaction> for SignTransaction {
+    fn command_name(&self) -> &'static str {
+        "SignTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<SignedTransaction> {
+        check_if_file_exists(&self.output, self.prompt_options)?;
+        let raw_transaction: RawTransaction = read_bcs_file(&self.input)?;
+        let private_key = self
+            .private_key_options
+            .extract_private_key(self.encoding_options.encoding, &self.profile_options)?;
+
+        // The sender may have rotated its key, so a different address is only a warning
+        let signer_address = account_address_from_public_key(&private_key.public_key());
+        if signer_address != raw_transaction.sender() {
+            eprintln!(
+                "The key is for account {}, which is not the sender {} (ignore if the sender's key was rotated)",
+                signer_address,
+                raw_transaction.sender()
+            );
+        }
+
+        let transaction_json = serde_json::to_string_pretty(&raw_transaction)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        prompt_yes_with_override(
+            &format!("{}\nDo you want to sign the transaction?", transaction_json),
+            self.prompt_options,
+        )?;
+
+        let signed_transaction = raw_transaction
+            .sign(&private_key, private_key.public_key())
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+            .into_inner();
+        write_to_file(
+            &self.output,
+            "Signed transaction",
+            &bcs::to_bytes(&signed_transaction)?,
+        )?;
+        Ok(signed_transaction)
+    }
+}
+
+/// Submit a transaction signed with `aptos transaction sign`
+#[derive(Parser)]
+pub struct SubmitTransaction {
+    /// Input file of the signed transaction
+    #[clap(long, value_parser)]
+    pub(crate) signed: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for SubmitTransaction {
+    fn command_name(&self) -> &'static str {
+        "SubmitTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let signed_transaction: SignedTransaction = read_bcs_file(&self.signed)?;
+
+        // Catch corrupted files before they reach the network
+        signed_transaction
+            .clone()
+            .check_signature()
+            .map_err(|err| {
+                CliError::CommandArgumentError(format!("Invalid transaction signature: {}", err))
+            })?;
+
+        let client = self.rest_options.client(&self.profile_options)?;
+        let response = client
+            .submit_and_wait(&signed_transaction)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        Ok(response.into_inner().into())
+    }
+}
+
+/// Reads a BCS encoded file
+fn read_bcs_file<T: serde::de::DeserializeOwned>(path: &Path) -> CliTypedResult<T> {
+    bcs::from_bytes(&read_from_file(path)?)
+        .map_err(|err| CliError::UnableToParse("transaction file", err.to_string()))
+}
@@ -122,6 +122,11 @@ pub struct EmitArgs {
     #[clap(long)]
     pub target_tps: Option<usize>,
 
+    /// Send transactions at target_tps as a Poisson process (open-loop), instead of at evenly
+    /// spread times, and report the queueing delay and dropped submissions under overload.
+    #[clap(long, requires = "target_tps")]
+    pub poisson_arrivals: bool,
+
     #[clap(long, default_value_t = 30)]
     pub txn_expiration_time_secs: u64,
 
@@ -76,6 +76,9 @@ pub struct EmitModeParams {
     pub wait_millis: u64,
     pub check_account_sequence_only_once_fraction: f32,
     pub check_account_sequence_sleep: Duration,
+    /// Whether each worker waits an exponentially distributed time between its batches,
+    /// instead of wait_millis, so that batches arrive as a Poisson process.
+    pub poisson_arrivals: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -99,21 +102,39 @@ pub enum EmitJobMode {
         wave_ratio: f32,
         num_waves: usize,
     },
+    /// Open-loop load, with batches arriving as a Poisson process at the given average rate,
+    /// regardless of how fast transactions are committed. Arrivals that can't be submitted in
+    /// time are delayed (see queueing delay in stats), or dropped, rather than slowing down
+    /// the following ones, so the load can be kept above what the network can handle.
+    PoissonTps {
+        tps: usize,
+    },
 }
 
 impl EmitJobMode {
-    pub fn create(mempool_backlog: Option<usize>, target_tps: Option<usize>) -> Self {
+    pub fn create(
+        mempool_backlog: Option<usize>,
+        target_tps: Option<usize>,
+        poisson_arrivals: bool,
+    ) -> Self {
         if let Some(mempool_backlog_val) = mempool_backlog {
             assert!(
                 target_tps.is_none(),
                 "Cannot set both mempool_backlog and target_tps"
             );
+            assert!(
+                !poisson_arrivals,
+                "Poisson arrivals can only be used with target_tps"
+            );
             Self::MaxLoad {
                 mempool_backlog: mempool_backlog_val,
             }
         } else {
-            Self::ConstTps {
-                tps: target_tps.expect("Need to set either mempool_backlog or target_tps"),
+            let tps = target_tps.expect("Need to set either mempool_backlog or target_tps");
+            if poisson_arrivals {
+                Self::PoissonTps { tps }
+            } else {
+                Self::ConstTps { tps }
             }
         }
     }
@@ -416,12 +437,14 @@ impl EmitJobRequest {
                     endpoints: clients_count,
                     check_account_sequence_only_once_fraction: 0.0,
                     check_account_sequence_sleep: self.latency_polling_interval,
+                    poisson_arrivals: false,
                 }
             },
             EmitJobMode::ConstTps { tps }
             | EmitJobMode::WaveTps {
                 average_tps: tps, ..
-            } => {
+            }
+            | EmitJobMode::PoissonTps { tps } => {
                 // We are going to create ConstTps (open-loop) txn-emitter, by:
                 // - having a single worker handle a single account, with:
                 //   - issuing a batch request (which generally either suceeeds or fails)
@@ -436,6 +459,8 @@ impl EmitJobRequest {
                 //
                 // That's why we set wait_seconds conservativelly, to make sure all processing and
                 // client calls finish within that time.
+                // With PoissonTps, wait_seconds is only the average time between batches of a
+                // worker, so some batches arrive before the previous ones are processed.
 
                 let wait_seconds = self.txn_expiration_time_secs + 180;
                 // In case we set a very low TPS, we need to still be able to spread out
@@ -505,6 +530,7 @@ impl EmitJobRequest {
                     endpoints: clients_count,
                     check_account_sequence_only_once_fraction: 1.0 - sample_latency_fraction,
                     check_account_sequence_sleep: self.latency_polling_interval,
+                    poisson_arrivals: matches!(self.mode, EmitJobMode::PoissonTps { .. }),
                 }
             },
         }
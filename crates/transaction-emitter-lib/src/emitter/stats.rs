@@ -17,35 +17,44 @@ pub struct TxnStats {
     pub committed: u64,
     pub expired: u64,
     pub failed_submission: u64,
-    pub latency: u64,         // total milliseconds across all latency measurements
+    pub dropped: u64, // open-loop arrivals not submitted, as the worker was still busy
+    pub latency: u64, // total milliseconds across all latency measurements
     pub latency_samples: u64, // number of events with latency measured
     pub latency_buckets: AtomicHistogramSnapshot, // millisecond snapshot buckets
+    pub queueing_delay: u64, // total milliseconds between arrival and submission
+    pub queueing_delay_samples: u64, // number of events with queueing delay measured
+    pub queueing_delay_buckets: AtomicHistogramSnapshot, // millisecond snapshot buckets
     pub lasted: Duration,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct TxnStatsRate {
-    pub submitted: f64,         // per second
-    pub committed: f64,         // per second
-    pub expired: f64,           // per second
-    pub failed_submission: f64, // per second
-    pub latency: f64,           // mean latency (milliseconds)
-    pub latency_samples: u64,   // number latency-measured events
-    pub p50_latency: u64,       // milliseconds, 50% this or better
-    pub p90_latency: u64,       // milliseconds, 90% this or better
-    pub p99_latency: u64,       // milliseconds, 99% this or better
+    pub submitted: f64,          // per second
+    pub committed: f64,          // per second
+    pub expired: f64,            // per second
+    pub failed_submission: f64,  // per second
+    pub dropped: f64,            // per second
+    pub latency: f64,            // mean latency (milliseconds)
+    pub latency_samples: u64,    // number latency-measured events
+    pub p50_latency: u64,        // milliseconds, 50% this or better
+    pub p90_latency: u64,        // milliseconds, 90% this or better
+    pub p99_latency: u64,        // milliseconds, 99% this or better
+    pub queueing_delay: f64,     // mean queueing delay (milliseconds)
+    pub p99_queueing_delay: u64, // milliseconds, 99% this or better
 }
 
 impl fmt::Display for TxnStatsRate {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "committed: {} txn/s{}{}{}, latency: {} ms, (p50: {} ms, p90: {} ms, p99: {} ms), latency samples: {}",
+            "committed: {} txn/s{}{}{}{}, latency: {} ms, (p50: {} ms, p90: {} ms, p99: {} ms), latency samples: {}{}",
             self.committed,
             if self.submitted != self.committed { format!(", submitted: {} txn/s", self.submitted) } else { "".to_string()},
             if self.failed_submission != 0.0 { format!(", failed submission: {} txn/s", self.failed_submission) } else { "".to_string()},
             if self.expired != 0.0 { format!(", expired: {} txn/s", self.expired) } else { "".to_string()},
+            if self.dropped != 0.0 { format!(", dropped: {} txn/s", self.dropped) } else { "".to_string()},
             self.latency, self.p50_latency, self.p90_latency, self.p99_latency, self.latency_samples,
+            if self.queueing_delay != 0.0 { format!(", queueing delay: {} ms (p99: {} ms)", self.queueing_delay, self.p99_queueing_delay) } else { "".to_string()},
         )
     }
 }
@@ -58,6 +67,7 @@ impl TxnStats {
             committed: (self.committed as f64) / window_secs,
             expired: (self.expired as f64) / window_secs,
             failed_submission: (self.failed_submission as f64) / window_secs,
+            dropped: (self.dropped as f64) / window_secs,
             latency: if self.latency_samples == 0 {
                 0.0
             } else {
@@ -67,6 +77,12 @@ impl TxnStats {
             p50_latency: self.latency_buckets.percentile(50, 100),
             p90_latency: self.latency_buckets.percentile(90, 100),
             p99_latency: self.latency_buckets.percentile(99, 100),
+            queueing_delay: if self.queueing_delay_samples == 0 {
+                0.0
+            } else {
+                (self.queueing_delay as f64) / (self.queueing_delay_samples as f64)
+            },
+            p99_queueing_delay: self.queueing_delay_buckets.percentile(99, 100),
         }
     }
 }
@@ -75,8 +91,8 @@ impl fmt::Display for TxnStats {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "submitted: {}, committed: {}, expired: {}, failed submission: {}",
-            self.submitted, self.committed, self.expired, self.failed_submission,
+            "submitted: {}, committed: {}, expired: {}, failed submission: {}, dropped: {}",
+            self.submitted, self.committed, self.expired, self.failed_submission, self.dropped,
         )
     }
 }
@@ -90,9 +106,13 @@ impl Sub for &TxnStats {
             committed: self.committed - other.committed,
             expired: self.expired - other.expired,
             failed_submission: self.failed_submission - other.failed_submission,
+            dropped: self.dropped - other.dropped,
             latency: self.latency - other.latency,
             latency_samples: self.latency_samples - other.latency_samples,
             latency_buckets: &self.latency_buckets - &other.latency_buckets,
+            queueing_delay: self.queueing_delay - other.queueing_delay,
+            queueing_delay_samples: self.queueing_delay_samples - other.queueing_delay_samples,
+            queueing_delay_buckets: &self.queueing_delay_buckets - &other.queueing_delay_buckets,
             lasted: self.lasted - other.lasted,
         }
     }
@@ -107,9 +127,13 @@ impl Add for &TxnStats {
             committed: self.committed + other.committed,
             expired: self.expired + other.expired,
             failed_submission: self.failed_submission + other.failed_submission,
+            dropped: self.dropped + other.dropped,
             latency: self.latency + other.latency,
             latency_samples: self.latency_samples + other.latency_samples,
             latency_buckets: &self.latency_buckets + &other.latency_buckets,
+            queueing_delay: self.queueing_delay + other.queueing_delay,
+            queueing_delay_samples: self.queueing_delay_samples + other.queueing_delay_samples,
+            queueing_delay_buckets: &self.queueing_delay_buckets + &other.queueing_delay_buckets,
             lasted: self.lasted + other.lasted,
         }
     }
@@ -121,9 +145,13 @@ pub struct StatsAccumulator {
     pub committed: AtomicU64,
     pub expired: AtomicU64,
     pub failed_submission: AtomicU64,
+    pub dropped: AtomicU64,
     pub latency: AtomicU64, // total milliseconds across all latency measurements
     pub latency_samples: AtomicU64, // number of events with latency measured
     pub latencies: Arc<AtomicHistogramAccumulator>, // millisecond histogram buckets
+    pub queueing_delay: AtomicU64, // total milliseconds across all queueing delay measurements
+    pub queueing_delay_samples: AtomicU64, // number of events with queueing delay measured
+    pub queueing_delays: Arc<AtomicHistogramAccumulator>, // millisecond histogram buckets
 }
 
 impl StatsAccumulator {
@@ -133,9 +161,13 @@ impl StatsAccumulator {
             committed: self.committed.load(Ordering::Relaxed),
             expired: self.expired.load(Ordering::Relaxed),
             failed_submission: self.failed_submission.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
             latency: self.latency.load(Ordering::Relaxed),
             latency_samples: self.latency_samples.load(Ordering::Relaxed),
             latency_buckets: self.latencies.snapshot(),
+            queueing_delay: self.queueing_delay.load(Ordering::Relaxed),
+            queueing_delay_samples: self.queueing_delay_samples.load(Ordering::Relaxed),
+            queueing_delay_buckets: self.queueing_delays.snapshot(),
             lasted,
         }
     }
@@ -399,9 +431,13 @@ mod test {
             committed: 10,
             expired: 0,
             failed_submission: 0,
+            dropped: 0,
             latency: 0,
             latency_samples: 0,
             latency_buckets: histogram.snapshot(),
+            queueing_delay: 0,
+            queueing_delay_samples: 0,
+            queueing_delay_buckets: AtomicHistogramSnapshot::default(),
             lasted: Duration::from_secs(10),
         };
         let res = stat.latency_buckets.percentile(9, 10);
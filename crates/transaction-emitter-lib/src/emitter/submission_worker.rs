@@ -23,7 +23,7 @@ use core::{
 };
 use futures::future::join_all;
 use itertools::Itertools;
-use rand::seq::IteratorRandom;
+use rand::{seq::IteratorRandom, Rng};
 use std::{
     collections::HashMap,
     sync::{atomic::AtomicU64, Arc},
@@ -83,7 +83,15 @@ impl SubmissionWorker {
             let loop_stats = stats_clone.get_cur();
 
             let loop_start_time = Instant::now();
-            if wait_duration.as_secs() > 0
+            // When arrivals are open-loop, the time the batch should have been sent at counts
+            // towards its latency, not only the time since it was actually sent.
+            let arrival_time = if self.params.poisson_arrivals {
+                min(wait_until, loop_start_time)
+            } else {
+                loop_start_time
+            };
+            if !self.params.poisson_arrivals
+                && wait_duration.as_secs() > 0
                 && loop_start_time.duration_since(wait_until) > Duration::from_secs(5)
             {
                 sample!(
@@ -95,10 +103,32 @@ impl SubmissionWorker {
                     )
                 );
             }
-            // always add expected cycle duration, to not drift from expected pace.
-            wait_until += wait_duration;
+            let num_dropped = if self.params.poisson_arrivals {
+                self.schedule_next_arrival(&mut wait_until, wait_duration, loop_start_time)
+            } else {
+                // always add expected cycle duration, to not drift from expected pace.
+                wait_until += wait_duration;
+                0
+            };
 
             let requests = self.gen_requests();
+            if self.params.poisson_arrivals {
+                let queueing_delay =
+                    loop_start_time.duration_since(arrival_time).as_millis() as u64;
+                let num_requests = requests.len() as u64;
+                loop_stats
+                    .queueing_delay
+                    .fetch_add(queueing_delay * num_requests, Ordering::Relaxed);
+                loop_stats
+                    .queueing_delay_samples
+                    .fetch_add(num_requests, Ordering::Relaxed);
+                loop_stats
+                    .queueing_delays
+                    .record_data_point(queueing_delay, num_requests);
+                loop_stats
+                    .dropped
+                    .fetch_add(num_dropped * num_requests, Ordering::Relaxed);
+            }
             if !requests.is_empty() {
                 let mut account_to_start_and_end_seq_num = HashMap::new();
                 for req in requests.iter() {
@@ -172,7 +202,7 @@ impl SubmissionWorker {
                 }
 
                 self.wait_and_update_stats(
-                    loop_start_time,
+                    arrival_time,
                     txn_offset_time.load(Ordering::Relaxed) / (requests.len() as u64),
                     account_to_start_and_end_seq_num,
                     // skip latency if asked to check seq_num only once
@@ -202,6 +232,27 @@ impl SubmissionWorker {
         self.accounts
     }
 
+    /// Moves `wait_until` to the next arrival after `now`, with exponentially distributed times
+    /// between arrivals, averaging `wait_duration`. Only one arrival can be waiting while the
+    /// worker is busy, so returns the number of arrivals skipped over, which are dropped.
+    fn schedule_next_arrival(
+        &mut self,
+        wait_until: &mut Instant,
+        wait_duration: Duration,
+        now: Instant,
+    ) -> u64 {
+        let mut num_dropped = 0;
+        loop {
+            // Inverse transform sampling of the exponential distribution
+            let uniform: f64 = self.rng.gen_range(f64::EPSILON, 1.0);
+            *wait_until += wait_duration.mul_f64(-uniform.ln());
+            if *wait_until > now {
+                return num_dropped;
+            }
+            num_dropped += 1;
+        }
+    }
+
     // returns true if it returned early
     async fn sleep_check_done(&self, duration: Duration) {
         let start_time = Instant::now();
@@ -70,7 +70,8 @@ pub async fn emit_transactions_with_cluster(
     cluster: &Cluster,
     args: &EmitArgs,
 ) -> Result<TxnStats> {
-    let emitter_mode = EmitJobMode::create(args.mempool_backlog, args.target_tps);
+    let emitter_mode =
+        EmitJobMode::create(args.mempool_backlog, args.target_tps, args.poisson_arrivals);
 
     let duration = Duration::from_secs(args.duration);
     let client = cluster.random_instance().rest_client();
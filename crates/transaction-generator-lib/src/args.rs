@@ -20,6 +20,8 @@ pub enum TransactionTypeArg {
     AccountGenerationLargePool,
     Batch100Transfer,
     PublishPackage,
+    LargeEvents1KB,
+    LargeEvents10KB,
     // Simple EntryPoints
     NoOp,
     NoOpFeePayer,
@@ -115,6 +117,14 @@ impl TransactionTypeArg {
             TransactionTypeArg::Batch100Transfer => {
                 TransactionType::BatchTransfer { batch_size: 100 }
             },
+            TransactionTypeArg::LargeEvents1KB => TransactionType::LargeEvents {
+                event_size_bytes: 1024,
+                use_account_pool: sender_use_account_pool,
+            },
+            TransactionTypeArg::LargeEvents10KB => TransactionType::LargeEvents {
+                event_size_bytes: 10 * 1024,
+                use_account_pool: sender_use_account_pool,
+            },
             TransactionTypeArg::AccountResource32B => TransactionType::CallCustomModules {
                 entry_point: EntryPoints::BytesMakeOrChange {
                     data_length: Some(32),
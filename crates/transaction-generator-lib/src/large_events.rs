@@ -0,0 +1,95 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{TransactionGenerator, TransactionGeneratorCreator};
+use aptos_sdk::{
+    bcs,
+    move_types::{account_address::AccountAddress, ident_str, language_storage::ModuleId},
+    transaction_builder::TransactionFactory,
+    types::{
+        transaction::{EntryFunction, SignedTransaction, TransactionPayload},
+        LocalAccount,
+    },
+};
+use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+
+/// Creates a token v1 collection per transaction, which stores its description, and emits it in
+/// the collection creation event, so the size of the description sets the size of the event.
+pub struct LargeEventsTransactionGenerator {
+    rng: StdRng,
+    event_size_bytes: usize,
+    txn_factory: TransactionFactory,
+}
+
+impl LargeEventsTransactionGenerator {
+    pub fn new(rng: StdRng, event_size_bytes: usize, txn_factory: TransactionFactory) -> Self {
+        Self {
+            rng,
+            event_size_bytes,
+            txn_factory,
+        }
+    }
+
+    fn create_collection_payload(&mut self) -> TransactionPayload {
+        // Collection names must be unique per creator
+        let name: Vec<u8> = (&mut self.rng).sample_iter(Alphanumeric).take(32).collect();
+        let description: Vec<u8> = (&mut self.rng)
+            .sample_iter(Alphanumeric)
+            .take(self.event_size_bytes)
+            .collect();
+        let uri: Vec<u8> = vec![];
+        let maximum: u64 = 0;
+        let mutate_setting = vec![false, false, false];
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            ModuleId::new(AccountAddress::THREE, ident_str!("token").to_owned()),
+            ident_str!("create_collection_script").to_owned(),
+            vec![],
+            vec![
+                bcs::to_bytes(&name).unwrap(),
+                bcs::to_bytes(&description).unwrap(),
+                bcs::to_bytes(&uri).unwrap(),
+                bcs::to_bytes(&maximum).unwrap(),
+                bcs::to_bytes(&mutate_setting).unwrap(),
+            ],
+        ))
+    }
+}
+
+impl TransactionGenerator for LargeEventsTransactionGenerator {
+    fn generate_transactions(
+        &mut self,
+        account: &LocalAccount,
+        num_to_create: usize,
+    ) -> Vec<SignedTransaction> {
+        let mut requests = Vec::with_capacity(num_to_create);
+        for _ in 0..num_to_create {
+            let payload = self.create_collection_payload();
+            requests.push(account.sign_with_transaction_builder(self.txn_factory.payload(payload)));
+        }
+        requests
+    }
+}
+
+pub struct LargeEventsTransactionGeneratorCreator {
+    txn_factory: TransactionFactory,
+    event_size_bytes: usize,
+}
+
+impl LargeEventsTransactionGeneratorCreator {
+    pub fn new(txn_factory: TransactionFactory, event_size_bytes: usize) -> Self {
+        Self {
+            txn_factory,
+            event_size_bytes,
+        }
+    }
+}
+
+impl TransactionGeneratorCreator for LargeEventsTransactionGeneratorCreator {
+    fn create_transaction_generator(&self) -> Box<dyn TransactionGenerator> {
+        Box::new(LargeEventsTransactionGenerator::new(
+            StdRng::from_entropy(),
+            self.event_size_bytes,
+            self.txn_factory.clone(),
+        ))
+    }
+}
@@ -32,6 +32,7 @@ mod batch_transfer;
 mod bounded_batch_wrapper;
 mod call_custom_modules;
 mod entry_points;
+mod large_events;
 mod p2p_transaction_generator;
 pub mod publish_modules;
 pub mod publishing;
@@ -47,7 +48,8 @@ use self::{
 use crate::{
     accounts_pool_wrapper::AccountsPoolWrapperCreator,
     batch_transfer::BatchTransferTransactionGeneratorCreator,
-    entry_points::EntryPointTransactionGenerator, p2p_transaction_generator::SamplingMode,
+    entry_points::EntryPointTransactionGenerator,
+    large_events::LargeEventsTransactionGeneratorCreator, p2p_transaction_generator::SamplingMode,
     workflow_delegator::WorkflowTxnGeneratorCreator,
 };
 pub use publishing::module_simple::EntryPoints;
@@ -80,6 +82,10 @@ pub enum TransactionType {
     BatchTransfer {
         batch_size: usize,
     },
+    LargeEvents {
+        event_size_bytes: usize,
+        use_account_pool: bool,
+    },
     Workflow {
         workflow_kind: WorkflowKind,
         num_modules: usize,
@@ -363,6 +369,17 @@ pub async fn create_txn_generator_creator(
                         *batch_size,
                     ))
                 },
+                TransactionType::LargeEvents {
+                    event_size_bytes,
+                    use_account_pool,
+                } => wrap_accounts_pool(
+                    Box::new(LargeEventsTransactionGeneratorCreator::new(
+                        txn_factory.clone(),
+                        *event_size_bytes,
+                    )),
+                    *use_account_pool,
+                    &accounts_pool,
+                ),
                 TransactionType::Workflow {
                     num_modules,
                     use_account_pool,
@@ -1,9 +1,12 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::service::RawDataServerWrapper;
+use crate::{resume_token::ResumeTokenSigner, service::RawDataServerWrapper};
 use anyhow::{bail, Result};
-use aptos_indexer_grpc_server_framework::RunnableConfig;
+use aptos_indexer_grpc_server_framework::{
+    auth::{AuthConfig, AuthInterceptor},
+    RunnableConfig,
+};
 use aptos_indexer_grpc_utils::{
     compression_util::StorageFormat, config::IndexerGrpcFileStoreConfig,
     in_memory_cache::InMemoryCacheConfig, types::RedisUrl,
@@ -15,7 +18,9 @@ use aptos_protos::{
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashSet, net::SocketAddr, sync::Arc};
-use tonic::{codec::CompressionEncoding, transport::Server};
+use tonic::{
+    codec::CompressionEncoding, service::interceptor::InterceptedService, transport::Server,
+};
 
 pub const SERVER_NAME: &str = "idxdatasvc";
 
@@ -28,6 +33,9 @@ const DEFAULT_MAX_RESPONSE_CHANNEL_SIZE: usize = 3;
 const HTTP2_PING_INTERVAL_DURATION: std::time::Duration = std::time::Duration::from_secs(60);
 const HTTP2_PING_TIMEOUT_DURATION: std::time::Duration = std::time::Duration::from_secs(10);
 
+// Resume tokens are signed with HMAC-SHA256, so the secret should be as long as its output.
+const MIN_RESUME_TOKEN_SECRET_LENGTH: usize = 32;
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct TlsConfig {
@@ -60,6 +68,10 @@ pub struct IndexerGrpcDataServiceConfig {
     /// Deprecated: if set, don't check for auth tokens.
     #[serde(default)]
     pub disable_auth_check: bool,
+    /// If given, requests must be authenticated with an API key or JWT from this config, and
+    /// are rate limited per key. Otherwise, authentication is left to an API gateway.
+    #[serde(default)]
+    pub auth_config: Option<AuthConfig>,
     /// File store config.
     pub file_store_config: IndexerGrpcFileStoreConfig,
     /// Redis read replica address.
@@ -72,6 +84,10 @@ pub struct IndexerGrpcDataServiceConfig {
     /// Sender addresses to ignore. Transactions from these addresses will not be indexed.
     #[serde(default = "IndexerGrpcDataServiceConfig::default_sender_addresses_to_ignore")]
     pub sender_addresses_to_ignore: Vec<String>,
+    /// If given, responses carry resume tokens signed with this secret. It must be the same for
+    /// all the data services, so that a stream can be resumed by any of them.
+    #[serde(default)]
+    pub resume_token_secret: Option<String>,
 }
 
 impl IndexerGrpcDataServiceConfig {
@@ -93,11 +109,13 @@ impl IndexerGrpcDataServiceConfig {
                 .unwrap_or_else(Self::default_data_service_response_channel_size),
             whitelisted_auth_tokens: vec![],
             disable_auth_check,
+            auth_config: None,
             file_store_config,
             redis_read_replica_address,
             enable_cache_compression,
             in_memory_cache_config,
             sender_addresses_to_ignore,
+            resume_token_secret: None,
         }
     }
 
@@ -123,6 +141,17 @@ impl RunnableConfig for IndexerGrpcDataServiceConfig {
             bail!("At least one of data_service_grpc_non_tls_config and data_service_grpc_tls_config must be set");
         }
         self.in_memory_cache_config.validate()?;
+        if let Some(auth_config) = &self.auth_config {
+            auth_config.validate()?;
+        }
+        if let Some(secret) = &self.resume_token_secret {
+            if secret.len() < MIN_RESUME_TOKEN_SECRET_LENGTH {
+                bail!(
+                    "resume_token_secret must be at least {} bytes long",
+                    MIN_RESUME_TOKEN_SECRET_LENGTH
+                );
+            }
+        }
         Ok(())
     }
 
@@ -157,7 +186,6 @@ impl RunnableConfig for IndexerGrpcDataServiceConfig {
                 cache_storage_format,
             )
             .await?;
-        // Add authentication interceptor.
         let server = RawDataServerWrapper::new(
             self.redis_read_replica_address.clone(),
             self.file_store_config.clone(),
@@ -168,11 +196,15 @@ impl RunnableConfig for IndexerGrpcDataServiceConfig {
                 .collect::<HashSet<_>>(),
             cache_storage_format,
             Arc::new(in_memory_cache),
+            self.resume_token_secret
+                .as_deref()
+                .map(|secret| Arc::new(ResumeTokenSigner::new(secret))),
         )?;
         let svc = aptos_protos::indexer::v1::raw_data_server::RawDataServer::new(server)
             .send_compressed(CompressionEncoding::Gzip)
             .accept_compressed(CompressionEncoding::Gzip)
             .accept_compressed(CompressionEncoding::Zstd);
+        let svc = InterceptedService::new(svc, AuthInterceptor::new(self.auth_config.as_ref()));
         let svc_clone = svc.clone();
         let reflection_service_clone = reflection_service.clone();
 
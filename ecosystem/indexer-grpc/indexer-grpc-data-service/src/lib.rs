@@ -3,6 +3,8 @@
 
 mod config;
 mod metrics;
+mod resume_token;
 mod service;
+mod transaction_filter;
 
 pub use config::{IndexerGrpcDataServiceConfig, NonTlsConfig, SERVER_NAME};
@@ -0,0 +1,123 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resume tokens let clients reconnect, e.g. after a data service restart, and resume the stream
+//! right after the last response they received, without tracking versions themselves. Tokens
+//! are signed with a secret shared by all the data services, so any of them can resume a stream,
+//! and clients can't forge them.
+
+use anyhow::{ensure, format_err, Context, Result};
+use hmac::{Hmac, Mac};
+use sha2_0_10_6::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Changed whenever the layout of the token changes, so that older tokens are rejected.
+const RESUME_TOKEN_FORMAT_VERSION: u8 = 1;
+// Format version, chain id and the next version to stream.
+const PAYLOAD_LENGTH: usize = 1 + 8 + 8;
+const SIGNATURE_LENGTH: usize = 32;
+
+/// Where to resume a stream.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResumePosition {
+    pub chain_id: u64,
+    pub next_version: u64,
+}
+
+pub struct ResumeTokenSigner {
+    secret: Vec<u8>,
+}
+
+impl ResumeTokenSigner {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            secret: secret.as_bytes().to_vec(),
+        }
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.secret).expect("HMAC can take a key of any size")
+    }
+
+    /// Issues a token to resume the stream of `chain_id` at `next_version`.
+    pub fn issue(&self, chain_id: u64, next_version: u64) -> String {
+        let mut token = Vec::with_capacity(PAYLOAD_LENGTH + SIGNATURE_LENGTH);
+        token.push(RESUME_TOKEN_FORMAT_VERSION);
+        token.extend_from_slice(&chain_id.to_be_bytes());
+        token.extend_from_slice(&next_version.to_be_bytes());
+        let mut mac = self.mac();
+        mac.update(&token);
+        token.extend_from_slice(&mac.finalize().into_bytes());
+        base64::encode_config(token, base64::URL_SAFE_NO_PAD)
+    }
+
+    /// Checks that the token was issued with the same secret, and returns where to resume.
+    pub fn verify(&self, token: &str) -> Result<ResumePosition> {
+        let token = base64::decode_config(token, base64::URL_SAFE_NO_PAD)
+            .context("Resume token is not valid base64")?;
+        ensure!(
+            token.len() == PAYLOAD_LENGTH + SIGNATURE_LENGTH,
+            "Resume token has an invalid length"
+        );
+        let (payload, signature) = token.split_at(PAYLOAD_LENGTH);
+        let mut mac = self.mac();
+        mac.update(payload);
+        mac.verify_slice(signature)
+            .map_err(|_| format_err!("Resume token has an invalid signature"))?;
+        ensure!(
+            payload[0] == RESUME_TOKEN_FORMAT_VERSION,
+            "Resume token format version {} is not supported",
+            payload[0]
+        );
+        Ok(ResumePosition {
+            chain_id: u64::from_be_bytes(payload[1..9].try_into().unwrap()),
+            next_version: u64::from_be_bytes(payload[9..].try_into().unwrap()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "a secret shared by all the data services";
+
+    #[test]
+    fn test_issue_and_verify() {
+        let signer = ResumeTokenSigner::new(SECRET);
+        let token = signer.issue(1, 123_456_789);
+        assert_eq!(signer.verify(&token).unwrap(), ResumePosition {
+            chain_id: 1,
+            next_version: 123_456_789,
+        });
+        // Another data service, e.g. after a restart, resumes the same stream.
+        let other_signer = ResumeTokenSigner::new(SECRET);
+        assert_eq!(
+            other_signer.verify(&token).unwrap().next_version,
+            123_456_789
+        );
+    }
+
+    #[test]
+    fn test_invalid_tokens() {
+        let signer = ResumeTokenSigner::new(SECRET);
+        let token = signer.issue(1, 100);
+
+        // Issued with another secret.
+        assert!(ResumeTokenSigner::new("another secret")
+            .verify(&token)
+            .is_err());
+
+        // Tampered with.
+        let mut bytes = base64::decode_config(&token, base64::URL_SAFE_NO_PAD).unwrap();
+        bytes[PAYLOAD_LENGTH - 1] ^= 1;
+        let tampered = base64::encode_config(bytes, base64::URL_SAFE_NO_PAD);
+        assert!(signer.verify(&tampered).is_err());
+
+        // Truncated, or not a token at all.
+        assert!(signer.verify(&token[..token.len() - 4]).is_err());
+        assert!(signer.verify("100").is_err());
+        assert!(signer.verify("").is_err());
+    }
+}
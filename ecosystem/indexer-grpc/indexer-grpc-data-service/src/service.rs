@@ -1,12 +1,17 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::metrics::{
-    BYTES_READY_TO_TRANSFER_FROM_SERVER, CONNECTION_COUNT, ERROR_COUNT,
-    LATEST_PROCESSED_VERSION_PER_PROCESSOR, PROCESSED_LATENCY_IN_SECS_PER_PROCESSOR,
-    PROCESSED_VERSIONS_COUNT_PER_PROCESSOR, SHORT_CONNECTION_COUNT,
+use crate::{
+    metrics::{
+        BYTES_READY_TO_TRANSFER_FROM_SERVER, CONNECTION_COUNT, ERROR_COUNT,
+        LATEST_PROCESSED_VERSION_PER_PROCESSOR, PROCESSED_LATENCY_IN_SECS_PER_PROCESSOR,
+        PROCESSED_VERSIONS_COUNT_PER_PROCESSOR, SHORT_CONNECTION_COUNT,
+    },
+    resume_token::ResumeTokenSigner,
+    transaction_filter::TransactionFilter,
 };
 use anyhow::{Context, Result};
+use aptos_indexer_grpc_server_framework::auth::AuthIdentity;
 use aptos_indexer_grpc_utils::{
     cache_operator::{CacheBatchGetStatus, CacheCoverageStatus, CacheOperator},
     chunk_transactions,
@@ -14,12 +19,13 @@ use aptos_indexer_grpc_utils::{
     config::IndexerGrpcFileStoreConfig,
     constants::{
         IndexerGrpcRequestMetadata, GRPC_AUTH_TOKEN_HEADER, GRPC_REQUEST_NAME_HEADER,
-        MESSAGE_SIZE_LIMIT, REQUEST_HEADER_APTOS_APPLICATION_NAME, REQUEST_HEADER_APTOS_EMAIL,
-        REQUEST_HEADER_APTOS_IDENTIFIER, REQUEST_HEADER_APTOS_IDENTIFIER_TYPE,
+        GRPC_TRANSACTION_FILTER_HEADER, MESSAGE_SIZE_LIMIT, REQUEST_HEADER_APTOS_APPLICATION_NAME,
+        REQUEST_HEADER_APTOS_EMAIL, REQUEST_HEADER_APTOS_IDENTIFIER,
+        REQUEST_HEADER_APTOS_IDENTIFIER_TYPE,
     },
     counters::{log_grpc_step, IndexerGrpcStep, NUM_MULTI_FETCH_OVERLAPPED_VERSIONS},
     file_store_operator::FileStoreOperator,
-    in_memory_cache::InMemoryCache,
+    in_memory_cache::{CompressedTransaction, InMemoryCache},
     time_diff_since_pb_timestamp_in_secs,
     types::RedisUrl,
 };
@@ -80,6 +86,7 @@ pub struct RawDataServerWrapper {
     pub sender_addresses_to_ignore: HashSet<String>,
     pub cache_storage_format: StorageFormat,
     in_memory_cache: Arc<InMemoryCache>,
+    resume_token_signer: Option<Arc<ResumeTokenSigner>>,
 }
 
 impl RawDataServerWrapper {
@@ -90,6 +97,7 @@ impl RawDataServerWrapper {
         sender_addresses_to_ignore: HashSet<String>,
         cache_storage_format: StorageFormat,
         in_memory_cache: Arc<InMemoryCache>,
+        resume_token_signer: Option<Arc<ResumeTokenSigner>>,
     ) -> anyhow::Result<Self> {
         Ok(Self {
             redis_client: Arc::new(
@@ -102,6 +110,7 @@ impl RawDataServerWrapper {
             sender_addresses_to_ignore,
             cache_storage_format,
             in_memory_cache,
+            resume_token_signer,
         })
     }
 }
@@ -136,19 +145,53 @@ impl RawData for RawDataServerWrapper {
             Ok(request_metadata) => request_metadata,
             _ => return Result::Err(Status::aborted("Invalid request token")),
         };
+        let transaction_filter = match get_transaction_filter(&req) {
+            Ok(transaction_filter) => transaction_filter.map(Arc::new),
+            Err(e) => {
+                return Result::Err(Status::invalid_argument(format!(
+                    "Invalid transaction filter: {:#}",
+                    e
+                )))
+            },
+        };
         CONNECTION_COUNT
             .with_label_values(&request_metadata.get_label_values())
             .inc();
         let request = req.into_inner();
 
+        // A resume token takes precedence over the starting version, which is kept for clients
+        // tracking versions themselves.
+        let resume_position = match (&request.resume_token, &self.resume_token_signer) {
+            (None, _) => None,
+            (Some(_), None) => {
+                return Result::Err(Status::failed_precondition(
+                    "Resume tokens are not enabled on this data service",
+                ))
+            },
+            (Some(resume_token), Some(resume_token_signer)) => {
+                match resume_token_signer.verify(resume_token) {
+                    Ok(resume_position) => Some(resume_position),
+                    Err(e) => {
+                        return Result::Err(Status::invalid_argument(format!(
+                            "Invalid resume token: {:#}",
+                            e
+                        )))
+                    },
+                }
+            },
+        };
+
         let transactions_count = request.transactions_count;
+        let accept_compressed_transactions =
+            request.accept_compressed_transactions.unwrap_or(false);
 
         // Response channel to stream the data to the client.
         let (tx, rx) = channel(self.data_service_response_channel_size);
-        let current_version = match &request.starting_version {
-            Some(version) => *version,
+        let current_version = match (&resume_position, &request.starting_version) {
+            (Some(resume_position), _) => resume_position.next_version,
+            (None, Some(version)) => *version,
             // Live mode if starting version isn't specified
-            None => self
+            (None, None) => self
                 .in_memory_cache
                 .latest_version()
                 .await
@@ -177,6 +220,7 @@ impl RawData for RawDataServerWrapper {
         let request_metadata = Arc::new(request_metadata);
         let sender_addresses_to_ignore = self.sender_addresses_to_ignore.clone();
         let in_memory_cache = self.in_memory_cache.clone();
+        let resume_token_signer = self.resume_token_signer.clone();
         tokio::spawn({
             let request_metadata = request_metadata.clone();
             async move {
@@ -188,6 +232,10 @@ impl RawData for RawDataServerWrapper {
                     transactions_count,
                     tx,
                     sender_addresses_to_ignore,
+                    transaction_filter,
+                    resume_token_signer,
+                    resume_position.map(|resume_position| resume_position.chain_id),
+                    accept_compressed_transactions,
                     current_version,
                     in_memory_cache,
                 )
@@ -376,11 +424,19 @@ async fn data_fetcher_task(
     transactions_count: Option<u64>,
     tx: tokio::sync::mpsc::Sender<Result<TransactionsResponse, Status>>,
     sender_addresses_to_ignore: HashSet<String>,
+    transaction_filter: Option<Arc<TransactionFilter>>,
+    resume_token_signer: Option<Arc<ResumeTokenSigner>>,
+    resume_token_chain_id: Option<u64>,
+    accept_compressed_transactions: bool,
     mut current_version: u64,
     in_memory_cache: Arc<InMemoryCache>,
 ) {
     let mut connection_start_time = Some(std::time::Instant::now());
     let mut transactions_count = transactions_count;
+    // Compressed transactions can only be sent as they are if none of them is filtered out.
+    let send_compressed_transactions = accept_compressed_transactions
+        && transaction_filter.is_none()
+        && sender_addresses_to_ignore.is_empty();
 
     // Establish redis connection
     let conn = match redis_client.get_tokio_connection_manager().await {
@@ -458,76 +514,167 @@ async fn data_fetcher_task(
         return;
     }
 
+    if let Some(resume_token_chain_id) = resume_token_chain_id {
+        if resume_token_chain_id != chain_id {
+            let _result = tx
+                .send_timeout(
+                    Err(Status::invalid_argument(format!(
+                        "[Data Service] Resume token was issued for chain id {}, not {}.",
+                        resume_token_chain_id, chain_id
+                    ))),
+                    RESPONSE_CHANNEL_SEND_TIMEOUT,
+                )
+                .await;
+            return;
+        }
+    }
+
     // Data service metrics.
     let mut tps_calculator = MovingAverage::new(MOVING_AVERAGE_WINDOW_SIZE);
 
     loop {
-        // 1. Fetch data from cache and file store.
-        let transaction_data = match get_data_with_tasks(
-            current_version,
-            transactions_count,
-            chain_id,
-            &mut cache_operator,
-            file_store_operator.clone(),
-            request_metadata.clone(),
-            cache_storage_format,
-            in_memory_cache.clone(),
-        )
-        .await
-        {
-            DataFetchSubTaskResult::BatchSuccess(txns) => txns,
-            DataFetchSubTaskResult::Success(_) => {
-                unreachable!("Fetching from multiple tasks will never return a single vector")
-            },
-            DataFetchSubTaskResult::NoResults => continue,
-        };
-
-        let mut transaction_data = ensure_sequential_transactions(transaction_data);
+        if transactions_count == Some(0) {
+            // End the data stream.
+            // Since the client receives all the data it requested, we don't count it as a short connection.
+            connection_start_time = None;
+            break;
+        }
 
-        // TODO: Unify the truncation logic for start and end.
-        if let Some(count) = transactions_count {
-            if count == 0 {
-                // End the data stream.
-                // Since the client receives all the data it requested, we don't count it as a short connection.
-                connection_start_time = None;
-                break;
-            } else if (count as usize) < transaction_data.len() {
-                // Trim the data to the requested end version.
-                transaction_data.truncate(count as usize);
-                transactions_count = Some(0);
-            } else {
-                transactions_count = Some(count - transaction_data.len() as u64);
-            }
+        // 1. Fetch data from cache and file store. The transactions in the in-memory cache are
+        // sent as they are to the clients accepting compressed transactions.
+        let compressed_transactions = if send_compressed_transactions {
+            in_memory_cache
+                .get_compressed_transactions(current_version)
+                .await
+        } else {
+            vec![]
         };
-        // Note: this is the protobuf encoded transaction size.
-        let bytes_ready_to_transfer = transaction_data
-            .iter()
-            .map(|t| t.encoded_len())
-            .sum::<usize>();
-        BYTES_READY_TO_TRANSFER_FROM_SERVER
-            .with_label_values(&request_metadata.get_label_values())
-            .inc_by(bytes_ready_to_transfer as u64);
+        let (
+            mut resp_items,
+            resp_item_last_versions,
+            current_batch_size,
+            end_of_batch_version,
+            data_latency_in_secs,
+        ) = if !compressed_transactions.is_empty() {
+            let mut compressed_transactions = compressed_transactions;
+            compressed_transactions.truncate(take_transactions_count(
+                &mut transactions_count,
+                compressed_transactions.len(),
+            ));
+            // Note: this is the compressed transaction size.
+            let bytes_ready_to_transfer = compressed_transactions
+                .iter()
+                .map(|t| t.size())
+                .sum::<u64>();
+            BYTES_READY_TO_TRANSFER_FROM_SERVER
+                .with_label_values(&request_metadata.get_label_values())
+                .inc_by(bytes_ready_to_transfer);
+            let current_batch_size = compressed_transactions.len();
+            let end_of_batch_version = current_version + current_batch_size as u64 - 1;
+            let (resp_items, resp_item_last_versions) =
+                get_compressed_transactions_responses_builder(
+                    compressed_transactions,
+                    current_version,
+                    chain_id as u32,
+                );
+            // The timestamp isn't known without decompressing the transactions.
+            (
+                resp_items,
+                resp_item_last_versions,
+                current_batch_size,
+                end_of_batch_version,
+                None,
+            )
+        } else {
+            let transaction_data = match get_data_with_tasks(
+                current_version,
+                transactions_count,
+                chain_id,
+                &mut cache_operator,
+                file_store_operator.clone(),
+                request_metadata.clone(),
+                cache_storage_format,
+                in_memory_cache.clone(),
+            )
+            .await
+            {
+                DataFetchSubTaskResult::BatchSuccess(txns) => txns,
+                DataFetchSubTaskResult::Success(_) => {
+                    unreachable!("Fetching from multiple tasks will never return a single vector")
+                },
+                DataFetchSubTaskResult::NoResults => continue,
+            };
+
+            let mut transaction_data = ensure_sequential_transactions(transaction_data);
+
+            // Trim the data to the requested end version.
+            transaction_data.truncate(take_transactions_count(
+                &mut transactions_count,
+                transaction_data.len(),
+            ));
+            // Note: this is the protobuf encoded transaction size.
+            let bytes_ready_to_transfer = transaction_data
+                .iter()
+                .map(|t| t.encoded_len())
+                .sum::<usize>();
+            BYTES_READY_TO_TRANSFER_FROM_SERVER
+                .with_label_values(&request_metadata.get_label_values())
+                .inc_by(bytes_ready_to_transfer as u64);
+            let current_batch_size = transaction_data.as_slice().len();
+            let end_of_batch_version = transaction_data.as_slice().last().unwrap().version;
+            let data_latency_in_secs = transaction_data
+                .last()
+                .unwrap()
+                .timestamp
+                .as_ref()
+                .map(time_diff_since_pb_timestamp_in_secs);
+            let resp_items = get_transactions_responses_builder(
+                transaction_data,
+                chain_id as u32,
+                &sender_addresses_to_ignore,
+                transaction_filter.as_deref(),
+            );
+            let resp_item_last_versions = resp_items
+                .iter()
+                .map(|resp_item| resp_item.transactions.last().unwrap().version)
+                .collect();
+            (
+                resp_items,
+                resp_item_last_versions,
+                current_batch_size,
+                end_of_batch_version,
+                data_latency_in_secs,
+            )
+        };
+
         // 2. Push the data to the response channel, i.e. stream the data to the client.
-        let current_batch_size = transaction_data.as_slice().len();
-        let end_of_batch_version = transaction_data.as_slice().last().unwrap().version;
-        let resp_items = get_transactions_responses_builder(
-            transaction_data,
-            chain_id as u32,
-            &sender_addresses_to_ignore,
-        );
-        let data_latency_in_secs = resp_items
-            .last()
-            .unwrap()
-            .transactions
-            .last()
-            .unwrap()
-            .timestamp
-            .as_ref()
-            .map(time_diff_since_pb_timestamp_in_secs);
+        if let Some(resume_token_signer) = &resume_token_signer {
+            let num_resp_items = resp_items.len();
+            for (i, (resp_item, last_version)) in resp_items
+                .iter_mut()
+                .zip(resp_item_last_versions)
+                .enumerate()
+            {
+                // The last response resumes after the whole batch, including the transactions
+                // that were filtered out.
+                let last_version = if i + 1 == num_resp_items {
+                    end_of_batch_version
+                } else {
+                    last_version
+                };
+                resp_item.resume_token =
+                    Some(resume_token_signer.issue(chain_id, last_version + 1));
+            }
+        }
 
-        match channel_send_multiple_with_timeout(resp_items, tx.clone(), request_metadata.clone())
-            .await
-        {
+        // If no transaction in the batch matches the filter, there is nothing to send.
+        let send_result = if resp_items.is_empty() {
+            Ok(())
+        } else {
+            channel_send_multiple_with_timeout(resp_items, tx.clone(), request_metadata.clone())
+                .await
+        };
+        match send_result {
             Ok(_) => {
                 // TODO: Reasses whether this metric useful
                 LATEST_PROCESSED_VERSION_PER_PROCESSOR
@@ -654,11 +801,20 @@ fn ensure_sequential_transactions(mut batches: Vec<Vec<Transaction>>) -> Vec<Tra
 }
 
 /// Builds the response for the get transactions request. Partial batch is ok, i.e., a batch with transactions < 1000.
+/// Transactions not matching the filter of the request are dropped before being serialized.
 fn get_transactions_responses_builder(
     transactions: Vec<Transaction>,
     chain_id: u32,
     sender_addresses_to_ignore: &HashSet<String>,
+    transaction_filter: Option<&TransactionFilter>,
 ) -> Vec<TransactionsResponse> {
+    let transactions = match transaction_filter {
+        Some(transaction_filter) => transactions
+            .into_iter()
+            .filter(|txn| transaction_filter.matches(txn))
+            .collect(),
+        None => transactions,
+    };
     let filtered_transactions =
         filter_transactions_for_sender_addresses(transactions, sender_addresses_to_ignore);
     let chunks = chunk_transactions(filtered_transactions, MESSAGE_SIZE_LIMIT);
@@ -667,10 +823,69 @@ fn get_transactions_responses_builder(
         .map(|chunk| TransactionsResponse {
             chain_id: Some(chain_id as u64),
             transactions: chunk,
+            resume_token: None,
+            compressed_transactions: vec![],
         })
         .collect()
 }
 
+/// Builds the responses for compressed transactions starting at `starting_version`, without
+/// decompressing them, along with the last version in each response.
+fn get_compressed_transactions_responses_builder(
+    compressed_transactions: Vec<CompressedTransaction>,
+    starting_version: u64,
+    chain_id: u32,
+) -> (Vec<TransactionsResponse>, Vec<u64>) {
+    let mut responses = vec![];
+    let mut last_versions = vec![];
+    let mut chunk = vec![];
+    let mut current_size = 0;
+    let mut version = starting_version;
+    for transaction in compressed_transactions {
+        // A transaction larger than the limit is still sent, in a response of its own.
+        if !chunk.is_empty() && current_size + transaction.size() > MESSAGE_SIZE_LIMIT as u64 {
+            responses.push(TransactionsResponse {
+                chain_id: Some(chain_id as u64),
+                transactions: vec![],
+                resume_token: None,
+                compressed_transactions: std::mem::take(&mut chunk),
+            });
+            last_versions.push(version - 1);
+            current_size = 0;
+        }
+        current_size += transaction.size();
+        chunk.push(transaction.as_bytes().to_vec());
+        version += 1;
+    }
+    if !chunk.is_empty() {
+        last_versions.push(version - 1);
+        responses.push(TransactionsResponse {
+            chain_id: Some(chain_id as u64),
+            transactions: vec![],
+            resume_token: None,
+            compressed_transactions: chunk,
+        });
+    }
+    (responses, last_versions)
+}
+
+/// Returns how many of the fetched transactions to send, and updates the number of transactions
+/// left to send accordingly.
+fn take_transactions_count(transactions_count: &mut Option<u64>, num_fetched: usize) -> usize {
+    match transactions_count {
+        Some(count) if (*count as usize) < num_fetched => {
+            let num_to_send = *count as usize;
+            *count = 0;
+            num_to_send
+        },
+        Some(count) => {
+            *count -= num_fetched as u64;
+            num_fetched
+        },
+        None => num_fetched,
+    }
+}
+
 // This is a CPU bound operation, so we spawn_blocking
 async fn deserialize_cached_transactions(
     transactions: Vec<Vec<u8>>,
@@ -853,6 +1068,18 @@ fn get_request_metadata(
             )
         })
         .collect();
+    // Requests authenticated by the service itself, rather than by the API gateway, are
+    // identified by their API key or JWT subject.
+    if let Some(identity) = req.extensions().get::<AuthIdentity>() {
+        request_metadata_map.insert(
+            "request_identifier_type".to_string(),
+            identity.identifier_type().to_string(),
+        );
+        request_metadata_map.insert(
+            "request_identifier".to_string(),
+            identity.identifier().to_string(),
+        );
+    }
     request_metadata_map.insert(
         "request_connection_id".to_string(),
         Uuid::new_v4().to_string(),
@@ -863,6 +1090,19 @@ fn get_request_metadata(
     Ok(request_metadata)
 }
 
+/// Parses the transaction filter of the request, if any.
+fn get_transaction_filter(
+    req: &Request<GetTransactionsRequest>,
+) -> Result<Option<TransactionFilter>> {
+    let Some(filter) = req.metadata().get(GRPC_TRANSACTION_FILTER_HEADER) else {
+        return Ok(None);
+    };
+    let filter = filter
+        .to_str()
+        .context("Transaction filter is not a valid string")?;
+    filter.parse().map(Some)
+}
+
 async fn channel_send_multiple_with_timeout(
     resp_items: Vec<TransactionsResponse>,
     tx: tokio::sync::mpsc::Sender<Result<TransactionsResponse, Status>>,
@@ -962,7 +1202,11 @@ fn filter_transactions_for_sender_addresses(
 
 #[cfg(test)]
 mod tests {
-    use super::{ensure_sequential_transactions, filter_transactions_for_sender_addresses};
+    use super::{
+        ensure_sequential_transactions, filter_transactions_for_sender_addresses,
+        get_compressed_transactions_responses_builder, take_transactions_count,
+    };
+    use aptos_indexer_grpc_utils::in_memory_cache::CompressedTransaction;
     use aptos_protos::transaction::v1::{
         transaction::TxnData, Event, Signature, Transaction, TransactionInfo, TransactionPayload,
         UserTransaction, UserTransactionRequest, WriteSetChange,
@@ -1050,4 +1294,43 @@ mod tests {
         assert_eq!(user_transaction.events.len(), 0);
         assert_eq!(txn.info.as_ref().unwrap().changes.len(), 0);
     }
+
+    #[test]
+    fn test_compressed_transactions_are_passed_through() {
+        let compressed_transactions: Vec<CompressedTransaction> = (10..15)
+            .map(|i| {
+                CompressedTransaction::from_transaction(&Transaction {
+                    version: i,
+                    ..Default::default()
+                })
+            })
+            .collect();
+        let (responses, last_versions) =
+            get_compressed_transactions_responses_builder(compressed_transactions.clone(), 10, 1);
+        assert_eq!(responses.len(), 1);
+        assert_eq!(last_versions, vec![14]);
+        let response = responses.first().unwrap();
+        assert_eq!(response.chain_id, Some(1));
+        assert!(response.transactions.is_empty());
+        assert_eq!(
+            response.compressed_transactions,
+            compressed_transactions
+                .iter()
+                .map(|t| t.as_bytes().to_vec())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_take_transactions_count() {
+        let mut transactions_count = None;
+        assert_eq!(take_transactions_count(&mut transactions_count, 10), 10);
+        assert_eq!(transactions_count, None);
+
+        let mut transactions_count = Some(15);
+        assert_eq!(take_transactions_count(&mut transactions_count, 10), 10);
+        assert_eq!(transactions_count, Some(5));
+        assert_eq!(take_transactions_count(&mut transactions_count, 10), 5);
+        assert_eq!(transactions_count, Some(0));
+    }
 }
@@ -0,0 +1,442 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Server-side filtering of the transaction stream, so that clients only receive the
+//! transactions they process. A filter is an expression over the fields of a transaction, e.g.
+//!
+//! ```text
+//! type == user && success && (entry_function == 0x1::coin::transfer || event_type == 0x1::coin::DepositEvent)
+//! ```
+//!
+//! * `type == <genesis | block_metadata | state_checkpoint | user | validator>`
+//! * `success`: the transaction was executed successfully
+//! * `sender == <address>`: the sender of a user transaction
+//! * `entry_function == <address>::<module>[::<function>]`: the entry function called by a user
+//!   transaction, directly or through a multisig account
+//! * `event_type == <address>::<module>::<struct>`: any event emitted, ignoring type arguments
+//!
+//! Conditions can be negated with `!` or `!=`, and combined with `&&`, `||` and parentheses.
+
+use anyhow::{bail, ensure, format_err, Result};
+use aptos_protos::transaction::v1::{
+    multisig_transaction_payload, transaction::TxnData, transaction_payload, EntryFunctionId,
+    Event, Transaction, TransactionPayload,
+};
+use std::{iter::Peekable, str::FromStr, vec::IntoIter};
+
+/// The maximum length of a filter expression, to bound the cost of evaluating it.
+const MAX_FILTER_LENGTH: usize = 4096;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransactionFilter {
+    And(Box<TransactionFilter>, Box<TransactionFilter>),
+    Or(Box<TransactionFilter>, Box<TransactionFilter>),
+    Not(Box<TransactionFilter>),
+    Success,
+    Type(TxnType),
+    Sender(String),
+    EntryFunction {
+        address: String,
+        module: String,
+        function: Option<String>,
+    },
+    EventType {
+        address: String,
+        module: String,
+        name: String,
+    },
+}
+
+/// The transaction types of `TxnData`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TxnType {
+    Genesis,
+    BlockMetadata,
+    StateCheckpoint,
+    User,
+    Validator,
+}
+
+impl TransactionFilter {
+    pub fn matches(&self, txn: &Transaction) -> bool {
+        match self {
+            Self::And(left, right) => left.matches(txn) && right.matches(txn),
+            Self::Or(left, right) => left.matches(txn) || right.matches(txn),
+            Self::Not(inner) => !inner.matches(txn),
+            Self::Success => txn.info.as_ref().map_or(false, |info| info.success),
+            Self::Type(txn_type) => {
+                matches!(
+                    (txn_type, &txn.txn_data),
+                    (TxnType::Genesis, Some(TxnData::Genesis(_)))
+                        | (TxnType::BlockMetadata, Some(TxnData::BlockMetadata(_)))
+                        | (TxnType::StateCheckpoint, Some(TxnData::StateCheckpoint(_)))
+                        | (TxnType::User, Some(TxnData::User(_)))
+                        | (TxnType::Validator, Some(TxnData::Validator(_)))
+                )
+            },
+            Self::Sender(sender) => match &txn.txn_data {
+                Some(TxnData::User(user_txn)) => {
+                    user_txn.request.as_ref().map_or(false, |request| {
+                        &standardize_address(&request.sender) == sender
+                    })
+                },
+                _ => false,
+            },
+            Self::EntryFunction {
+                address,
+                module,
+                function,
+            } => entry_function(txn).map_or(false, |entry_function| {
+                entry_function.module.as_ref().map_or(false, |module_id| {
+                    &standardize_address(&module_id.address) == address && &module_id.name == module
+                }) && function
+                    .as_ref()
+                    .map_or(true, |function| &entry_function.name == function)
+            }),
+            Self::EventType {
+                address,
+                module,
+                name,
+            } => events(txn).iter().any(|event| {
+                // Type arguments are ignored, e.g. `0x1::coin::CoinDeposit<0x1::aptos_coin::AptosCoin>`
+                let type_str = event.type_str.split('<').next().unwrap_or_default();
+                let mut parts = type_str.split("::");
+                parts.next().map(standardize_address).as_ref() == Some(address)
+                    && parts.next() == Some(module.as_str())
+                    && parts.next() == Some(name.as_str())
+            }),
+        }
+    }
+}
+
+fn entry_function(txn: &Transaction) -> Option<&EntryFunctionId> {
+    let Some(TxnData::User(user_txn)) = &txn.txn_data else {
+        return None;
+    };
+    let payload: &TransactionPayload = user_txn.request.as_ref()?.payload.as_ref()?;
+    match payload.payload.as_ref()? {
+        transaction_payload::Payload::EntryFunctionPayload(payload) => payload.function.as_ref(),
+        transaction_payload::Payload::MultisigPayload(payload) => {
+            match payload.transaction_payload.as_ref()?.payload.as_ref()? {
+                multisig_transaction_payload::Payload::EntryFunctionPayload(payload) => {
+                    payload.function.as_ref()
+                },
+            }
+        },
+        _ => None,
+    }
+}
+
+fn events(txn: &Transaction) -> &[Event] {
+    match &txn.txn_data {
+        Some(TxnData::BlockMetadata(txn)) => &txn.events,
+        Some(TxnData::Genesis(txn)) => &txn.events,
+        Some(TxnData::User(txn)) => &txn.events,
+        _ => &[],
+    }
+}
+
+/// Addresses are compared in their short form, e.g. `0x1`, as event types are.
+fn standardize_address(address: &str) -> String {
+    let address = address.trim_start_matches("0x").trim_start_matches('0');
+    format!("0x{}", if address.is_empty() { "0" } else { address }).to_lowercase()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    And,
+    Or,
+    Not,
+    Eq,
+    NotEq,
+    LeftParen,
+    RightParen,
+    Word(String),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '&' if chars.next_if_eq(&'&').is_some() => Token::And,
+            '|' if chars.next_if_eq(&'|').is_some() => Token::Or,
+            '=' if chars.next_if_eq(&'=').is_some() => Token::Eq,
+            '!' if chars.next_if_eq(&'=').is_some() => Token::NotEq,
+            '!' => Token::Not,
+            c if c.is_ascii_alphanumeric() || c == '_' || c == ':' => {
+                let mut word = c.to_string();
+                while let Some(c) =
+                    chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == ':')
+                {
+                    word.push(c);
+                }
+                Token::Word(word)
+            },
+            c => bail!("Unexpected character `{}`", c),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Peekable<IntoIter<Token>>,
+}
+
+impl Parser {
+    // or := and ('||' and)*
+    fn parse_or(&mut self) -> Result<TransactionFilter> {
+        let mut filter = self.parse_and()?;
+        while self.tokens.next_if_eq(&Token::Or).is_some() {
+            filter = TransactionFilter::Or(Box::new(filter), Box::new(self.parse_and()?));
+        }
+        Ok(filter)
+    }
+
+    // and := unary ('&&' unary)*
+    fn parse_and(&mut self) -> Result<TransactionFilter> {
+        let mut filter = self.parse_unary()?;
+        while self.tokens.next_if_eq(&Token::And).is_some() {
+            filter = TransactionFilter::And(Box::new(filter), Box::new(self.parse_unary()?));
+        }
+        Ok(filter)
+    }
+
+    // unary := '!' unary | '(' or ')' | condition
+    fn parse_unary(&mut self) -> Result<TransactionFilter> {
+        match self.tokens.next() {
+            Some(Token::Not) => Ok(TransactionFilter::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LeftParen) => {
+                let filter = self.parse_or()?;
+                ensure!(
+                    self.tokens.next() == Some(Token::RightParen),
+                    "Expected `)`"
+                );
+                Ok(filter)
+            },
+            Some(Token::Word(field)) => self.parse_condition(&field),
+            token => bail!("Expected a condition, found {:?}", token),
+        }
+    }
+
+    // condition := 'success' | field ('==' | '!=') value
+    fn parse_condition(&mut self, field: &str) -> Result<TransactionFilter> {
+        if field == "success" {
+            return Ok(TransactionFilter::Success);
+        }
+        let negated = match self.tokens.next() {
+            Some(Token::Eq) => false,
+            Some(Token::NotEq) => true,
+            token => bail!("Expected `==` or `!=` after `{}`, found {:?}", field, token),
+        };
+        let Some(Token::Word(value)) = self.tokens.next() else {
+            bail!("Expected a value for `{}`", field);
+        };
+        let filter = match field {
+            "type" => TransactionFilter::Type(value.parse()?),
+            "sender" => TransactionFilter::Sender(parse_address(&value)?),
+            "entry_function" => match value.split("::").collect::<Vec<_>>()[..] {
+                [address, module] => TransactionFilter::EntryFunction {
+                    address: parse_address(address)?,
+                    module: module.to_string(),
+                    function: None,
+                },
+                [address, module, function] => TransactionFilter::EntryFunction {
+                    address: parse_address(address)?,
+                    module: module.to_string(),
+                    function: Some(function.to_string()),
+                },
+                _ => bail!(
+                    "Expected `<address>::<module>[::<function>]` for entry_function, found `{}`",
+                    value
+                ),
+            },
+            "event_type" => match value.split("::").collect::<Vec<_>>()[..] {
+                [address, module, name] => TransactionFilter::EventType {
+                    address: parse_address(address)?,
+                    module: module.to_string(),
+                    name: name.to_string(),
+                },
+                _ => bail!(
+                    "Expected `<address>::<module>::<struct>` for event_type, found `{}`",
+                    value
+                ),
+            },
+            _ => bail!("Unknown field `{}`", field),
+        };
+        Ok(if negated {
+            TransactionFilter::Not(Box::new(filter))
+        } else {
+            filter
+        })
+    }
+}
+
+fn parse_address(address: &str) -> Result<String> {
+    let hex = address.strip_prefix("0x").ok_or_else(|| {
+        format_err!(
+            "Expected an address starting with `0x`, found `{}`",
+            address
+        )
+    })?;
+    ensure!(
+        !hex.is_empty() && hex.len() <= 64 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        "Invalid address `{}`",
+        address
+    );
+    Ok(standardize_address(address))
+}
+
+impl FromStr for TxnType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "genesis" => Self::Genesis,
+            "block_metadata" => Self::BlockMetadata,
+            "state_checkpoint" => Self::StateCheckpoint,
+            "user" => Self::User,
+            "validator" => Self::Validator,
+            _ => bail!("Unknown transaction type `{}`", s),
+        })
+    }
+}
+
+impl FromStr for TransactionFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        ensure!(
+            s.len() <= MAX_FILTER_LENGTH,
+            "Filter is longer than {} characters",
+            MAX_FILTER_LENGTH
+        );
+        let mut parser = Parser {
+            tokens: tokenize(s)?.into_iter().peekable(),
+        };
+        let filter = parser.parse_or()?;
+        if let Some(token) = parser.tokens.next() {
+            bail!("Unexpected {:?} after the end of the filter", token);
+        }
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::{
+        EntryFunctionPayload, MoveModuleId, TransactionInfo, UserTransaction,
+        UserTransactionRequest,
+    };
+
+    fn user_txn(sender: &str, success: bool, function: &str, event_type: &str) -> Transaction {
+        Transaction {
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: sender.to_string(),
+                    payload: Some(TransactionPayload {
+                        payload: Some(transaction_payload::Payload::EntryFunctionPayload(
+                            EntryFunctionPayload {
+                                function: Some(EntryFunctionId {
+                                    module: Some(MoveModuleId {
+                                        address: "0x1".to_string(),
+                                        name: "coin".to_string(),
+                                    }),
+                                    name: function.to_string(),
+                                }),
+                                ..Default::default()
+                            },
+                        )),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                events: vec![Event {
+                    type_str: event_type.to_string(),
+                    ..Default::default()
+                }],
+            })),
+            info: Some(TransactionInfo {
+                success,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn matches(filter: &str, txn: &Transaction) -> bool {
+        filter.parse::<TransactionFilter>().unwrap().matches(txn)
+    }
+
+    #[test]
+    fn test_conditions() {
+        let sender = format!("0x{:0>64}", "abc");
+        let txn = user_txn(
+            &sender,
+            true,
+            "transfer",
+            "0x1::coin::CoinDeposit<0x1::aptos_coin::AptosCoin>",
+        );
+        assert!(matches("type == user", &txn));
+        assert!(!matches("type == genesis", &txn));
+        assert!(matches("success", &txn));
+        assert!(matches("sender == 0xabc", &txn));
+        assert!(matches(&format!("sender == {}", sender), &txn));
+        assert!(!matches("sender == 0xabcd", &txn));
+        assert!(matches("entry_function == 0x1::coin", &txn));
+        assert!(matches("entry_function == 0x1::coin::transfer", &txn));
+        assert!(!matches("entry_function == 0x1::coin::mint", &txn));
+        assert!(matches("event_type == 0x1::coin::CoinDeposit", &txn));
+        assert!(!matches("event_type == 0x1::coin::CoinWithdraw", &txn));
+        assert!(!matches("event_type == 0x1::coin::Coin", &txn));
+
+        let failed_txn = user_txn(&sender, false, "transfer", "0x1::coin::CoinDeposit");
+        assert!(!matches("success", &failed_txn));
+        assert!(matches("!success", &failed_txn));
+        assert!(!matches("type == user", &Transaction::default()));
+    }
+
+    #[test]
+    fn test_operators() {
+        let txn = user_txn("0x1", true, "transfer", "0x1::coin::CoinDeposit");
+        assert!(matches("type == user && success", &txn));
+        assert!(!matches("type == user && !success", &txn));
+        assert!(matches("type != genesis", &txn));
+        assert!(matches("sender == 0x2 || sender == 0x1", &txn));
+        // `&&` binds tighter than `||`
+        assert!(matches("sender == 0x1 || sender == 0x2 && !success", &txn));
+        assert!(!matches(
+            "(sender == 0x1 || sender == 0x2) && !success",
+            &txn
+        ));
+    }
+
+    #[test]
+    fn test_invalid_filters() {
+        for filter in [
+            "",
+            "type",
+            "type == transfer",
+            "sender == abc",
+            "sender == 0xg",
+            "entry_function == 0x1",
+            "event_type == 0x1::coin",
+            "(success",
+            "success)",
+            "success & success",
+            "unknown == 1",
+            "success success",
+        ] {
+            assert!(
+                filter.parse::<TransactionFilter>().is_err(),
+                "`{}` should be rejected",
+                filter
+            );
+        }
+    }
+}
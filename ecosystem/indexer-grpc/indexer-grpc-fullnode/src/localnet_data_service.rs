@@ -105,6 +105,8 @@ impl RawData for LocalnetDataService {
                         },
                         _ => panic!("Unexpected response type."),
                     },
+                    resume_token: None,
+                    compressed_transactions: vec![],
                 });
                 match external_service_tx.send(response).await {
                     Ok(_) => {},
@@ -0,0 +1,385 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Authentication of gRPC requests, for services exposed without an API gateway in front of
+//! them. Requests carry either an API key or a JWT as a bearer token in the `authorization`
+//! header, and are rate limited per key or per JWT subject.
+
+use anyhow::{bail, Result};
+use aptos_metrics_core::{register_int_counter_vec, IntCounterVec};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+use tonic::{metadata::MetadataMap, service::Interceptor, Request, Status};
+
+/// The gRPC request metadata key for the bearer token.
+pub const AUTHORIZATION_HEADER: &str = "authorization";
+
+const BEARER_PREFIX: &str = "Bearer ";
+
+/// Number of requests checked by the auth interceptor, by identity and result.
+pub static AUTH_REQUEST_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_grpc_auth_request_count",
+        "Number of requests checked by the auth interceptor",
+        &["identifier_type", "identifier", "result"],
+    )
+    .unwrap()
+});
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    /// API keys accepted as bearer tokens.
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,
+    /// If given, JWTs signed with this config are accepted as bearer tokens.
+    #[serde(default)]
+    pub jwt: Option<JwtConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// The name identifying the key holder, e.g. in metrics.
+    pub name: String,
+    pub key: String,
+    /// Maximum number of requests per second with this key. Unlimited if not set.
+    #[serde(default)]
+    pub max_requests_per_second: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct JwtConfig {
+    /// The secret the JWTs are signed with, using HS256.
+    pub secret: String,
+    /// If set, the `iss` claim must match.
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// If set, the `aud` claim must match.
+    #[serde(default)]
+    pub audience: Option<String>,
+    /// Maximum number of requests per second per subject (the `sub` claim). Unlimited if
+    /// not set.
+    #[serde(default)]
+    pub max_requests_per_second: Option<u32>,
+}
+
+impl AuthConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.api_keys.is_empty() && self.jwt.is_none() {
+            bail!("At least one of api_keys and jwt must be set in the auth config");
+        }
+        let mut names = HashSet::new();
+        for api_key in &self.api_keys {
+            if api_key.key.is_empty() {
+                bail!("API key `{}` is empty", api_key.name);
+            }
+            if !names.insert(&api_key.name) {
+                bail!("API key name `{}` is used more than once", api_key.name);
+            }
+        }
+        if let Some(jwt) = &self.jwt {
+            if jwt.secret.is_empty() {
+                bail!("JWT secret is empty");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The identity of an authenticated request, added to the request extensions.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum AuthIdentity {
+    ApiKey(String),
+    Jwt(String),
+}
+
+impl AuthIdentity {
+    pub fn identifier_type(&self) -> &'static str {
+        match self {
+            Self::ApiKey(_) => "api_key",
+            Self::Jwt(_) => "jwt",
+        }
+    }
+
+    pub fn identifier(&self) -> &str {
+        match self {
+            Self::ApiKey(name) | Self::Jwt(name) => name,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    sub: String,
+}
+
+/// Allows up to `max_per_second` requests in any second, refilling continuously.
+struct TokenBucket {
+    max_per_second: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(max_per_second: u32) -> Self {
+        Self {
+            max_per_second: max_per_second as f64,
+            tokens: max_per_second as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn try_acquire(&mut self) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.max_per_second).min(self.max_per_second);
+        self.last_refill = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+struct AuthInner {
+    api_keys: HashMap<String, ApiKeyConfig>,
+    jwt: Option<(JwtConfig, DecodingKey, Validation)>,
+    rate_limits: Mutex<HashMap<AuthIdentity, TokenBucket>>,
+}
+
+/// A tonic interceptor which rejects requests without a valid bearer token, or over the rate
+/// limit of their identity. If no config is given, all requests are let through.
+#[derive(Clone)]
+pub struct AuthInterceptor {
+    inner: Option<Arc<AuthInner>>,
+}
+
+impl AuthInterceptor {
+    pub fn new(config: Option<&AuthConfig>) -> Self {
+        let inner = config.map(|config| {
+            let api_keys = config
+                .api_keys
+                .iter()
+                .map(|api_key| (api_key.key.clone(), api_key.clone()))
+                .collect();
+            let jwt = config.jwt.as_ref().map(|jwt| {
+                let mut validation = Validation::new(Algorithm::HS256);
+                if let Some(issuer) = &jwt.issuer {
+                    validation.set_issuer(&[issuer]);
+                }
+                if let Some(audience) = &jwt.audience {
+                    validation.set_audience(&[audience]);
+                }
+                let key = DecodingKey::from_secret(jwt.secret.as_bytes());
+                (jwt.clone(), key, validation)
+            });
+            Arc::new(AuthInner {
+                api_keys,
+                jwt,
+                rate_limits: Mutex::new(HashMap::new()),
+            })
+        });
+        Self { inner }
+    }
+
+    fn authenticate(inner: &AuthInner, metadata: &MetadataMap) -> Result<AuthIdentity, Status> {
+        let token = metadata
+            .get(AUTHORIZATION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+            .ok_or_else(|| Status::unauthenticated("Missing bearer token"))?;
+
+        if let Some(api_key) = inner.api_keys.get(token) {
+            return Ok(AuthIdentity::ApiKey(api_key.name.clone()));
+        }
+        match &inner.jwt {
+            Some((_, key, validation)) => jsonwebtoken::decode::<Claims>(token, key, validation)
+                .map(|data| AuthIdentity::Jwt(data.claims.sub))
+                .map_err(|e| Status::unauthenticated(format!("Invalid bearer token: {}", e))),
+            None => Err(Status::unauthenticated("Invalid bearer token")),
+        }
+    }
+
+    fn max_requests_per_second(inner: &AuthInner, identity: &AuthIdentity) -> Option<u32> {
+        match identity {
+            AuthIdentity::ApiKey(name) => inner
+                .api_keys
+                .values()
+                .find(|api_key| &api_key.name == name)
+                .and_then(|api_key| api_key.max_requests_per_second),
+            AuthIdentity::Jwt(_) => inner
+                .jwt
+                .as_ref()
+                .and_then(|(jwt, _, _)| jwt.max_requests_per_second),
+        }
+    }
+
+    fn check_rate_limit(inner: &AuthInner, identity: &AuthIdentity) -> bool {
+        let Some(max_per_second) = Self::max_requests_per_second(inner, identity) else {
+            return true;
+        };
+        inner
+            .rate_limits
+            .lock()
+            .unwrap()
+            .entry(identity.clone())
+            .or_insert_with(|| TokenBucket::new(max_per_second))
+            .try_acquire()
+    }
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let Some(inner) = &self.inner else {
+            return Ok(request);
+        };
+
+        let identity = match Self::authenticate(inner, request.metadata()) {
+            Ok(identity) => identity,
+            Err(status) => {
+                AUTH_REQUEST_COUNT
+                    .with_label_values(&["unknown", "unknown", "unauthenticated"])
+                    .inc();
+                return Err(status);
+            },
+        };
+        let labels = [identity.identifier_type(), identity.identifier()];
+
+        if !Self::check_rate_limit(inner, &identity) {
+            AUTH_REQUEST_COUNT
+                .with_label_values(&[labels[0], labels[1], "rate_limited"])
+                .inc();
+            return Err(Status::resource_exhausted(format!(
+                "Rate limit exceeded for {}",
+                identity.identifier()
+            )));
+        }
+
+        AUTH_REQUEST_COUNT
+            .with_label_values(&[labels[0], labels[1], "authorized"])
+            .inc();
+        request.extensions_mut().insert(identity);
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{EncodingKey, Header};
+
+    const SECRET: &str = "secret";
+
+    #[derive(Serialize)]
+    struct TestClaims<'a> {
+        sub: &'a str,
+        iss: &'a str,
+        exp: u64,
+    }
+
+    fn config() -> AuthConfig {
+        AuthConfig {
+            api_keys: vec![
+                ApiKeyConfig {
+                    name: "unlimited".to_string(),
+                    key: "key1".to_string(),
+                    max_requests_per_second: None,
+                },
+                ApiKeyConfig {
+                    name: "limited".to_string(),
+                    key: "key2".to_string(),
+                    max_requests_per_second: Some(2),
+                },
+            ],
+            jwt: Some(JwtConfig {
+                secret: SECRET.to_string(),
+                issuer: Some("issuer".to_string()),
+                audience: None,
+                max_requests_per_second: None,
+            }),
+        }
+    }
+
+    fn request(token: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(token) = token {
+            request.metadata_mut().insert(
+                AUTHORIZATION_HEADER,
+                format!("{}{}", BEARER_PREFIX, token).parse().unwrap(),
+            );
+        }
+        request
+    }
+
+    fn jwt(issuer: &str, secret: &str) -> String {
+        jsonwebtoken::encode(
+            &Header::default(),
+            &TestClaims {
+                sub: "subject",
+                iss: issuer,
+                exp: u32::MAX as u64,
+            },
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_api_key() {
+        let mut interceptor = AuthInterceptor::new(Some(&config()));
+        let authorized = interceptor.call(request(Some("key1"))).unwrap();
+        assert_eq!(
+            authorized.extensions().get::<AuthIdentity>(),
+            Some(&AuthIdentity::ApiKey("unlimited".to_string()))
+        );
+
+        let status = interceptor.call(request(Some("key3"))).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        let status = interceptor.call(request(None)).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+    }
+
+    #[test]
+    fn test_jwt() {
+        let mut interceptor = AuthInterceptor::new(Some(&config()));
+        let authorized = interceptor
+            .call(request(Some(&jwt("issuer", SECRET))))
+            .unwrap();
+        assert_eq!(
+            authorized.extensions().get::<AuthIdentity>(),
+            Some(&AuthIdentity::Jwt("subject".to_string()))
+        );
+
+        for token in [jwt("other issuer", SECRET), jwt("issuer", "other secret")] {
+            let status = interceptor.call(request(Some(&token))).unwrap_err();
+            assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        }
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let mut interceptor = AuthInterceptor::new(Some(&config()));
+        assert!(interceptor.call(request(Some("key2"))).is_ok());
+        assert!(interceptor.call(request(Some("key2"))).is_ok());
+        let status = interceptor.call(request(Some("key2"))).unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        // Other keys have their own limits
+        assert!(interceptor.call(request(Some("key1"))).is_ok());
+    }
+
+    #[test]
+    fn test_no_config() {
+        let mut interceptor = AuthInterceptor::new(None);
+        assert!(interceptor.call(request(None)).is_ok());
+    }
+}
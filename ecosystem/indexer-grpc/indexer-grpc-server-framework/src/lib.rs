@@ -15,6 +15,8 @@ use tracing::error;
 use tracing_subscriber::EnvFilter;
 use warp::{http::Response, Filter};
 
+pub mod auth;
+
 /// ServerArgs bootstraps a server with all common pieces. And then triggers the run method for
 /// the specific service.
 #[derive(Parser)]
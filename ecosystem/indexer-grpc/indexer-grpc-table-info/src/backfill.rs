@@ -0,0 +1,202 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, ensure, Result};
+use aptos_db_indexer::db_v2::IndexerAsyncV2;
+use aptos_logger::info;
+use aptos_storage_interface::DbReader;
+use aptos_types::{transaction::Version, write_set::WriteSet};
+use std::{ops::Range, sync::Arc, time::Instant};
+
+/// Backfills the table info db for historical versions, reading the write sets from the db of an
+/// (archival) node directly, instead of parsing them one batch at a time behind the node.
+///
+/// Versions are processed in rounds of `num_workers` batches, parsed in parallel. Like in the
+/// table info service, table infos that couldn't be parsed because their parent table was
+/// parsed by another worker are retried sequentially at the end of the round. The round is then
+/// checkpointed in the table info db, so that a backfill can be resumed (or a node started)
+/// from there.
+pub struct TableInfoBackfiller {
+    db_reader: Arc<dyn DbReader>,
+    indexer_async_v2: Arc<IndexerAsyncV2>,
+    num_workers: usize,
+    batch_size: u64,
+}
+
+impl TableInfoBackfiller {
+    pub fn new(
+        db_reader: Arc<dyn DbReader>,
+        indexer_async_v2: Arc<IndexerAsyncV2>,
+        num_workers: usize,
+        batch_size: u64,
+    ) -> Self {
+        Self {
+            db_reader,
+            indexer_async_v2,
+            num_workers: num_workers.max(1),
+            batch_size: batch_size.max(1),
+        }
+    }
+
+    /// Backfills the versions in `[start_version, end_version)`. If the table info db is already
+    /// checkpointed past `start_version` (e.g., by an interrupted backfill), it resumes from the
+    /// checkpoint. Returns the version the backfill ended at.
+    pub fn run(&self, start_version: Version, end_version: Version) -> Result<Version> {
+        let mut version = start_version.max(self.indexer_async_v2.next_version());
+        if version > start_version {
+            info!(
+                start_version = start_version,
+                resume_version = version,
+                "[Table Info Backfill] Resuming from the checkpoint in the table info db"
+            );
+        }
+
+        while version < end_version {
+            let start_time = Instant::now();
+            let round_end_version = std::cmp::min(
+                end_version,
+                version.saturating_add(self.num_workers as u64 * self.batch_size),
+            );
+            self.process_round(version..round_end_version)?;
+            // The db records the last version processed, so a resumed backfill (or the node)
+            // parses that version again, which is idempotent.
+            self.indexer_async_v2
+                .update_next_version(round_end_version)?;
+            info!(
+                first_version = version,
+                end_version = round_end_version,
+                target_version = end_version,
+                duration_in_secs = start_time.elapsed().as_secs_f64(),
+                "[Table Info Backfill] Versions processed"
+            );
+            version = round_end_version;
+        }
+        Ok(version)
+    }
+
+    /// Parses the table infos of the versions in parallel, and then sequentially if any of them
+    /// are still pending on their parent table's info.
+    fn process_round(&self, versions: Range<Version>) -> Result<()> {
+        let batches = split_into_batches(versions.clone(), self.batch_size);
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = batches
+                .into_iter()
+                .map(|batch| scope.spawn(move || self.process_batch(batch, false)))
+                .collect();
+            workers.into_iter().try_for_each(|worker| {
+                worker
+                    .join()
+                    .map_err(|_| anyhow!("Table info backfill worker panicked"))?
+            })
+        })?;
+
+        self.indexer_async_v2.cleanup_pending_on_items()?;
+        if !self.indexer_async_v2.is_indexer_async_v2_pending_on_empty() {
+            self.process_batch(
+                versions.clone(),
+                true, /* end_early_if_pending_on_empty */
+            )?;
+        }
+        ensure!(
+            self.indexer_async_v2.is_indexer_async_v2_pending_on_empty(),
+            "Missing data in table info parsing of versions {:?} after sequential retry",
+            versions,
+        );
+        Ok(())
+    }
+
+    fn process_batch(
+        &self,
+        versions: Range<Version>,
+        end_early_if_pending_on_empty: bool,
+    ) -> Result<()> {
+        let write_sets = self
+            .db_reader
+            .get_write_set_iterator(versions.start, versions.end - versions.start)?
+            .collect::<aptos_storage_interface::Result<Vec<WriteSet>>>()?;
+        let write_sets: Vec<&WriteSet> = write_sets.iter().collect();
+        self.indexer_async_v2.index_table_info(
+            self.db_reader.clone(),
+            versions.start,
+            &write_sets,
+            end_early_if_pending_on_empty,
+        )?;
+        Ok(())
+    }
+}
+
+/// Splits the versions into consecutive batches of (at most) `batch_size` versions.
+fn split_into_batches(versions: Range<Version>, batch_size: u64) -> Vec<Range<Version>> {
+    versions
+        .clone()
+        .step_by(batch_size as usize)
+        .map(|start| start..std::cmp::min(start + batch_size, versions.end))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_config::config::RocksdbConfig;
+    use aptos_db_indexer::db_ops::open_db;
+    use std::sync::Mutex;
+
+    /// Returns empty write sets, and records the versions requested
+    #[derive(Default)]
+    struct MockDbReader {
+        requested_versions: Mutex<Vec<Range<Version>>>,
+    }
+
+    impl DbReader for MockDbReader {
+        fn get_write_set_iterator(
+            &self,
+            start_version: Version,
+            limit: u64,
+        ) -> aptos_storage_interface::Result<
+            Box<dyn Iterator<Item = aptos_storage_interface::Result<WriteSet>> + '_>,
+        > {
+            self.requested_versions
+                .lock()
+                .unwrap()
+                .push(start_version..start_version + limit);
+            Ok(Box::new((0..limit).map(|_| Ok(WriteSet::default()))))
+        }
+    }
+
+    #[test]
+    fn test_resume_backfill() {
+        let table_info_db_dir = tempfile::tempdir().unwrap();
+        let new_backfiller = |db_reader: Arc<MockDbReader>| {
+            let indexer_async_v2 = Arc::new(
+                IndexerAsyncV2::new(
+                    open_db(table_info_db_dir.path(), &RocksdbConfig::default()).unwrap(),
+                )
+                .unwrap(),
+            );
+            TableInfoBackfiller::new(db_reader, indexer_async_v2, 2, 10)
+        };
+
+        // Backfill the first versions
+        let backfiller = new_backfiller(Arc::new(MockDbReader::default()));
+        assert_eq!(backfiller.run(0, 30).unwrap(), 30);
+        assert_eq!(backfiller.indexer_async_v2.next_version(), 29);
+        drop(backfiller);
+
+        // Rerunning the backfill resumes it from the last version processed
+        let db_reader = Arc::new(MockDbReader::default());
+        let backfiller = new_backfiller(db_reader.clone());
+        assert_eq!(backfiller.run(0, 50).unwrap(), 50);
+        assert_eq!(backfiller.indexer_async_v2.next_version(), 49);
+        let mut requested_versions = db_reader.requested_versions.lock().unwrap().clone();
+        requested_versions.sort_by_key(|versions| versions.start);
+        assert_eq!(requested_versions, vec![29..39, 39..49, 49..50]);
+    }
+
+    #[test]
+    fn test_split_into_batches() {
+        assert_eq!(split_into_batches(10..35, 10), vec![10..20, 20..30, 30..35]);
+        assert_eq!(split_into_batches(10..30, 10), vec![10..20, 20..30]);
+        assert_eq!(split_into_batches(10..11, 10), vec![10..11]);
+        assert!(split_into_batches(10..10, 10).is_empty());
+    }
+}
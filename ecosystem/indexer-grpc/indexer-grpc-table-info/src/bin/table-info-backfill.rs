@@ -0,0 +1,94 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use aptos_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_db::AptosDB;
+use aptos_db_indexer::{db_ops::open_db, db_v2::IndexerAsyncV2};
+use aptos_indexer_grpc_table_info::backfill::TableInfoBackfiller;
+use aptos_logger::info;
+use aptos_storage_interface::DbReader;
+use clap::Parser;
+use std::{path::PathBuf, sync::Arc};
+
+/// Backfills the table info db of a node for a range of historical versions, with parallel
+/// workers. The node's db must have the state of those versions, i.e., the node must be an
+/// archival one.
+///
+/// Progress is checkpointed in the table info db, so the backfill can be stopped and rerun with
+/// the same arguments to resume it. Once done, the table info db can be moved into place on the
+/// node (`index_indexer_async_v2_db` in the storage dir) and table info enabled: the node resumes
+/// parsing from where the backfill ended.
+#[derive(Debug, Parser)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Storage dir of the node to read the transactions from. It is opened read only, so the
+    /// node doesn't need to be stopped.
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    /// Path of the table info db to backfill, created if it doesn't exist.
+    #[clap(long, value_parser)]
+    table_info_db_dir: PathBuf,
+
+    /// First version to backfill.
+    #[clap(long, default_value_t = 0)]
+    start_version: u64,
+
+    /// Version to backfill up to (exclusive). [Defaults to the latest version of the node]
+    #[clap(long)]
+    end_version: Option<u64>,
+
+    /// Number of batches parsed in parallel.
+    #[clap(long, default_value_t = num_cpus::get())]
+    num_workers: usize,
+
+    /// Number of transactions in each batch.
+    #[clap(long, default_value_t = 1000)]
+    batch_size: u64,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    aptos_logger::Logger::builder()
+        .level(aptos_logger::Level::Info)
+        .build();
+
+    let rocksdb_configs = RocksdbConfigs::default();
+    let db = Arc::new(AptosDB::open(
+        StorageDirPaths::from_path(&args.db_dir),
+        true, /* readonly */
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        rocksdb_configs,
+        false, /* indexer */
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )?);
+    let latest_version = db.get_latest_ledger_info_version()?;
+    let end_version = args.end_version.unwrap_or(latest_version);
+    ensure!(
+        end_version <= latest_version,
+        "End version {} is past the latest version of the node {}.",
+        end_version,
+        latest_version,
+    );
+
+    let indexer_async_v2 = Arc::new(IndexerAsyncV2::new(open_db(
+        &args.table_info_db_dir,
+        &rocksdb_configs.index_db_config,
+    )?)?);
+    let backfiller =
+        TableInfoBackfiller::new(db, indexer_async_v2, args.num_workers, args.batch_size);
+    let version = backfiller.run(args.start_version, end_version)?;
+    info!(version = version, "[Table Info Backfill] Done");
+    Ok(())
+}
+
+#[test]
+fn verify_tool() {
+    use clap::CommandFactory;
+    Args::command().debug_assert()
+}
@@ -1,14 +1,16 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::table_info_service::TableInfoService;
+use crate::{backup_restore::gcs::GcsBackupRestoreOperator, table_info_service::TableInfoService};
 use aptos_api::context::Context;
 use aptos_config::config::NodeConfig;
 use aptos_db_indexer::{db_ops::open_db, db_v2::IndexerAsyncV2};
+use aptos_executor_types::block_output_stream::BlockOutputPublisher;
+use aptos_logger::info;
 use aptos_mempool::MempoolClientSender;
 use aptos_storage_interface::DbReaderWriter;
 use aptos_types::chain_id::ChainId;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use tokio::runtime::Runtime;
 
 const INDEX_ASYNC_V2_DB_NAME: &str = "index_indexer_async_v2_db";
@@ -20,6 +22,7 @@ pub fn bootstrap(
     chain_id: ChainId,
     db_rw: DbReaderWriter,
     mp_sender: MempoolClientSender,
+    block_output_publisher: &BlockOutputPublisher,
 ) -> Option<(Runtime, Arc<IndexerAsyncV2>)> {
     if !config.indexer_table_info.enabled {
         return None;
@@ -34,6 +37,15 @@ pub fn bootstrap(
         .get_dir_paths()
         .default_root_path()
         .join(INDEX_ASYNC_V2_DB_NAME);
+    if let Some(bucket_name) = node_config
+        .indexer_table_info
+        .db_snapshot_gcs_bucket
+        .clone()
+    {
+        if !db_path.exists() {
+            runtime.block_on(restore_db_snapshot(bucket_name, chain_id, db_path.clone()));
+        }
+    }
     let rocksdb_config = node_config.storage.rocksdb_configs.index_db_config;
     let db =
         open_db(db_path, &rocksdb_config).expect("Failed to open up indexer async v2 db initially");
@@ -41,6 +53,7 @@ pub fn bootstrap(
     let indexer_async_v2 =
         Arc::new(IndexerAsyncV2::new(db).expect("Failed to initialize indexer async v2"));
     let indexer_async_v2_clone = Arc::clone(&indexer_async_v2);
+    let block_output_subscriber = block_output_publisher.subscribe("table_info_service");
 
     // Spawn the runtime for table info parsing
     runtime.spawn(async move {
@@ -59,6 +72,7 @@ pub fn bootstrap(
             node_config.indexer_table_info.parser_batch_size,
             node_config.indexer_table_info.enable_expensive_logging,
             indexer_async_v2_clone,
+            Some(block_output_subscriber),
         );
 
         parser.run().await;
@@ -66,3 +80,29 @@ pub fn bootstrap(
 
     Some((runtime, indexer_async_v2))
 }
+
+/// Restores the table info db from the latest snapshot in the bucket, if there is one.
+/// The restored db carries its own processed version, so parsing resumes from there.
+async fn restore_db_snapshot(bucket_name: String, chain_id: ChainId, db_path: PathBuf) {
+    let backup_restore_operator = GcsBackupRestoreOperator::new(bucket_name).await;
+    backup_restore_operator
+        .verify_storage_bucket_existence()
+        .await;
+    match backup_restore_operator.get_metadata().await {
+        Some(metadata) => {
+            let base_path = db_path
+                .parent()
+                .expect("Table info db path must have a parent")
+                .to_path_buf();
+            backup_restore_operator
+                .restore_db_snapshot(chain_id.id() as u64, metadata, db_path, base_path)
+                .await
+                .expect("Failed to restore table info db snapshot");
+            info!(
+                epoch = metadata.epoch,
+                "[Table Info] Restored table info db from snapshot"
+            );
+        },
+        None => info!("[Table Info] No table info db snapshot found, parsing from genesis"),
+    }
+}
@@ -5,17 +5,19 @@ use anyhow::Error;
 use aptos_api::context::Context;
 use aptos_api_types::TransactionOnChainData;
 use aptos_db_indexer::db_v2::IndexerAsyncV2;
+use aptos_executor_types::block_output_stream::{BlockOutputStreamError, BlockOutputSubscriber};
 use aptos_indexer_grpc_fullnode::stream_coordinator::{
     IndexerStreamCoordinator, TransactionBatchInfo,
 };
 use aptos_indexer_grpc_utils::counters::{log_grpc_step, IndexerGrpcStep};
-use aptos_logger::{debug, error, info, sample, sample::SampleRate};
+use aptos_logger::{debug, error, info, sample, sample::SampleRate, warn};
 use aptos_types::write_set::WriteSet;
 use std::{sync::Arc, time::Duration};
 use tonic::Status;
 
 type EndVersion = u64;
 const LEDGER_VERSION_RETRY_TIME_MILLIS: u64 = 10;
+const BLOCK_OUTPUT_STREAM_TIMEOUT_MILLIS: u64 = 1_000;
 const SERVICE_TYPE: &str = "table_info_service";
 
 pub struct TableInfoService {
@@ -25,6 +27,7 @@ pub struct TableInfoService {
     pub context: Arc<Context>,
     pub enable_expensive_logging: bool,
     pub indexer_async_v2: Arc<IndexerAsyncV2>,
+    pub block_output_subscriber: Option<BlockOutputSubscriber>,
 }
 
 impl TableInfoService {
@@ -35,6 +38,7 @@ impl TableInfoService {
         parser_batch_size: u16,
         enable_expensive_logging: bool,
         indexer_async_v2: Arc<IndexerAsyncV2>,
+        block_output_subscriber: Option<BlockOutputSubscriber>,
     ) -> Self {
         Self {
             current_version: request_start_version,
@@ -43,6 +47,7 @@ impl TableInfoService {
             context,
             enable_expensive_logging,
             indexer_async_v2,
+            block_output_subscriber,
         }
     }
 
@@ -297,14 +302,14 @@ impl TableInfoService {
     /// TODO(jill): consolidate it with `ensure_highest_known_version`
     /// Will keep looping and checking the latest ledger info to see if there are new transactions
     /// If there are, it will update the ledger version version
-    async fn get_highest_known_version(&self) -> Result<u64, Error> {
+    async fn get_highest_known_version(&mut self) -> Result<u64, Error> {
         let mut info = self.context.get_latest_ledger_info_wrapped();
         let mut ledger_version = info.unwrap().ledger_version.0;
         let mut empty_loops = 0;
 
         while ledger_version == 0 || self.current_version > ledger_version {
             if empty_loops > 0 {
-                tokio::time::sleep(Duration::from_millis(LEDGER_VERSION_RETRY_TIME_MILLIS)).await;
+                self.wait_for_new_commits().await;
             }
             empty_loops += 1;
             if let Err(err) = {
@@ -329,4 +334,27 @@ impl TableInfoService {
         }
         Ok(ledger_version)
     }
+
+    /// Waits for new transactions to be committed. If the block output stream is
+    /// available, this waits for the next committed block (or a timeout), instead
+    /// of polling the DB. Missed blocks are fine, as the DB is checked afterwards.
+    async fn wait_for_new_commits(&mut self) {
+        let block_output_subscriber = match &mut self.block_output_subscriber {
+            Some(block_output_subscriber) => block_output_subscriber,
+            None => {
+                tokio::time::sleep(Duration::from_millis(LEDGER_VERSION_RETRY_TIME_MILLIS)).await;
+                return;
+            },
+        };
+
+        let result = tokio::time::timeout(
+            Duration::from_millis(BLOCK_OUTPUT_STREAM_TIMEOUT_MILLIS),
+            block_output_subscriber.recv(),
+        )
+        .await;
+        if let Ok(Err(BlockOutputStreamError::Closed)) = result {
+            warn!("[Table Info] The block output stream was closed! Falling back to polling.");
+            self.block_output_subscriber = None;
+        }
+    }
 }
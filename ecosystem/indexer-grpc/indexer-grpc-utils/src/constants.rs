@@ -11,6 +11,9 @@ pub const GRPC_AUTH_TOKEN_HEADER: &str = "x-aptos-data-authorization";
 // data destination.
 pub const GRPC_REQUEST_NAME_HEADER: &str = "x-aptos-request-name";
 pub const GRPC_API_GATEWAY_API_KEY_HEADER: &str = "authorization";
+// GRPC request metadata key for the filter expression of the transactions to return. See the
+// data service for the syntax.
+pub const GRPC_TRANSACTION_FILTER_HEADER: &str = "x-aptos-transaction-filter";
 // Limit the message size to 15MB. By default the downstream can receive up to 15MB.
 pub const MESSAGE_SIZE_LIMIT: usize = 1024 * 1024 * 15;
 
@@ -4,6 +4,7 @@
 use crate::compression_util::{CacheEntry, StorageFormat};
 use anyhow::Context;
 use aptos_protos::transaction::v1::Transaction;
+use async_trait::async_trait;
 use dashmap::DashMap;
 use itertools::Itertools;
 use prost::Message;
@@ -20,6 +21,9 @@ const IN_MEMORY_CACHE_GC_INTERVAL_MS: u64 = 100;
 // Warm-up cache entries. Pre-fetch the cache entries to warm up the cache.
 pub const WARM_UP_CACHE_ENTRIES: u64 = 20_000;
 pub const MAX_REDIS_FETCH_BATCH_SIZE: usize = 500;
+// Zstd compression level of the cached transactions; favors speed, as every transaction is
+// compressed once, but decompressed for every subscriber.
+const IN_MEMORY_CACHE_COMPRESSION_LEVEL: i32 = 1;
 
 /// Configuration for when we want to explicitly declare how large the cache should be.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -77,6 +81,51 @@ impl InMemoryCacheConfig {
     }
 }
 
+/// A cached transaction, protobuf encoded and compressed with zstd. Memory per cached version
+/// would otherwise be dominated by the deserialized protos.
+#[derive(Clone, Debug)]
+pub struct CompressedTransaction(Arc<[u8]>);
+
+impl CompressedTransaction {
+    pub fn from_transaction(transaction: &Transaction) -> Self {
+        let compressed = zstd::encode_all(
+            transaction.encode_to_vec().as_slice(),
+            IN_MEMORY_CACHE_COMPRESSION_LEVEL,
+        )
+        .expect("Zstd compression failed.");
+        Self(compressed.into())
+    }
+
+    /// The compressed bytes, e.g. for subscribers that accept zstd compressed transactions.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// The size held in memory; the bytes are allocated to their exact length.
+    pub fn size(&self) -> u64 {
+        self.0.len() as u64
+    }
+
+    pub fn to_transaction(&self) -> Transaction {
+        let bytes = zstd::decode_all(self.as_bytes()).expect("Zstd decompression failed.");
+        Transaction::decode(bytes.as_slice()).expect("proto deserialization failed.")
+    }
+}
+
+/// Listens to the changes of the in-memory cache, e.g. so that the file store uploader can make
+/// sure a transaction is durably persisted before it leaves memory.
+#[async_trait]
+pub trait InMemoryCacheListener: Send + Sync {
+    /// Called after the transaction at `version` is inserted into the cache.
+    async fn on_insert(&self, _version: u64, _transaction: &CompressedTransaction) {}
+
+    /// Called before the transaction at `version` is evicted from the cache; the eviction waits
+    /// for this to return, and the transaction is still served from the cache until then.
+    async fn on_evict(&self, _version: u64, _transaction: &CompressedTransaction) {}
+}
+
+type InMemoryCacheListeners = Arc<RwLock<Vec<Arc<dyn InMemoryCacheListener>>>>;
+
 #[derive(Debug, Clone, Copy)]
 struct CacheMetadata {
     total_size_in_bytes: u64,
@@ -84,11 +133,12 @@ struct CacheMetadata {
     first_version: u64,
 }
 
-/// InMemoryCache is a simple in-memory cache that stores the protobuf Transaction.
+/// InMemoryCache is a simple in-memory cache that stores the compressed protobuf Transaction.
 pub struct InMemoryCache {
-    /// Cache maps the cache key to the deserialized Transaction.
-    cache: Arc<DashMap<u64, Arc<Transaction>>>,
+    /// Cache maps the cache key to the compressed Transaction.
+    cache: Arc<DashMap<u64, CompressedTransaction>>,
     cache_metadata: Arc<RwLock<CacheMetadata>>,
+    listeners: InMemoryCacheListeners,
     _cancellation_token_drop_guard: tokio_util::sync::DropGuard,
 }
 
@@ -98,12 +148,27 @@ impl InMemoryCache {
         conn: C,
         storage_format: StorageFormat,
     ) -> anyhow::Result<Self>
+    where
+        C: redis::aio::ConnectionLike + Send + Sync + Clone + 'static,
+    {
+        Self::new_with_redis_connection_and_listeners(cache_config, conn, storage_format, vec![])
+            .await
+    }
+
+    /// Same as `new_with_redis_connection`, but the listeners are also notified of the
+    /// transactions inserted while warming up the cache.
+    pub async fn new_with_redis_connection_and_listeners<C>(
+        cache_config: InMemoryCacheConfig,
+        conn: C,
+        storage_format: StorageFormat,
+        listeners: Vec<Arc<dyn InMemoryCacheListener>>,
+    ) -> anyhow::Result<Self>
     where
         C: redis::aio::ConnectionLike + Send + Sync + Clone + 'static,
     {
         let cache = Arc::new(DashMap::new());
         let (in_memory_first_version, in_memory_latest_version, total_size_in_bytes) =
-            warm_up_the_cache(conn.clone(), cache.clone(), storage_format).await?;
+            warm_up_the_cache(conn.clone(), cache.clone(), storage_format, &listeners).await?;
         tracing::info!(
             "In-memory cache is warmed up to version {}",
             in_memory_latest_version
@@ -114,23 +179,27 @@ impl InMemoryCache {
             total_size_in_bytes,
             latest_version: in_memory_latest_version,
         }));
+        let listeners = Arc::new(RwLock::new(listeners));
         spawn_update_task(
             conn,
             cache.clone(),
             cache_metadata.clone(),
             storage_format,
+            listeners.clone(),
             cancellation_token.clone(),
         );
         spawn_cleanup_task(
             cache_config.size_config.clone(),
             cache.clone(),
             cache_metadata.clone(),
+            listeners.clone(),
             cancellation_token.clone(),
         );
         tracing::info!("In-memory cache is created");
         Ok(Self {
             cache,
             cache_metadata,
+            listeners,
             _cancellation_token_drop_guard: cancellation_token.drop_guard(),
         })
     }
@@ -139,12 +208,20 @@ impl InMemoryCache {
         self.cache_metadata.read().await.latest_version
     }
 
-    // This returns the transaction if it exists in the cache.
-    // If requested version is not in the cache, it blocks until the version is available.
-    // Otherwise, empty.
-    pub async fn get_transactions(&self, starting_version: u64) -> Vec<Transaction> {
-        let start_time = std::time::Instant::now();
-        let (versions_to_fetch, in_memory_latest_version) = loop {
+    /// Subscribes the listener to the transactions inserted into and evicted from the cache from
+    /// now on.
+    pub async fn subscribe(&self, listener: Arc<dyn InMemoryCacheListener>) {
+        self.listeners.write().await.push(listener);
+    }
+
+    // This returns the compressed transactions if they exist in the cache, for subscribers that
+    // don't need them deserialized. If requested version is not in the cache, it blocks until
+    // the version is available. Otherwise, empty.
+    pub async fn get_compressed_transactions(
+        &self,
+        starting_version: u64,
+    ) -> Vec<CompressedTransaction> {
+        let versions_to_fetch = loop {
             let latest_version = self.latest_version().await;
             if starting_version >= latest_version {
                 tokio::time::sleep(std::time::Duration::from_millis(
@@ -158,36 +235,44 @@ impl InMemoryCache {
                 latest_version,
                 starting_version + MAX_REDIS_FETCH_BATCH_SIZE as u64,
             );
-            break (
-                (starting_version..ending_version).collect::<Vec<u64>>(),
-                latest_version,
-            );
+            break (starting_version..ending_version).collect::<Vec<u64>>();
         };
-        let lock_waiting_time = start_time.elapsed().as_secs_f64();
-        let mut arc_transactions = Vec::new();
+        let mut compressed_transactions = Vec::new();
         for key in versions_to_fetch {
             if let Some(transaction) = self.cache.get(&key) {
-                arc_transactions.push(transaction.clone());
+                compressed_transactions.push(transaction.clone());
             } else {
                 break;
             }
         }
+        compressed_transactions
+    }
 
-        let map_lookup_time = start_time.elapsed().as_secs_f64();
-        // Actual clone.
-        let res: Vec<Transaction> = arc_transactions
-            .into_iter()
-            .map(|t| t.as_ref().clone())
-            .collect();
-        let actual_copy_time = start_time.elapsed().as_secs_f64();
+    // This returns the transaction if it exists in the cache.
+    // If requested version is not in the cache, it blocks until the version is available.
+    // Otherwise, empty.
+    pub async fn get_transactions(&self, starting_version: u64) -> Vec<Transaction> {
+        let start_time = std::time::Instant::now();
+        let compressed_transactions = self.get_compressed_transactions(starting_version).await;
+        let lookup_time = start_time.elapsed().as_secs_f64();
+        let compressed_size_in_bytes: u64 = compressed_transactions.iter().map(|t| t.size()).sum();
+        // Decompression is CPU bound, so we spawn_blocking.
+        let res: Vec<Transaction> = tokio::task::spawn_blocking(move || {
+            compressed_transactions
+                .iter()
+                .map(CompressedTransaction::to_transaction)
+                .collect()
+        })
+        .await
+        .expect("Transaction decompression task failed.");
+        let decompression_time = start_time.elapsed().as_secs_f64() - lookup_time;
         tracing::info!(
             transactions_count = res.len(),
             starting_version,
-            in_memory_latest_version,
+            compressed_size_in_bytes,
             duration_in_seconds = start_time.elapsed().as_secs_f64(),
-            lock_waiting_time,
-            map_lookup_time,
-            actual_copy_time,
+            lookup_time,
+            decompression_time,
             "In-memory cache lookup",
         );
         res
@@ -197,8 +282,9 @@ impl InMemoryCache {
 /// Warm up the cache with the latest transactions.
 async fn warm_up_the_cache<C>(
     conn: C,
-    cache: Arc<DashMap<u64, Arc<Transaction>>>,
+    cache: Arc<DashMap<u64, CompressedTransaction>>,
     storage_format: StorageFormat,
+    listeners: &[Arc<dyn InMemoryCacheListener>],
 ) -> anyhow::Result<(u64, u64, u64)>
 where
     C: redis::aio::ConnectionLike + Send + Sync + Clone + 'static,
@@ -215,18 +301,26 @@ where
         (latest_version.saturating_sub(WARM_UP_CACHE_ENTRIES)..latest_version).collect();
     let first_version = versions_to_fetch[0];
     let transactions = batch_get_transactions(&mut conn, versions_to_fetch, storage_format).await?;
-    let total_size_in_bytes = transactions.iter().map(|t| t.encoded_len() as u64).sum();
+    let mut total_size_in_bytes = 0;
     for transaction in transactions {
-        cache.insert(transaction.version, Arc::new(transaction));
+        let compressed_transaction = CompressedTransaction::from_transaction(&transaction);
+        total_size_in_bytes += compressed_transaction.size();
+        cache.insert(transaction.version, compressed_transaction.clone());
+        for listener in listeners {
+            listener
+                .on_insert(transaction.version, &compressed_transaction)
+                .await;
+        }
     }
     Ok((first_version, latest_version, total_size_in_bytes))
 }
 
 fn spawn_update_task<C>(
     conn: C,
-    cache: Arc<DashMap<u64, Arc<Transaction>>>,
+    cache: Arc<DashMap<u64, CompressedTransaction>>,
     cache_metadata: Arc<RwLock<CacheMetadata>>,
     storage_format: StorageFormat,
+    listeners: InMemoryCacheListeners,
     cancellation_token: tokio_util::sync::CancellationToken,
 ) where
     C: redis::aio::ConnectionLike + Send + Sync + Clone + 'static,
@@ -261,22 +355,29 @@ fn spawn_update_task<C>(
                 .await
                 .unwrap();
             // Ensure that transactions are ordered by version.
-            let mut newly_added_bytes = 0;
             for (ind, transaction) in transactions.iter().enumerate() {
                 if transaction.version != in_cache_latest_version + ind as u64 {
                     panic!("Transactions are not ordered by version");
                 }
-                newly_added_bytes += transaction.encoded_len() as u64;
             }
+            let current_listeners = { listeners.read().await.clone() };
+            let mut newly_added_bytes = 0;
             for transaction in transactions {
-                cache.insert(transaction.version, Arc::new(transaction));
+                let compressed_transaction = CompressedTransaction::from_transaction(&transaction);
+                newly_added_bytes += compressed_transaction.size();
+                cache.insert(transaction.version, compressed_transaction.clone());
+                for listener in &current_listeners {
+                    listener
+                        .on_insert(transaction.version, &compressed_transaction)
+                        .await;
+                }
             }
-            let mut current_cache_metadata = { *cache_metadata.read().await };
-            current_cache_metadata.latest_version = end_version;
-            current_cache_metadata.total_size_in_bytes += newly_added_bytes;
-            // Get the data available.
+            // Get the data available. The metadata is updated under the write lock, so that
+            // the concurrent eviction isn't overwritten.
             {
-                *cache_metadata.write().await = current_cache_metadata;
+                let mut current_cache_metadata = cache_metadata.write().await;
+                current_cache_metadata.latest_version = end_version;
+                current_cache_metadata.total_size_in_bytes += newly_added_bytes;
             }
         }
     });
@@ -284,8 +385,9 @@ fn spawn_update_task<C>(
 
 fn spawn_cleanup_task(
     cache_size_config: InMemoryCacheSizeConfig,
-    cache: Arc<DashMap<u64, Arc<Transaction>>>,
+    cache: Arc<DashMap<u64, CompressedTransaction>>,
     cache_metadata: Arc<RwLock<CacheMetadata>>,
+    listeners: InMemoryCacheListeners,
     cancellation_token: tokio_util::sync::CancellationToken,
 ) {
     tokio::spawn(async move {
@@ -294,7 +396,7 @@ fn spawn_cleanup_task(
                 tracing::info!("In-memory cache cleanup task is cancelled.");
                 return;
             }
-            let mut current_cache_metadata = { *cache_metadata.read().await };
+            let current_cache_metadata = { *cache_metadata.read().await };
             let should_evict = current_cache_metadata
                 .total_size_in_bytes
                 .saturating_sub(cache_size_config.cache_eviction_trigger_size_bytes)
@@ -306,21 +408,35 @@ fn spawn_cleanup_task(
                 .await;
                 continue;
             }
+            let current_listeners = { listeners.read().await.clone() };
             let mut actual_bytes_removed = 0;
+            let mut first_version = current_cache_metadata.first_version;
             let mut bytes_to_remove = current_cache_metadata
                 .total_size_in_bytes
                 .saturating_sub(cache_size_config.cache_target_size_bytes);
             while bytes_to_remove > 0 {
-                let key_to_remove = current_cache_metadata.first_version;
+                let key_to_remove = first_version;
+                if !current_listeners.is_empty() {
+                    let transaction = cache
+                        .get(&key_to_remove)
+                        .map(|entry| entry.value().clone())
+                        .expect("Failed to get the key");
+                    for listener in &current_listeners {
+                        listener.on_evict(key_to_remove, &transaction).await;
+                    }
+                }
                 let (_k, v) = cache
                     .remove(&key_to_remove)
                     .expect("Failed to remove the key");
-                bytes_to_remove = bytes_to_remove.saturating_sub(v.encoded_len() as u64);
-                actual_bytes_removed += v.encoded_len() as u64;
-                current_cache_metadata.first_version += 1;
+                bytes_to_remove = bytes_to_remove.saturating_sub(v.size());
+                actual_bytes_removed += v.size();
+                first_version += 1;
             }
+            // The listeners may take a while, so the metadata is updated under the write lock
+            // instead of writing back the copy read above, which may be stale by now.
+            let mut current_cache_metadata = cache_metadata.write().await;
+            current_cache_metadata.first_version = first_version;
             current_cache_metadata.total_size_in_bytes -= actual_bytes_removed;
-            *cache_metadata.write().await = current_cache_metadata;
         }
     });
 }
@@ -392,6 +508,7 @@ where
 mod tests {
     use super::*;
     use redis_test::{MockCmd, MockRedisConnection};
+    use std::sync::Mutex;
 
     fn generate_redis_value_bulk(
         starting_version: u64,
@@ -471,6 +588,54 @@ mod tests {
         assert_eq!(txns[0].version, 0);
     }
 
+    #[tokio::test]
+    async fn test_in_memory_cache_size_accounting() {
+        let mock_connection = MockRedisConnection::new(vec![
+            MockCmd::new(redis::cmd("GET").arg("latest_version"), Ok(3)),
+            MockCmd::new(
+                redis::cmd("MGET").arg(generate_redis_key_bulk(
+                    0,
+                    StorageFormat::Lz4CompressedProto,
+                    3,
+                )),
+                Ok(generate_redis_value_bulk(
+                    0,
+                    StorageFormat::Lz4CompressedProto,
+                    3,
+                )),
+            ),
+        ]);
+        let in_memory_cache = InMemoryCache::new_with_redis_connection(
+            InMemoryCacheConfig::default(),
+            mock_connection.clone(),
+            StorageFormat::Lz4CompressedProto,
+        )
+        .await
+        .unwrap();
+
+        // The size is the size of the compressed transactions held in memory.
+        let compressed_transactions = in_memory_cache.get_compressed_transactions(0).await;
+        assert_eq!(compressed_transactions.len(), 3);
+        assert_eq!(
+            in_memory_cache
+                .cache_metadata
+                .read()
+                .await
+                .total_size_in_bytes,
+            compressed_transactions
+                .iter()
+                .map(|t| t.size())
+                .sum::<u64>()
+        );
+        for (version, compressed_transaction) in compressed_transactions.iter().enumerate() {
+            assert_eq!(compressed_transaction.to_transaction(), Transaction {
+                version: version as u64,
+                block_height: 1,
+                ..Default::default()
+            });
+        }
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn test_in_memory_cache_with_2_batches() {
         let mock_connection = MockRedisConnection::new(vec![
@@ -515,4 +680,66 @@ mod tests {
         assert_eq!(txns.len(), 1);
         assert_eq!(txns[0].version, 1);
     }
+
+    /// Records the versions it's notified of.
+    #[derive(Default)]
+    struct RecordingListener {
+        inserted_versions: Mutex<Vec<u64>>,
+        evicted_versions: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl InMemoryCacheListener for RecordingListener {
+        async fn on_insert(&self, version: u64, _transaction: &CompressedTransaction) {
+            self.inserted_versions.lock().unwrap().push(version);
+        }
+
+        async fn on_evict(&self, version: u64, _transaction: &CompressedTransaction) {
+            self.evicted_versions.lock().unwrap().push(version);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_cache_listeners() {
+        let mock_connection = MockRedisConnection::new(vec![
+            MockCmd::new(redis::cmd("GET").arg("latest_version"), Ok(3)),
+            MockCmd::new(
+                redis::cmd("MGET").arg(generate_redis_key_bulk(
+                    0,
+                    StorageFormat::Base64UncompressedProto,
+                    3,
+                )),
+                Ok(generate_redis_value_bulk(
+                    0,
+                    StorageFormat::Base64UncompressedProto,
+                    3,
+                )),
+            ),
+        ]);
+        // Evict (almost) everything as soon as the cache is warmed up.
+        let cache_config = InMemoryCacheConfig {
+            size_config: InMemoryCacheSizeConfig {
+                cache_target_size_bytes: 1,
+                cache_eviction_trigger_size_bytes: 1,
+            },
+        };
+        let listener = Arc::new(RecordingListener::default());
+        let _in_memory_cache = InMemoryCache::new_with_redis_connection_and_listeners(
+            cache_config,
+            mock_connection.clone(),
+            StorageFormat::Base64UncompressedProto,
+            vec![listener.clone() as Arc<dyn InMemoryCacheListener>],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(*listener.inserted_versions.lock().unwrap(), vec![0, 1, 2]);
+        for _ in 0..100 {
+            if listener.evicted_versions.lock().unwrap().len() == 3 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(*listener.evicted_versions.lock().unwrap(), vec![0, 1, 2]);
+    }
 }
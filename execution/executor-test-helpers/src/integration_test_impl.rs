@@ -2,7 +2,10 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{bootstrap_genesis, gen_block_id, gen_ledger_info_with_sigs};
+use crate::{
+    bootstrap_genesis, gen_block_id, gen_ledger_info_with_sigs,
+    test_ledger_builder::{TestLedger, TestLedgerBuilder},
+};
 use anyhow::{ensure, Result};
 use aptos_cached_packages::aptos_stdlib;
 use aptos_config::config::DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD;
@@ -11,16 +14,15 @@ use aptos_db::AptosDB;
 use aptos_executor::block_executor::BlockExecutor;
 use aptos_executor_types::BlockExecutorTrait;
 use aptos_sdk::{
-    move_types::account_address::AccountAddress,
-    transaction_builder::TransactionFactory,
-    types::{AccountKey, LocalAccount},
+    move_types::account_address::AccountAddress, transaction_builder::TransactionFactory,
+    types::LocalAccount,
 };
 use aptos_storage_interface::{
     state_view::{DbStateViewAtVersion, VerifiedStateViewAtVersion},
     DbReaderWriter, Order,
 };
 use aptos_types::{
-    account_config::{aptos_test_root_address, AccountResource, CoinStoreResource},
+    account_config::{AccountResource, CoinStoreResource},
     block_metadata::BlockMetadata,
     chain_id::ChainId,
     event::EventKey,
@@ -33,7 +35,7 @@ use aptos_types::{
         },
         Transaction,
         Transaction::UserTransaction,
-        TransactionListWithProof, TransactionWithProof, WriteSetPayload,
+        TransactionListWithProof, TransactionWithProof,
     },
     trusted_state::{TrustedState, TrustedStateChange},
     waypoint::Waypoint,
@@ -54,23 +56,19 @@ pub fn test_execution_with_storage_impl_inner(
 ) -> Arc<AptosDB> {
     const B: u64 = 1_000_000_000;
 
-    let (genesis, validators) = aptos_vm_genesis::test_genesis_change_set_and_validators(Some(1));
-    let genesis_txn = Transaction::GenesisTransaction(WriteSetPayload::Direct(genesis));
-
-    let core_resources_account: LocalAccount = LocalAccount::new(
-        aptos_test_root_address(),
-        AccountKey::from_private_key(aptos_vm_genesis::GENESIS_KEYPAIR.0.clone()),
-        0,
-    );
-
-    let (aptos_db, db, executor, waypoint) =
-        create_db_and_executor(db_path, &genesis_txn, force_sharding);
+    let TestLedger {
+        aptos_db,
+        db,
+        executor,
+        waypoint,
+        signer,
+        core_resources_account,
+        ..
+    } = TestLedgerBuilder::new()
+        .force_sharding(force_sharding)
+        .bootstrap(db_path);
 
     let parent_block_id = executor.committed_block_id();
-    let signer = aptos_types::validator_signer::ValidatorSigner::new(
-        validators[0].data.owner_address,
-        validators[0].consensus_key.clone(),
-    );
 
     // This generates accounts that do not overlap with genesis
     let seed = [3u8; 32];
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod integration_test_impl;
+pub mod test_ledger_builder;
 
 use aptos_config::config::NodeConfig;
 use aptos_crypto::{
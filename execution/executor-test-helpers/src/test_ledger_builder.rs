@@ -0,0 +1,342 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{gen_ledger_info_with_sigs, integration_test_impl::create_db_and_executor};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_crypto::HashValue;
+use aptos_db::AptosDB;
+use aptos_executor::block_executor::BlockExecutor;
+use aptos_executor_types::BlockExecutorTrait;
+use aptos_sdk::{
+    transaction_builder::TransactionFactory,
+    types::{AccountKey, LocalAccount},
+};
+use aptos_storage_interface::DbReaderWriter;
+use aptos_types::{
+    account_config::aptos_test_root_address,
+    block_metadata::BlockMetadata,
+    chain_id::ChainId,
+    ledger_info::LedgerInfoWithSignatures,
+    test_helpers::transaction_test_helpers::TEST_BLOCK_EXECUTOR_ONCHAIN_CONFIG,
+    transaction::{
+        signature_verified_transaction::{
+            into_signature_verified_block, SignatureVerifiedTransaction,
+        },
+        SignedTransaction, Transaction, WriteSetPayload,
+    },
+    validator_signer::ValidatorSigner,
+    waypoint::Waypoint,
+};
+use aptos_vm::AptosVM;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{path::Path, sync::Arc};
+
+// The amount of coins minted to each generated account
+pub const ACCOUNT_INITIAL_BALANCE: u64 = 1_000_000_000_000;
+
+// The amount of coins moved by each generated transfer (or mint)
+const TRANSFER_AMOUNT: u64 = 1_000;
+
+/// The mix of user transactions in each generated block (on top of the block
+/// metadata transaction, and the reconfiguration transaction at epoch ends).
+#[derive(Clone, Copy, Debug)]
+pub struct TransactionMix {
+    /// The number of transfers between random generated accounts
+    pub num_transfers: usize,
+    /// The number of mints (by the root account) to random generated accounts
+    pub num_mints: usize,
+}
+
+impl Default for TransactionMix {
+    fn default() -> Self {
+        Self {
+            num_transfers: 10,
+            num_mints: 0,
+        }
+    }
+}
+
+/// A block that was executed and committed by the `TestLedgerBuilder`
+pub struct TestBlock {
+    pub id: HashValue,
+    pub epoch: u64,
+    pub transactions: Vec<SignatureVerifiedTransaction>,
+    pub ledger_info: LedgerInfoWithSignatures,
+}
+
+impl TestBlock {
+    /// Returns true iff the block ends the epoch
+    pub fn ends_epoch(&self) -> bool {
+        self.ledger_info.ledger_info().ends_epoch()
+    }
+}
+
+/// A ledger generated by the `TestLedgerBuilder`
+pub struct TestLedger {
+    pub aptos_db: Arc<AptosDB>,
+    pub db: DbReaderWriter,
+    pub executor: BlockExecutor<AptosVM>,
+    pub waypoint: Waypoint,
+    pub signer: ValidatorSigner,
+    pub core_resources_account: LocalAccount,
+    pub accounts: Vec<LocalAccount>,
+    /// The committed blocks (in order), starting with the block that creates the accounts
+    pub blocks: Vec<TestBlock>,
+}
+
+impl TestLedger {
+    /// Returns the ledger info of the last committed block
+    pub fn latest_ledger_info(&self) -> &LedgerInfoWithSignatures {
+        &self
+            .blocks
+            .last()
+            .expect("The test ledger has no blocks!")
+            .ledger_info
+    }
+}
+
+/// A builder for test ledgers: it bootstraps a DB with the test genesis, and then
+/// executes and commits a configurable number of blocks on top of it (using a
+/// single validator). All randomness is seeded, so the ledgers are deterministic.
+pub struct TestLedgerBuilder {
+    num_accounts: usize,
+    num_blocks: usize,
+    transaction_mix: TransactionMix,
+    blocks_per_epoch: Option<usize>,
+    force_sharding: bool,
+    seed: [u8; 32],
+}
+
+impl Default for TestLedgerBuilder {
+    fn default() -> Self {
+        Self {
+            num_accounts: 4,
+            num_blocks: 1,
+            transaction_mix: TransactionMix::default(),
+            blocks_per_epoch: None,
+            force_sharding: false,
+            seed: [3u8; 32],
+        }
+    }
+}
+
+impl TestLedgerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of accounts to create (and fund) in the first block
+    pub fn num_accounts(mut self, num_accounts: usize) -> Self {
+        self.num_accounts = num_accounts;
+        self
+    }
+
+    /// Sets the number of blocks to generate after the first block
+    pub fn num_blocks(mut self, num_blocks: usize) -> Self {
+        self.num_blocks = num_blocks;
+        self
+    }
+
+    /// Sets the mix of user transactions in each generated block
+    pub fn transaction_mix(mut self, transaction_mix: TransactionMix) -> Self {
+        self.transaction_mix = transaction_mix;
+        self
+    }
+
+    /// Ends the epoch every `blocks_per_epoch` generated blocks
+    pub fn blocks_per_epoch(mut self, blocks_per_epoch: usize) -> Self {
+        assert!(blocks_per_epoch > 0, "Epochs must have at least one block!");
+        self.blocks_per_epoch = Some(blocks_per_epoch);
+        self
+    }
+
+    /// Uses a sharded DB (instead of the default one)
+    pub fn force_sharding(mut self, force_sharding: bool) -> Self {
+        self.force_sharding = force_sharding;
+        self
+    }
+
+    /// Sets the seed used to generate the accounts and transactions
+    pub fn seed(mut self, seed: [u8; 32]) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Creates the DB (at the given path) and only commits genesis to it, for tests that
+    /// execute their own blocks
+    pub fn bootstrap(&self, db_path: &Path) -> TestLedger {
+        let (genesis, validators) =
+            aptos_vm_genesis::test_genesis_change_set_and_validators(Some(1));
+        let genesis_txn = Transaction::GenesisTransaction(WriteSetPayload::Direct(genesis));
+        let (aptos_db, db, executor, waypoint) =
+            create_db_and_executor(db_path, &genesis_txn, self.force_sharding);
+        let signer = ValidatorSigner::new(
+            validators[0].data.owner_address,
+            validators[0].consensus_key.clone(),
+        );
+
+        TestLedger {
+            aptos_db,
+            db,
+            executor,
+            waypoint,
+            signer,
+            core_resources_account: LocalAccount::new(
+                aptos_test_root_address(),
+                AccountKey::from_private_key(aptos_vm_genesis::GENESIS_KEYPAIR.0.clone()),
+                0,
+            ),
+            accounts: vec![],
+            blocks: vec![],
+        }
+    }
+
+    /// Creates the DB (at the given path) and commits all blocks to it
+    pub fn build(self, db_path: &Path) -> TestLedger {
+        let mut ledger = self.bootstrap(db_path);
+        let mut generator = BlockGenerator {
+            rng: StdRng::from_seed(self.seed),
+            txn_factory: TransactionFactory::new(ChainId::test()),
+            epoch: 1,
+            round: 0,
+        };
+
+        // Create and fund the accounts (these don't overlap with genesis)
+        let mut user_txns = vec![];
+        for _ in 0..self.num_accounts {
+            let account = LocalAccount::generate(&mut generator.rng);
+            user_txns.push(
+                ledger.core_resources_account.sign_with_transaction_builder(
+                    generator
+                        .txn_factory
+                        .create_user_account(account.public_key()),
+                ),
+            );
+            user_txns.push(
+                ledger.core_resources_account.sign_with_transaction_builder(
+                    generator
+                        .txn_factory
+                        .mint(account.address(), ACCOUNT_INITIAL_BALANCE),
+                ),
+            );
+            ledger.accounts.push(account);
+        }
+        generator.execute_and_commit_block(&mut ledger, user_txns, false);
+
+        // Generate the remaining blocks
+        for block_index in 1..=self.num_blocks {
+            let user_txns = generator.generate_user_txns(&ledger, self.transaction_mix);
+            let ends_epoch = self.blocks_per_epoch.map_or(false, |blocks_per_epoch| {
+                block_index % blocks_per_epoch == 0
+            });
+            generator.execute_and_commit_block(&mut ledger, user_txns, ends_epoch);
+        }
+
+        ledger
+    }
+}
+
+/// Generates, executes and commits the blocks of a test ledger
+struct BlockGenerator {
+    rng: StdRng,
+    txn_factory: TransactionFactory,
+    epoch: u64,
+    round: u64,
+}
+
+impl BlockGenerator {
+    fn generate_user_txns(
+        &mut self,
+        ledger: &TestLedger,
+        transaction_mix: TransactionMix,
+    ) -> Vec<SignedTransaction> {
+        let num_accounts = ledger.accounts.len();
+        let mut user_txns = vec![];
+
+        // Transfers need (at least) two accounts, so that no account transfers to itself
+        if num_accounts > 1 {
+            for _ in 0..transaction_mix.num_transfers {
+                let sender_index = self.rng.gen_range(0, num_accounts);
+                let receiver_index =
+                    (sender_index + self.rng.gen_range(1, num_accounts)) % num_accounts;
+                let receiver = ledger.accounts[receiver_index].address();
+                user_txns.push(ledger.accounts[sender_index].sign_with_transaction_builder(
+                    self.txn_factory.transfer(receiver, TRANSFER_AMOUNT),
+                ));
+            }
+        }
+        if num_accounts > 0 {
+            for _ in 0..transaction_mix.num_mints {
+                let receiver = ledger.accounts[self.rng.gen_range(0, num_accounts)].address();
+                user_txns.push(ledger.core_resources_account.sign_with_transaction_builder(
+                    self.txn_factory.mint(receiver, TRANSFER_AMOUNT),
+                ));
+            }
+        }
+        user_txns
+    }
+
+    fn execute_and_commit_block(
+        &mut self,
+        ledger: &mut TestLedger,
+        mut user_txns: Vec<SignedTransaction>,
+        ends_epoch: bool,
+    ) {
+        // Reconfigurations must come last, as the transactions after them are retried
+        if ends_epoch {
+            user_txns.push(
+                ledger.core_resources_account.sign_with_transaction_builder(
+                    self.txn_factory
+                        .payload(aptos_stdlib::aptos_governance_force_end_epoch_test_only()),
+                ),
+            );
+        }
+
+        let block_index = ledger.blocks.len() as u64 + 1;
+        let block_id = HashValue::sha3_256_of(&block_index.to_le_bytes());
+        let block_metadata = Transaction::BlockMetadata(BlockMetadata::new(
+            block_id,
+            self.epoch,
+            self.round,
+            ledger.signer.author(),
+            vec![0],
+            vec![],
+            block_index, // timestamp
+        ));
+        let transactions = into_signature_verified_block(
+            std::iter::once(block_metadata)
+                .chain(user_txns.into_iter().map(Transaction::UserTransaction))
+                .collect(),
+        );
+
+        let parent_block_id = ledger.executor.committed_block_id();
+        let output = ledger
+            .executor
+            .execute_block(
+                (block_id, transactions.clone()).into(),
+                parent_block_id,
+                TEST_BLOCK_EXECUTOR_ONCHAIN_CONFIG,
+            )
+            .unwrap();
+        let ledger_info =
+            gen_ledger_info_with_sigs(self.epoch, &output, block_id, &[ledger.signer.clone()]);
+        ledger
+            .executor
+            .commit_blocks(vec![block_id], ledger_info.clone())
+            .unwrap();
+        ledger.blocks.push(TestBlock {
+            id: block_id,
+            epoch: self.epoch,
+            transactions,
+            ledger_info,
+        });
+
+        // Move to the next round (or epoch)
+        if ends_epoch {
+            self.epoch += 1;
+            self.round = 0;
+        } else {
+            self.round += 1;
+        }
+    }
+}
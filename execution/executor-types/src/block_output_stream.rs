@@ -0,0 +1,242 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use crate::metrics::{
+    BLOCK_OUTPUT_STREAM_MISSED_BLOCKS, BLOCK_OUTPUT_STREAM_PUBLISHED_BLOCKS,
+    BLOCK_OUTPUT_STREAM_SUBSCRIBER_LAG,
+};
+use aptos_types::{
+    contract_event::EventWithVersion,
+    transaction::{TransactionToCommit, Version},
+    write_set::WriteOp,
+};
+use std::{
+    ops::Range,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use thiserror::Error;
+use tokio::sync::broadcast;
+
+/// A summary of the state writes made by a committed block
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct WriteSetSummary {
+    pub num_creations: usize,
+    pub num_modifications: usize,
+    pub num_deletions: usize,
+    pub num_bytes_written: usize,
+}
+
+/// The output of a committed block (or chunk of transactions, when committed by
+/// state sync), as published to the in-process subscribers.
+#[derive(Clone, Debug)]
+pub struct CommittedBlockOutput {
+    /// The versions of the committed transactions
+    pub versions: Range<Version>,
+    /// The events emitted by the committed transactions
+    pub events: Vec<EventWithVersion>,
+    /// A summary of the state writes of the committed transactions
+    pub write_set_summary: WriteSetSummary,
+}
+
+impl CommittedBlockOutput {
+    pub fn new(first_version: Version, txns_to_commit: &[TransactionToCommit]) -> Self {
+        let mut events = vec![];
+        let mut write_set_summary = WriteSetSummary::default();
+        for (version, txn_to_commit) in (first_version..).zip(txns_to_commit) {
+            events.extend(
+                txn_to_commit
+                    .events()
+                    .iter()
+                    .map(|event| EventWithVersion::new(version, event.clone())),
+            );
+            for (_, write_op) in txn_to_commit.write_set().iter() {
+                match write_op {
+                    WriteOp::Creation { .. } => write_set_summary.num_creations += 1,
+                    WriteOp::Modification { .. } => write_set_summary.num_modifications += 1,
+                    WriteOp::Deletion { .. } => write_set_summary.num_deletions += 1,
+                }
+                write_set_summary.num_bytes_written += write_op.size();
+            }
+        }
+
+        Self {
+            versions: first_version..first_version + txns_to_commit.len() as Version,
+            events,
+            write_set_summary,
+        }
+    }
+}
+
+/// The errors returned to subscribers of the block output stream
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+pub enum BlockOutputStreamError {
+    #[error("The subscriber fell behind and missed {0} committed blocks")]
+    Lagged(u64),
+    #[error("The block output stream was closed")]
+    Closed,
+}
+
+/// Publishes the outputs of committed blocks to in-process subscribers (e.g., indexers),
+/// so that they don't have to poll the DB for new commits. The stream is bounded, and
+/// publishing never blocks the commit path: subscribers that fall too far behind miss
+/// the oldest blocks, and must catch up by reading the DB.
+#[derive(Clone, Debug)]
+pub struct BlockOutputPublisher {
+    sender: broadcast::Sender<Arc<CommittedBlockOutput>>,
+
+    // The version after the last published block (shared with the subscribers)
+    next_version: Arc<AtomicU64>,
+}
+
+impl BlockOutputPublisher {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            next_version: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Returns a new subscriber that receives all blocks committed from now on.
+    /// The name is used to label the subscriber metrics.
+    pub fn subscribe(&self, name: &'static str) -> BlockOutputSubscriber {
+        BlockOutputSubscriber {
+            name,
+            receiver: self.sender.subscribe(),
+            next_version: self.next_version.clone(),
+        }
+    }
+
+    /// Publishes the given committed transactions (starting at `first_version`) as a block
+    pub fn publish(&self, first_version: Version, txns_to_commit: &[TransactionToCommit]) {
+        if txns_to_commit.is_empty() {
+            return;
+        }
+        self.next_version.store(
+            first_version + txns_to_commit.len() as Version,
+            Ordering::Relaxed,
+        );
+
+        // Avoid building the block output if no one is listening
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+        let block_output = CommittedBlockOutput::new(first_version, txns_to_commit);
+        if self.sender.send(Arc::new(block_output)).is_ok() {
+            BLOCK_OUTPUT_STREAM_PUBLISHED_BLOCKS.inc();
+        }
+    }
+}
+
+/// A subscriber to the block output stream (see `BlockOutputPublisher`)
+#[derive(Debug)]
+pub struct BlockOutputSubscriber {
+    name: &'static str,
+    receiver: broadcast::Receiver<Arc<CommittedBlockOutput>>,
+    next_version: Arc<AtomicU64>,
+}
+
+impl BlockOutputSubscriber {
+    /// Waits for the next committed block. If the subscriber fell too far behind,
+    /// the oldest blocks are dropped and `Lagged` is returned (with the number of
+    /// missed blocks). The next call then returns the oldest block still buffered.
+    pub async fn recv(&mut self) -> Result<Arc<CommittedBlockOutput>, BlockOutputStreamError> {
+        match self.receiver.recv().await {
+            Ok(block_output) => {
+                let lag = self
+                    .next_version
+                    .load(Ordering::Relaxed)
+                    .saturating_sub(block_output.versions.end);
+                BLOCK_OUTPUT_STREAM_SUBSCRIBER_LAG
+                    .with_label_values(&[self.name])
+                    .set(lag as i64);
+                Ok(block_output)
+            },
+            Err(broadcast::error::RecvError::Lagged(num_missed_blocks)) => {
+                BLOCK_OUTPUT_STREAM_MISSED_BLOCKS
+                    .with_label_values(&[self.name])
+                    .inc_by(num_missed_blocks);
+                Err(BlockOutputStreamError::Lagged(num_missed_blocks))
+            },
+            Err(broadcast::error::RecvError::Closed) => Err(BlockOutputStreamError::Closed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        contract_event::ContractEvent, state_store::state_key::StateKey, write_set::WriteSetMut,
+    };
+
+    fn create_txn_to_commit(num_events: usize) -> TransactionToCommit {
+        let events = (0..num_events)
+            .map(|_| ContractEvent::new_v2_with_type_tag_str("0x1::test::TestEvent", vec![]))
+            .collect();
+        let write_set = WriteSetMut::new(vec![
+            (
+                StateKey::raw(b"created"),
+                WriteOp::legacy_creation(vec![0; 10].into()),
+            ),
+            (StateKey::raw(b"deleted"), WriteOp::legacy_deletion()),
+        ])
+        .freeze()
+        .unwrap();
+        TransactionToCommit {
+            write_set,
+            ..TransactionToCommit::dummy_with_events(events)
+        }
+    }
+
+    #[test]
+    fn test_committed_block_output() {
+        let txns_to_commit = vec![create_txn_to_commit(1), create_txn_to_commit(2)];
+        let block_output = CommittedBlockOutput::new(10, &txns_to_commit);
+
+        assert_eq!(block_output.versions, 10..12);
+        let event_versions: Vec<_> = block_output
+            .events
+            .iter()
+            .map(|event| event.transaction_version)
+            .collect();
+        assert_eq!(event_versions, vec![10, 11, 11]);
+        assert_eq!(block_output.write_set_summary, WriteSetSummary {
+            num_creations: 2,
+            num_modifications: 0,
+            num_deletions: 2,
+            num_bytes_written: 20,
+        });
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber() {
+        let publisher = BlockOutputPublisher::new(2);
+        let mut subscriber = publisher.subscribe("test");
+
+        // Publish more blocks than the subscriber can buffer
+        for first_version in 0..4 {
+            publisher.publish(first_version, &[create_txn_to_commit(0)]);
+        }
+
+        // Verify the subscriber missed the oldest blocks, and then receives the rest
+        assert_eq!(
+            subscriber.recv().await.unwrap_err(),
+            BlockOutputStreamError::Lagged(2)
+        );
+        assert_eq!(subscriber.recv().await.unwrap().versions, 2..3);
+        assert_eq!(subscriber.recv().await.unwrap().versions, 3..4);
+
+        // Verify the subscriber is notified once the publisher is dropped
+        drop(publisher);
+        assert_eq!(
+            subscriber.recv().await.unwrap_err(),
+            BlockOutputStreamError::Closed
+        );
+    }
+}
@@ -42,10 +42,12 @@ use std::{
     },
 };
 
+pub mod block_output_stream;
 mod error;
 mod executed_chunk;
 pub mod execution_output;
 mod ledger_update_output;
+mod metrics;
 pub mod parsed_transaction_output;
 pub mod state_checkpoint_output;
 
@@ -0,0 +1,34 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_metrics_core::{
+    register_int_counter, register_int_counter_vec, register_int_gauge_vec, IntCounter,
+    IntCounterVec, IntGaugeVec,
+};
+use once_cell::sync::Lazy;
+
+pub static BLOCK_OUTPUT_STREAM_PUBLISHED_BLOCKS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_executor_block_output_stream_published_blocks",
+        "The number of committed blocks published to the block output stream"
+    )
+    .unwrap()
+});
+
+pub static BLOCK_OUTPUT_STREAM_SUBSCRIBER_LAG: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_executor_block_output_stream_subscriber_lag",
+        "The number of versions a block output stream subscriber is behind the latest commit",
+        &["subscriber"]
+    )
+    .unwrap()
+});
+
+pub static BLOCK_OUTPUT_STREAM_MISSED_BLOCKS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_executor_block_output_stream_missed_blocks",
+        "The number of committed blocks missed by slow block output stream subscribers",
+        &["subscriber"]
+    )
+    .unwrap()
+});
@@ -19,8 +19,9 @@ use crate::{
 use anyhow::Result;
 use aptos_crypto::HashValue;
 use aptos_executor_types::{
-    execution_output::ExecutionOutput, state_checkpoint_output::StateCheckpointOutput,
-    BlockExecutorTrait, ExecutorError, ExecutorResult, StateComputeResult,
+    block_output_stream::BlockOutputPublisher, execution_output::ExecutionOutput,
+    state_checkpoint_output::StateCheckpointOutput, BlockExecutorTrait, ExecutorError,
+    ExecutorResult, StateComputeResult,
 };
 use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
 use aptos_infallible::RwLock;
@@ -62,6 +63,7 @@ impl TransactionBlockExecutor for AptosVM {
 pub struct BlockExecutor<V> {
     pub db: DbReaderWriter,
     inner: RwLock<Option<BlockExecutorInner<V>>>,
+    block_output_publisher: Option<BlockOutputPublisher>,
 }
 
 impl<V> BlockExecutor<V>
@@ -72,6 +74,19 @@ where
         Self {
             db,
             inner: RwLock::new(None),
+            block_output_publisher: None,
+        }
+    }
+
+    /// Creates a block executor that publishes the outputs of committed blocks
+    pub fn new_with_block_output_publisher(
+        db: DbReaderWriter,
+        block_output_publisher: BlockOutputPublisher,
+    ) -> Self {
+        Self {
+            db,
+            inner: RwLock::new(None),
+            block_output_publisher: Some(block_output_publisher),
         }
     }
 
@@ -105,7 +120,10 @@ where
     }
 
     fn reset(&self) -> Result<()> {
-        *self.inner.write() = Some(BlockExecutorInner::new(self.db.clone())?);
+        *self.inner.write() = Some(BlockExecutorInner::new(
+            self.db.clone(),
+            self.block_output_publisher.clone(),
+        )?);
         Ok(())
     }
 
@@ -158,6 +176,7 @@ where
 struct BlockExecutorInner<V> {
     db: DbReaderWriter,
     block_tree: BlockTree,
+    block_output_publisher: Option<BlockOutputPublisher>,
     phantom: PhantomData<V>,
 }
 
@@ -165,11 +184,15 @@ impl<V> BlockExecutorInner<V>
 where
     V: TransactionBlockExecutor,
 {
-    pub fn new(db: DbReaderWriter) -> Result<Self> {
+    pub fn new(
+        db: DbReaderWriter,
+        block_output_publisher: Option<BlockOutputPublisher>,
+    ) -> Result<Self> {
         let block_tree = BlockTree::new(&db.reader)?;
         Ok(Self {
             db,
             block_tree,
+            block_output_publisher,
             phantom: PhantomData,
         })
     }
@@ -372,6 +395,7 @@ where
             .get_ledger_update()
             .txn_accumulator()
             .num_leaves();
+        let first_version_to_commit = first_version;
 
         let to_commit = blocks
             .iter()
@@ -422,6 +446,16 @@ where
             .prune(ledger_info_with_sigs.ledger_info())
             .expect("Failure pruning block tree.");
 
+        // Publish the committed blocks (only once the ledger info is also committed)
+        if let Some(block_output_publisher) = &self.block_output_publisher {
+            let mut first_version = first_version_to_commit;
+            for block in &blocks {
+                let txns_to_commit = block.output.get_ledger_update().transactions_to_commit();
+                block_output_publisher.publish(first_version, txns_to_commit);
+                first_version += txns_to_commit.len() as u64;
+            }
+        }
+
         Ok(())
     }
 }
@@ -20,8 +20,8 @@ use crate::{
 use anyhow::{anyhow, ensure, Result};
 use aptos_drop_helper::DEFAULT_DROPPER;
 use aptos_executor_types::{
-    ChunkCommitNotification, ChunkExecutorTrait, ExecutedChunk, ParsedTransactionOutput,
-    TransactionReplayer, VerifyExecutionMode,
+    block_output_stream::BlockOutputPublisher, ChunkCommitNotification, ChunkExecutorTrait,
+    ExecutedChunk, ParsedTransactionOutput, TransactionReplayer, VerifyExecutionMode,
 };
 use aptos_experimental_runtimes::thread_manager::{optimal_min_len, THREAD_MANAGER};
 use aptos_infallible::{Mutex, RwLock};
@@ -63,6 +63,7 @@ pub static SIG_VERIFY_POOL: Lazy<Arc<rayon::ThreadPool>> = Lazy::new(|| {
 pub struct ChunkExecutor<V> {
     db: DbReaderWriter,
     inner: RwLock<Option<ChunkExecutorInner<V>>>,
+    block_output_publisher: Option<BlockOutputPublisher>,
 }
 
 impl<V: VMExecutor> ChunkExecutor<V> {
@@ -70,6 +71,19 @@ impl<V: VMExecutor> ChunkExecutor<V> {
         Self {
             db,
             inner: RwLock::new(None),
+            block_output_publisher: None,
+        }
+    }
+
+    /// Creates a chunk executor that publishes the committed chunks as block outputs
+    pub fn new_with_block_output_publisher(
+        db: DbReaderWriter,
+        block_output_publisher: BlockOutputPublisher,
+    ) -> Self {
+        Self {
+            db,
+            inner: RwLock::new(None),
+            block_output_publisher: Some(block_output_publisher),
         }
     }
 
@@ -130,7 +144,10 @@ impl<V: VMExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
     }
 
     fn reset(&self) -> Result<()> {
-        *self.inner.write() = Some(ChunkExecutorInner::new(self.db.clone())?);
+        *self.inner.write() = Some(ChunkExecutorInner::new(
+            self.db.clone(),
+            self.block_output_publisher.clone(),
+        )?);
         Ok(())
     }
 
@@ -142,15 +159,20 @@ impl<V: VMExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
 struct ChunkExecutorInner<V> {
     db: DbReaderWriter,
     commit_queue: Mutex<ChunkCommitQueue>,
+    block_output_publisher: Option<BlockOutputPublisher>,
     _phantom: PhantomData<V>,
 }
 
 impl<V: VMExecutor> ChunkExecutorInner<V> {
-    pub fn new(db: DbReaderWriter) -> Result<Self> {
+    pub fn new(
+        db: DbReaderWriter,
+        block_output_publisher: Option<BlockOutputPublisher>,
+    ) -> Result<Self> {
         let commit_queue = Mutex::new(ChunkCommitQueue::new_from_db(&db.reader)?);
         Ok(Self {
             db,
             commit_queue,
+            block_output_publisher,
             _phantom: PhantomData,
         })
     }
@@ -194,6 +216,13 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
                     .clone(),
                 Some(&chunk.ledger_update_output.sharded_state_cache),
             )?;
+
+            if let Some(block_output_publisher) = &self.block_output_publisher {
+                block_output_publisher.publish(
+                    persisted_state.next_version(),
+                    chunk.transactions_to_commit(),
+                );
+            }
         }
 
         DEFAULT_DROPPER.schedule_drop(persisted_state);
@@ -5,29 +5,28 @@
 #![forbid(unsafe_code)]
 
 use aptos_cached_packages::aptos_stdlib;
-use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey, Uniform};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey};
 use aptos_db::AptosDB;
 use aptos_executor::{
     block_executor::BlockExecutor,
     db_bootstrapper::{generate_waypoint, maybe_bootstrap},
 };
 use aptos_executor_test_helpers::{
-    bootstrap_genesis, gen_ledger_info_with_sigs, get_test_signed_transaction,
+    gen_ledger_info_with_sigs, get_test_signed_transaction,
+    test_ledger_builder::{TestLedgerBuilder, ACCOUNT_INITIAL_BALANCE},
 };
 use aptos_executor_types::BlockExecutorTrait;
 use aptos_storage_interface::{state_view::LatestDbStateCheckpointView, DbReaderWriter};
 use aptos_temppath::TempPath;
 use aptos_types::{
     account_address::AccountAddress,
-    account_config::{
-        aptos_test_root_address, new_block_event_key, CoinStoreResource, NewBlockEvent,
-    },
+    account_config::{new_block_event_key, CoinStoreResource, NewBlockEvent},
     contract_event::ContractEvent,
     event::EventHandle,
     on_chain_config::{ConfigurationResource, OnChainConfig, ValidatorSet},
     state_store::{state_key::StateKey, MoveResourceExt},
     test_helpers::transaction_test_helpers::{block, TEST_BLOCK_EXECUTOR_ONCHAIN_CONFIG},
-    transaction::{authenticator::AuthenticationKey, ChangeSet, Transaction, WriteSetPayload},
+    transaction::{ChangeSet, Transaction, WriteSetPayload},
     trusted_state::TrustedState,
     validator_signer::ValidatorSigner,
     waypoint::Waypoint,
@@ -35,7 +34,6 @@ use aptos_types::{
 };
 use aptos_vm::AptosVM;
 use move_core_types::{language_storage::TypeTag, move_resource::MoveStructType};
-use rand::SeedableRng;
 
 #[test]
 fn test_empty_db() {
@@ -95,59 +93,6 @@ fn execute_and_commit(txns: Vec<Transaction>, db: &DbReaderWriter, signer: &Vali
         .unwrap();
 }
 
-fn get_demo_accounts() -> (
-    AccountAddress,
-    Ed25519PrivateKey,
-    AccountAddress,
-    Ed25519PrivateKey,
-) {
-    // This seed avoids collisions with other accounts
-    let seed = [3u8; 32];
-    let mut rng = ::rand::rngs::StdRng::from_seed(seed);
-
-    let privkey1 = Ed25519PrivateKey::generate(&mut rng);
-    let pubkey1 = privkey1.public_key();
-    let account1_auth_key = AuthenticationKey::ed25519(&pubkey1);
-    let account1 = account1_auth_key.account_address();
-
-    let privkey2 = Ed25519PrivateKey::generate(&mut rng);
-    let pubkey2 = privkey2.public_key();
-    let account2_auth_key = AuthenticationKey::ed25519(&pubkey2);
-    let account2 = account2_auth_key.account_address();
-
-    (account1, privkey1, account2, privkey2)
-}
-
-fn get_aptos_coin_mint_transaction(
-    aptos_root_key: &Ed25519PrivateKey,
-    aptos_root_seq_num: u64,
-    account: &AccountAddress,
-    amount: u64,
-) -> Transaction {
-    get_test_signed_transaction(
-        aptos_test_root_address(),
-        /* sequence_number = */ aptos_root_seq_num,
-        aptos_root_key.clone(),
-        aptos_root_key.public_key(),
-        Some(aptos_stdlib::aptos_coin_mint(*account, amount)),
-    )
-}
-
-fn get_account_transaction(
-    aptos_root_key: &Ed25519PrivateKey,
-    aptos_root_seq_num: u64,
-    account: &AccountAddress,
-    _account_key: &Ed25519PrivateKey,
-) -> Transaction {
-    get_test_signed_transaction(
-        aptos_test_root_address(),
-        /* sequence_number = */ aptos_root_seq_num,
-        aptos_root_key.clone(),
-        aptos_root_key.public_key(),
-        Some(aptos_stdlib::aptos_account_create_account(*account)),
-    )
-}
-
 fn get_aptos_coin_transfer_transaction(
     sender: AccountAddress,
     sender_seq_number: u64,
@@ -180,27 +125,19 @@ fn get_configuration(db: &DbReaderWriter) -> ConfigurationResource {
 #[test]
 #[cfg_attr(feature = "consensus-only-perf-test", ignore)]
 fn test_new_genesis() {
-    let genesis = aptos_vm_genesis::test_genesis_change_set_and_validators(Some(1));
-    let genesis_key = &aptos_vm_genesis::GENESIS_KEYPAIR.0;
-    let genesis_txn = Transaction::GenesisTransaction(WriteSetPayload::Direct(genesis.0));
-    // Create bootstrapped DB.
+    // Create bootstrapped DB, with 2 funded accounts.
     let tmp_dir = TempPath::new();
-    let db = DbReaderWriter::new(AptosDB::new_for_test(&tmp_dir));
-    let waypoint = bootstrap_genesis::<AptosVM>(&db, &genesis_txn).unwrap();
-    let signer = ValidatorSigner::new(
-        genesis.1[0].data.owner_address,
-        genesis.1[0].consensus_key.clone(),
-    );
-
-    // Mint for 2 demo accounts.
-    let (account1, account1_key, account2, account2_key) = get_demo_accounts();
-    let txn1 = get_account_transaction(genesis_key, 0, &account1, &account1_key);
-    let txn2 = get_account_transaction(genesis_key, 1, &account2, &account2_key);
-    let txn3 = get_aptos_coin_mint_transaction(genesis_key, 2, &account1, 200_000_000);
-    let txn4 = get_aptos_coin_mint_transaction(genesis_key, 3, &account2, 200_000_000);
-    execute_and_commit(vec![txn1, txn2, txn3, txn4], &db, &signer);
-    assert_eq!(get_balance(&account1, &db), 200_000_000);
-    assert_eq!(get_balance(&account2, &db), 200_000_000);
+    let ledger = TestLedgerBuilder::new()
+        .num_accounts(2)
+        .num_blocks(0)
+        .build(tmp_dir.path());
+    let db = ledger.db;
+    let waypoint = ledger.waypoint;
+    let signer = ledger.signer;
+    let account1 = ledger.accounts[0].address();
+    let account2 = ledger.accounts[1].address();
+    assert_eq!(get_balance(&account1, &db), ACCOUNT_INITIAL_BALANCE);
+    assert_eq!(get_balance(&account2, &db), ACCOUNT_INITIAL_BALANCE);
 
     let trusted_state = TrustedState::from_epoch_waypoint(waypoint);
     let state_proof = db.reader.get_state_proof(trusted_state.version()).unwrap();
@@ -264,7 +201,7 @@ fn test_new_genesis() {
     assert!(maybe_bootstrap::<AptosVM>(&db, &genesis_txn, waypoint)
         .unwrap()
         .is_some());
-    assert_eq!(waypoint.version(), 6);
+    assert_eq!(waypoint.version(), 7);
 
     // Client bootable from waypoint.
     let trusted_state = TrustedState::from_epoch_waypoint(waypoint);
@@ -272,18 +209,22 @@ fn test_new_genesis() {
     let trusted_state_change = trusted_state.verify_and_ratchet(&state_proof).unwrap();
     assert!(trusted_state_change.is_epoch_change());
     let trusted_state = trusted_state_change.new_state().unwrap();
-    assert_eq!(trusted_state.version(), 6);
+    assert_eq!(trusted_state.version(), 7);
 
     // Effect of bootstrapping reflected.
     assert_eq!(get_balance(&account1, &db), 100_000_000);
     // State before new genesis accessible.
-    assert_eq!(get_balance(&account2, &db), 200_000_000);
+    assert_eq!(get_balance(&account2, &db), ACCOUNT_INITIAL_BALANCE);
 
     println!("FINAL TRANSFER");
     // Transfer some money.
-    let txn = get_aptos_coin_transfer_transaction(account1, 0, &account1_key, account2, 50_000_000);
+    let account1_key = ledger.accounts[0].private_key();
+    let txn = get_aptos_coin_transfer_transaction(account1, 0, account1_key, account2, 50_000_000);
     execute_and_commit(vec![txn], &db, &signer);
 
     // And verify.
-    assert_eq!(get_balance(&account2, &db), 250_000_000);
+    assert_eq!(
+        get_balance(&account2, &db),
+        ACCOUNT_INITIAL_BALANCE + 50_000_000
+    );
 }
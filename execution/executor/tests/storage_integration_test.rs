@@ -9,6 +9,7 @@ use aptos_executor_test_helpers::{
     integration_test_impl::{
         create_db_and_executor, test_execution_with_storage_impl, verify_committed_txn_status,
     },
+    test_ledger_builder::{TestBlock, TestLedgerBuilder, TransactionMix},
 };
 use aptos_executor_types::BlockExecutorTrait;
 use aptos_storage_interface::state_view::DbStateViewAtVersion;
@@ -186,3 +187,52 @@ fn test_reconfiguration() {
 fn test_execution_with_storage() {
     test_execution_with_storage_impl();
 }
+
+#[test]
+#[cfg_attr(feature = "consensus-only-perf-test", ignore)]
+fn test_multi_epoch_ledger() {
+    // Create a ledger with several epochs
+    let path = aptos_temppath::TempPath::new();
+    path.create_as_dir().unwrap();
+    let ledger = TestLedgerBuilder::new()
+        .num_accounts(3)
+        .num_blocks(4)
+        .transaction_mix(TransactionMix {
+            num_transfers: 5,
+            num_mints: 1,
+        })
+        .blocks_per_epoch(2)
+        .build(path.path());
+
+    // Verify the epochs end every two blocks (after the account creation block)
+    let epochs: Vec<_> = ledger.blocks.iter().map(|block| block.epoch).collect();
+    assert_eq!(epochs, vec![1, 1, 1, 2, 2]);
+    let ends_epoch: Vec<_> = ledger.blocks.iter().map(TestBlock::ends_epoch).collect();
+    assert_eq!(ends_epoch, vec![false, false, true, false, true]);
+
+    // Verify the state proof ratchets the trusted state across all epochs
+    let trusted_state = TrustedState::from_epoch_waypoint(ledger.waypoint);
+    let state_proof = ledger
+        .db
+        .reader
+        .get_state_proof(trusted_state.version())
+        .unwrap();
+    trusted_state.verify_and_ratchet(&state_proof).unwrap();
+    let latest_li = state_proof.latest_ledger_info();
+    assert_eq!(latest_li, ledger.latest_ledger_info().ledger_info());
+
+    // Verify all transactions were committed (blocks that don't end an epoch
+    // also commit a state checkpoint transaction, after the block transactions)
+    let mut first_version = 1;
+    for block in &ledger.blocks {
+        for (version, txn) in (first_version..).zip(&block.transactions) {
+            let txn_with_proof = ledger
+                .db
+                .reader
+                .get_transaction_by_version(version, latest_li.version(), false)
+                .unwrap();
+            verify_committed_txn_status(latest_li, &txn_with_proof, txn).unwrap();
+        }
+        first_version = block.ledger_info.ledger_info().version() + 1;
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{PepperClient, PepperClientError};
+use aptos_infallible::Mutex;
+use aptos_keyless_pepper_common::{
+    jwt, PepperRequest, PepperResponse, PepperV0VufPubKey, SignatureResponse,
+};
+use async_trait::async_trait;
+use lru::LruCache;
+
+/// The default number of peppers kept in the cache
+const DEFAULT_CACHE_SIZE: usize = 1_000;
+/// The JWT field identifying the user, if the request doesn't specify one
+const DEFAULT_UID_KEY: &str = "sub";
+
+/// The identity that a pepper is derived from. The pepper service derives the same pepper
+/// (and account address) for all requests with the same identity, e.g., across logins with
+/// different ephemeral keys.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct PepperCacheKey {
+    iss: String,
+    aud: String,
+    uid_key: String,
+    uid_val: String,
+    derivation_path: Option<String>,
+}
+
+impl PepperCacheKey {
+    /// Returns the identity of the request, or `None` if it can't be determined from the JWT
+    /// (in which case the pepper service will reject the request anyway).
+    fn from_request(request: &PepperRequest) -> Option<Self> {
+        let claims = jwt::parse(&request.jwt).ok()?.claims;
+        let uid_key = request
+            .uid_key
+            .clone()
+            .unwrap_or_else(|| DEFAULT_UID_KEY.to_string());
+        let uid_val = match uid_key.as_str() {
+            "sub" => claims.sub,
+            "email" => claims.email?,
+            _ => return None,
+        };
+
+        Some(Self {
+            iss: claims.iss,
+            aud: claims.aud,
+            uid_key,
+            uid_val,
+            derivation_path: request.derivation_path.clone(),
+        })
+    }
+}
+
+/// A pepper client that caches the peppers fetched by the given client, keyed by the identity
+/// in the JWT of the request.
+///
+/// Note: cached peppers are returned without sending the request to the pepper service, so
+/// requests that the service would reject (e.g., because the ephemeral key expired) are only
+/// rejected if the pepper is not cached yet.
+pub struct CachingPepperClient<C> {
+    client: C,
+    cache: Mutex<LruCache<PepperCacheKey, PepperResponse>>,
+}
+
+impl<C: PepperClient> CachingPepperClient<C> {
+    pub fn new(client: C) -> Self {
+        Self::new_with_cache_size(client, DEFAULT_CACHE_SIZE)
+    }
+
+    pub fn new_with_cache_size(client: C, cache_size: usize) -> Self {
+        Self {
+            client,
+            cache: Mutex::new(LruCache::new(cache_size)),
+        }
+    }
+
+    /// Returns the wrapped client
+    pub fn inner(&self) -> &C {
+        &self.client
+    }
+}
+
+#[async_trait]
+impl<C: PepperClient> PepperClient for CachingPepperClient<C> {
+    async fn fetch_pepper(
+        &self,
+        request: &PepperRequest,
+    ) -> Result<PepperResponse, PepperClientError> {
+        let cache_key = PepperCacheKey::from_request(request);
+        if let Some(cache_key) = &cache_key {
+            if let Some(response) = self.cache.lock().get(cache_key) {
+                return Ok(response.clone());
+            }
+        }
+
+        let response = self.client.fetch_pepper(request).await?;
+        if let Some(cache_key) = cache_key {
+            self.cache.lock().put(cache_key, response.clone());
+        }
+        Ok(response)
+    }
+
+    async fn fetch_signature(
+        &self,
+        request: &PepperRequest,
+    ) -> Result<SignatureResponse, PepperClientError> {
+        self.client.fetch_signature(request).await
+    }
+
+    async fn fetch_vuf_pub_key(&self) -> Result<PepperV0VufPubKey, PepperClientError> {
+        self.client.fetch_vuf_pub_key().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+    use aptos_types::transaction::authenticator::EphemeralPublicKey;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const TEST_JWT: &str = "eyJhbGciOiJSUzI1NiIsImtpZCI6ImUxYjkzYzY0MDE0NGI4NGJkMDViZjI5NmQ2NzI2MmI2YmM2MWE0ODciLCJ0eXAiOiJKV1QifQ.eyJpc3MiOiJodHRwczovL2FjY291bnRzLmdvb2dsZS5jb20iLCJhenAiOiI0MDc0MDg3MTgxOTIuYXBwcy5nb29nbGV1c2VyY29udGVudC5jb20iLCJhdWQiOiI0MDc0MDg3MTgxOTIuYXBwcy5nb29nbGV1c2VyY29udGVudC5jb20iLCJzdWIiOiIxMTE2Mjc3NzI0NjA3NTIzNDIzMTIiLCJhdF9oYXNoIjoiaG5OWHFJVTZ3dWFPYlVqR05lRVhGQSIsIm5vbmNlIjoiNzQyMDQxODMxNDYwMDk1MDM0MTU3NzQ0MzEzMzY0MTU4OTk0NzYwNTExMjc1MDEwNDIyNjY5MDY3NTc3OTY3NTIyNDAwNjA0OTI0NCIsIm5hbWUiOiJPbGl2ZXIgSGUiLCJnaXZlbl9uYW1lIjoiT2xpdmVyIiwiZmFtaWx5X25hbWUiOiJIZSIsImlhdCI6MTcxNDQ0MTc4MywiZXhwIjoxNzE0NDQ1MzgzfQ.iNeVzp4BTQj2I_WH6UaUOfUBV4Q_wUriV7jWkh1fUqTPSs30jMMSjEDZml8lQ_NUIpivnGvfEHt_rF9rlrsuRur9pTVKRRKhJUNf5avrAujvLzrz-bwdgKXtTY_nmYisNNNQwmFIVP004ICois4DHD7EmO8PI88CzSzdDbl9qAIoxOP3JRKRwU05wK5qkGz6FpYzTYiG50lQCybSzzUN5Lws49ANCAOZiROG5lmszOW41mAbFSd6MUX469uvyMA2ZZ5av9ArKricHJPutGtLoOSWpzKQ_mlCzofVs5tHoMhGgcOFKuhnEVdY4J7TdcV6pZv9Ih5F8MX3-Wz9Iz9O4w";
+
+    /// A mock pepper client that counts the pepper requests, and returns a different pepper
+    /// for each of them
+    #[derive(Default)]
+    struct MockPepperClient {
+        num_pepper_requests: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PepperClient for MockPepperClient {
+        async fn fetch_pepper(
+            &self,
+            request: &PepperRequest,
+        ) -> Result<PepperResponse, PepperClientError> {
+            let num_pepper_requests = self.num_pepper_requests.fetch_add(1, Ordering::SeqCst);
+            if request.jwt.is_empty() {
+                return Err(PepperClientError::BadRequest("Empty JWT".to_string()));
+            }
+            Ok(PepperResponse {
+                pepper: vec![num_pepper_requests as u8],
+                address: vec![],
+            })
+        }
+
+        async fn fetch_signature(
+            &self,
+            _request: &PepperRequest,
+        ) -> Result<SignatureResponse, PepperClientError> {
+            unimplemented!()
+        }
+
+        async fn fetch_vuf_pub_key(&self) -> Result<PepperV0VufPubKey, PepperClientError> {
+            unimplemented!()
+        }
+    }
+
+    /// Creates a pepper request for the given JWT and derivation path
+    fn create_request(jwt: &str, derivation_path: Option<&str>) -> PepperRequest {
+        let ephemeral_private_key = Ed25519PrivateKey::generate_for_testing();
+        PepperRequest {
+            jwt: jwt.to_string(),
+            epk: EphemeralPublicKey::ed25519(ephemeral_private_key.public_key()),
+            exp_date_secs: 0,
+            epk_blinder: vec![0; 31],
+            uid_key: None,
+            derivation_path: derivation_path.map(|path| path.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_peppers_are_cached_by_identity() {
+        let client = CachingPepperClient::new(MockPepperClient::default());
+
+        // Fetch the pepper twice for the same identity, and verify it's only requested once
+        let pepper = client
+            .fetch_pepper(&create_request(TEST_JWT, None))
+            .await
+            .unwrap();
+        let cached_pepper = client
+            .fetch_pepper(&create_request(TEST_JWT, None))
+            .await
+            .unwrap();
+        assert_eq!(cached_pepper.pepper, pepper.pepper);
+        assert_eq!(client.inner().num_pepper_requests.load(Ordering::SeqCst), 1);
+
+        // Fetch the pepper for a different derivation path, and verify it's requested
+        let derived_pepper = client
+            .fetch_pepper(&create_request(TEST_JWT, Some("m/44'/637'/0'/0'/1'")))
+            .await
+            .unwrap();
+        assert_ne!(derived_pepper.pepper, pepper.pepper);
+        assert_eq!(client.inner().num_pepper_requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let client = CachingPepperClient::new(MockPepperClient::default());
+
+        // Verify failed requests (without an identity) are always forwarded
+        for _ in 0..2 {
+            let error = client
+                .fetch_pepper(&create_request("", None))
+                .await
+                .unwrap_err();
+            assert!(matches!(error, PepperClientError::BadRequest(_)));
+        }
+        assert_eq!(client.inner().num_pepper_requests.load(Ordering::SeqCst), 2);
+    }
+}
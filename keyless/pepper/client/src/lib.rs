@@ -0,0 +1,55 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A client for the keyless pepper service, shared by the CLI, the SDK and other integrations
+//! instead of each of them sending the HTTP requests themselves.
+//!
+//! [`RestPepperClient`] talks to the pepper service over HTTP, retrying failed requests and
+//! failing over between multiple endpoints. [`CachingPepperClient`] wraps any [`PepperClient`]
+//! (e.g., a mock in tests) and caches the peppers by the identity in the JWT.
+
+use aptos_keyless_pepper_common::{
+    PepperRequest, PepperResponse, PepperV0VufPubKey, SignatureResponse,
+};
+use async_trait::async_trait;
+use thiserror::Error;
+
+mod caching_client;
+mod rest_client;
+
+pub use caching_client::CachingPepperClient;
+pub use rest_client::RestPepperClient;
+
+/// Errors returned by the pepper service clients
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+pub enum PepperClientError {
+    /// The pepper service rejected the request (e.g., the JWT is invalid or the ephemeral
+    /// key expired), so it shouldn't be retried
+    #[error("Bad pepper request: {0}")]
+    BadRequest(String),
+    /// None of the pepper service endpoints could serve the request
+    #[error("Pepper service unavailable: {0}")]
+    Unavailable(String),
+    /// The pepper service returned a response that can't be parsed
+    #[error("Invalid pepper service response: {0}")]
+    InvalidResponse(String),
+}
+
+/// The API of the pepper service (v0)
+#[async_trait]
+pub trait PepperClient: Send + Sync {
+    /// Fetches the pepper (and the derived account address) for the identity in the request
+    async fn fetch_pepper(
+        &self,
+        request: &PepperRequest,
+    ) -> Result<PepperResponse, PepperClientError>;
+
+    /// Fetches the VUF signature that the pepper is derived from
+    async fn fetch_signature(
+        &self,
+        request: &PepperRequest,
+    ) -> Result<SignatureResponse, PepperClientError>;
+
+    /// Fetches the VUF public key that the signatures can be verified with
+    async fn fetch_vuf_pub_key(&self) -> Result<PepperV0VufPubKey, PepperClientError>;
+}
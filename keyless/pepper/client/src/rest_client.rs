@@ -0,0 +1,233 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{PepperClient, PepperClientError};
+use aptos_keyless_pepper_common::{
+    BadPepperRequestError, PepperRequest, PepperResponse, PepperV0VufPubKey, SignatureResponse,
+};
+use async_trait::async_trait;
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+use url::Url;
+
+/// The default number of attempts on each endpoint, before failing over to the next one
+const DEFAULT_MAX_ATTEMPTS_PER_ENDPOINT: usize = 3;
+/// The default delay between two attempts on the same endpoint
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// The default timeout of each request
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A client for the pepper service over HTTP. Requests that fail (other than those rejected as
+/// bad requests) are retried, and then sent to the next endpoint.
+#[derive(Clone, Debug)]
+pub struct RestPepperClient {
+    client: reqwest::Client,
+    endpoints: Vec<Url>,
+    max_attempts_per_endpoint: usize,
+    retry_delay: Duration,
+}
+
+impl RestPepperClient {
+    /// Creates a client for the given pepper service endpoints, which are tried in order. Each
+    /// endpoint is the versioned base URL of a deployment, e.g.,
+    /// `https://api.mainnet.aptoslabs.com/keyless/pepper/v0`.
+    pub fn new(endpoints: Vec<Url>) -> Self {
+        // Relative paths are joined onto the last segment only with a trailing slash
+        let endpoints = endpoints
+            .into_iter()
+            .map(|mut endpoint| {
+                if !endpoint.path().ends_with('/') {
+                    endpoint.set_path(&format!("{}/", endpoint.path()));
+                }
+                endpoint
+            })
+            .collect();
+
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(DEFAULT_REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to build the pepper service HTTP client!"),
+            endpoints,
+            max_attempts_per_endpoint: DEFAULT_MAX_ATTEMPTS_PER_ENDPOINT,
+            retry_delay: DEFAULT_RETRY_DELAY,
+        }
+    }
+
+    /// Sets the number of attempts on each endpoint, before failing over to the next one
+    pub fn max_attempts_per_endpoint(mut self, max_attempts_per_endpoint: usize) -> Self {
+        self.max_attempts_per_endpoint = max_attempts_per_endpoint.max(1);
+        self
+    }
+
+    /// Sets the delay between two attempts on the same endpoint
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Sends the request built for the given path to each endpoint in turn (with retries),
+    /// until one of them serves it or rejects it as a bad request
+    async fn send<Response: DeserializeOwned>(
+        &self,
+        path: &str,
+        build_request: impl Fn(&reqwest::Client, Url) -> RequestBuilder,
+    ) -> Result<Response, PepperClientError> {
+        let mut last_error = PepperClientError::Unavailable(
+            "No pepper service endpoints are configured".to_string(),
+        );
+        for endpoint in &self.endpoints {
+            let url = endpoint.join(path).map_err(|error| {
+                PepperClientError::Unavailable(format!("Invalid endpoint {}: {}", endpoint, error))
+            })?;
+            for attempt in 0..self.max_attempts_per_endpoint {
+                if attempt > 0 {
+                    tokio::time::sleep(self.retry_delay).await;
+                }
+                match send_once(build_request(&self.client, url.clone())).await {
+                    Ok(response) => return Ok(response),
+                    // Retrying (anywhere) won't help if the request itself is bad
+                    Err(error @ PepperClientError::BadRequest(_)) => return Err(error),
+                    Err(error) => last_error = error,
+                }
+            }
+        }
+        Err(last_error)
+    }
+}
+
+/// Sends the request once, and parses the response
+async fn send_once<Response: DeserializeOwned>(
+    request: RequestBuilder,
+) -> Result<Response, PepperClientError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|error| PepperClientError::Unavailable(error.to_string()))?;
+
+    let status = response.status();
+    if status == StatusCode::BAD_REQUEST {
+        let body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<BadPepperRequestError>(&body)
+            .map(|error| error.message)
+            .unwrap_or(body);
+        return Err(PepperClientError::BadRequest(message));
+    }
+    if !status.is_success() {
+        return Err(PepperClientError::Unavailable(format!(
+            "{} returned {}",
+            response.url(),
+            status
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|error| PepperClientError::InvalidResponse(error.to_string()))
+}
+
+#[async_trait]
+impl PepperClient for RestPepperClient {
+    async fn fetch_pepper(
+        &self,
+        request: &PepperRequest,
+    ) -> Result<PepperResponse, PepperClientError> {
+        self.send("fetch", |client, url| client.post(url).json(request))
+            .await
+    }
+
+    async fn fetch_signature(
+        &self,
+        request: &PepperRequest,
+    ) -> Result<SignatureResponse, PepperClientError> {
+        self.send("signature", |client, url| client.post(url).json(request))
+            .await
+    }
+
+    async fn fetch_vuf_pub_key(&self) -> Result<PepperV0VufPubKey, PepperClientError> {
+        self.send("vuf-pub-key", |client, url| client.get(url))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::MockServer;
+
+    /// Returns a client for the given servers, which doesn't wait between retries
+    fn create_client(servers: &[&MockServer]) -> RestPepperClient {
+        let endpoints = servers
+            .iter()
+            .map(|server| Url::parse(&server.url("/v0")).unwrap())
+            .collect();
+        RestPepperClient::new(endpoints).retry_delay(Duration::from_millis(0))
+    }
+
+    #[tokio::test]
+    async fn test_failover_to_next_endpoint() {
+        // Create a failing and a healthy pepper service
+        let failing_server = MockServer::start();
+        let failing_mock = failing_server.mock(|when, then| {
+            when.method("GET").path("/v0/vuf-pub-key");
+            then.status(500);
+        });
+        let healthy_server = MockServer::start();
+        let healthy_mock = healthy_server.mock(|when, then| {
+            when.method("GET").path("/v0/vuf-pub-key");
+            then.status(200).json_body_obj(&PepperV0VufPubKey {
+                public_key: vec![1, 2, 3],
+            });
+        });
+
+        // Verify the request is retried on the failing service, and then served by the healthy one
+        let client = create_client(&[&failing_server, &healthy_server]);
+        let response = client.fetch_vuf_pub_key().await.unwrap();
+        assert_eq!(response.public_key, vec![1, 2, 3]);
+        assert_eq!(failing_mock.hits(), DEFAULT_MAX_ATTEMPTS_PER_ENDPOINT);
+        assert_eq!(healthy_mock.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_all_endpoints_unavailable() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method("GET").path("/v0/vuf-pub-key");
+            then.status(503);
+        });
+
+        let client = create_client(&[&server]).max_attempts_per_endpoint(2);
+        let error = client.fetch_vuf_pub_key().await.unwrap_err();
+        assert!(matches!(error, PepperClientError::Unavailable(_)));
+        assert_eq!(mock.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_bad_request_is_not_retried() {
+        // Create two pepper services that reject the request
+        let servers = [MockServer::start(), MockServer::start()];
+        let mocks: Vec<_> = servers
+            .iter()
+            .map(|server| {
+                server.mock(|when, then| {
+                    when.method("GET").path("/v0/vuf-pub-key");
+                    then.status(400).json_body_obj(&BadPepperRequestError {
+                        message: "epk expired".to_string(),
+                    });
+                })
+            })
+            .collect();
+
+        // Verify the error is returned without retrying or failing over
+        let client = create_client(&[&servers[0], &servers[1]]);
+        let error = client.fetch_vuf_pub_key().await.unwrap_err();
+        assert_eq!(
+            error,
+            PepperClientError::BadRequest("epk expired".to_string())
+        );
+        assert_eq!(mocks[0].hits(), 1);
+        assert_eq!(mocks[1].hits(), 0);
+    }
+}
@@ -94,7 +94,7 @@ pub struct PepperRequest {
 }
 
 /// The response to `PepperRequest`, which contains either the pepper or a processing error.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PepperResponse {
     #[serde(
         serialize_with = "serialize_bytes_to_hex",
@@ -109,7 +109,7 @@ pub struct PepperResponse {
 }
 
 /// The response to /signature, which contains the VUF signature.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SignatureResponse {
     #[serde(
         serialize_with = "serialize_bytes_to_hex",
@@ -120,7 +120,7 @@ pub struct SignatureResponse {
 
 /// The response to `/v0/vuf-pub-key`.
 /// NOTE that in pepper v0, VUF is fixed to be `BLS12381_G1_BLS`.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PepperV0VufPubKey {
     #[serde(
         serialize_with = "serialize_bytes_to_hex",
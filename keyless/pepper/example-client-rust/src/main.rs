@@ -2,17 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
+use aptos_keyless_pepper_client::{PepperClient, RestPepperClient};
 use aptos_keyless_pepper_common::{
     jwt,
     vuf::{self, VUF},
     PepperInput, PepperRequest, PepperResponse, PepperV0VufPubKey, SignatureResponse,
 };
+use aptos_rest_client::Client;
 use aptos_types::{
+    account_config::CORE_CODE_ADDRESS,
     keyless::{Configuration, OpenIdSig},
     transaction::authenticator::EphemeralPublicKey,
 };
 use ark_serialize::CanonicalDeserialize;
-use reqwest::StatusCode;
+use reqwest::Url;
 use std::{fs, io::stdin};
 
 const TEST_JWT: &str = "eyJhbGciOiJSUzI1NiIsImtpZCI6ImUxYjkzYzY0MDE0NGI4NGJkMDViZjI5NmQ2NzI2MmI2YmM2MWE0ODciLCJ0eXAiOiJKV1QifQ.eyJpc3MiOiJodHRwczovL2FjY291bnRzLmdvb2dsZS5jb20iLCJhenAiOiI0MDc0MDg3MTgxOTIuYXBwcy5nb29nbGV1c2VyY29udGVudC5jb20iLCJhdWQiOiI0MDc0MDg3MTgxOTIuYXBwcy5nb29nbGV1c2VyY29udGVudC5jb20iLCJzdWIiOiIxMTE2Mjc3NzI0NjA3NTIzNDIzMTIiLCJhdF9oYXNoIjoiaG5OWHFJVTZ3dWFPYlVqR05lRVhGQSIsIm5vbmNlIjoiNzQyMDQxODMxNDYwMDk1MDM0MTU3NzQ0MzEzMzY0MTU4OTk0NzYwNTExMjc1MDEwNDIyNjY5MDY3NTc3OTY3NTIyNDAwNjA0OTI0NCIsIm5hbWUiOiJPbGl2ZXIgSGUiLCJnaXZlbl9uYW1lIjoiT2xpdmVyIiwiZmFtaWx5X25hbWUiOiJIZSIsImlhdCI6MTcxNDQ0MTc4MywiZXhwIjoxNzE0NDQ1MzgzfQ.iNeVzp4BTQj2I_WH6UaUOfUBV4Q_wUriV7jWkh1fUqTPSs30jMMSjEDZml8lQ_NUIpivnGvfEHt_rF9rlrsuRur9pTVKRRKhJUNf5avrAujvLzrz-bwdgKXtTY_nmYisNNNQwmFIVP004ICois4DHD7EmO8PI88CzSzdDbl9qAIoxOP3JRKRwU05wK5qkGz6FpYzTYiG50lQCybSzzUN5Lws49ANCAOZiROG5lmszOW41mAbFSd6MUX469uvyMA2ZZ5av9ArKricHJPutGtLoOSWpzKQ_mlCzofVs5tHoMhGgcOFKuhnEVdY4J7TdcV6pZv9Ih5F8MX3-Wz9Iz9O4w";
@@ -47,6 +50,37 @@ fn get_pepper_service_url() -> String {
     }
 }
 
+/// The nonce commits to the keyless configuration of the network the account is used on.
+async fn get_keyless_configuration() -> Configuration {
+    let url = match std::env::var("OIDB_PEPPER_TEST_CLIENT__FULLNODE_URL") {
+        Ok(val) => {
+            println!();
+            println!(
+                "Fullnode url found from envvar OIDB_PEPPER_TEST_CLIENT__FULLNODE_URL: {}",
+                val
+            );
+            val
+        },
+        Err(_) => {
+            println!();
+            println!("Fullnode url not found from envvar OIDB_PEPPER_TEST_CLIENT__FULLNODE_URL.");
+            println!("Enter the URL of a fullnode of the targeted network (default: use the devnet keyless configuration):");
+            read_line_from_stdin().trim().to_string()
+        },
+    };
+    if url.is_empty() {
+        return Configuration::new_for_devnet();
+    }
+    Client::new(Url::parse(&url).unwrap())
+        .get_account_resource_bcs::<Configuration>(
+            CORE_CODE_ADDRESS,
+            "0x1::keyless_account::Configuration",
+        )
+        .await
+        .unwrap()
+        .into_inner()
+}
+
 fn get_jwt_or_path() -> String {
     println!();
     println!(
@@ -66,24 +100,14 @@ async fn main() {
     println!();
     println!("Starting an interaction with aptos-oidb-pepper-service.");
     let url = get_pepper_service_url();
-    println!();
-    let vuf_pub_key_url = format!("{url}/v0/vuf-pub-key");
-    let fetch_url = format!("{url}/v0/fetch");
-    let sig_url = format!("{url}/v0/signature");
+    let keyless_configuration = get_keyless_configuration().await;
+    let client = RestPepperClient::new(vec![Url::parse(&format!("{url}/v0")).unwrap()]);
     println!();
     println!(
-        "Action 1: fetch its verification key with a GET request to {}",
-        vuf_pub_key_url
+        "Action 1: fetch its verification key with a GET request to {}/v0/vuf-pub-key",
+        url
     );
-    let client = reqwest::Client::new();
-    let response = client
-        .get(vuf_pub_key_url)
-        .send()
-        .await
-        .unwrap()
-        .json::<PepperV0VufPubKey>()
-        .await
-        .unwrap();
+    let response = client.fetch_vuf_pub_key().await.unwrap();
     println!();
     println!(
         "response_json={}",
@@ -119,7 +143,7 @@ async fn main() {
         blinder.as_slice(),
         epk_expiry_time_secs,
         &epk,
-        &Configuration::new_for_devnet(),
+        &keyless_configuration,
     )
     .unwrap();
     println!("nonce_string={}", nonce_str);
@@ -145,18 +169,11 @@ async fn main() {
     };
     println!();
     println!(
-        "Request pepper with a POST to {} and the body being {}",
-        fetch_url,
+        "Request pepper with a POST to {}/v0/fetch and the body being {}",
+        url,
         serde_json::to_string_pretty(&pepper_request).unwrap()
     );
-    let pepper_raw_response = client
-        .post(fetch_url)
-        .json(&pepper_request)
-        .send()
-        .await
-        .unwrap();
-    assert_eq!(StatusCode::OK, pepper_raw_response.status());
-    let pepper_response = pepper_raw_response.json::<PepperResponse>().await.unwrap();
+    let pepper_response = client.fetch_pepper(&pepper_request).await.unwrap();
     println!();
     println!(
         "pepper_service_response={}",
@@ -164,17 +181,7 @@ async fn main() {
     );
     let PepperResponse { pepper, address } = pepper_response;
 
-    let signature_raw_response = client
-        .post(sig_url)
-        .json(&pepper_request)
-        .send()
-        .await
-        .unwrap();
-    assert_eq!(StatusCode::OK, signature_raw_response.status());
-    let signature_response = signature_raw_response
-        .json::<SignatureResponse>()
-        .await
-        .unwrap();
+    let signature_response = client.fetch_signature(&pepper_request).await.unwrap();
     println!(
         "signature_response={}",
         serde_json::to_string_pretty(&signature_response).unwrap()
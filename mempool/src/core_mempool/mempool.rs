@@ -7,14 +7,15 @@
 use crate::{
     core_mempool::{
         index::TxnPointer,
-        transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        transaction::{AccountTransactionInfo, InsertionInfo, MempoolTransaction, TimelineState},
         transaction_store::TransactionStore,
+        ttl_policy::PayloadTtlPolicy,
     },
     counters,
     logging::{LogEntry, LogSchema, TxnsLog},
-    shared_mempool::types::MultiBucketTimelineIndexIds,
+    shared_mempool::types::{MempoolCapacities, MultiBucketTimelineIndexIds},
 };
-use aptos_config::config::NodeConfig;
+use aptos_config::config::{NodeConfig, PriorityBandConfig};
 use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
@@ -25,7 +26,7 @@ use aptos_types::{
     vm_status::DiscardedVMStatus,
 };
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     sync::atomic::Ordering,
     time::{Duration, Instant, SystemTime},
 };
@@ -35,6 +36,12 @@ pub struct Mempool {
     transactions: TransactionStore,
 
     pub system_transaction_timeout: Duration,
+
+    // Overrides of the system transaction timeout for classes of transactions.
+    payload_ttl_policy: PayloadTtlPolicy,
+
+    // Gas price bands with a quota on their share of the batches pulled by consensus.
+    priority_bands: Vec<PriorityBandConfig>,
 }
 
 impl Mempool {
@@ -44,6 +51,8 @@ impl Mempool {
             system_transaction_timeout: Duration::from_secs(
                 config.mempool.system_transaction_timeout_secs,
             ),
+            payload_ttl_policy: PayloadTtlPolicy::new(&config.mempool),
+            priority_bands: config.mempool.priority_bands.clone(),
         }
     }
 
@@ -248,8 +257,11 @@ impl Mempool {
         }
 
         let now = SystemTime::now();
-        let expiration_time =
-            aptos_infallible::duration_since_epoch_at(&now) + self.system_transaction_timeout;
+        let expiration_time = self.payload_ttl_policy.system_expiration_time(
+            &txn,
+            aptos_infallible::duration_since_epoch_at(&now),
+            self.system_transaction_timeout,
+        );
 
         let txn_info = MempoolTransaction::new(
             txn,
@@ -303,6 +315,9 @@ impl Mempool {
     ///                          exclude_transactions. Should only be true for Quorum Store.
     /// `exclude_transactions` - transactions that were sent to Consensus but were not committed yet
     ///  mempool should filter out such transactions.
+    ///
+    /// Transactions of a priority band are left out once the band's quota of the batch is
+    /// reached, along with the later transactions of their accounts.
     #[allow(clippy::explicit_counter_loop)]
     pub(crate) fn get_batch(
         &self,
@@ -318,13 +333,14 @@ impl Mempool {
         let gas_end_time = start_time.elapsed();
 
         let mut result = vec![];
+        let mut quotas = PriorityBandQuotas::new(&self.priority_bands, max_txns);
         // Helper DS. Helps to mitigate scenarios where account submits several transactions
         // with increasing gas price (e.g. user submits transactions with sequence number 1, 2
         // and gas_price 1, 10 respectively)
         // Later txn has higher gas price and will be observed first in priority index iterator,
         // but can't be executed before first txn. Once observed, such txn will be saved in
         // `skipped` DS and rechecked once it's ancestor becomes available
-        let mut skipped = HashSet::new();
+        let mut skipped = HashMap::new();
         let mut total_bytes = 0;
         let mut txn_walked = 0usize;
         // iterate over the queue of transactions based on gas price
@@ -343,6 +359,9 @@ impl Mempool {
             // include transaction if it's "next" for given account or
             // we've already sent its ancestor to Consensus.
             if txn_in_sequence || account_sequence_number == Some(&tx_seq) {
+                if !quotas.try_take(txn.gas_ranking_score) {
+                    continue;
+                }
                 inserted.insert((txn.address, tx_seq));
                 result.push((txn.address, tx_seq));
                 if (result.len() as u64) == max_txns {
@@ -352,7 +371,10 @@ impl Mempool {
                 // check if we can now include some transactions
                 // that were skipped before for given account
                 let mut skipped_txn = (txn.address, tx_seq + 1);
-                while skipped.contains(&skipped_txn) {
+                while let Some(&ranking_score) = skipped.get(&skipped_txn) {
+                    if !quotas.try_take(ranking_score) {
+                        break;
+                    }
                     inserted.insert(skipped_txn);
                     result.push(skipped_txn);
                     if (result.len() as u64) == max_txns {
@@ -361,9 +383,10 @@ impl Mempool {
                     skipped_txn = (skipped_txn.0, skipped_txn.1 + 1);
                 }
             } else {
-                skipped.insert((txn.address, tx_seq));
+                skipped.insert((txn.address, tx_seq), txn.gas_ranking_score);
             }
         }
+        quotas.observe();
         let result_size = result.len();
         let result_end_time = start_time.elapsed();
         let result_time = result_end_time.saturating_sub(gas_end_time);
@@ -472,6 +495,28 @@ impl Mempool {
         self.transactions.timeline_range(start_end_pairs)
     }
 
+    /// Returns the transactions of the account, including the parked ones.
+    pub(crate) fn get_account_transactions(
+        &self,
+        address: &AccountAddress,
+    ) -> Vec<AccountTransactionInfo> {
+        self.transactions.get_account_transactions(address)
+    }
+
+    /// Evicts a transaction on request of an operator, e.g. one which blocks the account.
+    pub(crate) fn evict_transaction(
+        &mut self,
+        sender: &AccountAddress,
+        sequence_number: u64,
+    ) -> bool {
+        self.transactions.evict_transaction(sender, sequence_number)
+    }
+
+    /// Updates the capacities, e.g. when the node config is reloaded
+    pub(crate) fn update_capacities(&mut self, capacities: MempoolCapacities) {
+        self.transactions.update_capacities(capacities);
+    }
+
     pub fn gen_snapshot(&self) -> TxnsLog {
         self.transactions.gen_snapshot()
     }
@@ -486,3 +531,60 @@ impl Mempool {
         &self.transactions
     }
 }
+
+/// Counts the transactions of each priority band in a batch, to enforce the bands' quotas.
+struct PriorityBandQuotas<'a> {
+    bands: &'a [PriorityBandConfig],
+    max_txns: Vec<u64>,
+    num_txns: Vec<u64>,
+    num_throttled: Vec<u64>,
+}
+
+impl<'a> PriorityBandQuotas<'a> {
+    fn new(bands: &'a [PriorityBandConfig], max_txns: u64) -> Self {
+        Self {
+            bands,
+            max_txns: bands
+                .iter()
+                .map(|band| {
+                    let quota = max_txns as u128 * band.max_batch_pct as u128 / 100;
+                    // Every band gets at least one transaction, so that small batches don't
+                    // exclude it
+                    (quota as u64).max(1)
+                })
+                .collect(),
+            num_txns: vec![0; bands.len()],
+            num_throttled: vec![0; bands.len()],
+        }
+    }
+
+    /// Counts a transaction with the ranking score towards its band, unless the band's quota is
+    /// reached. Returns whether the transaction can be added to the batch.
+    fn try_take(&mut self, ranking_score: u64) -> bool {
+        let band = self
+            .bands
+            .iter()
+            .rposition(|band| band.min_gas_unit_price <= ranking_score);
+        match band {
+            Some(band) if self.num_txns[band] >= self.max_txns[band] => {
+                self.num_throttled[band] += 1;
+                false
+            },
+            Some(band) => {
+                self.num_txns[band] += 1;
+                true
+            },
+            None => true,
+        }
+    }
+
+    fn observe(&self) {
+        for (idx, band) in self.bands.iter().enumerate() {
+            counters::core_mempool_priority_band(
+                band.min_gas_unit_price,
+                self.num_txns[idx],
+                self.num_throttled[idx],
+            );
+        }
+    }
+}
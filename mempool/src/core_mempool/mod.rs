@@ -6,10 +6,12 @@ mod index;
 mod mempool;
 mod transaction;
 mod transaction_store;
+mod ttl_policy;
 
 #[cfg(test)]
 pub use self::transaction::{MempoolTransaction, SubmittedBy};
 pub use self::{
-    mempool::Mempool as CoreMempool, transaction::TimelineState,
+    mempool::Mempool as CoreMempool,
+    transaction::{AccountTransactionInfo, TimelineState},
     transaction_store::TXN_INDEX_ESTIMATED_BYTES,
 };
@@ -80,6 +80,24 @@ pub enum TimelineState {
     NonQualified,
 }
 
+/// A transaction of an account in mempool, and where it is in the indexes, for debugging
+/// accounts whose transactions are stuck.
+#[derive(Clone, Debug, Serialize)]
+pub struct AccountTransactionInfo {
+    pub sequence_number: u64,
+    pub hash: HashValue,
+    pub gas_unit_price: u64,
+    pub expiration_timestamp_secs: u64,
+    pub insertion_time: SystemTime,
+    /// Whether the transaction can be pulled into the next block
+    pub ready: bool,
+    /// Whether the transaction is in the parking lot, waiting for a sequence number gap to be
+    /// filled
+    pub parked: bool,
+    /// Whether, and at which position, the transaction is broadcast to peers
+    pub timeline_state: TimelineState,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct SequenceInfo {
     pub transaction_sequence_number: u64,
@@ -9,12 +9,13 @@ use crate::{
             PriorityQueueIter, TTLIndex,
         },
         mempool::Mempool,
-        transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        transaction::{AccountTransactionInfo, InsertionInfo, MempoolTransaction, TimelineState},
+        ttl_policy::{keyless_key_expiration, PayloadTtlPolicy},
     },
     counters,
     counters::{BROADCAST_BATCHED_LABEL, BROADCAST_READY_LABEL, CONSENSUS_READY_LABEL},
     logging::{LogEntry, LogEvent, LogSchema, TxnsLog},
-    shared_mempool::types::MultiBucketTimelineIndexIds,
+    shared_mempool::types::{MempoolCapacities, MultiBucketTimelineIndexIds},
 };
 use aptos_config::config::MempoolConfig;
 use aptos_crypto::HashValue;
@@ -76,6 +77,12 @@ pub struct TransactionStore {
     // eager expiration
     eager_expire_threshold: Option<Duration>,
     eager_expire_time: Duration,
+
+    // replace-by-fee
+    replace_by_fee_min_gas_price_bump_pct: Option<u64>,
+
+    // system TTL overrides (to classify garbage-collected transactions)
+    payload_ttl_policy: PayloadTtlPolicy,
 }
 
 impl TransactionStore {
@@ -107,6 +114,12 @@ impl TransactionStore {
             // eager expiration
             eager_expire_threshold: config.eager_expire_threshold_ms.map(Duration::from_millis),
             eager_expire_time: Duration::from_millis(config.eager_expire_time_ms),
+
+            // replace-by-fee
+            replace_by_fee_min_gas_price_bump_pct: config.replace_by_fee_min_gas_price_bump_pct,
+
+            // system TTL overrides
+            payload_ttl_policy: PayloadTtlPolicy::new(config),
         }
     }
 
@@ -191,12 +204,29 @@ impl TransactionStore {
 
         // If the transaction is already in Mempool, we only allow the user to
         // increase the gas unit price to speed up a transaction, but not the max gas.
+        // If replace-by-fee is enabled, the transaction may also be replaced by a different one
+        // with a sufficiently higher gas unit price, e.g. to unblock an account.
         //
         // Transactions with all the same inputs (but possibly signed differently) are idempotent
         // since the raw transaction is the same
         if let Some(txns) = self.transactions.get_mut(&address) {
             if let Some(current_version) = txns.get_mut(&txn_seq_num) {
-                if current_version.txn.payload() != txn.txn.payload() {
+                let is_different_txn = current_version.txn.payload() != txn.txn.payload()
+                    || current_version.txn.expiration_timestamp_secs()
+                        != txn.txn.expiration_timestamp_secs()
+                    || current_version.txn.max_gas_amount() != txn.txn.max_gas_amount();
+                if is_different_txn
+                    && Self::is_replace_by_fee(
+                        self.replace_by_fee_min_gas_price_bump_pct,
+                        current_version.get_gas_price(),
+                        txn.get_gas_price(),
+                    )
+                {
+                    if let Some(txn) = txns.remove(&txn_seq_num) {
+                        self.index_remove(&txn);
+                    };
+                    counters::CORE_MEMPOOL_REPLACED_BY_FEE_TXNS.inc();
+                } else if current_version.txn.payload() != txn.txn.payload() {
                     return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
                         "Transaction already in mempool with a different payload".to_string(),
                     );
@@ -269,6 +299,22 @@ impl TransactionStore {
         MempoolStatus::new(MempoolStatusCode::Accepted)
     }
 
+    /// Whether the new gas unit price exceeds the current one by at least the minimum bump for
+    /// replace-by-fee.
+    fn is_replace_by_fee(
+        min_gas_price_bump_pct: Option<u64>,
+        current_gas_price: u64,
+        new_gas_price: u64,
+    ) -> bool {
+        match min_gas_price_bump_pct {
+            Some(bump_pct) => {
+                let min_gas_price = current_gas_price as u128 * (100 + bump_pct as u128) / 100;
+                new_gas_price > current_gas_price && new_gas_price as u128 >= min_gas_price
+            },
+            None => false,
+        }
+    }
+
     fn track_indices(&self) {
         counters::core_mempool_index_size(
             counters::SYSTEM_TTL_INDEX_LABEL,
@@ -327,6 +373,14 @@ impl TransactionStore {
         self.is_full()
     }
 
+    /// Updates the capacities. Transactions are not evicted if mempool is over a lowered
+    /// capacity, but new ones are rejected until it drains below it.
+    pub(crate) fn update_capacities(&mut self, capacities: MempoolCapacities) {
+        self.capacity = capacities.capacity;
+        self.capacity_bytes = capacities.capacity_bytes;
+        self.capacity_per_user = capacities.capacity_per_user;
+    }
+
     fn is_full(&self) -> bool {
         self.system_ttl_index.size() >= self.capacity || self.size_bytes >= self.capacity_bytes
     }
@@ -530,6 +584,71 @@ impl TransactionStore {
         }
     }
 
+    /// Returns the transactions of the account in sequence number order, with their state.
+    pub(crate) fn get_account_transactions(
+        &self,
+        address: &AccountAddress,
+    ) -> Vec<AccountTransactionInfo> {
+        self.transactions
+            .get(address)
+            .map(|txns| {
+                txns.values()
+                    .map(|txn| AccountTransactionInfo {
+                        sequence_number: txn.sequence_info.transaction_sequence_number,
+                        hash: txn.get_committed_hash(),
+                        gas_unit_price: txn.get_gas_price(),
+                        expiration_timestamp_secs: txn.txn.expiration_timestamp_secs(),
+                        insertion_time: txn.insertion_info.insertion_time,
+                        ready: self.priority_index.contains(txn),
+                        parked: self.parking_lot_index.contains(
+                            address,
+                            txn.sequence_info.transaction_sequence_number,
+                            txn.get_committed_hash(),
+                        ),
+                        timeline_state: txn.timeline_state,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Evicts the transaction of the account with the given sequence number, regardless of its
+    /// state. The later transactions of the account can't be included in a block until the gap
+    /// is filled, so they are parked. Returns whether the transaction was in mempool.
+    pub(crate) fn evict_transaction(
+        &mut self,
+        address: &AccountAddress,
+        sequence_number: u64,
+    ) -> bool {
+        let txn_to_remove = match self
+            .transactions
+            .get_mut(address)
+            .and_then(|txns| txns.remove(&sequence_number))
+        {
+            Some(txn) => txn,
+            None => return false,
+        };
+
+        if let Some(txns) = self.transactions.get_mut(address) {
+            // mark all following txns as non-ready, i.e. park them
+            for (_, t) in txns.range_mut((Bound::Excluded(sequence_number), Bound::Unbounded)) {
+                self.parking_lot_index.insert(t);
+                self.priority_index.remove(t);
+                self.timeline_index.remove(t);
+                if let TimelineState::Ready(_) = t.timeline_state {
+                    t.timeline_state = TimelineState::NotReady;
+                }
+            }
+        }
+        self.index_remove(&txn_to_remove);
+
+        info!(
+            LogSchema::new(LogEntry::EvictTxn).txns(TxnsLog::new_txn(*address, sequence_number)),
+            "txn evicted"
+        );
+        true
+    }
+
     /// Removes transaction from all indexes. Only call after removing from main transactions DS.
     fn index_remove(&mut self, txn: &MempoolTransaction) {
         counters::CORE_MEMPOOL_REMOVED_TXNS.inc();
@@ -717,6 +836,19 @@ impl TransactionStore {
                     let account = txn.get_sender();
                     let txn_sequence_number = txn.sequence_info.transaction_sequence_number;
                     gc_txns_log.add_with_status(account, txn_sequence_number, status);
+                    let gc_reason = if by_system_ttl
+                        && keyless_key_expiration(&txn.txn).map_or(false, |exp| exp <= now)
+                    {
+                        counters::GC_KEYLESS_KEY_EXP_LABEL
+                    } else {
+                        metric_label
+                    };
+                    counters::CORE_MEMPOOL_GC_TXN_COUNT
+                        .with_label_values(&[
+                            gc_reason,
+                            self.payload_ttl_policy.payload_class(&txn.txn).get_label(),
+                        ])
+                        .inc();
                     if let Ok(time_delta) =
                         SystemTime::now().duration_since(txn.insertion_info.insertion_time)
                     {
@@ -0,0 +1,117 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::config::MempoolConfig;
+use aptos_types::{
+    keyless,
+    transaction::{SignedTransaction, TransactionPayload},
+};
+use std::time::Duration;
+
+/// The classes of transactions that can have their own system TTL (see
+/// `PayloadTtlPolicyConfig`). A transaction belongs to the first class that
+/// applies to it, in the order below.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum PayloadClass {
+    Keyless,
+    Large,
+    Script,
+    Other,
+}
+
+impl PayloadClass {
+    pub(crate) fn get_label(&self) -> &'static str {
+        match self {
+            PayloadClass::Keyless => "keyless",
+            PayloadClass::Large => "large",
+            PayloadClass::Script => "script",
+            PayloadClass::Other => "other",
+        }
+    }
+}
+
+/// The policy for how long transactions can stay in mempool, regardless of their
+/// (client-specified) expiration time, depending on the class of the transaction.
+#[derive(Clone, Debug)]
+pub(crate) struct PayloadTtlPolicy {
+    keyless_transaction_timeout: Option<Duration>,
+    script_transaction_timeout: Option<Duration>,
+    large_transaction_timeout: Option<Duration>,
+    large_transaction_min_bytes: usize,
+}
+
+impl PayloadTtlPolicy {
+    pub(crate) fn new(config: &MempoolConfig) -> Self {
+        let policy_config = &config.payload_ttl_policy;
+        Self {
+            keyless_transaction_timeout: policy_config
+                .keyless_transaction_timeout_secs
+                .map(Duration::from_secs),
+            script_transaction_timeout: policy_config
+                .script_transaction_timeout_secs
+                .map(Duration::from_secs),
+            large_transaction_timeout: policy_config
+                .large_transaction_timeout_secs
+                .map(Duration::from_secs),
+            large_transaction_min_bytes: policy_config.large_transaction_min_bytes as usize,
+        }
+    }
+
+    /// Returns the class of the transaction (used for metrics)
+    pub(crate) fn payload_class(&self, txn: &SignedTransaction) -> PayloadClass {
+        if keyless_key_expiration(txn).is_some() {
+            PayloadClass::Keyless
+        } else if self.is_large(txn) {
+            PayloadClass::Large
+        } else if matches!(txn.payload(), TransactionPayload::Script(_)) {
+            PayloadClass::Script
+        } else {
+            PayloadClass::Other
+        }
+    }
+
+    /// Returns the time at which the transaction (inserted at `insertion_time`) is
+    /// garbage collected by the system TTL. If several classes apply to the transaction,
+    /// the shortest of their timeouts is used, and `default_timeout` if none is set.
+    /// Keyless transactions are also garbage collected when their ephemeral key expires,
+    /// as they can't be executed after that.
+    pub(crate) fn system_expiration_time(
+        &self,
+        txn: &SignedTransaction,
+        insertion_time: Duration,
+        default_timeout: Duration,
+    ) -> Duration {
+        let key_expiration = keyless_key_expiration(txn);
+        let timeout = [
+            key_expiration.and(self.keyless_transaction_timeout),
+            self.script_transaction_timeout
+                .filter(|_| matches!(txn.payload(), TransactionPayload::Script(_))),
+            self.large_transaction_timeout
+                .filter(|_| self.is_large(txn)),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(default_timeout);
+
+        let expiration_time = insertion_time.saturating_add(timeout);
+        match key_expiration {
+            Some(key_expiration) => expiration_time.min(key_expiration),
+            None => expiration_time,
+        }
+    }
+
+    fn is_large(&self, txn: &SignedTransaction) -> bool {
+        txn.raw_txn_bytes_len() >= self.large_transaction_min_bytes
+    }
+}
+
+/// Returns the time at which the (first to expire) ephemeral key of a keyless
+/// transaction expires, or None if the transaction is not a keyless one.
+pub(crate) fn keyless_key_expiration(txn: &SignedTransaction) -> Option<Duration> {
+    keyless::get_authenticators(txn)
+        .ok()?
+        .iter()
+        .map(|(_, signature)| Duration::from_secs(signature.exp_date_secs))
+        .min()
+}
@@ -38,6 +38,7 @@ pub const NON_PARKED_COMMIT_ACCEPTED_LABEL: &str = "non_park_commit_accepted";
 // Core mempool GC type labels
 pub const GC_SYSTEM_TTL_LABEL: &str = "system_ttl";
 pub const GC_CLIENT_EXP_LABEL: &str = "client_expiration";
+pub const GC_KEYLESS_KEY_EXP_LABEL: &str = "keyless_key_expiration";
 
 // Core mempool GC txn status label
 pub const GC_ACTIVE_TXN_LABEL: &str = "active";
@@ -66,6 +67,7 @@ pub const SUCCESS_LABEL: &str = "success";
 // Bounded executor task labels
 pub const CLIENT_EVENT_LABEL: &str = "client_event";
 pub const CLIENT_EVENT_GET_TXN_LABEL: &str = "client_event_get_txn";
+pub const CLIENT_EVENT_ADMIN_LABEL: &str = "client_event_admin";
 pub const RECONFIG_EVENT_LABEL: &str = "reconfig";
 pub const PEER_BROADCAST_EVENT_LABEL: &str = "peer_broadcast";
 
@@ -185,6 +187,16 @@ pub static CORE_MEMPOOL_GAS_UPGRADED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter tracking number of txns replaced by a different txn with the same sequence number and
+/// a sufficiently higher gas price
+pub static CORE_MEMPOOL_REPLACED_BY_FEE_TXNS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_core_mempool_replaced_by_fee_txns_count",
+        "Number of txns replaced by a different txn with the same sequence number and a higher gas price"
+    )
+    .unwrap()
+});
+
 pub fn core_mempool_txn_commit_latency(
     stage: &'static str,
     submitted_by: &'static str,
@@ -240,6 +252,38 @@ static CORE_MEMPOOL_TXN_RANKING_SCORE: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(histogram_opts, &["stage", "status"]).unwrap()
 });
 
+/// Number of txns of each priority band in the batches pulled by consensus
+static CORE_MEMPOOL_PRIORITY_BAND_TXNS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_core_mempool_priority_band_txns",
+        "Number of txns of each priority band in a batch pulled by consensus",
+        &["band"],
+        TRANSACTION_COUNT_BUCKETS.clone()
+    )
+    .unwrap()
+});
+
+/// Counter of txns left out of batches pulled by consensus, as their priority band's quota was
+/// reached
+static CORE_MEMPOOL_PRIORITY_BAND_THROTTLED_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_core_mempool_priority_band_throttled_txns",
+        "Number of txns left out of batches pulled by consensus as their priority band's quota was reached",
+        &["band"]
+    )
+    .unwrap()
+});
+
+pub fn core_mempool_priority_band(min_gas_unit_price: u64, num_txns: u64, num_throttled: u64) {
+    let band = min_gas_unit_price.to_string();
+    CORE_MEMPOOL_PRIORITY_BAND_TXNS
+        .with_label_values(&[&band])
+        .observe(num_txns as f64);
+    CORE_MEMPOOL_PRIORITY_BAND_THROTTLED_TXNS
+        .with_label_values(&[&band])
+        .inc_by(num_throttled);
+}
+
 /// Counter for number of periodic garbage-collection (=GC) events that happen, regardless of
 /// how many txns were actually cleaned up in this GC event
 pub static CORE_MEMPOOL_GC_EVENT_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
@@ -259,6 +303,17 @@ pub static CORE_MEMPOOL_GC_EAGER_EXPIRE_EVENT_COUNT: Lazy<IntCounter> = Lazy::ne
         .unwrap()
 });
 
+/// Counter for number of transactions garbage-collected from core mempool, by the reason they
+/// were garbage-collected for and their payload class
+pub static CORE_MEMPOOL_GC_TXN_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_core_mempool_gc_txn_count",
+        "Number of txns garbage-collected from core mempool, by reason and payload class",
+        &["reason", "payload_class"]
+    )
+    .unwrap()
+});
+
 /// Counter tracking time for how long a transaction stayed in core-mempool before being garbage-collected
 pub static CORE_MEMPOOL_GC_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
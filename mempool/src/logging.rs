@@ -169,6 +169,9 @@ pub enum LogEntry {
     GCRemoveTxns,
     CleanCommittedTxn,
     CleanRejectedTxn,
+    EvictTxn,
+    UpdateCapacities,
+    GetAccountTxns,
     ProcessReadyTxns,
     DBError,
     UnexpectedNetworkMsg,
@@ -194,6 +194,61 @@ async fn handle_client_request<NetworkClient, TransactionValidator>(
                 ))
                 .await;
         },
+        MempoolClientRequest::GetAccountTransactions(address, callback) => {
+            let _timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_ADMIN_LABEL,
+                counters::SPAWN_LABEL,
+            );
+            let task_start_timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_ADMIN_LABEL,
+                counters::START_LABEL,
+            );
+            bounded_executor
+                .spawn(tasks::process_client_get_account_transactions(
+                    smp.clone(),
+                    address,
+                    callback,
+                    task_start_timer,
+                ))
+                .await;
+        },
+        MempoolClientRequest::EvictTransaction(address, sequence_number, callback) => {
+            let _timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_ADMIN_LABEL,
+                counters::SPAWN_LABEL,
+            );
+            let task_start_timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_ADMIN_LABEL,
+                counters::START_LABEL,
+            );
+            bounded_executor
+                .spawn(tasks::process_client_evict_transaction(
+                    smp.clone(),
+                    address,
+                    sequence_number,
+                    callback,
+                    task_start_timer,
+                ))
+                .await;
+        },
+        MempoolClientRequest::UpdateCapacities(capacities, callback) => {
+            let _timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_ADMIN_LABEL,
+                counters::SPAWN_LABEL,
+            );
+            let task_start_timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_ADMIN_LABEL,
+                counters::START_LABEL,
+            );
+            bounded_executor
+                .spawn(tasks::process_client_update_capacities(
+                    smp.clone(),
+                    capacities,
+                    callback,
+                    task_start_timer,
+                ))
+                .await;
+        },
     }
 }
 
@@ -331,7 +386,7 @@ async fn handle_network_event<NetworkClient, TransactionValidator>(
                 },
             }
         },
-        Event::RpcRequest(peer_id, _msg, _, _res_tx) => {
+        Event::RpcRequest(peer_id, _msg, _, _) | Event::RpcStreamRequest(peer_id, _msg, _, _) => {
             counters::unexpected_msg_count_inc(&network_id);
             sample!(
                 SampleRate::Duration(Duration::from_secs(60)),
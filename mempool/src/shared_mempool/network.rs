@@ -24,7 +24,10 @@ use aptos_infallible::RwLock;
 use aptos_logger::prelude::*;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_network::{
-    application::{error::Error, interface::NetworkClientInterface, metadata::PeerMetadata},
+    application::{
+        error::Error, interface::NetworkClientInterface, metadata::PeerMetadata,
+        reputation::PeerMisbehavior,
+    },
     transport::ConnectionMetadata,
 };
 use aptos_time_service::TimeService;
@@ -470,6 +473,13 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         self.network_client.send_to_peer(message, peer)
     }
 
+    /// Reports the given misbehavior for the peer (e.g., invalid transactions)
+    pub fn report_peer_misbehavior(&self, peer: PeerNetworkId, misbehavior: PeerMisbehavior) {
+        self.network_client
+            .get_peers_and_metadata()
+            .report_peer_misbehavior(peer, misbehavior);
+    }
+
     /// Updates the local tracker for a broadcast.  This is used to handle `DirectSend` tracking of
     /// responses
     fn update_broadcast_state(
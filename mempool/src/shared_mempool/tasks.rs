@@ -4,12 +4,12 @@
 
 //! Tasks that are executed by coordinators (short-lived compared to coordinators)
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{AccountTransactionInfo, CoreMempool, TimelineState},
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     network::{BroadcastError, MempoolSyncMsg},
     shared_mempool::types::{
-        notify_subscribers, MultiBatchId, ScheduledBroadcast, SharedMempool,
+        notify_subscribers, MempoolCapacities, MultiBatchId, ScheduledBroadcast, SharedMempool,
         SharedMempoolNotification, SubmissionStatusBundle,
     },
     thread_pool::IO_POOL,
@@ -20,12 +20,16 @@ use aptos_config::network_id::PeerNetworkId;
 use aptos_consensus_types::common::RejectedTransactionSummary;
 use aptos_crypto::HashValue;
 use aptos_infallible::{Mutex, RwLock};
-use aptos_logger::prelude::*;
+use aptos_logger::{
+    prelude::*,
+    txn_trace::{self, TxnTraceStage},
+};
 use aptos_mempool_notifications::CommittedTransaction;
 use aptos_metrics_core::HistogramTimer;
-use aptos_network::application::interface::NetworkClientInterface;
+use aptos_network::application::{interface::NetworkClientInterface, reputation::PeerMisbehavior};
 use aptos_storage_interface::state_view::LatestDbStateCheckpointView;
 use aptos_types::{
+    account_address::AccountAddress,
     mempool_status::{MempoolStatus, MempoolStatusCode},
     on_chain_config::{OnChainConfigPayload, OnChainConfigProvider, OnChainConsensusConfig},
     transaction::SignedTransaction,
@@ -37,7 +41,7 @@ use rayon::prelude::*;
 use std::{
     cmp,
     sync::Arc,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 use tokio::runtime::Handle;
 
@@ -164,6 +168,80 @@ pub(crate) async fn process_client_get_transaction<NetworkClient, TransactionVal
     }
 }
 
+/// Processes request by an operator for the transactions of an account.
+pub(crate) async fn process_client_get_account_transactions<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    address: AccountAddress,
+    callback: oneshot::Sender<Vec<AccountTransactionInfo>>,
+    timer: HistogramTimer,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    timer.stop_and_record();
+    let txns = smp.mempool.lock().get_account_transactions(&address);
+
+    if callback.send(txns).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::GetAccountTxns,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
+/// Processes request by an operator to evict a transaction.
+pub(crate) async fn process_client_evict_transaction<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    address: AccountAddress,
+    sequence_number: u64,
+    callback: oneshot::Sender<bool>,
+    timer: HistogramTimer,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    timer.stop_and_record();
+    let evicted = smp
+        .mempool
+        .lock()
+        .evict_transaction(&address, sequence_number);
+
+    if callback.send(evicted).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::EvictTxn,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
+/// Processes a request to update the capacities of mempool
+pub(crate) async fn process_client_update_capacities<NetworkClient, TransactionValidator>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    capacities: MempoolCapacities,
+    callback: oneshot::Sender<()>,
+    timer: HistogramTimer,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    timer.stop_and_record();
+    smp.mempool.lock().update_capacities(capacities);
+    info!(
+        LogSchema::new(LogEntry::UpdateCapacities),
+        "Updated the mempool capacities: {:?}", capacities
+    );
+
+    if callback.send(()).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::UpdateCapacities,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
 /// Processes transactions from other nodes.
 pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionValidator>(
     smp: SharedMempool<NetworkClient, TransactionValidator>,
@@ -181,6 +259,15 @@ pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionVali
     let results = process_incoming_transactions(&smp, transactions, timeline_state, false);
     log_txn_process_results(&results, Some(peer));
 
+    // Transactions with invalid signatures can't have been valid at any point,
+    // so forwarding them is considered misbehavior by the peer.
+    if results.iter().any(|(_, (_, maybe_vm_status))| {
+        *maybe_vm_status == Some(DiscardedVMStatus::INVALID_SIGNATURE)
+    }) {
+        smp.network_interface
+            .report_peer_misbehavior(peer, PeerMisbehavior::InvalidMempoolTransactions);
+    }
+
     let ack_response = gen_ack_response(request_id, results, &peer);
 
     // Respond to the peer with an ack. Note: ack response messages should be
@@ -264,6 +351,7 @@ where
 {
     let mut statuses = vec![];
 
+    let admission_start_time = SystemTime::now();
     let start_storage_read = Instant::now();
     let state_view = smp
         .db
@@ -327,6 +415,20 @@ where
         client_submitted,
     );
     notify_subscribers(SharedMempoolNotification::NewTransactions, &smp.subscribers);
+
+    if txn_trace::is_txn_tracing_enabled() {
+        let admission_end_time = SystemTime::now();
+        for (transaction, (mempool_status, _)) in statuses.iter() {
+            if mempool_status.code == MempoolStatusCode::Accepted {
+                txn_trace::record_txn_span(
+                    *transaction.committed_hash(),
+                    TxnTraceStage::MempoolAdmission,
+                    admission_start_time,
+                    admission_end_time,
+                );
+            }
+        }
+    }
     statuses
 }
 
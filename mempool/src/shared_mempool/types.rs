@@ -4,7 +4,7 @@
 
 //! Objects used by/related to shared mempool
 use crate::{
-    core_mempool::CoreMempool,
+    core_mempool::{AccountTransactionInfo, CoreMempool},
     network::{MempoolNetworkInterface, MempoolSyncMsg},
 };
 use anyhow::Result;
@@ -20,7 +20,8 @@ use aptos_infallible::{Mutex, RwLock};
 use aptos_network::application::interface::NetworkClientInterface;
 use aptos_storage_interface::DbReader;
 use aptos_types::{
-    mempool_status::MempoolStatus, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
+    account_address::AccountAddress, mempool_status::MempoolStatus, transaction::SignedTransaction,
+    vm_status::DiscardedVMStatus,
 };
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
@@ -226,6 +227,31 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    GetAccountTransactions(AccountAddress, oneshot::Sender<Vec<AccountTransactionInfo>>),
+    /// Evicts the transaction with the sequence number of the account, replying whether it was
+    /// in mempool
+    EvictTransaction(AccountAddress, u64, oneshot::Sender<bool>),
+    /// Updates the capacities of mempool, e.g. when the node config is reloaded. Lowering a
+    /// capacity doesn't evict any transactions, mempool stays full until it drains below it.
+    UpdateCapacities(MempoolCapacities, oneshot::Sender<()>),
+}
+
+/// The capacities of mempool that can be updated without restarting the node
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MempoolCapacities {
+    pub capacity: usize,
+    pub capacity_bytes: usize,
+    pub capacity_per_user: usize,
+}
+
+impl From<&MempoolConfig> for MempoolCapacities {
+    fn from(config: &MempoolConfig) -> Self {
+        Self {
+            capacity: config.capacity,
+            capacity_bytes: config.capacity_bytes,
+            capacity_per_user: config.capacity_per_user,
+        }
+    }
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
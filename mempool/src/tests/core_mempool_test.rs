@@ -4,12 +4,13 @@
 
 use crate::{
     core_mempool::{CoreMempool, MempoolTransaction, SubmittedBy, TimelineState},
+    shared_mempool::types::MempoolCapacities,
     tests::common::{
         add_signed_txn, add_txn, add_txns_to_mempool, setup_mempool,
         setup_mempool_with_broadcast_buckets, txn_bytes_len, TestTransaction,
     },
 };
-use aptos_config::config::NodeConfig;
+use aptos_config::config::{NodeConfig, PriorityBandConfig};
 use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
 use aptos_crypto::HashValue;
 use aptos_types::{
@@ -173,6 +174,80 @@ fn test_update_invalid_transaction_in_mempool() {
     assert_eq!(next_tnx[0].gas_unit_price(), 1);
 }
 
+#[test]
+fn test_replace_by_fee() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.broadcast_buckets = vec![0];
+    config.mempool.replace_by_fee_min_gas_price_bump_pct = Some(50);
+    let mut mempool = CoreMempool::new(&config);
+    add_txns_to_mempool(&mut mempool, vec![TestTransaction::new(0, 0, 10)]);
+
+    // A different transaction needs a high enough gas price to replace the pending one
+    let replacement = TestTransaction::make_signed_transaction_with_max_gas_amount(
+        &TestTransaction::new(0, 0, 14),
+        200,
+    );
+    assert!(add_signed_txn(&mut mempool, replacement).is_err());
+    let replacement = TestTransaction::make_signed_transaction_with_max_gas_amount(
+        &TestTransaction::new(0, 0, 15),
+        200,
+    );
+    assert!(add_signed_txn(&mut mempool, replacement.clone()).is_ok());
+
+    assert_eq!(mempool.get_batch(10, 10240, true, btreemap![]), vec![
+        replacement.clone()
+    ]);
+    assert_eq!(
+        mempool.get_by_hash(replacement.committed_hash()),
+        Some(replacement.clone())
+    );
+    // The replacement is broadcast, like a new transaction
+    let (timeline, _) = mempool.read_timeline(&vec![0].into(), 10, None);
+    assert_eq!(timeline, vec![replacement]);
+}
+
+#[test]
+fn test_priority_band_quotas() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.broadcast_buckets = vec![0];
+    config.mempool.priority_bands = vec![
+        PriorityBandConfig {
+            min_gas_unit_price: 1,
+            max_batch_pct: 50,
+        },
+        PriorityBandConfig {
+            min_gas_unit_price: 10,
+            max_batch_pct: 100,
+        },
+    ];
+    let mut mempool = CoreMempool::new(&config);
+    add_txns_to_mempool(&mut mempool, vec![
+        TestTransaction::new(0, 0, 10),
+        TestTransaction::new(1, 0, 1),
+        TestTransaction::new(1, 1, 1),
+        TestTransaction::new(2, 0, 1),
+        TestTransaction::new(3, 0, 1),
+    ]);
+
+    // The cheap transactions may only take half of the batch
+    let batch = mempool.get_batch(4, 10240, true, btreemap![]);
+    assert_eq!(batch.len(), 3);
+    assert_eq!(batch[0].gas_unit_price(), 10);
+    assert_eq!(
+        batch.iter().filter(|txn| txn.gas_unit_price() == 1).count(),
+        2
+    );
+
+    // The quota doesn't apply to transactions priced below the first band
+    let mut mempool = CoreMempool::new(&config);
+    add_txns_to_mempool(&mut mempool, vec![
+        TestTransaction::new(0, 0, 0),
+        TestTransaction::new(1, 0, 0),
+        TestTransaction::new(2, 0, 0),
+    ]);
+    assert_eq!(mempool.get_batch(4, 10240, true, btreemap![]).len(), 3);
+}
+
 #[test]
 fn test_commit_transaction() {
     let (mut pool, mut consensus) = setup_mempool();
@@ -277,6 +352,49 @@ fn test_reject_transaction() {
         .is_none());
 }
 
+#[test]
+fn test_evict_transaction() {
+    let (mut pool, _) = setup_mempool();
+    add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(0, 1, 1),
+        TestTransaction::new(0, 2, 1),
+        TestTransaction::new(0, 4, 1),
+    ]);
+    let address = TestTransaction::get_address(0);
+
+    let txns = pool.get_account_transactions(&address);
+    assert_eq!(
+        txns.iter()
+            .map(|txn| (txn.sequence_number, txn.ready, txn.parked))
+            .collect::<Vec<_>>(),
+        vec![
+            (0, true, false),
+            (1, true, false),
+            (2, true, false),
+            (4, false, true)
+        ]
+    );
+
+    // Evicting a transaction parks the later ones, as there's a gap now
+    assert!(pool.evict_transaction(&address, 1));
+    assert!(!pool.evict_transaction(&address, 1));
+    let txns = pool.get_account_transactions(&address);
+    assert_eq!(
+        txns.iter()
+            .map(|txn| (txn.sequence_number, txn.ready, txn.parked))
+            .collect::<Vec<_>>(),
+        vec![(0, true, false), (2, false, true), (4, false, true)]
+    );
+    assert_eq!(view(pool.get_batch(10, 10240, true, btreemap![])), vec![0]);
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10, None);
+    assert_eq!(view(timeline), vec![0]);
+
+    assert!(pool
+        .get_account_transactions(&TestTransaction::get_address(1))
+        .is_empty());
+}
+
 #[test]
 fn test_system_ttl() {
     // Created mempool with system_transaction_timeout = 0.
@@ -299,6 +417,41 @@ fn test_system_ttl() {
     assert_eq!(vec![transaction.make_signed_transaction()], batch);
 }
 
+#[test]
+fn test_payload_ttl_policy() {
+    // Create a mempool where script transactions expire immediately
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.system_transaction_timeout_secs = 600;
+    config
+        .mempool
+        .payload_ttl_policy
+        .script_transaction_timeout_secs = Some(0);
+    let mut mempool = CoreMempool::new(&config);
+
+    // Add a (script) transaction, and verify it's removed on the next GC run
+    add_txn(&mut mempool, TestTransaction::new(0, 0, 1)).unwrap();
+    mempool.gc();
+    assert!(mempool.get_batch(1, 1024, true, btreemap![]).is_empty());
+
+    // Create a mempool where only large transactions expire immediately
+    config
+        .mempool
+        .payload_ttl_policy
+        .script_transaction_timeout_secs = None;
+    config
+        .mempool
+        .payload_ttl_policy
+        .large_transaction_timeout_secs = Some(0);
+    let mut mempool = CoreMempool::new(&config);
+
+    // Add a small transaction, and verify it stays in mempool after the next GC run
+    let transaction = TestTransaction::new(1, 0, 1);
+    add_txn(&mut mempool, transaction.clone()).unwrap();
+    mempool.gc();
+    let batch = mempool.get_batch(1, 1024, true, btreemap![]);
+    assert_eq!(vec![transaction.make_signed_transaction()], batch);
+}
+
 #[test]
 fn test_commit_callback() {
     // Consensus commit callback should unlock txns in parking lot.
@@ -552,6 +705,27 @@ fn test_capacity() {
     assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_ok());
 }
 
+#[test]
+fn test_update_capacities() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.capacity = 1;
+    let mut pool = CoreMempool::new(&config);
+
+    let txn = add_txn(&mut pool, TestTransaction::new(1, 0, 1)).unwrap();
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 1, 1)).is_err());
+
+    // Raising the capacity makes room for more transactions
+    config.mempool.capacity = 2;
+    pool.update_capacities(MempoolCapacities::from(&config.mempool));
+    add_txn(&mut pool, TestTransaction::new(1, 1, 1)).unwrap();
+
+    // Lowering it doesn't evict any, but new ones are rejected
+    config.mempool.capacity = 1;
+    pool.update_capacities(MempoolCapacities::from(&config.mempool));
+    assert!(pool.get_by_hash(txn.committed_hash()).is_some());
+    assert!(add_txn(&mut pool, TestTransaction::new(2, 0, 1)).is_err());
+}
+
 #[test]
 fn test_capacity_bytes() {
     let capacity_bytes = 2_048;
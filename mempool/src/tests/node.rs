@@ -495,6 +495,7 @@ impl NodeNetworkInterface {
         let remote_peer_id = match &message {
             PeerManagerNotification::RecvRpc(peer_id, _) => *peer_id,
             PeerManagerNotification::RecvMessage(peer_id, _) => *peer_id,
+            PeerManagerNotification::RecvRpcStream(peer_id, _) => *peer_id,
         };
 
         self.network_notifs_tx
@@ -359,6 +359,9 @@ impl MempoolNode {
             PeerManagerRequest::SendDirectSend(peer_id, msg) => {
                 (peer_id, msg.protocol_id, msg.mdata, None)
             },
+            PeerManagerRequest::SendRpcStream(_, _) => {
+                panic!("Mempool should not be sending rpc stream requests")
+            },
         };
         assert_eq!(peer_id, expected_peer_id);
         let mempool_message = common::decompress_and_deserialize(&data.to_vec());
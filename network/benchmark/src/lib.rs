@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_config::{
-    config::NodeConfig,
+    config::{NetbenchPayloadDistribution, NodeConfig},
     network_id::{NetworkId, PeerNetworkId},
 };
 use aptos_logger::{
@@ -10,11 +10,15 @@ use aptos_logger::{
     prelude::{sample, SampleRate},
     warn,
 };
-use aptos_metrics_core::{register_int_counter_vec, IntCounter, IntCounterVec};
+use aptos_metrics_core::{
+    exponential_buckets, register_histogram_vec, register_int_counter_vec, Histogram, HistogramVec,
+    IntCounter, IntCounterVec,
+};
 use aptos_network::{
     application::interface::{NetworkClient, NetworkClientInterface, NetworkServiceEvents},
     protocols::{network::Event, rpc::error::RpcError, wire::handshake::v1::ProtocolId},
 };
+use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{account_address::AccountAddress, PeerId};
 use bytes::Bytes;
@@ -25,7 +29,7 @@ use futures::{
 use once_cell::sync::Lazy;
 use rand::{rngs::OsRng, Rng};
 use serde::{Deserialize, Serialize};
-use std::{ops::DerefMut, sync::Arc, time::Duration};
+use std::{collections::VecDeque, ops::DerefMut, sync::Arc, time::Duration};
 use tokio::{runtime::Handle, select, sync::RwLock};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -33,6 +37,7 @@ use tokio::{runtime::Handle, select, sync::RwLock};
 pub enum NetbenchMessage {
     DataSend(NetbenchDataSend),
     DataReply(NetbenchDataReply),
+    DataEcho(NetbenchDataSend), // An RPC request that is replied to with itself (i.e., the data round-trips)
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -135,6 +140,9 @@ async fn handle_direct(
                 )
             }
         },
+        NetbenchMessage::DataEcho(_) => {
+            direct_messages("bad"); // Echoes are only sent as RPCs
+        },
     }
 }
 
@@ -145,36 +153,41 @@ async fn handle_rpc(
     time_service: TimeService,
     sender: Sender<Result<Bytes, RpcError>>,
 ) {
-    match msg_wrapper {
+    let (request_counter, reply) = match msg_wrapper {
         NetbenchMessage::DataSend(send) => {
             let reply = NetbenchDataReply {
                 request_counter: send.request_counter,
                 send_micros: time_service.now_unix_time().as_micros() as u64,
                 request_send_micros: send.send_micros,
             };
-            let reply = NetbenchMessage::DataReply(reply);
-            let reply_bytes = match protocol_id.to_bytes(&reply) {
-                Ok(rb) => rb,
-                Err(_) => {
-                    rpc_messages("err");
-                    return;
-                },
-            };
-            let reply_bytes: Bytes = reply_bytes.into();
-            let result = sender.send(Ok(reply_bytes));
-            if let Err(err) = result {
-                match err {
-                    Ok(_) => {}, // what? Ok inside Err?
-                    Err(err) => {
-                        rpc_messages("err");
-                        info!("netbench rpc [{}] reply err: {}", send.request_counter, err);
-                    },
-                }
-            }
+            (send.request_counter, NetbenchMessage::DataReply(reply))
+        },
+        NetbenchMessage::DataEcho(send) => {
+            // Reply with the request itself, so that the data round-trips
+            (send.request_counter, NetbenchMessage::DataEcho(send))
         },
         NetbenchMessage::DataReply(_) => {
             rpc_messages("err");
+            return;
         },
+    };
+    let reply_bytes = match protocol_id.to_bytes(&reply) {
+        Ok(rb) => rb,
+        Err(_) => {
+            rpc_messages("err");
+            return;
+        },
+    };
+    let reply_bytes: Bytes = reply_bytes.into();
+    let result = sender.send(Ok(reply_bytes));
+    if let Err(err) = result {
+        match err {
+            Ok(_) => {}, // what? Ok inside Err?
+            Err(err) => {
+                rpc_messages("err");
+                info!("netbench rpc [{}] reply err: {}", request_counter, err);
+            },
+        }
     }
 }
 
@@ -240,7 +253,8 @@ async fn handler_task(
                     ));
                 }
             },
-            Event::LostPeer(_) => {}, // don't care
+            Event::LostPeer(_) => {},          // don't care
+            Event::RpcStreamRequest(..) => {}, // not benchmarked
         }
     }
 }
@@ -377,17 +391,20 @@ pub async fn rpc_sender(
     let interval = Duration::from_nanos(1_000_000_000 / config.rpc_per_second);
     let ticker = time_service.interval(interval);
     futures::pin_mut!(ticker);
-    // random payload filler
+    // random payload filler (large enough for the largest payload)
+    let payload_distribution = config.rpc_payload_distribution;
     let data_size = config.rpc_data_size;
-    let mut blob = Vec::<u8>::with_capacity(data_size);
+    let max_data_size = payload_distribution.max_payload_size(data_size).max(8);
+    let mut blob = Vec::<u8>::with_capacity(max_data_size);
     let mut rng = OsRng;
-    for _ in 0..data_size {
+    for _ in 0..max_data_size {
         blob.push(rng.gen());
     }
 
     let mut counter: u64 = rng.gen();
 
     let mut open_rpcs = FuturesUnordered::new();
+    let mut latency_tracker = RpcLatencyTracker::new(network_id, peer_id, config.rpc_echo);
 
     loop {
         select! {
@@ -403,29 +420,34 @@ pub async fn rpc_sender(
                     let (dest, _) = blob.deref_mut().split_at_mut(8);
                     dest.copy_from_slice(&counter_bytes);
                 }
+                let payload_size = sample_payload_size(payload_distribution, data_size, &mut rng);
 
                 let nowu = time_service.now_unix_time().as_micros() as u64;
                 let msg = NetbenchDataSend {
                     request_counter: counter,
                     send_micros: nowu,
-                    data: blob.clone(),
+                    data: blob[..payload_size].to_vec(),
                 };
                 {
                     shared.write().await.set(SendRecord{
                         request_counter: counter,
                         send_micros: nowu,
-                        bytes_sent: blob.len(),
+                        bytes_sent: payload_size,
                     })
                 }
-                let wrapper = NetbenchMessage::DataSend(msg);
+                let wrapper = if config.rpc_echo {
+                    NetbenchMessage::DataEcho(msg)
+                } else {
+                    NetbenchMessage::DataSend(msg)
+                };
                 let result = network_client.send_to_peer_rpc(wrapper, Duration::from_secs(10), PeerNetworkId::new(network_id, peer_id));
                 rpc_messages("sent");
-                open_rpcs.push(result);
+                open_rpcs.push(async move { (payload_size, result.await) });
 
                 sample!(SampleRate::Duration(Duration::from_millis(BLAB_MILLIS)), info!("netbench rpc counter={}", counter));
             }
             result = open_rpcs.next() => {
-                let result = match result {
+                let (payload_size, result) = match result {
                     Some(subr) => {subr}
                     None => {
                         continue
@@ -440,15 +462,27 @@ pub async fn rpc_sender(
                     }
                     Ok(msg_wrapper) => {
                         let nowu = time_service.now_unix_time().as_micros() as u64;
-                        if let NetbenchMessage::DataReply(msg) = msg_wrapper {
-                            let send_dt = nowu - msg.request_send_micros;
-                            info!("netbench [{}] rpc at {} µs, took {} µs", msg.request_counter, nowu, send_dt);
-                            rpc_messages("ok");
-                            rpc_bytes("ok").inc_by(data_size as u64);
-                            rpc_micros("ok").inc_by(send_dt);
-                        } else {
-                            rpc_messages("bad");
-                            info!("netbench [{}] rpc garbage reply", counter);
+                        match msg_wrapper {
+                            NetbenchMessage::DataReply(msg) => {
+                                let send_dt = nowu - msg.request_send_micros;
+                                info!("netbench [{}] rpc at {} µs, took {} µs", msg.request_counter, nowu, send_dt);
+                                rpc_messages("ok");
+                                rpc_bytes("ok").inc_by(payload_size as u64);
+                                rpc_micros("ok").inc_by(send_dt);
+                                latency_tracker.observe(send_dt, nowu);
+                            }
+                            NetbenchMessage::DataEcho(msg) => {
+                                let send_dt = nowu - msg.send_micros;
+                                rpc_messages("ok");
+                                // The payload was sent in both directions
+                                rpc_bytes("ok").inc_by(2 * msg.data.len() as u64);
+                                rpc_micros("ok").inc_by(send_dt);
+                                latency_tracker.observe(send_dt, nowu);
+                            }
+                            _ => {
+                                rpc_messages("bad");
+                                info!("netbench [{}] rpc garbage reply", counter);
+                            }
                         }
                     }
                 }
@@ -457,6 +491,94 @@ pub async fn rpc_sender(
     }
 }
 
+/// Returns the size of the next payload, sampled from the given distribution
+/// (`data_size` is the size of all payloads in the fixed distribution).
+fn sample_payload_size(
+    payload_distribution: NetbenchPayloadDistribution,
+    data_size: usize,
+    rng: &mut impl Rng,
+) -> usize {
+    match payload_distribution {
+        NetbenchPayloadDistribution::Fixed => data_size,
+        NetbenchPayloadDistribution::Uniform {
+            min_bytes,
+            max_bytes,
+        } => rng.gen_range(min_bytes, max_bytes + 1),
+        NetbenchPayloadDistribution::Bimodal {
+            small_bytes,
+            large_bytes,
+            large_percentage,
+        } => {
+            if rng.gen_range(0, 100) < large_percentage {
+                large_bytes
+            } else {
+                small_bytes
+            }
+        },
+    }
+}
+
+// The number of recent RPC latencies used to calculate the percentiles for a peer
+const MAX_TRACKED_RPC_LATENCIES: usize = 10_000;
+
+/// Tracks the RPC latencies to a single peer, and periodically
+/// reports the latency percentiles (e.g., p50, p90 and p99).
+struct RpcLatencyTracker {
+    network_id: NetworkId,
+    peer_id: PeerId,
+    rpc_mode: &'static str,
+    latencies_micros: VecDeque<u64>, // The most recent latencies
+    last_report_micros: u64,         // The time at which the percentiles were last reported
+}
+
+impl RpcLatencyTracker {
+    fn new(network_id: NetworkId, peer_id: PeerId, rpc_echo: bool) -> Self {
+        Self {
+            network_id,
+            peer_id,
+            rpc_mode: if rpc_echo { "echo" } else { "reply" },
+            latencies_micros: VecDeque::with_capacity(MAX_TRACKED_RPC_LATENCIES),
+            last_report_micros: 0,
+        }
+    }
+
+    fn observe(&mut self, latency_micros: u64, now_micros: u64) {
+        rpc_latency(self.network_id, &self.peer_id, self.rpc_mode)
+            .observe(latency_micros as f64 / 1_000_000.0);
+
+        if self.latencies_micros.len() >= MAX_TRACKED_RPC_LATENCIES {
+            self.latencies_micros.pop_front();
+        }
+        self.latencies_micros.push_back(latency_micros);
+
+        // Report the percentiles at most once every BLAB_MILLIS (per peer)
+        if now_micros.saturating_sub(self.last_report_micros) >= BLAB_MILLIS * 1000 {
+            self.last_report_micros = now_micros;
+            self.log_percentiles();
+        }
+    }
+
+    fn log_percentiles(&self) {
+        let mut latencies_micros: Vec<_> = self.latencies_micros.iter().copied().collect();
+        latencies_micros.sort_unstable();
+        let percentile = |percentile: usize| {
+            let index = (latencies_micros.len() * percentile / 100)
+                .min(latencies_micros.len().saturating_sub(1));
+            latencies_micros.get(index).copied().unwrap_or_default()
+        };
+        info!(
+            "netbench [{},{}] rpc {} latency over the last {} rpcs: p50={} µs, p90={} µs, p99={} µs",
+            self.network_id,
+            self.peer_id,
+            self.rpc_mode,
+            latencies_micros.len(),
+            percentile(50),
+            percentile(90),
+            percentile(99)
+        );
+    }
+}
+
 pub struct NetbenchSharedState {
     // Circular buffer of sent records
     sent: Vec<SendRecord>,
@@ -609,3 +731,21 @@ pub static APTOS_NETWORK_BENCHMARK_RPC_MICROS: Lazy<IntCounterVec> = Lazy::new(|
 pub fn rpc_micros(state_label: &'static str) -> IntCounter {
     APTOS_NETWORK_BENCHMARK_RPC_MICROS.with_label_values(&[state_label])
 }
+
+pub static APTOS_NETWORK_BENCHMARK_RPC_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_network_benchmark_rpc_latency_seconds",
+        "Net benchmark RPC round-trip latencies (seconds) per peer",
+        &["network_id", "peer_id", "mode"],
+        exponential_buckets(/*start=*/ 1e-4, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+fn rpc_latency(network_id: NetworkId, peer_id: &PeerId, rpc_mode: &'static str) -> Histogram {
+    APTOS_NETWORK_BENCHMARK_RPC_LATENCY.with_label_values(&[
+        network_id.as_str(),
+        peer_id.short_str().as_str(),
+        rpc_mode,
+    ])
+}
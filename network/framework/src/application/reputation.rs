@@ -0,0 +1,95 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{Duration, Instant};
+
+/// The maximum (and initial) reputation score of a peer
+pub const MAX_REPUTATION_SCORE: u64 = 100;
+
+/// Peers with a score at (or below) this threshold have a low reputation,
+/// and are only dialed if there are no other peers to dial.
+pub const LOW_REPUTATION_THRESHOLD: u64 = 50;
+
+/// Peers with a score at (or below) this threshold are disconnected
+pub const DISCONNECT_REPUTATION_THRESHOLD: u64 = 0;
+
+/// The time it takes for a peer to recover a single point of reputation
+pub const REPUTATION_RECOVERY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The types of peer misbehavior that applications can report
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PeerMisbehavior {
+    /// The peer sent a consensus message that failed verification
+    InvalidConsensusMessage,
+    /// The peer sent state sync data that failed verification
+    InvalidStateSyncData,
+    /// The peer sent mempool transactions that failed validation
+    /// (e.g., with invalid signatures)
+    InvalidMempoolTransactions,
+}
+
+impl PeerMisbehavior {
+    /// Returns the reputation penalty for the misbehavior
+    pub fn get_penalty(&self) -> u64 {
+        match self {
+            PeerMisbehavior::InvalidConsensusMessage => 25,
+            PeerMisbehavior::InvalidStateSyncData => 25,
+            PeerMisbehavior::InvalidMempoolTransactions => 10,
+        }
+    }
+
+    /// Returns a summary label for the misbehavior
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            PeerMisbehavior::InvalidConsensusMessage => "invalid_consensus_message",
+            PeerMisbehavior::InvalidStateSyncData => "invalid_state_sync_data",
+            PeerMisbehavior::InvalidMempoolTransactions => "invalid_mempool_transactions",
+        }
+    }
+}
+
+/// The reputation of a peer. The score is decreased by the penalty of each
+/// reported misbehavior, and recovers slowly over time (up to the maximum).
+#[derive(Clone, Copy, Debug)]
+pub struct PeerReputation {
+    score: u64,
+    last_update_time: Instant,
+}
+
+impl PeerReputation {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            score: MAX_REPUTATION_SCORE,
+            last_update_time: now,
+        }
+    }
+
+    /// Returns the score of the peer at the given time (including any recovery)
+    pub fn get_score(&self, now: Instant) -> u64 {
+        let elapsed_time = now.saturating_duration_since(self.last_update_time);
+        let recovered_points =
+            elapsed_time.as_secs() / REPUTATION_RECOVERY_INTERVAL.as_secs().max(1);
+        self.score
+            .saturating_add(recovered_points)
+            .min(MAX_REPUTATION_SCORE)
+    }
+
+    /// Applies the penalty of the given misbehavior and returns the new score
+    pub fn record_misbehavior(&mut self, misbehavior: PeerMisbehavior, now: Instant) -> u64 {
+        self.score = self
+            .get_score(now)
+            .saturating_sub(misbehavior.get_penalty());
+        self.last_update_time = now;
+        self.score
+    }
+
+    /// Returns true iff the peer has a low reputation at the given time
+    pub fn is_low(&self, now: Instant) -> bool {
+        self.get_score(now) <= LOW_REPUTATION_THRESHOLD
+    }
+
+    /// Returns true iff the peer should be disconnected at the given time
+    pub fn should_disconnect(&self, now: Instant) -> bool {
+        self.get_score(now) <= DISCONNECT_REPUTATION_THRESHOLD
+    }
+}
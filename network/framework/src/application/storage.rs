@@ -6,7 +6,9 @@ use crate::{
     application::{
         error::Error,
         metadata::{ConnectionState, PeerMetadata},
+        reputation::{PeerMisbehavior, PeerReputation, MAX_REPUTATION_SCORE},
     },
+    counters,
     transport::{ConnectionId, ConnectionMetadata},
     ProtocolId,
 };
@@ -22,6 +24,7 @@ use std::{
     collections::{hash_map::Entry, HashMap},
     ops::Deref,
     sync::{Arc, RwLockWriteGuard},
+    time::Instant,
 };
 
 /// A simple container that tracks all peers and peer metadata for the node.
@@ -39,6 +42,11 @@ pub struct PeersAndMetadata {
     //
     // TODO: should we remove this when generational versioning is supported?
     cached_peers_and_metadata: Arc<ArcSwap<HashMap<NetworkId, HashMap<PeerId, PeerMetadata>>>>,
+
+    // The reputations of peers that have been reported for misbehavior. These are
+    // maintained separately from the peer metadata, so that they persist across
+    // reconnections (i.e., a peer can't reset its reputation by reconnecting).
+    peer_reputations: RwLock<HashMap<PeerNetworkId, PeerReputation>>,
 }
 
 impl PeersAndMetadata {
@@ -48,6 +56,7 @@ impl PeersAndMetadata {
             peers_and_metadata: RwLock::new(HashMap::new()),
             trusted_peers: HashMap::new(),
             cached_peers_and_metadata: Arc::new(ArcSwap::from(Arc::new(HashMap::new()))),
+            peer_reputations: RwLock::new(HashMap::new()),
         };
 
         // Initialize each network mapping and trusted peer set
@@ -345,6 +354,54 @@ impl PeersAndMetadata {
         Ok(())
     }
 
+    /// Reports the given misbehavior for the peer, and lowers the peer's
+    /// reputation accordingly. Returns the new reputation score of the peer.
+    pub fn report_peer_misbehavior(
+        &self,
+        peer_network_id: PeerNetworkId,
+        misbehavior: PeerMisbehavior,
+    ) -> u64 {
+        counters::peer_misbehavior_reports(&peer_network_id.network_id(), misbehavior).inc();
+
+        let now = Instant::now();
+        self.peer_reputations
+            .write()
+            .entry(peer_network_id)
+            .or_insert_with(|| PeerReputation::new(now))
+            .record_misbehavior(misbehavior, now)
+    }
+
+    /// Returns the reputation score of the given peer. Peers that
+    /// have never been reported have the maximum score.
+    pub fn get_peer_reputation_score(&self, peer_network_id: &PeerNetworkId) -> u64 {
+        let now = Instant::now();
+        self.peer_reputations
+            .read()
+            .get(peer_network_id)
+            .map(|peer_reputation| peer_reputation.get_score(now))
+            .unwrap_or(MAX_REPUTATION_SCORE)
+    }
+
+    /// Returns true iff the given peer currently has a low reputation
+    pub fn has_low_reputation(&self, peer_network_id: &PeerNetworkId) -> bool {
+        let now = Instant::now();
+        self.peer_reputations
+            .read()
+            .get(peer_network_id)
+            .map_or(false, |peer_reputation| peer_reputation.is_low(now))
+    }
+
+    /// Returns true iff the given peer should be disconnected due to its reputation
+    pub fn should_disconnect_peer(&self, peer_network_id: &PeerNetworkId) -> bool {
+        let now = Instant::now();
+        self.peer_reputations
+            .read()
+            .get(peer_network_id)
+            .map_or(false, |peer_reputation| {
+                peer_reputation.should_disconnect(now)
+            })
+    }
+
     #[cfg(test)]
     /// Returns all internal maps (for testing purposes only)
     pub(crate) fn get_all_internal_maps(
@@ -7,6 +7,9 @@ use crate::{
         error::Error,
         interface::{NetworkClient, NetworkClientInterface, NetworkServiceEvents},
         metadata::{ConnectionState, PeerMetadata},
+        reputation::{
+            PeerMisbehavior, PeerReputation, MAX_REPUTATION_SCORE, REPUTATION_RECOVERY_INTERVAL,
+        },
         storage::PeersAndMetadata,
     },
     peer_manager::{
@@ -37,7 +40,7 @@ use std::{
     hash::Hash,
     ops::Deref,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::time::timeout;
 
@@ -432,6 +435,93 @@ fn test_peers_and_metadata_caching() {
     );
 }
 
+#[test]
+fn test_peers_and_metadata_peer_reputations() {
+    // Create the peers and metadata container
+    let network_ids = vec![NetworkId::Validator, NetworkId::Public];
+    let peers_and_metadata = PeersAndMetadata::new(&network_ids);
+
+    // Create two peers and verify they both have the maximum reputation
+    let (peer_network_id_1, connection_1) = create_peer_and_connection(
+        NetworkId::Public,
+        vec![ProtocolId::MempoolDirectSend],
+        peers_and_metadata.clone(),
+    );
+    let (peer_network_id_2, _) = create_peer_and_connection(
+        NetworkId::Public,
+        vec![ProtocolId::StorageServiceRpc],
+        peers_and_metadata.clone(),
+    );
+    for peer_network_id in [peer_network_id_1, peer_network_id_2] {
+        assert_eq!(
+            peers_and_metadata.get_peer_reputation_score(&peer_network_id),
+            MAX_REPUTATION_SCORE
+        );
+        assert!(!peers_and_metadata.has_low_reputation(&peer_network_id));
+        assert!(!peers_and_metadata.should_disconnect_peer(&peer_network_id));
+    }
+
+    // Report peer 1 for misbehavior until it has a low reputation
+    let misbehavior = PeerMisbehavior::InvalidStateSyncData;
+    let score = peers_and_metadata.report_peer_misbehavior(peer_network_id_1, misbehavior);
+    assert_eq!(score, MAX_REPUTATION_SCORE - misbehavior.get_penalty());
+    peers_and_metadata.report_peer_misbehavior(peer_network_id_1, misbehavior);
+    assert!(peers_and_metadata.has_low_reputation(&peer_network_id_1));
+    assert!(!peers_and_metadata.should_disconnect_peer(&peer_network_id_1));
+
+    // Disconnect peer 1 and verify its reputation persists
+    remove_peer_metadata(
+        &peers_and_metadata,
+        peer_network_id_1,
+        connection_1.connection_id.get_inner(),
+    )
+    .unwrap();
+    assert!(peers_and_metadata.has_low_reputation(&peer_network_id_1));
+
+    // Report peer 1 again until it should be disconnected
+    for _ in 0..2 {
+        peers_and_metadata.report_peer_misbehavior(peer_network_id_1, misbehavior);
+    }
+    assert_eq!(
+        peers_and_metadata.get_peer_reputation_score(&peer_network_id_1),
+        0
+    );
+    assert!(peers_and_metadata.should_disconnect_peer(&peer_network_id_1));
+
+    // Verify peer 2 is unaffected
+    assert_eq!(
+        peers_and_metadata.get_peer_reputation_score(&peer_network_id_2),
+        MAX_REPUTATION_SCORE
+    );
+}
+
+#[test]
+fn test_peer_reputation_recovery() {
+    // Create a peer reputation and report misbehavior
+    let start_time = Instant::now();
+    let mut peer_reputation = PeerReputation::new(start_time);
+    let misbehavior = PeerMisbehavior::InvalidConsensusMessage;
+    for _ in 0..4 {
+        peer_reputation.record_misbehavior(misbehavior, start_time);
+    }
+    assert_eq!(peer_reputation.get_score(start_time), 0);
+    assert!(peer_reputation.should_disconnect(start_time));
+
+    // Verify the score recovers over time
+    let recovery_time = start_time + REPUTATION_RECOVERY_INTERVAL * 10;
+    assert_eq!(peer_reputation.get_score(recovery_time), 10);
+    assert!(peer_reputation.is_low(recovery_time));
+    assert!(!peer_reputation.should_disconnect(recovery_time));
+
+    // Verify the score never exceeds the maximum
+    let recovery_time = start_time + REPUTATION_RECOVERY_INTERVAL * 1000;
+    assert_eq!(
+        peer_reputation.get_score(recovery_time),
+        MAX_REPUTATION_SCORE
+    );
+    assert!(!peer_reputation.is_low(recovery_time));
+}
+
 #[test]
 fn test_network_client_available_peers() {
     // Create the peers and metadata container
@@ -1120,6 +1210,9 @@ async fn wait_for_network_event(
                     // Create and return the peer manager notification
                     (message.protocol_id, PeerManagerNotification::RecvMessage(peer_id, message))
                 }
+                PeerManagerRequest::SendRpcStream(_, _) => {
+                    panic!("Unexpected rpc stream request!")
+                }
             };
 
             // Pass the message from the outbound request receivers to the inbound request
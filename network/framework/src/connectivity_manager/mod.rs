@@ -36,7 +36,7 @@ use crate::{
 };
 use aptos_config::{
     config::{Peer, PeerRole, PeerSet},
-    network_id::NetworkContext,
+    network_id::{NetworkContext, PeerNetworkId},
 };
 use aptos_crypto::x25519;
 use aptos_infallible::RwLock;
@@ -528,6 +528,54 @@ where
         }
     }
 
+    /// Disconnect from all peers that have been reported for misbehavior
+    /// so often that their reputation is too low to remain connected.
+    ///
+    /// Note: peers on mutually authenticated networks (e.g., the validator
+    /// network) are trusted, so they are never disconnected for misbehavior.
+    async fn close_misbehaving_connections(&mut self) {
+        if self.mutual_authentication {
+            return;
+        }
+
+        // Identify misbehaving peer connections
+        let network_id = self.network_context.network_id();
+        let misbehaving_peers: Vec<_> = self
+            .connected
+            .keys()
+            .filter(|peer_id| {
+                self.peers_and_metadata
+                    .should_disconnect_peer(&PeerNetworkId::new(network_id, **peer_id))
+            })
+            .cloned()
+            .collect();
+
+        // Close existing connections to misbehaving peers
+        for misbehaving_peer in misbehaving_peers {
+            info!(
+                NetworkSchema::new(&self.network_context).remote_peer(&misbehaving_peer),
+                "{} Closing connection to misbehaving peer {}",
+                self.network_context,
+                misbehaving_peer.short_str()
+            );
+
+            if let Err(disconnect_error) = self
+                .connection_reqs_tx
+                .disconnect_peer(misbehaving_peer)
+                .await
+            {
+                info!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&misbehaving_peer),
+                    error = %disconnect_error,
+                    "{} Failed to close connection to misbehaving peer {}, error: {}",
+                    self.network_context,
+                    misbehaving_peer.short_str(),
+                    disconnect_error
+                );
+            }
+        }
+    }
+
     /// Cancel all pending dials to peers that are no longer eligible.
     ///
     /// For instance, a validator might leave the validator set after a
@@ -583,6 +631,26 @@ where
             })
             .collect();
 
+        // Prefer peers with a good reputation, i.e., only dial peers
+        // with a low reputation if there are no other eligible peers.
+        // Peers on mutually authenticated networks (e.g., the validator
+        // network) are trusted, so they are always dialed.
+        let eligible_peers = if self.mutual_authentication {
+            eligible_peers
+        } else {
+            let (preferred_peers, low_reputation_peers): (Vec<_>, Vec<_>) =
+                eligible_peers.into_iter().partition(|(peer_id, _)| {
+                    !self
+                        .peers_and_metadata
+                        .has_low_reputation(&PeerNetworkId::new(network_id, *peer_id))
+                });
+            if preferred_peers.is_empty() {
+                low_reputation_peers
+            } else {
+                preferred_peers
+            }
+        };
+
         // Initialize the dial state for any new peers
         for (peer_id, _) in &eligible_peers {
             self.dial_states
@@ -825,6 +893,8 @@ where
         self.cancel_stale_dials().await;
         // Disconnect from connected peers that are no longer eligible.
         self.close_stale_connections().await;
+        // Disconnect from connected peers with a reputation that is too low.
+        self.close_misbehaving_connections().await;
         // Dial peers which are eligible but are neither connected nor queued for dialing in the
         // future.
         self.dial_eligible_peers(pending_dials).await;
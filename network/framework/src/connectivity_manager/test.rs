@@ -4,6 +4,7 @@
 
 use super::*;
 use crate::{
+    application::reputation::PeerMisbehavior,
     peer::DisconnectReason,
     peer_manager::{conn_notifs_channel, ConnectionNotification, ConnectionRequest},
     transport::ConnectionMetadata,
@@ -20,7 +21,7 @@ use aptos_types::{account_address::AccountAddress, network_address::NetworkAddre
 use futures::{executor::block_on, future, SinkExt};
 use maplit::{hashmap, hashset};
 use rand::{rngs::StdRng, SeedableRng};
-use std::{io, str::FromStr};
+use std::{collections::HashSet, io, str::FromStr};
 use tokio_retry::strategy::FixedInterval;
 
 const MAX_TEST_CONNECTIONS: usize = 3;
@@ -909,6 +910,90 @@ async fn test_stale_peers_vfn_inbound() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_misbehaving_peers_disconnected() {
+    // Create a connectivity manager with mutual authentication disabled
+    let (mut mock, mut connectivity_manager) = TestHarness::new(HashMap::new());
+    connectivity_manager.mutual_authentication = false;
+
+    // Create and connect peers 1 and 2 (unknown inbound connections)
+    let network_context = mock.network_context;
+    let mut connection_metadata = vec![];
+    for _ in 0..2 {
+        let metadata = ConnectionMetadata::mock_with_role_and_origin(
+            PeerId::random(),
+            PeerRole::Unknown,
+            ConnectionOrigin::Inbound,
+        );
+        let connection_notification =
+            ConnectionNotification::NewPeer(metadata.clone(), network_context);
+        connectivity_manager.handle_control_notification(connection_notification);
+        connection_metadata.push(metadata);
+    }
+    assert_eq!(connectivity_manager.get_connected_peers().len(), 2);
+
+    // Report peer 1 for misbehavior until it should be disconnected
+    let peer_network_id_1 = PeerNetworkId::new(
+        network_context.network_id(),
+        connection_metadata[0].remote_peer_id,
+    );
+    while !mock
+        .peers_and_metadata
+        .should_disconnect_peer(&peer_network_id_1)
+    {
+        mock.peers_and_metadata
+            .report_peer_misbehavior(peer_network_id_1, PeerMisbehavior::InvalidStateSyncData);
+    }
+
+    // Close the misbehaving connections and verify that only peer 1 is disconnected
+    tokio::join!(
+        connectivity_manager.close_misbehaving_connections(),
+        mock.expect_disconnect_fail(
+            peer_network_id_1.peer_id(),
+            connection_metadata[0].addr.clone()
+        )
+    );
+    assert!(mock.connection_reqs_rx.next().now_or_never().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_low_reputation_peers_dialed() {
+    // Create a connectivity manager with two seed peers
+    let (peer_id_1, peer_1, _, _) = test_peer(AccountAddress::ONE);
+    let (peer_id_2, peer_2, _, _) = test_peer(AccountAddress::TWO);
+    let seeds = hashmap! {peer_id_1 => peer_1, peer_id_2 => peer_2};
+    let (mock, mut connectivity_manager) = TestHarness::new(seeds);
+
+    // Report peer 1 for misbehavior until it has a low reputation
+    let peer_network_id_1 = PeerNetworkId::new(mock.network_context.network_id(), peer_id_1);
+    while !mock
+        .peers_and_metadata
+        .has_low_reputation(&peer_network_id_1)
+    {
+        mock.peers_and_metadata
+            .report_peer_misbehavior(peer_network_id_1, PeerMisbehavior::InvalidConsensusMessage);
+    }
+
+    // Verify that both peers are chosen (the network is mutually authenticated)
+    let peers_to_dial: HashSet<_> = connectivity_manager
+        .choose_peers_to_dial()
+        .await
+        .into_iter()
+        .map(|(peer_id, _)| peer_id)
+        .collect();
+    assert_eq!(peers_to_dial, hashset! {peer_id_1, peer_id_2});
+
+    // Disable mutual authentication and verify that only peer 2 is chosen
+    connectivity_manager.mutual_authentication = false;
+    let peers_to_dial: Vec<_> = connectivity_manager
+        .choose_peers_to_dial()
+        .await
+        .into_iter()
+        .map(|(peer_id, _)| peer_id)
+        .collect();
+    assert_eq!(peers_to_dial, vec![peer_id_2]);
+}
+
 /// Verifies that the trusted peers match the expected set
 fn verify_trusted_peers(
     peers_and_metadata: &Arc<PeersAndMetadata>,
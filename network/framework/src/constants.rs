@@ -14,6 +14,12 @@ pub const INBOUND_RPC_TIMEOUT_MS: u64 = 10_000;
 pub const MAX_CONCURRENT_OUTBOUND_RPCS: u32 = 100;
 /// Limit on concurrent Inbound RPC requests before backpressure is applied
 pub const MAX_CONCURRENT_INBOUND_RPCS: u32 = 100;
+/// Limit on the number of response frames in a single streaming RPC. Also used as
+/// the buffer size for frames awaiting delivery to the application.
+pub const MAX_RPC_STREAM_FRAMES: u32 = 256;
+/// Limit on the size of the message in an application error sent to a remote
+/// peer. Longer messages are truncated, so that error responses are never streamed.
+pub const MAX_RPC_ERROR_MESSAGE_SIZE: usize = 1024; /* 1 KiB */
 
 // These are only used in tests
 // TODO: Fix this so the tests and the defaults in config are the same
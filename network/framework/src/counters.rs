@@ -2,8 +2,8 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::protocols::wire::handshake::v1::ProtocolId;
-use aptos_config::network_id::NetworkContext;
+use crate::{application::reputation::PeerMisbehavior, protocols::wire::handshake::v1::ProtocolId};
+use aptos_config::network_id::{NetworkContext, NetworkId};
 use aptos_metrics_core::{
     exponential_buckets, register_histogram_vec, register_int_counter_vec, register_int_gauge,
     register_int_gauge_vec, Histogram, HistogramTimer, HistogramVec, IntCounter, IntCounterVec,
@@ -26,6 +26,7 @@ pub const RECEIVED_LABEL: &str = "received";
 pub const SENT_LABEL: &str = "sent";
 pub const SUCCEEDED_LABEL: &str = "succeeded";
 pub const FAILED_LABEL: &str = "failed";
+pub const RATE_LIMITED_LABEL: &str = "rate_limited";
 
 // Direction labels
 pub const INBOUND_LABEL: &str = "inbound";
@@ -39,6 +40,11 @@ const PRE_DIAL_LABEL: &str = "pre_dial";
 pub const SERIALIZATION_LABEL: &str = "serialization";
 pub const DESERIALIZATION_LABEL: &str = "deserialization";
 
+// Compression labels
+pub const RAW_BYTES_LABEL: &str = "raw_bytes";
+pub const COMPRESSED_BYTES_LABEL: &str = "compressed_bytes";
+pub const UNCOMPRESSED_BYTES_LABEL: &str = "uncompressed_bytes";
+
 pub static APTOS_CONNECTIONS: Lazy<IntGaugeVec> = Lazy::new(|| {
     register_int_gauge_vec!(
         "aptos_connections",
@@ -198,6 +204,24 @@ pub fn rpc_messages(
     ])
 }
 
+/// Counters for inbound rpc requests rejected by the rate limiter (by protocol)
+pub static APTOS_NETWORK_RPC_RATE_LIMITED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_rpc_rate_limited",
+        "Number of inbound RPC requests rejected by the rate limiter",
+        &["role_type", "network_id", "protocol_id"]
+    )
+    .unwrap()
+});
+
+pub fn rpc_rate_limited(network_context: &NetworkContext, protocol_id: ProtocolId) -> IntCounter {
+    APTOS_NETWORK_RPC_RATE_LIMITED.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        protocol_id.as_str(),
+    ])
+}
+
 pub static APTOS_NETWORK_RPC_BYTES: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
         "aptos_network_rpc_bytes",
@@ -239,6 +263,22 @@ pub static INVALID_NETWORK_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static PEER_MISBEHAVIOR_REPORTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_misbehavior_reports",
+        "Number of misbehavior reports for peers (by applications)",
+        &["network_id", "misbehavior"]
+    )
+    .unwrap()
+});
+
+pub fn peer_misbehavior_reports(
+    network_id: &NetworkId,
+    misbehavior: PeerMisbehavior,
+) -> IntCounter {
+    PEER_MISBEHAVIOR_REPORTS.with_label_values(&[network_id.as_str(), misbehavior.get_label()])
+}
+
 pub static PEER_SEND_FAILURES: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
         "aptos_network_peer_send_failures",
@@ -602,6 +642,25 @@ pub fn start_serialization_timer(protocol_id: ProtocolId, operation: &str) -> Hi
         .start_timer()
 }
 
+/// Counters for tracking the on-the-wire compression ratio of each protocol.
+/// Messages sent without compression (e.g., because they are below the
+/// compression threshold) are tracked using the uncompressed bytes label.
+pub static NETWORK_COMPRESSION_BYTE_COUNTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_compression_byte_count",
+        "Counters for tracking the on-the-wire compression ratio",
+        &["protocol_id", "data_type"]
+    )
+    .unwrap()
+});
+
+/// Increments the compression byte count for the given protocol and data type
+pub fn update_compression_byte_count(protocol_id: ProtocolId, data_type: &str, num_bytes: usize) {
+    NETWORK_COMPRESSION_BYTE_COUNTS
+        .with_label_values(&[protocol_id.as_str(), data_type])
+        .inc_by(num_bytes as u64)
+}
+
 /// Counters related to peer ping times (before and after dialing)
 pub static NETWORK_PEER_PING_TIMES: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
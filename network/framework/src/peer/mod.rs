@@ -21,21 +21,28 @@ use crate::{
         FAILED_LABEL, RECEIVED_LABEL, SENT_LABEL,
     },
     logging::NetworkSchema,
+    peer::outbound_queue::OutboundQueue,
     peer_manager::{PeerManagerError, TransportNotification},
     protocols::{
         direct_send::Message,
-        rpc::{error::RpcError, InboundRpcRequest, InboundRpcs, OutboundRpcRequest, OutboundRpcs},
+        rpc::{
+            error::RpcError, InboundRpcRequest, InboundRpcStreamRequest, InboundRpcs,
+            OutboundRpcRequest, OutboundRpcStreamRequest, OutboundRpcs,
+        },
         stream::{InboundStreamBuffer, OutboundStream, StreamMessage},
-        wire::messaging::v1::{
-            DirectSendMsg, ErrorCode, MultiplexMessage, MultiplexMessageSink,
-            MultiplexMessageStream, NetworkMessage, Priority, ReadError, WriteError,
+        wire::{
+            handshake::v1::MessagingCapability,
+            messaging::v1::{
+                DirectSendMsg, ErrorCode, MultiplexMessage, MultiplexMessageSink,
+                MultiplexMessageStream, NetworkMessage, ReadError, WriteError,
+            },
         },
     },
     transport::{self, Connection, ConnectionMetadata},
     ProtocolId,
 };
 use aptos_channels::aptos_channel;
-use aptos_config::network_id::NetworkContext;
+use aptos_config::{config::RpcRateLimitConfig, network_id::NetworkContext};
 use aptos_logger::prelude::*;
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{TimeService, TimeServiceTrait};
@@ -44,8 +51,9 @@ use bytes::Bytes;
 use futures::{
     self,
     channel::oneshot,
+    future::{self, Fuse, FutureExt},
     io::{AsyncRead, AsyncWrite},
-    stream::StreamExt,
+    stream::{FusedStream, StreamExt},
     SinkExt,
 };
 use futures_util::stream::select;
@@ -56,6 +64,7 @@ use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
 };
 
+mod outbound_queue;
 #[cfg(test)]
 mod test;
 
@@ -67,6 +76,8 @@ pub mod fuzzing;
 pub enum PeerRequest {
     /// Send an RPC request to peer.
     SendRpc(OutboundRpcRequest),
+    /// Send a streaming RPC request to peer.
+    SendRpcStream(OutboundRpcStreamRequest),
     /// Fire-and-forget style message send to peer.
     SendDirectSend(Message),
 }
@@ -76,6 +87,8 @@ pub enum PeerRequest {
 pub enum PeerNotification {
     /// A new RPC request has been received from peer.
     RecvRpc(InboundRpcRequest),
+    /// A new streaming RPC request has been received from peer.
+    RecvRpcStream(InboundRpcStreamRequest),
     /// A new message has been received from peer.
     RecvMessage(Message),
 }
@@ -107,6 +120,21 @@ enum State {
     ShuttingDown(DisconnectReason),
 }
 
+/// The maximum number of outbound messages waiting to be scheduled onto the wire
+const MAX_PENDING_OUTBOUND_MESSAGES: usize = 1024;
+/// The maximum number of scheduled messages waiting to be written to the wire
+const MAX_PENDING_WRITER_MESSAGES: usize = 16;
+
+/// Events handled by the multiplex task of a `Peer`'s writer
+enum MultiplexEvent {
+    /// A new outbound message (or `None` if the request channel has closed)
+    Message(Option<NetworkMessage>),
+    /// The writer is ready for the next message (or has ended, if `false`)
+    WriterReady(bool),
+    /// The connection is being closed
+    Close,
+}
+
 /// The `Peer` actor manages a single connection to another remote peer after
 /// the initial connection establishment and handshake.
 pub struct Peer<TSocket> {
@@ -158,12 +186,22 @@ where
         max_concurrent_outbound_rpcs: u32,
         max_frame_size: usize,
         max_message_size: usize,
+        inbound_rpc_rate_limit_config: Option<RpcRateLimitConfig>,
     ) -> Self {
         let Connection {
             metadata: connection_metadata,
             socket,
         } = connection;
         let remote_peer_id = connection_metadata.remote_peer_id;
+        let send_request_deadlines = connection_metadata
+            .application_protocols
+            .supports(MessagingCapability::RpcRequestDeadlines);
+        let send_stream_requests = connection_metadata
+            .application_protocols
+            .supports(MessagingCapability::RpcStreams);
+        let send_error_responses = connection_metadata
+            .application_protocols
+            .supports(MessagingCapability::RpcErrorResponses);
         let max_fragments = max_message_size / max_frame_size;
         Self {
             network_context,
@@ -180,12 +218,16 @@ where
                 remote_peer_id,
                 inbound_rpc_timeout,
                 max_concurrent_inbound_rpcs,
+                inbound_rpc_rate_limit_config,
+                send_error_responses,
             ),
             outbound_rpcs: OutboundRpcs::new(
                 network_context,
                 time_service,
                 remote_peer_id,
                 max_concurrent_outbound_rpcs,
+                send_request_deadlines,
+                send_stream_requests,
             ),
             state: State::Connected,
             max_frame_size,
@@ -198,6 +240,26 @@ where
         self.connection_metadata.remote_peer_id
     }
 
+    /// Logs an error in handling an inbound rpc request. Rate limited requests
+    /// are expected when a peer floods us (and are tracked by the rate limiting
+    /// counters), so they are only logged periodically.
+    fn log_inbound_rpc_error(&self, request_type: &str, error: &RpcError) {
+        let network_schema = NetworkSchema::new(&self.network_context)
+            .connection_metadata(&self.connection_metadata);
+        let error_string = format!(
+            "{} Error handling inbound {}: {}",
+            self.network_context, request_type, error
+        );
+        if let RpcError::RateLimited { .. } = error {
+            sample!(
+                SampleRate::Duration(Duration::from_secs(10)),
+                warn!(network_schema, error = %error, "{}", error_string)
+            );
+        } else {
+            warn!(network_schema, error = %error, "{}", error_string);
+        }
+    }
+
     pub async fn start(mut self) {
         let remote_peer_id = self.remote_peer_id();
         trace!(
@@ -272,7 +334,7 @@ where
                 maybe_response = self.inbound_rpcs.next_completed_response() => {
                     // Extract the relevant metadata from the message
                     let message_metadata = match &maybe_response {
-                        Ok((response, protocol_id)) => Some((response.request_id, *protocol_id)),
+                        Ok((request_id, _, protocol_id)) => Some((*request_id, *protocol_id)),
                         _ => None,
                     };
 
@@ -306,6 +368,20 @@ where
                 // successfully or unsuccessfully completed request.
                 (request_id, maybe_completed_request) = self.outbound_rpcs.next_completed_request() => {
                     self.outbound_rpcs.handle_completed_request(request_id, maybe_completed_request);
+                },
+                // Drive the queue of pending inbound rpc streams. Their frames
+                // are written as they are produced, so there is only cleanup left.
+                maybe_stream = self.inbound_rpcs.next_completed_stream() => {
+                    if let Err(error) = self.inbound_rpcs.handle_completed_stream(maybe_stream) {
+                        warn!(
+                            NetworkSchema::new(&self.network_context)
+                                .connection_metadata(&self.connection_metadata),
+                            error = %error,
+                            "{} Error in handling inbound rpc stream request, error: {}",
+                            self.network_context,
+                            error
+                        );
+                    }
                 }
             }
         };
@@ -322,6 +398,9 @@ where
     // 2. The second channel is used to instruct the task to close the connection and terminate.
     // If outbound messages are queued when the task receives a close instruction, it discards
     // them and immediately closes the connection.
+    //
+    // Outbound messages are scheduled onto the wire by priority (see `OutboundQueue`), so that
+    // bulk traffic doesn't delay latency sensitive traffic when the connection is saturated.
     fn start_writer_task(
         executor: &Handle,
         time_service: TimeService,
@@ -336,7 +415,12 @@ where
             aptos_channels::new(1024, &counters::PENDING_WIRE_MESSAGES);
         let (close_tx, mut close_rx) = oneshot::channel();
 
-        let (mut msg_tx, msg_rx) = aptos_channels::new(1024, &counters::PENDING_MULTIPLEX_MESSAGE);
+        // Keep the writer queue short so that messages wait in the outbound queue instead,
+        // where they can be prioritized.
+        let (mut msg_tx, msg_rx) = aptos_channels::new(
+            MAX_PENDING_WRITER_MESSAGES,
+            &counters::PENDING_MULTIPLEX_MESSAGE,
+        );
         let (stream_msg_tx, stream_msg_rx) =
             aptos_channels::new(1024, &counters::PENDING_MULTIPLEX_STREAM);
 
@@ -402,14 +486,44 @@ where
         let multiplex_task = async move {
             let mut outbound_stream =
                 OutboundStream::new(max_frame_size, max_message_size, stream_msg_tx);
+            let mut outbound_queue = OutboundQueue::new(MAX_PENDING_OUTBOUND_MESSAGES);
             loop {
-                futures::select! {
-                    message = write_reqs_rx.select_next_some() => {
+                let event = {
+                    // Only accept new messages while there is room in the outbound queue
+                    let mut next_message =
+                        if outbound_queue.is_full() || write_reqs_rx.is_terminated() {
+                            Fuse::terminated()
+                        } else {
+                            write_reqs_rx.next().fuse()
+                        };
+                    // Only wait for the writer if there is something to write
+                    let mut writer_ready = if outbound_queue.is_empty() {
+                        Fuse::terminated()
+                    } else {
+                        future::poll_fn(|cx| msg_tx.poll_ready_unpin(cx)).fuse()
+                    };
+                    futures::select! {
+                        message = next_message => MultiplexEvent::Message(message),
+                        result = writer_ready => MultiplexEvent::WriterReady(result.is_ok()),
+                        _ = close_rx => MultiplexEvent::Close,
+                    }
+                };
+
+                match event {
+                    MultiplexEvent::Message(Some(message)) => outbound_queue.push(message),
+                    MultiplexEvent::Message(None) => {},
+                    MultiplexEvent::WriterReady(true) => {
+                        let message = outbound_queue
+                            .pop()
+                            .expect("Outbound queue should not be empty");
                         // either channel full would block the other one
                         let result = if outbound_stream.should_stream(&message) {
                             outbound_stream.stream_message(message).await
                         } else {
-                            msg_tx.send(MultiplexMessage::Message(message)).await.map_err(|_| anyhow::anyhow!("Writer task ended"))
+                            msg_tx
+                                .send(MultiplexMessage::Message(message))
+                                .await
+                                .map_err(|_| anyhow::anyhow!("Writer task ended"))
                         };
                         if let Err(err) = result {
                             warn!(
@@ -420,9 +534,16 @@ where
                             );
                         }
                     },
-                    _ = close_rx => {
-                        break;
-                    }
+                    MultiplexEvent::WriterReady(false) => {
+                        // The writer task has ended, so nothing can be sent anymore
+                        warn!(
+                            "{} Writer task ended, dropping pending messages to peer: {}",
+                            network_context,
+                            remote_peer_id.short_str(),
+                        );
+                        outbound_queue.clear();
+                    },
+                    MultiplexEvent::Close => break,
                 }
             }
         };
@@ -434,6 +555,7 @@ where
     async fn handle_inbound_network_message(
         &mut self,
         message: NetworkMessage,
+        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
     ) -> Result<(), PeerManagerError> {
         match message {
             NetworkMessage::DirectSendMsg(message) => self.handle_inbound_direct_send(message),
@@ -449,23 +571,42 @@ where
                 );
             },
             NetworkMessage::RpcRequest(request) => {
-                if let Err(err) = self
-                    .inbound_rpcs
-                    .handle_inbound_request(&mut self.peer_notifs_tx, request)
-                {
-                    warn!(
-                        NetworkSchema::new(&self.network_context)
-                            .connection_metadata(&self.connection_metadata),
-                        error = %err,
-                        "{} Error handling inbound rpc request: {}",
-                        self.network_context,
-                        err
-                    );
+                if let Err(err) = self.inbound_rpcs.handle_inbound_request(
+                    &mut self.peer_notifs_tx,
+                    request,
+                    None,
+                ) {
+                    self.log_inbound_rpc_error("rpc request", &err);
+                }
+            },
+            NetworkMessage::RpcRequestWithDeadline(request) => {
+                let remaining_time = request.remaining_time();
+                if let Err(err) = self.inbound_rpcs.handle_inbound_request(
+                    &mut self.peer_notifs_tx,
+                    request.request,
+                    Some(remaining_time),
+                ) {
+                    self.log_inbound_rpc_error("rpc request", &err);
                 }
             },
             NetworkMessage::RpcResponse(response) => {
                 self.outbound_rpcs.handle_inbound_response(response)
             },
+            NetworkMessage::RpcStreamRequest(request) => {
+                if let Err(err) = self.inbound_rpcs.handle_inbound_stream_request(
+                    &mut self.peer_notifs_tx,
+                    write_reqs_tx.clone(),
+                    request,
+                ) {
+                    self.log_inbound_rpc_error("rpc stream request", &err);
+                }
+            },
+            NetworkMessage::RpcStreamResponse(response) => {
+                self.outbound_rpcs.handle_inbound_stream_response(response)
+            },
+            NetworkMessage::RpcErrorResponse(response) => {
+                self.outbound_rpcs.handle_inbound_error_response(response)
+            },
         };
         Ok(())
     }
@@ -473,6 +614,7 @@ where
     async fn handle_inbound_stream_message(
         &mut self,
         message: StreamMessage,
+        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
     ) -> Result<(), PeerManagerError> {
         match message {
             StreamMessage::Header(header) => {
@@ -480,7 +622,8 @@ where
             },
             StreamMessage::Fragment(fragment) => {
                 if let Some(message) = self.inbound_stream.append_fragment(fragment)? {
-                    self.handle_inbound_network_message(message).await?;
+                    self.handle_inbound_network_message(message, write_reqs_tx)
+                        .await?;
                 }
             },
         }
@@ -525,9 +668,13 @@ where
 
         match message {
             MultiplexMessage::Message(message) => {
-                self.handle_inbound_network_message(message).await
+                self.handle_inbound_network_message(message, write_reqs_tx)
+                    .await
+            },
+            MultiplexMessage::Stream(message) => {
+                self.handle_inbound_stream_message(message, write_reqs_tx)
+                    .await
             },
-            MultiplexMessage::Stream(message) => self.handle_inbound_stream_message(message).await,
         }
     }
 
@@ -594,7 +741,7 @@ where
                 let protocol_id = message.protocol_id;
                 let message = NetworkMessage::DirectSendMsg(DirectSendMsg {
                     protocol_id,
-                    priority: Priority::default(),
+                    priority: protocol_id.priority(),
                     raw_msg: Vec::from(message.mdata.as_ref()),
                 });
 
@@ -634,6 +781,24 @@ where
                     );
                 }
             },
+            PeerRequest::SendRpcStream(request) => {
+                let protocol_id = request.protocol_id;
+                if let Err(e) = self
+                    .outbound_rpcs
+                    .handle_outbound_stream_request(request, write_reqs_tx)
+                    .await
+                {
+                    warn!(
+                        NetworkSchema::new(&self.network_context)
+                            .connection_metadata(&self.connection_metadata),
+                        error = %e,
+                        "Failed to send outbound rpc stream request for protocol {} to peer: {}. Error: {}",
+                        protocol_id,
+                        self.remote_peer_id().short_str(),
+                        e,
+                    );
+                }
+            },
         }
     }
 
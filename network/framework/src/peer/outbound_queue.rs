@@ -0,0 +1,192 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A weighted fair queue for outbound [`NetworkMessage`]s.
+//!
+//! Messages are bucketed into priority classes (see [`ProtocolId::priority`])
+//! and dequeued using deficit round robin, where each class receives a share of
+//! the outbound bandwidth proportional to its weight. This ensures that bulk
+//! traffic (e.g., state sync chunks) cannot starve latency sensitive traffic
+//! (e.g., consensus votes) when the connection is saturated, while still making
+//! progress on the bulk traffic.
+//!
+//! [`ProtocolId::priority`]: crate::ProtocolId::priority
+
+use crate::protocols::wire::messaging::v1::{
+    NetworkMessage, Priority, HIGH_PRIORITY, NORMAL_PRIORITY,
+};
+use std::collections::VecDeque;
+
+/// The number of priority classes
+const NUM_CLASSES: usize = 3;
+/// The relative bandwidth share of each class (high, normal, low)
+const CLASS_WEIGHTS: [usize; NUM_CLASSES] = [4, 2, 1];
+/// The number of bytes a class with weight 1 may send per round
+const QUANTUM_BYTES: usize = 256 * 1024; /* 256 KiB */
+
+/// Returns the class index for the given priority (lower index is served more)
+fn priority_class(priority: Priority) -> usize {
+    if priority >= HIGH_PRIORITY {
+        0
+    } else if priority >= NORMAL_PRIORITY {
+        1
+    } else {
+        2
+    }
+}
+
+pub struct OutboundQueue {
+    /// The pending messages of each priority class
+    queues: [VecDeque<NetworkMessage>; NUM_CLASSES],
+    /// The number of bytes each class may still send in the current round
+    deficits: [usize; NUM_CLASSES],
+    /// The class currently being served
+    current_class: usize,
+    /// Whether the current class has already been credited its quantum
+    current_class_credited: bool,
+    /// The total number of pending messages
+    len: usize,
+    /// The maximum number of pending messages
+    max_len: usize,
+}
+
+impl OutboundQueue {
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            queues: Default::default(),
+            deficits: [0; NUM_CLASSES],
+            current_class: 0,
+            current_class_credited: false,
+            len: 0,
+            max_len,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len >= self.max_len
+    }
+
+    /// Enqueues the message according to its priority
+    pub fn push(&mut self, message: NetworkMessage) {
+        let class = priority_class(message.priority());
+        self.queues[class].push_back(message);
+        self.len += 1;
+    }
+
+    /// Dequeues the next message to send, if any
+    pub fn pop(&mut self) -> Option<NetworkMessage> {
+        if self.is_empty() {
+            return None;
+        }
+
+        loop {
+            let class = self.current_class;
+            match self.queues[class].front() {
+                Some(message) => {
+                    if !self.current_class_credited {
+                        self.deficits[class] += CLASS_WEIGHTS[class] * QUANTUM_BYTES;
+                        self.current_class_credited = true;
+                    }
+                    let message_len = message.data_len();
+                    if self.deficits[class] >= message_len {
+                        self.deficits[class] -= message_len;
+                        self.len -= 1;
+                        return self.queues[class].pop_front();
+                    }
+                },
+                // Idle classes don't accumulate credit
+                None => self.deficits[class] = 0,
+            }
+
+            // Move on to the next class
+            self.current_class = (class + 1) % NUM_CLASSES;
+            self.current_class_credited = false;
+        }
+    }
+
+    /// Drops all pending messages
+    pub fn clear(&mut self) {
+        for queue in self.queues.iter_mut() {
+            queue.clear();
+        }
+        self.deficits = [0; NUM_CLASSES];
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocols::wire::messaging::v1::{DirectSendMsg, LOW_PRIORITY},
+        ProtocolId,
+    };
+
+    fn message(priority: Priority, len: usize) -> NetworkMessage {
+        NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id: ProtocolId::MempoolDirectSend,
+            priority,
+            raw_msg: vec![0; len],
+        })
+    }
+
+    #[test]
+    fn test_fifo_within_class() {
+        let mut queue = OutboundQueue::new(10);
+        for len in 1..=3 {
+            queue.push(message(NORMAL_PRIORITY, len));
+        }
+        for len in 1..=3 {
+            assert_eq!(queue.pop().unwrap().data_len(), len);
+        }
+        assert!(queue.is_empty());
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn test_weighted_bandwidth_share() {
+        let mut queue = OutboundQueue::new(1000);
+        for _ in 0..100 {
+            queue.push(message(LOW_PRIORITY, QUANTUM_BYTES));
+            queue.push(message(HIGH_PRIORITY, QUANTUM_BYTES));
+        }
+
+        // Within a round, the high priority class sends 4x more than the low
+        let priorities: Vec<_> = (0..10).map(|_| queue.pop().unwrap().priority()).collect();
+        let num_high = priorities.iter().filter(|p| **p == HIGH_PRIORITY).count();
+        assert_eq!(num_high, 8);
+        assert_eq!(priorities[0], HIGH_PRIORITY);
+    }
+
+    #[test]
+    fn test_low_priority_is_not_starved() {
+        let mut queue = OutboundQueue::new(1000);
+        queue.push(message(LOW_PRIORITY, 4 * QUANTUM_BYTES));
+        for _ in 0..100 {
+            queue.push(message(HIGH_PRIORITY, QUANTUM_BYTES));
+        }
+
+        // The large low priority message is sent after a few rounds
+        let position = (0..101)
+            .position(|_| queue.pop().unwrap().priority() == LOW_PRIORITY)
+            .unwrap();
+        assert!(position < 100);
+        assert_eq!(queue.len, 101 - position - 1);
+    }
+
+    #[test]
+    fn test_full_and_clear() {
+        let mut queue = OutboundQueue::new(2);
+        queue.push(message(HIGH_PRIORITY, 1));
+        assert!(!queue.is_full());
+        queue.push(message(LOW_PRIORITY, 1));
+        assert!(queue.is_full());
+        queue.clear();
+        assert!(queue.is_empty());
+        assert!(queue.pop().is_none());
+    }
+}
@@ -11,12 +11,17 @@ use crate::{
     peer_manager::TransportNotification,
     protocols::{
         direct_send::Message,
-        rpc::{error::RpcError, InboundRpcRequest, OutboundRpcRequest},
+        rpc::{
+            error::{RpcApplicationError, RpcError},
+            InboundRpcRequest, InboundRpcStreamRequest, OutboundRpcRequest,
+            OutboundRpcStreamRequest,
+        },
         wire::{
-            handshake::v1::{MessagingProtocolVersion, ProtocolIdSet},
+            handshake::v1::{MessagingCapability, MessagingProtocolVersion, ProtocolIdSet},
             messaging::v1::{
                 DirectSendMsg, MultiplexMessage, MultiplexMessageSink, MultiplexMessageStream,
-                NetworkMessage, RpcRequest, RpcResponse,
+                NetworkMessage, RpcErrorResponse, RpcRequest, RpcRequestWithDeadline, RpcResponse,
+                RpcStreamResponse, RpcStreamStatus,
             },
         },
     },
@@ -24,14 +29,17 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::PeerRole, network_id::NetworkContext};
+use aptos_config::{
+    config::{PeerRole, RpcRateLimitConfig},
+    network_id::NetworkContext,
+};
 use aptos_memsocket::MemorySocket;
 use aptos_netcore::transport::ConnectionOrigin;
 use aptos_time_service::{MockTimeService, TimeService};
 use aptos_types::{network_address::NetworkAddress, PeerId};
 use bytes::Bytes;
 use futures::{
-    channel::oneshot,
+    channel::{mpsc, oneshot},
     future::{self, FutureExt},
     io::{AsyncRead, AsyncWrite, AsyncWriteExt},
     stream::{StreamExt, TryStreamExt},
@@ -55,6 +63,22 @@ fn build_test_peer(
     MemorySocket,
     aptos_channels::Receiver<TransportNotification<MemorySocket>>,
     aptos_channel::Receiver<ProtocolId, PeerNotification>,
+) {
+    build_test_peer_with_options(executor, time_service, origin, ProtocolIdSet::empty(), None)
+}
+
+fn build_test_peer_with_options(
+    executor: Handle,
+    time_service: TimeService,
+    origin: ConnectionOrigin,
+    application_protocols: ProtocolIdSet,
+    inbound_rpc_rate_limit_config: Option<RpcRateLimitConfig>,
+) -> (
+    Peer<MemorySocket>,
+    PeerHandle,
+    MemorySocket,
+    aptos_channels::Receiver<TransportNotification<MemorySocket>>,
+    aptos_channel::Receiver<ProtocolId, PeerNotification>,
 ) {
     let (a, b) = MemorySocket::new_pair();
     let peer_id = PeerId::random();
@@ -65,7 +89,7 @@ fn build_test_peer(
             NetworkAddress::from_str("/ip4/127.0.0.1/tcp/8081").unwrap(),
             origin,
             MessagingProtocolVersion::V1,
-            ProtocolIdSet::empty(),
+            application_protocols,
             PeerRole::Unknown,
         ),
         socket: a,
@@ -90,6 +114,7 @@ fn build_test_peer(
         MAX_CONCURRENT_OUTBOUND_RPCS,
         MAX_FRAME_SIZE,
         MAX_MESSAGE_SIZE,
+        inbound_rpc_rate_limit_config,
     );
     let peer_handle = PeerHandle(peer_reqs_tx);
 
@@ -193,6 +218,25 @@ impl PeerHandle {
         let response_data = res_rx.await??;
         Ok(response_data)
     }
+
+    fn send_rpc_stream_request(
+        &mut self,
+        protocol_id: ProtocolId,
+        data: Bytes,
+        timeout: Duration,
+    ) -> mpsc::Receiver<Result<Bytes, RpcError>> {
+        let (res_tx, res_rx) = mpsc::channel(16);
+        let request = OutboundRpcStreamRequest {
+            protocol_id,
+            data,
+            res_tx,
+            timeout,
+        };
+        self.0
+            .push(protocol_id, PeerRequest::SendRpcStream(request))
+            .unwrap();
+        res_rx
+    }
 }
 
 // Sending an outbound DirectSend should write it to the wire.
@@ -214,7 +258,7 @@ fn peer_send_message() {
     };
     let recv_msg = MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
         protocol_id: PROTOCOL,
-        priority: 0,
+        priority: PROTOCOL.priority(),
         raw_msg: Vec::from("hello world"),
     }));
 
@@ -395,6 +439,255 @@ fn peer_recv_rpc() {
     rt.block_on(future::join3(peer.start(), server, client));
 }
 
+#[test]
+fn peer_recv_rpc_application_error() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    // The remote peer supports rpc error responses
+    let mut application_protocols = ProtocolIdSet::empty();
+    application_protocols.insert_capability(MessagingCapability::RpcErrorResponses);
+    let (peer, _peer_handle, mut connection, _connection_notifs_rx, mut peer_notifs_rx) =
+        build_test_peer_with_options(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+            application_protocols,
+            None,
+        );
+    let (mut client_sink, mut client_stream) = build_network_sink_stream(&mut connection);
+
+    let send_msg = MultiplexMessage::Message(NetworkMessage::RpcRequest(RpcRequest {
+        request_id: 123,
+        protocol_id: PROTOCOL,
+        priority: 0,
+        raw_request: Vec::from("hello world"),
+    }));
+    let error = RpcApplicationError::new(7, true, "try again later");
+    let resp_msg = MultiplexMessage::Message(NetworkMessage::RpcErrorResponse(RpcErrorResponse {
+        request_id: 123,
+        priority: 0,
+        error: error.clone(),
+    }));
+
+    let client = async move {
+        // Client should send the rpc request.
+        client_sink.send(&send_msg).await.unwrap();
+        // Client should then receive the application error.
+        let received = client_stream.next().await.unwrap().unwrap();
+        assert_eq!(received, resp_msg);
+        // Client then closes connection.
+        client_sink.close().await.unwrap();
+    };
+    let server = async move {
+        // Wait to receive RpcRequest from Peer and respond with an error.
+        match peer_notifs_rx.next().await.unwrap() {
+            PeerNotification::RecvRpc(req) => req.res_tx.send(Err(error.into())).unwrap(),
+            received => panic!("Unexpected PeerNotification: {:?}", received),
+        }
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
+#[test]
+fn peer_recv_rpc_application_error_without_capability() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    // The remote peer doesn't support rpc error responses
+    let (peer, _peer_handle, mut connection, _connection_notifs_rx, mut peer_notifs_rx) =
+        build_test_peer(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+        );
+    let (mut client_sink, mut client_stream) = build_network_sink_stream(&mut connection);
+
+    let request = |request_id| {
+        MultiplexMessage::Message(NetworkMessage::RpcRequest(RpcRequest {
+            request_id,
+            protocol_id: PROTOCOL,
+            priority: 0,
+            raw_request: Vec::from("hello world"),
+        }))
+    };
+    let resp_msg = MultiplexMessage::Message(NetworkMessage::RpcResponse(RpcResponse {
+        request_id: 2,
+        priority: 0,
+        raw_response: Vec::from("goodbye world"),
+    }));
+
+    let client = async move {
+        // Client sends two rpc requests.
+        client_sink.send(&request(1)).await.unwrap();
+        client_sink.send(&request(2)).await.unwrap();
+        // Client should only receive the response to the second request, as
+        // the application error for the first one is dropped.
+        let received = client_stream.next().await.unwrap().unwrap();
+        assert_eq!(received, resp_msg);
+        // Client then closes connection.
+        client_sink.close().await.unwrap();
+    };
+    let server = async move {
+        // Fail the first request with an application error.
+        match peer_notifs_rx.next().await.unwrap() {
+            PeerNotification::RecvRpc(req) => {
+                let error = RpcApplicationError::new(7, true, "try again later");
+                req.res_tx.send(Err(error.into())).unwrap()
+            },
+            received => panic!("Unexpected PeerNotification: {:?}", received),
+        }
+        // Respond to the second request.
+        match peer_notifs_rx.next().await.unwrap() {
+            PeerNotification::RecvRpc(req) => {
+                req.res_tx.send(Ok(Bytes::from("goodbye world"))).unwrap()
+            },
+            received => panic!("Unexpected PeerNotification: {:?}", received),
+        }
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
+#[test]
+fn peer_recv_rpc_rate_limited() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let rate_limit_config = RpcRateLimitConfig {
+        request_bucket_rate: 1,
+        request_bucket_size: 1,
+        enabled: true,
+    };
+    // The remote peer supports rpc error responses
+    let mut application_protocols = ProtocolIdSet::empty();
+    application_protocols.insert_capability(MessagingCapability::RpcErrorResponses);
+    let (peer, _peer_handle, mut connection, _connection_notifs_rx, mut peer_notifs_rx) =
+        build_test_peer_with_options(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+            application_protocols,
+            Some(rate_limit_config),
+        );
+    let (mut client_sink, mut client_stream) = build_network_sink_stream(&mut connection);
+
+    let request = |request_id| {
+        MultiplexMessage::Message(NetworkMessage::RpcRequest(RpcRequest {
+            request_id,
+            protocol_id: PROTOCOL,
+            priority: 0,
+            raw_request: Vec::from("hello world"),
+        }))
+    };
+    let resp_msg = MultiplexMessage::Message(NetworkMessage::RpcResponse(RpcResponse {
+        request_id: 1,
+        priority: 0,
+        raw_response: Vec::from("goodbye world"),
+    }));
+    let rate_limited_msg =
+        MultiplexMessage::Message(NetworkMessage::RpcErrorResponse(RpcErrorResponse {
+            request_id: 2,
+            priority: 0,
+            error: RpcApplicationError::rate_limited(Duration::from_secs(1)),
+        }));
+
+    let client = async move {
+        // Client sends two rpc requests, but only the first is within the rate.
+        client_sink.send(&request(1)).await.unwrap();
+        client_sink.send(&request(2)).await.unwrap();
+        // Client should receive the response to the first request, and a rate
+        // limited error (with the retry time) for the second. The error may
+        // arrive first, as it doesn't wait on the upstream handler.
+        let mut received = vec![
+            client_stream.next().await.unwrap().unwrap(),
+            client_stream.next().await.unwrap().unwrap(),
+        ];
+        if received[0] != resp_msg {
+            received.swap(0, 1);
+        }
+        assert_eq!(received, vec![resp_msg, rate_limited_msg]);
+        // Client then closes connection.
+        client_sink.close().await.unwrap();
+    };
+    let server = async move {
+        // Only the first request is forwarded upstream.
+        match peer_notifs_rx.next().await.unwrap() {
+            PeerNotification::RecvRpc(req) => {
+                req.res_tx.send(Ok(Bytes::from("goodbye world"))).unwrap()
+            },
+            received => panic!("Unexpected PeerNotification: {:?}", received),
+        }
+        assert!(peer_notifs_rx.next().await.is_none());
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
+#[test]
+fn peer_recv_rpc_rate_limited_without_capability() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let rate_limit_config = RpcRateLimitConfig {
+        request_bucket_rate: 1,
+        request_bucket_size: 1,
+        enabled: true,
+    };
+    // The remote peer doesn't support rpc error responses
+    let (peer, _peer_handle, mut connection, _connection_notifs_rx, mut peer_notifs_rx) =
+        build_test_peer_with_options(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+            ProtocolIdSet::empty(),
+            Some(rate_limit_config),
+        );
+    let (mut client_sink, mut client_stream) = build_network_sink_stream(&mut connection);
+
+    let request = |request_id| {
+        MultiplexMessage::Message(NetworkMessage::RpcRequest(RpcRequest {
+            request_id,
+            protocol_id: PROTOCOL,
+            priority: 0,
+            raw_request: Vec::from("hello world"),
+        }))
+    };
+    let direct_send_msg = MultiplexMessage::Message(NetworkMessage::DirectSendMsg(DirectSendMsg {
+        protocol_id: PROTOCOL,
+        priority: 0,
+        raw_msg: Vec::from("hello world"),
+    }));
+    let resp_msg = MultiplexMessage::Message(NetworkMessage::RpcResponse(RpcResponse {
+        request_id: 1,
+        priority: 0,
+        raw_response: Vec::from("goodbye world"),
+    }));
+
+    let client = async move {
+        // Client sends two rpc requests, but only the first is within the rate.
+        // The direct send lets the server know that both have been handled.
+        client_sink.send(&request(1)).await.unwrap();
+        client_sink.send(&request(2)).await.unwrap();
+        client_sink.send(&direct_send_msg).await.unwrap();
+        // Client should only receive the response to the first request, as the
+        // second one is dropped without a rate limited error.
+        let received = client_stream.next().await.unwrap().unwrap();
+        assert_eq!(received, resp_msg);
+        // Client then closes connection.
+        client_sink.close().await.unwrap();
+    };
+    let server = async move {
+        // Only the first request is forwarded upstream, followed by the direct send.
+        let res_tx = match peer_notifs_rx.next().await.unwrap() {
+            PeerNotification::RecvRpc(req) => req.res_tx,
+            received => panic!("Unexpected PeerNotification: {:?}", received),
+        };
+        match peer_notifs_rx.next().await.unwrap() {
+            PeerNotification::RecvMessage(_) => (),
+            received => panic!("Unexpected PeerNotification: {:?}", received),
+        }
+        // Respond to the first request once the second has been rejected.
+        res_tx.send(Ok(Bytes::from("goodbye world"))).unwrap();
+        assert!(peer_notifs_rx.next().await.is_none());
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
 #[test]
 fn peer_recv_rpc_concurrent() {
     ::aptos_logger::Logger::init_for_testing();
@@ -520,6 +813,112 @@ fn peer_recv_rpc_timeout() {
     rt.block_on(future::join(peer.start(), test));
 }
 
+#[test]
+fn peer_recv_rpc_deadline() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let mock_time = MockTimeService::new();
+    let (peer, _peer_handle, mut connection, _connection_notifs_rx, mut peer_notifs_rx) =
+        build_test_peer(
+            rt.handle().clone(),
+            mock_time.clone().into(),
+            ConnectionOrigin::Inbound,
+        );
+    let (mut client_sink, client_stream) = build_network_sink_stream(&mut connection);
+
+    // The client's deadline is shorter than the inbound rpc timeout.
+    let deadline_ms = INBOUND_RPC_TIMEOUT_MS / 10;
+    let request = RpcRequest {
+        request_id: 123,
+        protocol_id: PROTOCOL,
+        priority: 0,
+        raw_request: Vec::from("hello world"),
+    };
+    let send_msg = MultiplexMessage::Message(NetworkMessage::RpcRequestWithDeadline(
+        RpcRequestWithDeadline::new(request, Duration::from_millis(deadline_ms)),
+    ));
+
+    let test = async move {
+        // Client sends the rpc request.
+        client_sink.send(&send_msg).await.unwrap();
+
+        // Server receives the rpc request from client.
+        let mut res_tx = match peer_notifs_rx.next().await.unwrap() {
+            PeerNotification::RecvRpc(req) => req.res_tx,
+            received => panic!("Unexpected PeerNotification: {:?}", received),
+        };
+        assert!(!res_tx.is_canceled());
+
+        // The rpc response channel should be canceled once the client's
+        // deadline elapses, rather than the (longer) inbound rpc timeout.
+        mock_time.advance_ms_async(deadline_ms).await;
+        res_tx.cancellation().await;
+
+        // Client then half-closes write side.
+        client_sink.close().await.unwrap();
+
+        // Client shouldn't have received any messages.
+        let messages = client_stream.try_collect::<Vec<_>>().await.unwrap();
+        assert_eq!(messages, vec![]);
+    };
+    rt.block_on(future::join(peer.start(), test));
+}
+
+#[test]
+fn peer_recv_rpc_expired_deadline() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let (peer, _peer_handle, mut connection, _connection_notifs_rx, mut peer_notifs_rx) =
+        build_test_peer(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+        );
+    let (mut client_sink, client_stream) = build_network_sink_stream(&mut connection);
+
+    let build_request = |request_id, deadline_ms, raw_request: &str| {
+        let request = RpcRequest {
+            request_id,
+            protocol_id: PROTOCOL,
+            priority: 0,
+            raw_request: Vec::from(raw_request),
+        };
+        MultiplexMessage::Message(NetworkMessage::RpcRequestWithDeadline(
+            RpcRequestWithDeadline::new(request, Duration::from_millis(deadline_ms)),
+        ))
+    };
+    let recv_msg = PeerNotification::RecvRpc(InboundRpcRequest {
+        protocol_id: PROTOCOL,
+        data: Bytes::from("still waiting"),
+        res_tx: oneshot::channel().0,
+    });
+
+    let test = async move {
+        // Client sends a request that has already expired, followed by a live one.
+        client_sink
+            .send(&build_request(1, 0, "already expired"))
+            .await
+            .unwrap();
+        client_sink
+            .send(&build_request(2, 1_000, "still waiting"))
+            .await
+            .unwrap();
+
+        // Server only forwards the live request to the application.
+        let received = peer_notifs_rx.next().await.unwrap();
+        assert_eq!(received, recv_msg);
+        drop(received);
+
+        // Client then half-closes write side.
+        client_sink.close().await.unwrap();
+
+        // Client shouldn't have received any messages.
+        let messages = client_stream.try_collect::<Vec<_>>().await.unwrap();
+        assert_eq!(messages, vec![]);
+    };
+    rt.block_on(future::join(peer.start(), test));
+}
+
 #[test]
 fn peer_recv_rpc_cancel() {
     ::aptos_logger::Logger::init_for_testing();
@@ -610,7 +1009,7 @@ fn peer_send_rpc() {
             };
 
             assert_eq!(received.protocol_id, PROTOCOL);
-            assert_eq!(received.priority, 0);
+            assert_eq!(received.priority, PROTOCOL.priority());
             assert_eq!(received.raw_request, b"hello world");
 
             assert!(
@@ -633,6 +1032,49 @@ fn peer_send_rpc() {
     rt.block_on(future::join3(peer.start(), server, client));
 }
 
+#[test]
+fn peer_send_rpc_application_error() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let (peer, mut peer_handle, mut connection, _connection_notifs_rx, _peer_notifs_rx) =
+        build_test_peer(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+        );
+    let (mut server_sink, mut server_stream) = build_network_sink_stream(&mut connection);
+    let timeout = Duration::from_millis(10_000);
+    let error = RpcApplicationError::new(7, false, "invalid request");
+
+    let expected_error = error.clone();
+    let client = async move {
+        // Send RpcRequest to server and expect the application error.
+        let response = peer_handle
+            .send_rpc_request(PROTOCOL, Bytes::from(&b"hello world"[..]), timeout)
+            .await
+            .unwrap_err();
+        assert_eq!(response.application_error(), Some(&expected_error));
+        // Client then closes connection.
+    };
+    let server = async move {
+        // Server should receive the rpc request and respond with an error.
+        let received = server_stream.next().await.unwrap().unwrap();
+        let received = match received {
+            MultiplexMessage::Message(NetworkMessage::RpcRequest(request)) => request,
+            _ => panic!("Expected RpcRequest; unexpected: {:?}", received),
+        };
+        let response =
+            MultiplexMessage::Message(NetworkMessage::RpcErrorResponse(RpcErrorResponse {
+                request_id: received.request_id,
+                priority: 0,
+                error,
+            }));
+        server_sink.send(&response).await.unwrap();
+        assert!(server_stream.next().await.is_none());
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
 #[test]
 fn peer_send_rpc_concurrent() {
     ::aptos_logger::Logger::init_for_testing();
@@ -679,7 +1121,7 @@ fn peer_send_rpc_concurrent() {
             };
 
             assert_eq!(received.protocol_id, PROTOCOL);
-            assert_eq!(received.priority, 0);
+            assert_eq!(received.priority, PROTOCOL.priority());
             assert_eq!(received.raw_request, b"hello world");
 
             assert!(
@@ -734,7 +1176,7 @@ fn peer_send_rpc_cancel() {
         };
 
         assert_eq!(received.protocol_id, PROTOCOL);
-        assert_eq!(received.priority, 0);
+        assert_eq!(received.priority, PROTOCOL.priority());
         assert_eq!(received.raw_request, b"hello world");
 
         // Request should still be live. Ok(_) means the sender is not dropped.
@@ -767,11 +1209,16 @@ fn peer_send_rpc_timeout() {
     ::aptos_logger::Logger::init_for_testing();
     let rt = Runtime::new().unwrap();
     let mock_time = MockTimeService::new();
+    // The remote peer supports rpc deadlines
+    let mut application_protocols = ProtocolIdSet::empty();
+    application_protocols.insert_capability(MessagingCapability::RpcRequestDeadlines);
     let (peer, peer_handle, mut connection, _connection_notifs_rx, _peer_notifs_rx) =
-        build_test_peer(
+        build_test_peer_with_options(
             rt.handle().clone(),
             mock_time.clone().into(),
             ConnectionOrigin::Inbound,
+            application_protocols,
+            None,
         );
     let (mut server_sink, mut server_stream) = build_network_sink_stream(&mut connection);
     let timeout = Duration::from_millis(10_000);
@@ -790,12 +1237,19 @@ fn peer_send_rpc_timeout() {
         // Server receives the rpc request from client.
         let received = server_stream.next().await.unwrap().unwrap();
         let received = match received {
-            MultiplexMessage::Message(NetworkMessage::RpcRequest(request)) => request,
-            _ => panic!("Expected RpcRequest; unexpected: {:?}", received),
+            MultiplexMessage::Message(NetworkMessage::RpcRequestWithDeadline(request)) => {
+                // The request carries the client's timeout as its deadline.
+                assert_eq!(request.remaining_time(), timeout);
+                request.request
+            },
+            _ => panic!(
+                "Expected RpcRequestWithDeadline; unexpected: {:?}",
+                received
+            ),
         };
 
         assert_eq!(received.protocol_id, PROTOCOL);
-        assert_eq!(received.priority, 0);
+        assert_eq!(received.priority, PROTOCOL.priority());
         assert_eq!(received.raw_request, b"hello world");
 
         // Request should still be live. Ok(_) means the sender is not dropped.
@@ -827,6 +1281,208 @@ fn peer_send_rpc_timeout() {
 }
 
 // PeerManager can request a Peer to shutdown.
+#[test]
+fn peer_recv_rpc_stream() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    let (peer, _peer_handle, mut connection, _connection_notifs_rx, mut peer_notifs_rx) =
+        build_test_peer(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+        );
+    let (mut client_sink, mut client_stream) = build_network_sink_stream(&mut connection);
+
+    let send_msg = MultiplexMessage::Message(NetworkMessage::RpcStreamRequest(RpcRequest {
+        request_id: 123,
+        protocol_id: PROTOCOL,
+        priority: 0,
+        raw_request: Vec::from("hello world"),
+    }));
+    let recv_msg = PeerNotification::RecvRpcStream(InboundRpcStreamRequest {
+        protocol_id: PROTOCOL,
+        data: Bytes::from("hello world"),
+        res_tx: mpsc::channel(0).0,
+    });
+    let frames = vec!["goodbye", "cruel", "world"];
+
+    let client = async move {
+        // Client should send the rpc stream request.
+        client_sink.send(&send_msg).await.unwrap();
+
+        // Client should then receive each response frame in order.
+        for (frame_index, frame) in frames.iter().enumerate() {
+            let received = client_stream.next().await.unwrap().unwrap();
+            let expected =
+                MultiplexMessage::Message(NetworkMessage::RpcStreamResponse(RpcStreamResponse {
+                    request_id: 123,
+                    priority: 0,
+                    frame_index: frame_index as u32,
+                    status: RpcStreamStatus::Open,
+                    raw_response: Vec::from(*frame),
+                }));
+            assert_eq!(received, expected);
+        }
+
+        // Client should finally receive the end of the stream.
+        let received = client_stream.next().await.unwrap().unwrap();
+        let expected =
+            MultiplexMessage::Message(NetworkMessage::RpcStreamResponse(RpcStreamResponse {
+                request_id: 123,
+                priority: 0,
+                frame_index: frames.len() as u32,
+                status: RpcStreamStatus::Finished,
+                raw_response: Vec::new(),
+            }));
+        assert_eq!(received, expected);
+
+        // Client then closes connection.
+        client_sink.close().await.unwrap();
+    };
+    let server = async move {
+        // Wait to receive RpcStreamRequest from Peer.
+        let received = peer_notifs_rx.next().await.unwrap();
+        assert_eq!(recv_msg, received);
+
+        // Send the response frames and then finish the stream.
+        match received {
+            PeerNotification::RecvRpcStream(mut req) => {
+                for frame in ["goodbye", "cruel", "world"] {
+                    req.res_tx.send(Ok(Bytes::from(frame))).await.unwrap();
+                }
+            },
+            _ => panic!("Unexpected PeerNotification: {:?}", received),
+        }
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
+#[test]
+fn peer_send_rpc_stream() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    // The remote peer supports rpc streams
+    let mut application_protocols = ProtocolIdSet::empty();
+    application_protocols.insert_capability(MessagingCapability::RpcStreams);
+    let (peer, mut peer_handle, mut connection, _connection_notifs_rx, _peer_notifs_rx) =
+        build_test_peer_with_options(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+            application_protocols,
+            None,
+        );
+    let (mut server_sink, mut server_stream) = build_network_sink_stream(&mut connection);
+    let timeout = Duration::from_millis(10_000);
+
+    let client = async move {
+        // Send RpcStreamRequest to server and collect the finished stream.
+        let frames: Vec<_> = peer_handle
+            .send_rpc_stream_request(PROTOCOL, Bytes::from(&b"hello world"[..]), timeout)
+            .collect()
+            .await;
+        let frames: Vec<_> = frames.into_iter().map(Result::unwrap).collect();
+        assert_eq!(frames, vec![Bytes::from("goodbye"), Bytes::from("world")]);
+
+        // Send another request, which is aborted by the server.
+        let frames: Vec<_> = peer_handle
+            .send_rpc_stream_request(PROTOCOL, Bytes::from(&b"hello world"[..]), timeout)
+            .collect()
+            .await;
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].as_ref().unwrap(), &Bytes::from("goodbye"));
+        assert!(matches!(frames[1], Err(RpcError::StreamClosed(_))));
+        // Client then closes connection.
+    };
+    let server = async move {
+        for final_status in [RpcStreamStatus::Finished, RpcStreamStatus::Aborted] {
+            // Server should receive the expected rpc stream request.
+            let received = server_stream.next().await.unwrap().unwrap();
+            let received = match received {
+                MultiplexMessage::Message(NetworkMessage::RpcStreamRequest(request)) => request,
+                _ => panic!("Expected RpcStreamRequest; unexpected: {:?}", received),
+            };
+            assert_eq!(received.protocol_id, PROTOCOL);
+            assert_eq!(received.raw_request, b"hello world");
+
+            // Server should send the response frames, followed by the final status.
+            let frames: &[&[u8]] = match final_status {
+                RpcStreamStatus::Finished => &[b"goodbye", b"world"],
+                _ => &[b"goodbye"],
+            };
+            for (frame_index, frame) in frames.iter().enumerate() {
+                let response = MultiplexMessage::Message(NetworkMessage::RpcStreamResponse(
+                    RpcStreamResponse {
+                        request_id: received.request_id,
+                        priority: 0,
+                        frame_index: frame_index as u32,
+                        status: RpcStreamStatus::Open,
+                        raw_response: Vec::from(*frame),
+                    },
+                ));
+                server_sink.send(&response).await.unwrap();
+            }
+            let response =
+                MultiplexMessage::Message(NetworkMessage::RpcStreamResponse(RpcStreamResponse {
+                    request_id: received.request_id,
+                    priority: 0,
+                    frame_index: frames.len() as u32,
+                    status: final_status,
+                    raw_response: Vec::new(),
+                }));
+            server_sink.send(&response).await.unwrap();
+        }
+        assert!(server_stream.next().await.is_none());
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
+#[test]
+fn peer_send_rpc_stream_without_capability() {
+    ::aptos_logger::Logger::init_for_testing();
+    let rt = Runtime::new().unwrap();
+    // The remote peer doesn't support rpc streams
+    let (peer, mut peer_handle, mut connection, _connection_notifs_rx, _peer_notifs_rx) =
+        build_test_peer(
+            rt.handle().clone(),
+            TimeService::mock(),
+            ConnectionOrigin::Inbound,
+        );
+    let (mut server_sink, mut server_stream) = build_network_sink_stream(&mut connection);
+    let timeout = Duration::from_millis(10_000);
+
+    let client = async move {
+        // The unary response should be delivered as the only frame of the stream.
+        let frames: Vec<_> = peer_handle
+            .send_rpc_stream_request(PROTOCOL, Bytes::from(&b"hello world"[..]), timeout)
+            .collect()
+            .await;
+        let frames: Vec<_> = frames.into_iter().map(Result::unwrap).collect();
+        assert_eq!(frames, vec![Bytes::from("goodbye world")]);
+        // Client then closes connection.
+    };
+    let server = async move {
+        // Server should receive a plain rpc request instead of a stream request.
+        let received = server_stream.next().await.unwrap().unwrap();
+        let received = match received {
+            MultiplexMessage::Message(NetworkMessage::RpcRequest(request)) => request,
+            _ => panic!("Expected RpcRequest; unexpected: {:?}", received),
+        };
+        assert_eq!(received.protocol_id, PROTOCOL);
+        assert_eq!(received.raw_request, b"hello world");
+
+        // Server responds with a single unary response.
+        let response = MultiplexMessage::Message(NetworkMessage::RpcResponse(RpcResponse {
+            request_id: received.request_id,
+            priority: 0,
+            raw_response: Vec::from(&b"goodbye world"[..]),
+        }));
+        server_sink.send(&response).await.unwrap();
+        assert!(server_stream.next().await.is_none());
+    };
+    rt.block_on(future::join3(peer.start(), server, client));
+}
+
 #[test]
 fn peer_disconnect_request() {
     ::aptos_logger::Logger::init_for_testing();
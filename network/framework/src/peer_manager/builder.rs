@@ -12,13 +12,16 @@ use crate::{
     },
     protocols::{
         network::{NetworkClientConfig, NetworkServiceConfig},
-        wire::handshake::v1::ProtocolIdSet,
+        wire::handshake::v1::{MessagingCapability, ProtocolIdSet},
     },
     transport::{self, AptosNetTransport, Connection, APTOS_TCP_TRANSPORT},
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::HANDSHAKE_VERSION, network_id::NetworkContext};
+use aptos_config::{
+    config::{RpcRateLimitConfig, HANDSHAKE_VERSION},
+    network_id::NetworkContext,
+};
 use aptos_crypto::x25519;
 use aptos_logger::prelude::*;
 #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
@@ -79,6 +82,7 @@ struct PeerManagerContext {
     max_message_size: usize,
     inbound_connection_limit: usize,
     tcp_buffer_cfg: TCPBufferCfg,
+    inbound_rpc_rate_limit_config: Option<RpcRateLimitConfig>,
 }
 
 impl PeerManagerContext {
@@ -102,6 +106,7 @@ impl PeerManagerContext {
         max_message_size: usize,
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
+        inbound_rpc_rate_limit_config: Option<RpcRateLimitConfig>,
     ) -> Self {
         Self {
             pm_reqs_tx,
@@ -119,6 +124,7 @@ impl PeerManagerContext {
             max_message_size,
             inbound_connection_limit,
             tcp_buffer_cfg,
+            inbound_rpc_rate_limit_config,
         }
     }
 
@@ -177,6 +183,7 @@ impl PeerManagerBuilder {
         enable_proxy_protocol: bool,
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
+        inbound_rpc_rate_limit_config: Option<RpcRateLimitConfig>,
     ) -> Self {
         // Setup channel to send requests to peer manager.
         let (pm_reqs_tx, pm_reqs_rx) = aptos_channel::new(
@@ -212,6 +219,7 @@ impl PeerManagerBuilder {
                 max_message_size,
                 inbound_connection_limit,
                 tcp_buffer_cfg,
+                inbound_rpc_rate_limit_config,
             )),
             peer_manager: None,
             listen_address,
@@ -252,7 +260,11 @@ impl PeerManagerBuilder {
             .take()
             .expect("PeerManager can only be built once");
 
-        let protos = transport_context.supported_protocols;
+        // Advertise the messaging capabilities along with the application protocols
+        let mut protos = transport_context.supported_protocols;
+        protos.insert_capability(MessagingCapability::RpcRequestDeadlines);
+        protos.insert_capability(MessagingCapability::RpcStreams);
+        protos.insert_capability(MessagingCapability::RpcErrorResponses);
         let chain_id = transport_context.chain_id;
         let enable_proxy_protocol = transport_context.enable_proxy_protocol;
 
@@ -346,6 +358,7 @@ impl PeerManagerBuilder {
             pm_context.max_frame_size,
             pm_context.max_message_size,
             pm_context.inbound_connection_limit,
+            pm_context.inbound_rpc_rate_limit_config,
         );
 
         // PeerManager constructor appends a public key to the listen_address.
@@ -23,7 +23,10 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::network_id::{NetworkContext, PeerNetworkId};
+use aptos_config::{
+    config::RpcRateLimitConfig,
+    network_id::{NetworkContext, PeerNetworkId},
+};
 use aptos_logger::prelude::*;
 use aptos_netcore::transport::{ConnectionOrigin, Transport};
 use aptos_short_hex_str::AsShortHexStr;
@@ -119,6 +122,8 @@ where
     max_message_size: usize,
     /// Inbound connection limit separate of outbound connections
     inbound_connection_limit: usize,
+    /// Inbound rpc rate limit (per peer and protocol), if any
+    inbound_rpc_rate_limit_config: Option<RpcRateLimitConfig>,
 }
 
 impl<TTransport, TSocket> PeerManager<TTransport, TSocket>
@@ -147,6 +152,7 @@ where
         max_frame_size: usize,
         max_message_size: usize,
         inbound_connection_limit: usize,
+        inbound_rpc_rate_limit_config: Option<RpcRateLimitConfig>,
     ) -> Self {
         let (transport_notifs_tx, transport_notifs_rx) = aptos_channels::new(
             channel_size,
@@ -189,6 +195,7 @@ where
             max_frame_size,
             max_message_size,
             inbound_connection_limit,
+            inbound_rpc_rate_limit_config,
         }
     }
 
@@ -514,6 +521,9 @@ where
             PeerManagerRequest::SendRpc(peer_id, req) => {
                 (peer_id, req.protocol_id(), PeerRequest::SendRpc(req))
             },
+            PeerManagerRequest::SendRpcStream(peer_id, req) => {
+                (peer_id, req.protocol_id(), PeerRequest::SendRpcStream(req))
+            },
         };
 
         if let Some((conn_metadata, sender)) = self.active_peers.get_mut(&peer_id) {
@@ -672,6 +682,7 @@ where
             constants::MAX_CONCURRENT_OUTBOUND_RPCS,
             self.max_frame_size,
             self.max_message_size,
+            self.inbound_rpc_rate_limit_config,
         );
         self.executor.spawn(peer.start());
 
@@ -754,6 +765,10 @@ fn handle_inbound_request(
             req.protocol_id(),
             PeerManagerNotification::RecvRpc(peer_id, req),
         ),
+        PeerNotification::RecvRpcStream(req) => (
+            req.protocol_id(),
+            PeerManagerNotification::RecvRpcStream(peer_id, req),
+        ),
     };
 
     if let Some(handler) = upstream_handlers.get_mut(&protocol_id) {
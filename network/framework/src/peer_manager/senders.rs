@@ -3,17 +3,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    constants::MAX_RPC_STREAM_FRAMES,
     peer_manager::{types::PeerManagerRequest, ConnectionRequest, PeerManagerError},
     protocols::{
         direct_send::Message,
-        rpc::{error::RpcError, OutboundRpcRequest},
+        rpc::{error::RpcError, OutboundRpcRequest, OutboundRpcStreamRequest},
     },
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel};
 use aptos_types::{network_address::NetworkAddress, PeerId};
 use bytes::Bytes;
-use futures::channel::oneshot;
+use futures::channel::{mpsc, oneshot};
 use std::time::Duration;
 
 /// Convenience wrapper which makes it easy to issue communication requests and await the responses
@@ -106,6 +107,30 @@ impl PeerManagerRequestSender {
         )?;
         res_rx.await?
     }
+
+    /// Sends a streaming RPC to a remote peer. The returned receiver yields the
+    /// response frames in order, and is closed once the stream has finished. If
+    /// the stream fails or times out, a final error is yielded before closing.
+    pub fn send_rpc_stream(
+        &self,
+        peer_id: PeerId,
+        protocol_id: ProtocolId,
+        req: Bytes,
+        timeout: Duration,
+    ) -> Result<mpsc::Receiver<Result<Bytes, RpcError>>, RpcError> {
+        let (res_tx, res_rx) = mpsc::channel(MAX_RPC_STREAM_FRAMES as usize);
+        let request = OutboundRpcStreamRequest {
+            protocol_id,
+            data: req,
+            res_tx,
+            timeout,
+        };
+        self.inner.push(
+            (peer_id, protocol_id),
+            PeerManagerRequest::SendRpcStream(peer_id, request),
+        )?;
+        Ok(res_rx)
+    }
 }
 
 impl ConnectionRequestSender {
@@ -6,7 +6,10 @@ use crate::{
     peer_manager::PeerManagerError,
     protocols::{
         direct_send::Message,
-        rpc::{InboundRpcRequest, OutboundRpcRequest},
+        rpc::{
+            InboundRpcRequest, InboundRpcStreamRequest, OutboundRpcRequest,
+            OutboundRpcStreamRequest,
+        },
     },
     transport::{Connection, ConnectionMetadata},
 };
@@ -21,6 +24,8 @@ use std::fmt;
 pub enum PeerManagerRequest {
     /// Send an RPC request to a remote peer.
     SendRpc(PeerId, #[serde(skip)] OutboundRpcRequest),
+    /// Send a streaming RPC request to a remote peer.
+    SendRpcStream(PeerId, #[serde(skip)] OutboundRpcStreamRequest),
     /// Fire-and-forget style message send to a remote peer.
     SendDirectSend(PeerId, #[serde(skip)] Message),
 }
@@ -30,6 +35,8 @@ pub enum PeerManagerRequest {
 pub enum PeerManagerNotification {
     /// A new RPC request has been received from a remote peer.
     RecvRpc(PeerId, InboundRpcRequest),
+    /// A new streaming RPC request has been received from a remote peer.
+    RecvRpcStream(PeerId, InboundRpcStreamRequest),
     /// A new message has been received from a remote peer.
     RecvMessage(PeerId, Message),
 }
@@ -39,6 +46,7 @@ impl PeerManagerNotification {
     pub fn get_peer_id(&self) -> PeerId {
         match self {
             PeerManagerNotification::RecvRpc(peer_id, _) => *peer_id,
+            PeerManagerNotification::RecvRpcStream(peer_id, _) => *peer_id,
             PeerManagerNotification::RecvMessage(peer_id, _) => *peer_id,
         }
     }
@@ -191,6 +191,17 @@ impl<NetworkClient: NetworkClientInterface<HealthCheckerMsg> + Unpin> HealthChec
                             );
                             debug_assert!(false, "Unexpected network event");
                         }
+                        Event::RpcStreamRequest(peer_id, msg, _, _) => {
+                            warn!(
+                                SecurityEvent::InvalidHealthCheckerMsg,
+                                NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                                rpc_message = msg,
+                                "{} Unexpected RPC stream request from {}",
+                                self.network_context,
+                                peer_id
+                            );
+                            debug_assert!(false, "Unexpected rpc stream request");
+                        }
                     }
                 }
                 _ = ticker.select_next_some() => {
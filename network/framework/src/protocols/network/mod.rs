@@ -20,7 +20,7 @@ use aptos_short_hex_str::AsShortHexStr;
 use aptos_types::{network_address::NetworkAddress, PeerId};
 use bytes::Bytes;
 use futures::{
-    channel::oneshot,
+    channel::{mpsc, oneshot},
     stream::{FusedStream, Map, Select, Stream, StreamExt},
     task::{Context, Poll},
 };
@@ -57,6 +57,15 @@ pub enum Event<TMessage> {
         ProtocolId,
         oneshot::Sender<Result<Bytes, RpcError>>,
     ),
+    /// New inbound streaming rpc request. The request is fulfilled by sending
+    /// each serialized response frame over the `mpsc::Sender`, and finished by
+    /// dropping it. Sending an `Err` aborts the stream.
+    RpcStreamRequest(
+        PeerId,
+        TMessage,
+        ProtocolId,
+        mpsc::Sender<Result<Bytes, RpcError>>,
+    ),
     /// Peer which we have a newly established connection with.
     NewPeer(ConnectionMetadata),
     /// Peer with which we've lost our connection.
@@ -73,6 +82,10 @@ impl<TMessage: PartialEq> PartialEq for Event<TMessage> {
             (RpcRequest(pid1, msg1, proto1, _), RpcRequest(pid2, msg2, proto2, _)) => {
                 pid1 == pid2 && msg1 == msg2 && proto1 == proto2
             },
+            // ignore mpsc::Sender in comparison
+            (RpcStreamRequest(pid1, msg1, proto1, _), RpcStreamRequest(pid2, msg2, proto2, _)) => {
+                pid1 == pid2 && msg1 == msg2 && proto1 == proto2
+            },
             (NewPeer(metadata1), NewPeer(metadata2)) => metadata1 == metadata2,
             (LostPeer(metadata1), LostPeer(metadata2)) => metadata1 == metadata2,
             _ => false,
@@ -258,6 +271,11 @@ fn peer_mgr_notif_to_event<TMessage: Message>(
             request_to_network_event(peer_id, &rpc_req)
                 .map(|msg| Event::RpcRequest(peer_id, msg, rpc_req.protocol_id, rpc_req.res_tx))
         },
+        PeerManagerNotification::RecvRpcStream(peer_id, rpc_req) => {
+            request_to_network_event(peer_id, &rpc_req).map(|msg| {
+                Event::RpcStreamRequest(peer_id, msg, rpc_req.protocol_id, rpc_req.res_tx)
+            })
+        },
         PeerManagerNotification::RecvMessage(peer_id, request) => {
             request_to_network_event(peer_id, &request).map(|msg| Event::Message(peer_id, msg))
         },
@@ -409,6 +427,36 @@ impl<TMessage: Message + Send + 'static> NetworkSender<TMessage> {
         let res_msg = tokio::task::spawn_blocking(move || protocol.from_bytes(&res_data)).await??;
         Ok(res_msg)
     }
+
+    /// Send a protobuf streaming rpc request to a single recipient. The returned
+    /// stream yields each deserialized response frame in order, and ends once
+    /// the remote peer finishes the stream. Assumes that the request and the
+    /// response frames all have the same message type.
+    pub async fn send_rpc_stream(
+        &self,
+        recipient: PeerId,
+        protocol: ProtocolId,
+        req_msg: TMessage,
+        timeout: Duration,
+    ) -> Result<impl Stream<Item = Result<TMessage, RpcError>>, RpcError> {
+        // Serialize the request using a blocking task
+        let req_data = tokio::task::spawn_blocking(move || protocol.to_bytes(&req_msg))
+            .await??
+            .into();
+
+        // Send the request
+        let res_rx = self
+            .peer_mgr_reqs_tx
+            .send_rpc_stream(recipient, protocol, req_data, timeout)?;
+
+        // Deserialize each response frame using a blocking task
+        Ok(res_rx.then(move |maybe_frame| async move {
+            let res_data = maybe_frame?;
+            let res_msg =
+                tokio::task::spawn_blocking(move || protocol.from_bytes(&res_data)).await??;
+            Ok(res_msg)
+        }))
+    }
 }
 
 /// Generalized functionality for any request across `DirectSend` and `Rpc`.
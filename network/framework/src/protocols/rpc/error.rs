@@ -8,7 +8,10 @@ use crate::peer_manager::PeerManagerError;
 use anyhow::anyhow;
 use aptos_types::PeerId;
 use futures::channel::{mpsc, oneshot};
-use std::io;
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use std::{fmt, io, time::Duration};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -34,14 +37,112 @@ pub enum RpcError {
     #[error("Error in application layer handling rpc request: {0:?}")]
     ApplicationError(anyhow::Error),
 
+    #[error("Application layer rejected rpc request: {0}")]
+    ApplicationErrorResponse(RpcApplicationError),
+
     #[error("Error sending on mpsc channel, connection likely shutting down: {0:?}")]
     MpscSendError(#[from] mpsc::SendError),
 
     #[error("Too many pending RPCs: {0}")]
     TooManyPending(u32),
 
+    #[error("Rpc rate limited, retry after: {retry_after:?}")]
+    RateLimited { retry_after: Duration },
+
     #[error("Rpc timed out")]
     TimedOut,
+
+    #[error("Rpc stream closed before it finished: {0}")]
+    StreamClosed(String),
+}
+
+/// A structured error returned by the remote application layer when it fails
+/// to handle an rpc request. Unlike [`RpcError::ApplicationError`], this error
+/// is sent back to the requester, so that it can decide how to react (e.g.,
+/// whether to retry the request) without having to match on error strings.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct RpcApplicationError {
+    /// An application specific error code
+    pub code: u32,
+    /// Whether the request may succeed if it is retried later
+    pub retriable: bool,
+    /// A human readable description of the error
+    pub message: String,
+}
+
+/// The code of the [`RpcApplicationError`] sent back to a requester that
+/// exceeded the inbound rate limit. It is reserved for the network layer, and
+/// the message holds the number of milliseconds to wait before retrying.
+pub const RATE_LIMITED_ERROR_CODE: u32 = u32::MAX;
+
+impl RpcApplicationError {
+    /// Returns the error sent back to a requester that exceeded the inbound rate limit
+    pub fn rate_limited(retry_after: Duration) -> Self {
+        Self::new(
+            RATE_LIMITED_ERROR_CODE,
+            true,
+            retry_after.as_millis().to_string(),
+        )
+    }
+
+    pub fn new(code: u32, retriable: bool, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            retriable,
+            message: message.into(),
+        }
+    }
+
+    /// Truncates the error message to at most `max_len` bytes
+    pub fn truncate_message(&mut self, max_len: usize) {
+        if self.message.len() > max_len {
+            let mut index = max_len;
+            while !self.message.is_char_boundary(index) {
+                index -= 1;
+            }
+            self.message.truncate(index);
+        }
+    }
+}
+
+impl fmt::Display for RpcApplicationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "code: {}, retriable: {}, message: {}",
+            self.code, self.retriable, self.message
+        )
+    }
+}
+
+impl RpcError {
+    /// Converts the error sent back by the remote peer, restoring the rate
+    /// limiting errors raised by its network layer
+    pub fn from_error_response(error: RpcApplicationError) -> Self {
+        if error.code == RATE_LIMITED_ERROR_CODE {
+            if let Ok(retry_after_ms) = error.message.parse() {
+                return RpcError::RateLimited {
+                    retry_after: Duration::from_millis(retry_after_ms),
+                };
+            }
+        }
+        RpcError::ApplicationErrorResponse(error)
+    }
+
+    /// Returns the structured application error sent by the remote peer (if any)
+    pub fn application_error(&self) -> Option<&RpcApplicationError> {
+        match self {
+            RpcError::ApplicationErrorResponse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<RpcApplicationError> for RpcError {
+    fn from(error: RpcApplicationError) -> Self {
+        RpcError::ApplicationErrorResponse(error)
+    }
 }
 
 impl From<PeerManagerError> for RpcError {
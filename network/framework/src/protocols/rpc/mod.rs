@@ -36,48 +36,95 @@
 //! the task to complete with an error if the task isn't fulfilled before the
 //! deadline.
 //!
+//! Outbound requests are sent as `RpcRequestWithDeadline`s, which carry the
+//! time remaining until the requester gives up, if the remote peer negotiated
+//! the `RpcRequestDeadlines` capability during the handshake (and as plain
+//! `RpcRequest`s otherwise, e.g., to older peers). The responder bounds the
+//! inbound timeout by this deadline (see [`inbound_handler_timeout`]) and drops
+//! requests that have already expired on arrival. Once the deadline elapses,
+//! the request's `res_tx` channel is canceled, so the application can check
+//! `res_tx.is_canceled()` to avoid doing work that nobody is waiting for.
+//!
 //! ## Limits:
 //!
 //! We limit the number of pending inbound and outbound RPC tasks to ensure that
-//! resource usage is bounded.
+//! resource usage is bounded. Inbound requests may also be rate limited per
+//! protocol (see [`InboundRpcRateLimiter`]), in which case requests exceeding
+//! the rate are rejected with [`RpcError::RateLimited`].
+//!
+//! ## Streaming:
+//!
+//! An rpc may also be sent as a stream request (`RpcStreamRequest`), in which
+//! case the responder replies with a bounded, ordered sequence of
+//! `RpcStreamResponse` frames instead of a single `RpcResponse`. The last frame
+//! is always marked `Finished` or `Aborted` and carries no payload. The
+//! application hands frames to (and receives frames from) the rpc layer over
+//! bounded channels, so a slow consumer applies backpressure to the producer.
+//! Streams share the same timeouts and concurrency limits as unary rpcs.
+//!
+//! Stream requests are only sent if the remote peer negotiated the `RpcStreams`
+//! capability during the handshake. Otherwise (e.g., for older peers), a plain
+//! unary request is sent, and its response is handed to the application as
+//! the only frame of the stream.
+//!
+//! ## Application errors:
+//!
+//! If the application layer fails to handle a unary rpc request with an
+//! [`RpcApplicationError`], the error is sent back to the requester in an
+//! `RpcErrorResponse` (instead of an `RpcResponse`), and surfaced to the
+//! requesting application as [`RpcError::ApplicationErrorResponse`]. Unary
+//! requests rejected by the rate limiter are answered the same way, and
+//! surfaced to the requester as [`RpcError::RateLimited`]. Any other error
+//! causes the request to be dropped, leaving the requester to time out.
+//!
+//! Error responses (including rate limited replies) are only sent if the
+//! remote peer negotiated the `RpcErrorResponses` capability during the
+//! handshake. Otherwise (e.g., for older peers), the failed or rejected request
+//! is dropped like any other error.
 //!
 //! [AptosNet wire protocol v1]: https://github.com/aptos-labs/aptos-core/blob/main/specifications/network/messaging-v1.md
 //! [`Peer`]: crate::peer::Peer
 
 use crate::{
+    constants::{MAX_RPC_ERROR_MESSAGE_SIZE, MAX_RPC_STREAM_FRAMES},
     counters::{
         self, network_application_inbound_traffic, network_application_outbound_traffic,
         CANCELED_LABEL, DECLINED_LABEL, EXPIRED_LABEL, FAILED_LABEL, INBOUND_LABEL, OUTBOUND_LABEL,
-        RECEIVED_LABEL, REQUEST_LABEL, RESPONSE_LABEL, SENT_LABEL,
+        RATE_LIMITED_LABEL, RECEIVED_LABEL, REQUEST_LABEL, RESPONSE_LABEL, SENT_LABEL,
     },
     logging::NetworkSchema,
     peer::PeerNotification,
     protocols::{
         network::SerializedRequest,
-        wire::messaging::v1::{NetworkMessage, Priority, RequestId, RpcRequest, RpcResponse},
+        wire::messaging::v1::{
+            NetworkMessage, RequestId, RpcErrorResponse, RpcRequest, RpcRequestWithDeadline,
+            RpcResponse, RpcStreamResponse, RpcStreamStatus,
+        },
     },
     ProtocolId,
 };
 use anyhow::anyhow;
 use aptos_channels::aptos_channel;
-use aptos_config::network_id::NetworkContext;
+use aptos_config::{config::RpcRateLimitConfig, network_id::NetworkContext};
 use aptos_id_generator::{IdGenerator, U32IdGenerator};
 use aptos_logger::prelude::*;
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{timeout, TimeService, TimeServiceTrait};
 use aptos_types::PeerId;
 use bytes::Bytes;
-use error::RpcError;
+use error::{RpcApplicationError, RpcError};
 use futures::{
-    channel::oneshot,
-    future::{BoxFuture, FusedFuture, Future, FutureExt},
+    channel::{mpsc, oneshot},
+    future::{self, BoxFuture, FusedFuture, Future, FutureExt},
     sink::SinkExt,
     stream::{FuturesUnordered, StreamExt},
 };
+use rate_limit::InboundRpcRateLimiter;
 use serde::Serialize;
 use std::{cmp::PartialEq, collections::HashMap, fmt::Debug, time::Duration};
 
 pub mod error;
+mod rate_limit;
 
 /// A wrapper struct for an inbound rpc request and its associated context.
 #[derive(Debug)]
@@ -99,11 +146,15 @@ pub struct InboundRpcRequest {
     /// response from the upper layer. If there is an error in, e.g.,
     /// deserializing the request, the upper layer should send an [`RpcError`]
     /// down the channel to signify that there was an error while handling this
-    /// rpc request. Currently, we just log these errors and drop the request.
+    /// rpc request. [`RpcError::ApplicationErrorResponse`]s are sent back to
+    /// the requester, if it supports them. For all other errors, we just log
+    /// them and drop the request.
     ///
     /// The upper client layer should be prepared for `res_tx` to be disconnected
     /// when trying to send their response, as the rpc call might have timed out
-    /// while handling the request.
+    /// while handling the request. If the requester's deadline has already
+    /// passed, `res_tx.is_canceled()` returns true and the request can be
+    /// dropped without handling it.
     pub res_tx: oneshot::Sender<Result<Bytes, RpcError>>,
 }
 
@@ -160,6 +211,115 @@ impl PartialEq for InboundRpcRequest {
     }
 }
 
+/// A wrapper struct for an inbound streaming rpc request and its associated
+/// context.
+#[derive(Debug)]
+pub struct InboundRpcStreamRequest {
+    /// The [`ProtocolId`] for which of our upstream application modules should
+    /// handle this inbound streaming rpc request.
+    pub protocol_id: ProtocolId,
+    /// The serialized request data received from the sender.
+    pub data: Bytes,
+    /// Channel over which the response frames are sent from the upper
+    /// application layer to the network rpc layer.
+    ///
+    /// Each `Ok` item is sent to the remote peer as a single response frame.
+    /// Dropping the sender finishes the stream, while sending an [`RpcError`]
+    /// aborts it. The channel is bounded, so the application is only able to
+    /// produce frames as fast as they can be written to the wire. At most
+    /// [`MAX_RPC_STREAM_FRAMES`] frames may be sent before the stream is aborted.
+    pub res_tx: mpsc::Sender<Result<Bytes, RpcError>>,
+}
+
+impl SerializedRequest for InboundRpcStreamRequest {
+    fn protocol_id(&self) -> ProtocolId {
+        self.protocol_id
+    }
+
+    fn data(&self) -> &Bytes {
+        &self.data
+    }
+}
+
+impl PartialEq for InboundRpcStreamRequest {
+    fn eq(&self, other: &Self) -> bool {
+        self.protocol_id == other.protocol_id && self.data == other.data
+    }
+}
+
+/// A wrapper struct for an outbound streaming rpc request and its associated
+/// context.
+#[derive(Debug, Serialize)]
+pub struct OutboundRpcStreamRequest {
+    /// The remote peer's application module that should handle our outbound
+    /// streaming rpc request.
+    pub protocol_id: ProtocolId,
+    /// The serialized request data to be sent to the receiver.
+    #[serde(skip)]
+    pub data: Bytes,
+    /// Channel over which the response frames are sent from the rpc layer to
+    /// the upper client layer, in order.
+    ///
+    /// The channel is closed once the remote peer finishes the stream. If the
+    /// stream fails, e.g., the remote peer aborts it or the timeout elapses, a
+    /// final [`RpcError`] is sent over the channel before it is closed.
+    #[serde(skip)]
+    pub res_tx: mpsc::Sender<Result<Bytes, RpcError>>,
+    /// The timeout duration for the entire stream, i.e., until the last frame
+    /// has been received.
+    pub timeout: Duration,
+}
+
+impl SerializedRequest for OutboundRpcStreamRequest {
+    fn protocol_id(&self) -> ProtocolId {
+        self.protocol_id
+    }
+
+    fn data(&self) -> &Bytes {
+        &self.data
+    }
+}
+
+/// Converts the time remaining until the requester's deadline (if the requester
+/// sent one) into the timeout for handling an inbound request. The timeout is
+/// always bounded by `max_timeout`, so requesters can't hold onto our resources
+/// for longer than we allow.
+pub fn inbound_handler_timeout(
+    remaining_time: Option<Duration>,
+    max_timeout: Duration,
+) -> Duration {
+    match remaining_time {
+        Some(remaining_time) => remaining_time.min(max_timeout),
+        None => max_timeout,
+    }
+}
+
+/// Returns an error if the remote peer has exceeded the inbound request rate
+/// for the given protocol (if rate limiting is enabled).
+fn check_rate_limit(
+    rate_limiter: Option<&mut InboundRpcRateLimiter>,
+    network_context: &NetworkContext,
+    protocol_id: ProtocolId,
+) -> Result<(), RpcError> {
+    let rate_limiter = match rate_limiter {
+        Some(rate_limiter) => rate_limiter,
+        None => return Ok(()),
+    };
+    rate_limiter
+        .try_acquire(protocol_id)
+        .map_err(|retry_after| {
+            counters::rpc_messages(
+                network_context,
+                REQUEST_LABEL,
+                INBOUND_LABEL,
+                RATE_LIMITED_LABEL,
+            )
+            .inc();
+            counters::rpc_rate_limited(network_context, protocol_id).inc();
+            RpcError::RateLimited { retry_after }
+        })
+}
+
 /// `InboundRpcs` handles new inbound rpc requests off the wire, notifies the
 /// `PeerManager` of the new request, and stores the pending response on a queue.
 /// If the response eventually completes, `InboundRpc` records some metrics and
@@ -175,8 +335,14 @@ pub struct InboundRpcs {
     remote_peer_id: PeerId,
     /// The core async queue of pending inbound rpc tasks. The tasks are driven
     /// to completion by the `InboundRpcs::next_completed_response()` method.
-    inbound_rpc_tasks:
-        FuturesUnordered<BoxFuture<'static, Result<(RpcResponse, ProtocolId), RpcError>>>,
+    inbound_rpc_tasks: FuturesUnordered<
+        BoxFuture<'static, Result<(RequestId, NetworkMessage, ProtocolId), RpcError>>,
+    >,
+    /// The async queue of pending inbound streaming rpc tasks. Each task
+    /// forwards the application's response frames onto the outbound write
+    /// queue, and completes once the stream is finished or aborted. The tasks
+    /// are driven to completion by `InboundRpcs::next_completed_stream()`.
+    inbound_stream_tasks: FuturesUnordered<BoxFuture<'static, Result<(ProtocolId, u64), RpcError>>>,
     /// A blanket timeout on all inbound rpc requests. If the application handler
     /// doesn't respond to the request before this timeout, the request will be
     /// dropped.
@@ -184,6 +350,12 @@ pub struct InboundRpcs {
     /// Only allow this many concurrent inbound rpcs at one time from this remote
     /// peer.  New inbound requests exceeding this limit will be dropped.
     max_concurrent_inbound_rpcs: u32,
+    /// Limits the rate of inbound rpcs (per protocol) from this remote peer.
+    /// New inbound requests exceeding the rate will be dropped.
+    rate_limiter: Option<InboundRpcRateLimiter>,
+    /// True iff the remote peer understands `RpcErrorResponse` messages (i.e.,
+    /// it negotiated the `RpcErrorResponses` capability).
+    send_error_responses: bool,
 }
 
 impl InboundRpcs {
@@ -193,27 +365,36 @@ impl InboundRpcs {
         remote_peer_id: PeerId,
         inbound_rpc_timeout: Duration,
         max_concurrent_inbound_rpcs: u32,
+        rate_limit_config: Option<RpcRateLimitConfig>,
+        send_error_responses: bool,
     ) -> Self {
+        let rate_limiter = rate_limit_config
+            .map(|config| InboundRpcRateLimiter::new(config, time_service.clone()));
         Self {
             network_context,
             time_service,
             remote_peer_id,
             inbound_rpc_tasks: FuturesUnordered::new(),
+            inbound_stream_tasks: FuturesUnordered::new(),
             inbound_rpc_timeout,
             max_concurrent_inbound_rpcs,
+            rate_limiter,
+            send_error_responses,
         }
     }
 
-    /// Handle a new inbound `RpcRequest` message off the wire.
+    /// Handle a new inbound `RpcRequest` message off the wire. If the requester
+    /// sent a deadline, `remaining_time` is the time left until it elapses.
     pub fn handle_inbound_request(
         &mut self,
         peer_notifs_tx: &mut aptos_channel::Sender<ProtocolId, PeerNotification>,
         request: RpcRequest,
+        remaining_time: Option<Duration>,
     ) -> Result<(), RpcError> {
         let network_context = &self.network_context;
 
         // Drop new inbound requests if our completion queue is at capacity.
-        if self.inbound_rpc_tasks.len() as u32 == self.max_concurrent_inbound_rpcs {
+        if self.num_pending_inbound_rpcs() == self.max_concurrent_inbound_rpcs {
             // Increase counter of declined requests
             counters::rpc_messages(
                 network_context,
@@ -225,10 +406,44 @@ impl InboundRpcs {
             return Err(RpcError::TooManyPending(self.max_concurrent_inbound_rpcs));
         }
 
+        // Drop new inbound requests if the requester has already given up.
+        let handler_timeout = inbound_handler_timeout(remaining_time, self.inbound_rpc_timeout);
+        if handler_timeout.is_zero() {
+            counters::rpc_messages(network_context, REQUEST_LABEL, INBOUND_LABEL, EXPIRED_LABEL)
+                .inc();
+            return Err(RpcError::TimedOut);
+        }
+
         let protocol_id = request.protocol_id;
         let request_id = request.request_id;
         let priority = request.priority;
 
+        // Reject new inbound requests if the peer is sending them too fast, and
+        // let it know when to retry (if it supports error responses).
+        if let Err(error) =
+            check_rate_limit(self.rate_limiter.as_mut(), network_context, protocol_id)
+        {
+            match error {
+                RpcError::RateLimited { retry_after } if self.send_error_responses => {
+                    let rpc_response = RpcErrorResponse {
+                        request_id,
+                        priority,
+                        error: RpcApplicationError::rate_limited(retry_after),
+                    };
+                    self.inbound_rpc_tasks.push(
+                        future::ready(Ok((
+                            request_id,
+                            NetworkMessage::RpcErrorResponse(rpc_response),
+                            protocol_id,
+                        )))
+                        .boxed(),
+                    );
+                },
+                _ => {},
+            }
+            return Err(error);
+        }
+
         trace!(
             NetworkSchema::new(network_context).remote_peer(&self.remote_peer_id),
             "{} Received inbound rpc request from peer {} with request_id {} and protocol_id {}",
@@ -256,9 +471,10 @@ impl InboundRpcs {
         }
 
         // Create a new task that waits for a response from the upper layer with a timeout.
+        let send_error_responses = self.send_error_responses;
         let inbound_rpc_task = self
             .time_service
-            .timeout(self.inbound_rpc_timeout, response_rx)
+            .timeout(handler_timeout, response_rx)
             .map(move |result| {
                 // Flatten the errors
                 let maybe_response = match result {
@@ -268,7 +484,26 @@ impl InboundRpcs {
                             priority,
                             raw_response: Vec::from(response_bytes.as_ref()),
                         };
-                        Ok((rpc_response, protocol_id))
+                        Ok((
+                            request_id,
+                            NetworkMessage::RpcResponse(rpc_response),
+                            protocol_id,
+                        ))
+                    },
+                    Ok(Ok(Err(RpcError::ApplicationErrorResponse(mut error))))
+                        if send_error_responses =>
+                    {
+                        error.truncate_message(MAX_RPC_ERROR_MESSAGE_SIZE);
+                        let rpc_response = RpcErrorResponse {
+                            request_id,
+                            priority,
+                            error,
+                        };
+                        Ok((
+                            request_id,
+                            NetworkMessage::RpcErrorResponse(rpc_response),
+                            protocol_id,
+                        ))
                     },
                     Ok(Ok(Err(err))) => Err(err),
                     Ok(Err(oneshot::Canceled)) => Err(RpcError::UnexpectedResponseChannelCancel),
@@ -290,6 +525,129 @@ impl InboundRpcs {
         Ok(())
     }
 
+    /// Handle a new inbound `RpcStreamRequest` message off the wire. The
+    /// response frames produced by the application are written directly onto
+    /// `write_reqs_tx` by the spawned stream task.
+    pub fn handle_inbound_stream_request(
+        &mut self,
+        peer_notifs_tx: &mut aptos_channel::Sender<ProtocolId, PeerNotification>,
+        mut write_reqs_tx: aptos_channels::Sender<NetworkMessage>,
+        request: RpcRequest,
+    ) -> Result<(), RpcError> {
+        let network_context = &self.network_context;
+
+        // Drop new inbound requests if our completion queue is at capacity.
+        if self.num_pending_inbound_rpcs() == self.max_concurrent_inbound_rpcs {
+            counters::rpc_messages(
+                network_context,
+                REQUEST_LABEL,
+                INBOUND_LABEL,
+                DECLINED_LABEL,
+            )
+            .inc();
+            return Err(RpcError::TooManyPending(self.max_concurrent_inbound_rpcs));
+        }
+
+        let protocol_id = request.protocol_id;
+        let request_id = request.request_id;
+        let priority = request.priority;
+
+        // Drop new inbound requests if the peer is sending them too fast.
+        check_rate_limit(self.rate_limiter.as_mut(), network_context, protocol_id)?;
+
+        trace!(
+            NetworkSchema::new(network_context).remote_peer(&self.remote_peer_id),
+            "{} Received inbound rpc stream request from peer {} with request_id {} and protocol_id {}",
+            network_context,
+            self.remote_peer_id.short_str(),
+            request_id,
+            protocol_id,
+        );
+        self.update_inbound_rpc_request_metrics(protocol_id, request.raw_request.len() as u64);
+
+        // Forward request to PeerManager for handling.
+        let (response_tx, mut response_rx) = mpsc::channel(0);
+        let notif = PeerNotification::RecvRpcStream(InboundRpcStreamRequest {
+            protocol_id,
+            data: Bytes::from(request.raw_request),
+            res_tx: response_tx,
+        });
+        if let Err(err) = peer_notifs_tx.push(protocol_id, notif) {
+            counters::rpc_messages(network_context, REQUEST_LABEL, INBOUND_LABEL, FAILED_LABEL)
+                .inc();
+            return Err(err.into());
+        }
+
+        // Create a new task that forwards the response frames from the upper
+        // layer onto the write queue, until the stream ends or times out.
+        let time_service = self.time_service.clone();
+        let inbound_rpc_timeout = self.inbound_rpc_timeout;
+        let inbound_stream_task = async move {
+            let mut frame_index: u32 = 0;
+            let mut bytes_sent: u64 = 0;
+
+            let forward_frames = async {
+                while let Some(maybe_frame) = response_rx.next().await {
+                    let frame = maybe_frame?;
+                    if frame_index == MAX_RPC_STREAM_FRAMES {
+                        return Err(RpcError::StreamClosed(format!(
+                            "too many response frames, limit: {}",
+                            MAX_RPC_STREAM_FRAMES
+                        )));
+                    }
+                    bytes_sent += frame.len() as u64;
+                    let message = NetworkMessage::RpcStreamResponse(RpcStreamResponse {
+                        request_id,
+                        priority,
+                        frame_index,
+                        status: RpcStreamStatus::Open,
+                        raw_response: Vec::from(frame.as_ref()),
+                    });
+                    write_reqs_tx.send(message).await?;
+                    frame_index += 1;
+                }
+                Ok(())
+            };
+            let result = match time_service
+                .timeout(inbound_rpc_timeout, forward_frames)
+                .await
+            {
+                Ok(result) => result,
+                Err(timeout::Elapsed) => Err(RpcError::TimedOut),
+            };
+
+            // Always terminate the stream so the remote peer doesn't have to
+            // wait for its own timeout to elapse.
+            let status = if result.is_ok() {
+                RpcStreamStatus::Finished
+            } else {
+                RpcStreamStatus::Aborted
+            };
+            let message = NetworkMessage::RpcStreamResponse(RpcStreamResponse {
+                request_id,
+                priority,
+                frame_index,
+                status,
+                raw_response: Vec::new(),
+            });
+            write_reqs_tx.send(message).await?;
+
+            result.map(|()| (protocol_id, bytes_sent))
+        };
+
+        // Add that task to the inbound stream queue. These tasks are driven
+        // forward by `Peer` awaiting `self.next_completed_stream()`.
+        self.inbound_stream_tasks.push(inbound_stream_task.boxed());
+
+        Ok(())
+    }
+
+    /// Returns the number of inbound rpcs (unary and streaming) that are
+    /// still awaiting completion.
+    fn num_pending_inbound_rpcs(&self) -> u32 {
+        (self.inbound_rpc_tasks.len() + self.inbound_stream_tasks.len()) as u32
+    }
+
     /// Updates the inbound RPC request metrics (e.g., messages and bytes received)
     fn update_inbound_rpc_request_metrics(&self, protocol_id: ProtocolId, data_len: u64) {
         // Update the metrics for the new RPC request
@@ -317,20 +675,54 @@ impl InboundRpcs {
     /// `futures::select!`.
     pub fn next_completed_response(
         &mut self,
-    ) -> impl Future<Output = Result<(RpcResponse, ProtocolId), RpcError>> + FusedFuture + '_ {
+    ) -> impl Future<Output = Result<(RequestId, NetworkMessage, ProtocolId), RpcError>> + FusedFuture + '_
+    {
         self.inbound_rpc_tasks.select_next_some()
     }
 
+    /// Method for `Peer` actor to drive the pending inbound stream tasks
+    /// forward. The returned `Future` is a `FusedFuture` so it works correctly
+    /// in a `futures::select!`.
+    pub fn next_completed_stream(
+        &mut self,
+    ) -> impl Future<Output = Result<(ProtocolId, u64), RpcError>> + FusedFuture + '_ {
+        self.inbound_stream_tasks.select_next_some()
+    }
+
+    /// Handle a completed inbound stream. All frames have already been
+    /// enqueued onto the write queue, so we only need to update the counters.
+    pub fn handle_completed_stream(
+        &mut self,
+        result: Result<(ProtocolId, u64), RpcError>,
+    ) -> Result<(), RpcError> {
+        match result {
+            Ok((protocol_id, bytes_sent)) => {
+                self.update_outbound_rpc_response_metrics(protocol_id, bytes_sent);
+                Ok(())
+            },
+            Err(err) => {
+                counters::rpc_messages(
+                    &self.network_context,
+                    RESPONSE_LABEL,
+                    OUTBOUND_LABEL,
+                    FAILED_LABEL,
+                )
+                .inc();
+                Err(err)
+            },
+        }
+    }
+
     /// Handle a completed response from the application handler. If successful,
-    /// we update the appropriate counters and enqueue the response message onto
-    /// the outbound write queue.
+    /// we update the appropriate counters and enqueue the response message (or
+    /// the application error) onto the outbound write queue.
     pub async fn send_outbound_response(
         &mut self,
         write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
-        maybe_response: Result<(RpcResponse, ProtocolId), RpcError>,
+        maybe_response: Result<(RequestId, NetworkMessage, ProtocolId), RpcError>,
     ) -> Result<(), RpcError> {
         let network_context = &self.network_context;
-        let (response, protocol_id) = match maybe_response {
+        let (request_id, message, protocol_id) = match maybe_response {
             Ok(response) => response,
             Err(err) => {
                 counters::rpc_messages(
@@ -343,7 +735,7 @@ impl InboundRpcs {
                 return Err(err);
             },
         };
-        let res_len = response.raw_response.len() as u64;
+        let res_len = message.data_len() as u64;
 
         // Send outbound response to remote peer.
         trace!(
@@ -351,9 +743,8 @@ impl InboundRpcs {
             "{} Sending rpc response to peer {} for request_id {}",
             network_context,
             self.remote_peer_id.short_str(),
-            response.request_id,
+            request_id,
         );
-        let message = NetworkMessage::RpcResponse(response);
         write_reqs_tx.send(message).await?;
 
         // Update the outbound RPC response metrics
@@ -408,10 +799,27 @@ pub struct OutboundRpcs {
     /// Maps a `RequestId` into a handle to a task in the `outbound_rpc_tasks`
     /// completion queue. When a new `RpcResponse` message comes in, we will use
     /// this map to notify the corresponding task that its response has arrived.
-    pending_outbound_rpcs: HashMap<RequestId, (ProtocolId, oneshot::Sender<RpcResponse>)>,
+    pending_outbound_rpcs: HashMap<
+        RequestId,
+        (
+            ProtocolId,
+            oneshot::Sender<Result<RpcResponse, RpcApplicationError>>,
+        ),
+    >,
+    /// Maps a `RequestId` into a handle to a streaming task in the
+    /// `outbound_rpc_tasks` completion queue. Each new `RpcStreamResponse`
+    /// frame is forwarded to the corresponding task over this channel.
+    pending_outbound_streams:
+        HashMap<RequestId, (ProtocolId, mpsc::UnboundedSender<RpcStreamResponse>)>,
     /// Only allow this many concurrent outbound rpcs at one time from this remote
     /// peer. New outbound requests exceeding this limit will be dropped.
     max_concurrent_outbound_rpcs: u32,
+    /// True iff the remote peer understands `RpcRequestWithDeadline` messages
+    /// (i.e., it negotiated the `RpcRequestDeadlines` capability).
+    send_request_deadlines: bool,
+    /// True iff the remote peer understands `RpcStreamRequest` messages (i.e.,
+    /// it negotiated the `RpcStreams` capability).
+    send_stream_requests: bool,
 }
 
 impl OutboundRpcs {
@@ -420,6 +828,8 @@ impl OutboundRpcs {
         time_service: TimeService,
         remote_peer_id: PeerId,
         max_concurrent_outbound_rpcs: u32,
+        send_request_deadlines: bool,
+        send_stream_requests: bool,
     ) -> Self {
         Self {
             network_context,
@@ -428,7 +838,10 @@ impl OutboundRpcs {
             request_id_gen: U32IdGenerator::new(),
             outbound_rpc_tasks: FuturesUnordered::new(),
             pending_outbound_rpcs: HashMap::new(),
+            pending_outbound_streams: HashMap::new(),
             max_concurrent_outbound_rpcs,
+            send_request_deadlines,
+            send_stream_requests,
         }
     }
 
@@ -492,20 +905,28 @@ impl OutboundRpcs {
         let timer =
             counters::outbound_rpc_request_latency(network_context, protocol_id).start_timer();
 
-        // Enqueue rpc request message onto outbound write queue.
-        let message = NetworkMessage::RpcRequest(RpcRequest {
+        // Enqueue rpc request message onto outbound write queue. If the remote
+        // peer supports it, the request carries our timeout, so the remote peer
+        // can drop it once we give up.
+        let request = RpcRequest {
             protocol_id,
             request_id,
-            priority: Priority::default(),
+            priority: protocol_id.priority(),
             raw_request: Vec::from(request_data.as_ref()),
-        });
+        };
+        let message = if self.send_request_deadlines {
+            NetworkMessage::RpcRequestWithDeadline(RpcRequestWithDeadline::new(request, timeout))
+        } else {
+            NetworkMessage::RpcRequest(request)
+        };
         write_reqs_tx.send(message).await?;
 
         // Update the outbound RPC request metrics
         self.update_outbound_rpc_request_metrics(protocol_id, req_len);
 
         // Create channel over which response is delivered to outbound_rpc_task.
-        let (response_tx, response_rx) = oneshot::channel::<RpcResponse>();
+        let (response_tx, response_rx) =
+            oneshot::channel::<Result<RpcResponse, RpcApplicationError>>();
 
         // Store send-side in the pending map so we can notify outbound_rpc_task
         // when the rpc response has arrived.
@@ -521,7 +942,8 @@ impl OutboundRpcs {
             .map(|result| {
                 // Flatten errors.
                 match result {
-                    Ok(Ok(response)) => Ok(Bytes::from(response.raw_response)),
+                    Ok(Ok(Ok(response))) => Ok(Bytes::from(response.raw_response)),
+                    Ok(Ok(Err(error))) => Err(RpcError::from_error_response(error)),
                     Ok(Err(oneshot::Canceled)) => Err(RpcError::UnexpectedResponseChannelCancel),
                     Err(timeout::Elapsed) => Err(RpcError::TimedOut),
                 }
@@ -571,6 +993,212 @@ impl OutboundRpcs {
         Ok(())
     }
 
+    /// Handle a new outbound streaming rpc request from the application layer.
+    pub async fn handle_outbound_stream_request(
+        &mut self,
+        request: OutboundRpcStreamRequest,
+        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
+    ) -> Result<(), RpcError> {
+        let network_context = &self.network_context;
+        let peer_id = &self.remote_peer_id;
+
+        // Unpack request.
+        let OutboundRpcStreamRequest {
+            protocol_id,
+            data: request_data,
+            timeout,
+            res_tx: mut application_response_tx,
+        } = request;
+        let req_len = request_data.len() as u64;
+
+        // Drop the outbound request if the application layer has already canceled.
+        if application_response_tx.is_closed() {
+            counters::rpc_messages(
+                network_context,
+                REQUEST_LABEL,
+                OUTBOUND_LABEL,
+                CANCELED_LABEL,
+            )
+            .inc();
+            return Err(RpcError::UnexpectedResponseChannelCancel);
+        }
+
+        // Drop new outbound requests if our completion queue is at capacity.
+        if self.outbound_rpc_tasks.len() == self.max_concurrent_outbound_rpcs as usize {
+            counters::rpc_messages(
+                network_context,
+                REQUEST_LABEL,
+                OUTBOUND_LABEL,
+                DECLINED_LABEL,
+            )
+            .inc();
+            // Notify application that their request was dropped due to capacity.
+            let err = Err(RpcError::TooManyPending(self.max_concurrent_outbound_rpcs));
+            let _ = application_response_tx.try_send(err);
+            return Err(RpcError::TooManyPending(self.max_concurrent_outbound_rpcs));
+        }
+
+        let request_id = self.request_id_gen.next();
+
+        trace!(
+            NetworkSchema::new(network_context).remote_peer(peer_id),
+            "{} Sending outbound rpc stream request with request_id {} and protocol_id {} to {}",
+            network_context,
+            request_id,
+            protocol_id,
+            peer_id.short_str(),
+        );
+
+        // Start timer to collect outbound RPC latency.
+        let timer =
+            counters::outbound_rpc_request_latency(network_context, protocol_id).start_timer();
+
+        // Enqueue rpc request message onto outbound write queue. Peers that
+        // don't support streams are sent a unary request instead, and their
+        // response is handed to the application as a single frame.
+        let request = RpcRequest {
+            protocol_id,
+            request_id,
+            priority: protocol_id.priority(),
+            raw_request: Vec::from(request_data.as_ref()),
+        };
+        let message = if self.send_stream_requests {
+            NetworkMessage::RpcStreamRequest(request)
+        } else if self.send_request_deadlines {
+            NetworkMessage::RpcRequestWithDeadline(RpcRequestWithDeadline::new(request, timeout))
+        } else {
+            NetworkMessage::RpcRequest(request)
+        };
+        write_reqs_tx.send(message).await?;
+
+        // Update the outbound RPC request metrics
+        self.update_outbound_rpc_request_metrics(protocol_id, req_len);
+
+        let forward_frames = if self.send_stream_requests {
+            // Create channel over which frames are delivered to outbound_rpc_task.
+            // The number of frames per stream is bounded by MAX_RPC_STREAM_FRAMES,
+            // so the amount of buffered data is bounded as well.
+            let (frames_tx, mut frames_rx) = mpsc::unbounded::<RpcStreamResponse>();
+            self.pending_outbound_streams
+                .insert(request_id, (protocol_id, frames_tx));
+
+            // A future that forwards the frames to the application, in order, until
+            // the stream is finished or aborted.
+            let mut frames_application_tx = application_response_tx.clone();
+            async move {
+                let mut next_frame_index: u32 = 0;
+                let mut response_len: u64 = 0;
+                loop {
+                    let frame = match frames_rx.next().await {
+                        Some(frame) => frame,
+                        None => {
+                            return Err(RpcError::StreamClosed(
+                                "connection closed before the stream finished".into(),
+                            ))
+                        },
+                    };
+                    if frame.frame_index != next_frame_index {
+                        return Err(RpcError::StreamClosed(format!(
+                            "unexpected frame index, expected: {}, got: {}",
+                            next_frame_index, frame.frame_index
+                        )));
+                    }
+                    match frame.status {
+                        RpcStreamStatus::Open if next_frame_index < MAX_RPC_STREAM_FRAMES => {
+                            response_len += frame.raw_response.len() as u64;
+                            frames_application_tx
+                                .send(Ok(Bytes::from(frame.raw_response)))
+                                .await
+                                .map_err(|_| RpcError::UnexpectedResponseChannelCancel)?;
+                            next_frame_index += 1;
+                        },
+                        RpcStreamStatus::Open => {
+                            return Err(RpcError::StreamClosed(format!(
+                                "too many response frames, limit: {}",
+                                MAX_RPC_STREAM_FRAMES
+                            )))
+                        },
+                        RpcStreamStatus::Finished => return Ok(response_len),
+                        RpcStreamStatus::Aborted => {
+                            return Err(RpcError::StreamClosed(
+                                "stream aborted by the remote peer".into(),
+                            ))
+                        },
+                    }
+                }
+            }
+            .boxed()
+        } else {
+            // Wait for the unary response, like a regular outbound rpc.
+            let (response_tx, response_rx) =
+                oneshot::channel::<Result<RpcResponse, RpcApplicationError>>();
+            self.pending_outbound_rpcs
+                .insert(request_id, (protocol_id, response_tx));
+
+            // A future that forwards the response to the application as the
+            // only frame of the stream.
+            let mut frames_application_tx = application_response_tx.clone();
+            async move {
+                let response = match response_rx.await {
+                    Ok(Ok(response)) => response,
+                    Ok(Err(error)) => return Err(RpcError::from_error_response(error)),
+                    Err(oneshot::Canceled) => {
+                        return Err(RpcError::StreamClosed(
+                            "connection closed before the response arrived".into(),
+                        ))
+                    },
+                };
+                let response_len = response.raw_response.len() as u64;
+                frames_application_tx
+                    .send(Ok(Bytes::from(response.raw_response)))
+                    .await
+                    .map_err(|_| RpcError::UnexpectedResponseChannelCancel)?;
+                Ok(response_len)
+            }
+            .boxed()
+        };
+
+        // Wrap the stream with a timeout. We create the timeout out here to
+        // start the timer as soon as we push onto the queue.
+        let wait_for_stream = self
+            .time_service
+            .timeout(timeout, forward_frames)
+            .map(|result| match result {
+                Ok(result) => result,
+                Err(timeout::Elapsed) => Err(RpcError::TimedOut),
+            });
+
+        let outbound_rpc_task = async move {
+            // Always return the request_id so we can garbage collect the
+            // pending_outbound_streams map.
+            match wait_for_stream.await {
+                Ok(response_len) => {
+                    let latency = timer.stop_and_record();
+                    (request_id, Ok((latency, response_len)))
+                },
+                Err(err) => {
+                    // don't record
+                    timer.stop_and_discard();
+                    // Notify the application that the stream failed, unless
+                    // the application itself dropped the stream. RpcError is
+                    // not cloneable, so we keep a copy for our own bookkeeping.
+                    let err = match err {
+                        RpcError::UnexpectedResponseChannelCancel => err,
+                        err => {
+                            let err_copy = RpcError::Error(anyhow!(err.to_string()));
+                            let _ = application_response_tx.send(Err(err)).await;
+                            err_copy
+                        },
+                    };
+                    (request_id, Err(err))
+                },
+            }
+        };
+
+        self.outbound_rpc_tasks.push(outbound_rpc_task.boxed());
+        Ok(())
+    }
+
     /// Updates the outbound RPC request metrics (e.g., messages and bytes sent)
     fn update_outbound_rpc_request_metrics(&mut self, protocol_id: ProtocolId, data_len: u64) {
         // Update the metrics for the new RPC request
@@ -617,6 +1245,7 @@ impl OutboundRpcs {
         // Otherwise, if we received a response for our request, we will have
         // removed and triggered the oneshot from the pending map, notifying us.
         let _ = self.pending_outbound_rpcs.remove(&request_id);
+        let _ = self.pending_outbound_streams.remove(&request_id);
 
         let network_context = &self.network_context;
         let peer_id = &self.remote_peer_id;
@@ -697,7 +1326,7 @@ impl OutboundRpcs {
                 protocol_id,
                 response.raw_response.len() as u64,
             );
-            response_tx.send(response).is_err()
+            response_tx.send(Ok(response)).is_err()
         } else {
             true
         };
@@ -730,6 +1359,84 @@ impl OutboundRpcs {
         }
     }
 
+    /// Handle a new inbound `RpcErrorResponse` message. Like a regular response,
+    /// this completes the pending request with a matching request id (if any),
+    /// but surfaces the remote application error to the requester.
+    pub fn handle_inbound_error_response(&mut self, response: RpcErrorResponse) {
+        let network_context = &self.network_context;
+        let peer_id = &self.remote_peer_id;
+        let request_id = response.request_id;
+
+        let is_canceled = if let Some((protocol_id, response_tx)) =
+            self.pending_outbound_rpcs.remove(&request_id)
+        {
+            self.update_inbound_rpc_response_metrics(
+                protocol_id,
+                response.error.message.len() as u64,
+            );
+            response_tx.send(Err(response.error)).is_err()
+        } else {
+            true
+        };
+
+        if is_canceled {
+            debug!(
+                NetworkSchema::new(network_context).remote_peer(peer_id),
+                request_id = request_id,
+                "{} Received error response for expired request_id {} from {}. Discarding.",
+                network_context,
+                request_id,
+                peer_id.short_str(),
+            );
+            counters::rpc_messages(
+                network_context,
+                RESPONSE_LABEL,
+                INBOUND_LABEL,
+                EXPIRED_LABEL,
+            )
+            .inc();
+        }
+    }
+
+    /// Handle a new inbound `RpcStreamResponse` frame. If we have a pending
+    /// stream with a matching request id, the frame is forwarded to the
+    /// corresponding task, which validates it and hands it to the application.
+    pub fn handle_inbound_stream_response(&mut self, response: RpcStreamResponse) {
+        let network_context = &self.network_context;
+        let peer_id = &self.remote_peer_id;
+        let request_id = response.request_id;
+
+        let is_canceled = match self.pending_outbound_streams.get(&request_id) {
+            Some((protocol_id, frames_tx)) => {
+                network_application_inbound_traffic(
+                    self.network_context,
+                    *protocol_id,
+                    response.raw_response.len() as u64,
+                );
+                frames_tx.unbounded_send(response).is_err()
+            },
+            None => true,
+        };
+
+        if is_canceled {
+            debug!(
+                NetworkSchema::new(network_context).remote_peer(peer_id),
+                request_id = request_id,
+                "{} Received stream frame for expired request_id {} from {}. Discarding.",
+                network_context,
+                request_id,
+                peer_id.short_str(),
+            );
+            counters::rpc_messages(
+                network_context,
+                RESPONSE_LABEL,
+                INBOUND_LABEL,
+                EXPIRED_LABEL,
+            )
+            .inc();
+        }
+    }
+
     /// Updates the inbound RPC response metrics (e.g., messages and bytes received)
     fn update_inbound_rpc_response_metrics(&self, protocol_id: ProtocolId, data_len: u64) {
         // Update the metrics for the new RPC response
@@ -0,0 +1,140 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A token bucket rate limiter for inbound rpc requests.
+//!
+//! Each [`InboundRpcs`](super::InboundRpcs) queue (i.e., each remote peer)
+//! owns a rate limiter, which holds one token bucket per protocol. A request
+//! consumes one token, and tokens are refilled continuously at the configured
+//! rate, up to the configured bucket size (i.e., the maximum burst).
+
+use crate::ProtocolId;
+use aptos_config::config::RpcRateLimitConfig;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// The token bucket for a single protocol
+struct TokenBucket {
+    /// The number of available tokens (may be fractional between refills)
+    tokens: f64,
+    /// The last time the bucket was refilled
+    last_refill_time: Instant,
+}
+
+pub struct InboundRpcRateLimiter {
+    config: RpcRateLimitConfig,
+    time_service: TimeService,
+    buckets: HashMap<ProtocolId, TokenBucket>,
+}
+
+impl InboundRpcRateLimiter {
+    /// Creates a rate limiter for the config, which is expected to be valid
+    /// (i.e., the config sanitizer checks the rate and bucket size).
+    pub fn new(config: RpcRateLimitConfig, time_service: TimeService) -> Self {
+        Self {
+            config,
+            time_service,
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Attempts to acquire a token for a request of the given protocol. If the
+    /// bucket is empty, returns the time until the next token is available.
+    pub fn try_acquire(&mut self, protocol_id: ProtocolId) -> Result<(), Duration> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        // Refill the bucket based on the time elapsed since the last refill.
+        // New buckets start full, so that new connections aren't penalized.
+        let now = self.time_service.now();
+        let rate = self.config.request_bucket_rate as f64;
+        let size = self.config.request_bucket_size as f64;
+        let bucket = self
+            .buckets
+            .entry(protocol_id)
+            .or_insert_with(|| TokenBucket {
+                tokens: size,
+                last_refill_time: now,
+            });
+        let elapsed = now.saturating_duration_since(bucket.last_refill_time);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * rate).min(size);
+        bucket.last_refill_time = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_rate_limiter(rate: usize, size: usize) -> (InboundRpcRateLimiter, TimeService) {
+        let config = RpcRateLimitConfig {
+            request_bucket_rate: rate,
+            request_bucket_size: size,
+            enabled: true,
+        };
+        let time_service = TimeService::mock();
+        (
+            InboundRpcRateLimiter::new(config, time_service.clone()),
+            time_service,
+        )
+    }
+
+    #[test]
+    fn test_burst_and_refill() {
+        let (mut rate_limiter, time_service) = create_rate_limiter(10, 5);
+        let protocol_id = ProtocolId::ConsensusRpcBcs;
+
+        // The full burst is allowed, but no more
+        for _ in 0..5 {
+            rate_limiter.try_acquire(protocol_id).unwrap();
+        }
+        let retry_after = rate_limiter.try_acquire(protocol_id).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(100));
+
+        // A single token is refilled after the retry time
+        time_service.into_mock().advance(Duration::from_millis(100));
+        rate_limiter.try_acquire(protocol_id).unwrap();
+        rate_limiter.try_acquire(protocol_id).unwrap_err();
+    }
+
+    #[test]
+    fn test_protocols_are_independent() {
+        let (mut rate_limiter, _) = create_rate_limiter(1, 1);
+
+        rate_limiter
+            .try_acquire(ProtocolId::ConsensusRpcBcs)
+            .unwrap();
+        rate_limiter
+            .try_acquire(ProtocolId::ConsensusRpcBcs)
+            .unwrap_err();
+        rate_limiter
+            .try_acquire(ProtocolId::StorageServiceRpc)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_disabled() {
+        let config = RpcRateLimitConfig {
+            request_bucket_rate: 1,
+            request_bucket_size: 1,
+            enabled: false,
+        };
+        let mut rate_limiter = InboundRpcRateLimiter::new(config, TimeService::mock());
+        for _ in 0..10 {
+            rate_limiter
+                .try_acquire(ProtocolId::ConsensusRpcBcs)
+                .unwrap();
+        }
+    }
+}
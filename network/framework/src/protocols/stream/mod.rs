@@ -109,7 +109,10 @@ pub struct InboundStream {
 impl InboundStream {
     fn new(header: StreamHeader, max_fragments: usize) -> anyhow::Result<Self> {
         ensure!(
-            !matches!(header.message, NetworkMessage::Error(_)),
+            !matches!(
+                header.message,
+                NetworkMessage::Error(_) | NetworkMessage::RpcErrorResponse(_)
+            ),
             "Error message is not expected for stream"
         );
         ensure!(
@@ -142,6 +145,14 @@ impl InboundStream {
             NetworkMessage::RpcRequest(request) => request.raw_request.append(raw_data),
             NetworkMessage::RpcResponse(response) => response.raw_response.append(raw_data),
             NetworkMessage::DirectSendMsg(message) => message.raw_msg.append(raw_data),
+            NetworkMessage::RpcStreamRequest(request) => request.raw_request.append(raw_data),
+            NetworkMessage::RpcStreamResponse(response) => response.raw_response.append(raw_data),
+            NetworkMessage::RpcErrorResponse(_) => {
+                panic!("StreamHeader with RpcErrorResponse should be rejected")
+            },
+            NetworkMessage::RpcRequestWithDeadline(request) => {
+                request.request.raw_request.append(raw_data)
+            },
         }
         Ok(self.current_fragment_id == self.num_fragments)
     }
@@ -207,6 +218,18 @@ impl OutboundStream {
             NetworkMessage::DirectSendMsg(message) => {
                 message.raw_msg.split_off(self.max_frame_size)
             },
+            NetworkMessage::RpcStreamRequest(request) => {
+                request.raw_request.split_off(self.max_frame_size)
+            },
+            NetworkMessage::RpcStreamResponse(response) => {
+                response.raw_response.split_off(self.max_frame_size)
+            },
+            NetworkMessage::RpcErrorResponse(_) => {
+                unreachable!("NetworkMessage::RpcErrorResponse should always fit in a single frame")
+            },
+            NetworkMessage::RpcRequestWithDeadline(request) => {
+                request.request.raw_request.split_off(self.max_frame_size)
+            },
         };
         let chunks = rest.chunks(self.max_frame_size);
         ensure!(
@@ -13,9 +13,15 @@
 //!
 //! [AptosNet Handshake v1 Specification]: https://github.com/aptos-labs/aptos-core/blob/main/specifications/network/handshake-v1.md
 
-use crate::counters::{start_serialization_timer, DESERIALIZATION_LABEL, SERIALIZATION_LABEL};
+use crate::{
+    counters::{
+        start_serialization_timer, update_compression_byte_count, COMPRESSED_BYTES_LABEL,
+        DESERIALIZATION_LABEL, RAW_BYTES_LABEL, SERIALIZATION_LABEL, UNCOMPRESSED_BYTES_LABEL,
+    },
+    protocols::wire::messaging::v1::{Priority, HIGH_PRIORITY, LOW_PRIORITY, NORMAL_PRIORITY},
+};
 use anyhow::anyhow;
-use aptos_compression::client::CompressionClient;
+use aptos_compression::{client::CompressionClient, CompressionAlgorithm};
 use aptos_config::{config::MAX_APPLICATION_MESSAGE_SIZE, network_id::NetworkId};
 use aptos_types::chain_id::ChainId;
 #[cfg(any(test, feature = "fuzzing"))]
@@ -39,6 +45,15 @@ mod test;
 pub const USER_INPUT_RECURSION_LIMIT: usize = 32;
 pub const RECURSION_LIMIT: usize = 64;
 
+/// Messages smaller than this (once serialized) are not worth compressing
+/// and are sent as-is by protocols that use adaptive compression.
+pub const COMPRESSION_THRESHOLD_BYTES: usize = 4 * 1024; /* 4 KiB */
+
+/// The flag prepended to adaptively compressed messages to indicate
+/// whether the remaining bytes are compressed or not.
+const UNCOMPRESSED_FLAG: u8 = 0;
+const COMPRESSED_FLAG: u8 = 1;
+
 /// Unique identifier associated with each application protocol.
 #[repr(u8)]
 #[derive(Clone, Copy, Hash, Eq, PartialEq, Deserialize, Serialize)]
@@ -72,12 +87,18 @@ pub enum ProtocolId {
     JWKConsensusRpcBcs = 25,
     JWKConsensusRpcJson = 26,
     ConsensusObserver = 27,
+    ConsensusRpcZstd = 28,
+    ConsensusDirectSendZstd = 29,
+    CommitStreamDirectSend = 30,
 }
 
 /// The encoding types for Protocols
 enum Encoding {
     Bcs(usize),
     CompressedBcs(usize),
+    /// Compresses messages with the given algorithm, but only if they are
+    /// larger than the compression threshold (see [`COMPRESSION_THRESHOLD_BYTES`]).
+    AdaptiveCompressedBcs(usize, CompressionAlgorithm),
     Json,
 }
 
@@ -113,6 +134,9 @@ impl ProtocolId {
             JWKConsensusRpcBcs => "JWKConsensusRpcBcs",
             JWKConsensusRpcJson => "JWKConsensusRpcJson",
             ConsensusObserver => "ConsensusObserver",
+            ConsensusRpcZstd => "ConsensusRpcZstd",
+            ConsensusDirectSendZstd => "ConsensusDirectSendZstd",
+            CommitStreamDirectSend => "CommitStreamDirectSend",
         }
     }
 
@@ -147,9 +171,51 @@ impl ProtocolId {
             ProtocolId::JWKConsensusRpcBcs,
             ProtocolId::JWKConsensusRpcJson,
             ProtocolId::ConsensusObserver,
+            ProtocolId::ConsensusRpcZstd,
+            ProtocolId::ConsensusDirectSendZstd,
+            ProtocolId::CommitStreamDirectSend,
         ]
     }
 
+    /// Returns the priority with which messages of this protocol are sent.
+    /// Consensus traffic is prioritized over regular traffic, which is in turn
+    /// prioritized over bulk data transfers (e.g., state sync).
+    pub fn priority(self) -> Priority {
+        use ProtocolId::*;
+        match self {
+            ConsensusRpcBcs
+            | ConsensusDirectSendBcs
+            | ConsensusDirectSendJson
+            | ConsensusRpcJson
+            | ConsensusRpcCompressed
+            | ConsensusDirectSendCompressed
+            | ConsensusRpcZstd
+            | ConsensusDirectSendZstd
+            | DKGDirectSendCompressed
+            | DKGDirectSendBcs
+            | DKGDirectSendJson
+            | DKGRpcCompressed
+            | DKGRpcBcs
+            | DKGRpcJson
+            | JWKConsensusDirectSendCompressed
+            | JWKConsensusDirectSendBcs
+            | JWKConsensusDirectSendJson
+            | JWKConsensusRpcCompressed
+            | JWKConsensusRpcBcs
+            | JWKConsensusRpcJson
+            | HealthCheckerRpc => HIGH_PRIORITY,
+            MempoolDirectSend
+            | MempoolRpc
+            | DiscoveryDirectSend
+            | PeerMonitoringServiceRpc
+            | ConsensusObserver
+            | CommitStreamDirectSend
+            | NetbenchDirectSend
+            | NetbenchRpc => NORMAL_PRIORITY,
+            StateSyncDirectSend | StorageServiceRpc => LOW_PRIORITY,
+        }
+    }
+
     /// Specifies how to encode messages for a given `ProtocolId`
     fn encoding(self) -> Encoding {
         match self {
@@ -157,6 +223,9 @@ impl ProtocolId {
             ProtocolId::ConsensusDirectSendCompressed | ProtocolId::ConsensusRpcCompressed => {
                 Encoding::CompressedBcs(RECURSION_LIMIT)
             },
+            ProtocolId::ConsensusDirectSendZstd | ProtocolId::ConsensusRpcZstd => {
+                Encoding::AdaptiveCompressedBcs(RECURSION_LIMIT, CompressionAlgorithm::Zstd)
+            },
             ProtocolId::DKGDirectSendCompressed | ProtocolId::DKGRpcCompressed => {
                 Encoding::CompressedBcs(RECURSION_LIMIT)
             },
@@ -171,9 +240,10 @@ impl ProtocolId {
     /// Returns the compression client label based on the current protocol id
     fn get_compression_client(self) -> CompressionClient {
         match self {
-            ProtocolId::ConsensusDirectSendCompressed | ProtocolId::ConsensusRpcCompressed => {
-                CompressionClient::Consensus
-            },
+            ProtocolId::ConsensusDirectSendCompressed
+            | ProtocolId::ConsensusRpcCompressed
+            | ProtocolId::ConsensusDirectSendZstd
+            | ProtocolId::ConsensusRpcZstd => CompressionClient::Consensus,
             ProtocolId::MempoolDirectSend => CompressionClient::Mempool,
             ProtocolId::DKGDirectSendCompressed | ProtocolId::DKGRpcCompressed => {
                 CompressionClient::DKG
@@ -206,6 +276,10 @@ impl ProtocolId {
                 )
                 .map_err(|e| anyhow!("{:?}", e))
             },
+            Encoding::AdaptiveCompressedBcs(limit, algorithm) => {
+                let bcs_bytes = self.bcs_encode(value, limit)?;
+                self.adaptive_compress(bcs_bytes, algorithm)
+            },
             Encoding::Json => serde_json::to_vec(value).map_err(|e| anyhow!("{:?}", e)),
         };
 
@@ -236,6 +310,10 @@ impl ProtocolId {
                 .map_err(|e| anyhow! {"{:?}", e})?;
                 self.bcs_decode(&raw_bytes, limit)
             },
+            Encoding::AdaptiveCompressedBcs(limit, algorithm) => {
+                let raw_bytes = self.adaptive_decompress(bytes, algorithm)?;
+                self.bcs_decode(&raw_bytes, limit)
+            },
             Encoding::Json => serde_json::from_slice(bytes).map_err(|e| anyhow!("{:?}", e)),
         };
 
@@ -247,6 +325,54 @@ impl ProtocolId {
         result
     }
 
+    /// Compresses the given bytes if they are larger than the compression
+    /// threshold, and prepends a flag indicating whether compression was used.
+    fn adaptive_compress(
+        &self,
+        raw_bytes: Vec<u8>,
+        algorithm: CompressionAlgorithm,
+    ) -> anyhow::Result<Vec<u8>> {
+        let num_raw_bytes = raw_bytes.len();
+        if num_raw_bytes < COMPRESSION_THRESHOLD_BYTES {
+            update_compression_byte_count(*self, UNCOMPRESSED_BYTES_LABEL, num_raw_bytes);
+            return Ok(prepend_flag(UNCOMPRESSED_FLAG, &raw_bytes));
+        }
+
+        let compressed_bytes = aptos_compression::compress_with_algorithm(
+            raw_bytes,
+            self.get_compression_client(),
+            MAX_APPLICATION_MESSAGE_SIZE,
+            algorithm,
+        )
+        .map_err(|e| anyhow!("{:?}", e))?;
+        update_compression_byte_count(*self, RAW_BYTES_LABEL, num_raw_bytes);
+        update_compression_byte_count(*self, COMPRESSED_BYTES_LABEL, compressed_bytes.len());
+        Ok(prepend_flag(COMPRESSED_FLAG, &compressed_bytes))
+    }
+
+    /// Strips the compression flag from the given bytes, and decompresses
+    /// the remaining bytes (if required).
+    fn adaptive_decompress(
+        &self,
+        bytes: &[u8],
+        algorithm: CompressionAlgorithm,
+    ) -> anyhow::Result<Vec<u8>> {
+        match bytes.split_first() {
+            Some((&UNCOMPRESSED_FLAG, raw_bytes)) => Ok(raw_bytes.to_vec()),
+            Some((&COMPRESSED_FLAG, compressed_bytes)) => {
+                aptos_compression::decompress_with_algorithm(
+                    &compressed_bytes.to_vec(),
+                    self.get_compression_client(),
+                    MAX_APPLICATION_MESSAGE_SIZE,
+                    algorithm,
+                )
+                .map_err(|e| anyhow!("{:?}", e))
+            },
+            Some((flag, _)) => Err(anyhow!("Unknown compression flag: {}", flag)),
+            None => Err(anyhow!("Missing compression flag in empty message!")),
+        }
+    }
+
     /// Serializes the value using BCS encoding (with a specified limit)
     fn bcs_encode<T: Serialize>(&self, value: &T, limit: usize) -> anyhow::Result<Vec<u8>> {
         bcs::to_bytes_with_limit(value, limit).map_err(|e| anyhow!("{:?}", e))
@@ -258,6 +384,14 @@ impl ProtocolId {
     }
 }
 
+/// Returns a copy of the given bytes, prefixed by the given flag
+fn prepend_flag(flag: u8, bytes: &[u8]) -> Vec<u8> {
+    let mut flagged_bytes = Vec::with_capacity(bytes.len() + 1);
+    flagged_bytes.push(flag);
+    flagged_bytes.extend_from_slice(bytes);
+    flagged_bytes
+}
+
 impl fmt::Debug for ProtocolId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self)
@@ -329,6 +463,16 @@ impl ProtocolIdSet {
     pub fn insert(&mut self, protocol: ProtocolId) {
         self.0.set(protocol as u16)
     }
+
+    /// Returns if the messaging capability is set.
+    pub fn supports(&self, capability: MessagingCapability) -> bool {
+        self.0.is_set(capability.bit())
+    }
+
+    /// Insert a new messaging capability into the set.
+    pub fn insert_capability(&mut self, capability: MessagingCapability) {
+        self.0.set(capability.bit())
+    }
 }
 
 impl FromIterator<ProtocolId> for ProtocolIdSet {
@@ -343,6 +487,36 @@ impl<'a> FromIterator<&'a ProtocolId> for ProtocolIdSet {
     }
 }
 
+//
+// MessagingCapability
+//
+
+/// Optional features of the messaging protocol that both peers must support
+/// before they can be used on a connection (e.g., new `NetworkMessage` types).
+/// Capabilities are advertised in the `ProtocolIdSet` of the handshake, using
+/// the highest bits (counting down), which never map to a `ProtocolId`. Peers
+/// that don't know about a capability ignore its bit, so the capability is
+/// negotiated without changing the handshake format.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MessagingCapability {
+    /// The peer understands `RpcRequestWithDeadline` messages
+    RpcRequestDeadlines,
+    /// The peer understands `RpcStreamRequest` and `RpcStreamResponse` messages
+    RpcStreams,
+    /// The peer understands `RpcErrorResponse` messages
+    RpcErrorResponses,
+}
+
+impl MessagingCapability {
+    fn bit(self) -> u16 {
+        match self {
+            MessagingCapability::RpcRequestDeadlines => u8::MAX as u16,
+            MessagingCapability::RpcStreams => u8::MAX as u16 - 1,
+            MessagingCapability::RpcErrorResponses => u8::MAX as u16 - 2,
+        }
+    }
+}
+
 //
 // MessageProtocolVersion
 //
@@ -445,12 +619,12 @@ impl HandshakeMsg {
         }
 
         // find the greatest common MessagingProtocolVersion where we both support
-        // at least one common ProtocolId.
+        // at least one common ProtocolId (messaging capabilities don't count).
         for (our_handshake_version, our_protocols) in self.supported_protocols.iter().rev() {
             if let Some(their_protocols) = other.supported_protocols.get(our_handshake_version) {
                 let common_protocols = our_protocols.intersect(their_protocols);
 
-                if !common_protocols.is_empty() {
+                if common_protocols.iter().next().is_some() {
                     return Ok((*our_handshake_version, common_protocols));
                 }
             }
@@ -183,3 +183,85 @@ fn ignore_unknown_protocols() {
         ProtocolIdSet::empty(),
     );
 }
+
+#[test]
+fn negotiate_messaging_capabilities() {
+    let protos = ProtocolIdSet::from_iter([ProtocolId::MempoolDirectSend]);
+    let mut protos_with_capability = protos.clone();
+    protos_with_capability.insert_capability(MessagingCapability::RpcRequestDeadlines);
+
+    // Capabilities are not protocols
+    assert!(protos_with_capability.supports(MessagingCapability::RpcRequestDeadlines));
+    assert!(!protos.supports(MessagingCapability::RpcRequestDeadlines));
+    assert_eq!(
+        ProtocolIdSet::from_iter(protos_with_capability.iter()),
+        protos
+    );
+
+    // Case 1: both peers support the capability
+    let hs_with_capability = HandshakeMsg::from_supported(protos_with_capability.clone());
+    let (_, common_protos) = hs_with_capability
+        .perform_handshake(&hs_with_capability)
+        .unwrap();
+    assert!(common_protos.supports(MessagingCapability::RpcRequestDeadlines));
+
+    // Case 2: only one of the peers supports the capability
+    let hs = HandshakeMsg::from_supported(protos);
+    let (_, common_protos) = hs_with_capability.perform_handshake(&hs).unwrap();
+    assert!(!common_protos.supports(MessagingCapability::RpcRequestDeadlines));
+    let (_, common_protos) = hs.perform_handshake(&hs_with_capability).unwrap();
+    assert!(!common_protos.supports(MessagingCapability::RpcRequestDeadlines));
+
+    // Capabilities are negotiated independently of each other
+    let mut protos_with_streams = protos_with_capability.clone();
+    protos_with_streams.insert_capability(MessagingCapability::RpcStreams);
+    protos_with_streams.insert_capability(MessagingCapability::RpcErrorResponses);
+    let (_, common_protos) = HandshakeMsg::from_supported(protos_with_streams)
+        .perform_handshake(&hs_with_capability)
+        .unwrap();
+    assert!(common_protos.supports(MessagingCapability::RpcRequestDeadlines));
+    assert!(!common_protos.supports(MessagingCapability::RpcStreams));
+    assert!(!common_protos.supports(MessagingCapability::RpcErrorResponses));
+
+    // Case 3: the peers only have the capability in common
+    let mut only_capability = ProtocolIdSet::from_iter([ProtocolId::StateSyncDirectSend]);
+    only_capability.insert_capability(MessagingCapability::RpcRequestDeadlines);
+    assert_eq!(
+        hs_with_capability
+            .perform_handshake(&HandshakeMsg::from_supported(only_capability))
+            .unwrap_err(),
+        HandshakeError::NoCommonProtocols,
+    );
+}
+
+#[test]
+fn test_adaptive_compression() {
+    let protocol_id = ProtocolId::ConsensusDirectSendZstd;
+
+    // Small messages are sent uncompressed
+    let small_message = vec![7u8; 10];
+    let bytes = protocol_id.to_bytes(&small_message).unwrap();
+    assert_eq!(bytes[0], UNCOMPRESSED_FLAG);
+    assert_eq!(
+        &bytes[1..],
+        bcs::to_bytes(&small_message).unwrap().as_slice()
+    );
+    assert_eq!(
+        protocol_id.from_bytes::<Vec<u8>>(&bytes).unwrap(),
+        small_message
+    );
+
+    // Large messages are compressed
+    let large_message = vec![7u8; 4 * COMPRESSION_THRESHOLD_BYTES];
+    let bytes = protocol_id.to_bytes(&large_message).unwrap();
+    assert_eq!(bytes[0], COMPRESSED_FLAG);
+    assert!(bytes.len() < large_message.len());
+    assert_eq!(
+        protocol_id.from_bytes::<Vec<u8>>(&bytes).unwrap(),
+        large_message
+    );
+
+    // Unknown and missing flags are rejected
+    protocol_id.from_bytes::<Vec<u8>>(&[2, 0]).unwrap_err();
+    protocol_id.from_bytes::<Vec<u8>>(&[]).unwrap_err();
+}
@@ -10,7 +10,9 @@
 //! describes in greater detail how these messages are sent and received
 //! over-the-wire.
 
-use crate::protocols::{stream::StreamMessage, wire::handshake::v1::ProtocolId};
+use crate::protocols::{
+    rpc::error::RpcApplicationError, stream::StreamMessage, wire::handshake::v1::ProtocolId,
+};
 use bytes::Bytes;
 use futures::{
     io::{AsyncRead, AsyncWrite},
@@ -25,6 +27,7 @@ use std::{
     io,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 use thiserror::Error;
 use tokio_util::{
@@ -43,6 +46,10 @@ pub enum NetworkMessage {
     RpcRequest(RpcRequest),
     RpcResponse(RpcResponse),
     DirectSendMsg(DirectSendMsg),
+    RpcStreamRequest(RpcRequest),
+    RpcStreamResponse(RpcStreamResponse),
+    RpcErrorResponse(RpcErrorResponse),
+    RpcRequestWithDeadline(RpcRequestWithDeadline),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
@@ -60,6 +67,25 @@ impl NetworkMessage {
             NetworkMessage::RpcRequest(request) => request.raw_request.len(),
             NetworkMessage::RpcResponse(response) => response.raw_response.len(),
             NetworkMessage::DirectSendMsg(message) => message.raw_msg.len(),
+            NetworkMessage::RpcStreamRequest(request) => request.raw_request.len(),
+            NetworkMessage::RpcStreamResponse(response) => response.raw_response.len(),
+            NetworkMessage::RpcErrorResponse(response) => response.error.message.len(),
+            NetworkMessage::RpcRequestWithDeadline(request) => request.request.raw_request.len(),
+        }
+    }
+
+    /// The priority with which the message should be scheduled onto the wire.
+    /// Error messages are tiny and always sent first.
+    pub fn priority(&self) -> Priority {
+        match self {
+            NetworkMessage::Error(_) => HIGH_PRIORITY,
+            NetworkMessage::RpcRequest(request) => request.priority,
+            NetworkMessage::RpcResponse(response) => response.priority,
+            NetworkMessage::DirectSendMsg(message) => message.priority,
+            NetworkMessage::RpcStreamRequest(request) => request.priority,
+            NetworkMessage::RpcStreamResponse(response) => response.priority,
+            NetworkMessage::RpcErrorResponse(response) => response.priority,
+            NetworkMessage::RpcRequestWithDeadline(request) => request.request.priority,
         }
     }
 }
@@ -103,6 +129,14 @@ pub type RequestId = u32;
 /// Create alias Priority for u8.
 pub type Priority = u8;
 
+/// Priority for latency sensitive traffic, e.g., consensus messages.
+pub const HIGH_PRIORITY: Priority = 255;
+/// Priority for regular traffic, e.g., mempool broadcasts.
+pub const NORMAL_PRIORITY: Priority = 128;
+/// Priority for bulk traffic, e.g., state sync chunks. This is also the
+/// priority of messages from peers that don't set one.
+pub const LOW_PRIORITY: Priority = 0;
+
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 pub struct RpcRequest {
@@ -117,6 +151,33 @@ pub struct RpcRequest {
     pub raw_request: Vec<u8>,
 }
 
+/// An `RpcRequest` that also carries the requester's deadline, so that the
+/// responder can drop the request once the requester has given up on it.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct RpcRequestWithDeadline {
+    /// The rpc request.
+    pub request: RpcRequest,
+    /// The time remaining (in milliseconds) until the requester's deadline, as
+    /// of when the request was sent. The deadline is relative, so the clocks of
+    /// the two peers don't need to be synchronized.
+    pub remaining_time_ms: u64,
+}
+
+impl RpcRequestWithDeadline {
+    pub fn new(request: RpcRequest, remaining_time: Duration) -> Self {
+        Self {
+            request,
+            remaining_time_ms: u64::try_from(remaining_time.as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// The time remaining until the requester's deadline
+    pub fn remaining_time(&self) -> Duration {
+        Duration::from_millis(self.remaining_time_ms)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 pub struct RpcResponse {
@@ -130,6 +191,49 @@ pub struct RpcResponse {
     pub raw_response: Vec<u8>,
 }
 
+/// The state of a streaming rpc response after a given frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub enum RpcStreamStatus {
+    /// The frame carries a response chunk and more frames may follow.
+    Open,
+    /// The responder has sent all of its frames. This frame carries no payload.
+    Finished,
+    /// The responder failed or gave up on the stream. This frame carries no payload.
+    Aborted,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct RpcStreamResponse {
+    /// RequestId for corresponding stream request. This is copied as is from the RpcRequest.
+    pub request_id: RequestId,
+    /// Response priority in the range 0..=255. This will likely be same as the priority of
+    /// corresponding request.
+    pub priority: Priority,
+    /// Index of this frame within the stream, starting at 0. Frames are delivered in order.
+    pub frame_index: u32,
+    /// Whether the stream remains open after this frame.
+    pub status: RpcStreamStatus,
+    /// Response chunk payload. Empty unless `status` is `Open`.
+    #[serde(with = "serde_bytes")]
+    pub raw_response: Vec<u8>,
+}
+
+/// Sent in place of an `RpcResponse` when the application layer failed to
+/// handle the corresponding request.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct RpcErrorResponse {
+    /// RequestId for corresponding request. This is copied as is from the RpcRequest.
+    pub request_id: RequestId,
+    /// Response priority in the range 0..=255. This will likely be same as the priority of
+    /// corresponding request.
+    pub priority: Priority,
+    /// The error returned by the application layer.
+    pub error: RpcApplicationError,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 pub struct DirectSendMsg {
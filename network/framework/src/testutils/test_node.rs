@@ -337,6 +337,9 @@ pub trait TestNode: ApplicationNode + Sync {
             PeerManagerRequest::SendDirectSend(peer_id, message) => {
                 (peer_id, message.protocol_id, message.mdata)
             },
+            PeerManagerRequest::SendRpcStream(_, _) => {
+                panic!("Unexpected rpc stream request!")
+            },
         }
     }
 
@@ -354,6 +357,9 @@ pub trait TestNode: ApplicationNode + Sync {
             PeerManagerRequest::SendDirectSend(peer_id, msg) => {
                 (peer_id, msg.protocol_id, msg.mdata, None)
             },
+            PeerManagerRequest::SendRpcStream(_, _) => {
+                panic!("Unexpected rpc stream request!")
+            },
         };
 
         let sender_peer_network_id = self.peer_network_id(network_id);
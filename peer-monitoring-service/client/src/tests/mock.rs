@@ -157,6 +157,9 @@ impl MockMonitoringServer {
             Some(PeerManagerRequest::SendDirectSend(_, _)) => {
                 panic!("Unexpected direct send message received!")
             },
+            Some(PeerManagerRequest::SendRpcStream(_, _)) => {
+                panic!("Unexpected rpc stream request received!")
+            },
             None => None,
         }
     }
@@ -28,32 +28,50 @@ class TransactionsInStorage(_message.Message):
     ) -> None: ...
 
 class GetTransactionsRequest(_message.Message):
-    __slots__ = ["starting_version", "transactions_count", "batch_size"]
+    __slots__ = [
+        "starting_version",
+        "transactions_count",
+        "batch_size",
+        "resume_token",
+        "accept_compressed_transactions",
+    ]
     STARTING_VERSION_FIELD_NUMBER: _ClassVar[int]
     TRANSACTIONS_COUNT_FIELD_NUMBER: _ClassVar[int]
     BATCH_SIZE_FIELD_NUMBER: _ClassVar[int]
+    RESUME_TOKEN_FIELD_NUMBER: _ClassVar[int]
+    ACCEPT_COMPRESSED_TRANSACTIONS_FIELD_NUMBER: _ClassVar[int]
     starting_version: int
     transactions_count: int
     batch_size: int
+    resume_token: str
+    accept_compressed_transactions: bool
     def __init__(
         self,
         starting_version: _Optional[int] = ...,
         transactions_count: _Optional[int] = ...,
         batch_size: _Optional[int] = ...,
+        resume_token: _Optional[str] = ...,
+        accept_compressed_transactions: _Optional[bool] = ...,
     ) -> None: ...
 
 class TransactionsResponse(_message.Message):
-    __slots__ = ["transactions", "chain_id"]
+    __slots__ = ["transactions", "chain_id", "resume_token", "compressed_transactions"]
     TRANSACTIONS_FIELD_NUMBER: _ClassVar[int]
     CHAIN_ID_FIELD_NUMBER: _ClassVar[int]
+    RESUME_TOKEN_FIELD_NUMBER: _ClassVar[int]
+    COMPRESSED_TRANSACTIONS_FIELD_NUMBER: _ClassVar[int]
     transactions: _containers.RepeatedCompositeFieldContainer[
         _transaction_pb2.Transaction
     ]
     chain_id: int
+    resume_token: str
+    compressed_transactions: _containers.RepeatedScalarFieldContainer[bytes]
     def __init__(
         self,
         transactions: _Optional[
             _Iterable[_Union[_transaction_pb2.Transaction, _Mapping]]
         ] = ...,
         chain_id: _Optional[int] = ...,
+        resume_token: _Optional[str] = ...,
+        compressed_transactions: _Optional[_Iterable[bytes]] = ...,
     ) -> None: ...
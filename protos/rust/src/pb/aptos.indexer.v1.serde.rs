@@ -19,6 +19,12 @@ impl serde::Serialize for GetTransactionsRequest {
         if self.batch_size.is_some() {
             len += 1;
         }
+        if self.resume_token.is_some() {
+            len += 1;
+        }
+        if self.accept_compressed_transactions.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.GetTransactionsRequest", len)?;
         if let Some(v) = self.starting_version.as_ref() {
             struct_ser.serialize_field("startingVersion", ToString::to_string(&v).as_str())?;
@@ -29,6 +35,12 @@ impl serde::Serialize for GetTransactionsRequest {
         if let Some(v) = self.batch_size.as_ref() {
             struct_ser.serialize_field("batchSize", ToString::to_string(&v).as_str())?;
         }
+        if let Some(v) = self.resume_token.as_ref() {
+            struct_ser.serialize_field("resumeToken", v)?;
+        }
+        if let Some(v) = self.accept_compressed_transactions.as_ref() {
+            struct_ser.serialize_field("acceptCompressedTransactions", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -45,6 +57,10 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsRequest {
             "transactionsCount",
             "batch_size",
             "batchSize",
+            "resume_token",
+            "resumeToken",
+            "accept_compressed_transactions",
+            "acceptCompressedTransactions",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -52,6 +68,8 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsRequest {
             StartingVersion,
             TransactionsCount,
             BatchSize,
+            ResumeToken,
+            AcceptCompressedTransactions,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -76,6 +94,8 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsRequest {
                             "startingVersion" | "starting_version" => Ok(GeneratedField::StartingVersion),
                             "transactionsCount" | "transactions_count" => Ok(GeneratedField::TransactionsCount),
                             "batchSize" | "batch_size" => Ok(GeneratedField::BatchSize),
+                            "resumeToken" | "resume_token" => Ok(GeneratedField::ResumeToken),
+                            "acceptCompressedTransactions" | "accept_compressed_transactions" => Ok(GeneratedField::AcceptCompressedTransactions),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -98,6 +118,8 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsRequest {
                 let mut starting_version__ = None;
                 let mut transactions_count__ = None;
                 let mut batch_size__ = None;
+                let mut resume_token__ = None;
+                let mut accept_compressed_transactions__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
                         GeneratedField::StartingVersion => {
@@ -124,12 +146,26 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsRequest {
                                 map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
                             ;
                         }
+                        GeneratedField::ResumeToken => {
+                            if resume_token__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resumeToken"));
+                            }
+                            resume_token__ = map.next_value()?;
+                        }
+                        GeneratedField::AcceptCompressedTransactions => {
+                            if accept_compressed_transactions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("acceptCompressedTransactions"));
+                            }
+                            accept_compressed_transactions__ = map.next_value()?;
+                        }
                     }
                 }
                 Ok(GetTransactionsRequest {
                     starting_version: starting_version__,
                     transactions_count: transactions_count__,
                     batch_size: batch_size__,
+                    resume_token: resume_token__,
+                    accept_compressed_transactions: accept_compressed_transactions__,
                 })
             }
         }
@@ -261,6 +297,12 @@ impl serde::Serialize for TransactionsResponse {
         if self.chain_id.is_some() {
             len += 1;
         }
+        if self.resume_token.is_some() {
+            len += 1;
+        }
+        if !self.compressed_transactions.is_empty() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("aptos.indexer.v1.TransactionsResponse", len)?;
         if !self.transactions.is_empty() {
             struct_ser.serialize_field("transactions", &self.transactions)?;
@@ -268,6 +310,12 @@ impl serde::Serialize for TransactionsResponse {
         if let Some(v) = self.chain_id.as_ref() {
             struct_ser.serialize_field("chainId", ToString::to_string(&v).as_str())?;
         }
+        if let Some(v) = self.resume_token.as_ref() {
+            struct_ser.serialize_field("resumeToken", v)?;
+        }
+        if !self.compressed_transactions.is_empty() {
+            struct_ser.serialize_field("compressedTransactions", &self.compressed_transactions.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
         struct_ser.end()
     }
 }
@@ -281,12 +329,18 @@ impl<'de> serde::Deserialize<'de> for TransactionsResponse {
             "transactions",
             "chain_id",
             "chainId",
+            "resume_token",
+            "resumeToken",
+            "compressed_transactions",
+            "compressedTransactions",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             Transactions,
             ChainId,
+            ResumeToken,
+            CompressedTransactions,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -310,6 +364,8 @@ impl<'de> serde::Deserialize<'de> for TransactionsResponse {
                         match value {
                             "transactions" => Ok(GeneratedField::Transactions),
                             "chainId" | "chain_id" => Ok(GeneratedField::ChainId),
+                            "resumeToken" | "resume_token" => Ok(GeneratedField::ResumeToken),
+                            "compressedTransactions" | "compressed_transactions" => Ok(GeneratedField::CompressedTransactions),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -331,6 +387,8 @@ impl<'de> serde::Deserialize<'de> for TransactionsResponse {
             {
                 let mut transactions__ = None;
                 let mut chain_id__ = None;
+                let mut resume_token__ = None;
+                let mut compressed_transactions__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
                         GeneratedField::Transactions => {
@@ -347,11 +405,28 @@ impl<'de> serde::Deserialize<'de> for TransactionsResponse {
                                 map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
                             ;
                         }
+                        GeneratedField::ResumeToken => {
+                            if resume_token__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resumeToken"));
+                            }
+                            resume_token__ = map.next_value()?;
+                        }
+                        GeneratedField::CompressedTransactions => {
+                            if compressed_transactions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("compressedTransactions"));
+                            }
+                            compressed_transactions__ =
+                                Some(map.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
                     }
                 }
                 Ok(TransactionsResponse {
                     transactions: transactions__.unwrap_or_default(),
                     chain_id: chain_id__,
+                    resume_token: resume_token__,
+                    compressed_transactions: compressed_transactions__.unwrap_or_default(),
                 })
             }
         }
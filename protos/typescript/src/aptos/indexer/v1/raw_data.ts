@@ -23,7 +23,7 @@ export interface TransactionsInStorage {
 }
 
 export interface GetTransactionsRequest {
-  /** Required; start version of current stream. */
+  /** Required, unless `resume_token` is present; start version of current stream. */
   startingVersion?:
     | bigint
     | undefined;
@@ -38,7 +38,21 @@ export interface GetTransactionsRequest {
    * Optional; number of transactions in each `TransactionsResponse` for current stream.
    * If not present, default to 1000. If larger than 1000, request will be rejected.
    */
-  batchSize?: bigint | undefined;
+  batchSize?:
+    | bigint
+    | undefined;
+  /**
+   * Optional; token from a `TransactionsResponse` of a previous stream, to resume right after it.
+   * If present, `starting_version` is ignored.
+   */
+  resumeToken?:
+    | string
+    | undefined;
+  /**
+   * Optional; whether the client accepts zstd compressed transactions, see
+   * `TransactionsResponse.compressed_transactions`.
+   */
+  acceptCompressedTransactions?: boolean | undefined;
 }
 
 /** TransactionsResponse is a batch of transactions. */
@@ -48,7 +62,21 @@ export interface TransactionsResponse {
     | Transaction[]
     | undefined;
   /** Required; chain id. */
-  chainId?: bigint | undefined;
+  chainId?:
+    | bigint
+    | undefined;
+  /**
+   * Optional; opaque token to resume the stream right after this batch, see
+   * `GetTransactionsRequest.resume_token`. Only present if the server issues tokens.
+   */
+  resumeToken?:
+    | string
+    | undefined;
+  /**
+   * Optional; zstd compressed, protobuf encoded `aptos.transaction.v1.Transaction`s. Only sent
+   * if the request sets `accept_compressed_transactions`, instead of `transactions`.
+   */
+  compressedTransactions?: Uint8Array[] | undefined;
 }
 
 function createBaseTransactionsInStorage(): TransactionsInStorage {
@@ -167,7 +195,13 @@ export const TransactionsInStorage = {
 };
 
 function createBaseGetTransactionsRequest(): GetTransactionsRequest {
-  return { startingVersion: undefined, transactionsCount: undefined, batchSize: undefined };
+  return {
+    startingVersion: undefined,
+    transactionsCount: undefined,
+    batchSize: undefined,
+    resumeToken: undefined,
+    acceptCompressedTransactions: undefined,
+  };
 }
 
 export const GetTransactionsRequest = {
@@ -190,6 +224,12 @@ export const GetTransactionsRequest = {
       }
       writer.uint32(24).uint64(message.batchSize.toString());
     }
+    if (message.resumeToken !== undefined) {
+      writer.uint32(34).string(message.resumeToken);
+    }
+    if (message.acceptCompressedTransactions !== undefined) {
+      writer.uint32(40).bool(message.acceptCompressedTransactions);
+    }
     return writer;
   },
 
@@ -221,6 +261,20 @@ export const GetTransactionsRequest = {
 
           message.batchSize = longToBigint(reader.uint64() as Long);
           continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.resumeToken = reader.string();
+          continue;
+        case 5:
+          if (tag !== 40) {
+            break;
+          }
+
+          message.acceptCompressedTransactions = reader.bool();
+          continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -269,6 +323,10 @@ export const GetTransactionsRequest = {
       startingVersion: isSet(object.startingVersion) ? BigInt(object.startingVersion) : undefined,
       transactionsCount: isSet(object.transactionsCount) ? BigInt(object.transactionsCount) : undefined,
       batchSize: isSet(object.batchSize) ? BigInt(object.batchSize) : undefined,
+      resumeToken: isSet(object.resumeToken) ? globalThis.String(object.resumeToken) : undefined,
+      acceptCompressedTransactions: isSet(object.acceptCompressedTransactions)
+        ? globalThis.Boolean(object.acceptCompressedTransactions)
+        : undefined,
     };
   },
 
@@ -283,6 +341,12 @@ export const GetTransactionsRequest = {
     if (message.batchSize !== undefined) {
       obj.batchSize = message.batchSize.toString();
     }
+    if (message.resumeToken !== undefined) {
+      obj.resumeToken = message.resumeToken;
+    }
+    if (message.acceptCompressedTransactions !== undefined) {
+      obj.acceptCompressedTransactions = message.acceptCompressedTransactions;
+    }
     return obj;
   },
 
@@ -294,12 +358,14 @@ export const GetTransactionsRequest = {
     message.startingVersion = object.startingVersion ?? undefined;
     message.transactionsCount = object.transactionsCount ?? undefined;
     message.batchSize = object.batchSize ?? undefined;
+    message.resumeToken = object.resumeToken ?? undefined;
+    message.acceptCompressedTransactions = object.acceptCompressedTransactions ?? undefined;
     return message;
   },
 };
 
 function createBaseTransactionsResponse(): TransactionsResponse {
-  return { transactions: [], chainId: undefined };
+  return { transactions: [], chainId: undefined, resumeToken: undefined, compressedTransactions: [] };
 }
 
 export const TransactionsResponse = {
@@ -315,6 +381,14 @@ export const TransactionsResponse = {
       }
       writer.uint32(16).uint64(message.chainId.toString());
     }
+    if (message.resumeToken !== undefined) {
+      writer.uint32(26).string(message.resumeToken);
+    }
+    if (message.compressedTransactions !== undefined && message.compressedTransactions.length !== 0) {
+      for (const v of message.compressedTransactions) {
+        writer.uint32(34).bytes(v!);
+      }
+    }
     return writer;
   },
 
@@ -339,6 +413,20 @@ export const TransactionsResponse = {
 
           message.chainId = longToBigint(reader.uint64() as Long);
           continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.resumeToken = reader.string();
+          continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.compressedTransactions!.push(reader.bytes());
+          continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -388,6 +476,10 @@ export const TransactionsResponse = {
         ? object.transactions.map((e: any) => Transaction.fromJSON(e))
         : [],
       chainId: isSet(object.chainId) ? BigInt(object.chainId) : undefined,
+      resumeToken: isSet(object.resumeToken) ? globalThis.String(object.resumeToken) : undefined,
+      compressedTransactions: globalThis.Array.isArray(object?.compressedTransactions)
+        ? object.compressedTransactions.map((e: any) => bytesFromBase64(e))
+        : [],
     };
   },
 
@@ -399,6 +491,12 @@ export const TransactionsResponse = {
     if (message.chainId !== undefined) {
       obj.chainId = message.chainId.toString();
     }
+    if (message.resumeToken !== undefined) {
+      obj.resumeToken = message.resumeToken;
+    }
+    if (message.compressedTransactions?.length) {
+      obj.compressedTransactions = message.compressedTransactions.map((e) => base64FromBytes(e));
+    }
     return obj;
   },
 
@@ -409,6 +507,8 @@ export const TransactionsResponse = {
     const message = createBaseTransactionsResponse();
     message.transactions = object.transactions?.map((e) => Transaction.fromPartial(e)) || [];
     message.chainId = object.chainId ?? undefined;
+    message.resumeToken = object.resumeToken ?? undefined;
+    message.compressedTransactions = object.compressedTransactions?.map((e) => e) || [];
     return message;
   },
 };
@@ -451,6 +551,31 @@ export const RawDataClient = makeGenericClientConstructor(RawDataService, "aptos
   serviceName: string;
 };
 
+function bytesFromBase64(b64: string): Uint8Array {
+  if ((globalThis as any).Buffer) {
+    return Uint8Array.from(globalThis.Buffer.from(b64, "base64"));
+  } else {
+    const bin = globalThis.atob(b64);
+    const arr = new Uint8Array(bin.length);
+    for (let i = 0; i < bin.length; ++i) {
+      arr[i] = bin.charCodeAt(i);
+    }
+    return arr;
+  }
+}
+
+function base64FromBytes(arr: Uint8Array): string {
+  if ((globalThis as any).Buffer) {
+    return globalThis.Buffer.from(arr).toString("base64");
+  } else {
+    const bin: string[] = [];
+    arr.forEach((byte) => {
+      bin.push(globalThis.String.fromCharCode(byte));
+    });
+    return globalThis.btoa(bin.join(""));
+  }
+}
+
 type Builtin = Date | Function | Uint8Array | string | number | boolean | bigint | undefined;
 
 type DeepPartial<T> = T extends Builtin ? T
@@ -27,7 +27,9 @@ use aptos_id_generator::{IdGenerator, U64IdGenerator};
 use aptos_infallible::Mutex;
 use aptos_logger::{info, sample, sample::SampleRate, trace, warn};
 use aptos_network::{
-    application::{interface::NetworkClient, storage::PeersAndMetadata},
+    application::{
+        interface::NetworkClient, reputation::PeerMisbehavior, storage::PeersAndMetadata,
+    },
     protocols::network::RpcError,
 };
 use aptos_storage_interface::DbReader;
@@ -862,6 +864,12 @@ impl AptosDataClient {
         _request: &StorageServiceRequest,
         error_type: ErrorType,
     ) {
+        // Malicious responses (e.g., data that fails proof verification)
+        // also lower the peer's reputation across all applications.
+        if matches!(error_type, ErrorType::Malicious) {
+            self.get_peers_and_metadata()
+                .report_peer_misbehavior(peer, PeerMisbehavior::InvalidStateSyncData);
+        }
         self.peer_states.update_score_error(peer, error_type);
     }
 
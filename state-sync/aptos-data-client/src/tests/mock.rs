@@ -253,6 +253,7 @@ impl MockNetwork {
                 })
             },
             Some(PeerManagerRequest::SendDirectSend(_, _)) => panic!("Unexpected direct send msg"),
+            Some(PeerManagerRequest::SendRpcStream(_, _)) => panic!("Unexpected rpc stream msg"),
             None => None,
         }
     }
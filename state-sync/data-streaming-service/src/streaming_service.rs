@@ -51,13 +51,17 @@ impl StreamUpdateNotification {
     }
 }
 
+/// A shared handle to the streaming service config, which can be updated at runtime
+pub type StreamingServiceConfigHandle = Arc<ArcSwap<DataStreamingServiceConfig>>;
+
 /// The data streaming service that responds to data stream requests.
 pub struct DataStreamingService<T> {
     // The configuration for the data client
     data_client_config: AptosDataClientConfig,
 
-    // The configuration for the streaming service
-    streaming_service_config: DataStreamingServiceConfig,
+    // The configuration for the streaming service (new data streams use
+    // the latest config, which can be updated at runtime)
+    streaming_service_config: StreamingServiceConfigHandle,
 
     // The data client through which to fetch data from the Aptos network
     aptos_data_client: T,
@@ -102,7 +106,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         // Create the streaming service
         Self {
             data_client_config,
-            streaming_service_config,
+            streaming_service_config: Arc::new(ArcSwap::from_pointee(streaming_service_config)),
             aptos_data_client,
             global_data_summary: Arc::new(ArcSwap::new(Arc::new(GlobalDataSummary::empty()))),
             data_streams: HashMap::new(),
@@ -115,18 +119,31 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         }
     }
 
+    /// Returns a handle through which the streaming service config can be updated
+    /// at runtime (e.g., when the node config is reloaded). Only new data streams
+    /// use the updated config, existing streams keep the config they were created with.
+    pub fn get_streaming_service_config(&self) -> StreamingServiceConfigHandle {
+        self.streaming_service_config.clone()
+    }
+
+    /// Returns the latest streaming service config
+    fn latest_streaming_service_config(&self) -> DataStreamingServiceConfig {
+        **self.streaming_service_config.load()
+    }
+
     /// Starts the dedicated streaming service
     pub async fn start_service(mut self) {
         // Spawn a dedicated task that refreshes the global data summary
         spawn_global_data_summary_refresher(
-            self.streaming_service_config,
+            self.latest_streaming_service_config(),
             self.aptos_data_client.clone(),
             self.global_data_summary.clone(),
         );
 
         // Create a ticker that periodically checks the progress of all data streams
         let mut progress_check_interval = IntervalStream::new(interval(Duration::from_millis(
-            self.streaming_service_config.progress_check_interval_ms,
+            self.latest_streaming_service_config()
+                .progress_check_interval_ms,
         )))
         .fuse();
 
@@ -274,7 +291,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         let advertised_data = self.get_global_data_summary().advertised_data.clone();
         let (data_stream, stream_listener) = DataStream::new(
             self.data_client_config,
-            self.streaming_service_config,
+            self.latest_streaming_service_config(),
             stream_id,
             &request_message.stream_request,
             stream_update_notifier,
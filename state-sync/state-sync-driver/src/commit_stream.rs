@@ -0,0 +1,187 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    logging::{LogEntry, LogSchema},
+    metrics,
+};
+use aptos_config::network_id::PeerNetworkId;
+use aptos_logger::prelude::*;
+use aptos_network::{
+    application::interface::{NetworkClient, NetworkClientInterface, NetworkServiceEvents},
+    protocols::network::Event,
+};
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::Version};
+use futures::{
+    future,
+    stream::{select_all, BoxStream, Fuse, FusedStream},
+    Stream, StreamExt,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+// The frequency at which to log commit stream errors
+const COMMIT_STREAM_ERROR_LOG_FREQ_SECS: u64 = 3;
+
+/// The messages sent over the commit stream, i.e., by validators to their
+/// downstream fullnodes (e.g., VFNs) as soon as new blocks are committed.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum CommitStreamMessage {
+    /// The ledger info of the latest commit (signed by a quorum of validators)
+    CommitCertificate(LedgerInfoWithSignatures),
+}
+
+/// A commit certificate received from a peer over the commit stream
+#[derive(Clone, Debug)]
+pub struct CommitCertificate {
+    pub peer_network_id: PeerNetworkId,
+    pub ledger_info: LedgerInfoWithSignatures,
+}
+
+/// The handler for the commit stream. It publishes commit certificates to
+/// downstream peers, and yields the commit certificates received from
+/// upstream peers. If the commit stream is disabled, it does neither.
+pub struct CommitStreamHandler {
+    // The network client used to publish commit certificates
+    network_client: Option<NetworkClient<CommitStreamMessage>>,
+
+    // The stream of commit certificates received from peers
+    commit_certificates: Option<Fuse<BoxStream<'static, CommitCertificate>>>,
+
+    // The version of the highest commit certificate published
+    highest_published_version: Option<Version>,
+}
+
+impl CommitStreamHandler {
+    pub fn new(
+        network_client: NetworkClient<CommitStreamMessage>,
+        network_service_events: NetworkServiceEvents<CommitStreamMessage>,
+    ) -> Self {
+        // Transform the event streams to also include the network ID
+        let network_events: Vec<_> = network_service_events
+            .into_network_and_events()
+            .into_iter()
+            .map(|(network_id, events)| events.map(move |event| (network_id, event)))
+            .collect();
+
+        // Transform each message into a commit certificate
+        let commit_certificates = select_all(network_events)
+            .filter_map(|(network_id, event)| {
+                future::ready(match event {
+                    Event::Message(
+                        peer_id,
+                        CommitStreamMessage::CommitCertificate(ledger_info),
+                    ) => Some(CommitCertificate {
+                        peer_network_id: PeerNetworkId::new(network_id, peer_id),
+                        ledger_info,
+                    }),
+                    _ => None, // The commit stream only uses direct send messages
+                })
+            })
+            .boxed()
+            .fuse();
+
+        Self {
+            network_client: Some(network_client),
+            commit_certificates: Some(commit_certificates),
+            highest_published_version: None,
+        }
+    }
+
+    /// Returns a handler for a node where the commit stream is disabled
+    pub fn disabled() -> Self {
+        Self {
+            network_client: None,
+            commit_certificates: None,
+            highest_published_version: None,
+        }
+    }
+
+    /// Publishes the given commit certificate to all downstream peers
+    /// that support the commit stream. Certificates that are not newer
+    /// than the last published certificate are ignored.
+    pub fn publish_commit_certificate(&mut self, ledger_info: LedgerInfoWithSignatures) {
+        let network_client = match &self.network_client {
+            Some(network_client) => network_client,
+            None => return, // The commit stream is disabled
+        };
+
+        // Only publish newer commits
+        let version = ledger_info.ledger_info().version();
+        if self
+            .highest_published_version
+            .map_or(false, |highest_version| version <= highest_version)
+        {
+            return;
+        }
+        self.highest_published_version = Some(version);
+
+        // Send the certificate to all downstream peers
+        let downstream_peers = get_downstream_peers(network_client);
+        if !downstream_peers.is_empty() {
+            let message = CommitStreamMessage::CommitCertificate(ledger_info);
+            if let Err(error) = network_client.send_to_peers(message, &downstream_peers) {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(COMMIT_STREAM_ERROR_LOG_FREQ_SECS)),
+                    warn!(LogSchema::new(LogEntry::CommitStream).message(&format!(
+                        "Failed to publish the commit certificate for version {}! Error: {:?}",
+                        version, error
+                    )))
+                );
+            }
+        }
+        metrics::set_gauge(
+            &metrics::COMMIT_STREAM_VERSIONS,
+            metrics::COMMIT_STREAM_PUBLISHED_VERSION,
+            version,
+        );
+    }
+}
+
+/// Returns the downstream peers that support the commit stream, i.e., the
+/// peers that connected to this node from a non-validator network.
+fn get_downstream_peers(network_client: &NetworkClient<CommitStreamMessage>) -> Vec<PeerNetworkId> {
+    let available_peers = match network_client.get_available_peers() {
+        Ok(available_peers) => available_peers,
+        Err(_) => return vec![],
+    };
+
+    let peers_and_metadata = network_client.get_peers_and_metadata();
+    available_peers
+        .into_iter()
+        .filter(|peer_network_id| {
+            !peer_network_id.network_id().is_validator_network()
+                && peers_and_metadata
+                    .get_metadata_for_peer(*peer_network_id)
+                    .map(|peer_metadata| {
+                        !peer_metadata
+                            .get_connection_metadata()
+                            .is_outbound_connection()
+                    })
+                    .unwrap_or(false)
+        })
+        .collect()
+}
+
+impl Stream for CommitStreamHandler {
+    type Item = CommitCertificate;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match &mut self.get_mut().commit_certificates {
+            Some(commit_certificates) => Pin::new(commit_certificates).poll_next(cx),
+            None => Poll::Pending, // The commit stream is disabled
+        }
+    }
+}
+
+impl FusedStream for CommitStreamHandler {
+    fn is_terminated(&self) -> bool {
+        self.commit_certificates
+            .as_ref()
+            .map_or(false, FusedStream::is_terminated)
+    }
+}
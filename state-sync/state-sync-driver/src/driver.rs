@@ -4,6 +4,7 @@
 
 use crate::{
     bootstrapper::Bootstrapper,
+    commit_stream::{CommitCertificate, CommitStreamHandler},
     continuous_syncer::ContinuousSyncer,
     driver_client::{ClientNotificationListener, DriverNotification},
     error::Error,
@@ -35,7 +36,7 @@ use aptos_mempool_notifications::MempoolNotificationSender;
 use aptos_storage_interface::DbReader;
 use aptos_storage_service_notifications::StorageServiceNotificationSender;
 use aptos_time_service::{TimeService, TimeServiceTrait};
-use aptos_types::{contract_event::ContractEvent, waypoint::Waypoint};
+use aptos_types::{contract_event::ContractEvent, epoch_change::Verifier, waypoint::Waypoint};
 use futures::StreamExt;
 use std::{sync::Arc, time::Instant};
 use tokio::{
@@ -97,6 +98,9 @@ pub struct StateSyncDriver<
     // The listener for commit notifications
     commit_notification_listener: CommitNotificationListener,
 
+    // The handler for the commit stream (to and from other nodes)
+    commit_stream_handler: CommitStreamHandler,
+
     // The handler for notifications from consensus
     consensus_notification_handler: ConsensusNotificationHandler,
 
@@ -154,6 +158,7 @@ impl<
     pub fn new(
         client_notification_listener: ClientNotificationListener,
         commit_notification_listener: CommitNotificationListener,
+        commit_stream_handler: CommitStreamHandler,
         consensus_notification_handler: ConsensusNotificationHandler,
         driver_configuration: DriverConfiguration,
         error_notification_listener: ErrorNotificationListener,
@@ -191,6 +196,7 @@ impl<
             bootstrapper,
             client_notification_listener,
             commit_notification_listener,
+            commit_stream_handler,
             consensus_notification_handler,
             continuous_syncer,
             aptos_data_client,
@@ -226,6 +232,9 @@ impl<
                     // now that we aren't reusing it.
                     self.handle_commit_notification(notification).await;
                 }
+                commit_certificate = self.commit_stream_handler.select_next_some() => {
+                    self.handle_commit_certificate(commit_certificate).await;
+                }
                 notification = self.consensus_notification_handler.select_next_some() => {
                     self.handle_consensus_notification(notification).await;
                 }
@@ -334,11 +343,35 @@ impl<
             .respond_to_commit_notification(consensus_commit_notification, Ok(()))
             .await?;
 
+        // Publish the new commit to any downstream fullnodes
+        self.publish_latest_commit_certificate();
+
         // Check the progress of any sync requests. We need this here because
         // consensus might issue a sync request and then commit (asynchronously).
         self.check_sync_request_progress().await
     }
 
+    /// Publishes the latest synced ledger info over the commit stream.
+    /// Only validators publish commit certificates.
+    fn publish_latest_commit_certificate(&mut self) {
+        if self.driver_configuration.role != RoleType::Validator
+            || !self.driver_configuration.config.enable_commit_stream
+        {
+            return;
+        }
+
+        match utils::fetch_latest_synced_ledger_info(self.storage.clone()) {
+            Ok(latest_synced_ledger_info) => self
+                .commit_stream_handler
+                .publish_commit_certificate(latest_synced_ledger_info),
+            Err(error) => {
+                warn!(LogSchema::new(LogEntry::CommitStream)
+                    .error(&error)
+                    .message("Failed to fetch the commit certificate to publish!"));
+            },
+        }
+    }
+
     /// Updates the storage synchronizer metrics based on the consensus
     /// commit notification.
     fn update_consensus_commit_metrics(
@@ -447,6 +480,71 @@ impl<
         .await;
     }
 
+    /// Handles a commit certificate received over the commit stream. If the
+    /// certificate is valid and ahead of storage, we drive progress immediately
+    /// (instead of waiting for the next progress check).
+    async fn handle_commit_certificate(&mut self, commit_certificate: CommitCertificate) {
+        metrics::increment_counter(
+            &metrics::DRIVER_COUNTERS,
+            metrics::DRIVER_COMMIT_STREAM_NOTIFICATION,
+        );
+
+        match self.verify_commit_certificate(&commit_certificate) {
+            Ok(true) => self.drive_progress().await,
+            Ok(false) => {}, // There's nothing new to sync
+            Err(error) => {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(DRIVER_ERROR_LOG_FREQ_SECS)),
+                    warn!(LogSchema::new(LogEntry::CommitStream)
+                        .error(&error)
+                        .message(&format!(
+                            "Received an invalid commit certificate from peer: {:?}",
+                            commit_certificate.peer_network_id
+                        )));
+                );
+            },
+        }
+    }
+
+    /// Verifies the commit certificate against the latest epoch state in
+    /// storage. Returns true iff the certificate is valid and ahead of the
+    /// latest synced version.
+    fn verify_commit_certificate(
+        &self,
+        commit_certificate: &CommitCertificate,
+    ) -> Result<bool, Error> {
+        // Ignore certificates that we've already synced
+        let ledger_info = commit_certificate.ledger_info.ledger_info();
+        let latest_synced_version = utils::fetch_latest_synced_version(self.storage.clone())?;
+        if ledger_info.version() <= latest_synced_version {
+            return Ok(false);
+        }
+
+        // Ignore certificates for future epochs (we can't verify them yet, and
+        // we'll need to sync the epoch changes first anyway).
+        let epoch_state = utils::fetch_latest_epoch_state(self.storage.clone())?;
+        if ledger_info.epoch() > epoch_state.epoch {
+            return Ok(false);
+        }
+
+        // Verify the certificate
+        epoch_state
+            .verify(&commit_certificate.ledger_info)
+            .map_err(|error| {
+                Error::VerificationError(format!(
+                    "Failed to verify the commit certificate: {:?}",
+                    error
+                ))
+            })?;
+        metrics::set_gauge(
+            &metrics::COMMIT_STREAM_VERSIONS,
+            metrics::COMMIT_STREAM_RECEIVED_VERSION,
+            ledger_info.version(),
+        );
+
+        Ok(true)
+    }
+
     /// Handles an error notification sent by the storage synchronizer
     async fn handle_error_notification(&mut self, error_notification: ErrorNotification) {
         warn!(LogSchema::new(LogEntry::SynchronizerNotification)
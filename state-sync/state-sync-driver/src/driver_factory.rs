@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    commit_stream::CommitStreamHandler,
     driver::{DriverConfiguration, StateSyncDriver},
     driver_client::{ClientNotificationListener, DriverClient, DriverNotification},
     metadata_storage::MetadataStorageInterface,
@@ -57,6 +58,7 @@ impl DriverFactory {
         event_subscription_service: EventSubscriptionService,
         aptos_data_client: AptosDataClient,
         streaming_service_client: StreamingServiceClient,
+        commit_stream_handler: CommitStreamHandler,
         time_service: TimeService,
     ) -> Self {
         let (driver_factory, _) = Self::create_and_spawn_driver_internal(
@@ -72,6 +74,7 @@ impl DriverFactory {
             event_subscription_service,
             aptos_data_client,
             streaming_service_client,
+            commit_stream_handler,
             time_service,
         );
         driver_factory
@@ -98,6 +101,7 @@ impl DriverFactory {
         mut event_subscription_service: EventSubscriptionService,
         aptos_data_client: AptosDataClient,
         streaming_service_client: StreamingServiceClient,
+        commit_stream_handler: CommitStreamHandler,
         time_service: TimeService,
     ) -> (Self, UnboundedSender<CommitNotification>) {
         // Notify subscribers of the initial on-chain config values
@@ -167,6 +171,7 @@ impl DriverFactory {
         let state_sync_driver = StateSyncDriver::new(
             client_notification_listener,
             commit_notification_listener,
+            commit_stream_handler,
             consensus_notification_handler,
             driver_configuration,
             error_notification_listener,
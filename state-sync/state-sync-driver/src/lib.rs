@@ -5,6 +5,7 @@
 #![forbid(unsafe_code)]
 
 mod bootstrapper;
+pub mod commit_stream;
 mod continuous_syncer;
 mod driver;
 mod driver_client;
@@ -30,6 +30,7 @@ pub enum LogEntry {
     AutoBootstrapping,
     Bootstrapper,
     ClientNotification,
+    CommitStream,
     ConsensusNotification,
     Driver,
     NotificationHandler,
@@ -10,9 +10,14 @@ use std::time::Instant;
 
 /// Driver metric labels
 pub const DRIVER_CLIENT_NOTIFICATION: &str = "driver_client_notification";
+pub const DRIVER_COMMIT_STREAM_NOTIFICATION: &str = "driver_commit_stream_notification";
 pub const DRIVER_CONSENSUS_COMMIT_NOTIFICATION: &str = "driver_consensus_commit_notification";
 pub const DRIVER_CONSENSUS_SYNC_NOTIFICATION: &str = "driver_consensus_sync_notification";
 
+/// Commit stream metric labels
+pub const COMMIT_STREAM_PUBLISHED_VERSION: &str = "published_version";
+pub const COMMIT_STREAM_RECEIVED_VERSION: &str = "received_version";
+
 /// Data notification metric labels
 pub const NOTIFICATION_CREATE_TO_APPLY: &str = "notification_create_to_apply";
 pub const NOTIFICATION_CREATE_TO_COMMIT: &str = "notification_create_to_commit";
@@ -98,6 +103,26 @@ pub static CONTINUOUS_SYNCER_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Gauges for the highest versions published and received over the commit stream
+pub static COMMIT_STREAM_VERSIONS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_state_sync_commit_stream_versions",
+        "Gauges for the highest versions published and received over the commit stream",
+        &["label"]
+    )
+    .unwrap()
+});
+
+/// Counter for pending network events of the commit stream
+pub static PENDING_COMMIT_STREAM_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_state_sync_commit_stream_pending_network_events",
+        "Counters for pending network events of the commit stream",
+        &["state"]
+    )
+    .unwrap()
+});
+
 /// Counters related to the state sync driver
 pub static DRIVER_COUNTERS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -0,0 +1,87 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    commit_stream::{CommitStreamHandler, CommitStreamMessage},
+    tests::utils::create_ledger_info_at_version,
+};
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_config::network_id::{NetworkId, PeerNetworkId};
+use aptos_network::{
+    application::{
+        interface::{NetworkClient, NetworkServiceEvents},
+        storage::PeersAndMetadata,
+    },
+    peer_manager::PeerManagerNotification,
+    protocols::{
+        direct_send::Message,
+        network::{NetworkEvents, NewNetworkEvents},
+        wire::handshake::v1::ProtocolId,
+    },
+};
+use aptos_types::PeerId;
+use futures::{stream::FusedStream, FutureExt, StreamExt};
+use std::collections::HashMap;
+
+#[tokio::test]
+async fn test_receive_commit_certificates() {
+    // Create the network events for the commit stream
+    let network_id = NetworkId::Vfn;
+    let queue_config = aptos_channel::Config::new(10).queue_style(QueueStyle::FIFO);
+    let (peer_manager_notifier, peer_manager_notification_receiver) = queue_config.build();
+    let (_, connection_notification_receiver) = queue_config.build();
+    let network_events = NetworkEvents::new(
+        peer_manager_notification_receiver,
+        connection_notification_receiver,
+        None,
+    );
+
+    // Create the commit stream handler
+    let network_client = NetworkClient::new(
+        vec![ProtocolId::CommitStreamDirectSend],
+        vec![],
+        HashMap::new(),
+        PeersAndMetadata::new(&[network_id]),
+    );
+    let network_service_events =
+        NetworkServiceEvents::new(HashMap::from([(network_id, network_events)]));
+    let mut commit_stream_handler =
+        CommitStreamHandler::new(network_client, network_service_events);
+
+    // Send a commit certificate from a peer
+    let peer_id = PeerId::random();
+    let ledger_info = create_ledger_info_at_version(100);
+    let protocol_id = ProtocolId::CommitStreamDirectSend;
+    let message = Message {
+        protocol_id,
+        mdata: protocol_id
+            .to_bytes(&CommitStreamMessage::CommitCertificate(ledger_info.clone()))
+            .unwrap()
+            .into(),
+    };
+    peer_manager_notifier
+        .push(
+            (peer_id, protocol_id),
+            PeerManagerNotification::RecvMessage(peer_id, message),
+        )
+        .unwrap();
+
+    // Verify the commit certificate is received
+    let commit_certificate = commit_stream_handler.select_next_some().await;
+    assert_eq!(
+        commit_certificate.peer_network_id,
+        PeerNetworkId::new(network_id, peer_id)
+    );
+    assert_eq!(commit_certificate.ledger_info, ledger_info);
+}
+
+#[tokio::test]
+async fn test_disabled_commit_stream() {
+    // Create a disabled commit stream handler
+    let mut commit_stream_handler = CommitStreamHandler::disabled();
+
+    // Verify publishing is a no-op, and nothing is ever received
+    commit_stream_handler.publish_commit_certificate(create_ledger_info_at_version(100));
+    assert!(commit_stream_handler.next().now_or_never().is_none());
+    assert!(!commit_stream_handler.is_terminated());
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    commit_stream::CommitStreamHandler,
     driver_factory::DriverFactory,
     metadata_storage::PersistentMetadataStorage,
     notification_handlers::CommitNotification,
@@ -405,6 +406,7 @@ async fn create_driver_for_tests(
             event_subscription_service,
             aptos_data_client,
             streaming_service_client,
+            CommitStreamHandler::disabled(),
             time_service.clone(),
         );
 
@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{driver_factory::DriverFactory, metadata_storage::PersistentMetadataStorage};
+use crate::{
+    commit_stream::CommitStreamHandler, driver_factory::DriverFactory,
+    metadata_storage::PersistentMetadataStorage,
+};
 use aptos_config::{
     config::{
         RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
@@ -98,6 +101,7 @@ fn test_new_initialized_configs() {
         event_subscription_service,
         aptos_data_client,
         streaming_service_client,
+        CommitStreamHandler::disabled(),
         TimeService::mock(),
     );
 
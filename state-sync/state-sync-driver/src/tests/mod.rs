@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod bootstrapper;
+mod commit_stream;
 mod continuous_syncer;
 mod driver;
 mod driver_factory;
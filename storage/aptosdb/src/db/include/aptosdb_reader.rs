@@ -224,6 +224,19 @@ impl DbReader for AptosDB {
         })
     }
 
+    /// Get the first version of which the state snapshot is not pruned. Snapshots at the end of
+    /// epochs can be older than this, as they are pruned separately.
+    fn get_first_state_snapshot_version(&self) -> Result<Option<Version>> {
+        gauged_api("get_first_state_snapshot_version", || {
+            Ok(Some(
+                self.state_store
+                    .state_db
+                    .state_merkle_pruner
+                    .get_min_readable_version(),
+            ))
+        })
+    }
+
     /// Returns a batch of transactions for the purpose of synchronizing state to another node.
     ///
     /// If any version beyond ledger_version is requested, it is ignored.
@@ -724,6 +737,15 @@ impl DbReader for AptosDB {
             self.state_store.get_usage(version)
         })
     }
+
+    fn pin_state_snapshot(&self, version: Version) -> Result<StateSnapshotPin> {
+        gauged_api("pin_state_snapshot", || {
+            self.state_store.state_snapshot_pins.pin(version, || {
+                self.error_if_state_merkle_pruned("State merkle", version)?;
+                self.error_if_state_kv_pruned("StateValue", version)
+            })
+        })
+    }
 }
 
 impl AptosDB {
@@ -606,7 +606,10 @@ impl AptosDB {
                 .maybe_set_pruner_target_db_version(last_version);
             self.state_store
                 .state_kv_pruner
-                .maybe_set_pruner_target_db_version(last_version);
+                .maybe_set_pruner_target_db_version_with_pins(
+                    last_version,
+                    &self.state_store.state_snapshot_pins,
+                );
         }
 
         // Note: this must happen after txns have been saved to db because types can be newly
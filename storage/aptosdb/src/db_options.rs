@@ -3,9 +3,9 @@
 
 use crate::schema::*;
 use aptos_config::config::RocksdbConfig;
+use aptos_rocksdb_options::ColumnFamilyOptionsGenerator;
 use aptos_schemadb::{
-    BlockBasedOptions, Cache, ColumnFamilyDescriptor, ColumnFamilyName, DBCompressionType, Options,
-    SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
+    ColumnFamilyDescriptor, ColumnFamilyName, Options, SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
 };
 use aptos_types::transaction::Version;
 
@@ -115,6 +115,23 @@ pub(super) fn skip_reporting_cf(cf_name: &str) -> bool {
     cf_name == DEFAULT_COLUMN_FAMILY_NAME || cf_name == DB_METADATA_CF_NAME
 }
 
+/// Whether the column family is read on the critical path, as opposed to holding the ledger
+/// history. The two kinds are tuned separately, see `RocksdbConfig`.
+fn is_hot_cf(cf_name: ColumnFamilyName) -> bool {
+    matches!(
+        cf_name,
+        DB_METADATA_CF_NAME
+            | EPOCH_BY_VERSION_CF_NAME
+            | JELLYFISH_MERKLE_NODE_CF_NAME
+            | LEDGER_INFO_CF_NAME
+            | STATE_VALUE_CF_NAME
+            | STATE_VALUE_INDEX_CF_NAME
+            | TRANSACTION_BY_ACCOUNT_CF_NAME
+            | TRANSACTION_BY_HASH_CF_NAME
+            | VERSION_DATA_CF_NAME
+    )
+}
+
 pub(super) fn state_kv_db_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
@@ -133,16 +150,10 @@ fn gen_cfds<F>(
 where
     F: Fn(ColumnFamilyName, &mut Options),
 {
-    let mut table_options = BlockBasedOptions::default();
-    table_options.set_cache_index_and_filter_blocks(rocksdb_config.cache_index_and_filter_blocks);
-    table_options.set_block_size(rocksdb_config.block_size as usize);
-    let cache = Cache::new_lru_cache(rocksdb_config.block_cache_size as usize);
-    table_options.set_block_cache(&cache);
+    let cf_opts_generator = ColumnFamilyOptionsGenerator::new(rocksdb_config);
     let mut cfds = Vec::with_capacity(cfs.len());
     for cf_name in cfs {
-        let mut cf_opts = Options::default();
-        cf_opts.set_compression_type(DBCompressionType::Lz4);
-        cf_opts.set_block_based_table_factory(&table_options);
+        let mut cf_opts = cf_opts_generator.gen_cf_options(is_hot_cf(cf_name));
         cf_opts_post_processor(cf_name, &mut cf_opts);
         cfds.push(ColumnFamilyDescriptor::new((*cf_name).to_string(), cf_opts));
     }
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::pruner::db_pruner::DBPruner;
-use aptos_storage_interface::Result;
+use aptos_storage_interface::{state_snapshot_pin::StateSnapshotPins, Result};
 use aptos_types::transaction::Version;
 
 /// This module provides `Pruner` which manages a thread pruning old data in the background and is
@@ -30,6 +30,24 @@ pub trait PrunerManager: Sync {
     /// Sets pruner target version when necessary.
     fn maybe_set_pruner_target_db_version(&self, latest_version: Version);
 
+    /// Sets pruner target version when necessary, but never past the lowest pinned state
+    /// snapshot (i.e., the min readable version is kept at or below the pinned version).
+    fn maybe_set_pruner_target_db_version_with_pins(
+        &self,
+        latest_version: Version,
+        state_snapshot_pins: &StateSnapshotPins,
+    ) {
+        state_snapshot_pins.with_min_pinned_version(|min_pinned_version| {
+            let latest_version = match min_pinned_version {
+                Some(min_pinned_version) => {
+                    latest_version.min(min_pinned_version.saturating_add(self.get_prune_window()))
+                },
+                None => latest_version,
+            };
+            self.maybe_set_pruner_target_db_version(latest_version)
+        })
+    }
+
     // Only used at the end of fast sync to store the min_readable_version to db and update the
     // in memory progress.
     fn save_min_readable_version(&self, min_readable_version: Version) -> Result<()>;
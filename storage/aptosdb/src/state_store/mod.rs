@@ -48,6 +48,7 @@ use aptos_storage_interface::{
     cached_state_view::{CachedStateView, ShardedStateCache},
     db_ensure as ensure,
     state_delta::StateDelta,
+    state_snapshot_pin::StateSnapshotPins,
     AptosDbError, DbReader, Result, StateSnapshotReceiver,
 };
 use aptos_types::{
@@ -89,6 +90,8 @@ pub(crate) struct StateDb {
     pub state_merkle_pruner: StateMerklePrunerManager<StaleNodeIndexSchema>,
     pub epoch_snapshot_pruner: StateMerklePrunerManager<StaleNodeIndexCrossEpochSchema>,
     pub state_kv_pruner: StateKvPrunerManager,
+    // The state snapshots that must not be pruned (see `DbReader::pin_state_snapshot`)
+    pub state_snapshot_pins: Arc<StateSnapshotPins>,
     pub skip_usage: bool,
 }
 
@@ -308,6 +311,7 @@ impl StateStore {
             state_merkle_pruner,
             epoch_snapshot_pruner,
             state_kv_pruner,
+            state_snapshot_pins: StateSnapshotPins::new(),
             skip_usage,
         });
         let (buffered_state, smt_ancestors) = if empty_buffered_state_for_restore {
@@ -422,6 +426,7 @@ impl StateStore {
             state_merkle_pruner,
             epoch_snapshot_pruner,
             state_kv_pruner,
+            state_snapshot_pins: StateSnapshotPins::new(),
             skip_usage: false,
         });
         let (buffered_state, _) = Self::create_buffered_state_from_latest_snapshot(
@@ -184,6 +184,9 @@ impl EpochEndingBackupController {
             waypoints,
             chunks,
         };
+        // Verify the manifest before it's saved and referenced by the metadata, so that a broken
+        // backup is never used for restoring.
+        manifest.verify()?;
         let (manifest_handle, mut manifest_file) = self
             .storage
             .create_for_write(backup_handle, Self::manifest_name())
@@ -376,6 +376,7 @@ impl StateSnapshotBackupController {
             chunks,
             proof: proof_handle,
         };
+        manifest.verify()?;
 
         let (manifest_handle, mut manifest_file) = self
             .storage
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::storage::FileHandle;
+use anyhow::{ensure, Result};
 use aptos_crypto::HashValue;
 use aptos_types::transaction::Version;
 use serde::{Deserialize, Serialize};
@@ -50,3 +51,35 @@ pub struct StateSnapshotBackup {
     /// limits the requirement on such `EpochStateBackup` to no older than the same epoch.
     pub proof: FileHandle,
 }
+
+impl StateSnapshotBackup {
+    pub fn verify(&self) -> Result<()> {
+        // check chunk ranges
+        ensure!(!self.chunks.is_empty(), "No chunks.");
+
+        let mut next_idx = 0;
+        for chunk in &self.chunks {
+            ensure!(
+                chunk.first_idx == next_idx,
+                "Chunk ranges not continuous. Expected first index: {}, actual: {}.",
+                next_idx,
+                chunk.first_idx,
+            );
+            ensure!(
+                chunk.last_idx >= chunk.first_idx,
+                "Chunk range invalid. [{}, {}]",
+                chunk.first_idx,
+                chunk.last_idx,
+            );
+            ensure!(
+                chunk.last_key >= chunk.first_key,
+                "Chunk key range invalid. [{}, {}]",
+                chunk.first_key,
+                chunk.last_key,
+            );
+            next_idx = chunk.last_idx + 1;
+        }
+
+        Ok(())
+    }
+}
@@ -196,6 +196,7 @@ impl TransactionBackupController {
             last_version,
             chunks,
         };
+        manifest.verify()?;
         let (manifest_handle, mut manifest_file) = self
             .storage
             .create_for_write(backup_handle, Self::manifest_name())
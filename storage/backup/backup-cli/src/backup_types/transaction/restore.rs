@@ -19,6 +19,7 @@ use crate::{
     utils::{
         error_notes::ErrorNotes,
         read_record_bytes::ReadRecordBytes,
+        restore_progress_pct,
         storage_ext::BackupStorageExt,
         stream::{StreamX, TryStreamX},
         GlobalRestoreOptions, RestoreRunMode,
@@ -473,6 +474,12 @@ impl TransactionRestoreBatchController {
                         TRANSACTION_SAVE_VERSION.set(last_saved as i64);
                         info!(
                             version = last_saved,
+                            target_version = target_version,
+                            progress_pct = ?restore_progress_pct(
+                                global_first_version,
+                                last_saved,
+                                target_version
+                            ),
                             accumulative_tps = ((last_saved - global_first_version + 1) as f64
                                 / start.elapsed().as_secs_f64())
                                 as u64,
@@ -512,6 +519,7 @@ impl TransactionRestoreBatchController {
         >,
     ) -> Result<()> {
         let (first_version, _) = self.replay_from_version.unwrap();
+        let target_version = self.global_opt.target_version;
         let mut base_version = first_version;
         let mut offset = 0u64;
         let replay_start = Instant::now();
@@ -560,6 +568,8 @@ impl TransactionRestoreBatchController {
                     TRANSACTION_REPLAY_VERSION.set(version as i64);
                     info!(
                         version = version,
+                        target_version = target_version,
+                        progress_pct = ?restore_progress_pct(first_version, version, target_version),
                         accumulative_tps =
                             (total_replayed as f64 / replay_start.elapsed().as_secs_f64()) as u64,
                         "KV replayed."
@@ -588,6 +598,7 @@ impl TransactionRestoreBatchController {
         >,
     ) -> Result<()> {
         let (first_version, _) = self.replay_from_version.unwrap();
+        let target_version = self.global_opt.target_version;
         restore_handler.reset_state_store();
         let replay_start = Instant::now();
         let db = DbReaderWriter::from_arc(Arc::clone(&restore_handler.aptosdb));
@@ -635,6 +646,8 @@ impl TransactionRestoreBatchController {
                         TRANSACTION_REPLAY_VERSION.set(v as i64);
                         info!(
                             version = v,
+                            target_version = target_version,
+                            progress_pct = ?restore_progress_pct(first_version, v, target_version),
                             accumulative_tps = (total_replayed as f64
                                 / replay_start.elapsed().as_secs_f64())
                                 as u64,
@@ -666,6 +679,7 @@ impl TransactionRestoreBatchController {
             .as_ref()
             .map(|dir| TransactionAnalysis::new(dir))
             .transpose()?;
+        let target_version = self.global_opt.target_version;
         let start = Instant::now();
         loaded_chunk_stream
             .try_fold(analysis, |mut analysis, chunk| async move {
@@ -682,6 +696,8 @@ impl TransactionRestoreBatchController {
                 VERIFY_TRANSACTION_VERSION.set(last_version as i64);
                 info!(
                     version = last_version,
+                    target_version = target_version,
+                    progress_pct = ?restore_progress_pct(first_version, last_version, target_version),
                     accumulative_tps = ((last_version - first_version + 1) as f64
                         / start.elapsed().as_secs_f64())
                         as u64,
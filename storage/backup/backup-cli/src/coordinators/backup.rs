@@ -9,9 +9,13 @@ use crate::{
         transaction::backup::{TransactionBackupController, TransactionBackupOpt},
     },
     metadata,
-    metadata::{cache::MetadataCacheOpt, view::MetadataView, CompactionTimestampsMeta, Metadata},
+    metadata::{
+        cache::MetadataCacheOpt, view::MetadataView, CompactionTimestampsMeta, Metadata,
+        StateSnapshotBackupMeta,
+    },
     metrics::backup::{
-        EPOCH_ENDING_EPOCH, HEARTBEAT_TS, STATE_SNAPSHOT_EPOCH, TRANSACTION_VERSION,
+        EPOCH_ENDING_EPOCH, HEARTBEAT_TS, PRUNED_STATE_SNAPSHOT_EPOCH, STATE_SNAPSHOT_EPOCH,
+        TRANSACTION_VERSION,
     },
     storage::{BackupStorage, FileHandle},
     utils::{
@@ -28,7 +32,9 @@ use clap::Parser;
 use futures::{stream, Future, StreamExt};
 use std::{
     collections::{HashMap, HashSet},
+    ffi::OsStr,
     fmt::Debug,
+    path::Path,
     sync::Arc,
 };
 use tokio::{
@@ -69,6 +75,23 @@ pub struct BackupCoordinatorOpt {
         many small files. "
     )]
     pub transaction_batch_size: usize,
+    #[clap(
+        long,
+        help = "Number of the latest state snapshots to keep. Older state snapshots are pruned \
+        periodically: their metadata files are moved to the metadata backup folder, so they are no \
+        longer used for restoring, the same way that metadata files replaced by compaction are. \
+        Notice: the backup data of pruned state snapshots is not deleted, use the lifecycle rules \
+        of the backup storage for that. State snapshots in compacted metadata files are not \
+        pruned. By default, all state snapshots are kept."
+    )]
+    pub state_snapshot_retention_count: Option<usize>,
+    #[clap(
+        long,
+        default_value_t = 3600,
+        help = "Frequency (in seconds) to prune state snapshots, if \
+        `--state-snapshot-retention-count` is set."
+    )]
+    pub prune_interval_secs: u64,
     #[clap(flatten)]
     pub concurrent_downloads: ConcurrentDownloadsOpt,
 }
@@ -79,6 +102,10 @@ impl BackupCoordinatorOpt {
             self.state_snapshot_interval_epochs > 0 && self.transaction_batch_size > 0,
             "Backup interval and batch size must be greater than 0."
         );
+        ensure!(
+            self.state_snapshot_retention_count != Some(0) && self.prune_interval_secs > 0,
+            "State snapshot retention count and prune interval must be greater than 0."
+        );
         Ok(())
     }
 }
@@ -90,6 +117,8 @@ pub struct BackupCoordinator {
     metadata_cache_opt: MetadataCacheOpt,
     state_snapshot_interval_epochs: usize,
     transaction_batch_size: usize,
+    state_snapshot_retention_count: Option<usize>,
+    prune_interval_secs: u64,
     concurrent_downloads: usize,
 }
 
@@ -108,6 +137,8 @@ impl BackupCoordinator {
             metadata_cache_opt: opt.metadata_cache_opt,
             state_snapshot_interval_epochs: opt.state_snapshot_interval_epochs,
             transaction_batch_size: opt.transaction_batch_size,
+            state_snapshot_retention_count: opt.state_snapshot_retention_count,
+            prune_interval_secs: opt.prune_interval_secs,
             concurrent_downloads: opt.concurrent_downloads.get(),
         }
     }
@@ -159,13 +190,22 @@ impl BackupCoordinator {
             )
             .boxed_local();
 
-        info!("Backup coordinator started.");
-        let mut all_work = stream::select_all(vec![
+        let mut work_streams = vec![
             watch_db_state,
             backup_epoch_endings,
             backup_state_snapshots,
             backup_transactions,
-        ]);
+        ];
+        if let Some(retention_count) = self.state_snapshot_retention_count {
+            let prune_state_snapshots =
+                IntervalStream::new(interval(Duration::from_secs(self.prune_interval_secs)))
+                    .then(move |_| self.try_prune_state_snapshots(retention_count))
+                    .boxed_local();
+            work_streams.push(prune_state_snapshots);
+        }
+
+        info!("Backup coordinator started.");
+        let mut all_work = stream::select_all(work_streams);
 
         loop {
             all_work
@@ -302,6 +342,35 @@ impl BackupCoordinator {
         }
     }
 
+    async fn try_prune_state_snapshots(&self, retention_count: usize) {
+        if let Err(e) = self.prune_state_snapshots(retention_count).await {
+            warn!("Failed pruning state snapshots: {}. Will keep trying.", e);
+        }
+    }
+
+    async fn prune_state_snapshots(&self, retention_count: usize) -> Result<()> {
+        let metaview = metadata::cache::sync_and_load(
+            &self.metadata_cache_opt,
+            Arc::clone(&self.storage),
+            self.concurrent_downloads,
+        )
+        .await?;
+        let snapshots_to_prune = metaview.select_state_snapshots_to_prune(retention_count);
+        if let Some(snapshot) = snapshots_to_prune.last() {
+            PRUNED_STATE_SNAPSHOT_EPOCH.set(snapshot.epoch as i64);
+        }
+
+        let files_to_prune = get_state_snapshot_files_to_prune(
+            self.storage.list_metadata_files().await?,
+            snapshots_to_prune,
+        );
+        for file in files_to_prune {
+            info!(file = file, "Pruning state snapshot metadata file.");
+            self.storage.backup_metadata_file(&file).await?;
+        }
+        Ok(())
+    }
+
     fn backup_work_stream<'a, S, W, Fut>(
         &'a self,
         initial_state: S,
@@ -491,6 +560,27 @@ fn get_batch_range(last_in_backup: Option<u64>, batch_size: usize) -> (u64, u64)
     })
 }
 
+fn get_state_snapshot_files_to_prune(
+    metadata_files: Vec<FileHandle>,
+    snapshots_to_prune: Vec<StateSnapshotBackupMeta>,
+) -> Vec<FileHandle> {
+    // Only the metadata files written when backing up a single state snapshot are pruned, because
+    // compacted ones can have state snapshots that are retained.
+    let names_to_prune: HashSet<String> = snapshots_to_prune
+        .into_iter()
+        .map(|snapshot| Metadata::StateSnapshotBackup(snapshot).name().to_string())
+        .collect();
+    metadata_files
+        .into_iter()
+        .filter(|file| {
+            Path::new(file)
+                .file_name()
+                .and_then(OsStr::to_str)
+                .map_or(false, |name| names_to_prune.contains(name))
+        })
+        .collect()
+}
+
 fn get_next_snapshot(last_in_backup: Option<u64>, db_state: DbState, interval: usize) -> u64 {
     // We don't try to guarantee snapshots are taken at each applicable interval: when the backup
     // progress can't keep up with the ledger growth, we favor timeliness over completeness.
@@ -510,7 +600,12 @@ fn get_next_snapshot(last_in_backup: Option<u64>, db_state: DbState, interval: u
 
 #[cfg(test)]
 mod tests {
-    use crate::coordinators::backup::{get_batch_range, get_next_snapshot};
+    use crate::{
+        coordinators::backup::{
+            get_batch_range, get_next_snapshot, get_state_snapshot_files_to_prune,
+        },
+        metadata::StateSnapshotBackupMeta,
+    };
     use aptos_db::backup::backup_handler::DbState;
 
     #[test]
@@ -540,4 +635,32 @@ mod tests {
         assert_eq!(get_next_snapshot(Some(0), _state(250), 100), 200);
         assert_eq!(get_next_snapshot(Some(200), _state(250), 100), 300);
     }
+
+    #[test]
+    fn test_get_state_snapshot_files_to_prune() {
+        let snapshot = |epoch| StateSnapshotBackupMeta {
+            epoch,
+            version: epoch * 100,
+            manifest: format!("state_epoch_{}/state.manifest", epoch),
+        };
+        let metadata_files = vec![
+            "metadata/state_snapshot_ver_100.meta".to_string(),
+            "metadata/state_snapshot_ver_200.meta".to_string(),
+            "metadata/state_snapshot_ver_300.meta".to_string(),
+            "metadata/state_snapshot_compacted_epoch_4_5.meta".to_string(),
+            "metadata/transaction_0-100.meta".to_string(),
+        ];
+
+        assert_eq!(
+            get_state_snapshot_files_to_prune(metadata_files.clone(), vec![]),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            get_state_snapshot_files_to_prune(metadata_files, vec![snapshot(1), snapshot(2)]),
+            vec![
+                "metadata/state_snapshot_ver_100.meta".to_string(),
+                "metadata/state_snapshot_ver_200.meta".to_string(),
+            ]
+        );
+    }
 }
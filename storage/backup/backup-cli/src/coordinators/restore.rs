@@ -172,9 +172,7 @@ impl RestoreCoordinator {
                     db_next_version, 0,
                     "DB should be empty if no in-progress state snapshot found"
                 );
-                metadata_view
-                    .select_state_snapshot(std::cmp::min(lhs, max_txn_ver))
-                    .expect("Cannot find any snapshot before ledger history start version")
+                metadata_view.select_state_snapshot(std::cmp::min(lhs, max_txn_ver))?
             },
         };
 
@@ -190,7 +188,13 @@ impl RestoreCoordinator {
         } else {
             metadata_view
                 .select_state_snapshot(target_version)?
-                .expect("Cannot find tree snapshot before target version")
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Cannot find any state snapshot before target version {}, \
+                        can't restore to it.",
+                        target_version
+                    )
+                })?
         };
 
         let do_phase_1 = if let Some(kv_snapshot) = kv_snapshot.as_ref() {
@@ -346,8 +350,11 @@ impl RestoreCoordinator {
                 .filter(|e| e.last_version >= db_next_version)
                 .map(|e| e.manifest.clone())
                 .collect();
+            // Only restore (and report progress) up to the target version found in the backups
+            let mut transaction_restore_opt = self.global_opt;
+            transaction_restore_opt.target_version = target_version;
             TransactionRestoreBatchController::new(
-                self.global_opt,
+                transaction_restore_opt,
                 self.storage,
                 txn_manifests,
                 first_version,
@@ -118,6 +118,19 @@ impl MetadataView {
             .map(Clone::clone))
     }
 
+    /// All state snapshots but the latest `retention_count` ones
+    pub fn select_state_snapshots_to_prune(
+        &self,
+        retention_count: usize,
+    ) -> Vec<StateSnapshotBackupMeta> {
+        let num_to_prune = self
+            .state_snapshot_backups
+            .len()
+            .saturating_sub(retention_count);
+        // `state_snapshot_backups` is sorted by epoch (and version)
+        self.state_snapshot_backups[..num_to_prune].to_vec()
+    }
+
     pub fn expect_state_snapshot(&self, version: Version) -> Result<StateSnapshotBackupMeta> {
         self.state_snapshot_backups
             .iter()
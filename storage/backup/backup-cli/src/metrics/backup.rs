@@ -31,6 +31,14 @@ pub static STATE_SNAPSHOT_EPOCH: Lazy<IntGauge> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static PRUNED_STATE_SNAPSHOT_EPOCH: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_db_backup_coordinator_pruned_state_snapshot_epoch",
+        "The epoch of the latest state snapshot pruned per the retention count."
+    )
+    .unwrap()
+});
+
 pub static TRANSACTION_VERSION: Lazy<IntGauge> = Lazy::new(|| {
     register_int_gauge!(
         "aptos_db_backup_coordinator_transaction_version",
@@ -109,6 +109,7 @@ impl From<RocksdbOpt> for RocksdbConfigs {
                 max_background_jobs: opt.max_background_jobs,
                 ..Default::default()
             },
+            ..Default::default()
         }
     }
 }
@@ -121,7 +122,12 @@ impl Default for RocksdbOpt {
 
 #[derive(Clone, Parser)]
 pub struct GlobalRestoreOpt {
-    #[clap(long, help = "Dry run without writing data to DB.")]
+    #[clap(
+        long,
+        alias = "verify-only",
+        help = "Dry run without writing data to DB, only verifying the backups (hashes and \
+        proofs) up to the target version."
+    )]
     pub dry_run: bool,
 
     #[clap(
@@ -411,3 +417,33 @@ impl<T: AsRef<Path>> PathToString for T {
 pub(crate) fn unix_timestamp_sec() -> i64 {
     duration_since_epoch().as_secs() as i64
 }
+
+/// Returns the progress (in percent) of restoring versions from `first_version` up to
+/// `target_version`, given `current_version` is done. Returns `None` if no target version is
+/// specified, i.e. everything in the backups is to be restored.
+pub(crate) fn restore_progress_pct(
+    first_version: Version,
+    current_version: Version,
+    target_version: Version,
+) -> Option<f64> {
+    if target_version == Version::MAX || target_version < first_version {
+        return None;
+    }
+    let done = current_version.saturating_sub(first_version) + 1;
+    let total = target_version - first_version + 1;
+    Some((done as f64 * 100.0 / total as f64).min(100.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_progress_pct() {
+        assert_eq!(restore_progress_pct(0, 0, Version::MAX), None);
+        assert_eq!(restore_progress_pct(10, 10, 5), None);
+        assert_eq!(restore_progress_pct(10, 10, 10), Some(100.0));
+        assert_eq!(restore_progress_pct(100, 149, 199), Some(50.0));
+        assert_eq!(restore_progress_pct(100, 299, 199), Some(100.0));
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Context, Result};
+use aptos_backup_cli::{
+    backup_types::{
+        epoch_ending::backup::{EpochEndingBackupController, EpochEndingBackupOpt},
+        state_snapshot::backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
+        transaction::backup::{TransactionBackupController, TransactionBackupOpt},
+    },
+    coordinators::restore::{RestoreCoordinator, RestoreCoordinatorOpt},
+    metadata::cache::MetadataCacheOpt,
+    storage::{local_fs::LocalFs, BackupStorage},
+    utils::{backup_service_client::BackupServiceClient, GlobalBackupOpt, GlobalRestoreOpt},
+};
+use aptos_backup_service::start_backup_service;
+use aptos_config::{
+    config::{
+        RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+    },
+    utils::get_available_port,
+};
+use aptos_db::AptosDB;
+use aptos_logger::info;
+use aptos_storage_interface::DbReader;
+use aptos_temppath::TempPath;
+use clap::Parser;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+    sync::Arc,
+};
+
+/// Copy a verified state snapshot from one DB into a fresh one, to bootstrap a node from it.
+///
+/// The state snapshot at the end of the epoch is copied along with the epoch ending ledger
+/// infos up to it, which the snapshot is verified against, and the transaction at its version.
+/// This goes through the backup and restore tooling, without needing a backup storage.
+#[derive(Parser)]
+pub struct Command {
+    #[clap(long, value_parser)]
+    source_db_dir: PathBuf,
+
+    #[clap(
+        long = "state-snapshot-epoch",
+        help = "Epoch at the end of which the state snapshot is taken. \
+        [Defaults to the latest ended epoch]"
+    )]
+    epoch: Option<u64>,
+
+    #[clap(
+        long,
+        value_parser,
+        help = "Directory to keep the intermediate backup in. [Defaults to a temporary dir]"
+    )]
+    backup_dir: Option<PathBuf>,
+
+    #[clap(flatten)]
+    global_backup: GlobalBackupOpt,
+
+    #[clap(flatten)]
+    global_restore: GlobalRestoreOpt,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        // Opening the DB read only, so that the source node doesn't need to be stopped.
+        let source_db = Arc::new(AptosDB::open(
+            StorageDirPaths::from_path(&self.source_db_dir),
+            true, /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            RocksdbConfigs::default(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )?);
+
+        let latest_ended_epoch = source_db
+            .get_latest_ledger_info()?
+            .ledger_info()
+            .next_block_epoch()
+            - 1;
+        let epoch = self.epoch.unwrap_or(latest_ended_epoch);
+        ensure!(
+            epoch <= latest_ended_epoch,
+            "Epoch {} hasn't ended yet, the latest ended epoch is {}.",
+            epoch,
+            latest_ended_epoch,
+        );
+        let version = source_db
+            .get_epoch_ending_ledger_infos(epoch, epoch + 1)?
+            .ledger_info_with_sigs
+            .first()
+            .ok_or_else(|| format_err!("Epoch ending ledger info of epoch {} not found.", epoch))?
+            .ledger_info()
+            .version();
+        info!(
+            epoch = epoch,
+            version = version,
+            "Copying state snapshot at the end of the epoch."
+        );
+
+        let temp_backup_dir = TempPath::new();
+        let backup_dir = match self.backup_dir {
+            Some(backup_dir) => backup_dir,
+            None => {
+                temp_backup_dir.create_as_dir()?;
+                temp_backup_dir.path().to_path_buf()
+            },
+        };
+        let storage: Arc<dyn BackupStorage> = Arc::new(LocalFs::new(backup_dir));
+
+        let port = get_available_port();
+        let backup_service = start_backup_service(
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port),
+            source_db,
+        );
+        let client = Arc::new(BackupServiceClient::new(format!(
+            "http://localhost:{}",
+            port
+        )));
+        let backup_result =
+            Self::backup(epoch, version, self.global_backup, client, storage.clone()).await;
+        backup_service.shutdown_background();
+        backup_result.context("Failed to back up the source DB.")?;
+
+        RestoreCoordinator::new(
+            RestoreCoordinatorOpt {
+                metadata_cache_opt: MetadataCacheOpt::new(None::<PathBuf>),
+                replay_all: false,
+                ledger_history_start_version: None,
+                skip_epoch_endings: false,
+            },
+            GlobalRestoreOpt {
+                target_version: Some(version),
+                ..self.global_restore
+            }
+            .try_into()?,
+            storage,
+        )
+        .run()
+        .await
+        .context("Failed to restore the state snapshot.")?;
+
+        println!(
+            "Copied state snapshot at version {} (end of epoch {}).",
+            version, epoch
+        );
+        Ok(())
+    }
+
+    async fn backup(
+        epoch: u64,
+        version: u64,
+        global_opt: GlobalBackupOpt,
+        client: Arc<BackupServiceClient>,
+        storage: Arc<dyn BackupStorage>,
+    ) -> Result<()> {
+        EpochEndingBackupController::new(
+            EpochEndingBackupOpt {
+                start_epoch: 0,
+                end_epoch: epoch + 1,
+            },
+            global_opt.clone(),
+            client.clone(),
+            storage.clone(),
+        )
+        .run()
+        .await?;
+        StateSnapshotBackupController::new(
+            StateSnapshotBackupOpt { epoch },
+            global_opt.clone(),
+            client.clone(),
+            storage.clone(),
+        )
+        .run()
+        .await?;
+        // The restore needs the transaction at the snapshot version, for the ledger history to
+        // start from it.
+        TransactionBackupController::new(
+            TransactionBackupOpt {
+                start_version: version,
+                num_transactions: 1,
+            },
+            global_opt,
+            client,
+            storage,
+        )
+        .run()
+        .await?;
+        Ok(())
+    }
+}
+
+#[test]
+fn verify_tool() {
+    use clap::CommandFactory;
+    Command::command().debug_assert()
+}
@@ -6,6 +6,7 @@ extern crate core;
 mod backup;
 mod backup_maintenance;
 mod bootstrap;
+mod copy_state_snapshot;
 mod replay_verify;
 pub mod restore;
 #[cfg(test)]
@@ -28,6 +29,8 @@ pub enum DBTool {
 
     Bootstrap(bootstrap::Command),
 
+    CopyStateSnapshot(copy_state_snapshot::Command),
+
     #[clap(subcommand)]
     Debug(db_debugger::Cmd),
 
@@ -43,6 +46,7 @@ impl DBTool {
             DBTool::Backup(cmd) => cmd.run().await,
             DBTool::BackupMaintenance(cmd) => cmd.run().await,
             DBTool::Bootstrap(cmd) => cmd.run(),
+            DBTool::CopyStateSnapshot(cmd) => cmd.run().await,
             DBTool::Debug(cmd) => Ok(cmd.run()?),
             DBTool::ReplayVerify(cmd) => {
                 let ret = cmd.run().await;
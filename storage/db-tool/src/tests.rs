@@ -46,6 +46,15 @@ fn test_various_cmd_parsing() {
         ".",
     ]);
 
+    run_cmd(&[
+        "aptos-db-tool",
+        "copy-state-snapshot",
+        "--source-db-dir",
+        ".",
+        "--target-db-dir",
+        ".",
+    ]);
+
     run_cmd(&["aptos-db-tool", "backup", "verify", "--local-fs-dir", "."]);
     run_cmd(&[
         "aptos-db-tool",
@@ -623,6 +632,64 @@ mod dbtool_tests {
         rt.shutdown_timeout(Duration::from_secs(1));
     }
 
+    #[test]
+    fn test_copy_state_snapshot() {
+        use aptos_db::utils::iterators::PrefixedStateValueIterator;
+        use itertools::zip_eq;
+
+        let old_db_dir = TempPath::new();
+        let new_db_dir = TempPath::new();
+        let db = test_execution_with_storage_impl_inner(false, old_db_dir.path());
+
+        let rt = Runtime::new().unwrap();
+        rt.block_on(
+            DBTool::try_parse_from([
+                "aptos-db-tool",
+                "copy-state-snapshot",
+                "--source-db-dir",
+                old_db_dir.path().to_str().unwrap(),
+                "--state-snapshot-epoch",
+                "1",
+                "--target-db-dir",
+                new_db_dir.path().to_str().unwrap(),
+            ])
+            .unwrap()
+            .run(),
+        )
+        .unwrap();
+
+        // The snapshot at the end of epoch 1 is copied, along with its tree
+        let snapshot_version: Version = 13;
+        let (_ledger_db, tree_db, state_kv_db) = AptosDB::open_dbs(
+            &StorageDirPaths::from_path(new_db_dir.path()),
+            RocksdbConfigs::default(),
+            false,
+            0,
+        )
+        .unwrap();
+        let new_iter = PrefixedStateValueIterator::new(
+            &state_kv_db,
+            StateKeyPrefix::new(AccessPath, b"".to_vec()),
+            None,
+            snapshot_version,
+            false,
+        )
+        .unwrap();
+        let old_iter = db
+            .deref()
+            .get_prefixed_state_value_iterator(
+                &StateKeyPrefix::new(AccessPath, b"".to_vec()),
+                None,
+                snapshot_version,
+            )
+            .unwrap();
+        zip_eq(new_iter, old_iter).for_each(|(new, old)| {
+            assert_eq!(new.unwrap(), old.unwrap());
+        });
+        assert!(tree_db.get_root_hash(snapshot_version).is_ok());
+        rt.shutdown_timeout(Duration::from_secs(1));
+    }
+
     fn dir_size<P: AsRef<Path>>(path: P) -> u64 {
         let mut size = 0;
 
@@ -1,22 +1,42 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::schema::column_families;
+use crate::schema::gen_cfds;
 use anyhow::Result;
 use aptos_config::config::RocksdbConfig;
 use aptos_rocksdb_options::gen_rocksdb_options;
 use aptos_schemadb::DB;
-use std::{mem, path::Path};
+use std::{fs, mem, path::Path};
 
 pub fn open_db<P: AsRef<Path>>(db_path: P, rocksdb_config: &RocksdbConfig) -> Result<DB> {
-    Ok(DB::open(
+    Ok(DB::open_cf(
+        &gen_rocksdb_options(rocksdb_config, false),
         db_path,
         "index_asnync_v2_db",
-        column_families(),
-        &gen_rocksdb_options(rocksdb_config, false),
+        gen_cfds(rocksdb_config),
     )?)
 }
 
 pub fn close_db(db: DB) {
     mem::drop(db)
 }
+
+/// Creates a rocksdb checkpoint of the db at `checkpoint_path`, replacing anything already there.
+/// The checkpoint can be opened directly with `open_db`, and carries the latest processed version
+/// in its metadata, so a node bootstrapped from it resumes indexing from that version.
+pub fn create_checkpoint<P: AsRef<Path>>(db: &DB, checkpoint_path: P) -> Result<()> {
+    fs::remove_dir_all(checkpoint_path.as_ref()).unwrap_or(());
+    Ok(db.create_checkpoint(checkpoint_path)?)
+}
+
+/// Moves a checkpoint created by `create_checkpoint` into place at `db_path`.
+/// Any existing db at `db_path` is removed, so the db must not be open.
+pub fn restore_from_checkpoint<P: AsRef<Path>>(checkpoint_path: P, db_path: P) -> Result<()> {
+    let db_path = db_path.as_ref();
+    fs::remove_dir_all(db_path).unwrap_or(());
+    if let Some(parent) = db_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(checkpoint_path.as_ref(), db_path)?;
+    Ok(())
+}
@@ -6,8 +6,12 @@
 /// from storage critical path to indexer, the other file will be removed
 /// and this file will be moved to /ecosystem/indexer-grpc/indexer-grpc-table-info.
 use crate::{
+    db_ops,
     metadata::{MetadataKey, MetadataValue},
-    schema::{indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema},
+    schema::{
+        indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema,
+        table_owner::TableOwnerSchema,
+    },
 };
 use aptos_logger::info;
 use aptos_resource_viewer::{AnnotatedMoveValue, AptosValueAnnotator};
@@ -20,7 +24,7 @@ use aptos_types::{
     account_address::AccountAddress,
     state_store::{
         state_key::{inner::StateKeyInner, StateKey},
-        table::{TableHandle, TableInfo},
+        table::{TableHandle, TableInfo, TableOwner},
         StateView,
     },
     transaction::Version,
@@ -34,7 +38,6 @@ use move_core_types::{
 };
 use std::{
     collections::{BTreeMap, HashMap},
-    fs,
     path::PathBuf,
     sync::{
         atomic::{AtomicU64, Ordering},
@@ -112,7 +115,10 @@ impl IndexerAsyncV2 {
             }
         }
         let mut batch = SchemaBatch::new();
-        match self.finish_table_info_parsing(&mut batch, &table_info_parser.result) {
+        match self
+            .finish_table_info_parsing(&mut batch, &table_info_parser.result)
+            .and_then(|_| self.finish_table_owner_parsing(&mut batch, &table_info_parser.owners))
+        {
             Ok(_) => {},
             Err(err) => {
                 aptos_logger::error!(
@@ -153,6 +159,18 @@ impl IndexerAsyncV2 {
         Ok(())
     }
 
+    /// Writes the parsed table handle to owner mapping to a SchemaBatch.
+    pub fn finish_table_owner_parsing(
+        &self,
+        batch: &mut SchemaBatch,
+        owners: &HashMap<TableHandle, TableOwner>,
+    ) -> Result<()> {
+        owners.iter().try_for_each(|(table_handle, table_owner)| {
+            batch.put::<TableOwnerSchema>(table_handle, table_owner)
+        })?;
+        Ok(())
+    }
+
     /// After multiple threads have processed batches of write sets, clean up the pending on items to
     /// remove any handles that have already been successfully parsed
     /// ideally pending on items should be empty after threads join, meaning that all batches have done the work
@@ -187,6 +205,11 @@ impl IndexerAsyncV2 {
         self.db.get::<TableInfoSchema>(&handle).map_err(Into::into)
     }
 
+    /// Returns the account and resource that own the given table handle, if it has been indexed.
+    pub fn get_table_owner(&self, handle: TableHandle) -> Result<Option<TableOwner>> {
+        self.db.get::<TableOwnerSchema>(&handle).map_err(Into::into)
+    }
+
     pub fn get_table_info_with_retry(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
         let mut retried = 0;
         loop {
@@ -208,8 +231,7 @@ impl IndexerAsyncV2 {
     }
 
     pub fn create_checkpoint(&self, path: &PathBuf) -> Result<()> {
-        fs::remove_dir_all(path).unwrap_or(());
-        self.db.create_checkpoint(path)
+        db_ops::create_checkpoint(&self.db, path).map_err(Into::into)
     }
 }
 
@@ -217,6 +239,7 @@ struct TableInfoParser<'a, R> {
     indexer_async_v2: &'a IndexerAsyncV2,
     annotator: &'a AptosValueAnnotator<'a, R>,
     result: HashMap<TableHandle, TableInfo>,
+    owners: HashMap<TableHandle, TableOwner>,
     pending_on: &'a DashMap<TableHandle, DashSet<Bytes>>,
 }
 
@@ -230,6 +253,7 @@ impl<'a, R: StateView> TableInfoParser<'a, R> {
             indexer_async_v2,
             annotator,
             result: HashMap::new(),
+            owners: HashMap::new(),
             pending_on,
         }
     }
@@ -242,8 +266,12 @@ impl<'a, R: StateView> TableInfoParser<'a, R> {
                     let path: Path = (&access_path.path).try_into()?;
                     match path {
                         Path::Code(_) => (),
-                        Path::Resource(struct_tag) => self.parse_struct(struct_tag, bytes)?,
-                        Path::ResourceGroup(_struct_tag) => self.parse_resource_group(bytes)?,
+                        Path::Resource(struct_tag) => {
+                            self.parse_struct(access_path.address, struct_tag, bytes)?
+                        },
+                        Path::ResourceGroup(_struct_tag) => {
+                            self.parse_resource_group(access_path.address, bytes)?
+                        },
                     }
                 },
                 StateKeyInner::TableItem { handle, .. } => self.parse_table_item(*handle, bytes)?,
@@ -253,27 +281,42 @@ impl<'a, R: StateView> TableInfoParser<'a, R> {
         Ok(())
     }
 
-    fn parse_struct(&mut self, struct_tag: StructTag, bytes: &Bytes) -> Result<()> {
-        self.parse_move_value(
-            &self
-                .annotator
-                .view_value(&TypeTag::Struct(Box::new(struct_tag)), bytes)?,
-        )
+    /// Parses a resource stored under `address`. Any table found inside of it is owned by
+    /// this resource.
+    fn parse_struct(
+        &mut self,
+        address: AccountAddress,
+        struct_tag: StructTag,
+        bytes: &Bytes,
+    ) -> Result<()> {
+        let move_value = self
+            .annotator
+            .view_value(&TypeTag::Struct(Box::new(struct_tag.clone())), bytes)?;
+        let owner = TableOwner {
+            address,
+            struct_tag,
+        };
+        self.parse_move_value(&move_value, Some(&owner))
     }
 
-    fn parse_resource_group(&mut self, bytes: &Bytes) -> Result<()> {
+    fn parse_resource_group(&mut self, address: AccountAddress, bytes: &Bytes) -> Result<()> {
         type ResourceGroup = BTreeMap<StructTag, Bytes>;
 
         for (struct_tag, bytes) in bcs::from_bytes::<ResourceGroup>(bytes)? {
-            self.parse_struct(struct_tag, &bytes)?;
+            self.parse_struct(address, struct_tag, &bytes)?;
         }
         Ok(())
     }
 
+    /// Parses a table item. Tables nested in the item inherit the owner of the parent table.
     fn parse_table_item(&mut self, handle: TableHandle, bytes: &Bytes) -> Result<()> {
         match self.get_table_info(handle)? {
             Some(table_info) => {
-                self.parse_move_value(&self.annotator.view_value(&table_info.value_type, bytes)?)?;
+                let owner = self.get_table_owner(handle)?;
+                self.parse_move_value(
+                    &self.annotator.view_value(&table_info.value_type, bytes)?,
+                    owner.as_ref(),
+                )?;
             },
             None => {
                 self.pending_on
@@ -293,11 +336,18 @@ impl<'a, R: StateView> TableInfoParser<'a, R> {
     /// performs different parsing actions. For Vector and Struct, it recursively
     /// calls itself to parse each element or field. This recursive approach allows
     /// the function to handle nested data structures in Move values.
-    fn parse_move_value(&mut self, move_value: &AnnotatedMoveValue) -> Result<()> {
+    ///
+    /// `owner` is the resource the value was reached from, and is recorded for every table
+    /// handle found along the way.
+    fn parse_move_value(
+        &mut self,
+        move_value: &AnnotatedMoveValue,
+        owner: Option<&TableOwner>,
+    ) -> Result<()> {
         match move_value {
             AnnotatedMoveValue::Vector(_type_tag, items) => {
                 for item in items {
-                    self.parse_move_value(item)?;
+                    self.parse_move_value(item, owner)?;
                 }
             },
             AnnotatedMoveValue::Struct(struct_value) => {
@@ -315,10 +365,10 @@ impl<'a, R: StateView> TableInfoParser<'a, R> {
                         },
                         _ => bail!("Table struct malformed. {:?}", struct_value),
                     };
-                    self.save_table_info(table_handle, table_info)?;
+                    self.save_table_info(table_handle, table_info, owner)?;
                 } else {
                     for (_identifier, field) in &struct_value.value {
-                        self.parse_move_value(field)?;
+                        self.parse_move_value(field, owner)?;
                     }
                 }
             },
@@ -337,9 +387,17 @@ impl<'a, R: StateView> TableInfoParser<'a, R> {
         Ok(())
     }
 
-    fn save_table_info(&mut self, handle: TableHandle, info: TableInfo) -> Result<()> {
+    fn save_table_info(
+        &mut self,
+        handle: TableHandle,
+        info: TableInfo,
+        owner: Option<&TableOwner>,
+    ) -> Result<()> {
         if self.get_table_info(handle)?.is_none() {
             self.result.insert(handle, info);
+            if let Some(owner) = owner {
+                self.owners.insert(handle, owner.clone());
+            }
             if let Some(pending_items) = self.pending_on.remove(&handle) {
                 for bytes in pending_items.1 {
                     self.parse_table_item(handle, &bytes)?;
@@ -368,4 +426,12 @@ impl<'a, R: StateView> TableInfoParser<'a, R> {
             None => self.indexer_async_v2.get_table_info(handle),
         }
     }
+
+    /// Retrieves the table owner from the in-memory results first, then from the database.
+    fn get_table_owner(&self, handle: TableHandle) -> Result<Option<TableOwner>> {
+        match self.owners.get(&handle) {
+            Some(table_owner) => Ok(Some(table_owner.clone())),
+            None => self.indexer_async_v2.get_table_owner(handle),
+        }
+    }
 }
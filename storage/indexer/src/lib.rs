@@ -12,9 +12,7 @@ pub mod table_info_reader;
 use crate::{
     db::INDEX_DB_NAME,
     metadata::{MetadataKey, MetadataValue},
-    schema::{
-        column_families, indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema,
-    },
+    schema::{gen_cfds, indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema},
 };
 use aptos_config::config::RocksdbConfig;
 use aptos_logger::warn;
@@ -59,11 +57,11 @@ impl Indexer {
     ) -> Result<Self> {
         let db_path = db_root_path.as_ref().join(INDEX_DB_NAME);
 
-        let db = DB::open(
+        let db = DB::open_cf(
+            &gen_rocksdb_options(&rocksdb_config, false),
             db_path,
             "index_db",
-            column_families(),
-            &gen_rocksdb_options(&rocksdb_config, false),
+            gen_cfds(&rocksdb_config),
         )?;
 
         let next_version = db
@@ -8,17 +8,33 @@
 
 pub(crate) mod indexer_metadata;
 pub(crate) mod table_info;
+pub(crate) mod table_owner;
 
-use aptos_schemadb::ColumnFamilyName;
+use aptos_config::config::RocksdbConfig;
+use aptos_rocksdb_options::ColumnFamilyOptionsGenerator;
+use aptos_schemadb::{ColumnFamilyDescriptor, ColumnFamilyName};
 
 pub const DEFAULT_COLUMN_FAMILY_NAME: ColumnFamilyName = "default";
 pub const INDEXER_METADATA_CF_NAME: ColumnFamilyName = "indexer_metadata";
 pub const TABLE_INFO_CF_NAME: ColumnFamilyName = "table_info";
+pub const TABLE_OWNER_CF_NAME: ColumnFamilyName = "table_owner";
 
 pub fn column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
         INDEXER_METADATA_CF_NAME,
         TABLE_INFO_CF_NAME,
+        TABLE_OWNER_CF_NAME,
     ]
 }
+
+/// All the column families of the indexer serve point lookups, so they're all tuned as hot ones.
+pub fn gen_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+    let cf_opts_generator = ColumnFamilyOptionsGenerator::new(rocksdb_config);
+    column_families()
+        .into_iter()
+        .map(|cf_name| {
+            ColumnFamilyDescriptor::new(cf_name.to_string(), cf_opts_generator.gen_cf_options(true))
+        })
+        .collect()
+}
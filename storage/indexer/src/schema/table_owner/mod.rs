@@ -0,0 +1,48 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema mapping table handles to the account and resource
+//! that own them, i.e. the reverse lookup of where a table handle lives on chain.
+//!
+//! ```text
+//! |<--key-->|<---value--->|
+//! | handle  | table_owner |
+//! ```
+
+use crate::schema::TABLE_OWNER_CF_NAME;
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::state_store::table::{TableHandle, TableOwner};
+
+define_schema!(
+    TableOwnerSchema,
+    TableHandle,
+    TableOwner,
+    TABLE_OWNER_CF_NAME
+);
+
+impl KeyCodec<TableOwnerSchema> for TableHandle {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+impl ValueCodec<TableOwnerSchema> for TableOwner {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+#[cfg(test)]
+mod test;
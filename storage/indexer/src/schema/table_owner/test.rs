@@ -0,0 +1,18 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        table_handle in any::<TableHandle>(),
+        table_owner in any::<TableOwner>(),
+    ) {
+        assert_encode_decode::<TableOwnerSchema>(&table_handle, &table_owner);
+    }
+}
+
+test_no_panic_decoding!(TableOwnerSchema);
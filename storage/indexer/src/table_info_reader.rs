@@ -3,17 +3,34 @@
 
 use crate::db_v2::IndexerAsyncV2;
 use aptos_storage_interface::Result;
-use aptos_types::state_store::table::{TableHandle, TableInfo};
+use aptos_types::{
+    state_store::table::{TableHandle, TableInfo, TableOwner},
+    transaction::Version,
+};
 
 /// Table info reader is to create a thin interface for other services to read the db data,
 /// this standalone db is officially not part of the AptosDB anymore.
 /// For services that need table info mapping, they need to acquire this reader in the FN bootstrapping stage.
 pub trait TableInfoReader: Send + Sync {
     fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>>;
+
+    /// Reverse lookup from a table handle to the account and resource that own it.
+    fn get_table_owner(&self, handle: TableHandle) -> Result<Option<TableOwner>>;
+
+    /// The next version to be indexed, i.e., the table info of all versions before it is indexed.
+    fn next_version(&self) -> Version;
 }
 
 impl TableInfoReader for IndexerAsyncV2 {
     fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
         self.get_table_info_with_retry(handle)
     }
+
+    fn get_table_owner(&self, handle: TableHandle) -> Result<Option<TableOwner>> {
+        IndexerAsyncV2::get_table_owner(self, handle)
+    }
+
+    fn next_version(&self) -> Version {
+        IndexerAsyncV2::next_version(self)
+    }
 }
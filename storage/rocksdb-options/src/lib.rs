@@ -1,8 +1,8 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_config::config::RocksdbConfig;
-use rocksdb::Options;
+use aptos_config::config::{ColumnFamilyConfig, RocksdbCompression, RocksdbConfig};
+use rocksdb::{BlockBasedOptions, Cache, DBCompressionType, Options};
 
 // TODO: Clean this up. It is currently separated into its own crate
 // to avoid circular dependencies, because it depends on aptos-config (which
@@ -20,3 +20,79 @@ pub fn gen_rocksdb_options(config: &RocksdbConfig, readonly: bool) -> Options {
 
     db_opts
 }
+
+/// Generates the options of the column families of a DB, according to the tuning of the hot and
+/// the cold ones. The block cache of the DB is shared by all its column families, except for the
+/// parts of it dedicated to the hot or the cold ones.
+pub struct ColumnFamilyOptionsGenerator {
+    hot_table_options: BlockBasedOptions,
+    hot_compression: DBCompressionType,
+    cold_table_options: BlockBasedOptions,
+    cold_compression: DBCompressionType,
+}
+
+impl ColumnFamilyOptionsGenerator {
+    pub fn new(config: &RocksdbConfig) -> Self {
+        let dedicated_block_cache_pct = [&config.hot_cf_config, &config.cold_cf_config]
+            .iter()
+            .filter_map(|cf_config| cf_config.block_cache_share_pct)
+            .sum::<u64>()
+            .min(100);
+        let shared_cache = Cache::new_lru_cache(block_cache_share(
+            config.block_cache_size,
+            100 - dedicated_block_cache_pct,
+        ));
+
+        Self {
+            hot_table_options: gen_table_options(config, &config.hot_cf_config, &shared_cache),
+            hot_compression: compression_type(config.hot_cf_config.compression),
+            cold_table_options: gen_table_options(config, &config.cold_cf_config, &shared_cache),
+            cold_compression: compression_type(config.cold_cf_config.compression),
+        }
+    }
+
+    pub fn gen_cf_options(&self, is_hot: bool) -> Options {
+        let mut cf_opts = Options::default();
+        if is_hot {
+            cf_opts.set_compression_type(self.hot_compression);
+            cf_opts.set_block_based_table_factory(&self.hot_table_options);
+        } else {
+            cf_opts.set_compression_type(self.cold_compression);
+            cf_opts.set_block_based_table_factory(&self.cold_table_options);
+        }
+        cf_opts
+    }
+}
+
+fn gen_table_options(
+    config: &RocksdbConfig,
+    cf_config: &ColumnFamilyConfig,
+    shared_cache: &Cache,
+) -> BlockBasedOptions {
+    let mut table_options = BlockBasedOptions::default();
+    table_options.set_cache_index_and_filter_blocks(config.cache_index_and_filter_blocks);
+    table_options.set_block_size(config.block_size as usize);
+    match cf_config.block_cache_share_pct {
+        Some(pct) => table_options.set_block_cache(&Cache::new_lru_cache(block_cache_share(
+            config.block_cache_size,
+            pct,
+        ))),
+        None => table_options.set_block_cache(shared_cache),
+    }
+    if cf_config.bloom_filter_bits_per_key > 0 {
+        table_options.set_bloom_filter(cf_config.bloom_filter_bits_per_key as f64, false);
+    }
+    table_options
+}
+
+fn block_cache_share(block_cache_size: u64, pct: u64) -> usize {
+    (block_cache_size as u128 * pct as u128 / 100) as usize
+}
+
+fn compression_type(compression: RocksdbCompression) -> DBCompressionType {
+    match compression {
+        RocksdbCompression::None => DBCompressionType::None,
+        RocksdbCompression::Lz4 => DBCompressionType::Lz4,
+        RocksdbCompression::Zstd => DBCompressionType::Zstd,
+    }
+}
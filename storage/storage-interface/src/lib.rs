@@ -44,9 +44,10 @@ mod metrics;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod mock;
 pub mod state_delta;
+pub mod state_snapshot_pin;
 pub mod state_view;
 
-use crate::state_delta::StateDelta;
+use crate::{state_delta::StateDelta, state_snapshot_pin::StateSnapshotPin};
 use aptos_scratchpad::SparseMerkleTree;
 pub use aptos_types::block_info::BlockHeight;
 use aptos_types::state_store::state_key::prefix::StateKeyPrefix;
@@ -187,6 +188,11 @@ pub trait DbReader: Send + Sync {
         /// [AptosDB::get_first_write_set_version]: ../aptosdb/struct.AptosDB.html#method.get_first_write_set_version
         fn get_first_write_set_version(&self) -> Result<Option<Version>>;
 
+        /// See [AptosDB::get_first_state_snapshot_version].
+        ///
+        /// [AptosDB::get_first_state_snapshot_version]: ../aptosdb/struct.AptosDB.html#method.get_first_state_snapshot_version
+        fn get_first_state_snapshot_version(&self) -> Result<Option<Version>>;
+
         /// See [AptosDB::get_transaction_outputs].
         ///
         /// [AptosDB::get_transaction_outputs]: ../aptosdb/struct.AptosDB.html#method.get_transaction_outputs
@@ -455,6 +461,12 @@ pub trait DbReader: Send + Sync {
 
         /// Returns state storage usage at the end of an epoch.
         fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage>;
+
+        /// Pins the state snapshot at the given version, i.e., keeps the pruners from pruning
+        /// it until the returned guard is dropped. This allows consistent reads of the state
+        /// at the version across multiple calls (e.g., paginated reads). Returns an error if
+        /// the state at the version is already pruned.
+        fn pin_state_snapshot(&self, version: Version) -> Result<StateSnapshotPin>;
     ); // end delegated
 
     /// Returns the latest ledger info.
@@ -0,0 +1,121 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Result;
+use aptos_types::transaction::Version;
+use parking_lot::Mutex;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Tracks the state snapshots that are currently pinned, i.e., the versions at which the state
+/// must not be pruned. This allows readers (e.g., API servers doing paginated reads across
+/// requests) to get consistent results, instead of racing the pruners.
+#[derive(Debug, Default)]
+pub struct StateSnapshotPins {
+    // The number of pins held for each pinned version
+    pinned_versions: Mutex<BTreeMap<Version, usize>>,
+}
+
+impl StateSnapshotPins {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Pins the state snapshot at the given version, if `ensure_readable` succeeds (i.e., the
+    /// state at the version hasn't been pruned yet). The pins are locked while checking, so a
+    /// version can't be pruned between the check and the pin (see `with_min_pinned_version`).
+    pub fn pin(
+        self: &Arc<Self>,
+        version: Version,
+        ensure_readable: impl FnOnce() -> Result<()>,
+    ) -> Result<StateSnapshotPin> {
+        let mut pinned_versions = self.pinned_versions.lock();
+        ensure_readable()?;
+        *pinned_versions.entry(version).or_insert(0) += 1;
+
+        Ok(StateSnapshotPin {
+            version,
+            pins: self.clone(),
+        })
+    }
+
+    /// Returns the lowest pinned version (if any)
+    pub fn get_min_pinned_version(&self) -> Option<Version> {
+        self.pinned_versions.lock().keys().next().copied()
+    }
+
+    /// Calls `f` with the lowest pinned version (if any). No versions can be pinned until `f`
+    /// returns, so pruners should update their min readable version within `f`.
+    pub fn with_min_pinned_version<T>(&self, f: impl FnOnce(Option<Version>) -> T) -> T {
+        let pinned_versions = self.pinned_versions.lock();
+        f(pinned_versions.keys().next().copied())
+    }
+
+    fn unpin(&self, version: Version) {
+        let mut pinned_versions = self.pinned_versions.lock();
+        if let Some(num_pins) = pinned_versions.get_mut(&version) {
+            *num_pins -= 1;
+            if *num_pins == 0 {
+                pinned_versions.remove(&version);
+            }
+        }
+    }
+}
+
+/// A guard that keeps the state snapshot at a version from being pruned (and so readable)
+/// until it is dropped. See `DbReader::pin_state_snapshot`.
+#[derive(Debug)]
+pub struct StateSnapshotPin {
+    version: Version,
+    pins: Arc<StateSnapshotPins>,
+}
+
+impl StateSnapshotPin {
+    /// Returns the version of the pinned state snapshot
+    pub fn version(&self) -> Version {
+        self.version
+    }
+}
+
+impl Drop for StateSnapshotPin {
+    fn drop(&mut self) {
+        self.pins.unpin(self.version);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AptosDbError;
+
+    #[test]
+    fn test_pin_and_unpin() {
+        let pins = StateSnapshotPins::new();
+        assert_eq!(pins.get_min_pinned_version(), None);
+
+        // Pin a few versions (including the same version twice)
+        let pin_10 = pins.pin(10, || Ok(())).unwrap();
+        let pin_20 = pins.pin(20, || Ok(())).unwrap();
+        let other_pin_10 = pins.pin(10, || Ok(())).unwrap();
+        assert_eq!(pin_10.version(), 10);
+        assert_eq!(pins.get_min_pinned_version(), Some(10));
+
+        // Verify the version stays pinned until all of its pins are dropped
+        drop(pin_10);
+        assert_eq!(pins.get_min_pinned_version(), Some(10));
+        drop(other_pin_10);
+        assert_eq!(pins.get_min_pinned_version(), Some(20));
+        drop(pin_20);
+        assert_eq!(pins.get_min_pinned_version(), None);
+    }
+
+    #[test]
+    fn test_pin_pruned_version() {
+        let pins = StateSnapshotPins::new();
+
+        // Verify versions that are no longer readable can't be pinned
+        pins.pin(10, || Err(AptosDbError::Other("pruned".to_string())))
+            .unwrap_err();
+        assert_eq!(pins.get_min_pinned_version(), None);
+        pins.with_min_pinned_version(|min_pinned_version| assert!(min_pinned_version.is_none()));
+    }
+}
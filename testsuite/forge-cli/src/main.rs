@@ -7,7 +7,7 @@
 use anyhow::{format_err, Context, Result};
 use aptos_config::config::{
     BootstrappingMode, ConsensusConfig, ContinuousSyncingMode, MempoolConfig, NetbenchConfig,
-    NodeConfig, StateSyncConfig,
+    NetbenchPayloadDistribution, NodeConfig, StateSyncConfig,
 };
 use aptos_forge::{
     args::TransactionTypeArg,
@@ -49,6 +49,7 @@ use aptos_testcases::{
     public_fullnode_performance::PFNPerformance,
     quorum_store_onchain_enable_test::QuorumStoreOnChainEnableTest,
     reconfiguration_test::ReconfigurationTest,
+    slow_validator_test::SlowValidatorTest,
     state_sync_performance::{
         StateSyncFullnodeFastSyncPerformance, StateSyncFullnodePerformance,
         StateSyncValidatorPerformance,
@@ -57,6 +58,7 @@ use aptos_testcases::{
     twin_validator_test::TwinValidatorTest,
     two_traffics_test::TwoTrafficsTest,
     validator_join_leave_test::ValidatorJoinLeaveTest,
+    validator_pause_test::ValidatorPauseTest,
     validator_reboot_stress_test::ValidatorRebootStressTest,
     CompositeNetworkTest,
 };
@@ -537,13 +539,17 @@ fn get_test_suite(
         "setup_test" => setup_test(),
         "single_vfn_perf" => single_vfn_perf(),
         "validator_reboot_stress_test" => validator_reboot_stress_test(),
+        "validator_pause_test" => validator_pause_test(),
+        "slow_validator_test" => slow_validator_test(),
         "fullnode_reboot_stress_test" => fullnode_reboot_stress_test(),
         "workload_mix" => workload_mix_test(),
+        "workload_shapes" => workload_shapes_test(),
         "account_creation" | "nft_mint" | "publishing" | "module_loading"
-        | "write_new_resource" => individual_workload_tests(test_name.into()),
+        | "write_new_resource" | "large_events" => individual_workload_tests(test_name.into()),
         "graceful_overload" => graceful_overload(),
         // not scheduled on continuous
         "load_vs_perf_benchmark" => load_vs_perf_benchmark(),
+        "open_loop_overload_benchmark" => open_loop_overload_benchmark(),
         "workload_vs_perf_benchmark" => workload_vs_perf_benchmark(),
         // maximizing number of rounds and epochs within a given time, to stress test consensus
         // so using small constant traffic, small blocks and fast rounds, and short epochs.
@@ -653,6 +659,10 @@ fn get_netbench_test(test_name: &str) -> Option<ForgeConfig> {
             net_bench_two_region_chaos(100 * KILOBYTE, 10)
         },
 
+        // Network tests with RPC echoes (i.e., round-trips) of mixed message sizes
+        "net_bench_rpc_echo_no_chaos" => net_bench_rpc_echo(false),
+        "net_bench_rpc_echo_bandwidth" => net_bench_rpc_echo(true),
+
         _ => return None, // The test name does not match a network benchmark test
     };
     Some(test)
@@ -1292,6 +1302,33 @@ fn load_vs_perf_benchmark() -> ForgeConfig {
         )
 }
 
+/// Like load_vs_perf_benchmark, but with open-loop Poisson arrivals, going beyond saturation,
+/// to see how latency, queueing delay and dropped submissions grow under overload.
+fn open_loop_overload_benchmark() -> ForgeConfig {
+    ForgeConfig::default()
+        .with_initial_validator_count(NonZeroUsize::new(20).unwrap())
+        .with_initial_fullnode_count(10)
+        .add_network_test(LoadVsPerfBenchmark {
+            test: Box::new(PerformanceBenchmark),
+            workloads: Workloads::POISSON(vec![1000, 5000, 10000, 15000, 20000, 30000]),
+            criteria: Vec::new(),
+            continuous_traffic: None,
+        })
+        .with_genesis_helm_config_fn(Arc::new(|helm_values| {
+            // no epoch change.
+            helm_values["chain"]["epoch_duration_secs"] = (24 * 3600).into();
+        }))
+        .with_success_criteria(
+            SuccessCriteria::new(0)
+                .add_no_restarts()
+                .add_wait_for_catchup_s(60)
+                .add_chain_progress(StateProgressThreshold {
+                    max_no_progress_secs: 30.0,
+                    max_round_gap: 10,
+                }),
+        )
+}
+
 fn workload_vs_perf_benchmark() -> ForgeConfig {
     ForgeConfig::default()
         .with_initial_validator_count(NonZeroUsize::new(7).unwrap())
@@ -1552,6 +1589,81 @@ fn workload_mix_test() -> ForgeConfig {
         )
 }
 
+/// Alternates a steady mix, which includes transactions emitting large events, with bursts of
+/// NFT and object mints, and storms of package publishing, one phase after the other.
+fn workload_shapes_emit_job() -> EmitJobRequest {
+    let steady_mix = vec![
+        (
+            TransactionTypeArg::CoinTransfer.materialize_default(),
+            10000,
+        ),
+        (
+            TransactionTypeArg::AccountGeneration.materialize_default(),
+            1000,
+        ),
+        (
+            TransactionTypeArg::LargeEvents10KB.materialize_default(),
+            300,
+        ),
+        (TransactionTypeArg::PublishPackage.materialize_default(), 3),
+    ];
+    let mut nft_mint_burst = steady_mix.clone();
+    nft_mint_burst.extend([
+        (
+            TransactionTypeArg::TokenV2AmbassadorMint.materialize_default(),
+            20000,
+        ),
+        (
+            TransactionTypeArg::TokenV1NFTMintAndTransferSequential.materialize_default(),
+            5000,
+        ),
+        (
+            TransactionTypeArg::CreateObjects10.materialize_default(),
+            5000,
+        ),
+    ]);
+    let mut publish_storm = steady_mix.clone();
+    publish_storm.push((
+        TransactionTypeArg::PublishPackage.materialize_default(),
+        3000,
+    ));
+
+    EmitJobRequest::default()
+        .mode(EmitJobMode::MaxLoad {
+            mempool_backlog: 20000,
+        })
+        .transaction_mix_per_phase(vec![
+            steady_mix.clone(),
+            nft_mint_burst.clone(),
+            steady_mix.clone(),
+            publish_storm.clone(),
+            steady_mix.clone(),
+            nft_mint_burst,
+            steady_mix,
+            publish_storm,
+        ])
+}
+
+fn workload_shapes_test() -> ForgeConfig {
+    ForgeConfig::default()
+        .with_initial_validator_count(NonZeroUsize::new(5).unwrap())
+        .with_initial_fullnode_count(3)
+        .add_network_test(PerformanceBenchmark)
+        .with_validator_override_node_config_fn(Arc::new(|config, _| {
+            config.execution.processed_transactions_detailed_counters = true;
+        }))
+        .with_emit_job(workload_shapes_emit_job())
+        .with_success_criteria(
+            SuccessCriteria::new(2000)
+                .add_no_restarts()
+                .add_wait_for_catchup_s(240)
+                .add_chain_progress(StateProgressThreshold {
+                    max_no_progress_secs: 20.0,
+                    max_round_gap: 6,
+                }),
+        )
+}
+
 fn individual_workload_tests(test_name: String) -> ForgeConfig {
     let job = EmitJobRequest::default().mode(EmitJobMode::MaxLoad {
         mempool_backlog: 30000,
@@ -1594,6 +1706,7 @@ fn individual_workload_tests(test_name: String) -> ForgeConfig {
                         TransactionTypeArg::AccountGeneration.materialize_default()
                     },
                     "publishing" => TransactionTypeArg::PublishPackage.materialize_default(),
+                    "large_events" => TransactionTypeArg::LargeEvents10KB.materialize_default(),
                     "module_loading" => TransactionTypeArg::NoOp.materialize(
                         1000,
                         false,
@@ -1607,6 +1720,7 @@ fn individual_workload_tests(test_name: String) -> ForgeConfig {
             SuccessCriteria::new(match test_name.as_str() {
                 "account_creation" => 3600,
                 "publishing" => 60,
+                "large_events" => 500,
                 "write_new_resource" => 3700,
                 "module_loading" => 1800,
                 _ => unreachable!("{}", test_name),
@@ -1644,6 +1758,30 @@ fn validator_reboot_stress_test() -> ForgeConfig {
         }))
 }
 
+fn validator_pause_test() -> ForgeConfig {
+    ForgeConfig::default()
+        .with_initial_validator_count(NonZeroUsize::new(7).unwrap())
+        .with_initial_fullnode_count(1)
+        .add_network_test(ValidatorPauseTest {
+            num_simultaneously: 2,
+            pause_time_secs: 10.0,
+            run_time_secs: 10.0,
+        })
+        .with_success_criteria(SuccessCriteria::new(2000).add_wait_for_catchup_s(600))
+}
+
+fn slow_validator_test() -> ForgeConfig {
+    ForgeConfig::default()
+        .with_initial_validator_count(NonZeroUsize::new(7).unwrap())
+        .with_initial_fullnode_count(1)
+        .add_network_test(SlowValidatorTest::default())
+        .with_success_criteria(
+            SuccessCriteria::new(2000)
+                .add_no_restarts()
+                .add_wait_for_catchup_s(240),
+        )
+}
+
 fn apply_config_for_quorum_store_single_node(config: &mut NodeConfig) {
     config
         .consensus
@@ -1708,6 +1846,57 @@ fn create_direct_send_netbench_config(
     netbench_config
 }
 
+/// Creates a netbench configuration for RPC echoes using the
+/// specified payload distribution, frequency and concurrency.
+fn create_rpc_echo_netbench_config(
+    payload_distribution: NetbenchPayloadDistribution,
+    rpc_frequency: u64,
+    rpc_in_flight: usize,
+) -> NetbenchConfig {
+    // Create the netbench config
+    let mut netbench_config = NetbenchConfig::default();
+
+    // Enable RPC echo network benchmarking
+    netbench_config.enabled = true;
+    netbench_config.enable_rpc_testing = true;
+    netbench_config.rpc_echo = true;
+
+    // Configure the payload sizes, frequency and concurrency
+    netbench_config.rpc_payload_distribution = payload_distribution;
+    netbench_config.rpc_per_second = rpc_frequency;
+    netbench_config.rpc_in_flight = rpc_in_flight;
+    netbench_config.max_network_channel_size = rpc_frequency * 2; // Double the channel size for an additional buffer
+
+    netbench_config
+}
+
+/// Performs RPC echo network benchmarking between validators, using mostly
+/// small messages interleaved with large ones (e.g., votes and blocks).
+/// If `limit_bandwidth` is set, the network bandwidth is also limited.
+fn net_bench_rpc_echo(limit_bandwidth: bool) -> ForgeConfig {
+    let netbench_config = create_rpc_echo_netbench_config(
+        NetbenchPayloadDistribution::Bimodal {
+            small_bytes: KILOBYTE,
+            large_bytes: MEGABYTE,
+            large_percentage: 5,
+        },
+        100,
+        16,
+    );
+    let forge_config = if limit_bandwidth {
+        ForgeConfig::default()
+            .with_initial_validator_count(NonZeroUsize::new(4).unwrap())
+            .add_network_test(NetworkBandwidthTest)
+    } else {
+        ForgeConfig::default()
+            .with_initial_validator_count(NonZeroUsize::new(2).unwrap())
+            .add_network_test(Delay::new(180))
+    };
+    forge_config.with_validator_override_node_config_fn(Arc::new(move |config, _| {
+        config.netbench = Some(netbench_config);
+    }))
+}
+
 /// Performs direct send network benchmarking between 2 validators
 /// using the specified message size and frequency.
 fn net_bench_no_chaos(message_size: usize, message_frequency: u64) -> ForgeConfig {
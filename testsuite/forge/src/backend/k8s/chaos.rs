@@ -3,7 +3,8 @@
 
 use crate::{
     dump_string_to_file, K8sSwarm, Result, Swarm, SwarmChaos, SwarmCpuStress, SwarmNetEm,
-    SwarmNetworkBandwidth, SwarmNetworkDelay, SwarmNetworkLoss, SwarmNetworkPartition, KUBECTL_BIN,
+    SwarmNetworkBandwidth, SwarmNetworkDelay, SwarmNetworkLoss, SwarmNetworkPartition,
+    SwarmResourceStress, KUBECTL_BIN,
 };
 use anyhow::bail;
 use aptos_logger::info;
@@ -45,6 +46,12 @@ macro_rules! CPU_STRESS_CHAOS_TEMPLATE {
     };
 }
 
+macro_rules! RESOURCE_STRESS_CHAOS_TEMPLATE {
+    () => {
+        "chaos/resource_stress.yaml"
+    };
+}
+
 // The node name for an address that could not be found in the swarm
 const INVALID_NODE_STRING: &str = "invalid-node";
 
@@ -211,6 +218,47 @@ impl K8sSwarm {
         Ok(cpu_stress_specs.join("\n---\n"))
     }
 
+    fn create_resource_stress_template(
+        &self,
+        swarm_resource_stress: &SwarmResourceStress,
+    ) -> Result<String> {
+        let mut resource_stress_specs = vec![];
+
+        for group_resource_stress in &swarm_resource_stress.group_resource_stresses {
+            let instance_labels = self.get_instance_labels(&group_resource_stress.target_nodes);
+
+            let mut stressors = vec![];
+            if let Some(cpu) = &group_resource_stress.cpu {
+                stressors.push(format!(
+                    "    cpu:\n      workers: {}\n      load: {}",
+                    cpu.num_workers, cpu.load_per_worker
+                ));
+            }
+            if let Some(memory) = &group_resource_stress.memory {
+                stressors.push(format!(
+                    "    memory:\n      workers: {}\n      size: {}",
+                    memory.num_workers, memory.size_per_worker
+                ));
+            }
+            if stressors.is_empty() {
+                bail!(
+                    "Resource stress {} has neither CPU nor memory stress",
+                    group_resource_stress.name
+                );
+            }
+
+            resource_stress_specs.push(format!(
+                include_str!(RESOURCE_STRESS_CHAOS_TEMPLATE!()),
+                name = &group_resource_stress.name,
+                namespace = self.kube_namespace,
+                instance_labels = &instance_labels,
+                stressors = stressors.join("\n"),
+            ));
+        }
+
+        Ok(resource_stress_specs.join("\n---\n"))
+    }
+
     fn create_chaos_template(&self, chaos: &SwarmChaos) -> Result<String> {
         match chaos {
             SwarmChaos::Delay(c) => self.create_network_delay_template(c),
@@ -219,6 +267,7 @@ impl K8sSwarm {
             SwarmChaos::Loss(c) => self.create_network_loss_template(c),
             SwarmChaos::NetEm(c) => self.create_netem_template(c),
             SwarmChaos::CpuStress(c) => self.create_cpu_stress_template(c),
+            SwarmChaos::Resource(c) => self.create_resource_stress_template(c),
         }
     }
 
@@ -109,6 +109,36 @@ impl K8sNode {
         }
     }
 
+    /// Sends the signal (e.g. `STOP` or `CONT`) to the node process in its container
+    fn signal(&self, signal: &str) -> Result<()> {
+        let signal_arg = format!("-{}", signal);
+        let pkill_args = [
+            "-n",
+            self.namespace(),
+            "exec",
+            &format!("sts/{}", self.stateful_set_name()),
+            "--",
+            "pkill",
+            &signal_arg,
+            "-x",
+            "aptos-node",
+        ];
+        info!("{:?}", pkill_args);
+        let pkill_output = Command::new(KUBECTL_BIN)
+            .stdout(Stdio::inherit())
+            .args(pkill_args)
+            .output()?;
+        if !pkill_output.status.success() {
+            return Err(anyhow!(
+                "Failed to send SIG{} to node {}: {}",
+                signal,
+                self.stateful_set_name(),
+                String::from_utf8_lossy(&pkill_output.stderr)
+            ));
+        }
+        Ok(())
+    }
+
     pub fn port_forward_rest_api(&self) -> Result<()> {
         let remote_rest_api_port = if self.haproxy_enabled {
             REST_API_HAPROXY_SERVICE_PORT
@@ -150,6 +180,16 @@ impl Node for K8sNode {
         scale_stateful_set_replicas(self.stateful_set_name(), self.namespace(), 0).await
     }
 
+    async fn pause(&mut self) -> Result<()> {
+        info!("going to pause node {}", self.stateful_set_name());
+        self.signal("STOP")
+    }
+
+    async fn resume(&mut self) -> Result<()> {
+        info!("going to resume node {}", self.stateful_set_name());
+        self.signal("CONT")
+    }
+
     fn version(&self) -> Version {
         self.version.clone()
     }
@@ -181,6 +181,26 @@ impl LocalNode {
         self.process = None;
     }
 
+    /// Sends the signal (e.g. `STOP` or `CONT`) to the node process
+    fn signal(&self, signal: &str) -> Result<()> {
+        let process = self
+            .process
+            .as_ref()
+            .ok_or_else(|| anyhow!("node {} is not running", self.name))?;
+        let status = Command::new("kill")
+            .arg(format!("-{}", signal))
+            .arg(process.0.id().to_string())
+            .status()?;
+        ensure!(
+            status.success(),
+            "failed to send SIG{} to node {}: {}",
+            signal,
+            self.name,
+            status
+        );
+        Ok(())
+    }
+
     pub fn port(&self) -> u16 {
         self.config.api.address.port()
     }
@@ -290,6 +310,14 @@ impl Node for LocalNode {
         Ok(())
     }
 
+    async fn pause(&mut self) -> Result<()> {
+        self.signal("STOP")
+    }
+
+    async fn resume(&mut self) -> Result<()> {
+        self.signal("CONT")
+    }
+
     async fn get_identity(&mut self) -> Result<String> {
         todo!()
     }
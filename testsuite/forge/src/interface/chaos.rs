@@ -12,6 +12,7 @@ pub enum SwarmChaos {
     Loss(SwarmNetworkLoss),
     NetEm(SwarmNetEm),
     CpuStress(SwarmCpuStress),
+    Resource(SwarmResourceStress),
 }
 
 #[derive(Eq, Hash, PartialEq, Debug, Clone)]
@@ -124,3 +125,41 @@ pub struct GroupCpuStress {
     pub num_workers: u64,
     pub load_per_worker: u64,
 }
+
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct SwarmResourceStress {
+    pub group_resource_stresses: Vec<GroupResourceStress>,
+}
+
+impl Display for SwarmResourceStress {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "ResourceStress nodes {:?}", self.group_resource_stresses)
+    }
+}
+
+/// Stresses the CPU and/or memory of the target nodes, from within their resource limits, so
+/// that they behave like underprovisioned nodes
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct GroupResourceStress {
+    pub name: String,
+    pub target_nodes: Vec<PeerId>,
+    /// The CPU stress, if any
+    pub cpu: Option<CpuStressor>,
+    /// The memory stress, if any
+    pub memory: Option<MemoryStressor>,
+}
+
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct CpuStressor {
+    pub num_workers: u64,
+    /// Load of each worker, as a percentage of a CPU
+    pub load_per_worker: u64,
+}
+
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct MemoryStressor {
+    pub num_workers: u64,
+    /// Memory kept allocated by each worker, either in bytes with a unit (e.g. `256MB`), or as
+    /// a percentage of the total memory (e.g. `10%`)
+    pub size_per_worker: String,
+}
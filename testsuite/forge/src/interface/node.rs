@@ -61,6 +61,13 @@ pub trait Node: Send + Sync {
     /// This should be a noop if the Node isn't running.
     async fn stop(&mut self) -> Result<()>;
 
+    /// Pause this Node's process, without stopping it, e.g. to make a validator unresponsive
+    /// while it keeps its connections and in-memory state.
+    async fn pause(&mut self) -> Result<()>;
+
+    /// Resume this Node's process after it was paused.
+    async fn resume(&mut self) -> Result<()>;
+
     async fn get_identity(&mut self) -> Result<String>;
 
     async fn set_identity(&mut self, k8s_secret_name: String) -> Result<()>;
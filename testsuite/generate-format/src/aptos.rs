@@ -14,6 +14,7 @@ use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use aptos_types::{
     block_metadata_ext::BlockMetadataExt,
     contract_event, event,
+    jwks::{rsa::RSA_JWK, unsupported::UnsupportedJWK, ObservedJWKs, PatchedJWKs},
     state_store::{
         state_key::StateKey,
         state_value::{PersistedStateValueMetadata, StateValueMetadata},
@@ -32,6 +33,9 @@ pub fn output_file() -> Option<&'static str> {
     Some("tests/staged/aptos.yaml")
 }
 
+/// The version of the recorded formats, to be bumped whenever they change.
+pub const SCHEMA_VERSION: u64 = 1;
+
 /// This aims at signing canonically serializable BCS data
 #[derive(CryptoHasher, BCSCryptoHash, Serialize, Deserialize)]
 struct TestAptosCrypto(String);
@@ -113,6 +117,12 @@ pub fn get_registry() -> Result<Registry> {
     tracer.trace_type::<aptos_types::keyless::EphemeralCertificate>(&samples)?;
     tracer.trace_type::<write_set::WriteOp>(&samples)?;
 
+    // JWKs on chain (and the JWK variants packed into `JWKMoveStruct`)
+    tracer.trace_type::<ObservedJWKs>(&samples)?;
+    tracer.trace_type::<PatchedJWKs>(&samples)?;
+    tracer.trace_type::<RSA_JWK>(&samples)?;
+    tracer.trace_type::<UnsupportedJWK>(&samples)?;
+
     // aliases within StructTag
     tracer.ignore_aliases("StructTag", &["type_params"])?;
 
@@ -3,9 +3,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::Parser;
-use generate_format::Corpus;
+use generate_format::{schema_digest, Corpus, SchemaVersions, SCHEMA_VERSIONS_FILE};
 use std::{fs::File, io::Write};
 
+/// The directory of this crate, relative to the root of the repository
+const CRATE_DIR: &str = "testsuite/generate-format/";
+
 #[derive(Debug, Parser)]
 #[clap(
     name = "Aptos format generator",
@@ -29,7 +32,8 @@ fn main() {
     if options.record {
         match output_file {
             Some(path) => {
-                let mut f = File::create("testsuite/generate-format/".to_string() + path).unwrap();
+                record_schema_version(options.corpus, &content);
+                let mut f = File::create(CRATE_DIR.to_string() + path).unwrap();
                 write!(f, "{}", content).unwrap();
             },
             None => panic!("Corpus {:?} doesn't record formats on disk", options.corpus),
@@ -39,6 +43,31 @@ fn main() {
     }
 }
 
+/// Records the digest of the formats under the current schema version of the corpus. Fails if
+/// the formats changed but the schema version was not bumped.
+fn record_schema_version(corpus: Corpus, content: &str) {
+    let path = CRATE_DIR.to_string() + SCHEMA_VERSIONS_FILE;
+    let mut versions: SchemaVersions =
+        serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+    let version = corpus.schema_version();
+    let digest = schema_digest(content);
+    let corpus_versions = versions.entry(corpus.to_string()).or_default();
+    match corpus_versions.get(&version) {
+        Some(recorded_digest) if *recorded_digest != digest => panic!(
+            "The formats of corpus {} changed, but schema version {} was already recorded. \
+            Please bump its `SCHEMA_VERSION`.",
+            corpus, version
+        ),
+        _ => {
+            corpus_versions.insert(version, digest);
+        },
+    }
+
+    let mut f = File::create(path).unwrap();
+    write!(f, "{}", serde_yaml::to_string(&versions).unwrap()).unwrap();
+}
+
 #[test]
 fn verify_tool() {
     use clap::CommandFactory;
@@ -32,6 +32,9 @@ pub fn output_file() -> Option<&'static str> {
     Some("tests/staged/consensus.yaml")
 }
 
+/// The version of the recorded formats, to be bumped whenever they change.
+pub const SCHEMA_VERSION: u64 = 1;
+
 /// This aims at signing canonically serializable BCS data
 #[derive(CryptoHasher, BCSCryptoHash, Serialize, Deserialize)]
 struct TestAptosCrypto(String);
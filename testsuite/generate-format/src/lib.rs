@@ -5,7 +5,10 @@
 //! How and where to record the Serde format of interesting Aptos types.
 //! See API documentation with `cargo doc -p serde-reflection --open`
 
-use aptos_crypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use aptos_crypto::{
+    ed25519::{Ed25519PublicKey, Ed25519Signature},
+    HashValue,
+};
 use aptos_types::{
     keyless,
     keyless::{EphemeralCertificate, Groth16Proof, IdCommitment, Pepper, ZeroKnowledgeSig},
@@ -13,7 +16,10 @@ use aptos_types::{
 };
 use clap::{Parser, ValueEnum};
 use serde_reflection::{Registry, Samples, Tracer};
-use std::fmt::{Display, Formatter};
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+};
 
 /// Rest API types
 mod api;
@@ -30,6 +36,18 @@ mod network;
 
 pub use linter::lint_bcs_format;
 
+/// Where the schema versions of the recorded corpuses are recorded on disk.
+pub const SCHEMA_VERSIONS_FILE: &str = "tests/staged/versions.yaml";
+
+/// The digests of the recorded formats of each corpus, by corpus name and schema version.
+/// Past versions are kept, so that external consumers can tell which formats they were built with.
+pub type SchemaVersions = BTreeMap<String, BTreeMap<u64, String>>;
+
+/// The digest of the recorded formats (i.e., the content of the recorded file).
+pub fn schema_digest(content: &str) -> String {
+    HashValue::sha3_256_of(content.as_bytes()).to_hex()
+}
+
 #[derive(Debug, Parser, Clone, Copy, ValueEnum)]
 /// A corpus of Rust types to trace, and optionally record on disk.
 pub enum Corpus {
@@ -68,6 +86,17 @@ impl Corpus {
             Corpus::MoveABI => move_abi::output_file(),
         }
     }
+
+    /// The current version of the formats of this corpus.
+    pub fn schema_version(self) -> u64 {
+        match self {
+            Corpus::API => api::SCHEMA_VERSION,
+            Corpus::Aptos => aptos::SCHEMA_VERSION,
+            Corpus::Consensus => consensus::SCHEMA_VERSION,
+            Corpus::Network => network::SCHEMA_VERSION,
+            Corpus::MoveABI => move_abi::SCHEMA_VERSION,
+        }
+    }
 }
 
 impl Display for Corpus {
@@ -11,6 +11,9 @@ pub fn output_file() -> Option<&'static str> {
     Some("tests/staged/move_abi.yaml")
 }
 
+/// The version of the recorded formats, to be bumped whenever they change.
+pub const SCHEMA_VERSION: u64 = 1;
+
 pub fn get_registry() -> Result<Registry> {
     let mut tracer =
         Tracer::new(TracerConfig::default().is_human_readable(bcs::is_human_readable()));
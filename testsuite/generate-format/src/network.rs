@@ -17,6 +17,9 @@ pub fn output_file() -> Option<&'static str> {
     Some("tests/staged/network.yaml")
 }
 
+/// The version of the recorded formats, to be bumped whenever they change.
+pub const SCHEMA_VERSION: u64 = 2;
+
 /// Record sample values for crypto types used by network.
 fn trace_crypto_values(tracer: &mut Tracer, samples: &mut Samples) -> Result<()> {
     let mut rng: StdRng = SeedableRng::from_seed([0; 32]);
@@ -48,6 +51,7 @@ pub fn get_registry() -> Result<Registry> {
     tracer.trace_type::<messaging::v1::ErrorCode>(&samples)?;
     tracer.trace_type::<messaging::v1::ParsingErrorType>(&samples)?;
     tracer.trace_type::<messaging::v1::NotSupportedType>(&samples)?;
+    tracer.trace_type::<messaging::v1::RpcStreamStatus>(&samples)?;
     tracer.trace_type::<handshake::v1::ProtocolId>(&samples)?;
     tracer.trace_type::<address::Protocol>(&samples)?;
     tracer.trace_type::<aptos_config::network_id::NetworkId>(&samples)?;
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::ValueEnum;
-use generate_format::Corpus;
+use generate_format::{schema_digest, Corpus, SchemaVersions, SCHEMA_VERSIONS_FILE};
 use serde_reflection::Registry;
 use std::collections::{btree_map::Entry, BTreeMap};
 
@@ -95,6 +95,52 @@ Type `{}` was added and has no recorded format in {} yet.{}
     }
 }
 
+#[test]
+fn recorded_formats_match_schema_versions() {
+    let content = std::fs::read_to_string(SCHEMA_VERSIONS_FILE).unwrap();
+    let versions = serde_yaml::from_str::<SchemaVersions>(content.as_str()).unwrap();
+
+    for corpus in Corpus::value_variants() {
+        let Some(path) = corpus.output_file() else {
+            continue;
+        };
+        let content = std::fs::read_to_string(path).unwrap();
+        let corpus_versions = versions.get(&corpus.to_string()).unwrap_or_else(|| {
+            panic!(
+                "Corpus {} has no recorded schema versions in {}.{}",
+                corpus,
+                SCHEMA_VERSIONS_FILE,
+                message(&corpus.to_string())
+            )
+        });
+
+        // Versions only increase, so the current version must be the latest recorded one
+        let version = corpus.schema_version();
+        assert_eq!(
+            corpus_versions.keys().next_back(),
+            Some(&version),
+            "Schema version {} of corpus {} is not the latest one recorded in {}",
+            version,
+            corpus,
+            SCHEMA_VERSIONS_FILE,
+        );
+        assert_eq!(
+            corpus_versions.get(&version),
+            Some(&schema_digest(&content)),
+            r#"
+----
+The recorded formats in {} do not match schema version {} of corpus {}.
+If the formats changed, please bump its `SCHEMA_VERSION` and record them again.{}
+----
+"#,
+            path,
+            version,
+            corpus,
+            message(&corpus.to_string()),
+        );
+    }
+}
+
 #[test]
 fn test_we_can_detect_changes_in_yaml() {
     let yaml1 = r#"---
@@ -55,7 +55,7 @@ async fn test_account_flow() {
             Some(GasOptions {
                 gas_unit_price: Some(2),
                 max_gas: None,
-                expiration_secs: 30,
+                expiration_secs: Some(30),
             }),
         )
         .await
@@ -79,7 +79,7 @@ async fn test_account_flow() {
             // NOTE(Gas): This should be equal to the min gas amount allowed.
             //            Read the comment above to understand why.
             max_gas: Some(2),
-            expiration_secs: 30,
+            expiration_secs: Some(30),
         }),
     )
     .await
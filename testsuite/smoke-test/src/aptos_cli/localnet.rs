@@ -0,0 +1,28 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos::node::local_testnet::InProcessLocalnetBuilder;
+use aptos_rest_client::{Client as RestClient, FaucetClient};
+use aptos_types::account_address::AccountAddress;
+
+#[tokio::test]
+async fn test_in_process_localnet() {
+    let localnet = InProcessLocalnetBuilder::new().start().await.unwrap();
+
+    // Fund an account through the faucet, and check its balance through the node API
+    let rest_client = RestClient::new(localnet.node_api_url().clone());
+    let faucet_client = FaucetClient::new_from_rest_client(
+        localnet.faucet_url().unwrap().clone(),
+        rest_client.clone(),
+    );
+    let account = AccountAddress::random();
+    faucet_client.fund(account, 1_000).await.unwrap();
+    let balance = rest_client
+        .get_account_balance(account)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(balance.get(), 1_000);
+
+    localnet.shutdown().await.unwrap();
+}
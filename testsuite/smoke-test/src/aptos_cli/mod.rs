@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod account;
+mod localnet;
 #[cfg(feature = "cli-framework-test-move")]
 mod r#move;
 pub mod validator;
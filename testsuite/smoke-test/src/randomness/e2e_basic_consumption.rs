@@ -51,7 +51,7 @@ async fn e2e_basic_consumption() {
         let gas_options = GasOptions {
             gas_unit_price: Some(100),
             max_gas: Some(10_000), // should match the default required gas deposit.
-            expiration_secs: 60,
+            expiration_secs: Some(60),
         };
         let txn_summary = cli
             .run_function(0, Some(gas_options), roll_func_id.clone(), vec![], vec![])
@@ -322,7 +322,7 @@ script {{
     let gas_options = GasOptions {
         gas_unit_price: Some(100),
         max_gas: Some(2000000),
-        expiration_secs: 60,
+        expiration_secs: Some(60),
     };
     let txn_summary = cli
         .run_script_with_gas_options(root_idx, script.as_str(), Some(gas_options))
@@ -345,7 +345,7 @@ script {{
         let gas_options = GasOptions {
             gas_unit_price: Some(100),
             max_gas: Some(max_gas),
-            expiration_secs: 60,
+            expiration_secs: Some(60),
         };
         let txn_summary = cli
             .run_function(
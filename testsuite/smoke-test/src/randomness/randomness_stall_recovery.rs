@@ -115,7 +115,7 @@ script {
     let gas_options = GasOptions {
         gas_unit_price: Some(1),
         max_gas: Some(2000000),
-        expiration_secs: 60,
+        expiration_secs: Some(60),
     };
     let txn_summary = cli
         .run_script_with_gas_options(root_idx, script, Some(gas_options))
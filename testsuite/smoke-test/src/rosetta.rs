@@ -114,6 +114,7 @@ async fn setup_test(
             validator.rest_api_endpoint(),
         )),
         cli.addresses(),
+        HashSet::new(),
     )
     .await
     .unwrap();
@@ -339,7 +340,7 @@ async fn test_account_balance() {
             Some(GasOptions {
                 gas_unit_price: None,
                 max_gas: Some(1000),
-                expiration_secs: 30,
+                expiration_secs: Some(30),
             }),
         )
         .await
@@ -1856,7 +1857,7 @@ async fn test_invalid_transaction_gas_charged() {
             Some(GasOptions {
                 gas_unit_price: None,
                 max_gas: Some(1000),
-                expiration_secs: 30,
+                expiration_secs: Some(30),
             }),
         )
         .await
@@ -19,11 +19,13 @@ pub mod performance_test;
 pub mod public_fullnode_performance;
 pub mod quorum_store_onchain_enable_test;
 pub mod reconfiguration_test;
+pub mod slow_validator_test;
 pub mod state_sync_performance;
 pub mod three_region_simulation_test;
 pub mod twin_validator_test;
 pub mod two_traffics_test;
 pub mod validator_join_leave_test;
+pub mod validator_pause_test;
 pub mod validator_reboot_stress_test;
 
 use anyhow::Context;
@@ -31,13 +31,15 @@ pub struct SingleRunStats {
 #[derive(Debug)]
 pub enum Workloads {
     TPS(Vec<usize>),
+    /// Open-loop load with Poisson arrivals, at each of the given average TPS
+    POISSON(Vec<usize>),
     TRANSACTIONS(Vec<TransactionWorkload>),
 }
 
 impl Workloads {
     fn len(&self) -> usize {
         match self {
-            Self::TPS(tpss) => tpss.len(),
+            Self::TPS(tpss) | Self::POISSON(tpss) => tpss.len(),
             Self::TRANSACTIONS(workloads) => workloads.len(),
         }
     }
@@ -45,13 +47,14 @@ impl Workloads {
     fn type_name(&self) -> String {
         match self {
             Self::TPS(_) => "Load (TPS)".to_string(),
+            Self::POISSON(_) => "Open-loop load (TPS)".to_string(),
             Self::TRANSACTIONS(_) => "Workload".to_string(),
         }
     }
 
     fn num_phases(&self, index: usize) -> usize {
         match self {
-            Self::TPS(_) => 1,
+            Self::TPS(_) | Self::POISSON(_) => 1,
             Self::TRANSACTIONS(workloads) => {
                 if workloads[index].is_phased() {
                     2
@@ -64,7 +67,7 @@ impl Workloads {
 
     fn phase_name(&self, index: usize, phase: usize) -> String {
         match self {
-            Self::TPS(tpss) => {
+            Self::TPS(tpss) | Self::POISSON(tpss) => {
                 assert_eq!(phase, 0);
                 format!("{}", tpss[index])
             },
@@ -84,6 +87,7 @@ impl Workloads {
     fn configure(&self, index: usize, request: EmitJobRequest) -> EmitJobRequest {
         match self {
             Self::TPS(tpss) => request.mode(EmitJobMode::ConstTps { tps: tpss[index] }),
+            Self::POISSON(tpss) => request.mode(EmitJobMode::PoissonTps { tps: tpss[index] }),
             Self::TRANSACTIONS(workloads) => workloads[index].configure(request),
         }
     }
@@ -351,17 +355,19 @@ impl NetworkTest for LoadVsPerfBenchmark {
 fn to_table(type_name: String, results: &[Vec<SingleRunStats>]) -> Vec<String> {
     let mut table = Vec::new();
     table.push(format!(
-        "{: <40} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12}",
+        "{: <40} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12}",
         type_name,
         "submitted/s",
         "committed/s",
         "expired/s",
         "rejected/s",
+        "dropped/s",
         "chain txn/s",
         "latency",
         "p50 lat",
         "p90 lat",
         "p99 lat",
+        "p99 queueing",
         "batch->pos",
         "pos->prop",
         "prop->order",
@@ -373,17 +379,19 @@ fn to_table(type_name: String, results: &[Vec<SingleRunStats>]) -> Vec<String> {
         for result in run_results {
             let rate = result.stats.rate();
             table.push(format!(
-                "{: <40} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12.3} | {: <12.3} | {: <12.3} | {: <12.3} | {: <12}",
+                "{: <40} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12} | {: <12.3} | {: <12.3} | {: <12.3} | {: <12.3} | {: <12}",
                 result.name,
                 rate.submitted,
                 rate.committed,
                 rate.expired,
                 rate.failed_submission,
+                rate.dropped,
                 result.ledger_transactions / result.actual_duration.as_secs(),
                 rate.latency,
                 rate.p50_latency,
                 rate.p90_latency,
                 rate.p99_latency,
+                rate.p99_queueing_delay,
                 result.latency_breakdown.get_samples(&LatencyBreakdownSlice::QsBatchToPos).max_sample(),
                 result.latency_breakdown.get_samples(&LatencyBreakdownSlice::QsPosToProposal).max_sample(),
                 result.latency_breakdown.get_samples(&LatencyBreakdownSlice::ConsensusProposalToOrdered).max_sample(),
@@ -0,0 +1,85 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{LoadDestination, NetworkLoadTest};
+use aptos_forge::{
+    CpuStressor, GroupResourceStress, MemoryStressor, NetworkContext, NetworkTest, Swarm,
+    SwarmChaos, SwarmResourceStress, Test,
+};
+
+/// Stresses the CPU and memory of a single validator for the duration of the test, to check
+/// that consensus tolerates one underprovisioned validator.
+pub struct SlowValidatorTest {
+    pub cpu: Option<CpuStressor>,
+    pub memory: Option<MemoryStressor>,
+}
+
+impl Default for SlowValidatorTest {
+    fn default() -> Self {
+        Self {
+            cpu: Some(CpuStressor {
+                num_workers: 4,
+                load_per_worker: 100,
+            }),
+            memory: Some(MemoryStressor {
+                num_workers: 1,
+                size_per_worker: "25%".to_string(),
+            }),
+        }
+    }
+}
+
+impl SlowValidatorTest {
+    /// The same validator is picked on setup and finish, so the chaos can be removed.
+    fn create_resource_stress(&self, swarm: &dyn Swarm) -> SwarmResourceStress {
+        let slow_validator = swarm
+            .validators()
+            .map(|v| v.peer_id())
+            .min()
+            .expect("There must be at least one validator");
+        SwarmResourceStress {
+            group_resource_stresses: vec![GroupResourceStress {
+                name: "slow-validator-resource-stress".to_string(),
+                target_nodes: vec![slow_validator],
+                cpu: self.cpu.clone(),
+                memory: self.memory.clone(),
+            }],
+        }
+    }
+}
+
+impl Test for SlowValidatorTest {
+    fn name(&self) -> &'static str {
+        "slow validator test"
+    }
+}
+
+impl NetworkLoadTest for SlowValidatorTest {
+    fn setup(&self, ctx: &mut NetworkContext) -> anyhow::Result<LoadDestination> {
+        let resource_stress = self.create_resource_stress(ctx.swarm());
+        let msg = format!("Injecting {}", resource_stress);
+        println!("{}", msg);
+        ctx.report.report_text(msg);
+
+        ctx.runtime.block_on(
+            ctx.swarm
+                .inject_chaos(SwarmChaos::Resource(resource_stress)),
+        )?;
+
+        Ok(LoadDestination::FullnodesOtherwiseValidators)
+    }
+
+    fn finish(&self, ctx: &mut NetworkContext) -> anyhow::Result<()> {
+        let resource_stress = self.create_resource_stress(ctx.swarm());
+        ctx.runtime.block_on(
+            ctx.swarm
+                .remove_chaos(SwarmChaos::Resource(resource_stress)),
+        )
+    }
+}
+
+impl NetworkTest for SlowValidatorTest {
+    fn run(&self, ctx: &mut NetworkContext<'_>) -> anyhow::Result<()> {
+        <dyn NetworkLoadTest>::run(self, ctx)
+    }
+}
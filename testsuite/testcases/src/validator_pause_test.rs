@@ -0,0 +1,161 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::NetworkLoadTest;
+use anyhow::bail;
+use aptos_forge::{NetworkContext, NetworkTest, NodeExt, Result, Swarm, Test, TestReport};
+use aptos_logger::warn;
+use aptos_sdk::types::PeerId;
+use rand::{seq::SliceRandom, thread_rng};
+use std::time::Duration;
+use tokio::{runtime::Runtime, time::Instant};
+
+/// Counts the rounds that ended with a timeout rather than a quorum certificate
+const TIMEOUT_COUNT_METRIC: &str = "aptos_consensus_timeout_count";
+
+/// Repeatedly pauses some validators' processes, e.g. with SIGSTOP, and resumes them. Unlike
+/// stopping a validator, its peers stay connected to it, so when a paused validator is the
+/// leader, the others have to time out of the round, and move on to the next leader.
+pub struct ValidatorPauseTest {
+    pub num_simultaneously: usize,
+    pub pause_time_secs: f32,
+    pub run_time_secs: f32,
+}
+
+impl Test for ValidatorPauseTest {
+    fn name(&self) -> &'static str {
+        "validator pause test"
+    }
+}
+
+/// Validators paused by the test, which are resumed when dropped, so that an error doesn't leave
+/// the network stalled.
+struct PausedValidators<'a> {
+    swarm: &'a mut dyn Swarm,
+    runtime: &'a Runtime,
+    paused: Vec<PeerId>,
+}
+
+impl<'a> PausedValidators<'a> {
+    fn pause(
+        swarm: &'a mut dyn Swarm,
+        runtime: &'a Runtime,
+        validators: &[PeerId],
+    ) -> Result<Self> {
+        let mut paused_validators = Self {
+            swarm,
+            runtime,
+            paused: vec![],
+        };
+        for peer_id in validators {
+            let validator = paused_validators.swarm.validator_mut(*peer_id).unwrap();
+            runtime.block_on(validator.pause())?;
+            paused_validators.paused.push(*peer_id);
+        }
+        Ok(paused_validators)
+    }
+
+    fn resume(mut self) -> Result<()> {
+        while let Some(peer_id) = self.paused.pop() {
+            let validator = self.swarm.validator_mut(peer_id).unwrap();
+            if let Err(err) = self.runtime.block_on(validator.resume()) {
+                // Leave it to be retried on drop
+                self.paused.push(peer_id);
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PausedValidators<'_> {
+    fn drop(&mut self) {
+        for peer_id in self.paused.drain(..) {
+            let validator = self.swarm.validator_mut(peer_id).unwrap();
+            if let Err(err) = self.runtime.block_on(validator.resume()) {
+                warn!("Failed to resume validator {}: {:?}", peer_id, err);
+            }
+        }
+    }
+}
+
+fn sum_timeout_counts(swarm: &dyn Swarm, runtime: &Runtime, validators: &[PeerId]) -> Result<i64> {
+    let mut sum = 0;
+    for peer_id in validators {
+        let validator = swarm.validator(*peer_id).unwrap();
+        sum += runtime
+            .block_on(validator.get_metric_i64(TIMEOUT_COUNT_METRIC))?
+            .unwrap_or(0);
+    }
+    Ok(sum)
+}
+
+impl NetworkLoadTest for ValidatorPauseTest {
+    fn test(
+        &self,
+        swarm: &mut dyn Swarm,
+        report: &mut TestReport,
+        duration: Duration,
+    ) -> Result<()> {
+        let start = Instant::now();
+        let runtime = Runtime::new().unwrap();
+
+        let all_validators = swarm.validators().map(|v| v.peer_id()).collect::<Vec<_>>();
+
+        let mut rng = thread_rng();
+
+        let mut num_pauses = 0;
+        let mut num_timeouts = 0;
+        while start.elapsed() < duration {
+            let addresses: Vec<_> = all_validators
+                .choose_multiple(&mut rng, self.num_simultaneously)
+                .cloned()
+                .collect();
+            let running_validators: Vec<_> = all_validators
+                .iter()
+                .filter(|peer_id| !addresses.contains(peer_id))
+                .cloned()
+                .collect();
+
+            // The counters of paused validators can't be read, so only the running ones are
+            // compared, and they aren't reset, as long as the validators aren't restarted.
+            let timeouts_before = sum_timeout_counts(swarm, &runtime, &running_validators)?;
+            // The paused validators are resumed on every exit path, including errors.
+            let paused_validators = PausedValidators::pause(swarm, &runtime, &addresses)?;
+            if self.pause_time_secs > 0.0 {
+                std::thread::sleep(Duration::from_secs_f32(self.pause_time_secs));
+            }
+            let timeouts_after =
+                sum_timeout_counts(paused_validators.swarm, &runtime, &running_validators)?;
+            paused_validators.resume()?;
+            num_pauses += 1;
+            num_timeouts += timeouts_after - timeouts_before;
+
+            if self.run_time_secs > 0.0 {
+                std::thread::sleep(Duration::from_secs_f32(self.run_time_secs));
+            }
+        }
+
+        let msg = format!(
+            "Paused {} validators {} times for {}s, the running validators timed out {} times",
+            self.num_simultaneously, num_pauses, self.pause_time_secs, num_timeouts
+        );
+        println!("{}", msg);
+        report.report_text(msg);
+
+        // A paused leader can only be moved on from by timing out of its round
+        if num_pauses > 0 && num_timeouts == 0 {
+            bail!(
+                "Validators were paused {} times, but none of the running validators timed out",
+                num_pauses
+            );
+        }
+        Ok(())
+    }
+}
+
+impl NetworkTest for ValidatorPauseTest {
+    fn run(&self, ctx: &mut NetworkContext<'_>) -> Result<()> {
+        <dyn NetworkLoadTest>::run(self, ctx)
+    }
+}
@@ -72,7 +72,7 @@ impl CompiledModule {
     }
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct DeserializerConfig {
     max_binary_format_version: u32,
     max_identifier_size: u64,
@@ -1,11 +1,12 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::compiler::{as_module, as_script, compile_units_with_stdlib};
+use crate::compiler::{as_module, as_script, compile_units, compile_units_with_stdlib};
 use move_binary_format::file_format::{Bytecode, CompiledModule, CompiledScript, SignatureIndex};
 use move_bytecode_verifier::VerifierConfig;
 use move_core_types::{
     account_address::AccountAddress,
+    ident_str,
     identifier::Identifier,
     language_storage::{StructTag, TypeTag},
     vm_status::StatusCode,
@@ -145,3 +146,49 @@ fn script_large_ty() {
 
     assert_eq!(res.major_status(), StatusCode::TOO_MANY_TYPE_NODES);
 }
+
+#[test]
+fn configurable_ty_depth_limit() {
+    let test_str = r#"
+    module 0x42::deep {
+        struct S<phantom T> has drop {}
+
+        public fun pack<T>() {
+            let _s = S<S<S<T>>> {};
+        }
+    }
+    "#;
+
+    let mut units = compile_units(test_str).unwrap();
+    let module = as_module(units.pop().unwrap());
+    let mut blob = vec![];
+    module.serialize(&mut blob).unwrap();
+
+    let mut storage = InMemoryStorage::new();
+    storage.publish_or_overwrite_module(module.self_id(), blob);
+
+    let run = |max_ty_depth| {
+        let move_vm = MoveVM::new_with_config(vec![], VMConfig {
+            max_ty_depth: Some(max_ty_depth),
+            ..Default::default()
+        })
+        .unwrap();
+        let mut session = move_vm.new_session(&storage);
+        let traversal_storage = TraversalStorage::new();
+        session.execute_function_bypass_visibility(
+            &module.self_id(),
+            ident_str!("pack"),
+            vec![TypeTag::U64],
+            Vec::<Vec<u8>>::new(),
+            &mut UnmeteredGasMeter,
+            &mut TraversalContext::new(&traversal_storage),
+        )
+    };
+
+    // Packing `S<S<S<u64>>>` instantiates `S<S<u64>>`, which has a depth of 3.
+    assert!(run(3).is_ok());
+    assert_eq!(
+        run(2).unwrap_err().major_status(),
+        StatusCode::VM_MAX_TYPE_DEPTH_REACHED
+    );
+}
@@ -6,9 +6,12 @@ use move_binary_format::{
     file_format_common::{IDENTIFIER_SIZE_MAX, VERSION_MAX},
 };
 use move_bytecode_verifier::VerifierConfig;
+use move_vm_types::loaded_data::runtime_types::TYPE_DEPTH_MAX;
 use serde::Serialize;
 
 pub const DEFAULT_MAX_VALUE_NEST_DEPTH: u64 = 128;
+pub const DEFAULT_MAX_TY_SIZE: u64 = 128;
+pub const DEFAULT_MAX_TY_DEPTH: u64 = TYPE_DEPTH_MAX as u64;
 
 /// Dynamic config options for the Move VM.
 #[derive(Clone, Serialize)]
@@ -26,6 +29,10 @@ pub struct VMConfig {
     pub type_base_cost: u64,
     pub type_byte_cost: u64,
     pub aggregator_v2_type_tagging: bool,
+    /// Maximum number of nodes of a type instantiation, not including the field types of structs
+    pub max_ty_size: u64,
+    /// Maximum depth of an instantiated type, if it is enforced
+    pub max_ty_depth: Option<u64>,
 }
 
 impl Default for VMConfig {
@@ -41,6 +48,8 @@ impl Default for VMConfig {
             type_base_cost: 0,
             type_byte_cost: 0,
             aggregator_v2_type_tagging: false,
+            max_ty_size: DEFAULT_MAX_TY_SIZE,
+            max_ty_depth: None,
         }
     }
 }
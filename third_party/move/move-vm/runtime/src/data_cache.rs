@@ -7,6 +7,7 @@ use crate::{
     logging::expect_no_verification_errors,
 };
 use bytes::Bytes;
+use lazy_static::lazy_static;
 use move_binary_format::{
     deserializer::DeserializerConfig,
     errors::*,
@@ -28,12 +29,171 @@ use move_vm_types::{
     value_serde::deserialize_and_allow_delayed_values,
     values::{GlobalValue, Value},
 };
+use parking_lot::RwLock;
 use sha3::{Digest, Sha3_256};
 use std::{
-    collections::btree_map::{self, BTreeMap},
-    sync::Arc,
+    collections::{
+        btree_map::{self, BTreeMap},
+        hash_map::DefaultHasher,
+        HashMap,
+    },
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
+// Max number of deserialized modules that are shared across loaders.
+const DESERIALIZED_MODULE_CACHE_SIZE: usize = 10_000;
+// Number of shards of the cache for deserialized modules, so that loaders running in parallel
+// (e.g. Block-STM workers) do not contend on a single lock.
+const DESERIALIZED_MODULE_CACHE_SHARDS: usize = 16;
+
+/// A module deserialized from the bytes with the given hash, using the given config.
+struct DeserializedModule {
+    module: Arc<CompiledModule>,
+    size: usize,
+    hash_value: [u8; 32],
+    deserializer_config: DeserializerConfig,
+    // Value of the cache clock when the module was last used, to evict the least recently used
+    // module of a full shard. Updated under the read lock of the shard.
+    last_used: AtomicU64,
+}
+
+type DeserializedModuleShard = RwLock<HashMap<ModuleId, DeserializedModule>>;
+
+struct DeserializedModuleCache {
+    shards: Vec<DeserializedModuleShard>,
+    // Advanced on every insertion only, so that hits do not write to a shared location.
+    clock: AtomicU64,
+    num_modules: AtomicUsize,
+    // Total size of the bytes of the cached modules.
+    size_in_bytes: AtomicUsize,
+}
+
+impl DeserializedModuleCache {
+    fn new() -> Self {
+        Self {
+            shards: (0..DESERIALIZED_MODULE_CACHE_SHARDS)
+                .map(|_| RwLock::new(HashMap::new()))
+                .collect(),
+            clock: AtomicU64::new(0),
+            num_modules: AtomicUsize::new(0),
+            size_in_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    fn shard(&self, id: &ModuleId) -> &DeserializedModuleShard {
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+
+    fn get(
+        &self,
+        id: &ModuleId,
+        hash_value: &[u8; 32],
+        deserializer_config: &DeserializerConfig,
+    ) -> Option<Arc<CompiledModule>> {
+        let shard = self.shard(id).read();
+        let cached = shard.get(id).filter(|cached| {
+            &cached.hash_value == hash_value && &cached.deserializer_config == deserializer_config
+        })?;
+        let now = self.clock.load(Ordering::Relaxed);
+        if cached.last_used.load(Ordering::Relaxed) != now {
+            cached.last_used.store(now, Ordering::Relaxed);
+        }
+        Some(cached.module.clone())
+    }
+
+    fn insert(&self, id: ModuleId, deserialized_module: DeserializedModule) {
+        let mut shard = self.shard(&id).write();
+        deserialized_module.last_used.store(
+            self.clock.fetch_add(1, Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+
+        // Evict the least recently used module if the shard is full. The scan only happens on a
+        // miss, which already paid for deserializing the module.
+        if shard.len() >= DESERIALIZED_MODULE_CACHE_SIZE / DESERIALIZED_MODULE_CACHE_SHARDS
+            && !shard.contains_key(&id)
+        {
+            let lru_id = shard
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_used.load(Ordering::Relaxed))
+                .map(|(lru_id, _)| lru_id.clone());
+            if let Some(evicted) = lru_id.and_then(|lru_id| shard.remove(&lru_id)) {
+                self.num_modules.fetch_sub(1, Ordering::Relaxed);
+                self.size_in_bytes
+                    .fetch_sub(evicted.size, Ordering::Relaxed);
+            }
+        }
+
+        self.size_in_bytes
+            .fetch_add(deserialized_module.size, Ordering::Relaxed);
+        // The previous version of the module, if any, is replaced.
+        match shard.insert(id, deserialized_module) {
+            Some(replaced) => {
+                self.size_in_bytes
+                    .fetch_sub(replaced.size, Ordering::Relaxed);
+            },
+            None => {
+                self.num_modules.fetch_add(1, Ordering::Relaxed);
+            },
+        }
+    }
+}
+
+// Cache for deserialized modules. Unlike the code cache of a loader, it is shared across loaders
+// and is not flushed when the code cache is invalidated (e.g. after a module is published), so
+// hot modules are not deserialized again in each block. A cached module is only used if the hash
+// of the bytes in storage matches, so that a republished module is deserialized again (and
+// replaces the cached one).
+lazy_static! {
+    static ref DESERIALIZED_MODULES: DeserializedModuleCache = DeserializedModuleCache::new();
+}
+
+static DESERIALIZED_MODULE_CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static DESERIALIZED_MODULE_CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Statistics of the cache for deserialized modules, which is shared across loaders.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeserializedModuleCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub num_modules: usize,
+    /// Approximated by the size of the bytes of the cached modules.
+    pub size_in_bytes: usize,
+}
+
+pub fn deserialized_module_cache_stats() -> DeserializedModuleCacheStats {
+    DeserializedModuleCacheStats {
+        hits: DESERIALIZED_MODULE_CACHE_HITS.load(Ordering::Relaxed),
+        misses: DESERIALIZED_MODULE_CACHE_MISSES.load(Ordering::Relaxed),
+        num_modules: DESERIALIZED_MODULES.num_modules.load(Ordering::Relaxed),
+        size_in_bytes: DESERIALIZED_MODULES.size_in_bytes.load(Ordering::Relaxed),
+    }
+}
+
+fn get_deserialized_module(
+    id: &ModuleId,
+    hash_value: &[u8; 32],
+    deserializer_config: &DeserializerConfig,
+) -> Option<Arc<CompiledModule>> {
+    let module = DESERIALIZED_MODULES.get(id, hash_value, deserializer_config);
+    if module.is_some() {
+        DESERIALIZED_MODULE_CACHE_HITS.fetch_add(1, Ordering::Relaxed);
+    } else {
+        DESERIALIZED_MODULE_CACHE_MISSES.fetch_add(1, Ordering::Relaxed);
+    }
+    module
+}
+
+fn put_deserialized_module(id: ModuleId, deserialized_module: DeserializedModule) {
+    DESERIALIZED_MODULES.insert(id, deserialized_module);
+}
+
 pub struct AccountDataCache {
     // The bool flag in the `data_map` indicates whether the resource contains
     // an aggregator or snapshot.
@@ -331,10 +491,19 @@ impl<'r> TransactionDataCache<'r> {
                 sha3_256.update(&bytes);
                 let hash_value: [u8; 32] = sha3_256.finalize().into();
 
-                // for bytes obtained from the data store, they should always deserialize and verify.
-                // It is an invariant violation if they don't.
-                let module =
-                    CompiledModule::deserialize_with_config(&bytes, &self.deserializer_config)
+                let module = match get_deserialized_module(
+                    entry.key(),
+                    &hash_value,
+                    &self.deserializer_config,
+                ) {
+                    Some(module) => module,
+                    None => {
+                        // for bytes obtained from the data store, they should always deserialize
+                        // and verify. It is an invariant violation if they don't.
+                        let module = CompiledModule::deserialize_with_config(
+                            &bytes,
+                            &self.deserializer_config,
+                        )
                         .map_err(|err| {
                             let msg = format!("Deserialization error: {:?}", err);
                             PartialVMError::new(StatusCode::CODE_DESERIALIZATION_ERROR)
@@ -342,10 +511,19 @@ impl<'r> TransactionDataCache<'r> {
                                 .finish(Location::Module(entry.key().clone()))
                         })
                         .map_err(expect_no_verification_errors)?;
+                        let module = Arc::new(module);
+                        put_deserialized_module(entry.key().clone(), DeserializedModule {
+                            module: module.clone(),
+                            size: bytes.len(),
+                            hash_value,
+                            deserializer_config: self.deserializer_config.clone(),
+                            last_used: AtomicU64::new(0),
+                        });
+                        module
+                    },
+                };
 
-                Ok(entry
-                    .insert((Arc::new(module), bytes.len(), hash_value))
-                    .clone())
+                Ok(entry.insert((module, bytes.len(), hash_value)).clone())
             },
         }
     }
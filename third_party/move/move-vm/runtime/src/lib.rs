@@ -30,4 +30,4 @@ mod debug;
 
 mod access_control;
 
-pub use loader::LoadedFunction;
+pub use loader::{struct_type_cache_stats, LoadedFunction, StructTypeCacheStats};
@@ -52,7 +52,10 @@ mod type_loader;
 
 pub use function::LoadedFunction;
 pub(crate) use function::{Function, FunctionHandle, FunctionInstantiation, Scope};
-pub(crate) use modules::{Module, ModuleCache, ModuleStorage, ModuleStorageAdapter};
+pub use modules::{struct_type_cache_stats, StructTypeCacheStats};
+pub(crate) use modules::{
+    Module, ModuleCache, ModuleStorage, ModuleStorageAdapter, StructTypeCache,
+};
 pub(crate) use script::{Script, ScriptCache};
 use type_loader::intern_type;
 
@@ -163,6 +166,8 @@ pub(crate) struct Loader {
     type_cache: RwLock<TypeCache>,
     natives: NativeFunctions,
     pub(crate) name_cache: StructNameCache,
+    // Struct types of the loaded modules, which survive the invalidation of the loader.
+    struct_type_cache: StructTypeCache,
 
     // The below field supports a hack to workaround well-known issues with the
     // loader cache. This cache is not designed to support module upgrade or deletion.
@@ -204,6 +209,7 @@ impl Clone for Loader {
             type_cache: RwLock::new(self.type_cache.read().clone()),
             natives: self.natives.clone(),
             name_cache: self.name_cache.clone(),
+            struct_type_cache: self.struct_type_cache.clone(),
             invalidated: RwLock::new(*self.invalidated.read()),
             module_cache_hits: RwLock::new(self.module_cache_hits.read().clone()),
             vm_config: self.vm_config.clone(),
@@ -217,6 +223,7 @@ impl Loader {
             scripts: RwLock::new(ScriptCache::new()),
             type_cache: RwLock::new(TypeCache::new()),
             name_cache: StructNameCache::new(),
+            struct_type_cache: StructTypeCache::new(),
             natives,
             invalidated: RwLock::new(false),
             module_cache_hits: RwLock::new(BTreeSet::new()),
@@ -324,7 +331,7 @@ impl Loader {
                 .iter()
                 .map(|ty| self.count_type_nodes(ty))
                 .sum::<u64>()
-                > MAX_TYPE_INSTANTIATION_NODES
+                > self.vm_config.max_ty_size
         {
             return Err(PartialVMError::new(StatusCode::TOO_MANY_TYPE_NODES)
                 .with_message(format!(
@@ -951,11 +958,13 @@ impl Loader {
         id: &ModuleId,
         data_store: &mut TransactionDataCache,
         allow_loading_failure: bool,
-    ) -> VMResult<(Arc<CompiledModule>, usize)> {
+    ) -> VMResult<(Arc<CompiledModule>, usize, [u8; 32])> {
         let (module, size, hash_value) =
             data_store.load_compiled_module_to_cache(id.clone(), allow_loading_failure)?;
 
-        fail::fail_point!("verifier-failpoint-2", |_| { Ok((module.clone(), size)) });
+        fail::fail_point!("verifier-failpoint-2", |_| {
+            Ok((module.clone(), size, hash_value))
+        });
 
         if self.vm_config.paranoid_type_checks && &module.self_id() != id {
             return Err(
@@ -978,7 +987,7 @@ impl Loader {
 
         self.check_natives(&module)
             .map_err(expect_no_verification_errors)?;
-        Ok((module, size))
+        Ok((module, size, hash_value))
     }
 
     // Everything in `load_and_verify_module` and also recursively load and verify all the
@@ -1000,7 +1009,7 @@ impl Loader {
         }
 
         // module self-check
-        let (module, size) =
+        let (module, size, hash_value) =
             self.load_and_verify_module(id, data_store, allow_module_loading_failure)?;
         visited.insert(id.clone());
         friends_discovered.extend(module.immediate_friends());
@@ -1018,8 +1027,15 @@ impl Loader {
         )?;
 
         // if linking goes well, insert the module to the code cache
-        let module_ref =
-            module_store.insert(&self.natives, id.clone(), size, module, &self.name_cache)?;
+        let module_ref = module_store.insert(
+            &self.natives,
+            id.clone(),
+            size,
+            hash_value,
+            module,
+            &self.name_cache,
+            &self.struct_type_cache,
+        )?;
 
         Ok(module_ref)
     }
@@ -1171,12 +1187,12 @@ impl Loader {
     fn subst(&self, ty: &Type, ty_args: &[Type]) -> PartialVMResult<Type> {
         // Before instantiating the type, count the # of nodes of all type arguments plus
         // existing type instantiation.
-        // If that number is larger than MAX_TYPE_INSTANTIATION_NODES, refuse to construct this type.
+        // If that number is larger than the maximum type size, refuse to construct this type.
         // This prevents constructing larger and lager types via struct instantiation.
         match ty {
             Type::MutableReference(_) | Type::Reference(_) | Type::Vector(_) => {
                 if self.vm_config.type_size_limit
-                    && self.count_type_nodes(ty) > MAX_TYPE_INSTANTIATION_NODES
+                    && self.count_type_nodes(ty) > self.vm_config.max_ty_size
                 {
                     return Err(PartialVMError::new(StatusCode::TOO_MANY_TYPE_NODES));
                 }
@@ -1188,7 +1204,7 @@ impl Loader {
                 let mut sum_nodes = 1u64;
                 for ty in ty_args.iter().chain(struct_inst.iter()) {
                     sum_nodes = sum_nodes.saturating_add(self.count_type_nodes(ty));
-                    if sum_nodes > MAX_TYPE_INSTANTIATION_NODES {
+                    if sum_nodes > self.vm_config.max_ty_size {
                         return Err(PartialVMError::new(StatusCode::TOO_MANY_TYPE_NODES));
                     }
                 }
@@ -1205,7 +1221,12 @@ impl Loader {
             | Type::U128
             | Type::U256 => (),
         };
-        ty.subst(ty_args)
+        match self.vm_config.max_ty_depth {
+            // The depth is checked while substituting, so that a too deep type is never built.
+            Some(max_ty_depth) => ty
+                .subst_with_max_depth(ty_args, usize::try_from(max_ty_depth).unwrap_or(usize::MAX)),
+            None => ty.subst(ty_args),
+        }
     }
 
     // Verify the kind (constraints) of an instantiation.
@@ -1364,11 +1385,12 @@ impl<'a> Resolver<'a> {
             instantiation.push(self.subst(ty, ty_args)?);
         }
         // Check if the function instantiation over all generics is larger
-        // than MAX_TYPE_INSTANTIATION_NODES.
+        // than the maximum type size.
+        let max_ty_size = self.loader.vm_config().max_ty_size;
         let mut sum_nodes = 1u64;
         for ty in ty_args.iter().chain(instantiation.iter()) {
             sum_nodes = sum_nodes.saturating_add(self.loader.count_type_nodes(ty));
-            if sum_nodes > MAX_TYPE_INSTANTIATION_NODES {
+            if sum_nodes > max_ty_size {
                 return Err(PartialVMError::new(StatusCode::TOO_MANY_TYPE_NODES));
             }
         }
@@ -1402,16 +1424,17 @@ impl<'a> Resolver<'a> {
 
         // Before instantiating the type, count the # of nodes of all type arguments plus
         // existing type instantiation.
-        // If that number is larger than MAX_TYPE_INSTANTIATION_NODES, refuse to construct this type.
+        // If that number is larger than the maximum type size, refuse to construct this type.
         // This prevents constructing larger and larger types via struct instantiation.
+        let max_ty_size = self.loader.vm_config().max_ty_size;
         let mut sum_nodes = 1u64;
         for ty in ty_args.iter().chain(struct_inst.instantiation.iter()) {
             sum_nodes = sum_nodes.saturating_add(self.loader.count_type_nodes(ty));
-            if sum_nodes > MAX_TYPE_INSTANTIATION_NODES {
+            if sum_nodes > max_ty_size {
                 return Err(
                     PartialVMError::new(StatusCode::TOO_MANY_TYPE_NODES).with_message(format!(
                         "Number of type instantiation nodes exceeded the maximum of {}",
-                        MAX_TYPE_INSTANTIATION_NODES
+                        max_ty_size
                     )),
                 );
             }
@@ -1682,10 +1705,6 @@ pub const VALUE_DEPTH_MAX: u64 = 128;
 /// fields for struct types.
 const MAX_TYPE_TO_LAYOUT_NODES: u64 = 256;
 
-/// Maximal nodes which are all allowed when instantiating a generic type. This does not include
-/// field types of structs.
-const MAX_TYPE_INSTANTIATION_NODES: u64 = 128;
-
 struct PseudoGasContext {
     max_cost: u64,
     cost: u64,
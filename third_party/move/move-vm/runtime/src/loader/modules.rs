@@ -34,7 +34,10 @@ use parking_lot::RwLock;
 use std::{
     collections::{BTreeMap, HashMap},
     fmt::Debug,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
 /// This trait provides an additional api for the Session to decide where the resolved modules should be stored.
@@ -80,6 +83,63 @@ impl ModuleStorage for ModuleCache {
     }
 }
 
+// Struct types defined by a module, in the order of its struct definitions.
+type DefinitionStructTypes = Arc<Vec<Arc<StructType>>>;
+
+/// Cache for the struct types defined by modules, keyed by the module id and the hash of the
+/// module bytes. Struct types only depend on the module defining them and on the struct name
+/// indices of the loader, which are never flushed. So unlike the code cache, this cache is kept
+/// when the loader is invalidated, and an entry is only replaced when the module is republished.
+pub(crate) struct StructTypeCache(RwLock<HashMap<ModuleId, ([u8; 32], DefinitionStructTypes)>>);
+
+static STRUCT_TYPE_CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static STRUCT_TYPE_CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Hits and misses of the struct type caches of all loaders.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StructTypeCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+pub fn struct_type_cache_stats() -> StructTypeCacheStats {
+    StructTypeCacheStats {
+        hits: STRUCT_TYPE_CACHE_HITS.load(Ordering::Relaxed),
+        misses: STRUCT_TYPE_CACHE_MISSES.load(Ordering::Relaxed),
+    }
+}
+
+impl StructTypeCache {
+    pub(crate) fn new() -> Self {
+        StructTypeCache(RwLock::new(HashMap::new()))
+    }
+
+    fn get(&self, id: &ModuleId, hash_value: &[u8; 32]) -> Option<DefinitionStructTypes> {
+        let struct_types = self
+            .0
+            .read()
+            .get(id)
+            .filter(|(cached_hash_value, _)| cached_hash_value == hash_value)
+            .map(|(_, struct_types)| struct_types.clone());
+        if struct_types.is_some() {
+            STRUCT_TYPE_CACHE_HITS.fetch_add(1, Ordering::Relaxed);
+        } else {
+            STRUCT_TYPE_CACHE_MISSES.fetch_add(1, Ordering::Relaxed);
+        }
+        struct_types
+    }
+
+    fn insert(&self, id: ModuleId, hash_value: [u8; 32], struct_types: DefinitionStructTypes) {
+        self.0.write().insert(id, (hash_value, struct_types));
+    }
+}
+
+impl Clone for StructTypeCache {
+    fn clone(&self) -> Self {
+        StructTypeCache(RwLock::new(self.0.read().clone()))
+    }
+}
+
 pub(crate) struct ModuleStorageAdapter {
     modules: Arc<dyn ModuleStorage>,
 }
@@ -108,14 +168,24 @@ impl ModuleStorageAdapter {
         natives: &NativeFunctions,
         id: ModuleId,
         module_size: usize,
+        hash_value: [u8; 32],
         module: Arc<CompiledModule>,
         name_cache: &StructNameCache,
+        struct_type_cache: &StructTypeCache,
     ) -> VMResult<Arc<Module>> {
         if let Some(cached) = self.module_at(&id) {
             return Ok(cached);
         }
 
-        match Module::new(natives, module_size, module, self, name_cache) {
+        match Module::new(
+            natives,
+            module_size,
+            &hash_value,
+            module,
+            self,
+            name_cache,
+            struct_type_cache,
+        ) {
             Ok(module) => Ok(self.modules.store_module(&id, module)),
             Err((err, _)) => Err(err.finish(Location::Undefined)),
         }
@@ -272,9 +342,11 @@ impl Module {
     pub(crate) fn new(
         natives: &NativeFunctions,
         size: usize,
+        hash_value: &[u8; 32],
         module: Arc<CompiledModule>,
         cache: &ModuleStorageAdapter,
         name_cache: &StructNameCache,
+        struct_type_cache: &StructTypeCache,
     ) -> Result<Self, (PartialVMError, Arc<CompiledModule>)> {
         let id = module.self_id();
 
@@ -325,9 +397,34 @@ impl Module {
                 )
             }
 
-            for (idx, struct_def) in module.struct_defs().iter().enumerate() {
-                let definition_struct_type =
-                    Arc::new(Self::make_struct_type(&module, struct_def, &struct_idxs)?);
+            let definition_struct_types = match struct_type_cache.get(&id, hash_value) {
+                Some(struct_types) => struct_types,
+                None => {
+                    let struct_types = Arc::new(
+                        module
+                            .struct_defs()
+                            .iter()
+                            .map(|struct_def| {
+                                Ok(Arc::new(Self::make_struct_type(
+                                    &module,
+                                    struct_def,
+                                    &struct_idxs,
+                                )?))
+                            })
+                            .collect::<PartialVMResult<Vec<_>>>()?,
+                    );
+                    struct_type_cache.insert(id.clone(), *hash_value, struct_types.clone());
+                    struct_types
+                },
+            };
+
+            for (idx, (struct_def, definition_struct_type)) in module
+                .struct_defs()
+                .iter()
+                .zip(definition_struct_types.iter())
+                .enumerate()
+            {
+                let definition_struct_type = definition_struct_type.clone();
                 structs.push(StructDef {
                     field_count: definition_struct_type.field_tys.len() as u16,
                     definition_struct_type,
@@ -286,15 +286,15 @@ impl Type {
     #[allow(deprecated)]
     const LEGACY_BASE_MEMORY_SIZE: AbstractMemorySize = AbstractMemorySize::new(1);
 
-    fn clone_impl(&self, depth: usize) -> PartialVMResult<Type> {
-        self.apply_subst(|idx, _| Ok(Type::TyParam(idx)), depth)
+    fn clone_impl(&self, depth: usize, max_depth: usize) -> PartialVMResult<Type> {
+        self.apply_subst(|idx, _| Ok(Type::TyParam(idx)), depth, max_depth)
     }
 
-    fn apply_subst<F>(&self, subst: F, depth: usize) -> PartialVMResult<Type>
+    fn apply_subst<F>(&self, subst: F, depth: usize, max_depth: usize) -> PartialVMResult<Type>
     where
         F: Fn(u16, usize) -> PartialVMResult<Type> + Copy,
     {
-        if depth > TYPE_DEPTH_MAX {
+        if depth > max_depth {
             return Err(PartialVMError::new(StatusCode::VM_MAX_TYPE_DEPTH_REACHED));
         }
         let res = match self {
@@ -308,10 +308,16 @@ impl Type {
             Type::U256 => Type::U256,
             Type::Address => Type::Address,
             Type::Signer => Type::Signer,
-            Type::Vector(ty) => Type::Vector(TriompheArc::new(ty.apply_subst(subst, depth + 1)?)),
-            Type::Reference(ty) => Type::Reference(Box::new(ty.apply_subst(subst, depth + 1)?)),
+            Type::Vector(ty) => Type::Vector(TriompheArc::new(ty.apply_subst(
+                subst,
+                depth + 1,
+                max_depth,
+            )?)),
+            Type::Reference(ty) => {
+                Type::Reference(Box::new(ty.apply_subst(subst, depth + 1, max_depth)?))
+            },
             Type::MutableReference(ty) => {
-                Type::MutableReference(Box::new(ty.apply_subst(subst, depth + 1)?))
+                Type::MutableReference(Box::new(ty.apply_subst(subst, depth + 1, max_depth)?))
             },
             Type::Struct { idx, ability } => Type::Struct {
                 idx: *idx,
@@ -324,7 +330,7 @@ impl Type {
             } => {
                 let mut inst = vec![];
                 for ty in instantiation.iter() {
-                    inst.push(ty.apply_subst(subst, depth + 1)?)
+                    inst.push(ty.apply_subst(subst, depth + 1, max_depth)?)
                 }
                 Type::StructInstantiation {
                     idx: *idx,
@@ -337,9 +343,20 @@ impl Type {
     }
 
     pub fn subst(&self, ty_args: &[Type]) -> PartialVMResult<Type> {
+        self.subst_with_max_depth(ty_args, TYPE_DEPTH_MAX)
+    }
+
+    /// Substitutes the type arguments like `subst`, and fails if the depth of the result exceeds
+    /// `max_depth` (e.g. 1 for `u64` and 3 for `Foo<vector<u64>>`), or `TYPE_DEPTH_MAX`.
+    pub fn subst_with_max_depth(
+        &self,
+        ty_args: &[Type],
+        max_depth: usize,
+    ) -> PartialVMResult<Type> {
+        let max_depth = max_depth.min(TYPE_DEPTH_MAX);
         self.apply_subst(
             |idx, depth| match ty_args.get(idx as usize) {
-                Some(ty) => ty.clone_impl(depth),
+                Some(ty) => ty.clone_impl(depth, max_depth),
                 None => Err(
                     PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
                         .with_message(format!(
@@ -350,6 +367,7 @@ impl Type {
                 ),
             },
             1,
+            max_depth,
         )
     }
 
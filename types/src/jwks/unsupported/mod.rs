@@ -11,7 +11,9 @@ use std::fmt::{Debug, Formatter};
 /// See its doc in Move for more details.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UnsupportedJWK {
+    #[serde(with = "serde_bytes")]
     pub id: Vec<u8>,
+    #[serde(with = "serde_bytes")]
     pub payload: Vec<u8>,
 }
 
@@ -0,0 +1,244 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    jwks::{jwk::JWK, rsa::RSA_JWK, PatchedJWKs},
+    keyless::{base64url_decode_as_str, Claims, JWTHeader},
+};
+use thiserror::Error;
+
+/// The claim with the time before which a JWT must not be accepted (<https://datatracker.ietf.org/doc/html/rfc7519#section-4.1.5>)
+const NOT_BEFORE_CLAIM: &str = "nbf";
+
+/// Errors returned when validating a JWT against the JWKs on chain
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+pub enum JwtValidationError {
+    /// The JWT (or one of its parts) can't be parsed
+    #[error("Malformed JWT: {0}")]
+    MalformedJwt(String),
+    /// There are no JWKs on chain for the issuer of the JWT
+    #[error("No JWKs found on chain for issuer {0}")]
+    UnknownIssuer(String),
+    /// The issuer has JWKs on chain, but none with the KID in the JWT header. Typically, the
+    /// provider rotated the key out (or the JWT is signed with a key not observed on chain yet).
+    #[error("JWK for {iss} with KID {kid} was not found, it may have been rotated")]
+    RotatedKey { iss: String, kid: String },
+    /// The JWK with the KID in the JWT header is of an unsupported type
+    #[error("JWK for {iss} with KID {kid} is not supported")]
+    UnsupportedJwk { iss: String, kid: String },
+    /// The algorithm in the JWT header is not the one of the JWK
+    #[error("JWK alg ({jwk_alg}) does not match JWT header's alg ({jwt_alg})")]
+    AlgMismatch { jwk_alg: String, jwt_alg: String },
+    /// The JWT signature doesn't verify under the JWK
+    #[error("JWT signature verification failed: {0}")]
+    BadSignature(String),
+    /// The JWT expired (i.e., its `exp` claim is not after the current time)
+    #[error("JWT expired at {exp}, the current time is {now_secs}")]
+    Expired { exp: u64, now_secs: u64 },
+    /// The JWT is not valid yet (i.e., its `nbf` claim is after the current time)
+    #[error("JWT is not valid before {nbf}, the current time is {now_secs}")]
+    NotYetValid { nbf: u64, now_secs: u64 },
+}
+
+/// Returns the RSA JWK on chain that a JWT from the given issuer and with the given header must
+/// be signed with, after checking that the algorithm in the header is the one of the JWK.
+pub fn get_jwk_for_jwt_header(
+    jwks: &PatchedJWKs,
+    iss: &str,
+    jwt_header: &JWTHeader,
+) -> Result<RSA_JWK, JwtValidationError> {
+    let provider_jwks = jwks
+        .get_provider_jwks(iss)
+        .ok_or_else(|| JwtValidationError::UnknownIssuer(iss.to_string()))?;
+    let jwk_move_struct =
+        provider_jwks
+            .get_jwk(&jwt_header.kid)
+            .map_err(|_| JwtValidationError::RotatedKey {
+                iss: iss.to_string(),
+                kid: jwt_header.kid.clone(),
+            })?;
+
+    let jwk = JWK::try_from(jwk_move_struct).map_err(|_| JwtValidationError::UnsupportedJwk {
+        iss: iss.to_string(),
+        kid: jwt_header.kid.clone(),
+    })?;
+    match jwk {
+        JWK::RSA(rsa_jwk) => {
+            if rsa_jwk.alg != jwt_header.alg {
+                return Err(JwtValidationError::AlgMismatch {
+                    jwk_alg: rsa_jwk.alg,
+                    jwt_alg: jwt_header.alg.clone(),
+                });
+            }
+            Ok(rsa_jwk)
+        },
+        JWK::Unsupported(_) => Err(JwtValidationError::UnsupportedJwk {
+            iss: iss.to_string(),
+            kid: jwt_header.kid.clone(),
+        }),
+    }
+}
+
+/// Validates a (base64url-encoded) JWT against the JWKs on chain for its issuer, by checking that:
+///  1. the issuer has JWKs on chain, one of which has the KID in the JWT header
+///  2. the algorithm in the JWT header is the one of the JWK
+///  3. the JWT signature verifies under the JWK
+///  4. the JWT is valid at `now_secs`, according to its `exp` and (optional) `nbf` claims
+///
+/// Returns the claims in the JWT if it is valid.
+///
+/// Note: keyless signatures don't check the `exp` of the JWT (but the expiration date committed in
+/// the nonce instead), so the VM uses `get_jwk_for_jwt_header` directly.
+pub fn validate_jwt(
+    jwks: &PatchedJWKs,
+    jwt: &str,
+    now_secs: u64,
+) -> Result<Claims, JwtValidationError> {
+    let (jwt_header, claims) = parse_jwt_unverified(jwt)?;
+    let rsa_jwk = get_jwk_for_jwt_header(jwks, &claims.oidc_claims.iss, &jwt_header)?;
+    rsa_jwk
+        .verify_signature_without_exp_check(jwt)
+        .map_err(|e| JwtValidationError::BadSignature(e.to_string()))?;
+
+    let exp = claims.oidc_claims.exp;
+    if now_secs >= exp {
+        return Err(JwtValidationError::Expired { exp, now_secs });
+    }
+    if let Some(nbf) = claims.additional_claims.get(NOT_BEFORE_CLAIM) {
+        let nbf = nbf.as_u64().ok_or_else(|| {
+            JwtValidationError::MalformedJwt("'nbf' claim is not a number".to_string())
+        })?;
+        if now_secs < nbf {
+            return Err(JwtValidationError::NotYetValid { nbf, now_secs });
+        }
+    }
+
+    Ok(claims)
+}
+
+/// Parses the header and the claims of the JWT, without verifying its signature
+fn parse_jwt_unverified(jwt: &str) -> Result<(JWTHeader, Claims), JwtValidationError> {
+    let malformed = |e: &dyn std::fmt::Display| JwtValidationError::MalformedJwt(e.to_string());
+
+    let parts: Vec<&str> = jwt.split('.').collect();
+    let [header_b64, payload_b64, _signature_b64] = parts.as_slice() else {
+        return Err(JwtValidationError::MalformedJwt(format!(
+            "expected 3 parts, found {}",
+            parts.len()
+        )));
+    };
+    let jwt_header: JWTHeader =
+        serde_json::from_str(&base64url_decode_as_str(header_b64).map_err(|e| malformed(&e))?)
+            .map_err(|e| malformed(&e))?;
+    let claims: Claims =
+        serde_json::from_str(&base64url_decode_as_str(payload_b64).map_err(|e| malformed(&e))?)
+            .map_err(|e| malformed(&e))?;
+    Ok((jwt_header, claims))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        jwks::{
+            insecure_test_rsa_jwk, issuer_from_str, jwk::JWKMoveStruct, secure_test_rsa_jwk,
+            unsupported::UnsupportedJWK, AllProvidersJWKs, ProviderJWKs,
+        },
+        keyless::{
+            circuit_testcases::{SAMPLE_JWT_PARSED, SAMPLE_TEST_ISS_VALUE},
+            test_utils::get_sample_jwt_token,
+        },
+    };
+
+    /// Returns the on-chain JWKs with the given JWKs for the issuer of the sample JWT
+    fn patched_jwks(jwks: Vec<JWKMoveStruct>) -> PatchedJWKs {
+        let mut provider_jwks = ProviderJWKs::new(issuer_from_str(SAMPLE_TEST_ISS_VALUE));
+        provider_jwks.jwks = jwks;
+        PatchedJWKs {
+            jwks: AllProvidersJWKs {
+                entries: vec![provider_jwks],
+            },
+        }
+    }
+
+    #[test]
+    fn test_validate_jwt() {
+        let jwks = patched_jwks(vec![insecure_test_rsa_jwk().into()]);
+        let jwt = get_sample_jwt_token();
+        let iat = SAMPLE_JWT_PARSED.oidc_claims.iat;
+        let exp = SAMPLE_JWT_PARSED.oidc_claims.exp;
+
+        let claims = validate_jwt(&jwks, &jwt, iat).unwrap();
+        assert_eq!(claims.oidc_claims.iss, SAMPLE_TEST_ISS_VALUE);
+        assert_eq!(
+            validate_jwt(&jwks, &jwt, exp).unwrap_err(),
+            JwtValidationError::Expired { exp, now_secs: exp }
+        );
+    }
+
+    #[test]
+    fn test_validate_jwt_errors() {
+        let jwt = get_sample_jwt_token();
+        let now_secs = SAMPLE_JWT_PARSED.oidc_claims.iat;
+
+        // No JWKs for the issuer
+        let jwks = PatchedJWKs {
+            jwks: AllProvidersJWKs::default(),
+        };
+        assert_eq!(
+            validate_jwt(&jwks, &jwt, now_secs).unwrap_err(),
+            JwtValidationError::UnknownIssuer(SAMPLE_TEST_ISS_VALUE.to_string())
+        );
+
+        // The key with the KID of the JWT was rotated out
+        let rotated_jwk = RSA_JWK {
+            kid: "rotated-rsa".to_string(),
+            ..insecure_test_rsa_jwk()
+        };
+        let jwks = patched_jwks(vec![rotated_jwk.into()]);
+        assert!(matches!(
+            validate_jwt(&jwks, &jwt, now_secs).unwrap_err(),
+            JwtValidationError::RotatedKey { .. }
+        ));
+
+        // The key with the KID of the JWT is not supported
+        let unsupported_jwk = UnsupportedJWK {
+            id: insecure_test_rsa_jwk().kid.into_bytes(),
+            payload: vec![],
+        };
+        let jwks = patched_jwks(vec![JWK::Unsupported(unsupported_jwk).into()]);
+        assert!(matches!(
+            validate_jwt(&jwks, &jwt, now_secs).unwrap_err(),
+            JwtValidationError::UnsupportedJwk { .. }
+        ));
+
+        // The key with the KID of the JWT has a different algorithm
+        let rs384_jwk = RSA_JWK {
+            alg: "RS384".to_string(),
+            ..insecure_test_rsa_jwk()
+        };
+        let jwks = patched_jwks(vec![rs384_jwk.into()]);
+        assert!(matches!(
+            validate_jwt(&jwks, &jwt, now_secs).unwrap_err(),
+            JwtValidationError::AlgMismatch { .. }
+        ));
+
+        // The key with the KID of the JWT didn't sign it
+        let other_jwk = RSA_JWK {
+            kid: insecure_test_rsa_jwk().kid,
+            ..secure_test_rsa_jwk()
+        };
+        let jwks = patched_jwks(vec![other_jwk.into()]);
+        assert!(matches!(
+            validate_jwt(&jwks, &jwt, now_secs).unwrap_err(),
+            JwtValidationError::BadSignature(_)
+        ));
+
+        // The JWT can't be parsed
+        let jwks = patched_jwks(vec![insecure_test_rsa_jwk().into()]);
+        assert!(matches!(
+            validate_jwt(&jwks, "not.a-jwt", now_secs).unwrap_err(),
+            JwtValidationError::MalformedJwt(_)
+        ));
+    }
+}
@@ -30,6 +30,7 @@ mod circuit_testcases;
 mod configuration;
 mod groth16_sig;
 mod groth16_vk;
+mod jwt_validation;
 mod openid_sig;
 pub mod test_utils;
 mod zkp_sig;
@@ -42,6 +43,7 @@ pub use bn254_circom::{
 pub use configuration::Configuration;
 pub use groth16_sig::{Groth16Proof, Groth16ProofAndStatement, ZeroKnowledgeSig};
 pub use groth16_vk::Groth16VerificationKey;
+pub use jwt_validation::{get_jwk_for_jwt_header, validate_jwt, JwtValidationError};
 pub use openid_sig::{Claims, OpenIdSig};
 pub use zkp_sig::ZKP;
 
@@ -353,7 +355,6 @@ pub(crate) fn base64url_encode_bytes(data: &[u8]) -> String {
     base64::encode_config(data, URL_SAFE_NO_PAD)
 }
 
-#[allow(unused)]
 fn base64url_decode_as_str(b64: &str) -> anyhow::Result<String> {
     let decoded_bytes = base64::decode_config(b64, URL_SAFE_NO_PAD)?;
     // Convert the decoded bytes to a UTF-8 string
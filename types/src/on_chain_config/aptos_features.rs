@@ -84,6 +84,7 @@ pub enum FeatureFlag {
     AGGREGATOR_V2_IS_AT_LEAST_API = 66,
     CONCURRENT_FUNGIBLE_BALANCE = 67,
     DEFAULT_TO_CONCURRENT_FUNGIBLE_BALANCE = 68,
+    LIMIT_VM_TYPE_SIZE = 69,
 }
 
 impl FeatureFlag {
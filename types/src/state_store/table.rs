@@ -3,7 +3,7 @@
 
 use move_core_types::{
     account_address::{AccountAddress, AccountAddressParseError},
-    language_storage::TypeTag,
+    language_storage::{StructTag, TypeTag},
 };
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -45,3 +45,12 @@ pub struct TableInfo {
     pub key_type: TypeTag,
     pub value_type: TypeTag,
 }
+
+/// The account and resource through which a table handle was first reached while indexing.
+/// Tables nested inside other tables inherit the owner of the outermost table.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+pub struct TableOwner {
+    pub address: AccountAddress,
+    pub struct_tag: StructTag,
+}
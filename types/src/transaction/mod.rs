@@ -62,7 +62,7 @@ pub use block_output::BlockOutput;
 pub use change_set::ChangeSet;
 pub use module::{Module, ModuleBundle};
 pub use move_core_types::transaction_argument::TransactionArgument;
-use move_core_types::vm_status::AbortLocation;
+use move_core_types::{language_storage::ModuleId, vm_status::AbortLocation};
 use move_vm_types::delayed_values::delayed_field_id::{
     ExtractUniqueIndex, ExtractWidth, TryFromMoveValue, TryIntoMoveValue,
 };
@@ -922,6 +922,65 @@ impl ExecutionStatus {
         matches!(self, ExecutionStatus::Success)
     }
 
+    /// Returns the abort code of a Move abort
+    pub fn abort_code(&self) -> Option<u64> {
+        match self {
+            ExecutionStatus::MoveAbort { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Returns the module that raised a Move abort (None for aborts in scripts)
+    pub fn abort_module(&self) -> Option<&ModuleId> {
+        match self {
+            ExecutionStatus::MoveAbort {
+                location: AbortLocation::Module(module_id),
+                ..
+            } => Some(module_id),
+            _ => None,
+        }
+    }
+
+    /// Returns the reason name and description of a Move abort, if the aborting
+    /// module has an error map (and the detail wasn't removed from the status)
+    pub fn abort_info(&self) -> Option<&AbortInfo> {
+        match self {
+            ExecutionStatus::MoveAbort { info, .. } => info.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Returns a human-readable message for a Move abort, e.g.,
+    /// `Move abort in 0x1::coin: EINSUFFICIENT_BALANCE(0x10006): Not enough coins`
+    pub fn abort_message(&self) -> Option<String> {
+        let ExecutionStatus::MoveAbort {
+            location,
+            code,
+            info,
+        } = self
+        else {
+            return None;
+        };
+        let message = match (location, info) {
+            (AbortLocation::Module(module_id), Some(info)) => format!(
+                "Move abort in {}::{}: {}({:#x}): {}",
+                module_id.address().to_hex_literal(),
+                module_id.name(),
+                info.reason_name,
+                code,
+                info.description
+            ),
+            (AbortLocation::Module(module_id), None) => format!(
+                "Move abort in {}::{}: {:#x}",
+                module_id.address().to_hex_literal(),
+                module_id.name(),
+                code
+            ),
+            (AbortLocation::Script, _) => format!("Move abort: code {:#x}", code),
+        };
+        Some(message)
+    }
+
     // Used by simulation API for showing detail error message. Should not be used by production code.
     pub fn convert_vm_status_for_simulation(vm_status: VMStatus) -> Self {
         let mut show_error_flags = Features::default();
@@ -997,6 +1056,14 @@ impl TransactionStatus {
         }
     }
 
+    /// Returns the abort info of a kept Move abort (see `ExecutionStatus::abort_info`)
+    pub fn abort_info(&self) -> Option<&AbortInfo> {
+        match self {
+            TransactionStatus::Keep(status) => status.abort_info(),
+            _ => None,
+        }
+    }
+
     pub fn from_vm_status(
         vm_status: VMStatus,
         charge_invariant_violation: bool,
@@ -6,8 +6,9 @@ use crate::{
     account_address::AccountAddress,
     chain_id::ChainId,
     transaction::{
-        AccountTransactionsWithProof, RawTransaction, Script, SignedTransaction, Transaction,
-        TransactionInfo, TransactionListWithProof, TransactionPayload, TransactionWithProof,
+        AbortInfo, AccountTransactionsWithProof, ExecutionStatus, RawTransaction, Script,
+        SignedTransaction, Transaction, TransactionInfo, TransactionListWithProof,
+        TransactionPayload, TransactionStatus, TransactionWithProof,
     },
 };
 use aptos_crypto::{
@@ -15,9 +16,56 @@ use aptos_crypto::{
     PrivateKey, Uniform,
 };
 use bcs::test_helpers::assert_canonical_encode_decode;
+use move_core_types::{
+    identifier::Identifier, language_storage::ModuleId, vm_status::AbortLocation,
+};
 use proptest::prelude::*;
 use std::convert::TryFrom;
 
+#[test]
+fn test_move_abort_info() {
+    let module_id = ModuleId::new(AccountAddress::ONE, Identifier::new("coin").unwrap());
+    let abort_info = AbortInfo {
+        reason_name: "EINSUFFICIENT_BALANCE".to_string(),
+        description: "Not enough coins".to_string(),
+    };
+    let status = ExecutionStatus::MoveAbort {
+        location: AbortLocation::Module(module_id.clone()),
+        code: 0x10006,
+        info: Some(abort_info.clone()),
+    };
+    assert_eq!(status.abort_code(), Some(0x10006));
+    assert_eq!(status.abort_module(), Some(&module_id));
+    assert_eq!(status.abort_info(), Some(&abort_info));
+    assert_eq!(
+        status.abort_message().unwrap(),
+        "Move abort in 0x1::coin: EINSUFFICIENT_BALANCE(0x10006): Not enough coins"
+    );
+    assert_eq!(
+        TransactionStatus::Keep(status.clone()).abort_info(),
+        Some(&abort_info)
+    );
+
+    // Verify the message falls back to the code if the detail was removed
+    let status = status.remove_error_detail();
+    assert_eq!(status.abort_info(), None);
+    assert_eq!(
+        status.abort_message().unwrap(),
+        "Move abort in 0x1::coin: 0x10006"
+    );
+
+    // Verify script aborts have no module, and other statuses have no abort details
+    let status = ExecutionStatus::MoveAbort {
+        location: AbortLocation::Script,
+        code: 1,
+        info: None,
+    };
+    assert_eq!(status.abort_module(), None);
+    assert_eq!(status.abort_message().unwrap(), "Move abort: code 0x1");
+    assert_eq!(ExecutionStatus::Success.abort_code(), None);
+    assert_eq!(ExecutionStatus::OutOfGas.abort_message(), None);
+}
+
 #[test]
 fn test_invalid_signature() {
     let txn: SignedTransaction = SignedTransaction::new(
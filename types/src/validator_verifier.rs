@@ -272,6 +272,50 @@ impl ValidatorVerifier {
         message: &T,
         multi_signature: &AggregateSignature,
     ) -> std::result::Result<(), VerifyError> {
+        let Some((multi_sig, aggregated_key)) =
+            self.check_multi_signature_and_aggregate_keys(multi_signature)?
+        else {
+            return Ok(());
+        };
+
+        multi_sig
+            .verify(message, &aggregated_key)
+            .map_err(|_| VerifyError::InvalidMultiSignature)?;
+        Ok(())
+    }
+
+    /// Verifies a batch of multi-signatures, each on its own message. This is faster than calling
+    /// `verify_multi_signatures` for each of them, but if the batch fails to verify, it does not
+    /// tell which of the multi-signatures are invalid.
+    pub fn batch_verify_multi_signatures<T: CryptoHash + Serialize>(
+        &self,
+        messages_and_multi_signatures: &[(&T, &AggregateSignature)],
+    ) -> std::result::Result<(), VerifyError> {
+        let mut messages = vec![];
+        let mut multi_sigs = vec![];
+        let mut aggregated_keys = vec![];
+        for (message, multi_signature) in messages_and_multi_signatures {
+            if let Some((multi_sig, aggregated_key)) =
+                self.check_multi_signature_and_aggregate_keys(multi_signature)?
+            {
+                messages.push(*message);
+                multi_sigs.push(multi_sig);
+                aggregated_keys.push(aggregated_key);
+            }
+        }
+
+        let aggregated_keys: Vec<&PublicKey> = aggregated_keys.iter().collect();
+        bls12381::Signature::verify_batch(&messages, &aggregated_keys, &multi_sigs)
+            .map_err(|_| VerifyError::InvalidMultiSignature)
+    }
+
+    /// Checks the signers and their voting power for the given multi-signature, and returns the
+    /// (non-empty) multi-signature along with the aggregated public key of its signers. Returns
+    /// `None` if the signature doesn't need to be verified (which only happens in tests).
+    fn check_multi_signature_and_aggregate_keys<'a>(
+        &self,
+        multi_signature: &'a AggregateSignature,
+    ) -> std::result::Result<Option<(&'a bls12381::Signature, PublicKey)>, VerifyError> {
         // Verify the number of signature is not greater than expected.
         Self::check_num_of_voters(self.len() as u16, multi_signature.get_signers_bitvec())?;
         let mut pub_keys = vec![];
@@ -292,7 +336,7 @@ impl ValidatorVerifier {
                 // This should happen only in case of tests.
                 // TODO(skedia): Clean up the test behaviors to not rely on empty signature
                 // verification
-                return Ok(());
+                return Ok(None);
             }
         }
         // Verify empty multi signature
@@ -300,14 +344,10 @@ impl ValidatorVerifier {
             .sig()
             .as_ref()
             .ok_or(VerifyError::EmptySignature)?;
-        // Verify the optimistically aggregated signature.
+        // Aggregate the public keys to verify the optimistically aggregated signature.
         let aggregated_key =
             PublicKey::aggregate(pub_keys).map_err(|_| VerifyError::FailedToAggregatePubKey)?;
-
-        multi_sig
-            .verify(message, &aggregated_key)
-            .map_err(|_| VerifyError::InvalidMultiSignature)?;
-        Ok(())
+        Ok(Some((multi_sig, aggregated_key)))
     }
 
     pub fn verify_aggregate_signatures<T: CryptoHash + Serialize>(
@@ -712,6 +752,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_batch_verify_multi_signatures() {
+        const NUM_SIGNERS: u8 = 4;
+        let validator_signers: Vec<ValidatorSigner> = (0..NUM_SIGNERS)
+            .map(|i| ValidatorSigner::random([i; 32]))
+            .collect();
+        let validator_verifier = generate_validator_verifier(&validator_signers);
+
+        // Create multi-signatures on different messages
+        let messages: Vec<TestAptosCrypto> = (0..3)
+            .map(|i| TestAptosCrypto(format!("Hello, World {}", i)))
+            .collect();
+        let multi_signatures: Vec<AggregateSignature> = messages
+            .iter()
+            .map(|message| {
+                let mut partial_signature = PartialSignatures::empty();
+                for validator in validator_signers.iter() {
+                    partial_signature
+                        .add_signature(validator.author(), validator.sign(message).unwrap());
+                }
+                validator_verifier
+                    .aggregate_signatures(&partial_signature)
+                    .unwrap()
+            })
+            .collect();
+
+        // Verify the batch of multi-signatures
+        let mut batch: Vec<_> = messages.iter().zip(multi_signatures.iter()).collect();
+        assert_eq!(
+            validator_verifier.batch_verify_multi_signatures(&batch),
+            Ok(())
+        );
+
+        // Swap two of the multi-signatures and verify the batch fails
+        batch[0].1 = &multi_signatures[1];
+        batch[1].1 = &multi_signatures[0];
+        assert_eq!(
+            validator_verifier.batch_verify_multi_signatures(&batch),
+            Err(VerifyError::InvalidMultiSignature)
+        );
+
+        // Verify an empty multi-signature in the batch is rejected
+        let empty_signature =
+            AggregateSignature::new(BitVec::from(vec![true; NUM_SIGNERS as usize]), None);
+        batch[0].1 = &empty_signature;
+        assert_eq!(
+            validator_verifier.batch_verify_multi_signatures(&batch),
+            Err(VerifyError::EmptySignature)
+        );
+    }
+
     #[test]
     fn test_insufficient_voting_power() {
         let validator_signer = ValidatorSigner::random(TEST_SEED);
@@ -7,7 +7,7 @@ use crate::on_chain_config::{
 };
 use move_binary_format::deserializer::DeserializerConfig;
 use move_bytecode_verifier::VerifierConfig;
-use move_vm_runtime::config::VMConfig;
+use move_vm_runtime::config::{VMConfig, DEFAULT_MAX_TY_SIZE};
 
 pub fn aptos_prod_deserializer_config(features: &Features) -> DeserializerConfig {
     DeserializerConfig::new(
@@ -43,9 +43,14 @@ pub fn aptos_prod_verifier_config(features: &Features) -> VerifierConfig {
     }
 }
 
+/// Returns the production VM config. The limits on the size and depth of types come from the
+/// gas schedule once the `LIMIT_VM_TYPE_SIZE` feature is enabled. Until then, the default size
+/// limit applies and the depth of types is not checked.
 pub fn aptos_prod_vm_config(
     features: &Features,
     timed_features: &TimedFeatures,
+    max_ty_size: u64,
+    max_ty_depth: u64,
     aggregator_v2_type_tagging: bool,
     paranoid_type_checks: bool,
 ) -> VMConfig {
@@ -62,6 +67,12 @@ pub fn aptos_prod_vm_config(
         type_byte_cost = 1;
     }
 
+    let (max_ty_size, max_ty_depth) = if features.is_enabled(FeatureFlag::LIMIT_VM_TYPE_SIZE) {
+        (max_ty_size, Some(max_ty_depth))
+    } else {
+        (DEFAULT_MAX_TY_SIZE, None)
+    };
+
     let deserializer_config = aptos_prod_deserializer_config(features);
     let verifier_config = aptos_prod_verifier_config(features);
 
@@ -76,6 +87,8 @@ pub fn aptos_prod_vm_config(
         type_base_cost,
         type_byte_cost,
         aggregator_v2_type_tagging,
+        max_ty_size,
+        max_ty_depth,
     }
 }
 